@@ -1,14 +1,13 @@
-use github_oauth::{
-    GitHubProvider, Login, OAuthProvider, OAuthResponse, Result, UserInfo as UserInfoResponse,
-    UserInfoBuilder,
-};
+use github_oauth::{GitHubProvider, OAuthProvider, OAuthResponse, Result};
+use oauth_core::{Login, UserInfoBuilder};
 use std::time::Duration;
 
 /// Example of generic OAuth function that works with any OAuth provider
 /// This same function could work with Google OAuth by just changing the type parameters
-async fn authenticate_with_provider<P>() -> Result<(OAuthResponse, UserInfoResponse)>
+async fn authenticate_with_provider<P>() -> Result<(OAuthResponse, P::UserInfo)>
 where
     P: OAuthProvider,
+    P::UserInfo: std::fmt::Debug,
 {
     println!("Authenticating with {}", P::provider_name());
 
@@ -17,7 +16,7 @@ where
     println!("Using default scopes: {:?}", default_scopes);
 
     // Create login builder and configure it
-    let oauth_response = Login::from_env()
+    let oauth_response = Login::<P>::from_env()
         .scopes(default_scopes.iter().map(|s| s.to_string()))?  // PKCE auto-enabled
         .port(8080)
         .timeout(Duration::from_secs(300))
@@ -27,7 +26,7 @@ where
     println!("Got access token: {}", oauth_response.access_token.as_str());
 
     // Get user info using the access token
-    let user_info = UserInfoBuilder::token(oauth_response.access_token.as_str())
+    let user_info = UserInfoBuilder::<P>::token(oauth_response.access_token.as_str())
         .get_info()
         .await?;
 
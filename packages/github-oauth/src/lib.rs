@@ -1,27 +1,32 @@
-mod auth_flow;
-mod error;
-mod future;
-mod login;
-mod pkce;
+//! GitHub OAuth 2.0 browser flow
+//!
+//! A thin provider definition over `oauth-core`: [`GitHubProvider`] supplies
+//! the endpoints and [`UserInfo`], and the builders below are the core
+//! builders specialised to it.
+
 mod provider;
-mod refresh;
-mod server;
-mod template;
-mod traits;
 mod types;
-mod user_info;
 
-pub use auth_flow::AuthFlow;
-pub use error::OAuthError;
-pub use future::WrappedFuture;
-pub use pkce::PkceChallenge;
+pub use oauth_core::{
+    default_success_template, minimal_template, AccessType, CallbackMode, DeviceCode,
+    DeviceCodeHandler, OAuthConfigBuilder, OAuthError, OAuthLogin, OAuthProvider, OAuthRefresh,
+    OAuthResponse, OAuthUserInfo, PkceChallenge, Result, TemplateContext, TokenResponse,
+    WrappedFuture,
+};
 pub use provider::GitHubProvider;
-pub use template::{default_success_template, minimal_template, TemplateContext};
-pub use traits::{OAuthConfigBuilder, OAuthLogin, OAuthProvider, OAuthRefresh, OAuthUserInfo};
-pub use types::{AccessType, CallbackMode, OAuthResponse, TokenResponse, UserInfo};
+pub use types::UserInfo;
+
+pub type AuthFlow = oauth_core::AuthFlow<GitHubProvider>;
+
+pub type Login = oauth_core::Login<GitHubProvider>;
+pub type LoginClientSecretBuilder = oauth_core::LoginClientSecretBuilder<GitHubProvider>;
+pub type LoginScopesBuilder = oauth_core::LoginScopesBuilder<GitHubProvider>;
+pub type LoginConfigBuilder = oauth_core::LoginConfigBuilder<GitHubProvider>;
 
-pub use login::{Login, LoginConfigBuilder, LoginScopesBuilder};
-pub use refresh::{Refresh, RefreshExecuteBuilder, RefreshTokenBuilder};
-pub use user_info::{UserInfo as UserInfoBuilder, UserInfoExecuteBuilder};
+pub type Refresh = oauth_core::Refresh<GitHubProvider>;
+pub type RefreshClientSecretBuilder = oauth_core::RefreshClientSecretBuilder<GitHubProvider>;
+pub type RefreshTokenBuilder = oauth_core::RefreshTokenBuilder<GitHubProvider>;
+pub type RefreshExecuteBuilder = oauth_core::RefreshExecuteBuilder<GitHubProvider>;
 
-pub type Result<T> = std::result::Result<T, OAuthError>;
+pub type UserInfoBuilder = oauth_core::UserInfoBuilder<GitHubProvider>;
+pub type UserInfoExecuteBuilder = oauth_core::UserInfoExecuteBuilder<GitHubProvider>;
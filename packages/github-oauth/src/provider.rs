@@ -1,9 +1,12 @@
-use crate::traits::OAuthProvider;
+use crate::types::UserInfo;
+use oauth_core::OAuthProvider;
 
 /// GitHub OAuth provider implementation
 pub struct GitHubProvider;
 
 impl OAuthProvider for GitHubProvider {
+    type UserInfo = UserInfo;
+
     fn provider_name() -> &'static str {
         "GitHub"
     }
@@ -23,4 +26,21 @@ impl OAuthProvider for GitHubProvider {
     fn user_info_endpoint() -> &'static str {
         "https://api.github.com/user"
     }
+
+    fn device_authorization_endpoint() -> Option<&'static str> {
+        Some("https://github.com/login/device/code")
+    }
+
+    fn client_id_env() -> &'static str {
+        "GITHUB_CLIENT_ID"
+    }
+
+    fn client_secret_env() -> &'static str {
+        "GITHUB_CLIENT_SECRET"
+    }
+
+    // GitHub OAuth app tokens do not expire; apps re-authorize instead
+    fn supports_refresh() -> bool {
+        false
+    }
 }
@@ -1,23 +1,32 @@
-mod auth_flow;
-mod error;
-mod future;
-mod login;
-mod pkce;
-mod refresh;
-mod server;
-mod template;
+//! Google OAuth 2.0 browser flow
+//!
+//! A thin provider definition over `oauth-core`: [`GoogleProvider`] supplies
+//! the endpoints, the `access_type` parameter and [`UserInfo`], and the
+//! builders below are the core builders specialised to it.
+
+mod provider;
 mod types;
-mod user_info;
 
-pub use auth_flow::AuthFlow;
-pub use error::OAuthError;
-pub use future::WrappedFuture;
-pub use pkce::PkceChallenge;
-pub use template::{default_success_template, minimal_template, TemplateContext};
-pub use types::{AccessType, CallbackMode, OAuthResponse, TokenResponse, UserInfo};
+pub use oauth_core::{
+    default_success_template, minimal_template, AccessType, CallbackMode, DeviceCode,
+    DeviceCodeHandler, OAuthConfigBuilder, OAuthError, OAuthLogin, OAuthProvider, OAuthRefresh,
+    OAuthResponse, OAuthUserInfo, PkceChallenge, Result, TemplateContext, TokenResponse,
+    WrappedFuture,
+};
+pub use provider::GoogleProvider;
+pub use types::UserInfo;
+
+pub type AuthFlow = oauth_core::AuthFlow<GoogleProvider>;
+
+pub type Login = oauth_core::Login<GoogleProvider>;
+pub type LoginClientSecretBuilder = oauth_core::LoginClientSecretBuilder<GoogleProvider>;
+pub type LoginScopesBuilder = oauth_core::LoginScopesBuilder<GoogleProvider>;
+pub type LoginConfigBuilder = oauth_core::LoginConfigBuilder<GoogleProvider>;
 
-pub use login::{Login, LoginClientSecretBuilder, LoginConfigBuilder, LoginScopesBuilder};
-pub use refresh::{Refresh, RefreshExecuteBuilder, RefreshTokenBuilder};
-pub use user_info::{UserInfo as UserInfoBuilder, UserInfoExecuteBuilder};
+pub type Refresh = oauth_core::Refresh<GoogleProvider>;
+pub type RefreshClientSecretBuilder = oauth_core::RefreshClientSecretBuilder<GoogleProvider>;
+pub type RefreshTokenBuilder = oauth_core::RefreshTokenBuilder<GoogleProvider>;
+pub type RefreshExecuteBuilder = oauth_core::RefreshExecuteBuilder<GoogleProvider>;
 
-pub type Result<T> = std::result::Result<T, OAuthError>;
+pub type UserInfoBuilder = oauth_core::UserInfoBuilder<GoogleProvider>;
+pub type UserInfoExecuteBuilder = oauth_core::UserInfoExecuteBuilder<GoogleProvider>;
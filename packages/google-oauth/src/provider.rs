@@ -0,0 +1,49 @@
+use crate::types::UserInfo;
+use oauth_core::{AccessType, OAuthProvider};
+
+/// Google OAuth provider implementation
+pub struct GoogleProvider;
+
+impl OAuthProvider for GoogleProvider {
+    type UserInfo = UserInfo;
+
+    fn provider_name() -> &'static str {
+        "Google"
+    }
+
+    fn default_scopes() -> Vec<&'static str> {
+        vec!["https://www.googleapis.com/auth/tasks"]
+    }
+
+    fn auth_endpoint() -> &'static str {
+        "https://accounts.google.com/o/oauth2/v2/auth"
+    }
+
+    fn token_endpoint() -> &'static str {
+        "https://www.googleapis.com/oauth2/v4/token"
+    }
+
+    fn user_info_endpoint() -> &'static str {
+        "https://www.googleapis.com/oauth2/v2/userinfo"
+    }
+
+    fn device_authorization_endpoint() -> Option<&'static str> {
+        Some("https://oauth2.googleapis.com/device/code")
+    }
+
+    fn client_id_env() -> &'static str {
+        "GOOGLE_CLIENT_ID"
+    }
+
+    fn client_secret_env() -> &'static str {
+        "GOOGLE_CLIENT_SECRET"
+    }
+
+    // Offline access is how Google hands out refresh tokens
+    fn auth_params(access_type: AccessType) -> Vec<(&'static str, String)> {
+        vec![
+            ("access_type", access_type.as_str().to_string()),
+            ("include_granted_scopes", "true".to_string()),
+        ]
+    }
+}
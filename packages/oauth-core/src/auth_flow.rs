@@ -0,0 +1,174 @@
+use crate::{
+    exchange::{authorization_url, exchange_code},
+    future::WrappedFuture,
+    pkce::PkceChallenge,
+    provider::OAuthProvider,
+    server,
+    types::{AccessType, OAuthResponse},
+    Result,
+};
+use std::marker::PhantomData;
+use zeroize::Zeroizing;
+
+/// Manual authorization code flow for callers that handle the redirect themselves
+pub struct AuthFlow<P> {
+    client_id: String,
+    client_secret: Zeroizing<String>,
+    redirect_uri: String,
+    scopes: Vec<String>,
+    state: Option<String>,
+    access_type: AccessType,
+    pkce_challenge: PkceChallenge,
+    provider: PhantomData<fn() -> P>,
+}
+
+impl<P: OAuthProvider> AuthFlow<P> {
+    pub fn new(
+        client_id: impl Into<String>,
+        client_secret: impl Into<String>,
+        redirect_uri: impl Into<String>,
+    ) -> Result<Self> {
+        let pkce_challenge = PkceChallenge::new()?;
+        Ok(Self {
+            client_id: client_id.into(),
+            client_secret: Zeroizing::new(client_secret.into()),
+            redirect_uri: redirect_uri.into(),
+            scopes: P::default_scopes().into_iter().map(String::from).collect(),
+            state: None,
+            access_type: AccessType::Online,
+            pkce_challenge,
+            provider: PhantomData,
+        })
+    }
+
+    pub fn with_scopes(mut self, scopes: Vec<String>) -> Self {
+        self.scopes = scopes;
+        self
+    }
+
+    pub fn with_state(mut self, state: impl Into<String>) -> Self {
+        self.state = Some(state.into());
+        self
+    }
+
+    pub fn with_access_type(mut self, access_type: AccessType) -> Self {
+        self.access_type = access_type;
+        self
+    }
+
+    /// Use a custom PKCE challenge/verifier pair
+    /// 
+    /// This allows you to provide your own PKCE challenge, which can be useful
+    /// for testing or when you need to manage the challenge lifecycle yourself.
+    pub fn with_pkce_challenge(mut self, challenge: PkceChallenge) -> Self {
+        self.pkce_challenge = challenge;
+        self
+    }
+
+    pub fn auth_url(&self) -> String {
+        let default_state;
+        let state = match self.state.as_deref() {
+            Some(s) => s,
+            None => {
+                default_state = uuid::Uuid::new_v4().to_string();
+                &default_state
+            }
+        };
+
+        authorization_url::<P>(
+            &self.client_id,
+            &self.redirect_uri,
+            &self.scopes.join(" "),
+            state,
+            self.access_type,
+            &self.pkce_challenge,
+        )
+    }
+
+    pub fn handle_callback(&self, callback_url: &str) -> WrappedFuture<Result<OAuthResponse>> {
+        let code = match server::extract_callback_code(callback_url) {
+            Ok(code) => code,
+            Err(e) => return WrappedFuture::new(async move { Err(e) }),
+        };
+
+        let client_id = self.client_id.clone();
+        let client_secret = self.client_secret.clone();
+        let redirect_uri = self.redirect_uri.clone();
+        let pkce_challenge = self.pkce_challenge.clone();
+
+        WrappedFuture::new(async move {
+            exchange_code::<P>(
+                &code,
+                &client_id,
+                &client_secret,
+                &redirect_uri,
+                &pkce_challenge,
+            )
+            .await
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct TestProvider;
+
+    impl OAuthProvider for TestProvider {
+        type UserInfo = serde_json::Value;
+
+        fn provider_name() -> &'static str {
+            "Test"
+        }
+
+        fn default_scopes() -> Vec<&'static str> {
+            vec!["profile"]
+        }
+
+        fn auth_endpoint() -> &'static str {
+            "https://auth.example.com/authorize"
+        }
+
+        fn token_endpoint() -> &'static str {
+            "https://auth.example.com/token"
+        }
+
+        fn user_info_endpoint() -> &'static str {
+            "https://auth.example.com/userinfo"
+        }
+
+        fn client_id_env() -> &'static str {
+            "TEST_CLIENT_ID"
+        }
+
+        fn client_secret_env() -> &'static str {
+            "TEST_CLIENT_SECRET"
+        }
+
+        fn auth_params(access_type: AccessType) -> Vec<(&'static str, String)> {
+            vec![("access_type", access_type.as_str().to_string())]
+        }
+    }
+
+    #[test]
+    fn test_auth_url_includes_provider_params_and_pkce() {
+        let pkce = PkceChallenge::from_verifier(
+            "dBjftJeZ4CVP-mB92K27uhbUJU1p1r_wW1gFWFOEjXk".to_string(),
+        )
+        .expect("PKCE from verifier should work in tests");
+        let flow = AuthFlow::<TestProvider>::new("id", "secret", "http://localhost:8080/callback")
+            .expect("auth flow should build in tests")
+            .with_state("xyz")
+            .with_access_type(AccessType::Offline)
+            .with_pkce_challenge(pkce);
+
+        assert_eq!(
+            flow.auth_url(),
+            "https://auth.example.com/authorize?scope=profile&access_type=offline\
+             &response_type=code&state=xyz\
+             &redirect_uri=http%3A%2F%2Flocalhost%3A8080%2Fcallback&client_id=id\
+             &code_challenge=E9Melhoa2OwvFrEMTJguCHaoeK1t8URWbuGJSstw-cM&code_challenge_method=S256"
+        );
+    }
+}
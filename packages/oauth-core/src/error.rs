@@ -20,6 +20,9 @@ pub enum OAuthError {
     #[error("Network error: {0}")]
     Network(#[from] reqwest::Error),
 
+    #[error("Transport error: {0}")]
+    Transport(String),
+
     #[error("URL parse error: {0}")]
     UrlParse(#[from] url::ParseError),
 
@@ -50,6 +53,12 @@ pub enum OAuthError {
     #[error("Server error: {0}")]
     Server(String),
 
+    #[error("Device code expired before the user approved the login")]
+    DeviceCodeExpired,
+
+    #[error("{0} does not support the device authorization flow")]
+    DeviceFlowUnsupported(&'static str),
+
     #[error("PKCE generation failed: {0}")]
     PkceGenerationFailed(String),
 
@@ -0,0 +1,89 @@
+//! Authorization URL construction and code exchange shared by
+//! [`crate::LoginConfigBuilder`] and [`crate::AuthFlow`]
+
+use crate::{
+    error::OAuthError,
+    login::security::sanitize_api_error,
+    pkce::PkceChallenge,
+    provider::{OAuthProvider, USER_AGENT},
+    transport,
+    types::{AccessType, OAuthResponse},
+    Result,
+};
+
+/// Build the provider's authorization URL with PKCE parameters
+pub(crate) fn authorization_url<P: OAuthProvider>(
+    client_id: &str,
+    redirect_uri: &str,
+    scope: &str,
+    state: &str,
+    access_type: AccessType,
+    pkce_challenge: &PkceChallenge,
+) -> String {
+    let mut auth_url = format!("{}?scope={}", P::auth_endpoint(), urlencoding::encode(scope));
+
+    for (key, value) in P::auth_params(access_type) {
+        auth_url.push_str(&format!("&{}={}", key, urlencoding::encode(&value)));
+    }
+
+    auth_url.push_str(&format!(
+        "&response_type=code&state={}&redirect_uri={}&client_id={}",
+        urlencoding::encode(state),
+        urlencoding::encode(redirect_uri),
+        urlencoding::encode(client_id)
+    ));
+
+    // Add PKCE parameters (always enabled for enhanced security)
+    auth_url.push_str(&format!(
+        "&code_challenge={}&code_challenge_method={}",
+        urlencoding::encode(&pkce_challenge.code_challenge),
+        pkce_challenge.challenge_method()
+    ));
+
+    auth_url
+}
+
+/// Exchange an authorization code for tokens at the provider's token endpoint
+pub(crate) async fn exchange_code<P: OAuthProvider>(
+    code: &str,
+    client_id: &str,
+    client_secret: &str,
+    redirect_uri: &str,
+    pkce_challenge: &PkceChallenge,
+) -> Result<OAuthResponse> {
+    let params = [
+        ("code", code),
+        ("client_id", client_id),
+        ("client_secret", client_secret),
+        ("redirect_uri", redirect_uri),
+        ("grant_type", "authorization_code"),
+        // PKCE code_verifier (always enabled for enhanced security)
+        ("code_verifier", pkce_challenge.code_verifier.as_str()),
+    ];
+
+    // GitHub answers form-encoded unless JSON is requested explicitly
+    let response = transport::send(
+        transport::client()
+            .post(P::token_endpoint())
+            .header("Accept", "application/json")
+            .header("User-Agent", USER_AGENT)
+            .form(&params),
+    )
+    .await?;
+
+    if !response.status().is_success() {
+        let status_code = response.status().as_u16();
+        let error_text = response.text().await?;
+        // Security: Sanitize error messages to prevent information disclosure
+        let sanitized_error = sanitize_api_error(&error_text, status_code);
+        return Err(OAuthError::TokenExchange(sanitized_error));
+    }
+
+    // GitHub reports some failures with a 200 and an `error` body
+    let body: serde_json::Value = response.json().await?;
+    if body.get("error").is_some() {
+        return Err(OAuthError::TokenExchange(sanitize_api_error(&body.to_string(), 200)));
+    }
+
+    Ok(serde_json::from_value(body)?)
+}
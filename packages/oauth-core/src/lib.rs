@@ -0,0 +1,42 @@
+//! Provider-agnostic OAuth 2.0 loopback browser flow
+//!
+//! PKCE, the local callback listener, token exchange, refresh and user info
+//! requests are implemented once here and parameterised by an
+//! [`OAuthProvider`]. Requests go through a replaceable [`HttpTransport`]. Provider packages define a unit struct, its user info
+//! type and a handful of type aliases.
+
+mod auth_flow;
+mod error;
+mod exchange;
+mod future;
+mod login;
+mod pkce;
+mod provider;
+mod refresh;
+mod server;
+mod template;
+mod traits;
+mod transport;
+mod types;
+mod user_info;
+
+pub use auth_flow::AuthFlow;
+pub use error::OAuthError;
+pub use future::WrappedFuture;
+pub use pkce::PkceChallenge;
+pub use provider::OAuthProvider;
+pub use template::{default_success_template, minimal_template, TemplateContext};
+pub use traits::{OAuthConfigBuilder, OAuthLogin, OAuthRefresh, OAuthUserInfo};
+pub use transport::{set_transport, HttpTransport, TransportFuture};
+pub use types::{
+    AccessType, CallbackMode, DeviceCode, DeviceCodeHandler, OAuthResponse, TokenResponse,
+};
+
+pub use login::callback::wait_for_callback;
+pub use login::{Login, LoginClientSecretBuilder, LoginConfigBuilder, LoginScopesBuilder};
+pub use refresh::{
+    Refresh, RefreshClientSecretBuilder, RefreshExecuteBuilder, RefreshTokenBuilder,
+};
+pub use user_info::{UserInfo as UserInfoBuilder, UserInfoExecuteBuilder};
+
+pub type Result<T> = std::result::Result<T, OAuthError>;
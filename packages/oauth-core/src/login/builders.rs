@@ -1,56 +1,67 @@
 use crate::{
     pkce::PkceChallenge,
-    types::{AccessType, CallbackMode},
+    provider::OAuthProvider,
+    types::{AccessType, CallbackMode, DeviceCode, DeviceCodeHandler},
     Result,
 };
+use std::marker::PhantomData;
 use std::time::Duration;
 use zeroize::Zeroizing;
 
 /// Main entry point for OAuth login flow
-pub struct Login;
+pub struct Login<P> {
+    provider: PhantomData<fn() -> P>,
+}
 
-impl Login {
+impl<P: OAuthProvider> Login<P> {
     /// Create a new Login instance (hidden from docs)
     #[doc(hidden)]
     pub fn new() -> Self {
-        Self
+        Self {
+            provider: PhantomData,
+        }
     }
 
     /// Create login flow using environment variables for credentials
     /// 
-    /// Expects GOOGLE_CLIENT_ID and GOOGLE_CLIENT_SECRET environment variables.
-    pub fn from_env() -> LoginScopesBuilder {
+    /// Reads the variables named by [`OAuthProvider::client_id_env`] and
+    /// [`OAuthProvider::client_secret_env`].
+    pub fn from_env() -> LoginScopesBuilder<P> {
         LoginScopesBuilder {
             client_id: None,
             client_secret: None,
             from_env: true,
+            provider: PhantomData,
         }
     }
 
     /// Create login flow with explicit client ID
     /// 
     /// This requires calling `.client_secret()` next to provide the secret.
-    pub fn client_id(id: impl Into<String>) -> LoginClientSecretBuilder {
+    pub fn client_id(id: impl Into<String>) -> LoginClientSecretBuilder<P> {
         LoginClientSecretBuilder {
             client_id: id.into(),
+            provider: PhantomData,
         }
     }
 }
 
 /// Builder for providing client secret after client ID
-pub struct LoginClientSecretBuilder {
+pub struct LoginClientSecretBuilder<P> {
     client_id: String,
+    provider: PhantomData<fn() -> P>,
 }
 
-impl LoginClientSecretBuilder {
+impl<P: OAuthProvider> LoginClientSecretBuilder<P> {
     /// Provide the client secret
     /// 
     /// This transitions to the scopes builder where you must specify OAuth scopes.
-    pub fn client_secret(self, secret: impl Into<String>) -> LoginScopesBuilder {
+    pub fn client_secret(self, secret: impl Into<String>) -> LoginScopesBuilder<P> {
         LoginScopesBuilder {
             client_id: Some(self.client_id),
             client_secret: Some(Zeroizing::new(secret.into())),
             from_env: false,
+            provider: PhantomData,
         }
     }
 }
@@ -58,33 +69,42 @@ impl LoginClientSecretBuilder {
 /// Builder for configuring OAuth scopes
 /// 
 /// You must call either `.scopes()` or `.add_scope()` to proceed to configuration.
-pub struct LoginScopesBuilder {
+pub struct LoginScopesBuilder<P> {
     pub(crate) client_id: Option<String>,
     pub(crate) client_secret: Option<Zeroizing<String>>,
     pub(crate) from_env: bool,
+    pub(crate) provider: PhantomData<fn() -> P>,
 }
 
-impl LoginScopesBuilder {
+impl<P: OAuthProvider> LoginScopesBuilder<P> {
+    /// Provide or replace the client secret
+    pub fn client_secret(mut self, secret: impl Into<String>) -> Self {
+        self.client_secret = Some(Zeroizing::new(secret.into()));
+        self
+    }
+
     /// Set multiple OAuth scopes at once
     /// 
     /// # Arguments
     /// * `scopes` - An iterator of scope strings or convertible types
     /// 
     /// # Returns
-    /// A `Result<LoginConfigBuilder>` for further configuration or execution
-    pub fn scopes(self, scopes: impl IntoIterator<Item = impl Into<String>>) -> Result<LoginConfigBuilder> {
+    /// A `Result<LoginConfigBuilder<P>>` for further configuration or execution
+    pub fn scopes(self, scopes: impl IntoIterator<Item = impl Into<String>>) -> Result<LoginConfigBuilder<P>> {
         let pkce_challenge = PkceChallenge::new()?;
         Ok(LoginConfigBuilder {
             client_id: self.client_id,
             client_secret: self.client_secret,
             from_env: self.from_env,
             scopes: scopes.into_iter().map(Into::into).collect(),
-            callback_mode: CallbackMode::server(8080),
+            callback_mode: CallbackMode::server(0),
             redirect_uri: None,
             state: None,
             access_type: AccessType::Online,
             timeout: Duration::from_secs(300),
             pkce_challenge,
+            on_device_code: None,
+            provider: PhantomData,
         })
     }
 
@@ -94,20 +114,22 @@ impl LoginScopesBuilder {
     /// * `scope` - The OAuth scope string to add
     /// 
     /// # Returns
-    /// A `Result<LoginConfigBuilder>` for further configuration or execution
-    pub fn add_scope(self, scope: impl Into<String>) -> Result<LoginConfigBuilder> {
+    /// A `Result<LoginConfigBuilder<P>>` for further configuration or execution
+    pub fn add_scope(self, scope: impl Into<String>) -> Result<LoginConfigBuilder<P>> {
         let pkce_challenge = PkceChallenge::new()?;
         Ok(LoginConfigBuilder {
             client_id: self.client_id,
             client_secret: self.client_secret,
             from_env: self.from_env,
             scopes: vec![scope.into()],
-            callback_mode: CallbackMode::server(8080),
+            callback_mode: CallbackMode::server(0),
             redirect_uri: None,
             state: None,
             access_type: AccessType::Online,
             timeout: Duration::from_secs(300),
             pkce_challenge,
+            on_device_code: None,
+            provider: PhantomData,
         })
     }
 }
@@ -115,7 +137,7 @@ impl LoginScopesBuilder {
 /// Main configuration builder for OAuth login flow
 /// 
 /// Configure various OAuth parameters before calling `.login()` to execute.
-pub struct LoginConfigBuilder {
+pub struct LoginConfigBuilder<P> {
     pub(crate) client_id: Option<String>,
     pub(crate) client_secret: Option<Zeroizing<String>>,
     pub(crate) from_env: bool,
@@ -126,9 +148,11 @@ pub struct LoginConfigBuilder {
     pub(crate) access_type: AccessType,
     pub(crate) timeout: Duration,
     pub(crate) pkce_challenge: PkceChallenge,
+    pub(crate) on_device_code: Option<DeviceCodeHandler>,
+    pub(crate) provider: PhantomData<fn() -> P>,
 }
 
-impl LoginConfigBuilder {
+impl<P: OAuthProvider> LoginConfigBuilder<P> {
     /// Add an additional scope to the existing scopes
     #[inline]
     pub fn add_scope(mut self, scope: impl Into<String>) -> Self {
@@ -138,8 +162,12 @@ impl LoginConfigBuilder {
 
     /// Set the local port for the OAuth callback server
     /// 
+    /// Defaults to 0, which binds a free ephemeral port and puts it in the
+    /// default `http://127.0.0.1:{port}/callback` redirect URI. Only pin a
+    /// port for providers that require an exact redirect URI match.
+    /// 
     /// # Arguments
-    /// * `port` - Port number for the local HTTP server (typically 8080)
+    /// * `port` - Port number for the loopback HTTP server
     #[inline]
     pub fn port(mut self, port: u16) -> Self {
         self.callback_mode = CallbackMode::server(port);
@@ -156,6 +184,22 @@ impl LoginConfigBuilder {
         self
     }
 
+    /// Log in with the RFC 8628 device authorization flow
+    /// 
+    /// Instead of redirecting a local browser, the provider issues a short
+    /// user code. `on_code` receives it with the verification URL so the
+    /// caller can show both; `.login()` then polls the token endpoint until
+    /// the user approves, denies or the code expires.
+    /// 
+    /// # Arguments
+    /// * `on_code` - Called once with the code to show to the user
+    #[inline]
+    pub fn device_code(mut self, on_code: impl FnOnce(&DeviceCode) + Send + 'static) -> Self {
+        self.callback_mode = CallbackMode::Device;
+        self.on_device_code = Some(Box::new(on_code));
+        self
+    }
+
     /// Set a custom redirect URI (optional)
     /// 
     /// # Arguments
@@ -0,0 +1,305 @@
+use crate::{
+    error::OAuthError,
+    template::{default_success_template, TemplateContext},
+    Result,
+};
+use super::security::create_secure_http_response;
+use std::sync::Arc;
+use tokio::{
+    io::{AsyncReadExt, AsyncWriteExt},
+    net::{TcpListener, TcpStream},
+    task::JoinSet,
+};
+use zeroize::Zeroizing;
+
+/// Security: Limit request size to prevent DoS attacks
+///
+/// 8KB is plenty for a request line with an authorization code plus the
+/// headers browsers send.
+const MAX_REQUEST_SIZE: usize = 8192;
+
+/// What a request to the callback server means
+#[derive(Debug, PartialEq, Eq)]
+enum Callback {
+    /// Not the callback path (favicon, probes): answer 404 and keep waiting
+    Ignored,
+    /// Callback for another login attempt, or a forged one: keep waiting
+    InvalidState,
+    /// The provider redirected back with an `error`
+    Denied(String),
+    /// The authorization code for this login
+    Code(Zeroizing<String>),
+}
+
+/// Wait for OAuth callback from browser and extract authorization code
+///
+/// Serves the loopback listener until a request to `callback_path` carrying
+/// `expected_state` arrives. Browsers open speculative pre-connects and ask
+/// for `/favicon.ico`, so each connection is handled on its own task and
+/// anything that is not the callback is answered and ignored.
+///
+/// # Arguments
+/// * `listener` - TCP listener bound to the loopback callback port
+/// * `callback_path` - Path of the redirect URI, e.g. `/callback`
+/// * `expected_state` - Expected state parameter for CSRF validation
+///
+/// # Returns
+/// The authorization code on success, or an error if the provider reports one
+///
+/// # Security
+/// - Limits request size to prevent DoS attacks
+/// - Validates CSRF state parameter, rejecting mismatches without aborting
+/// - Sends secure HTTP response headers
+pub async fn wait_for_callback(
+    listener: TcpListener,
+    callback_path: &str,
+    expected_state: &str,
+) -> Result<Zeroizing<String>> {
+    log::debug!("Waiting for OAuth callback on {:?}", listener.local_addr());
+    let callback_path: Arc<str> = Arc::from(callback_path);
+    let expected_state: Arc<str> = Arc::from(expected_state);
+
+    // Dropping the set aborts connections still open once the callback arrives
+    let mut connections = JoinSet::new();
+    loop {
+        tokio::select! {
+            accepted = listener.accept() => {
+                let (stream, peer) = accepted?;
+                log::debug!("Accepted connection from {}", peer);
+                let callback_path = Arc::clone(&callback_path);
+                let expected_state = Arc::clone(&expected_state);
+                connections.spawn(async move {
+                    handle_connection(stream, &callback_path, &expected_state).await
+                });
+            }
+            Some(joined) = connections.join_next() => {
+                if let Ok(Some(result)) = joined {
+                    log::info!("OAuth callback handled");
+                    return result;
+                }
+            }
+        }
+    }
+}
+
+/// Answer one connection, returning the login outcome if it was the callback
+async fn handle_connection(
+    mut stream: TcpStream,
+    callback_path: &str,
+    expected_state: &str,
+) -> Option<Result<Zeroizing<String>>> {
+    let head = match read_request_head(&mut stream).await {
+        Ok(Some(head)) => head,
+        // Pre-connect closed without sending anything
+        Ok(None) => return None,
+        Err(e) => {
+            log::debug!("Dropping callback connection: {}", e);
+            if matches!(e, OAuthError::Server(_)) {
+                respond(&mut stream, "431 Request Header Fields Too Large", "Request too large").await;
+            }
+            return None;
+        }
+    };
+
+    let callback = match request_target(&head) {
+        Some(target) => parse_callback(&target, callback_path, expected_state),
+        None => Callback::Ignored,
+    };
+
+    match callback {
+        Callback::Ignored => {
+            respond(&mut stream, "404 Not Found", "Not found").await;
+            None
+        }
+        Callback::InvalidState => {
+            log::warn!("Ignoring OAuth callback with an unexpected state parameter");
+            let html = default_success_template(&TemplateContext::error(
+                "Invalid state parameter - please restart the sign-in",
+            ))
+            .into_string();
+            respond(&mut stream, "400 Bad Request", &html).await;
+            None
+        }
+        Callback::Denied(error_desc) => {
+            let html =
+                default_success_template(&TemplateContext::error(error_desc.clone())).into_string();
+            respond(&mut stream, "200 OK", &html).await;
+            Some(Err(OAuthError::Authorization(error_desc)))
+        }
+        Callback::Code(code) => {
+            log::debug!("Sending success response to browser");
+            let html = default_success_template(&TemplateContext::success()).into_string();
+            respond(&mut stream, "200 OK", &html).await;
+            Some(Ok(code))
+        }
+    }
+}
+
+/// Read until the end of the request head, across as many reads as it takes
+///
+/// Returns `Ok(None)` if the peer closes before sending a complete head and
+/// `OAuthError::Server` if the head exceeds [`MAX_REQUEST_SIZE`].
+async fn read_request_head(stream: &mut TcpStream) -> Result<Option<Vec<u8>>> {
+    let mut head = Vec::new();
+    let mut buffer = [0u8; 2048];
+    while !contains_head_end(&head) {
+        let n = stream.read(&mut buffer).await?;
+        if n == 0 {
+            return Ok(None);
+        }
+        head.extend_from_slice(&buffer[..n]);
+        if head.len() > MAX_REQUEST_SIZE {
+            return Err(OAuthError::Server("Request too large".to_string()));
+        }
+    }
+    Ok(Some(head))
+}
+
+fn contains_head_end(head: &[u8]) -> bool {
+    head.windows(4).any(|window| window == b"\r\n\r\n")
+}
+
+/// Target of a GET request line (`/callback?code=...`)
+fn request_target(head: &[u8]) -> Option<String> {
+    let head = String::from_utf8_lossy(head);
+    let mut parts = head.lines().next()?.split_whitespace();
+    match (parts.next(), parts.next()) {
+        (Some("GET"), Some(target)) => Some(target.to_string()),
+        _ => None,
+    }
+}
+
+fn parse_callback(target: &str, callback_path: &str, expected_state: &str) -> Callback {
+    let Ok(url) = url::Url::parse(&format!("http://127.0.0.1{target}")) else {
+        return Callback::Ignored;
+    };
+    if url.path() != callback_path {
+        return Callback::Ignored;
+    }
+
+    let param = |name: &str| {
+        url.query_pairs()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.into_owned())
+    };
+
+    // Security: Verify state matches expected value (CSRF protection)
+    if param("state").as_deref() != Some(expected_state) {
+        return Callback::InvalidState;
+    }
+    if let Some(error) = param("error") {
+        return Callback::Denied(param("error_description").unwrap_or(error));
+    }
+    match param("code") {
+        Some(code) => Callback::Code(Zeroizing::new(code)),
+        None => Callback::Denied("No authorization code found".to_string()),
+    }
+}
+
+async fn respond(stream: &mut TcpStream, status: &str, html: &str) {
+    let response = create_secure_http_response(status, html);
+    if let Err(e) = stream.write_all(response.as_bytes()).await {
+        log::debug!("Failed to write callback response: {}", e);
+        return;
+    }
+    let _ = stream.shutdown().await;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_callback() {
+        assert_eq!(
+            parse_callback("/callback?code=abc&state=s1", "/callback", "s1"),
+            Callback::Code(Zeroizing::new("abc".to_string()))
+        );
+        assert_eq!(parse_callback("/favicon.ico", "/callback", "s1"), Callback::Ignored);
+        assert_eq!(
+            parse_callback("/callback?code=abc&state=other", "/callback", "s1"),
+            Callback::InvalidState
+        );
+        assert_eq!(parse_callback("/callback?code=abc", "/callback", "s1"), Callback::InvalidState);
+        assert_eq!(
+            parse_callback(
+                "/callback?error=access_denied&error_description=User+said+no&state=s1",
+                "/callback",
+                "s1"
+            ),
+            Callback::Denied("User said no".to_string())
+        );
+        assert_eq!(
+            parse_callback("/callback?state=s1", "/callback", "s1"),
+            Callback::Denied("No authorization code found".to_string())
+        );
+    }
+
+    #[test]
+    fn test_request_target() {
+        let head = b"GET /callback?code=abc HTTP/1.1\r\nHost: 127.0.0.1\r\n\r\n";
+        assert_eq!(request_target(head).as_deref(), Some("/callback?code=abc"));
+        assert_eq!(request_target(b"POST /callback HTTP/1.1\r\n\r\n"), None);
+        assert_eq!(request_target(b""), None);
+    }
+
+    async fn send(port: u16, chunks: &[&[u8]]) -> String {
+        let mut stream = TcpStream::connect(("127.0.0.1", port)).await.unwrap();
+        for chunk in chunks {
+            stream.write_all(chunk).await.unwrap();
+            stream.flush().await.unwrap();
+            tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+        }
+        let mut response = String::new();
+        stream.read_to_string(&mut response).await.unwrap();
+        response
+    }
+
+    #[tokio::test]
+    async fn test_wait_for_callback_survives_unrelated_requests() {
+        let listener = TcpListener::bind(("127.0.0.1", 0)).await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let server = tokio::spawn(async move {
+            wait_for_callback(listener, "/callback", "s1").await
+        });
+
+        // Browser pre-connect that never sends a request
+        let _idle = TcpStream::connect(("127.0.0.1", port)).await.unwrap();
+
+        let favicon = send(port, &[b"GET /favicon.ico HTTP/1.1\r\nHost: x\r\n\r\n"]).await;
+        assert!(favicon.starts_with("HTTP/1.1 404"));
+
+        let stale = send(port, &[b"GET /callback?code=old&state=s0 HTTP/1.1\r\n\r\n"]).await;
+        assert!(stale.starts_with("HTTP/1.1 400"));
+
+        // Request head split across reads
+        let ok = send(
+            port,
+            &[b"GET /callback?code=abc&st", b"ate=s1 HTTP/1.1\r\nHost: x\r\n", b"\r\n"],
+        )
+        .await;
+        assert!(ok.starts_with("HTTP/1.1 200"));
+
+        let code = server.await.unwrap().unwrap();
+        assert_eq!(code.as_str(), "abc");
+    }
+
+    #[tokio::test]
+    async fn test_wait_for_callback_provider_error() {
+        let listener = TcpListener::bind(("127.0.0.1", 0)).await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let server = tokio::spawn(async move {
+            wait_for_callback(listener, "/callback", "s1").await
+        });
+
+        let oversized = format!("GET /callback?pad={} HTTP/1.1\r\n\r\n", "a".repeat(MAX_REQUEST_SIZE));
+        let rejected = send(port, &[oversized.as_bytes()]).await;
+        assert!(rejected.starts_with("HTTP/1.1 431"));
+
+        send(port, &[b"GET /callback?error=access_denied&state=s1 HTTP/1.1\r\n\r\n"]).await;
+        assert!(matches!(
+            server.await.unwrap(),
+            Err(OAuthError::Authorization(error)) if error == "access_denied"
+        ));
+    }
+}
@@ -0,0 +1,222 @@
+use crate::{
+    error::OAuthError,
+    provider::{OAuthProvider, USER_AGENT},
+    transport,
+    types::{DeviceCode, DeviceCodeHandler, OAuthResponse},
+    Result,
+};
+use super::security::sanitize_api_error;
+use serde::Deserialize;
+use std::time::Duration;
+use tokio::time::Instant;
+use zeroize::Zeroizing;
+
+/// Grant type for polling the token endpoint (RFC 8628 §3.4)
+const DEVICE_CODE_GRANT: &str = "urn:ietf:params:oauth:grant-type:device_code";
+
+/// Polling interval when the provider does not name one (RFC 8628 §3.2)
+const DEFAULT_INTERVAL: u64 = 5;
+
+/// Increase applied on `slow_down` when the provider does not name a new interval
+const SLOW_DOWN_STEP: Duration = Duration::from_secs(5);
+
+/// Device authorization response (RFC 8628 §3.2)
+#[derive(Deserialize)]
+struct DeviceAuthorizationResponse {
+    device_code: String,
+    user_code: String,
+    // Google still uses the draft name `verification_url`
+    #[serde(alias = "verification_url")]
+    verification_uri: String,
+    verification_uri_complete: Option<String>,
+    expires_in: u64,
+    interval: Option<u64>,
+}
+
+/// Result of one token endpoint poll
+#[derive(Debug)]
+enum Poll {
+    Token(OAuthResponse),
+    Pending,
+    /// `slow_down`, with the new interval if the provider sent one
+    SlowDown(Option<u64>),
+}
+
+/// Run the device authorization flow to completion
+///
+/// Requests a device code, hands it to `on_code`, then polls the token
+/// endpoint at the provider's interval until the user approves or denies the
+/// login or the code expires.
+pub(crate) async fn authorize<P: OAuthProvider>(
+    client_id: &str,
+    client_secret: &str,
+    scope: &str,
+    on_code: DeviceCodeHandler,
+) -> Result<OAuthResponse> {
+    let endpoint = P::device_authorization_endpoint()
+        .ok_or(OAuthError::DeviceFlowUnsupported(P::provider_name()))?;
+
+    let client = transport::client();
+    let response = transport::send(
+        client
+            .post(endpoint)
+            .header("Accept", "application/json")
+            .header("User-Agent", USER_AGENT)
+            .form(&[("client_id", client_id), ("scope", scope)]),
+    )
+    .await?;
+
+    if !response.status().is_success() {
+        let status_code = response.status().as_u16();
+        let error_text = response.text().await?;
+        // Security: Sanitize error messages to prevent information disclosure
+        return Err(OAuthError::Authorization(sanitize_api_error(&error_text, status_code)));
+    }
+
+    let authorization: DeviceAuthorizationResponse = response.json().await?;
+    let device_code = Zeroizing::new(authorization.device_code);
+    let deadline = Instant::now() + Duration::from_secs(authorization.expires_in);
+    let mut interval = Duration::from_secs(authorization.interval.unwrap_or(DEFAULT_INTERVAL));
+
+    log::info!(
+        "{} device login: enter code {} at {}",
+        P::provider_name(),
+        authorization.user_code,
+        authorization.verification_uri
+    );
+    on_code(&DeviceCode {
+        user_code: authorization.user_code,
+        verification_uri: authorization.verification_uri,
+        verification_uri_complete: authorization.verification_uri_complete,
+        expires_in: authorization.expires_in,
+    });
+
+    let mut params = vec![
+        ("client_id", client_id),
+        ("device_code", device_code.as_str()),
+        ("grant_type", DEVICE_CODE_GRANT),
+    ];
+    // Public clients (GitHub device flow) have no secret; don't send an empty one
+    if !client_secret.is_empty() {
+        params.push(("client_secret", client_secret));
+    }
+
+    loop {
+        tokio::time::sleep(interval).await;
+        if Instant::now() >= deadline {
+            return Err(OAuthError::DeviceCodeExpired);
+        }
+
+        let response = transport::send(
+            client
+                .post(P::token_endpoint())
+                .header("Accept", "application/json")
+                .header("User-Agent", USER_AGENT)
+                .form(&params),
+        )
+        .await?;
+
+        let status_code = response.status().as_u16();
+        let body = response.text().await?;
+        match classify(status_code, &body)? {
+            Poll::Token(token) => return Ok(token),
+            Poll::Pending => log::debug!("Device login pending, polling again in {:?}", interval),
+            Poll::SlowDown(next) => {
+                interval = next_interval(interval, next);
+                log::debug!("Device login asked to slow down, polling every {:?}", interval);
+            }
+        }
+    }
+}
+
+/// Interpret a token endpoint response while polling
+///
+/// Pending and slow-down errors arrive as 400s from most providers and as
+/// 200s from GitHub, so the body decides, not the status.
+fn classify(status_code: u16, body: &str) -> Result<Poll> {
+    let json: serde_json::Value = match serde_json::from_str(body) {
+        Ok(json) => json,
+        Err(_) => {
+            return Err(OAuthError::TokenExchange(sanitize_api_error(body, status_code)));
+        }
+    };
+
+    match json.get("error").and_then(|error| error.as_str()) {
+        None if (200..300).contains(&status_code) => Ok(Poll::Token(serde_json::from_value(json)?)),
+        Some("authorization_pending") => Ok(Poll::Pending),
+        Some("slow_down") => Ok(Poll::SlowDown(
+            json.get("interval").and_then(|interval| interval.as_u64()),
+        )),
+        Some("access_denied") => Err(OAuthError::AuthorizationDenied),
+        Some("expired_token") => Err(OAuthError::DeviceCodeExpired),
+        _ => Err(OAuthError::TokenExchange(sanitize_api_error(body, status_code))),
+    }
+}
+
+/// Polling interval after a `slow_down` (RFC 8628 §3.5)
+fn next_interval(current: Duration, requested: Option<u64>) -> Duration {
+    match requested {
+        Some(seconds) => Duration::from_secs(seconds),
+        None => current + SLOW_DOWN_STEP,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_classify_pending_from_200_and_400() {
+        let body = r#"{"error":"authorization_pending"}"#;
+        assert!(matches!(classify(200, body), Ok(Poll::Pending)));
+        assert!(matches!(classify(400, body), Ok(Poll::Pending)));
+    }
+
+    #[test]
+    fn test_classify_slow_down_interval() {
+        assert!(matches!(
+            classify(400, r#"{"error":"slow_down"}"#),
+            Ok(Poll::SlowDown(None))
+        ));
+        assert!(matches!(
+            classify(200, r#"{"error":"slow_down","interval":10}"#),
+            Ok(Poll::SlowDown(Some(10)))
+        ));
+    }
+
+    #[test]
+    fn test_classify_terminal_errors() {
+        assert!(matches!(
+            classify(400, r#"{"error":"access_denied"}"#),
+            Err(OAuthError::AuthorizationDenied)
+        ));
+        assert!(matches!(
+            classify(400, r#"{"error":"expired_token"}"#),
+            Err(OAuthError::DeviceCodeExpired)
+        ));
+        assert!(matches!(
+            classify(401, r#"{"error":"invalid_client"}"#),
+            Err(OAuthError::TokenExchange(_))
+        ));
+        assert!(matches!(classify(502, "Bad Gateway"), Err(OAuthError::TokenExchange(_))));
+    }
+
+    #[test]
+    fn test_classify_token() {
+        let body = r#"{"access_token":"gho_abc","token_type":"bearer","scope":"read:user"}"#;
+        match classify(200, body) {
+            Ok(Poll::Token(token)) => {
+                assert_eq!(token.access_token.as_str(), "gho_abc");
+                assert_eq!(token.expires_in, None);
+            }
+            other => panic!("expected a token, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_next_interval() {
+        let current = Duration::from_secs(5);
+        assert_eq!(next_interval(current, None), Duration::from_secs(10));
+        assert_eq!(next_interval(current, Some(15)), Duration::from_secs(15));
+    }
+}
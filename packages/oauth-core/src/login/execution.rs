@@ -0,0 +1,130 @@
+use crate::{
+    error::OAuthError,
+    exchange::{authorization_url, exchange_code},
+    future::WrappedFuture,
+    provider::{env_credentials, OAuthProvider},
+    types::{CallbackMode, OAuthResponse},
+    Result,
+};
+use super::{builders::LoginConfigBuilder, callback::wait_for_callback, device};
+
+impl<P: OAuthProvider> LoginConfigBuilder<P> {
+    /// Execute the OAuth login flow
+    /// 
+    /// This starts a loopback server to receive the callback, opens a
+    /// browser, and exchanges the authorization code for tokens. In
+    /// [`CallbackMode::Device`] it polls for a device code approval instead.
+    /// 
+    /// # Returns
+    /// A future that resolves to `Result<OAuthResponse>` containing access tokens
+    pub fn login(self) -> WrappedFuture<Result<OAuthResponse>> {
+        WrappedFuture::new(async move {
+            // Get credentials from environment or builder
+            let (client_id, client_secret) = if self.from_env {
+                env_credentials::<P>()?
+            } else {
+                (
+                    self.client_id.ok_or(OAuthError::MissingClientId)?,
+                    self.client_secret.ok_or(OAuthError::MissingClientSecret)?,
+                )
+            };
+
+            // Build scope string with fallback
+            let scope = if self.scopes.is_empty() {
+                P::default_scopes().join(" ")
+            } else {
+                self.scopes.join(" ")
+            };
+
+            let port = match self.callback_mode {
+                CallbackMode::Server { port } => port,
+                CallbackMode::Device => {
+                    // No redirect: the user approves on any device while we poll
+                    let on_code = self.on_device_code.unwrap_or_else(|| {
+                        Box::new(|code| {
+                            log::info!(
+                                "To sign in, visit {} and enter the code {}",
+                                code.verification_uri, code.user_code
+                            )
+                        })
+                    });
+                    return tokio::time::timeout(
+                        self.timeout,
+                        device::authorize::<P>(&client_id, &client_secret, &scope, on_code),
+                    )
+                    .await
+                    .map_err(|_| OAuthError::Timeout)?;
+                }
+            };
+
+            // Bind before building the redirect URI: port 0 lets the OS pick a
+            // free loopback port, which RFC 8252 §7.3 requires providers to accept
+            log::debug!("Attempting to bind to 127.0.0.1:{}", port);
+            let listener = tokio::net::TcpListener::bind(("127.0.0.1", port))
+                .await
+                .map_err(|e| {
+                    OAuthError::Server(format!("Failed to bind to port {}: {}", port, e))
+                })?;
+            let port = listener.local_addr()?.port();
+            log::info!("Successfully bound to 127.0.0.1:{}", port);
+
+            // Determine redirect URI
+            let redirect_uri = self
+                .redirect_uri
+                .unwrap_or_else(|| format!("http://127.0.0.1:{}/callback", port));
+            let callback_path = url::Url::parse(&redirect_uri)
+                .map_err(|_| OAuthError::InvalidRedirectUri(redirect_uri.clone()))?
+                .path()
+                .to_string();
+
+            // Generate or use provided state for CSRF protection
+            let state = self
+                .state
+                .unwrap_or_else(|| uuid::Uuid::new_v4().to_string());
+
+            // Build OAuth authorization URL with PKCE parameters
+            log::debug!("{} OAuth redirect_uri: {}", P::provider_name(), &redirect_uri);
+            let auth_url = authorization_url::<P>(
+                &client_id,
+                &redirect_uri,
+                &scope,
+                &state,
+                self.access_type,
+                &self.pkce_challenge,
+            );
+            log::debug!("OAuth auth URL: {}", &auth_url);
+
+            // Open browser to authorization URL
+            if webbrowser::open(&auth_url).is_err() {
+                log::warn!("Failed to open browser. Please visit this URL manually: {}", auth_url);
+            }
+
+            // Wait for callback with configured timeout
+            log::debug!("Starting wait_for_callback with timeout: {:?}", self.timeout);
+            let code = tokio::time::timeout(
+                self.timeout,
+                wait_for_callback(listener, &callback_path, &state),
+            )
+            .await
+            .map_err(|_| {
+                log::error!("OAuth callback timed out after {:?}", self.timeout);
+                OAuthError::Timeout
+            })?
+            .map_err(|e| {
+                log::error!("OAuth callback error: {}", e);
+                OAuthError::Server(e.to_string())
+            })?;
+            log::debug!("OAuth callback completed successfully");
+
+            // Exchange authorization code for access token using PKCE
+            exchange_code::<P>(
+                &code,
+                &client_id,
+                &client_secret,
+                &redirect_uri,
+                &self.pkce_challenge,
+            )
+            .await
+        })
+    }
+}
@@ -0,0 +1,42 @@
+//! OAuth Login Flow Module
+//!
+//! Provider-agnostic OAuth 2.0 loopback login with PKCE (Proof Key for Code
+//! Exchange), secure callback handling and sanitized errors. Every builder is
+//! parameterised by an [`OAuthProvider`](crate::OAuthProvider); provider
+//! packages expose them as type aliases.
+//!
+//! # Architecture
+//!
+//! The module is decomposed into focused submodules:
+//! - `builders` - Login builder pattern implementation
+//! - `execution` - OAuth flow execution logic
+//! - `callback` - HTTP server and callback handling
+//! - `device` - RFC 8628 device authorization polling
+//! - `security` - Error sanitization and secure response generation
+//!
+//! # Usage
+//!
+//! ```rust,no_run
+//! use oauth_core::{AccessType, Login, OAuthProvider};
+//!
+//! # async fn example<P: OAuthProvider>() -> Result<(), Box<dyn std::error::Error>> {
+//! let response = Login::<P>::from_env()
+//!     .scopes(P::default_scopes())?
+//!     .access_type(AccessType::Offline)
+//!     .timeout(std::time::Duration::from_secs(120))
+//!     .login()
+//!     .await?;
+//!
+//! println!("Access token: {}", &*response.access_token);
+//! # Ok(())
+//! # }
+//! ```
+
+pub mod builders;
+pub mod callback;
+pub mod device;
+pub mod execution;
+pub mod security;
+
+// Re-export the main types for ergonomic public API
+pub use builders::{Login, LoginClientSecretBuilder, LoginConfigBuilder, LoginScopesBuilder};
@@ -70,6 +70,7 @@ pub fn sanitize_api_error(error_text: &str, status_code: u16) -> String {
 /// various web vulnerabilities in the OAuth callback response.
 /// 
 /// # Arguments
+/// * `status` - Status line text, e.g. `200 OK` or `404 Not Found`
 /// * `html` - The HTML content to include in the response body
 /// 
 /// # Returns
@@ -84,10 +85,12 @@ pub fn sanitize_api_error(error_text: &str, status_code: u16) -> String {
 /// - Cache-Control: prevent sensitive data caching
 /// - Pragma: additional cache prevention for older clients
 /// - Expires: ensure immediate expiration
-pub fn create_secure_http_response(html: &str) -> String {
+pub fn create_secure_http_response(status: &str, html: &str) -> String {
     format!(
-        "HTTP/1.1 200 OK\r\n\
+        "HTTP/1.1 {status}\r\n\
          Content-Type: text/html; charset=utf-8\r\n\
+         Content-Length: {}\r\n\
+         Connection: close\r\n\
          X-Frame-Options: DENY\r\n\
          X-Content-Type-Options: nosniff\r\n\
          X-XSS-Protection: 1; mode=block\r\n\
@@ -96,6 +99,7 @@ pub fn create_secure_http_response(html: &str) -> String {
          Cache-Control: no-cache, no-store, must-revalidate\r\n\
          Pragma: no-cache\r\n\
          Expires: 0\r\n\
-         \r\n{html}"
+         \r\n{html}",
+        html.len()
     )
 }
\ No newline at end of file
@@ -31,7 +31,7 @@ impl PkceChallenge {
     /// # Example
     /// 
     /// ```rust
-    /// use github_oauth::PkceChallenge;
+    /// use oauth_core::PkceChallenge;
     /// 
     /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
     /// let challenge = PkceChallenge::new()?;
@@ -151,7 +151,7 @@ fn validate_code_verifier(verifier: &Zeroizing<String>) -> Result<()> {
     let len = verifier_str.len();
     
     // RFC 7636: code verifier must be 43-128 characters
-    if len < 43 || len > 128 {
+    if !(43..=128).contains(&len) {
         return Err(OAuthError::InvalidCodeChallenge(format!(
             "Code verifier length {} is invalid (must be 43-128 characters)", 
             len
@@ -0,0 +1,63 @@
+use crate::types::AccessType;
+use serde::de::DeserializeOwned;
+
+/// Everything that distinguishes one OAuth 2.0 provider from another
+///
+/// The loopback browser flow, PKCE, token exchange, refresh and user info
+/// requests are implemented once in this crate and parameterised by a
+/// provider type. A new provider is a unit struct implementing this trait
+/// plus its user info type; see the `github-oauth` and `google-oauth`
+/// packages for complete examples.
+pub trait OAuthProvider: Send + 'static {
+    /// User profile returned by [`OAuthProvider::user_info_endpoint`]
+    type UserInfo: DeserializeOwned + Send + 'static;
+
+    /// The name of the OAuth provider (e.g., "GitHub", "Google")
+    fn provider_name() -> &'static str;
+
+    /// Scopes requested when the caller does not name any
+    fn default_scopes() -> Vec<&'static str>;
+
+    /// Authorization endpoint URL
+    fn auth_endpoint() -> &'static str;
+
+    /// Token exchange endpoint URL
+    fn token_endpoint() -> &'static str;
+
+    /// User info endpoint URL
+    fn user_info_endpoint() -> &'static str;
+
+    /// RFC 8628 device authorization endpoint URL, if the provider has one
+    fn device_authorization_endpoint() -> Option<&'static str> {
+        None
+    }
+
+    /// Environment variable holding the client ID for `from_env()` builders
+    fn client_id_env() -> &'static str;
+
+    /// Environment variable holding the client secret for `from_env()` builders
+    fn client_secret_env() -> &'static str;
+
+    /// Provider-specific query parameters appended to the authorization URL
+    fn auth_params(_access_type: AccessType) -> Vec<(&'static str, String)> {
+        Vec::new()
+    }
+
+    /// Whether the token endpoint accepts the `refresh_token` grant
+    fn supports_refresh() -> bool {
+        true
+    }
+}
+
+/// Read the client credentials named by the provider from the environment
+pub(crate) fn env_credentials<P: OAuthProvider>(
+) -> crate::Result<(String, zeroize::Zeroizing<String>)> {
+    let id = std::env::var(P::client_id_env())
+        .map_err(|_| crate::OAuthError::EnvVar(P::client_id_env().to_string()))?;
+    let secret = std::env::var(P::client_secret_env())
+        .map_err(|_| crate::OAuthError::EnvVar(P::client_secret_env().to_string()))?;
+    Ok((id, zeroize::Zeroizing::new(secret)))
+}
+
+/// User agent sent with every request (GitHub rejects requests without one)
+pub(crate) const USER_AGENT: &str = concat!(env!("CARGO_PKG_NAME"), "/", env!("CARGO_PKG_VERSION"));
@@ -1,77 +1,99 @@
-use crate::{error::OAuthError, future::WrappedFuture, types::TokenResponse, Result};
+use crate::{
+    error::OAuthError,
+    future::WrappedFuture,
+    provider::{env_credentials, OAuthProvider, USER_AGENT},
+    transport,
+    types::TokenResponse,
+    Result,
+};
+use std::marker::PhantomData;
 use zeroize::Zeroizing;
 
-pub struct Refresh;
+pub struct Refresh<P> {
+    provider: PhantomData<fn() -> P>,
+}
 
-impl Refresh {
+impl<P: OAuthProvider> Refresh<P> {
     #[doc(hidden)]
     pub fn new() -> Self {
-        Self
+        Self {
+            provider: PhantomData,
+        }
     }
 
-    pub fn from_env() -> RefreshTokenBuilder {
+    pub fn from_env() -> RefreshTokenBuilder<P> {
         RefreshTokenBuilder {
             client_id: None,
             client_secret: None,
             from_env: true,
+            provider: PhantomData,
         }
     }
 
-    pub fn client_id(id: impl Into<String>) -> RefreshClientSecretBuilder {
+    pub fn client_id(id: impl Into<String>) -> RefreshClientSecretBuilder<P> {
         RefreshClientSecretBuilder {
             client_id: id.into(),
+            provider: PhantomData,
         }
     }
 }
 
-pub struct RefreshClientSecretBuilder {
+pub struct RefreshClientSecretBuilder<P> {
     client_id: String,
+    provider: PhantomData<fn() -> P>,
 }
 
-impl RefreshClientSecretBuilder {
-    pub fn client_secret(self, secret: impl Into<String>) -> RefreshTokenBuilder {
+impl<P: OAuthProvider> RefreshClientSecretBuilder<P> {
+    pub fn client_secret(self, secret: impl Into<String>) -> RefreshTokenBuilder<P> {
         RefreshTokenBuilder {
             client_id: Some(self.client_id),
             client_secret: Some(Zeroizing::new(secret.into())),
             from_env: false,
+            provider: PhantomData,
         }
     }
 }
 
-pub struct RefreshTokenBuilder {
+pub struct RefreshTokenBuilder<P> {
     client_id: Option<String>,
     client_secret: Option<Zeroizing<String>>,
     from_env: bool,
+    provider: PhantomData<fn() -> P>,
 }
 
-impl RefreshTokenBuilder {
-    pub fn token(self, refresh_token: impl Into<String>) -> RefreshExecuteBuilder {
+impl<P: OAuthProvider> RefreshTokenBuilder<P> {
+    pub fn token(self, refresh_token: impl Into<String>) -> RefreshExecuteBuilder<P> {
         RefreshExecuteBuilder {
             client_id: self.client_id,
             client_secret: self.client_secret,
             from_env: self.from_env,
             refresh_token: Zeroizing::new(refresh_token.into()),
+            provider: PhantomData,
         }
     }
 }
 
-pub struct RefreshExecuteBuilder {
-    client_id: Option<String>,
-    client_secret: Option<Zeroizing<String>>,
-    from_env: bool,
-    refresh_token: Zeroizing<String>,
+pub struct RefreshExecuteBuilder<P> {
+    pub(crate) client_id: Option<String>,
+    pub(crate) client_secret: Option<Zeroizing<String>>,
+    pub(crate) from_env: bool,
+    pub(crate) refresh_token: Zeroizing<String>,
+    pub(crate) provider: PhantomData<fn() -> P>,
 }
 
-impl RefreshExecuteBuilder {
+impl<P: OAuthProvider> RefreshExecuteBuilder<P> {
     pub fn refresh(self) -> WrappedFuture<Result<TokenResponse>> {
         WrappedFuture::new(async move {
+            if !P::supports_refresh() {
+                return Err(OAuthError::Authorization(format!(
+                    "{} does not support refresh tokens. Re-authorization required.",
+                    P::provider_name()
+                )));
+            }
+
             // Get credentials
             let (client_id, client_secret) = if self.from_env {
-                let id = std::env::var("GOOGLE_CLIENT_ID")
-                    .map_err(|_| OAuthError::EnvVar("GOOGLE_CLIENT_ID".to_string()))?;
-                let secret = std::env::var("GOOGLE_CLIENT_SECRET")
-                    .map_err(|_| OAuthError::EnvVar("GOOGLE_CLIENT_SECRET".to_string()))?;
-                (id, Zeroizing::new(secret))
+                env_credentials::<P>()?
             } else {
                 (
                     self.client_id.ok_or(OAuthError::MissingClientId)?,
@@ -79,27 +101,21 @@ impl RefreshExecuteBuilder {
                 )
             };
 
-            #[derive(serde::Serialize)]
-            struct RefreshTokenRequest {
-                client_id: String,
-                client_secret: String,
-                refresh_token: String,
-                grant_type: String,
-            }
-
-            let request = RefreshTokenRequest {
-                client_id,
-                client_secret: client_secret.as_str().to_string(),
-                refresh_token: self.refresh_token.as_str().to_string(),
-                grant_type: "refresh_token".to_string(),
-            };
-
-            let client = reqwest::Client::new();
-            let response = client
-                .post("https://www.googleapis.com/oauth2/v4/token")
-                .json(&request)
-                .send()
-                .await?;
+            let params = [
+                ("client_id", client_id.as_str()),
+                ("client_secret", client_secret.as_str()),
+                ("refresh_token", self.refresh_token.as_str()),
+                ("grant_type", "refresh_token"),
+            ];
+
+            let response = transport::send(
+                transport::client()
+                    .post(P::token_endpoint())
+                    .header("Accept", "application/json")
+                    .header("User-Agent", USER_AGENT)
+                    .form(&params),
+            )
+            .await?;
 
             if !response.status().is_success() {
                 let status_code = response.status().as_u16();
@@ -0,0 +1,153 @@
+use crate::future::WrappedFuture;
+use crate::{
+    login::{Login, LoginConfigBuilder, LoginScopesBuilder},
+    provider::OAuthProvider,
+    refresh::RefreshExecuteBuilder,
+    types::{OAuthResponse, TokenResponse},
+    user_info::UserInfoExecuteBuilder,
+    Result,
+};
+use std::marker::PhantomData;
+use std::time::Duration;
+use zeroize::Zeroizing;
+
+/// Common trait for OAuth login builders
+pub trait OAuthLogin {
+    type Builder;
+
+    /// Create a new login builder from environment variables
+    fn from_env() -> Self::Builder;
+
+    /// Create a new login builder with explicit client ID
+    fn client_id(id: impl Into<String>) -> Self::Builder;
+}
+
+/// Common trait for OAuth configuration builders
+pub trait OAuthConfigBuilder {
+    /// Add a scope to the OAuth request
+    fn add_scope(self, scope: impl Into<String>) -> Self;
+
+    /// Set scopes for the OAuth request
+    fn scopes(self, scopes: impl IntoIterator<Item = impl Into<String>>) -> Result<Self>
+    where
+        Self: Sized;
+
+    /// Set the local port for the callback server
+    fn port(self, port: u16) -> Self;
+
+    /// Set custom redirect URI
+    fn redirect_uri(self, uri: impl Into<String>) -> Self;
+
+    /// Set custom state parameter
+    fn state(self, state: impl Into<String>) -> Self;
+
+    /// Set timeout for the OAuth flow
+    fn timeout(self, timeout: Duration) -> Self;
+
+    /// Execute the OAuth login flow
+    fn login(self) -> WrappedFuture<Result<OAuthResponse>>;
+}
+
+/// Common trait for user info retrieval
+pub trait OAuthUserInfo {
+    type Info;
+
+    /// Create a new user info builder with an access token
+    fn with_token(token: impl Into<String>) -> Self;
+
+    /// Execute the user info request
+    fn execute(self) -> WrappedFuture<Result<Self::Info>>;
+}
+
+/// Common trait for token refresh
+pub trait OAuthRefresh {
+    /// Create a new refresh builder with a refresh token
+    fn with_refresh_token(token: impl Into<String>) -> Self;
+
+    /// Execute the token refresh
+    fn execute(self) -> WrappedFuture<Result<TokenResponse>>;
+}
+
+impl<P: OAuthProvider> OAuthLogin for Login<P> {
+    type Builder = LoginScopesBuilder<P>;
+
+    fn from_env() -> Self::Builder {
+        Login::from_env()
+    }
+
+    fn client_id(id: impl Into<String>) -> Self::Builder {
+        LoginScopesBuilder {
+            client_id: Some(id.into()),
+            client_secret: None,
+            from_env: false,
+            provider: PhantomData,
+        }
+    }
+}
+
+impl<P: OAuthProvider> OAuthConfigBuilder for LoginConfigBuilder<P> {
+    fn add_scope(self, scope: impl Into<String>) -> Self {
+        self.add_scope(scope)
+    }
+
+    fn scopes(self, scopes: impl IntoIterator<Item = impl Into<String>>) -> Result<Self> {
+        LoginScopesBuilder {
+            client_id: self.client_id,
+            client_secret: self.client_secret,
+            from_env: self.from_env,
+            provider: PhantomData,
+        }
+        .scopes(scopes)
+    }
+
+    fn port(self, port: u16) -> Self {
+        self.port(port)
+    }
+
+    fn redirect_uri(self, uri: impl Into<String>) -> Self {
+        self.redirect_uri(uri)
+    }
+
+    fn state(self, state: impl Into<String>) -> Self {
+        self.state(state)
+    }
+
+    fn timeout(self, timeout: Duration) -> Self {
+        self.timeout(timeout)
+    }
+
+    fn login(self) -> WrappedFuture<Result<OAuthResponse>> {
+        self.login()
+    }
+}
+
+impl<P: OAuthProvider> OAuthUserInfo for UserInfoExecuteBuilder<P> {
+    type Info = P::UserInfo;
+
+    fn with_token(token: impl Into<String>) -> Self {
+        UserInfoExecuteBuilder {
+            access_token: Zeroizing::new(token.into()),
+            provider: PhantomData,
+        }
+    }
+
+    fn execute(self) -> WrappedFuture<Result<Self::Info>> {
+        self.get_info()
+    }
+}
+
+impl<P: OAuthProvider> OAuthRefresh for RefreshExecuteBuilder<P> {
+    fn with_refresh_token(token: impl Into<String>) -> Self {
+        RefreshExecuteBuilder {
+            client_id: None,
+            client_secret: None,
+            from_env: true,
+            refresh_token: Zeroizing::new(token.into()),
+            provider: PhantomData,
+        }
+    }
+
+    fn execute(self) -> WrappedFuture<Result<TokenResponse>> {
+        self.refresh()
+    }
+}
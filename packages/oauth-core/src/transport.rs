@@ -0,0 +1,95 @@
+//! Outbound HTTP for token, device code and user info requests
+//!
+//! Requests are built with a shared `reqwest::Client` and sent through a
+//! process-wide [`HttpTransport`]. The default transport sends them with that
+//! same client; applications with their own retrying, instrumented client
+//! install it once at startup with [`set_transport`].
+
+use crate::Result;
+use reqwest::{Request, RequestBuilder, Response};
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{Arc, OnceLock};
+
+/// Future returned by [`HttpTransport::execute`]
+pub type TransportFuture<'a> = Pin<Box<dyn Future<Output = Result<Response>> + Send + 'a>>;
+
+/// Sends the requests this crate builds
+pub trait HttpTransport: Send + Sync {
+    /// Send a request and return the response, whatever its status
+    ///
+    /// Status handling stays with the caller, which reads provider error
+    /// bodies. Failures to get a response at all should be reported as
+    /// [`OAuthError::Transport`](crate::OAuthError::Transport).
+    fn execute(&self, request: Request) -> TransportFuture<'_>;
+}
+
+/// Sends requests with the shared client, as-is
+struct ReqwestTransport;
+
+impl HttpTransport for ReqwestTransport {
+    fn execute(&self, request: Request) -> TransportFuture<'_> {
+        Box::pin(async move { Ok(client().execute(request).await?) })
+    }
+}
+
+static CLIENT: OnceLock<reqwest::Client> = OnceLock::new();
+static TRANSPORT: OnceLock<Arc<dyn HttpTransport>> = OnceLock::new();
+
+/// Route this crate's requests through `transport`
+///
+/// Only the first call takes effect, and only before the first request.
+///
+/// # Returns
+/// * `true` - `transport` is now in use
+/// * `false` - A transport was already in use
+pub fn set_transport(transport: Arc<dyn HttpTransport>) -> bool {
+    TRANSPORT.set(transport).is_ok()
+}
+
+/// Client requests are built with
+pub(crate) fn client() -> &'static reqwest::Client {
+    CLIENT.get_or_init(reqwest::Client::new)
+}
+
+/// Build a request and send it through the installed transport
+pub(crate) async fn send(request: RequestBuilder) -> Result<Response> {
+    let request = request.build()?;
+    TRANSPORT
+        .get_or_init(|| Arc::new(ReqwestTransport))
+        .execute(request)
+        .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::OAuthError;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    struct Counting(AtomicUsize);
+
+    impl HttpTransport for Counting {
+        fn execute(&self, request: Request) -> TransportFuture<'_> {
+            Box::pin(async move {
+                self.0.fetch_add(1, Ordering::SeqCst);
+                Err(OAuthError::Transport(format!("refused {}", request.url())))
+            })
+        }
+    }
+
+    // The only test in this binary that sends requests, so installing a
+    // process-wide transport here affects no other test
+    #[tokio::test]
+    async fn test_requests_go_through_the_installed_transport() {
+        let transport = Arc::new(Counting(AtomicUsize::new(0)));
+        assert!(set_transport(transport.clone()));
+        assert!(!set_transport(Arc::new(ReqwestTransport)));
+
+        let error = send(client().get("https://example.invalid/userinfo"))
+            .await
+            .unwrap_err();
+        assert!(matches!(error, OAuthError::Transport(_)));
+        assert_eq!(transport.0.load(Ordering::SeqCst), 1);
+    }
+}
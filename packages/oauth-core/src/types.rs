@@ -0,0 +1,150 @@
+use serde::{Deserialize, Serialize};
+use zeroize::Zeroizing;
+
+/// Token endpoint response for the authorization code grant
+///
+/// `expires_in` is optional per RFC 6749 §5.1; providers such as GitHub omit
+/// it for non-expiring tokens.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct OAuthResponse {
+    #[serde(deserialize_with = "deserialize_zeroizing")]
+    #[serde(serialize_with = "serialize_zeroizing")]
+    pub access_token: Zeroizing<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expires_in: Option<u64>,
+    #[serde(default)]
+    pub scope: String,
+    pub token_type: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[serde(deserialize_with = "deserialize_option_zeroizing")]
+    #[serde(serialize_with = "serialize_option_zeroizing")]
+    pub refresh_token: Option<Zeroizing<String>>,
+}
+
+impl std::fmt::Debug for OAuthResponse {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("OAuthResponse")
+            .field("access_token", &"[REDACTED]")
+            .field("expires_in", &self.expires_in)
+            .field("scope", &self.scope)
+            .field("token_type", &self.token_type)
+            .field("refresh_token", &match &self.refresh_token {
+                Some(_) => &Some("[REDACTED]"),
+                None => &None::<&str>,
+            })
+            .finish()
+    }
+}
+
+/// Token endpoint response for the refresh token grant
+#[derive(Clone, Serialize, Deserialize)]
+pub struct TokenResponse {
+    #[serde(deserialize_with = "deserialize_zeroizing")]
+    #[serde(serialize_with = "serialize_zeroizing")]
+    pub access_token: Zeroizing<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expires_in: Option<u64>,
+    #[serde(default)]
+    pub scope: String,
+    pub token_type: String,
+}
+
+impl std::fmt::Debug for TokenResponse {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TokenResponse")
+            .field("access_token", &"[REDACTED]")
+            .field("expires_in", &self.expires_in)
+            .field("scope", &self.scope)
+            .field("token_type", &self.token_type)
+            .finish()
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub enum AccessType {
+    Online,
+    Offline,
+}
+
+impl AccessType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AccessType::Online => "online",
+            AccessType::Offline => "offline",
+        }
+    }
+}
+
+/// Callback handler for OAuth flow
+#[derive(Debug)]
+pub enum CallbackMode {
+    /// HTTP server mode - starts a loopback server on the specified port,
+    /// or on an ephemeral one when the port is 0
+    Server { port: u16 },
+    /// RFC 8628 device authorization - the user approves the login on any
+    /// device and the token endpoint is polled, so no local browser or
+    /// loopback port is needed (SSH sessions, containers)
+    Device,
+}
+
+impl CallbackMode {
+    /// Create a server-based callback mode
+    pub fn server(port: u16) -> Self {
+        CallbackMode::Server { port }
+    }
+}
+
+/// What the user needs to approve a device authorization login
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DeviceCode {
+    /// Code the user enters at the verification URL
+    pub user_code: String,
+    /// Page where the user enters the code
+    pub verification_uri: String,
+    /// Verification URL with the code filled in, if the provider offers one
+    pub verification_uri_complete: Option<String>,
+    /// Seconds until the code expires
+    pub expires_in: u64,
+}
+
+/// Receives the [`DeviceCode`] to show while the login is polled
+pub type DeviceCodeHandler = Box<dyn FnOnce(&DeviceCode) + Send>;
+
+// Custom serde functions for Zeroizing<String> fields
+fn deserialize_zeroizing<'de, D>(deserializer: D) -> Result<Zeroizing<String>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    let s = String::deserialize(deserializer)?;
+    Ok(Zeroizing::new(s))
+}
+
+fn serialize_zeroizing<S>(value: &Zeroizing<String>, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: serde::Serializer,
+{
+    value.as_str().serialize(serializer)
+}
+
+fn deserialize_option_zeroizing<'de, D>(
+    deserializer: D,
+) -> Result<Option<Zeroizing<String>>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    let opt = Option::<String>::deserialize(deserializer)?;
+    Ok(opt.map(Zeroizing::new))
+}
+
+fn serialize_option_zeroizing<S>(
+    value: &Option<Zeroizing<String>>,
+    serializer: S,
+) -> Result<S::Ok, S::Error>
+where
+    S: serde::Serializer,
+{
+    match value {
+        Some(v) => Some(v.as_str()).serialize(serializer),
+        None => None::<&str>.serialize(serializer),
+    }
+}
@@ -1,38 +1,50 @@
 use crate::{
-    error::OAuthError, future::WrappedFuture, types::UserInfo as UserInfoResponse, Result,
+    error::OAuthError,
+    future::WrappedFuture,
+    provider::{OAuthProvider, USER_AGENT},
+    transport, Result,
 };
+use std::marker::PhantomData;
 use zeroize::Zeroizing;
 
-pub struct UserInfo;
+pub struct UserInfo<P> {
+    provider: PhantomData<fn() -> P>,
+}
 
-impl UserInfo {
+impl<P: OAuthProvider> UserInfo<P> {
     #[doc(hidden)]
     pub fn new() -> Self {
-        Self
+        Self {
+            provider: PhantomData,
+        }
     }
 
-    pub fn token(access_token: impl Into<String>) -> UserInfoExecuteBuilder {
+    pub fn token(access_token: impl Into<String>) -> UserInfoExecuteBuilder<P> {
         UserInfoExecuteBuilder {
             access_token: Zeroizing::new(access_token.into()),
+            provider: PhantomData,
         }
     }
 }
 
-pub struct UserInfoExecuteBuilder {
-    access_token: Zeroizing<String>,
+pub struct UserInfoExecuteBuilder<P> {
+    pub(crate) access_token: Zeroizing<String>,
+    pub(crate) provider: PhantomData<fn() -> P>,
 }
 
-impl UserInfoExecuteBuilder {
-    pub fn get_info(self) -> WrappedFuture<Result<UserInfoResponse>> {
+impl<P: OAuthProvider> UserInfoExecuteBuilder<P> {
+    pub fn get_info(self) -> WrappedFuture<Result<P::UserInfo>> {
         WrappedFuture::new(async move {
-            let client = reqwest::Client::new();
-            let url = "https://www.googleapis.com/oauth2/v2/userinfo";
-
-            let response = client
-                .get(url)
-                .header("Authorization", format!("Bearer {}", &*self.access_token))
-                .send()
-                .await?;
+            let response = transport::send(
+                transport::client()
+                    .get(P::user_info_endpoint())
+                    .header(
+                        "Authorization",
+                        format!("Bearer {}", self.access_token.as_str()),
+                    )
+                    .header("User-Agent", USER_AGENT),
+            )
+            .await?;
 
             if !response.status().is_success() {
                 if response.status() == 401 {
@@ -47,7 +59,7 @@ impl UserInfoExecuteBuilder {
                 return Err(OAuthError::Authorization(sanitized_error));
             }
 
-            let user_info: UserInfoResponse = response.json().await?;
+            let user_info: P::UserInfo = response.json().await?;
             Ok(user_info)
         })
     }
@@ -91,7 +103,7 @@ fn sanitize_api_error(error_text: &str, status_code: u16) -> String {
         401 => "Unauthorized - invalid or expired access token".to_string(),
         403 => "Forbidden - insufficient permissions".to_string(),
         429 => "Rate limit exceeded - please try again later".to_string(),
-        500..=599 => "Google API temporarily unavailable".to_string(),
+        500..=599 => "User info service temporarily unavailable".to_string(),
         _ => "User info request failed".to_string(),
     }
 }
@@ -0,0 +1,232 @@
+//! RFC 8628 device authorization against the scripted mock server
+
+use super::mock_server::{self, Recorded};
+use oauth_core::{DeviceCode, Login, OAuthError, OAuthProvider};
+use serde_json::json;
+use std::sync::mpsc;
+use std::time::Duration;
+
+struct MockProvider;
+
+impl OAuthProvider for MockProvider {
+    type UserInfo = serde_json::Value;
+
+    fn provider_name() -> &'static str {
+        "Mock"
+    }
+
+    fn default_scopes() -> Vec<&'static str> {
+        vec!["profile"]
+    }
+
+    fn auth_endpoint() -> &'static str {
+        "http://127.0.0.1:9/authorize"
+    }
+
+    fn token_endpoint() -> &'static str {
+        &mock_server::server().token_url
+    }
+
+    fn user_info_endpoint() -> &'static str {
+        "http://127.0.0.1:9/userinfo"
+    }
+
+    fn device_authorization_endpoint() -> Option<&'static str> {
+        Some(&mock_server::server().device_url)
+    }
+
+    fn client_id_env() -> &'static str {
+        "MOCK_CLIENT_ID"
+    }
+
+    fn client_secret_env() -> &'static str {
+        "MOCK_CLIENT_SECRET"
+    }
+}
+
+/// A provider with only the loopback flow
+struct LoopbackOnly;
+
+impl OAuthProvider for LoopbackOnly {
+    type UserInfo = serde_json::Value;
+
+    fn provider_name() -> &'static str {
+        "LoopbackOnly"
+    }
+
+    fn default_scopes() -> Vec<&'static str> {
+        vec![]
+    }
+
+    fn auth_endpoint() -> &'static str {
+        "http://127.0.0.1:9/authorize"
+    }
+
+    fn token_endpoint() -> &'static str {
+        "http://127.0.0.1:9/token"
+    }
+
+    fn user_info_endpoint() -> &'static str {
+        "http://127.0.0.1:9/userinfo"
+    }
+
+    fn client_id_env() -> &'static str {
+        "LOOPBACK_CLIENT_ID"
+    }
+
+    fn client_secret_env() -> &'static str {
+        "LOOPBACK_CLIENT_SECRET"
+    }
+}
+
+fn device_response(interval: u64, expires_in: u64) -> (u16, serde_json::Value) {
+    (
+        200,
+        // Google's field name, to cover the `verification_url` alias
+        json!({
+            "device_code": "dev-123",
+            "user_code": "WDJB-MJHT",
+            "verification_url": "https://example.com/device",
+            "expires_in": expires_in,
+            "interval": interval,
+        }),
+    )
+}
+
+fn paths(requests: &[Recorded]) -> Vec<&str> {
+    requests.iter().map(|request| request.path.as_str()).collect()
+}
+
+async fn device_login(
+    client_id: &str,
+    client_secret: &str,
+) -> (oauth_core::Result<oauth_core::OAuthResponse>, Option<DeviceCode>) {
+    let (tx, rx) = mpsc::channel();
+    let result = Login::<MockProvider>::client_id(client_id)
+        .client_secret(client_secret)
+        .scopes(["profile", "email"])
+        .unwrap()
+        .device_code(move |code| tx.send(code.clone()).unwrap())
+        .timeout(Duration::from_secs(30))
+        .login()
+        .await;
+    (result, rx.try_recv().ok())
+}
+
+#[tokio::test]
+async fn test_device_login_polls_through_pending_and_slow_down() {
+    let server = mock_server::server();
+    server.script(
+        "slow-client",
+        device_response(1, 900),
+        vec![
+            // GitHub reports pending with a 200
+            (200, json!({ "error": "authorization_pending" })),
+            // Google reports slow_down with a 400; this one names the new interval
+            (400, json!({ "error": "slow_down", "interval": 2 })),
+            (
+                200,
+                json!({ "access_token": "tok-1", "token_type": "Bearer", "expires_in": 3599 }),
+            ),
+        ],
+    );
+
+    let (result, code) = device_login("slow-client", "shh").await;
+    let token = result.unwrap();
+    assert_eq!(token.access_token.as_str(), "tok-1");
+    assert_eq!(token.expires_in, Some(3599));
+
+    assert_eq!(
+        code,
+        Some(DeviceCode {
+            user_code: "WDJB-MJHT".to_string(),
+            verification_uri: "https://example.com/device".to_string(),
+            verification_uri_complete: None,
+            expires_in: 900,
+        })
+    );
+
+    let requests = server.requests("slow-client");
+    assert_eq!(paths(&requests), ["/device/code", "/token", "/token", "/token"]);
+    assert_eq!(requests[0].form["scope"], "profile email");
+    for poll in &requests[1..] {
+        assert_eq!(poll.form["device_code"], "dev-123");
+        assert_eq!(poll.form["client_secret"], "shh");
+        assert_eq!(
+            poll.form["grant_type"],
+            "urn:ietf:params:oauth:grant-type:device_code"
+        );
+    }
+
+    // One second between polls, two after slow_down
+    assert!(requests[2].at - requests[1].at >= Duration::from_secs(1));
+    assert!(requests[3].at - requests[2].at >= Duration::from_secs(2));
+}
+
+#[tokio::test]
+async fn test_device_login_denied_without_secret() {
+    let server = mock_server::server();
+    server.script(
+        "denied-client",
+        device_response(0, 900),
+        vec![(400, json!({ "error": "access_denied" }))],
+    );
+
+    let (result, code) = device_login("denied-client", "").await;
+    assert!(matches!(result, Err(OAuthError::AuthorizationDenied)));
+    assert!(code.is_some());
+
+    // Public clients don't send an empty secret
+    let requests = server.requests("denied-client");
+    assert_eq!(paths(&requests), ["/device/code", "/token"]);
+    assert!(!requests[1].form.contains_key("client_secret"));
+}
+
+#[tokio::test]
+async fn test_device_login_expired_token() {
+    let server = mock_server::server();
+    server.script(
+        "expired-client",
+        device_response(0, 900),
+        vec![
+            (400, json!({ "error": "authorization_pending" })),
+            (400, json!({ "error": "expired_token" })),
+        ],
+    );
+
+    let (result, _) = device_login("expired-client", "shh").await;
+    assert!(matches!(result, Err(OAuthError::DeviceCodeExpired)));
+    assert_eq!(server.requests("expired-client").len(), 3);
+}
+
+#[tokio::test]
+async fn test_device_login_stops_polling_after_expiry() {
+    let server = mock_server::server();
+    server.script("lapsed-client", device_response(0, 0), vec![]);
+
+    let (result, _) = device_login("lapsed-client", "shh").await;
+    assert!(matches!(result, Err(OAuthError::DeviceCodeExpired)));
+    assert_eq!(paths(&server.requests("lapsed-client")), ["/device/code"]);
+}
+
+#[tokio::test]
+async fn test_device_login_rejected_client() {
+    let (result, code) = device_login("unknown-client", "shh").await;
+    assert!(matches!(result, Err(OAuthError::Authorization(_))));
+    assert!(code.is_none());
+}
+
+#[tokio::test]
+async fn test_device_login_unsupported_provider() {
+    let result = Login::<LoopbackOnly>::client_id("id")
+        .client_secret("secret")
+        .scopes(["profile"])
+        .unwrap()
+        .device_code(|_| panic!("no code without a device endpoint"))
+        .login()
+        .await;
+    assert!(matches!(
+        result,
+        Err(OAuthError::DeviceFlowUnsupported("LoopbackOnly"))
+    ));
+}
@@ -0,0 +1,171 @@
+//! Scripted OAuth authorization server on a loopback port
+//!
+//! One server is shared by every test in the binary. Each test scripts the
+//! responses for its own `client_id`, so tests can run in parallel, and reads
+//! back the requests that client made with the time each one arrived.
+
+use serde_json::Value;
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::Instant;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+
+/// A request received by the mock server
+#[derive(Debug, Clone)]
+pub struct Recorded {
+    pub path: String,
+    pub form: HashMap<String, String>,
+    pub at: Instant,
+}
+
+#[derive(Default)]
+struct Script {
+    device: Option<(u16, Value)>,
+    polls: VecDeque<(u16, Value)>,
+    requests: Vec<Recorded>,
+}
+
+type Scripts = Arc<Mutex<HashMap<String, Script>>>;
+
+pub struct MockAuthServer {
+    pub device_url: String,
+    pub token_url: String,
+    scripts: Scripts,
+}
+
+impl MockAuthServer {
+    /// Script the device authorization response and the token poll responses
+    /// for `client_id`
+    pub fn script(&self, client_id: &str, device: (u16, Value), polls: Vec<(u16, Value)>) {
+        self.scripts.lock().unwrap().insert(
+            client_id.to_string(),
+            Script {
+                device: Some(device),
+                polls: polls.into(),
+                requests: Vec::new(),
+            },
+        );
+    }
+
+    /// Requests made so far by `client_id`, in arrival order
+    pub fn requests(&self, client_id: &str) -> Vec<Recorded> {
+        self.scripts
+            .lock()
+            .unwrap()
+            .get(client_id)
+            .map(|script| script.requests.clone())
+            .unwrap_or_default()
+    }
+}
+
+/// The shared server, started on first use
+pub fn server() -> &'static MockAuthServer {
+    static SERVER: OnceLock<MockAuthServer> = OnceLock::new();
+    SERVER.get_or_init(|| {
+        let scripts = Scripts::default();
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        listener.set_nonblocking(true).unwrap();
+        let base = format!("http://{}", listener.local_addr().unwrap());
+
+        let shared = scripts.clone();
+        // Own thread and runtime: each test's runtime ends with the test
+        std::thread::spawn(move || {
+            let runtime = tokio::runtime::Builder::new_current_thread()
+                .enable_all()
+                .build()
+                .unwrap();
+            runtime.block_on(async move {
+                let listener = TcpListener::from_std(listener).unwrap();
+                loop {
+                    let (stream, _) = listener.accept().await.unwrap();
+                    tokio::spawn(handle(stream, shared.clone()));
+                }
+            });
+        });
+
+        MockAuthServer {
+            device_url: format!("{}/device/code", base),
+            token_url: format!("{}/token", base),
+            scripts,
+        }
+    })
+}
+
+async fn handle(mut stream: TcpStream, scripts: Scripts) {
+    let Some((path, body)) = read_request(&mut stream).await else {
+        return;
+    };
+    let form: HashMap<String, String> = url::form_urlencoded::parse(body.as_bytes())
+        .into_owned()
+        .collect();
+
+    let (status, response) = {
+        let mut scripts = scripts.lock().unwrap();
+        let script = form
+            .get("client_id")
+            .and_then(|client_id| scripts.get_mut(client_id));
+        match script {
+            Some(script) => {
+                script.requests.push(Recorded {
+                    path: path.clone(),
+                    form: form.clone(),
+                    at: Instant::now(),
+                });
+                let next = match path.as_str() {
+                    "/device/code" => script.device.take(),
+                    "/token" => script.polls.pop_front(),
+                    _ => None,
+                };
+                next.unwrap_or((404, serde_json::json!({ "error": "not_scripted" })))
+            }
+            None => (400, serde_json::json!({ "error": "invalid_client" })),
+        }
+    };
+
+    let body = response.to_string();
+    let reply = format!(
+        "HTTP/1.1 {} Mock\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status,
+        body.len(),
+        body
+    );
+    let _ = stream.write_all(reply.as_bytes()).await;
+    let _ = stream.shutdown().await;
+}
+
+/// Read one request and return its path and body
+async fn read_request(stream: &mut TcpStream) -> Option<(String, String)> {
+    let mut buffer = Vec::new();
+    let mut chunk = [0u8; 1024];
+    let header_end = loop {
+        let n = stream.read(&mut chunk).await.ok()?;
+        if n == 0 {
+            return None;
+        }
+        buffer.extend_from_slice(&chunk[..n]);
+        if let Some(end) = buffer.windows(4).position(|window| window == b"\r\n\r\n") {
+            break end + 4;
+        }
+    };
+
+    let head = String::from_utf8_lossy(&buffer[..header_end]).to_string();
+    let path = head.split_whitespace().nth(1)?.to_string();
+    let length = head
+        .lines()
+        .filter_map(|line| line.split_once(':'))
+        .find(|(name, _)| name.eq_ignore_ascii_case("content-length"))
+        .and_then(|(_, value)| value.trim().parse::<usize>().ok())
+        .unwrap_or(0);
+
+    while buffer.len() < header_end + length {
+        let n = stream.read(&mut chunk).await.ok()?;
+        if n == 0 {
+            return None;
+        }
+        buffer.extend_from_slice(&chunk[..n]);
+    }
+
+    let body = String::from_utf8_lossy(&buffer[header_end..header_end + length]).to_string();
+    Some((path, body))
+}
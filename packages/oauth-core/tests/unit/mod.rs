@@ -0,0 +1,2 @@
+pub mod device_flow;
+pub mod mock_server;
@@ -19,6 +19,13 @@ pub fn run() -> Result<(), Box<dyn std::error::Error>> {
         .init();
     
     log::info!("CYRUP Chat starting...");
+
+    // Select profile before anything touches the database, settings or vault
+    let profile = crate::config::profile::Profile::resolve(std::env::args())?;
+    crate::config::profile::init_active_profile(profile);
+
+    // All outbound HTTP, including the OAuth packages', uses the shared client
+    crate::environment::native::http_client::install_oauth_transport();
     
     // Initialize i18n system
     crate::i18n::init_i18n();
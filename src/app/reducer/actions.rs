@@ -9,14 +9,23 @@ pub enum AppAction {
     // Environment lifecycle
     InitializeEnvironment,
     EnvironmentReady(Result<Environment, String>),
+    /// Drop the environment so the database can be closed before a relaunch
+    ReleaseEnvironment,
 
     // Authentication lifecycle
     CheckStoredAuth,
     AuthCheckComplete(Option<AuthState>),
     LoginRequested(Provider, OAuthCallbacks),
     LoginComplete(Result<AuthState, String>),
+    /// Show the login view to sign in another account, keeping this one
+    AddAccountRequested,
+    SwitchAccount(AuthState),
+    /// The refresh scheduler found this account's refresh token revoked
+    ReauthRequired(AuthState),
+    /// Sign out the active account
     LogoutRequested,
-    LogoutComplete(Result<(), String>),
+    /// The account active after signing out, if any other is signed in
+    LogoutComplete(Result<Option<AuthState>, String>),
 
     // Error handling
     ClearError,
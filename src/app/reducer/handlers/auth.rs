@@ -1,9 +1,10 @@
 //! Authentication action handlers
 
 use crate::app::OAuthCallbacks;
-use crate::app::reducer::state::AppState;
+use crate::app::reducer::state::{AppState, AppStatus};
 use crate::app::views::AuthStatus; // ← REUSE existing type
-use crate::auth::{self, AuthState, Provider};
+use crate::auth::{self, AuthState, Provider, SignOutResult};
+use crate::view_model::auth_event::AuthEventKind;
 use dioxus::prelude::*;
 
 pub fn handle_check_stored(mut signal: Signal<AppState>) -> Result<(), String> {
@@ -20,16 +21,23 @@ pub fn handle_check_stored(mut signal: Signal<AppState>) -> Result<(), String> {
 }
 
 async fn check_stored_auth() -> Option<AuthState> {
-    // Note: Auth check now handled by auth::Vault - stored auth moved to secure keyring
-    // User metadata is in Settings, but tokens are in Vault
-    // For now, return None to require fresh login
-    None
+    // Resume as the account that was active last time (tokens stay in the vault)
+    match AuthState::load_from_vault().await {
+        Ok(auth_state) => auth_state,
+        Err(e) => {
+            log::warn!("Could not load stored accounts: {}", e);
+            None
+        }
+    }
 }
 
 pub fn handle_check_complete(
     mut signal: Signal<AppState>,
     auth_state: Option<AuthState>,
 ) -> Result<(), String> {
+    if let Some(auth) = &auth_state {
+        adopt_legacy_user_data(signal, auth);
+    }
     signal.with_mut(|state| {
         state.auth_status = match auth_state {
             Some(auth) => AuthStatus::Authenticated(auth),
@@ -62,6 +70,13 @@ pub fn handle_login_complete(
     mut signal: Signal<AppState>,
     result: Result<AuthState, String>,
 ) -> Result<(), String> {
+    if let Ok(auth_state) = &result {
+        auth::accounts::set_active_account(Some(auth_state.clone()));
+        adopt_legacy_user_data(signal, auth_state);
+        record_sign_in(signal, auth_state);
+        // Pick up the new account's token expiry
+        auth::refresh::reschedule();
+    }
     signal.with_mut(|state| match result {
         Ok(auth_state) => {
             state.auth_status = AuthStatus::Authenticated(auth_state);
@@ -74,27 +89,152 @@ pub fn handle_login_complete(
     Ok(())
 }
 
+pub fn handle_add_account_requested(mut signal: Signal<AppState>) -> Result<(), String> {
+    // Signed-in accounts stay in the vault; the login view adds one more
+    signal.with_mut(|state| {
+        state.auth_status = AuthStatus::NotAuthenticated;
+    });
+    Ok(())
+}
+
+pub fn handle_switch_account(
+    mut signal: Signal<AppState>,
+    auth_state: AuthState,
+) -> Result<(), String> {
+    // Loading unmounts the main view so nothing keeps the previous account's data
+    signal.with_mut(|state| {
+        state.auth_status = AuthStatus::Loading;
+    });
+
+    spawn(async move {
+        let result = auth::accounts::switch_account(&auth_state).await;
+        signal.with_mut(|state| match result {
+            Ok(()) => {
+                log::info!("Switched to account {}", auth_state.account_key());
+                state.auth_status = AuthStatus::Authenticated(auth_state);
+                state.error = None;
+            }
+            Err(e) => {
+                state.auth_status = AuthStatus::Error(format!("Could not switch account: {e}"));
+            }
+        });
+    });
+
+    Ok(())
+}
+
+pub fn handle_reauth_required(
+    mut signal: Signal<AppState>,
+    account: AuthState,
+) -> Result<(), String> {
+    // Other accounts just drop out of the switcher; only the active one blocks
+    let is_active = matches!(
+        &signal.read().auth_status,
+        AuthStatus::Authenticated(active) if active.account_key() == account.account_key()
+    );
+    if !is_active {
+        log::info!("Signed-out account {} needs to sign in again", account.account_key());
+        return Ok(());
+    }
+
+    auth::accounts::set_active_account(None);
+    signal.with_mut(|state| {
+        state.auth_status = AuthStatus::ReauthRequired(account);
+    });
+    Ok(())
+}
+
+/// Hand data saved before accounts existed, or under this account's
+/// provider-local id, to this account
+fn adopt_legacy_user_data(signal: Signal<AppState>, auth_state: &AuthState) {
+    let database = match &signal.read().app_status {
+        AppStatus::EnvironmentReady(environment) => environment.database.clone(),
+        _ => return,
+    };
+    let user_id = auth_state.account_key();
+    let local_id = auth_state.user.id.clone();
+    spawn(async move {
+        if let Err(e) = database.adopt_legacy_user_data(&user_id, &local_id).await {
+            log::warn!("Failed to adopt legacy bookmarks and reactions: {}", e);
+        }
+    });
+}
+
+/// Audit a successful sign-in
+fn record_sign_in(signal: Signal<AppState>, auth_state: &AuthState) {
+    let database = match &signal.read().app_status {
+        AppStatus::EnvironmentReady(environment) => environment.database.clone(),
+        _ => return,
+    };
+    let user_id = auth_state.user.id.clone();
+    let provider = auth_state.provider.to_string().to_lowercase();
+    spawn(async move {
+        if let Err(e) = database
+            .record_auth_event(&user_id, &provider, AuthEventKind::SignIn, false, None)
+            .await
+        {
+            log::warn!("Failed to record sign-in: {}", e);
+        }
+    });
+}
+
 pub fn handle_logout_requested(mut signal: Signal<AppState>) -> Result<(), String> {
     signal.with_mut(|state| {
         state.auth_status = AuthStatus::Loading;
     });
 
     spawn(async move {
-        // REUSE existing logout function
-        let result = auth::logout().await.map_err(|e| e.to_string());
-        let _ = handle_logout_complete(signal, result);
+        let result = auth::accounts::sign_out_active()
+            .await
+            .map_err(|e| e.to_string());
+        if let Ok(signed_out) = &result {
+            end_signed_out_sessions(signal, signed_out).await;
+        }
+        let _ = handle_logout_complete(signal, result.map(|signed_out| signed_out.next));
     });
 
     Ok(())
 }
 
+/// Stop the signed-out account's agents and audit the sign-out
+async fn end_signed_out_sessions(signal: Signal<AppState>, signed_out: &SignOutResult) {
+    let Some(account) = &signed_out.account else {
+        return;
+    };
+    let environment = match &signal.read().app_status {
+        AppStatus::EnvironmentReady(environment) => environment.clone(),
+        _ => return,
+    };
+
+    if let Err(e) = environment.model.logout(account.account_key()).await {
+        log::warn!("Failed to end agent sessions of {}: {}", account.account_key(), e);
+    }
+    if let Err(e) = environment
+        .database
+        .record_auth_event(
+            &account.user.id,
+            &account.provider.to_string().to_lowercase(),
+            AuthEventKind::SignOut,
+            signed_out.tokens_revoked,
+            signed_out.revocation_error.clone(),
+        )
+        .await
+    {
+        log::warn!("Failed to record sign-out: {}", e);
+    }
+}
+
 pub fn handle_logout_complete(
     mut signal: Signal<AppState>,
-    result: Result<(), String>,
+    result: Result<Option<AuthState>, String>,
 ) -> Result<(), String> {
     signal.with_mut(|state| match result {
-        Ok(()) => {
-            state.auth_status = AuthStatus::NotAuthenticated;
+        Ok(next) => {
+            // Continue as the next signed-in account, if there is one
+            state.auth_status = match next {
+                Some(auth_state) => AuthStatus::Authenticated(auth_state),
+                None => AuthStatus::NotAuthenticated,
+            };
             state.error = None;
         }
         Err(error) => {
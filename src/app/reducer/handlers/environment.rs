@@ -1,11 +1,12 @@
 //! Environment initialization handlers
 
 use crate::app::reducer::state::{AppState, AppStatus};
-use crate::database::Database;
+use crate::database::{BackupConfig, Database};
 use crate::environment::{
     Environment,
     native::{Model, Settings},
 };
+use crate::services::shutdown;
 use dioxus::prelude::*;
 use std::sync::Arc;
 
@@ -33,6 +34,47 @@ async fn initialize_environment() -> Result<Environment, String> {
             format!("Schema error: {}", e)
         })?;
 
+    let backup_config = match database.data_path() {
+        Some(_) => Some(BackupConfig::new(
+            crate::config::profile::active_profile()?.backup_dir(),
+        )),
+        None => None,
+    };
+
+    // Repair application-level references (replies, sessions, participants, pins)
+    match database.check_integrity().await {
+        Ok(report) if !report.is_clean() => {
+            // Repairs rewrite rows, so file-backed databases are snapshotted first
+            let repaired = match &backup_config {
+                Some(config) => database.backup_and_repair(&report, config).await,
+                None => database.repair_integrity(&report).await,
+            };
+            if let Err(e) = repaired {
+                log::error!("Integrity repair failed: {}", e);
+            }
+        }
+        Ok(_) => {}
+        Err(e) => log::error!("Integrity check failed: {}", e),
+    }
+
+    // Daily compressed snapshots of this profile's database
+    if let Some(config) = backup_config {
+        shutdown::register_task(crate::database::spawn_backup_schedule(
+            Arc::clone(&database),
+            config,
+        ));
+    }
+
+    // Scheduled prompts (catches up runs missed while the app was closed)
+    shutdown::register_task(crate::services::scheduler::spawn_scheduler(Arc::clone(
+        &database,
+    )));
+
+    // Opt-in localhost API for editors and scripts
+    tokio::spawn(crate::services::api_server::start_if_enabled(Arc::clone(
+        &database,
+    )));
+
     let model = Model::new(Arc::clone(&database)).await.map_err(|e| {
         log::error!("Model creation failed: {}", e);
         format!("Model error: {}", e)
@@ -45,6 +87,12 @@ async fn initialize_environment() -> Result<Environment, String> {
     Ok(env)
 }
 
+/// Drop the environment (and its database handles) ahead of a relaunch
+pub fn handle_release(mut signal: Signal<AppState>) -> Result<(), String> {
+    signal.with_mut(|state| state.app_status = AppStatus::Initializing);
+    Ok(())
+}
+
 pub fn handle_ready(
     mut signal: Signal<AppState>,
     result: Result<Environment, String>,
@@ -18,6 +18,9 @@ pub fn handle_app_action(mut signal: Signal<AppState>, action: AppAction) -> Res
         AppAction::EnvironmentReady(result) => {
             environment::handle_ready(signal, result)?;
         }
+        AppAction::ReleaseEnvironment => {
+            environment::handle_release(signal)?;
+        }
         AppAction::CheckStoredAuth => {
             auth::handle_check_stored(signal)?;
         }
@@ -30,6 +33,15 @@ pub fn handle_app_action(mut signal: Signal<AppState>, action: AppAction) -> Res
         AppAction::LoginComplete(result) => {
             auth::handle_login_complete(signal, result)?;
         }
+        AppAction::AddAccountRequested => {
+            auth::handle_add_account_requested(signal)?;
+        }
+        AppAction::SwitchAccount(auth_state) => {
+            auth::handle_switch_account(signal, auth_state)?;
+        }
+        AppAction::ReauthRequired(account) => {
+            auth::handle_reauth_required(signal, account)?;
+        }
         AppAction::LogoutRequested => {
             auth::handle_logout_requested(signal)?;
         }
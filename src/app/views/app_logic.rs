@@ -1,6 +1,6 @@
 //! Main application logic with root reducer architecture
 
-use super::auth_components::{AuthStatus, ErrorView, LoadingView, LoginView};
+use super::auth_components::{AuthStatus, ErrorView, LoadingView, LoginView, ReauthView};
 use super::main_components::MainView;
 use crate::app::oauth::OAuthProvider;
 use crate::app::reducer::state::AppStatus;
@@ -33,6 +33,20 @@ pub fn App() -> Element {
         dispatch(AppAction::CheckStoredAuth);
     });
 
+    // Refresh every signed-in account's token ahead of expiry
+    use_future(move || async move {
+        crate::auth::refresh::run_refresh_scheduler(move |account| {
+            dispatch(AppAction::ReauthRequired(account));
+        })
+        .await;
+    });
+
+    // Let go of the database when a profile switch shuts the app down
+    use_future(move || async move {
+        crate::services::shutdown::release_requested().await;
+        dispatch(AppAction::ReleaseEnvironment);
+    });
+
     // Provide dispatcher via context
     use_context_provider(|| dispatch);
 
@@ -63,6 +77,9 @@ pub fn App() -> Element {
                     MainView { auth_state: auth_state.clone() }
                 }
             }
+            (AppStatus::EnvironmentReady(_), AuthStatus::ReauthRequired(account)) => {
+                rsx! { ReauthView { account: account.clone() } }
+            }
             (AppStatus::EnvironmentReady(_), AuthStatus::Error(error)) => {
                 rsx! { ErrorView { error: error.clone(), auth_status: auth_status_signal } }
             }
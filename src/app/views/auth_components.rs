@@ -13,6 +13,8 @@ pub enum AuthStatus {
     Loading,
     NotAuthenticated,
     Authenticated(AuthState),
+    /// The account's refresh token was revoked; it has to sign in again
+    ReauthRequired(AuthState),
     Error(String),
 }
 
@@ -130,3 +132,45 @@ pub fn ErrorView(error: String, auth_status: Signal<AuthStatus>) -> Element {
         }
     }
 }
+
+#[component]
+pub fn ReauthView(account: AuthState) -> Element {
+    let dispatch = use_context::<Callback<AppAction>>();
+    let provider = account.provider.name().to_string();
+
+    rsx! {
+        div {
+            class: "flex items-center justify-center h-screen bg-gradient-to-br from-[#1a1a2e]/80 via-[#16213e]/80 to-[#0f0f1e]/80 bg-[length:400%_400%] animate-[gradientShift_45s_ease-in-out_infinite] relative",
+            div {
+                class: "backdrop-blur-xl bg-black/20 p-16 rounded-3xl border border-white/10 w-[400px] text-center shadow-2xl relative z-[2]",
+                h1 {
+                    class: "text-3xl font-bold text-white mb-4",
+                    "Sign in again"
+                }
+                p {
+                    class: "text-[var(--g-secondaryLabelColor)] mb-6",
+                    "{provider} no longer accepts the saved sign-in for {account.user.email}. Sign in again to keep using this account."
+                }
+
+                div {
+                    class: "flex flex-col gap-3",
+                    button {
+                        class: "px-6 py-3 bg-[var(--g-accentColor)] text-white rounded-lg font-semibold cursor-pointer transition-all duration-200 hover:bg-[var(--g-accentColorHighlight)] hover:-translate-y-px active:translate-y-0",
+                        onclick: move |_| {
+                            dispatch(AppAction::AddAccountRequested);
+                        },
+                        "Sign in with {provider}"
+                    }
+                    button {
+                        class: "px-6 py-3 bg-white/5 border border-white/10 text-white/70 rounded-lg cursor-pointer transition-all duration-200 hover:bg-white/10 hover:text-white",
+                        onclick: move |_| {
+                            // Continue as another signed-in account, if any
+                            dispatch(AppAction::CheckStoredAuth);
+                        },
+                        "Not now"
+                    }
+                }
+            }
+        }
+    }
+}
@@ -17,6 +17,10 @@ enum ViewMode {
     Rooms,
     More,
     Templates,
+    Schedules,
+    McpServers,
+    ApiKeys,
+    Network,
 }
 
 #[component]
@@ -118,6 +122,18 @@ pub fn MainView(auth_state: AuthState) -> Element {
                     ViewMode::Templates => rsx! {
                         crate::components::template_manager::TemplateManagerComponent {}
                     },
+                    ViewMode::Schedules => rsx! {
+                        crate::components::schedule_manager::ScheduleManagerComponent {}
+                    },
+                    ViewMode::McpServers => rsx! {
+                        crate::components::mcp_server_manager::McpServerManagerComponent {}
+                    },
+                    ViewMode::ApiKeys => rsx! {
+                        crate::components::api_key_manager::ApiKeyManagerComponent {}
+                    },
+                    ViewMode::Network => rsx! {
+                        crate::components::http_diagnostics::HttpDiagnosticsComponent {}
+                    },
                 }
             }
         }
@@ -145,9 +161,12 @@ pub fn ChatHistorySidebar(auth_state: AuthState) -> Element {
 
     // Load recent conversations from database (limit=10 for sidebar)
     // Full conversation list available in Timeline view
+    // Scoped to the signed-in account (plus conversations no account owns)
+    let user_id = auth_state.account_key();
     let conversations = use_resource(move || {
         let database = environment.database.clone();
-        async move { database.list_recent_conversations(10).await }
+        let user_id = user_id.clone();
+        async move { database.list_recent_conversations(&user_id, 10).await }
     });
 
     // Calculate total unread count across all conversations
@@ -210,10 +229,13 @@ pub fn ChatHistorySidebar(auth_state: AuthState) -> Element {
                 button {
                     class: "px-3 py-1 bg-white/5 border border-white/10 rounded text-white/70 text-[0.85em] cursor-pointer transition-all duration-200 hover:bg-white/10 hover:border-white/20 hover:text-white",
                     onclick: handle_logout,
-                    "Logout"
+                    "Sign out"
                 }
             }
 
+            // Other signed-in accounts
+            AccountSwitcher { auth_state: auth_state.clone() }
+
             // New conversation button
             div {
                 class: "p-4 border-b border-white/5",
@@ -308,6 +330,89 @@ pub fn ChatHistorySidebar(auth_state: AuthState) -> Element {
     }
 }
 
+/// Switch between signed-in accounts or add another one
+///
+/// Lists every account in the vault except the active one. Switching goes
+/// through the reducer, which remounts the main view for the new account.
+#[component]
+fn AccountSwitcher(auth_state: AuthState) -> Element {
+    let dispatch = use_context::<Callback<AppAction>>();
+    let mut expanded = use_signal(|| false);
+
+    let accounts = use_resource(move || async move {
+        crate::auth::accounts::list_accounts()
+            .await
+            .unwrap_or_else(|e| {
+                log::error!("[Accounts] Failed to list accounts: {}", e);
+                Vec::new()
+            })
+    });
+
+    let active_key = auth_state.account_key();
+    let others: Vec<AuthState> = accounts
+        .read()
+        .as_ref()
+        .map(|accounts| {
+            accounts
+                .iter()
+                .filter(|account| account.account_key() != active_key)
+                .cloned()
+                .collect()
+        })
+        .unwrap_or_default();
+
+    let toggle_label = if others.is_empty() {
+        "Accounts".to_string()
+    } else {
+        format!("Accounts ({})", others.len() + 1)
+    };
+
+    rsx! {
+        div {
+            class: "px-5 py-2 border-b border-white/5",
+            button {
+                class: "w-full text-left text-[0.8em] text-[var(--g-secondaryLabelColor)] cursor-pointer hover:text-white",
+                onclick: move |_| {
+                    let open = *expanded.read();
+                    expanded.set(!open);
+                },
+                if *expanded.read() { "▾ {toggle_label}" } else { "▸ {toggle_label}" }
+            }
+            if *expanded.read() {
+                div {
+                    class: "mt-2 space-y-1",
+                    {others.into_iter().map(|account| {
+                        let key = account.account_key();
+                        let provider = account.provider.name().to_string();
+                        let name = account.user.name.clone();
+                        let email = account.user.email.clone();
+                        rsx! {
+                            button {
+                                key: "{key}",
+                                class: "w-full px-3 py-2 rounded-lg text-left cursor-pointer transition-all duration-200 hover:bg-white/8",
+                                onclick: move |_| {
+                                    expanded.set(false);
+                                    dispatch(AppAction::SwitchAccount(account.clone()));
+                                },
+                                div { class: "text-[0.85em] text-[var(--g-labelColor)] whitespace-nowrap overflow-hidden text-ellipsis", "{name}" }
+                                div { class: "text-[0.75em] text-[var(--g-secondaryLabelColor)] whitespace-nowrap overflow-hidden text-ellipsis", "{email} · {provider}" }
+                            }
+                        }
+                    })}
+                    button {
+                        class: "w-full px-3 py-2 rounded-lg text-left text-[0.85em] text-[#00a8ff] cursor-pointer transition-all duration-200 hover:bg-white/8",
+                        onclick: move |_| {
+                            expanded.set(false);
+                            dispatch(AppAction::AddAccountRequested);
+                        },
+                        "+ Add account"
+                    }
+                }
+            }
+        }
+    }
+}
+
 /// Helper function for date formatting
 fn format_timestamp(timestamp: &DateTime<Utc>) -> String {
     let local_time = timestamp.with_timezone(&Local);
@@ -355,6 +460,7 @@ async fn create_new_conversation(
         last_summarized_message_id: None,
         last_message_at: now.into(),
         created_at: now.into(),
+        user_id: crate::auth::active_user_id(),
     };
 
     let created_id = db.create_conversation(&conversation).await?;
@@ -376,8 +482,8 @@ fn NavigationSection() -> Element {
     let bookmark_count = use_resource(move || {
         let database = environment.database.clone();
         async move {
-            let user_id = "hardcoded-david-maple";
-            match database.get_bookmarked_messages(user_id).await {
+            let user_id = crate::auth::active_user_id()?;
+            match database.get_bookmarked_messages(&user_id).await {
                 Ok(messages) => Some(messages.len()),
                 Err(e) => {
                     log::error!("[Navigation] Failed to load bookmark count: {}", e);
@@ -436,6 +542,10 @@ fn NavigationSection() -> Element {
             {create_button("Bookmarks", crate::icons::ICON_BOOKMARK2, ViewMode::Bookmarks, bookmark_count_val)}
             {create_button("More", crate::icons::ICON_MORE, ViewMode::More, None)}
             {create_button("Templates", crate::icons::ICON_OPTIONS, ViewMode::Templates, None)}
+            {create_button("Schedules", crate::icons::ICON_TIME, ViewMode::Schedules, None)}
+            {create_button("MCP Servers", "🔌", ViewMode::McpServers, None)}
+            {create_button("API Keys", "🔑", ViewMode::ApiKeys, None)}
+            {create_button("Network", "📶", ViewMode::Network, None)}
         }
     }
 }
@@ -0,0 +1,68 @@
+//! Signed-in accounts and the active account
+//!
+//! Any number of accounts can be signed in at once, including several with
+//! the same provider. Each account's credentials live in the vault under its
+//! account key (`google:1234`, see [`AuthState::account_key`]). One of them
+//! is active: its account key scopes conversations, bookmarks and reactions,
+//! since provider-local user ids can collide across providers.
+
+use crate::auth::vault_auth_manager::SignOutResult;
+use crate::auth::{AuthState, VaultAuthManager};
+use anyhow::Result;
+use std::sync::RwLock;
+
+/// Account the app is currently acting as
+static ACTIVE_ACCOUNT: RwLock<Option<AuthState>> = RwLock::new(None);
+
+/// The active account, if anyone is signed in
+pub fn active_account() -> Option<AuthState> {
+    ACTIVE_ACCOUNT
+        .read()
+        .ok()
+        .and_then(|account| account.clone())
+}
+
+/// Account key of the active account (`google:1234`), used to scope
+/// per-user data
+pub fn active_user_id() -> Option<String> {
+    active_account().map(|account| account.account_key())
+}
+
+/// Set the in-memory active account (the vault copy is kept by
+/// [`VaultAuthManager::switch_account`])
+pub(crate) fn set_active_account(account: Option<AuthState>) {
+    if let Ok(mut active) = ACTIVE_ACCOUNT.write() {
+        *active = account;
+    }
+}
+
+/// All signed-in accounts
+pub async fn list_accounts() -> Result<Vec<AuthState>> {
+    VaultAuthManager::new().await?.list_accounts().await
+}
+
+/// Make an account the active one, in memory and in the vault
+pub async fn switch_account(account: &AuthState) -> Result<()> {
+    // The in-memory switch still applies if the keyring is unavailable
+    if let Err(e) = VaultAuthManager::new().await?.switch_account(account).await {
+        log::warn!("Could not remember the active account: {}", e);
+    }
+    set_active_account(Some(account.clone()));
+    Ok(())
+}
+
+/// Sign out the active account and switch to the next signed-in one
+///
+/// The result names the account that is active afterwards, if any is left.
+pub async fn sign_out_active() -> Result<SignOutResult> {
+    let manager = VaultAuthManager::new().await?;
+    let result = match active_account() {
+        Some(account) => manager.sign_out_account(&account).await?,
+        None => SignOutResult {
+            next: manager.get_current_user().await?,
+            ..Default::default()
+        },
+    };
+    set_active_account(result.next.clone());
+    Ok(result)
+}
@@ -0,0 +1,628 @@
+//! Storage backends for the credential vault
+//!
+//! [`CredentialVault`](crate::auth::CredentialVault) keeps every entry as a
+//! string under a string key in a [`CredentialStore`]. Two backends exist:
+//!
+//! - [`KeyringStore`]: the OS keyring (Keychain, Secret Service, Credential
+//!   Manager). Preferred whenever it persists entries.
+//! - [`EncryptedFileStore`]: one file encrypted at rest with XChaCha20-Poly1305
+//!   under an Argon2id key derived from a passphrase. Used on minimal sessions
+//!   with no keyring daemon.
+//!
+//! [`default_store`] picks the backend once per service name. Set
+//! `CYRUP_CREDENTIAL_STORE=keyring|file` to force one. The file store's
+//! passphrase always comes from the user: `CYRUP_VAULT_PASSPHRASE`, or the
+//! unlock prompt through [`unlock`]. Without one the store stays locked and
+//! every vault operation fails; the passphrase is never written to disk.
+
+use anyhow::{Context, Result};
+use argon2::Argon2;
+use base64::{Engine, engine::general_purpose::STANDARD as BASE64};
+use chacha20poly1305::aead::rand_core::RngCore;
+use chacha20poly1305::aead::{Aead, AeadCore, KeyInit, OsRng, Payload};
+use chacha20poly1305::{Key, XChaCha20Poly1305, XNonce};
+use keyring::Entry;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use zeroize::{Zeroize, Zeroizing};
+
+/// Environment variable forcing a backend: `keyring` or `file`
+pub const STORE_ENV: &str = "CYRUP_CREDENTIAL_STORE";
+
+/// Environment variable holding the encrypted file store's passphrase
+pub const PASSPHRASE_ENV: &str = "CYRUP_VAULT_PASSPHRASE";
+
+/// Current encrypted file format
+const FORMAT_VERSION: u32 = 1;
+
+/// Associated data binding ciphertexts to this format
+const ASSOCIATED_DATA: &[u8] = b"cyrup-credentials-v1";
+
+const SALT_LEN: usize = 16;
+const KEY_LEN: usize = 32;
+
+/// String key/value storage for credentials
+///
+/// Implementations are blocking; entries are small and operations rare.
+pub trait CredentialStore: Send + Sync {
+    /// Read an entry, `None` if it doesn't exist
+    fn get(&self, key: &str) -> Result<Option<String>>;
+
+    /// Create or overwrite an entry
+    fn set(&self, key: &str, value: &str) -> Result<()>;
+
+    /// Delete an entry; returns whether it existed
+    fn delete(&self, key: &str) -> Result<bool>;
+
+    /// Backend name for logs and health checks
+    fn backend(&self) -> &'static str;
+}
+
+/// Credentials in the OS keyring under one service name
+pub struct KeyringStore {
+    service_name: String,
+}
+
+impl KeyringStore {
+    pub fn new(service_name: &str) -> Self {
+        Self {
+            service_name: service_name.to_string(),
+        }
+    }
+
+    /// Whether the keyring keeps entries in this session
+    ///
+    /// Writing succeeds against keyring's in-memory mock backend too, so the
+    /// probe reads its value back through a fresh entry.
+    pub fn is_available(&self) -> bool {
+        let probe = format!("probe-{}", uuid::Uuid::new_v4());
+        let persisted = self.set("__store_probe__", &probe).is_ok()
+            && self.get("__store_probe__").ok().flatten().as_deref() == Some(probe.as_str());
+        let _ = self.delete("__store_probe__");
+        persisted
+    }
+
+    fn entry(&self, key: &str) -> Result<Entry> {
+        Entry::new(&self.service_name, key).context("Failed to create keyring entry")
+    }
+}
+
+impl CredentialStore for KeyringStore {
+    fn get(&self, key: &str) -> Result<Option<String>> {
+        match self.entry(key)?.get_password() {
+            Ok(value) => Ok(Some(value)),
+            Err(keyring::Error::NoEntry) => Ok(None),
+            Err(e) => Err(anyhow::anyhow!("Failed to read keyring entry: {}", e)),
+        }
+    }
+
+    fn set(&self, key: &str, value: &str) -> Result<()> {
+        self.entry(key)?
+            .set_password(value)
+            .context("Failed to write keyring entry")
+    }
+
+    fn delete(&self, key: &str) -> Result<bool> {
+        match self.entry(key)?.delete_credential() {
+            Ok(()) => Ok(true),
+            Err(keyring::Error::NoEntry) => Ok(false),
+            Err(e) => Err(anyhow::anyhow!("Failed to delete keyring entry: {}", e)),
+        }
+    }
+
+    fn backend(&self) -> &'static str {
+        "keyring"
+    }
+}
+
+/// On-disk envelope of the encrypted file store
+#[derive(Serialize, Deserialize)]
+struct Envelope {
+    version: u32,
+    salt: String,
+    nonce: String,
+    ciphertext: String,
+}
+
+/// Decrypted entries, wiped when dropped
+#[derive(Default, Serialize, Deserialize)]
+#[serde(transparent)]
+struct Entries(BTreeMap<String, String>);
+
+impl Drop for Entries {
+    fn drop(&mut self) {
+        self.0.values_mut().for_each(Zeroize::zeroize);
+    }
+}
+
+/// Credentials in a single file encrypted with a passphrase-derived key
+///
+/// Every write re-encrypts the whole file under a fresh nonce and replaces it
+/// atomically (write to a temporary file, sync, rename), so a crash leaves
+/// either the old or the new contents. Decrypted data and the key are
+/// zeroized once no longer needed.
+pub struct EncryptedFileStore {
+    path: PathBuf,
+    salt: [u8; SALT_LEN],
+    key: Zeroizing<[u8; KEY_LEN]>,
+    /// Serializes read-modify-write cycles within the process
+    lock: Mutex<()>,
+}
+
+impl EncryptedFileStore {
+    /// Open (or prepare to create) the store at `path`
+    ///
+    /// # Errors
+    /// Fails if an existing file can't be read or decrypted, which includes a
+    /// wrong passphrase.
+    pub fn open(path: impl Into<PathBuf>, passphrase: &[u8]) -> Result<Self> {
+        let path = path.into();
+        let salt = match read_envelope(&path)? {
+            Some(envelope) => {
+                decode_array(&envelope.salt).context("Corrupt credential file salt")?
+            }
+            None => {
+                let mut salt = [0u8; SALT_LEN];
+                OsRng.fill_bytes(&mut salt);
+                salt
+            }
+        };
+
+        let mut key = Zeroizing::new([0u8; KEY_LEN]);
+        Argon2::default()
+            .hash_password_into(passphrase, &salt, &mut key[..])
+            .map_err(|e| anyhow::anyhow!("Failed to derive credential file key: {}", e))?;
+
+        let store = Self {
+            path,
+            salt,
+            key,
+            lock: Mutex::new(()),
+        };
+        // Surface a wrong passphrase now rather than on first use
+        store.load()?;
+        Ok(store)
+    }
+
+    /// Path of the encrypted file
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    fn cipher(&self) -> XChaCha20Poly1305 {
+        XChaCha20Poly1305::new(Key::from_slice(&self.key[..]))
+    }
+
+    fn load(&self) -> Result<Entries> {
+        let Some(envelope) = read_envelope(&self.path)? else {
+            return Ok(Entries::default());
+        };
+        if envelope.version != FORMAT_VERSION {
+            anyhow::bail!("Unsupported credential file version {}", envelope.version);
+        }
+
+        let nonce: [u8; 24] =
+            decode_array(&envelope.nonce).context("Corrupt credential file nonce")?;
+        let ciphertext = BASE64
+            .decode(&envelope.ciphertext)
+            .context("Corrupt credential file contents")?;
+        let plaintext = Zeroizing::new(
+            self.cipher()
+                .decrypt(
+                    XNonce::from_slice(&nonce),
+                    Payload {
+                        msg: &ciphertext,
+                        aad: ASSOCIATED_DATA,
+                    },
+                )
+                .map_err(|_| {
+                    anyhow::anyhow!(
+                        "Could not decrypt {} (wrong passphrase or corrupted file)",
+                        self.path.display()
+                    )
+                })?,
+        );
+        serde_json::from_slice(&plaintext).context("Failed to parse decrypted credentials")
+    }
+
+    fn save(&self, entries: &Entries) -> Result<()> {
+        let plaintext =
+            Zeroizing::new(serde_json::to_vec(entries).context("Failed to serialize credentials")?);
+        let nonce = XChaCha20Poly1305::generate_nonce(&mut OsRng);
+        let ciphertext = self
+            .cipher()
+            .encrypt(
+                &nonce,
+                Payload {
+                    msg: &plaintext,
+                    aad: ASSOCIATED_DATA,
+                },
+            )
+            .map_err(|_| anyhow::anyhow!("Failed to encrypt credentials"))?;
+
+        let envelope = Envelope {
+            version: FORMAT_VERSION,
+            salt: BASE64.encode(self.salt),
+            nonce: BASE64.encode(nonce),
+            ciphertext: BASE64.encode(ciphertext),
+        };
+        let data = serde_json::to_vec_pretty(&envelope).context("Failed to serialize envelope")?;
+        write_atomically(&self.path, &data)
+    }
+
+    fn modify<T>(&self, change: impl FnOnce(&mut Entries) -> T) -> Result<T> {
+        let _guard = self
+            .lock
+            .lock()
+            .map_err(|_| anyhow::anyhow!("Credential file lock poisoned"))?;
+        let mut entries = self.load()?;
+        let result = change(&mut entries);
+        self.save(&entries)?;
+        Ok(result)
+    }
+}
+
+impl CredentialStore for EncryptedFileStore {
+    fn get(&self, key: &str) -> Result<Option<String>> {
+        let _guard = self
+            .lock
+            .lock()
+            .map_err(|_| anyhow::anyhow!("Credential file lock poisoned"))?;
+        Ok(self.load()?.0.get(key).cloned())
+    }
+
+    fn set(&self, key: &str, value: &str) -> Result<()> {
+        self.modify(|entries| {
+            if let Some(mut previous) = entries.0.insert(key.to_string(), value.to_string()) {
+                previous.zeroize();
+            }
+        })
+    }
+
+    fn delete(&self, key: &str) -> Result<bool> {
+        if self.get(key)?.is_none() {
+            return Ok(false);
+        }
+        self.modify(|entries| match entries.0.remove(key) {
+            Some(mut previous) => {
+                previous.zeroize();
+                true
+            }
+            None => false,
+        })
+    }
+
+    fn backend(&self) -> &'static str {
+        "encrypted-file"
+    }
+}
+
+fn read_envelope(path: &Path) -> Result<Option<Envelope>> {
+    match std::fs::read(path) {
+        Ok(data) => Ok(Some(serde_json::from_slice(&data).with_context(|| {
+            format!("Corrupt credential file {}", path.display())
+        })?)),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(e).with_context(|| format!("Failed to read {}", path.display())),
+    }
+}
+
+fn decode_array<const N: usize>(encoded: &str) -> Result<[u8; N]> {
+    BASE64
+        .decode(encoded)?
+        .try_into()
+        .map_err(|_| anyhow::anyhow!("Expected {} bytes", N))
+}
+
+/// Replace `path` with `data`, readable by the owner only
+fn write_atomically(path: &Path, data: &[u8]) -> Result<()> {
+    let dir = path
+        .parent()
+        .ok_or_else(|| anyhow::anyhow!("Credential file has no parent directory"))?;
+    std::fs::create_dir_all(dir).with_context(|| format!("Failed to create {}", dir.display()))?;
+
+    let mut tmp_name = path.file_name().unwrap_or_default().to_os_string();
+    tmp_name.push(".tmp");
+    let tmp = dir.join(tmp_name);
+
+    let mut options = std::fs::OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    let mut file = options
+        .open(&tmp)
+        .with_context(|| format!("Failed to create {}", tmp.display()))?;
+    file.write_all(data)?;
+    file.sync_all()?;
+    drop(file);
+
+    std::fs::rename(&tmp, path).with_context(|| format!("Failed to replace {}", path.display()))?;
+    Ok(())
+}
+
+/// Passphrase for the file store from the environment
+///
+/// There is deliberately no fallback: a passphrase stored next to the file
+/// would protect nothing.
+fn env_passphrase() -> Result<Zeroizing<Vec<u8>>> {
+    match std::env::var(PASSPHRASE_ENV) {
+        Ok(passphrase) if !passphrase.is_empty() => Ok(Zeroizing::new(passphrase.into_bytes())),
+        _ => anyhow::bail!(
+            "The OS keyring is unavailable and the encrypted credential file is locked: \
+             unlock it with your vault passphrase or set {}",
+            PASSPHRASE_ENV
+        ),
+    }
+}
+
+fn file_store_path(service_name: &str) -> Result<PathBuf> {
+    Ok(crate::config::profile::active_profile()
+        .map_err(anyhow::Error::msg)?
+        .base_dir()
+        .join(format!("{}.credentials", service_name)))
+}
+
+fn active_service_name() -> Result<String> {
+    Ok(crate::config::profile::active_profile()
+        .map_err(anyhow::Error::msg)?
+        .vault_service_name())
+}
+
+/// Stores already chosen, by service name
+static STORES: Mutex<Option<HashMap<String, Arc<dyn CredentialStore>>>> = Mutex::new(None);
+
+fn with_stores<T>(
+    f: impl FnOnce(&mut HashMap<String, Arc<dyn CredentialStore>>) -> T,
+) -> Result<T> {
+    let mut stores = STORES
+        .lock()
+        .map_err(|_| anyhow::anyhow!("Credential store registry poisoned"))?;
+    Ok(f(stores.get_or_insert_with(HashMap::new)))
+}
+
+/// Whether `service_name` keeps its credentials in the encrypted file
+fn uses_file_store(service_name: &str) -> bool {
+    match std::env::var(STORE_ENV).as_deref() {
+        Ok("keyring") => false,
+        Ok("file") => true,
+        _ => {
+            let available = KeyringStore::new(service_name).is_available();
+            if !available {
+                log::warn!("OS keyring unavailable; using the encrypted credential file");
+            }
+            !available
+        }
+    }
+}
+
+/// The store for `service_name`: the OS keyring when it works, else the
+/// encrypted file store
+///
+/// The choice (and the file store's derived key) is made once per process.
+///
+/// # Errors
+/// Fails if the file store is needed but locked (see [`unlock`])
+pub fn default_store(service_name: &str) -> Result<Arc<dyn CredentialStore>> {
+    if let Some(store) = with_stores(|stores| stores.get(service_name).cloned())? {
+        return Ok(store);
+    }
+
+    let store: Arc<dyn CredentialStore> = if uses_file_store(service_name) {
+        let passphrase = env_passphrase()?;
+        Arc::new(EncryptedFileStore::open(
+            file_store_path(service_name)?,
+            &passphrase,
+        )?)
+    } else {
+        Arc::new(KeyringStore::new(service_name))
+    };
+    log::info!(
+        "Credential store for '{}': {}",
+        service_name,
+        store.backend()
+    );
+
+    with_stores(|stores| Arc::clone(stores.entry(service_name.to_string()).or_insert(store)))
+}
+
+/// Whether the active profile's credentials are waiting for [`unlock`]
+///
+/// True when the encrypted file store is in use and no passphrase has been
+/// supplied, by the unlock prompt or the environment.
+pub fn needs_unlock() -> bool {
+    let Ok(service_name) = active_service_name() else {
+        return false;
+    };
+    let unlocked = with_stores(|stores| stores.contains_key(&service_name)).unwrap_or(false);
+    !unlocked && env_passphrase().is_err() && uses_file_store(&service_name)
+}
+
+/// Open the active profile's encrypted credential file with a passphrase
+/// entered by the user
+///
+/// The store stays open for the rest of the process. The first unlock of a
+/// profile chooses the passphrase of its new file.
+///
+/// # Errors
+/// Fails on a wrong passphrase or an unreadable file
+pub fn unlock(passphrase: &str) -> Result<()> {
+    let service_name = active_service_name()?;
+    let store = EncryptedFileStore::open(file_store_path(&service_name)?, passphrase.as_bytes())?;
+    with_stores(|stores| stores.insert(service_name, Arc::new(store)))?;
+    Ok(())
+}
+
+#[cfg(test)]
+pub(crate) mod mock_keyring {
+    //! In-memory keyring shared by every entry, so tests exercise
+    //! [`KeyringStore`](super::KeyringStore) without an OS keyring
+
+    use keyring::credential::{Credential, CredentialApi, CredentialBuilder, CredentialBuilderApi};
+    use std::any::Any;
+    use std::collections::HashMap;
+    use std::sync::{Arc, Mutex, Once};
+
+    type Secrets = Arc<Mutex<HashMap<(String, String), Vec<u8>>>>;
+
+    struct SharedCredential {
+        secrets: Secrets,
+        id: (String, String),
+    }
+
+    impl CredentialApi for SharedCredential {
+        fn set_secret(&self, secret: &[u8]) -> keyring::Result<()> {
+            self.secrets
+                .lock()
+                .unwrap()
+                .insert(self.id.clone(), secret.to_vec());
+            Ok(())
+        }
+
+        fn get_secret(&self) -> keyring::Result<Vec<u8>> {
+            self.secrets
+                .lock()
+                .unwrap()
+                .get(&self.id)
+                .cloned()
+                .ok_or(keyring::Error::NoEntry)
+        }
+
+        fn delete_credential(&self) -> keyring::Result<()> {
+            self.secrets
+                .lock()
+                .unwrap()
+                .remove(&self.id)
+                .map(|_| ())
+                .ok_or(keyring::Error::NoEntry)
+        }
+
+        fn as_any(&self) -> &dyn Any {
+            self
+        }
+    }
+
+    struct SharedBuilder(Secrets);
+
+    impl CredentialBuilderApi for SharedBuilder {
+        fn build(
+            &self,
+            _target: Option<&str>,
+            service: &str,
+            user: &str,
+        ) -> keyring::Result<Box<Credential>> {
+            Ok(Box::new(SharedCredential {
+                secrets: Arc::clone(&self.0),
+                id: (service.to_string(), user.to_string()),
+            }))
+        }
+
+        fn as_any(&self) -> &dyn Any {
+            self
+        }
+    }
+
+    /// Make every keyring entry in this process use the shared map
+    pub(crate) fn install() {
+        static INSTALL: Once = Once::new();
+        INSTALL.call_once(|| {
+            let builder: Box<CredentialBuilder> = Box::new(SharedBuilder(Secrets::default()));
+            keyring::set_default_credential_builder(builder);
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Behaviour every backend must share
+    fn exercise(store: &dyn CredentialStore) {
+        let key = format!("contract-{}", uuid::Uuid::new_v4());
+        assert_eq!(store.get(&key).unwrap(), None);
+
+        store.set(&key, "first").unwrap();
+        assert_eq!(store.get(&key).unwrap().as_deref(), Some("first"));
+        store.set(&key, "second").unwrap();
+        assert_eq!(store.get(&key).unwrap().as_deref(), Some("second"));
+
+        assert!(store.delete(&key).unwrap());
+        assert!(!store.delete(&key).unwrap());
+        assert_eq!(store.get(&key).unwrap(), None);
+    }
+
+    #[test]
+    fn test_file_store_contract() {
+        let dir = tempfile::tempdir().unwrap();
+        let store =
+            EncryptedFileStore::open(dir.path().join("vault.credentials"), b"pass").unwrap();
+        exercise(&store);
+    }
+
+    #[test]
+    fn test_keyring_store_contract() {
+        mock_keyring::install();
+        let store = KeyringStore::new("ai.cyrup.chat.test.store");
+        assert!(store.is_available());
+        exercise(&store);
+    }
+
+    #[test]
+    fn test_file_store_is_encrypted_and_persistent() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("vault.credentials");
+
+        let store = EncryptedFileStore::open(&path, b"correct horse").unwrap();
+        store
+            .set("google:1_refresh_token", "1//secret-refresh-token")
+            .unwrap();
+        drop(store);
+
+        let raw = std::fs::read_to_string(&path).unwrap();
+        assert!(!raw.contains("secret-refresh-token"));
+        assert!(!raw.contains("google:1"));
+        // The write went through a temporary file that was renamed away
+        assert_eq!(std::fs::read_dir(dir.path()).unwrap().count(), 1);
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = std::fs::metadata(&path).unwrap().permissions().mode();
+            assert_eq!(mode & 0o777, 0o600);
+        }
+
+        let reopened = EncryptedFileStore::open(&path, b"correct horse").unwrap();
+        assert_eq!(
+            reopened.get("google:1_refresh_token").unwrap().as_deref(),
+            Some("1//secret-refresh-token")
+        );
+    }
+
+    #[test]
+    fn test_file_store_rejects_wrong_passphrase() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("vault.credentials");
+        EncryptedFileStore::open(&path, b"right")
+            .unwrap()
+            .set("key", "value")
+            .unwrap();
+
+        let error = EncryptedFileStore::open(&path, b"wrong").err().unwrap();
+        assert!(error.to_string().contains("wrong passphrase"));
+    }
+
+    #[test]
+    fn test_file_store_fails_closed_without_a_passphrase() {
+        // Only meaningful when the environment doesn't supply one
+        if std::env::var(PASSPHRASE_ENV).is_ok() {
+            return;
+        }
+
+        let error = env_passphrase().err().unwrap();
+        assert!(error.to_string().contains("locked"));
+    }
+}
@@ -3,23 +3,29 @@ use anyhow::Result;
 use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
 
+pub mod accounts;
+pub mod credential_store;
 pub mod embedded_vault;
 pub mod google_config;
 pub mod native_oauth;
+pub mod oidc;
 pub mod providers;
+pub mod refresh;
+pub mod revocation;
 pub mod services;
 pub mod vault;
 pub mod vault_auth_manager;
 
+pub use accounts::{active_account, active_user_id};
 pub use embedded_vault::{
     SecureString, get_github_oauth_client_id, get_github_oauth_client_secret,
     get_github_private_key, get_google_oauth_client_id, get_google_oauth_client_secret,
     initialize_vault,
 };
 pub use native_oauth::login_with_provider_native;
-pub use providers::{Provider, login_with_provider};
+pub use providers::{Provider, login_with_device_code, login_with_provider};
 pub use vault::{CredentialType, CredentialVault};
-pub use vault_auth_manager::{AuthResult, AuthStatus, VaultAuthManager};
+pub use vault_auth_manager::{AuthResult, AuthStatus, SignOutResult, VaultAuthManager};
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub struct AuthState {
@@ -39,6 +45,11 @@ pub struct UserInfo {
 // StoredTokens struct removed - credentials now live in vault only
 
 impl AuthState {
+    /// Vault key of this account's credentials (`google:1234`)
+    pub fn account_key(&self) -> String {
+        vault::account_key(&self.provider.to_string().to_lowercase(), &self.user.id)
+    }
+
     /// Get the path for storing auth state (legacy file-based storage)
     fn auth_file_path() -> Result<PathBuf> {
         let home =
@@ -52,9 +63,11 @@ impl AuthState {
     }
 
     /// Save auth state to secure vault (user info only - tokens handled separately)
+    ///
+    /// The account becomes the active one.
     pub async fn save_to_vault(&self) -> Result<()> {
         let vault = CredentialVault::new().await?;
-        let provider_name = self.provider.to_string().to_lowercase();
+        let account_key = self.account_key();
 
         // Store user info using the vault's UserInfo type
         let vault_user_info = vault::UserInfo {
@@ -66,28 +79,15 @@ impl AuthState {
             } else {
                 Some(self.user.picture.clone())
             },
-            provider: provider_name.clone(),
+            provider: self.provider.to_string().to_lowercase(),
         };
 
-        vault
-            .store_user_info(&provider_name, &vault_user_info)
+        vault.store_user_info(&account_key, &vault_user_info).await?;
+        VaultAuthManager::with_vault(vault)
+            .switch_account(self)
             .await?;
 
-        log::info!(
-            "Saved auth state to secure vault for provider: {}",
-            provider_name
-        );
-        Ok(())
-    }
-
-    /// Save auth state to legacy file (for backward compatibility)
-    pub async fn save_to_file(&self) -> Result<()> {
-        let path = Self::auth_file_path()?;
-        if let Some(parent) = path.parent() {
-            tokio::fs::create_dir_all(parent).await?;
-        }
-        let json = serde_json::to_string_pretty(self)?;
-        tokio::fs::write(path, json).await?;
+        log::info!("Saved auth state to secure vault for account: {}", account_key);
         Ok(())
     }
 
@@ -115,36 +115,18 @@ impl AuthState {
         Ok(None)
     }
 
-    /// Load auth state from secure vault (user info only - tokens accessed via vault)
+    /// Load the active account from secure vault (user info only - tokens accessed via vault)
     pub async fn load_from_vault() -> Result<Option<Self>> {
-        let vault = CredentialVault::new().await?;
-
-        // Try both Google and GitHub providers
-        for provider in [Provider::Google, Provider::GitHub] {
-            let provider_name = provider.to_string().to_lowercase();
-
-            // Check if user is authenticated with this provider
-            if vault.is_authenticated(&provider_name).await? {
-                // Get user info from vault
-                if let Some(vault_user_info) = vault.get_user_info(&provider_name).await? {
-                    let user = UserInfo {
-                        id: vault_user_info.id,
-                        email: vault_user_info.email,
-                        name: vault_user_info.name,
-                        picture: vault_user_info.avatar_url.unwrap_or_default(),
-                        username: None, // Will be populated by provider-specific logic if needed
-                    };
-
-                    log::info!(
-                        "Loaded auth state from secure vault for provider: {}",
-                        provider_name
-                    );
-                    return Ok(Some(AuthState { provider, user }));
-                }
-            }
+        let manager = VaultAuthManager::new().await?;
+        let state = manager.get_current_user().await?;
+        if let Some(state) = &state {
+            log::info!(
+                "Loaded auth state from secure vault for account: {}",
+                state.account_key()
+            );
         }
-
-        Ok(None)
+        accounts::set_active_account(state.clone());
+        Ok(state)
     }
 
     /// Load auth state from legacy file
@@ -158,15 +140,10 @@ impl AuthState {
         Ok(Some(state))
     }
 
-    /// Delete auth state from both vault and legacy file
+    /// Delete auth state of every account from both vault and legacy file
     pub async fn delete() -> Result<()> {
-        let vault = CredentialVault::new().await?;
-
-        // Delete from vault for all providers
-        for provider in [Provider::Google, Provider::GitHub] {
-            let provider_name = provider.to_string().to_lowercase();
-            let _ = vault.delete_provider_credentials(&provider_name).await;
-        }
+        VaultAuthManager::new().await?.logout_all().await?;
+        accounts::set_active_account(None);
 
         // Delete legacy file
         Self::delete_file().await?;
@@ -187,11 +164,10 @@ impl AuthState {
     /// Check if token needs refresh (uses vault to check expiration)
     pub async fn needs_refresh(&self) -> Result<bool> {
         let vault = CredentialVault::new().await?;
-        let provider_name = self.provider.to_string().to_lowercase();
 
         // Check if access token is valid (not expired)
         let is_valid = vault
-            .is_credential_valid(&provider_name, &CredentialType::AccessToken)
+            .is_credential_valid(&self.account_key(), &CredentialType::AccessToken)
             .await?;
         Ok(!is_valid)
     }
@@ -199,15 +175,13 @@ impl AuthState {
     /// Refresh the access token (uses vault methods)
     pub async fn refresh_token(&self) -> Result<()> {
         let vault = CredentialVault::new().await?;
-        let provider_name = self.provider.to_string().to_lowercase();
-        vault.refresh_token_if_needed(&provider_name).await
+        vault.refresh_token_if_needed(&self.account_key()).await
     }
 
     /// Get a valid access token (refreshing if needed)
     pub async fn get_valid_token(&self) -> Result<String> {
         let vault = CredentialVault::new().await?;
-        let provider_name = self.provider.to_string().to_lowercase();
-        vault.get_valid_access_token(&provider_name).await
+        vault.get_valid_access_token(&self.account_key()).await
     }
 }
 
@@ -216,7 +190,7 @@ pub async fn login() -> Result<AuthState> {
     login_with_provider(Provider::Google).await
 }
 
-/// Logout by deleting stored credentials
+/// Logout of every account by deleting stored credentials
 pub async fn logout() -> Result<()> {
     AuthState::delete().await
 }
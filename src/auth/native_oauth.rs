@@ -15,6 +15,7 @@ pub async fn login_with_provider_native(
     match provider {
         Provider::Google => login_google_native(oauth_callbacks).await,
         Provider::GitHub => login_github_native(oauth_callbacks).await,
+        Provider::Oidc(id) => crate::auth::oidc::login(&id).await,
     }
 }
 
@@ -36,7 +37,6 @@ async fn login_google_native(_oauth_callbacks: OAuthCallbacks) -> Result<AuthSta
         .client_secret(client_secret.as_str())
         .add_scope("https://www.googleapis.com/auth/userinfo.email")?
         .add_scope("https://www.googleapis.com/auth/userinfo.profile")
-        .port(33999)
         .timeout(std::time::Duration::from_secs(300))
         .login()
         .await
@@ -56,7 +56,9 @@ async fn login_google_native(_oauth_callbacks: OAuthCallbacks) -> Result<AuthSta
     );
 
     // Calculate token expiration from oauth_response
-    let expires_at = Some(Utc::now() + Duration::seconds(oauth_response.expires_in as i64));
+    let expires_at = oauth_response
+        .expires_in
+        .map(|secs| Utc::now() + Duration::seconds(secs as i64));
 
     // Create AuthState with real user data and tokens from google-oauth library
     let auth_state = AuthState {
@@ -72,11 +74,11 @@ async fn login_google_native(_oauth_callbacks: OAuthCallbacks) -> Result<AuthSta
 
     // Store tokens directly to vault
     let vault = crate::auth::CredentialVault::new().await?;
-    let provider_name = "google";
+    let account_key = auth_state.account_key();
 
     vault
         .store_credential(
-            provider_name,
+            &account_key,
             crate::auth::CredentialType::AccessToken,
             oauth_response.access_token.to_string(),
             expires_at,
@@ -86,7 +88,7 @@ async fn login_google_native(_oauth_callbacks: OAuthCallbacks) -> Result<AuthSta
     if let Some(refresh_token) = oauth_response.refresh_token {
         vault
             .store_credential(
-                provider_name,
+                &account_key,
                 crate::auth::CredentialType::RefreshToken,
                 refresh_token.to_string(),
                 None,
@@ -115,7 +117,6 @@ async fn login_github_native(_oauth_callbacks: OAuthCallbacks) -> Result<AuthSta
     let oauth_response = Login::client_id(client_id.as_str())
         .client_secret(client_secret.as_str())
         .scopes(["user:email", "read:user"])?
-        .port(33999)
         .timeout(std::time::Duration::from_secs(300))
         .login()
         .await
@@ -155,11 +156,11 @@ async fn login_github_native(_oauth_callbacks: OAuthCallbacks) -> Result<AuthSta
 
     // Store tokens directly to vault
     let vault = crate::auth::CredentialVault::new().await?;
-    let provider_name = "github";
+    let account_key = auth_state.account_key();
 
     vault
         .store_credential(
-            provider_name,
+            &account_key,
             crate::auth::CredentialType::AccessToken,
             oauth_response.access_token.to_string(),
             None, // GitHub tokens don't expire
@@ -169,7 +170,7 @@ async fn login_github_native(_oauth_callbacks: OAuthCallbacks) -> Result<AuthSta
     if let Some(refresh_token) = oauth_response.refresh_token {
         vault
             .store_credential(
-                provider_name,
+                &account_key,
                 crate::auth::CredentialType::RefreshToken,
                 refresh_token.to_string(),
                 None,
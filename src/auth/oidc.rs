@@ -0,0 +1,932 @@
+//! Generic OpenID Connect login
+//!
+//! Company SSO (Keycloak, Okta, Authentik, ...) is added at runtime as an
+//! [`OidcProviderConfig`] in the profile's `oidc_providers.json`; the
+//! optional client secret of confidential clients lives in the vault.
+//! Everything else comes from the issuer's discovery document:
+//!
+//! 1. `GET {issuer}/.well-known/openid-configuration`
+//! 2. Browser to the authorization endpoint with PKCE (S256), `state` and
+//!    `nonce`; the code comes back to a loopback listener
+//! 3. Code exchange at the token endpoint
+//! 4. ID token signature checked against the issuer's JWKS, plus `iss`,
+//!    `aud`, `exp` and `nonce`
+//! 5. Profile from the userinfo endpoint (falling back to ID token claims)
+//!
+//! Credentials are stored under the provider key `oidc_<id>`, the same way
+//! Google and GitHub tokens are, so [`Provider::Oidc`] works everywhere a
+//! built-in provider does.
+
+use crate::auth::{AuthState, CredentialType, CredentialVault, Provider, UserInfo};
+use crate::environment::native::HttpClient;
+use anyhow::{Context, Result};
+use chrono::{Duration, Utc};
+use jsonwebtoken::jwk::JwkSet;
+use jsonwebtoken::{Algorithm, DecodingKey, Validation};
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use oauth_core::PkceChallenge;
+use tokio::net::TcpListener;
+use zeroize::Zeroizing;
+
+/// Provider list, in the profile's settings directory
+pub const OIDC_PROVIDERS_FILE: &str = "oidc_providers.json";
+
+/// How long the browser step may take
+const LOGIN_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(300);
+
+/// Path the loopback listener accepts the authorization response on
+const CALLBACK_PATH: &str = "/callback";
+
+/// Vault application secret holding a confidential client's secret
+const CLIENT_SECRET: &str = "client_secret";
+
+/// A configured OpenID Connect provider
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct OidcProviderConfig {
+    /// Short identifier (lowercase letters, digits, '-' and '_')
+    pub id: String,
+    /// Label on the sign-in button
+    pub name: String,
+    /// Issuer URL, as it appears in the `iss` claim
+    pub issuer: String,
+    pub client_id: String,
+    /// Requested scopes; `openid` is always included
+    #[serde(default = "default_scopes")]
+    pub scopes: Vec<String>,
+    /// Loopback port for the redirect URI; 0 picks a free port
+    /// (RFC 8252), set it for issuers that need an exact redirect URI
+    #[serde(default)]
+    pub redirect_port: u16,
+}
+
+fn default_scopes() -> Vec<String> {
+    ["openid", "email", "profile"].map(String::from).to_vec()
+}
+
+impl OidcProviderConfig {
+    pub fn provider(&self) -> Provider {
+        Provider::Oidc(self.id.clone())
+    }
+
+    /// Check the entry and make sure `openid` is requested
+    ///
+    /// # Errors
+    /// Returns error if a field is missing or the issuer is not an HTTPS
+    /// URL (plain HTTP is allowed for localhost)
+    pub fn validate(&mut self) -> Result<()> {
+        validate_id(&self.id)?;
+        if self.name.trim().is_empty() {
+            anyhow::bail!("Provider name cannot be empty");
+        }
+        if self.client_id.trim().is_empty() {
+            anyhow::bail!("Client ID cannot be empty");
+        }
+
+        let issuer = url::Url::parse(&self.issuer)
+            .with_context(|| format!("Invalid issuer URL '{}'", self.issuer))?;
+        let local = matches!(issuer.host_str(), Some("localhost" | "127.0.0.1" | "[::1]"));
+        if issuer.scheme() != "https" && !(issuer.scheme() == "http" && local) {
+            anyhow::bail!("Issuer must use https (http is only allowed for localhost)");
+        }
+
+        if !self.scopes.iter().any(|scope| scope == "openid") {
+            self.scopes.insert(0, "openid".to_string());
+        }
+        Ok(())
+    }
+}
+
+/// Provider ids become part of vault keys and settings entries
+pub fn validate_id(id: &str) -> Result<()> {
+    if id.is_empty() || id.len() > 32 {
+        anyhow::bail!("Provider ID must be 1-32 characters");
+    }
+    if !id
+        .chars()
+        .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_' || c == '-')
+    {
+        anyhow::bail!("Provider ID may only contain lowercase letters, digits, '-' and '_'");
+    }
+    Ok(())
+}
+
+/// The configured OIDC providers of a profile
+pub struct OidcRegistry {
+    path: PathBuf,
+}
+
+impl OidcRegistry {
+    /// Providers of the active profile
+    pub fn new() -> Result<Self> {
+        Ok(Self::at(
+            crate::config::profile::active_profile()
+                .map_err(anyhow::Error::msg)?
+                .settings_dir()
+                .join(OIDC_PROVIDERS_FILE),
+        ))
+    }
+
+    /// Providers stored in `path` (for testing)
+    pub fn at(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+
+    /// All providers, in the order they were added
+    pub async fn list(&self) -> Result<Vec<OidcProviderConfig>> {
+        match tokio::fs::read(&self.path).await {
+            Ok(data) => serde_json::from_slice(&data)
+                .with_context(|| format!("Could not parse {}", self.path.display())),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Vec::new()),
+            Err(e) => Err(e).with_context(|| format!("Could not read {}", self.path.display())),
+        }
+    }
+
+    pub async fn get(&self, id: &str) -> Result<OidcProviderConfig> {
+        self.list()
+            .await?
+            .into_iter()
+            .find(|provider| provider.id == id)
+            .ok_or_else(|| anyhow::anyhow!("Unknown OpenID Connect provider '{}'", id))
+    }
+
+    /// Add a provider, or replace the one with the same id
+    pub async fn save(&self, mut provider: OidcProviderConfig) -> Result<()> {
+        provider.validate()?;
+        let mut providers = self.list().await?;
+        match providers.iter_mut().find(|p| p.id == provider.id) {
+            Some(existing) => *existing = provider,
+            None => providers.push(provider),
+        }
+        self.write(&providers).await
+    }
+
+    /// Remove a provider; returns whether it existed
+    pub async fn remove(&self, id: &str) -> Result<bool> {
+        let mut providers = self.list().await?;
+        let before = providers.len();
+        providers.retain(|provider| provider.id != id);
+        if providers.len() == before {
+            return Ok(false);
+        }
+        self.write(&providers).await?;
+        Ok(true)
+    }
+
+    async fn write(&self, providers: &[OidcProviderConfig]) -> Result<()> {
+        if let Some(dir) = self.path.parent() {
+            tokio::fs::create_dir_all(dir)
+                .await
+                .with_context(|| format!("Could not create {}", dir.display()))?;
+        }
+        let data = serde_json::to_vec_pretty(providers)?;
+        tokio::fs::write(&self.path, data)
+            .await
+            .with_context(|| format!("Could not write {}", self.path.display()))
+    }
+}
+
+/// Store (or, with an empty value, delete) a confidential client's secret
+pub async fn store_client_secret(id: &str, secret: &str) -> Result<()> {
+    let vault = CredentialVault::new().await?;
+    let provider = Provider::Oidc(id.to_string()).to_string();
+    if secret.is_empty() {
+        vault
+            .delete_application_secret(&provider, CLIENT_SECRET)
+            .await?;
+        return Ok(());
+    }
+    vault
+        .store_application_secret(&provider, CLIENT_SECRET, secret)
+        .await
+}
+
+/// Issuer metadata from `.well-known/openid-configuration`
+#[derive(Debug, Clone, Deserialize)]
+pub struct OidcMetadata {
+    pub issuer: String,
+    pub authorization_endpoint: String,
+    pub token_endpoint: String,
+    pub jwks_uri: String,
+    pub userinfo_endpoint: Option<String>,
+    /// RFC 7009 endpoint used to revoke tokens at sign-out
+    pub revocation_endpoint: Option<String>,
+    #[serde(default)]
+    pub code_challenge_methods_supported: Vec<String>,
+}
+
+/// Token endpoint response
+#[derive(Debug, Clone, Deserialize)]
+pub struct OidcTokens {
+    pub access_token: String,
+    pub id_token: Option<String>,
+    pub refresh_token: Option<String>,
+    pub expires_in: Option<u64>,
+}
+
+/// Identity claims, from the ID token or the userinfo endpoint
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct OidcClaims {
+    pub sub: String,
+    pub nonce: Option<String>,
+    pub email: Option<String>,
+    pub name: Option<String>,
+    pub preferred_username: Option<String>,
+    pub picture: Option<String>,
+}
+
+/// One authorization request, kept until the browser comes back
+pub struct AuthorizationRequest {
+    /// URL to open in the browser
+    pub url: String,
+    pub state: String,
+    pub nonce: String,
+    pub redirect_uri: String,
+    code_verifier: Zeroizing<String>,
+}
+
+/// Result of a completed login
+#[derive(Debug, Clone)]
+pub struct OidcLogin {
+    pub tokens: OidcTokens,
+    pub claims: OidcClaims,
+    pub user: UserInfo,
+}
+
+/// Client for one provider, after discovery
+pub struct OidcClient {
+    config: OidcProviderConfig,
+    client_secret: Option<String>,
+    metadata: OidcMetadata,
+}
+
+impl OidcClient {
+    /// Fetch the issuer's discovery document
+    ///
+    /// # Errors
+    /// Returns error if the document cannot be fetched, names a different
+    /// issuer, or the issuer does not support PKCE with S256
+    pub async fn discover(
+        config: OidcProviderConfig,
+        client_secret: Option<String>,
+    ) -> Result<Self> {
+        let url = format!(
+            "{}/.well-known/openid-configuration",
+            config.issuer.trim_end_matches('/')
+        );
+        let metadata: OidcMetadata = HttpClient::shared()
+            .send(|http| http.get(&url))
+            .await
+            .with_context(|| format!("Could not reach {}", url))?
+            .error_for_status()
+            .with_context(|| format!("Discovery failed for {}", config.issuer))?
+            .json()
+            .await
+            .context("Invalid discovery document")?;
+
+        if metadata.issuer.trim_end_matches('/') != config.issuer.trim_end_matches('/') {
+            anyhow::bail!(
+                "Discovery document is for issuer '{}', expected '{}'",
+                metadata.issuer,
+                config.issuer
+            );
+        }
+        // An empty list means the issuer does not advertise it; try anyway
+        if !metadata.code_challenge_methods_supported.is_empty()
+            && !metadata
+                .code_challenge_methods_supported
+                .iter()
+                .any(|m| m == "S256")
+        {
+            anyhow::bail!("Issuer does not support PKCE with S256");
+        }
+
+        Ok(Self {
+            config,
+            client_secret: client_secret.filter(|secret| !secret.is_empty()),
+            metadata,
+        })
+    }
+
+    pub fn metadata(&self) -> &OidcMetadata {
+        &self.metadata
+    }
+
+    /// Build the authorization URL for a loopback `redirect_uri`
+    pub fn authorization_request(&self, redirect_uri: &str) -> Result<AuthorizationRequest> {
+        let state = random_token();
+        let nonce = random_token();
+        let pkce = PkceChallenge::new()?;
+
+        let mut url = url::Url::parse(&self.metadata.authorization_endpoint)
+            .context("Invalid authorization endpoint")?;
+        url.query_pairs_mut()
+            .append_pair("response_type", "code")
+            .append_pair("client_id", &self.config.client_id)
+            .append_pair("redirect_uri", redirect_uri)
+            .append_pair("scope", &self.config.scopes.join(" "))
+            .append_pair("state", &state)
+            .append_pair("nonce", &nonce)
+            .append_pair("code_challenge", &pkce.code_challenge)
+            .append_pair("code_challenge_method", pkce.challenge_method());
+
+        Ok(AuthorizationRequest {
+            url: url.to_string(),
+            state,
+            nonce,
+            redirect_uri: redirect_uri.to_string(),
+            code_verifier: pkce.code_verifier,
+        })
+    }
+
+    /// Run the browser step and finish the login
+    ///
+    /// `open` is called with the authorization URL (normally it opens the
+    /// system browser). The code comes back to oauth-core's loopback
+    /// listener, the same one the Google and GitHub logins use.
+    pub async fn login(&self, open: impl FnOnce(&str) -> Result<()>) -> Result<OidcLogin> {
+        let port = self.config.redirect_port;
+        let listener = TcpListener::bind(("127.0.0.1", port))
+            .await
+            .with_context(|| format!("Could not listen on 127.0.0.1:{}", port))?;
+        let redirect_uri = format!(
+            "http://127.0.0.1:{}{}",
+            listener.local_addr()?.port(),
+            CALLBACK_PATH
+        );
+        let request = self.authorization_request(&redirect_uri)?;
+
+        open(&request.url)?;
+
+        let callback = oauth_core::wait_for_callback(listener, CALLBACK_PATH, &request.state);
+        let code = tokio::time::timeout(LOGIN_TIMEOUT, callback)
+            .await
+            .map_err(|_| anyhow::anyhow!("Timed out waiting for the browser to sign in"))?
+            .context("Sign-in was not completed")?;
+
+        self.finish(&request, &code).await
+    }
+
+    /// Exchange the code, validate the ID token and load the profile
+    pub async fn finish(&self, request: &AuthorizationRequest, code: &str) -> Result<OidcLogin> {
+        let tokens = self.exchange_code(request, code).await?;
+        let id_token = tokens
+            .id_token
+            .as_deref()
+            .ok_or_else(|| anyhow::anyhow!("Token response has no ID token"))?;
+        let claims = self.validate_id_token(id_token, &request.nonce).await?;
+
+        let profile = match &self.metadata.userinfo_endpoint {
+            Some(endpoint) => Some(self.userinfo(endpoint, &tokens.access_token).await?),
+            None => None,
+        };
+        if let Some(profile) = &profile
+            && profile.sub != claims.sub
+        {
+            anyhow::bail!("Userinfo subject does not match the ID token");
+        }
+
+        let user = map_user(&claims, profile.as_ref());
+        Ok(OidcLogin {
+            tokens,
+            claims,
+            user,
+        })
+    }
+
+    async fn exchange_code(
+        &self,
+        request: &AuthorizationRequest,
+        code: &str,
+    ) -> Result<OidcTokens> {
+        let mut params = vec![
+            ("grant_type", "authorization_code"),
+            ("code", code),
+            ("redirect_uri", request.redirect_uri.as_str()),
+            ("client_id", self.config.client_id.as_str()),
+            ("code_verifier", request.code_verifier.as_str()),
+        ];
+        if let Some(secret) = &self.client_secret {
+            params.push(("client_secret", secret.as_str()));
+        }
+
+        let response = HttpClient::shared()
+            .send(|http| {
+                http.post(&self.metadata.token_endpoint)
+                    .header("Accept", "application/json")
+                    .form(&params)
+            })
+            .await
+            .context("Failed to send token request")?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let body: serde_json::Value = response.json().await.unwrap_or_default();
+            let error = body["error"].as_str().unwrap_or("unknown_error");
+            return Err(match body["error_description"].as_str() {
+                Some(description) => anyhow::anyhow!(
+                    "Token request failed ({}): {}: {}",
+                    status,
+                    error,
+                    description
+                ),
+                None => anyhow::anyhow!("Token request failed ({}): {}", status, error),
+            });
+        }
+
+        response
+            .json()
+            .await
+            .context("Failed to parse token response")
+    }
+
+    /// Check the ID token's signature against the issuer's JWKS and its claims
+    pub async fn validate_id_token(&self, id_token: &str, nonce: &str) -> Result<OidcClaims> {
+        let header = jsonwebtoken::decode_header(id_token).context("Malformed ID token")?;
+        if matches!(
+            header.alg,
+            Algorithm::HS256 | Algorithm::HS384 | Algorithm::HS512
+        ) {
+            anyhow::bail!("ID token uses unsupported algorithm {:?}", header.alg);
+        }
+
+        let jwks: JwkSet = HttpClient::shared()
+            .send(|http| http.get(&self.metadata.jwks_uri))
+            .await
+            .context("Could not fetch the issuer's signing keys")?
+            .error_for_status()
+            .context("Could not fetch the issuer's signing keys")?
+            .json()
+            .await
+            .context("Invalid JWKS document")?;
+
+        let jwk = match &header.kid {
+            Some(kid) => jwks.find(kid),
+            // Without a key id the issuer must have exactly one key
+            None if jwks.keys.len() == 1 => jwks.keys.first(),
+            None => None,
+        }
+        .ok_or_else(|| anyhow::anyhow!("ID token is signed with an unknown key"))?;
+        let key = DecodingKey::from_jwk(jwk).context("Unusable signing key")?;
+
+        let mut validation = Validation::new(header.alg);
+        validation.set_issuer(&[self.metadata.issuer.as_str()]);
+        validation.set_audience(&[self.config.client_id.as_str()]);
+        validation.set_required_spec_claims(&["exp", "iss", "aud", "sub"]);
+
+        let claims = jsonwebtoken::decode::<OidcClaims>(id_token, &key, &validation)
+            .context("ID token rejected")?
+            .claims;
+        if claims.nonce.as_deref() != Some(nonce) {
+            anyhow::bail!("ID token nonce does not match the request");
+        }
+        Ok(claims)
+    }
+
+    async fn userinfo(&self, endpoint: &str, access_token: &str) -> Result<OidcClaims> {
+        HttpClient::shared()
+            .send(|http| http.get(endpoint).bearer_auth(access_token))
+            .await
+            .context("Failed to fetch user info")?
+            .error_for_status()
+            .context("Failed to fetch user info")?
+            .json()
+            .await
+            .context("Failed to parse user info")
+    }
+}
+
+/// Map identity claims to the app's user, preferring userinfo values
+pub fn map_user(claims: &OidcClaims, profile: Option<&OidcClaims>) -> UserInfo {
+    let pick = |field: fn(&OidcClaims) -> &Option<String>| {
+        profile
+            .and_then(|profile| field(profile).clone())
+            .or_else(|| field(claims).clone())
+    };
+
+    let email = pick(|c| &c.email).unwrap_or_default();
+    let username = pick(|c| &c.preferred_username);
+    let name = pick(|c| &c.name)
+        .or_else(|| username.clone())
+        .unwrap_or_else(|| email.clone());
+
+    UserInfo {
+        email,
+        name,
+        picture: pick(|c| &c.picture).unwrap_or_default(),
+        id: claims.sub.clone(),
+        username,
+    }
+}
+
+/// Sign in with a configured provider and store the result in the vault
+pub async fn login(id: &str) -> Result<AuthState> {
+    let config = OidcRegistry::new()?.get(id).await?;
+    let provider = config.provider();
+    let provider_name = provider.to_string();
+
+    let vault = CredentialVault::new().await?;
+    let client_secret = vault
+        .get_application_secret(&provider_name, CLIENT_SECRET)
+        .await?;
+
+    log::info!("Starting OpenID Connect login with {}", config.issuer);
+    let client = OidcClient::discover(config.clone(), client_secret.clone()).await?;
+    let login = client
+        .login(|url| {
+            webbrowser::open(url).context("Could not open the browser for sign-in")?;
+            Ok(())
+        })
+        .await?;
+    log::info!(
+        "Completed OpenID Connect login for {} ({})",
+        login.user.email,
+        config.name
+    );
+
+    let auth_state = AuthState {
+        provider,
+        user: login.user,
+    };
+    let account_key = auth_state.account_key();
+
+    let expires_at = login
+        .tokens
+        .expires_in
+        .map(|seconds| Utc::now() + Duration::seconds(seconds as i64));
+    vault
+        .store_credential(
+            &account_key,
+            CredentialType::AccessToken,
+            login.tokens.access_token.clone(),
+            expires_at,
+        )
+        .await?;
+    if let Some(refresh_token) = &login.tokens.refresh_token {
+        vault
+            .store_credential(
+                &account_key,
+                CredentialType::RefreshToken,
+                refresh_token.clone(),
+                None,
+            )
+            .await?;
+    }
+    // Lets the vault refresh and revoke tokens without knowing about OIDC
+    let client_credentials = serde_json::json!({
+        "client_id": config.client_id,
+        "client_secret": client_secret.unwrap_or_default(),
+        "token_endpoint": client.metadata().token_endpoint,
+        "revocation_endpoint": client.metadata().revocation_endpoint,
+    });
+    vault
+        .store_credential(
+            &account_key,
+            CredentialType::ClientCredentials,
+            client_credentials.to_string(),
+            None,
+        )
+        .await?;
+
+    auth_state.save().await?;
+    Ok(auth_state)
+}
+
+/// 122 random bits, URL safe
+fn random_token() -> String {
+    uuid::Uuid::new_v4().simple().to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use base64::Engine as _;
+    use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+    use jsonwebtoken::{EncodingKey, Header};
+    use serde_json::json;
+    use std::sync::{Arc, Mutex};
+    use wiremock::matchers::{body_string_contains, header, method, path};
+    use wiremock::{Mock, MockServer, Request, ResponseTemplate};
+
+    /// Test-only signing key; tests/fixtures/oidc/jwks.json has its public half
+    const ISSUER_KEY: &str = include_str!("../../tests/fixtures/oidc/issuer_key.pem");
+    const ISSUER_JWKS: &str = include_str!("../../tests/fixtures/oidc/jwks.json");
+
+    fn config(issuer: &str) -> OidcProviderConfig {
+        OidcProviderConfig {
+            id: "corp".to_string(),
+            name: "Corp SSO".to_string(),
+            issuer: issuer.to_string(),
+            client_id: "cyrup-chat".to_string(),
+            scopes: default_scopes(),
+            redirect_port: 0,
+        }
+    }
+
+    fn sign(claims: serde_json::Value, kid: &str) -> String {
+        let mut header = Header::new(Algorithm::RS256);
+        header.kid = Some(kid.to_string());
+        let key = EncodingKey::from_rsa_pem(ISSUER_KEY.as_bytes()).unwrap();
+        jsonwebtoken::encode(&header, &claims, &key).unwrap()
+    }
+
+    fn id_token(issuer: &str, nonce: &str) -> serde_json::Value {
+        json!({
+            "iss": issuer,
+            "aud": "cyrup-chat",
+            "sub": "user-42",
+            "exp": Utc::now().timestamp() + 300,
+            "iat": Utc::now().timestamp(),
+            "nonce": nonce,
+            "email": "ada@corp.example",
+            "name": "Ada from the token",
+        })
+    }
+
+    /// Mock issuer with discovery, JWKS and userinfo
+    async fn issuer() -> MockServer {
+        let server = MockServer::start().await;
+        let base = server.uri();
+        Mock::given(method("GET"))
+            .and(path("/.well-known/openid-configuration"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "issuer": base,
+                "authorization_endpoint": format!("{}/authorize", base),
+                "token_endpoint": format!("{}/token", base),
+                "jwks_uri": format!("{}/jwks", base),
+                "userinfo_endpoint": format!("{}/userinfo", base),
+                "code_challenge_methods_supported": ["S256"],
+            })))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/jwks"))
+            .respond_with(ResponseTemplate::new(200).set_body_raw(ISSUER_JWKS, "application/json"))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/userinfo"))
+            .and(header("authorization", "Bearer at-1"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "sub": "user-42",
+                "email": "ada@corp.example",
+                "name": "Ada Lovelace",
+                "preferred_username": "ada",
+            })))
+            .mount(&server)
+            .await;
+        server
+    }
+
+    fn query(url: &str, name: &str) -> String {
+        url::Url::parse(url)
+            .unwrap()
+            .query_pairs()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.into_owned())
+            .unwrap()
+    }
+
+    #[test]
+    fn test_validate_config() {
+        let mut entry = config("https://sso.corp.example/realms/main");
+        entry.scopes = vec!["email".to_string()];
+        entry.validate().unwrap();
+        assert_eq!(entry.scopes, ["openid", "email"]);
+
+        assert!(config("http://127.0.0.1:8080").validate().is_ok());
+        assert!(config("http://sso.corp.example").validate().is_err());
+        let mut entry = config("https://sso.corp.example");
+        entry.id = "Corp SSO".to_string();
+        assert!(entry.validate().is_err());
+    }
+
+    #[test]
+    fn test_map_user_prefers_userinfo() {
+        let claims = OidcClaims {
+            sub: "u1".to_string(),
+            email: Some("token@corp.example".to_string()),
+            name: Some("Token Name".to_string()),
+            ..Default::default()
+        };
+        let profile = OidcClaims {
+            sub: "u1".to_string(),
+            name: Some("Profile Name".to_string()),
+            preferred_username: Some("pn".to_string()),
+            ..Default::default()
+        };
+
+        let user = map_user(&claims, Some(&profile));
+        assert_eq!(user.id, "u1");
+        assert_eq!(user.email, "token@corp.example");
+        assert_eq!(user.name, "Profile Name");
+        assert_eq!(user.username.as_deref(), Some("pn"));
+
+        let bare = OidcClaims {
+            sub: "u2".to_string(),
+            preferred_username: Some("bob".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(map_user(&bare, None).name, "bob");
+    }
+
+    #[tokio::test]
+    async fn test_registry_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let registry = OidcRegistry::at(dir.path().join(OIDC_PROVIDERS_FILE));
+        assert!(registry.list().await.unwrap().is_empty());
+
+        registry.save(config("https://a.example")).await.unwrap();
+        let mut other = config("https://b.example");
+        other.id = "other".to_string();
+        registry.save(other).await.unwrap();
+        registry.save(config("https://c.example")).await.unwrap();
+
+        let providers = registry.list().await.unwrap();
+        assert_eq!(providers.len(), 2);
+        assert_eq!(
+            registry.get("corp").await.unwrap().issuer,
+            "https://c.example"
+        );
+
+        assert!(registry.remove("corp").await.unwrap());
+        assert!(!registry.remove("corp").await.unwrap());
+        assert!(registry.get("corp").await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_discovery_rejects_other_issuer() {
+        let server = issuer().await;
+        // A document served below the issuer path but naming the issuer itself
+        Mock::given(method("GET"))
+            .and(path("/realms/other/.well-known/openid-configuration"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "issuer": server.uri(),
+                "authorization_endpoint": format!("{}/authorize", server.uri()),
+                "token_endpoint": format!("{}/token", server.uri()),
+                "jwks_uri": format!("{}/jwks", server.uri()),
+            })))
+            .mount(&server)
+            .await;
+        let other = format!("{}/realms/other", server.uri());
+        let error = OidcClient::discover(config(&other), None)
+            .await
+            .err()
+            .unwrap();
+        assert!(error.to_string().contains("expected"), "{}", error);
+
+        let client = OidcClient::discover(config(&server.uri()), None)
+            .await
+            .unwrap();
+        assert_eq!(
+            client.metadata().token_endpoint,
+            format!("{}/token", server.uri())
+        );
+    }
+
+    #[tokio::test]
+    async fn test_id_token_validation() {
+        let server = issuer().await;
+        let iss = server.uri();
+        let client = OidcClient::discover(config(&iss), None).await.unwrap();
+
+        let valid = sign(id_token(&iss, "n1"), "test-key");
+        assert_eq!(
+            client.validate_id_token(&valid, "n1").await.unwrap().sub,
+            "user-42"
+        );
+
+        // Replayed into another request
+        assert!(client.validate_id_token(&valid, "n2").await.is_err());
+
+        let mut claims = id_token(&iss, "n1");
+        claims["aud"] = json!("someone-else");
+        assert!(
+            client
+                .validate_id_token(&sign(claims, "test-key"), "n1")
+                .await
+                .is_err()
+        );
+
+        let mut claims = id_token(&iss, "n1");
+        claims["exp"] = json!(Utc::now().timestamp() - 3600);
+        assert!(
+            client
+                .validate_id_token(&sign(claims, "test-key"), "n1")
+                .await
+                .is_err()
+        );
+
+        assert!(
+            client
+                .validate_id_token(&sign(id_token(&iss, "n1"), "rotated"), "n1")
+                .await
+                .is_err()
+        );
+
+        // Tampered payload
+        let mut parts: Vec<&str> = valid.split('.').collect();
+        let forged =
+            URL_SAFE_NO_PAD.encode(id_token(&iss, "n1").to_string().replace("user-42", "admin"));
+        parts[1] = &forged;
+        assert!(
+            client
+                .validate_id_token(&parts.join("."), "n1")
+                .await
+                .is_err()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_login_against_mock_issuer() {
+        let server = issuer().await;
+        let iss = server.uri();
+
+        // The authorization endpoint redirects straight back, remembering
+        // the nonce so the token endpoint can put it into the ID token
+        let nonce = Arc::new(Mutex::new(String::new()));
+        let seen = nonce.clone();
+        Mock::given(method("GET"))
+            .and(path("/authorize"))
+            .respond_with(move |request: &Request| {
+                let url = request.url.as_str();
+                assert_eq!(query(url, "code_challenge_method"), "S256");
+                assert_eq!(query(url, "scope"), "openid email profile");
+                *seen.lock().unwrap() = query(url, "nonce");
+                let location = format!(
+                    "{}?code=code-1&state={}",
+                    query(url, "redirect_uri"),
+                    query(url, "state")
+                );
+                ResponseTemplate::new(302).insert_header("Location", location.as_str())
+            })
+            .mount(&server)
+            .await;
+        let token_issuer = iss.clone();
+        Mock::given(method("POST"))
+            .and(path("/token"))
+            .and(body_string_contains("code=code-1"))
+            .and(body_string_contains("code_verifier="))
+            .respond_with(move |_: &Request| {
+                let claims = id_token(&token_issuer, &nonce.lock().unwrap());
+                ResponseTemplate::new(200).set_body_json(json!({
+                    "access_token": "at-1",
+                    "refresh_token": "rt-1",
+                    "id_token": sign(claims, "test-key"),
+                    "token_type": "Bearer",
+                    "expires_in": 3600,
+                }))
+            })
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let client = OidcClient::discover(config(&iss), None).await.unwrap();
+        let login = client
+            .login(|url| {
+                let url = url.to_string();
+                // Stand-in browser: a stray favicon request, then the real flow
+                tokio::spawn(async move {
+                    let redirect_uri = query(&url, "redirect_uri");
+                    let favicon = redirect_uri.replace("/callback", "/favicon.ico");
+                    let _ = reqwest::get(favicon).await;
+                    let _ = reqwest::get(url).await;
+                });
+                Ok(())
+            })
+            .await
+            .unwrap();
+
+        assert_eq!(login.tokens.refresh_token.as_deref(), Some("rt-1"));
+        assert_eq!(login.user.id, "user-42");
+        assert_eq!(login.user.name, "Ada Lovelace");
+        assert_eq!(login.user.username.as_deref(), Some("ada"));
+        assert_eq!(login.user.email, "ada@corp.example");
+    }
+
+    #[tokio::test]
+    async fn test_token_error_is_reported() {
+        let server = issuer().await;
+        Mock::given(method("POST"))
+            .and(path("/token"))
+            .respond_with(ResponseTemplate::new(400).set_body_json(json!({
+                "error": "invalid_grant",
+                "error_description": "Code expired",
+            })))
+            .mount(&server)
+            .await;
+
+        let client = OidcClient::discover(config(&server.uri()), None)
+            .await
+            .unwrap();
+        let request = client
+            .authorization_request("http://127.0.0.1:1/callback")
+            .unwrap();
+        let error = client.finish(&request, "stale").await.unwrap_err();
+        assert!(
+            error.to_string().contains("invalid_grant: Code expired"),
+            "{}",
+            error
+        );
+    }
+}
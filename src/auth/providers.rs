@@ -1,13 +1,18 @@
-use crate::auth::{AuthState, CredentialVault, UserInfo};
+use crate::auth::{AuthState, CredentialVault, UserInfo, oidc};
 use anyhow::Result;
 use chrono::{Duration, Utc};
+use oauth_core::DeviceCodeHandler;
 use serde::{Deserialize, Serialize};
 
 /// Supported OAuth providers
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+///
+/// Google and GitHub are built in; `Oidc` names an OpenID Connect provider
+/// configured at runtime (see [`oidc::OidcRegistry`]).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum Provider {
     Google,
     GitHub,
+    Oidc(String),
 }
 
 impl std::fmt::Display for Provider {
@@ -15,15 +20,18 @@ impl std::fmt::Display for Provider {
         match self {
             Provider::Google => write!(f, "Google"),
             Provider::GitHub => write!(f, "GitHub"),
+            // Also the vault key, so keep it lowercase
+            Provider::Oidc(id) => write!(f, "oidc_{}", id),
         }
     }
 }
 
 impl Provider {
-    pub fn name(&self) -> &'static str {
+    pub fn name(&self) -> &str {
         match self {
             Provider::Google => "Google",
             Provider::GitHub => "GitHub",
+            Provider::Oidc(id) => id,
         }
     }
 
@@ -31,6 +39,7 @@ impl Provider {
         match self {
             Provider::Google => "google-icon",
             Provider::GitHub => "github-icon",
+            Provider::Oidc(_) => "oidc-icon",
         }
     }
 
@@ -38,12 +47,39 @@ impl Provider {
         match self {
             Provider::Google => "google-login-button",
             Provider::GitHub => "github-login-button",
+            Provider::Oidc(_) => "oidc-login-button",
         }
     }
 
+    /// Inverse of the lowercase `Display` form used in vault keys
+    pub fn from_vault_name(name: &str) -> Option<Provider> {
+        match name {
+            "google" => Some(Provider::Google),
+            "github" => Some(Provider::GitHub),
+            _ => name
+                .strip_prefix("oidc_")
+                .map(|id| Provider::Oidc(id.to_string())),
+        }
+    }
+
+    /// Built-in providers
     pub fn all() -> Vec<Provider> {
         vec![Provider::Google, Provider::GitHub]
     }
+
+    /// Built-in providers followed by the configured OpenID Connect providers
+    pub async fn configured() -> Vec<Provider> {
+        let mut providers = Self::all();
+        let configs = match oidc::OidcRegistry::new() {
+            Ok(registry) => registry.list().await,
+            Err(e) => Err(e),
+        };
+        match configs {
+            Ok(configs) => providers.extend(configs.iter().map(|config| config.provider())),
+            Err(e) => log::warn!("Could not load OpenID Connect providers: {}", e),
+        }
+        providers
+    }
 }
 
 /// Generic OAuth login function that works with any provider
@@ -51,14 +87,18 @@ pub async fn login_with_provider(provider: Provider) -> Result<AuthState> {
     log::debug!("login_with_provider called for: {}", provider.name());
     log::info!("Starting OAuth login for provider: {}", provider.name());
 
-    let result = match provider {
+    let result = match &provider {
         Provider::Google => {
             log::debug!("Calling login_google()...");
-            login_google().await
+            login_google(None).await
         }
         Provider::GitHub => {
             log::debug!("Calling login_github()...");
-            login_github().await
+            login_github(None).await
+        }
+        Provider::Oidc(id) => {
+            log::debug!("Calling oidc::login({})...", id);
+            oidc::login(id).await
         }
     };
 
@@ -70,21 +110,55 @@ pub async fn login_with_provider(provider: Provider) -> Result<AuthState> {
     result
 }
 
+/// Device-code login (RFC 8628) for the built-in providers
+///
+/// Used where the loopback redirect cannot work, such as SSH sessions and
+/// containers. `on_code` receives the user code and verification URL to show
+/// while the provider is polled for approval.
+pub async fn login_with_device_code(
+    provider: Provider,
+    on_code: DeviceCodeHandler,
+) -> Result<AuthState> {
+    log::info!("Starting device login for provider: {}", provider.name());
+
+    let result = match &provider {
+        Provider::Google => login_google(Some(on_code)).await,
+        Provider::GitHub => login_github(Some(on_code)).await,
+        Provider::Oidc(_) => Err(anyhow::anyhow!(
+            "Device login is not available for {}",
+            provider.name()
+        )),
+    };
+
+    match &result {
+        Ok(_) => log::info!("Device login successful for {}", provider.name()),
+        Err(e) => log::error!("Device login failed for {}: {}", provider.name(), e),
+    }
+
+    result
+}
+
 /// Google OAuth login implementation using google-oauth library
-async fn login_google() -> Result<AuthState> {
+///
+/// With `on_device_code` the device flow replaces the loopback redirect.
+async fn login_google(on_device_code: Option<DeviceCodeHandler>) -> Result<AuthState> {
     use google_oauth::{AccessType, Login, UserInfoBuilder as GoogleUserInfo};
 
     log::info!("Starting Google OAuth login");
 
     // Use the google-oauth library exactly as designed - it handles everything
-    let oauth_response = Login::from_env()
+    let login = Login::from_env()
         .scopes([
             "https://www.googleapis.com/auth/userinfo.email",
             "https://www.googleapis.com/auth/userinfo.profile",
         ])?
         .access_type(AccessType::Offline) // Get refresh token
-        .port(33999) // Library starts local server automatically
-        .timeout(std::time::Duration::from_secs(300))
+        .timeout(std::time::Duration::from_secs(300));
+    let login = match on_device_code {
+        Some(on_code) => login.device_code(on_code),
+        None => login, // Library binds a free loopback port automatically
+    };
+    let oauth_response = login
         .login()
         .await
         .map_err(|e| anyhow::anyhow!("Google OAuth failed: {}", e))?;
@@ -116,12 +190,14 @@ async fn login_google() -> Result<AuthState> {
 
     // Store tokens directly to vault
     let vault = CredentialVault::new().await?;
-    let provider_name = "google";
-    let expires_at = Some(Utc::now() + Duration::seconds(oauth_response.expires_in as i64));
+    let account_key = auth_state.account_key();
+    let expires_at = oauth_response
+        .expires_in
+        .map(|secs| Utc::now() + Duration::seconds(secs as i64));
 
     vault
         .store_credential(
-            provider_name,
+            &account_key,
             crate::auth::CredentialType::AccessToken,
             oauth_response.access_token.to_string(),
             expires_at,
@@ -131,7 +207,7 @@ async fn login_google() -> Result<AuthState> {
     if let Some(refresh_token) = oauth_response.refresh_token {
         vault
             .store_credential(
-                provider_name,
+                &account_key,
                 crate::auth::CredentialType::RefreshToken,
                 refresh_token.to_string(),
                 None,
@@ -139,6 +215,8 @@ async fn login_google() -> Result<AuthState> {
             .await?;
     }
 
+    store_client_credentials::<google_oauth::GoogleProvider>(&vault, &account_key).await?;
+
     // Save to secure vault only on new login (credentials definitely changed)
     auth_state.save().await?;
 
@@ -148,16 +226,22 @@ async fn login_google() -> Result<AuthState> {
 }
 
 /// GitHub OAuth login implementation using github-oauth library
-async fn login_github() -> Result<AuthState> {
+///
+/// With `on_device_code` the device flow replaces the loopback redirect.
+async fn login_github(on_device_code: Option<DeviceCodeHandler>) -> Result<AuthState> {
     use github_oauth::{Login, UserInfoBuilder as GitHubUserInfo};
 
     log::info!("Starting GitHub OAuth login with real github-oauth library");
 
     // Use the github-oauth library exactly as designed - it handles everything
-    let oauth_response = Login::from_env()
+    let login = Login::from_env()
         .scopes(["user:email", "read:user"])?
-        .port(33999) // Library starts local server automatically
-        .timeout(std::time::Duration::from_secs(300))
+        .timeout(std::time::Duration::from_secs(300));
+    let login = match on_device_code {
+        Some(on_code) => login.device_code(on_code),
+        None => login, // Library binds a free loopback port automatically
+    };
+    let oauth_response = login
         .login()
         .await
         .map_err(|e| anyhow::anyhow!("GitHub OAuth failed: {}", e))?;
@@ -195,11 +279,11 @@ async fn login_github() -> Result<AuthState> {
 
     // Store tokens directly to vault
     let vault = CredentialVault::new().await?;
-    let provider_name = "github";
+    let account_key = auth_state.account_key();
 
     vault
         .store_credential(
-            provider_name,
+            &account_key,
             crate::auth::CredentialType::AccessToken,
             oauth_response.access_token.to_string(),
             None, // GitHub tokens don't expire
@@ -209,7 +293,7 @@ async fn login_github() -> Result<AuthState> {
     if let Some(refresh_token) = oauth_response.refresh_token {
         vault
             .store_credential(
-                provider_name,
+                &account_key,
                 crate::auth::CredentialType::RefreshToken,
                 refresh_token.to_string(),
                 None,
@@ -217,6 +301,8 @@ async fn login_github() -> Result<AuthState> {
             .await?;
     }
 
+    store_client_credentials::<github_oauth::GitHubProvider>(&vault, &account_key).await?;
+
     // Save to secure vault (this will also save to legacy file for backward compatibility)
     auth_state.save().await?;
 
@@ -225,25 +311,59 @@ async fn login_github() -> Result<AuthState> {
     Ok(auth_state)
 }
 
+/// Store the client a `from_env()` login used with the account's tokens
+///
+/// The vault needs it to refresh and revoke the tokens later.
+async fn store_client_credentials<P: oauth_core::OAuthProvider>(
+    vault: &CredentialVault,
+    account_key: &str,
+) -> Result<()> {
+    let (Ok(client_id), Ok(client_secret)) = (
+        std::env::var(P::client_id_env()),
+        std::env::var(P::client_secret_env()),
+    ) else {
+        return Ok(());
+    };
+    let client_credentials = serde_json::json!({
+        "client_id": client_id,
+        "client_secret": client_secret,
+    });
+    vault
+        .store_credential(
+            account_key,
+            crate::auth::CredentialType::ClientCredentials,
+            client_credentials.to_string(),
+            None,
+        )
+        .await
+}
+
 /// Refresh token for a specific provider
 pub async fn refresh_token_for_provider(auth_state: &mut AuthState) -> Result<()> {
     match auth_state.provider {
         Provider::Google => refresh_google_token(auth_state).await,
         Provider::GitHub => refresh_github_token(auth_state).await,
+        // The token endpoint is stored with the client credentials at login
+        Provider::Oidc(_) => {
+            let vault = CredentialVault::new().await?;
+            vault
+                .refresh_token_if_needed(&auth_state.provider.to_string())
+                .await
+        }
     }
 }
 
 async fn refresh_google_token(auth_state: &mut AuthState) -> Result<()> {
-    use reqwest::Client;
+    use crate::environment::native::HttpClient;
     use serde_json::json;
 
     // Get refresh token from vault instead of auth_state
     let vault = CredentialVault::new().await?;
-    let provider_name = auth_state.provider.to_string().to_lowercase();
+    let account_key = auth_state.account_key();
     let refresh_credential = vault
-        .get_credential(&provider_name, &crate::auth::CredentialType::RefreshToken)
+        .get_credential(&account_key, &crate::auth::CredentialType::RefreshToken)
         .await?
-        .ok_or_else(|| anyhow::anyhow!("No refresh token available for {} OAuth", provider_name))?;
+        .ok_or_else(|| anyhow::anyhow!("No refresh token available for {} OAuth", account_key))?;
     let refresh_token = &refresh_credential;
 
     // Get Google OAuth credentials from vault
@@ -256,7 +376,6 @@ async fn refresh_google_token(auth_state: &mut AuthState) -> Result<()> {
             anyhow::anyhow!("Failed to get Google OAuth client secret from vault: {}", e)
         })?;
 
-    let client = Client::new();
     let params = json!({
         "client_id": client_id.as_str(),
         "client_secret": client_secret.as_str(),
@@ -264,10 +383,8 @@ async fn refresh_google_token(auth_state: &mut AuthState) -> Result<()> {
         "grant_type": "refresh_token"
     });
 
-    let response = client
-        .post("https://oauth2.googleapis.com/token")
-        .json(&params)
-        .send()
+    let response = HttpClient::shared()
+        .send(|client| client.post("https://oauth2.googleapis.com/token").json(&params))
         .await
         .map_err(|e| anyhow::anyhow!("Failed to send refresh request: {}", e))?;
 
@@ -290,7 +407,7 @@ async fn refresh_google_token(auth_state: &mut AuthState) -> Result<()> {
     // Update tokens in vault with new access token
     if let Some(new_access_token) = token_response.get("access_token").and_then(|v| v.as_str()) {
         let vault = CredentialVault::new().await?;
-        let provider_name = auth_state.provider.to_string().to_lowercase();
+        let account_key = auth_state.account_key();
 
         // Calculate expiration time if provided
         let expires_at = token_response
@@ -300,7 +417,7 @@ async fn refresh_google_token(auth_state: &mut AuthState) -> Result<()> {
 
         vault
             .store_credential(
-                &provider_name,
+                &account_key,
                 crate::auth::CredentialType::AccessToken,
                 new_access_token.to_string(),
                 expires_at,
@@ -313,7 +430,7 @@ async fn refresh_google_token(auth_state: &mut AuthState) -> Result<()> {
         {
             vault
                 .store_credential(
-                    &provider_name,
+                    &account_key,
                     crate::auth::CredentialType::RefreshToken,
                     new_refresh_token.to_string(),
                     None,
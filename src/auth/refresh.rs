@@ -0,0 +1,326 @@
+//! Background token refresh
+//!
+//! [`run_refresh_scheduler`] sleeps until shortly before the earliest access
+//! token expiry across every signed-in account, refreshes the tokens that are
+//! due and goes back to sleep. Wake-ups are jittered so several app instances
+//! (or profiles) sharing a provider don't refresh in lockstep.
+//!
+//! A refresh token the provider rejects can't be retried: the account's tokens
+//! are removed and the caller is told so the UI can ask the user to sign in
+//! again. Progress is recorded in [`RefreshStatus`], which
+//! [`VaultAuthManager::health_check`](crate::auth::VaultAuthManager::health_check)
+//! reports.
+
+use crate::auth::vault::RefreshTokenRevoked;
+use crate::auth::vault_auth_manager::account_from_vault;
+use crate::auth::{AuthState, CredentialType, CredentialVault};
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use std::sync::RwLock;
+use std::time::Duration;
+use tokio::sync::Notify;
+
+/// Refresh this long before an access token expires
+const REFRESH_LEAD: Duration = Duration::from_secs(5 * 60);
+
+/// Upper bound of the random delay added ahead of each refresh
+const MAX_JITTER: Duration = Duration::from_secs(60);
+
+/// Look for new or changed accounts at least this often
+const RESCAN_INTERVAL: Duration = Duration::from_secs(15 * 60);
+
+/// Wait before retrying after a refresh failed for a transient reason
+const RETRY_DELAY: Duration = Duration::from_secs(60);
+
+/// What the refresh scheduler has been doing
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct RefreshStatus {
+    /// Whether the scheduler task is running
+    pub running: bool,
+    /// When the scheduler next wakes to refresh a token
+    pub next_refresh_at: Option<DateTime<Utc>>,
+    /// Last successful refresh
+    pub last_refresh_at: Option<DateTime<Utc>>,
+    /// Last transient failure, cleared by the next successful refresh
+    pub last_error: Option<String>,
+    /// Accounts whose refresh token was revoked and must sign in again
+    pub reauth_required: Vec<String>,
+}
+
+static STATUS: RwLock<RefreshStatus> = RwLock::new(RefreshStatus {
+    running: false,
+    next_refresh_at: None,
+    last_refresh_at: None,
+    last_error: None,
+    reauth_required: Vec::new(),
+});
+
+static RESCHEDULE: Notify = Notify::const_new();
+
+/// Snapshot of the scheduler's status
+pub fn refresh_status() -> RefreshStatus {
+    STATUS.read().map(|status| status.clone()).unwrap_or_default()
+}
+
+/// Wake the scheduler to re-read token expiries (after a sign-in, say)
+pub fn reschedule() {
+    RESCHEDULE.notify_one();
+}
+
+fn update_status(update: impl FnOnce(&mut RefreshStatus)) {
+    if let Ok(mut status) = STATUS.write() {
+        update(&mut status);
+    }
+}
+
+/// Keep every signed-in account's access token fresh, forever
+///
+/// `on_revoked` is called with each account whose refresh token the provider
+/// rejected, after its tokens have been removed from the vault.
+pub async fn run_refresh_scheduler(mut on_revoked: impl FnMut(AuthState)) {
+    log::info!("Token refresh scheduler started");
+    update_status(|status| status.running = true);
+
+    loop {
+        let delay = match refresh_due_tokens(&mut on_revoked).await {
+            Ok(delay) => delay,
+            Err(e) => {
+                log::warn!("Token refresh pass failed: {}", e);
+                update_status(|status| status.last_error = Some(e.to_string()));
+                RETRY_DELAY
+            }
+        };
+
+        log::debug!("Next token refresh check in {:?}", delay);
+        tokio::select! {
+            _ = tokio::time::sleep(delay) => {}
+            _ = RESCHEDULE.notified() => log::debug!("Token refresh rescheduled"),
+        }
+    }
+}
+
+/// Refresh every token that is due and return how long to sleep
+async fn refresh_due_tokens(on_revoked: &mut impl FnMut(AuthState)) -> Result<Duration> {
+    let vault = CredentialVault::new().await?;
+    let now = Utc::now();
+    let mut failed = false;
+
+    for key in due_accounts(&token_expiries(&vault).await?, now) {
+        match vault.refresh_token_if_needed(&key).await {
+            Ok(()) => {
+                log::info!("Refreshed access token for {} ahead of expiry", key);
+                update_status(|status| {
+                    status.last_refresh_at = Some(Utc::now());
+                    status.last_error = None;
+                });
+            }
+            Err(e) if e.is::<RefreshTokenRevoked>() => {
+                log::warn!("Refresh token for {} was revoked; sign-in required", key);
+                let account = vault
+                    .get_user_info(&key)
+                    .await
+                    .ok()
+                    .flatten()
+                    .and_then(account_from_vault);
+                if let Err(e) = vault.delete_provider_credentials(&key).await {
+                    log::warn!("Failed to remove revoked credentials for {}: {}", key, e);
+                }
+                update_status(|status| {
+                    if !status.reauth_required.contains(&key) {
+                        status.reauth_required.push(key.clone());
+                    }
+                });
+                if let Some(account) = account {
+                    on_revoked(account);
+                }
+            }
+            Err(e) => {
+                log::warn!("Failed to refresh access token for {}: {}", key, e);
+                update_status(|status| status.last_error = Some(e.to_string()));
+                failed = true;
+            }
+        }
+    }
+
+    let expiries = token_expiries(&vault).await?;
+    // Signing in again clears an account's re-authentication flag
+    update_status(|status| {
+        status
+            .reauth_required
+            .retain(|key| !expiries.iter().any(|(account, _)| account == key));
+    });
+
+    let next = next_refresh_at(&expiries, jitter());
+    update_status(|status| status.next_refresh_at = next);
+
+    if failed {
+        return Ok(RETRY_DELAY);
+    }
+    Ok(sleep_until(next, Utc::now()))
+}
+
+/// Access token expiry of every account that can refresh its token
+async fn token_expiries(vault: &CredentialVault) -> Result<Vec<(String, DateTime<Utc>)>> {
+    let mut expiries = Vec::new();
+    for key in vault.list_account_keys()? {
+        let can_refresh = vault
+            .get_credential(&key, &CredentialType::RefreshToken)
+            .await?
+            .is_some();
+        // Without the client the tokens were issued to, every attempt would
+        // fail and be retried for as long as the app runs
+        if !can_refresh || vault.client_credentials(&key).await?.is_none() {
+            continue;
+        }
+        // Tokens without an expiry (GitHub OAuth apps) never need refreshing
+        if let Some(expires_at) = vault
+            .credential_expires_at(&key, &CredentialType::AccessToken)
+            .await?
+        {
+            expiries.push((key, expires_at));
+        }
+    }
+    Ok(expiries)
+}
+
+/// Accounts whose access token expires within [`REFRESH_LEAD`] of `now`
+///
+/// The window includes [`MAX_JITTER`] so a jittered early wake-up still finds
+/// its token due.
+fn due_accounts(expiries: &[(String, DateTime<Utc>)], now: DateTime<Utc>) -> Vec<String> {
+    let window = lead() + chrono::Duration::from_std(MAX_JITTER).unwrap_or_default();
+    expiries
+        .iter()
+        .filter(|(_, expires_at)| *expires_at - window <= now)
+        .map(|(key, _)| key.clone())
+        .collect()
+}
+
+/// When to wake for the earliest expiring token
+fn next_refresh_at(
+    expiries: &[(String, DateTime<Utc>)],
+    jitter: chrono::Duration,
+) -> Option<DateTime<Utc>> {
+    expiries
+        .iter()
+        .map(|(_, expires_at)| *expires_at - lead() - jitter)
+        .min()
+}
+
+/// Sleep until `next`, but never longer than [`RESCAN_INTERVAL`]
+fn sleep_until(next: Option<DateTime<Utc>>, now: DateTime<Utc>) -> Duration {
+    next.map(|at| (at - now).to_std().unwrap_or(Duration::ZERO))
+        .map_or(RESCAN_INTERVAL, |delay| delay.min(RESCAN_INTERVAL))
+}
+
+fn lead() -> chrono::Duration {
+    chrono::Duration::from_std(REFRESH_LEAD).unwrap_or_default()
+}
+
+/// Random delay in `0..=MAX_JITTER`
+fn jitter() -> chrono::Duration {
+    let max_ms = MAX_JITTER.as_millis();
+    let random = uuid::Uuid::new_v4().as_u128() % (max_ms + 1);
+    chrono::Duration::milliseconds(random as i64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::auth::credential_store::EncryptedFileStore;
+    use crate::auth::vault::account_key;
+    use std::sync::Arc;
+
+    fn at(minutes: i64) -> DateTime<Utc> {
+        DateTime::from_timestamp(minutes * 60, 0).unwrap()
+    }
+
+    #[test]
+    fn test_due_accounts_include_expired_and_soon_expiring() {
+        let expiries = vec![
+            ("google:1".to_string(), at(60)),
+            ("google:2".to_string(), at(6)),
+            ("oidc_corp:3".to_string(), at(-10)),
+        ];
+        assert_eq!(due_accounts(&expiries, at(0)), ["google:2", "oidc_corp:3"]);
+        assert!(due_accounts(&expiries[..1], at(0)).is_empty());
+    }
+
+    #[test]
+    fn test_next_refresh_is_earliest_expiry_minus_lead_and_jitter() {
+        let expiries = vec![
+            ("google:1".to_string(), at(60)),
+            ("google:2".to_string(), at(30)),
+        ];
+        let jitter = chrono::Duration::seconds(20);
+        assert_eq!(next_refresh_at(&expiries, jitter), Some(at(25) - jitter));
+        assert_eq!(next_refresh_at(&[], jitter), None);
+    }
+
+    #[test]
+    fn test_sleep_is_capped_and_never_negative() {
+        assert_eq!(sleep_until(None, at(0)), RESCAN_INTERVAL);
+        assert_eq!(sleep_until(Some(at(-5)), at(0)), Duration::ZERO);
+        assert_eq!(sleep_until(Some(at(2)), at(0)), Duration::from_secs(120));
+        assert_eq!(sleep_until(Some(at(600)), at(0)), RESCAN_INTERVAL);
+    }
+
+    #[test]
+    fn test_jitter_is_bounded() {
+        for _ in 0..100 {
+            let jitter = jitter();
+            assert!(jitter >= chrono::Duration::zero());
+            assert!(jitter.to_std().unwrap() <= MAX_JITTER);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_accounts_without_a_client_are_not_scheduled() {
+        let dir = tempfile::tempdir().unwrap();
+        let store =
+            EncryptedFileStore::open(dir.path().join("vault.credentials"), b"test").unwrap();
+        let vault = CredentialVault::with_store(Arc::new(store));
+
+        // A Google sign-in from before the client was stored with the tokens
+        let key = account_key("google", "1");
+        let expires_at = Utc::now() + chrono::Duration::seconds(30);
+        vault
+            .store_credential(
+                &key,
+                CredentialType::AccessToken,
+                "access".to_string(),
+                Some(expires_at),
+            )
+            .await
+            .unwrap();
+        vault
+            .store_credential(
+                &key,
+                CredentialType::RefreshToken,
+                "refresh".to_string(),
+                None,
+            )
+            .await
+            .unwrap();
+
+        // Due, but with nothing to refresh it with it would fail every pass
+        assert!(token_expiries(&vault).await.unwrap().is_empty());
+        assert!(vault.refresh_token_if_needed(&key).await.is_err());
+
+        // The application's client secrets stand in for the stored client
+        vault
+            .store_application_secret("google", "client_id", "app-id")
+            .await
+            .unwrap();
+        vault
+            .store_application_secret("google", "client_secret", "app-secret")
+            .await
+            .unwrap();
+        let expiries = token_expiries(&vault).await.unwrap();
+        assert_eq!(expiries.len(), 1);
+        assert_eq!(expiries[0].0, key);
+        let creds = vault.client_credentials(&key).await.unwrap().unwrap();
+        assert_eq!(creds["client_id"], "app-id");
+    }
+}
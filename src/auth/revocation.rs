@@ -0,0 +1,390 @@
+//! Token revocation at sign-out
+//!
+//! Deleting an account's tokens from the vault leaves them valid at the
+//! provider until they expire. [`RevocationClient`] asks the provider to
+//! revoke them: Google's `/revoke` endpoint, GitHub's
+//! `DELETE /applications/{client_id}/grant`, and the RFC 7009 endpoint of
+//! OpenID Connect issuers that advertise one.
+//!
+//! Revocation is best effort. Requests go through the shared
+//! [`HttpClient`] and are retried on network errors, rate limiting and server
+//! errors even when POSTed, since revoking twice is harmless; callers sign
+//! the account out locally whatever the outcome.
+
+use crate::auth::vault::provider_of;
+use crate::auth::{CredentialType, CredentialVault};
+use crate::environment::native::HttpClient;
+use anyhow::{Context, Result};
+use reqwest::{RequestBuilder, StatusCode};
+
+/// Provider endpoints used for revocation
+///
+/// OpenID Connect endpoints come from the issuer's discovery document and
+/// are stored with each account's client credentials instead.
+#[derive(Debug, Clone)]
+pub struct RevocationEndpoints {
+    /// Google OAuth token revocation endpoint
+    pub google: String,
+    /// Base URL of the GitHub REST API
+    pub github_api: String,
+}
+
+impl Default for RevocationEndpoints {
+    fn default() -> Self {
+        Self {
+            google: "https://oauth2.googleapis.com/revoke".to_string(),
+            github_api: "https://api.github.com".to_string(),
+        }
+    }
+}
+
+/// Revokes an account's tokens at its provider
+#[derive(Debug, Clone)]
+pub struct RevocationClient {
+    endpoints: RevocationEndpoints,
+}
+
+impl Default for RevocationClient {
+    fn default() -> Self {
+        Self::with_endpoints(RevocationEndpoints::default())
+    }
+}
+
+/// How a revocation request ended
+#[derive(Debug, PartialEq)]
+enum Outcome {
+    /// The provider revoked the tokens, or they were already invalid
+    Revoked,
+    /// The provider refused, or was still failing after retries
+    Failed(String),
+}
+
+impl RevocationClient {
+    /// Create a client for the providers' public endpoints
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a client for other endpoints (mock servers in tests)
+    pub fn with_endpoints(endpoints: RevocationEndpoints) -> Self {
+        Self { endpoints }
+    }
+
+    /// Revoke the tokens of an account in the vault
+    ///
+    /// Must be called before the account's credentials are deleted.
+    ///
+    /// # Returns
+    /// * `Ok(true)` - The provider no longer accepts the account's tokens
+    /// * `Ok(false)` - Nothing to revoke, or the provider offers no revocation
+    /// * `Err` - The provider refused, or revocation failed after retries
+    pub async fn revoke_account(&self, vault: &CredentialVault, account_key: &str) -> Result<bool> {
+        let access_token = vault
+            .get_credential(account_key, &CredentialType::AccessToken)
+            .await?;
+        let refresh_token = vault
+            .get_credential(account_key, &CredentialType::RefreshToken)
+            .await?;
+        let client_credentials = match vault
+            .get_credential(account_key, &CredentialType::ClientCredentials)
+            .await?
+        {
+            Some(creds) => {
+                serde_json::from_str(&creds).context("Failed to parse client credentials")?
+            }
+            None => serde_json::Value::Null,
+        };
+
+        match provider_of(account_key) {
+            "google" => {
+                // Revoking the refresh token also revokes its access tokens
+                let Some(token) = refresh_token.or(access_token) else {
+                    return Ok(false);
+                };
+                send(|client| {
+                    client
+                        .post(&self.endpoints.google)
+                        .form(&[("token", token.as_str())])
+                })
+                .await?;
+            }
+            "github" => {
+                let Some(token) = access_token else {
+                    return Ok(false);
+                };
+                let (client_id, client_secret) =
+                    match github_client(vault, &client_credentials).await? {
+                        Some(client) => client,
+                        None => {
+                            log::warn!(
+                                "No GitHub client credentials; cannot revoke {}",
+                                account_key
+                            );
+                            return Ok(false);
+                        }
+                    };
+                let url = format!(
+                    "{}/applications/{}/grant",
+                    self.endpoints.github_api.trim_end_matches('/'),
+                    client_id
+                );
+                send(|client| {
+                    client
+                        .delete(&url)
+                        .basic_auth(&client_id, Some(&client_secret))
+                        .header("Accept", "application/vnd.github+json")
+                        .header("User-Agent", "cyrup-chat")
+                        .json(&serde_json::json!({ "access_token": token }))
+                })
+                .await?;
+            }
+            _ => {
+                let Some(endpoint) = client_credentials["revocation_endpoint"].as_str() else {
+                    return Ok(false);
+                };
+                let (token, hint) = match (refresh_token, access_token) {
+                    (Some(token), _) => (token, "refresh_token"),
+                    (None, Some(token)) => (token, "access_token"),
+                    (None, None) => return Ok(false),
+                };
+                let client_id = client_credentials["client_id"].as_str().unwrap_or_default();
+                let client_secret = client_credentials["client_secret"]
+                    .as_str()
+                    .unwrap_or_default();
+                send(|client| {
+                    let mut params = vec![
+                        ("token", token.as_str()),
+                        ("token_type_hint", hint),
+                        ("client_id", client_id),
+                    ];
+                    // Public clients (PKCE) have no secret
+                    if !client_secret.is_empty() {
+                        params.push(("client_secret", client_secret));
+                    }
+                    client.post(endpoint).form(&params)
+                })
+                .await?;
+            }
+        }
+
+        log::info!("Revoked tokens of {} at the provider", account_key);
+        Ok(true)
+    }
+}
+
+/// Send a revocation request through the shared client
+async fn send(request: impl FnOnce(&reqwest::Client) -> RequestBuilder) -> Result<()> {
+    let response = HttpClient::shared()
+        .send_idempotent(request)
+        .await
+        .context("Token revocation failed")?;
+    let status = response.status();
+    let body = response.text().await.unwrap_or_default();
+    match classify(status, &body) {
+        Outcome::Revoked => Ok(()),
+        Outcome::Failed(error) => Err(anyhow::anyhow!("Token revocation refused: {}", error)),
+    }
+}
+
+/// GitHub OAuth app credentials for the grant endpoint
+async fn github_client(
+    vault: &CredentialVault,
+    client_credentials: &serde_json::Value,
+) -> Result<Option<(String, String)>> {
+    if let (Some(id), Some(secret)) = (
+        client_credentials["client_id"].as_str(),
+        client_credentials["client_secret"].as_str(),
+    ) {
+        return Ok(Some((id.to_string(), secret.to_string())));
+    }
+    let id = vault.get_application_secret("github", "client_id").await?;
+    let secret = vault
+        .get_application_secret("github", "client_secret")
+        .await?;
+    Ok(id.zip(secret))
+}
+
+/// Interpret a revocation endpoint's final response
+fn classify(status: StatusCode, body: &str) -> Outcome {
+    // Google answers invalid_token and GitHub 404 for tokens already revoked
+    if status.is_success() || status == StatusCode::NOT_FOUND || body.contains("invalid_token") {
+        return Outcome::Revoked;
+    }
+    Outcome::Failed(format!("{}: {}", status, body))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::auth::credential_store::EncryptedFileStore;
+    use crate::auth::vault::account_key;
+    use std::sync::Arc;
+    use wiremock::matchers::{body_json, body_string_contains, header, method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    fn vault(dir: &tempfile::TempDir) -> CredentialVault {
+        let store =
+            EncryptedFileStore::open(dir.path().join("vault.credentials"), b"test").unwrap();
+        CredentialVault::with_store(Arc::new(store))
+    }
+
+    async fn store(
+        vault: &CredentialVault,
+        key: &str,
+        credential_type: CredentialType,
+        value: &str,
+    ) {
+        vault
+            .store_credential(key, credential_type, value.to_string(), None)
+            .await
+            .unwrap();
+    }
+
+    fn client(server: &MockServer) -> RevocationClient {
+        RevocationClient::with_endpoints(RevocationEndpoints {
+            google: format!("{}/revoke", server.uri()),
+            github_api: server.uri(),
+        })
+    }
+
+    #[test]
+    fn test_classify() {
+        assert_eq!(classify(StatusCode::OK, ""), Outcome::Revoked);
+        assert_eq!(classify(StatusCode::NO_CONTENT, ""), Outcome::Revoked);
+        assert_eq!(
+            classify(StatusCode::BAD_REQUEST, r#"{"error":"invalid_token"}"#),
+            Outcome::Revoked
+        );
+        assert_eq!(classify(StatusCode::NOT_FOUND, ""), Outcome::Revoked);
+        assert!(matches!(
+            classify(StatusCode::SERVICE_UNAVAILABLE, ""),
+            Outcome::Failed(_)
+        ));
+        assert!(matches!(
+            classify(StatusCode::UNAUTHORIZED, ""),
+            Outcome::Failed(_)
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_google_revokes_refresh_token_after_retry() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/revoke"))
+            .respond_with(ResponseTemplate::new(503))
+            .up_to_n_times(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/revoke"))
+            .and(body_string_contains("token=google-refresh"))
+            .respond_with(ResponseTemplate::new(200))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let dir = tempfile::tempdir().unwrap();
+        let vault = vault(&dir);
+        let key = account_key("google", "1");
+        store(&vault, &key, CredentialType::AccessToken, "google-access").await;
+        store(&vault, &key, CredentialType::RefreshToken, "google-refresh").await;
+
+        assert!(client(&server).revoke_account(&vault, &key).await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_github_deletes_grant() {
+        let server = MockServer::start().await;
+        Mock::given(method("DELETE"))
+            .and(path("/applications/gh-client/grant"))
+            .and(header(
+                "Authorization",
+                "Basic Z2gtY2xpZW50OmdoLXNlY3JldA==",
+            ))
+            .and(body_json(serde_json::json!({ "access_token": "gh-token" })))
+            .respond_with(ResponseTemplate::new(204))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let dir = tempfile::tempdir().unwrap();
+        let vault = vault(&dir);
+        vault
+            .store_application_secret("github", "client_id", "gh-client")
+            .await
+            .unwrap();
+        vault
+            .store_application_secret("github", "client_secret", "gh-secret")
+            .await
+            .unwrap();
+        let key = account_key("github", "7");
+        store(&vault, &key, CredentialType::AccessToken, "gh-token").await;
+
+        assert!(client(&server).revoke_account(&vault, &key).await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_oidc_uses_stored_revocation_endpoint() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/oauth/revoke"))
+            .and(body_string_contains("token_type_hint=refresh_token"))
+            .respond_with(ResponseTemplate::new(200))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let dir = tempfile::tempdir().unwrap();
+        let vault = vault(&dir);
+        let key = account_key("oidc_corp", "u");
+        store(&vault, &key, CredentialType::RefreshToken, "corp-refresh").await;
+        let creds = serde_json::json!({
+            "client_id": "corp",
+            "client_secret": "",
+            "revocation_endpoint": format!("{}/oauth/revoke", server.uri()),
+        });
+        store(
+            &vault,
+            &key,
+            CredentialType::ClientCredentials,
+            &creds.to_string(),
+        )
+        .await;
+
+        assert!(client(&server).revoke_account(&vault, &key).await.unwrap());
+
+        // Issuers without a revocation endpoint are skipped
+        let other = account_key("oidc_other", "u");
+        store(
+            &vault,
+            &other,
+            CredentialType::RefreshToken,
+            "other-refresh",
+        )
+        .await;
+        assert!(
+            !client(&server)
+                .revoke_account(&vault, &other)
+                .await
+                .unwrap()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_refused_revocation_is_not_retried() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/revoke"))
+            .respond_with(ResponseTemplate::new(401))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let dir = tempfile::tempdir().unwrap();
+        let vault = vault(&dir);
+        let key = account_key("google", "2");
+        store(&vault, &key, CredentialType::AccessToken, "google-access").await;
+
+        assert!(client(&server).revoke_account(&vault, &key).await.is_err());
+    }
+}
@@ -1,5 +1,6 @@
 // Authentication Services Module
 
+use crate::environment::native::HttpClient;
 use crate::environment::native::model::types::TokenData;
 use serde::{Deserialize, Serialize};
 
@@ -20,8 +21,6 @@ pub async fn refresh_access_token(
     client_id: &str,
     client_secret: &str,
 ) -> Result<TokenData, Box<dyn std::error::Error + Send + Sync>> {
-    let client = reqwest::Client::new();
-
     let token_url = format!("{}/oauth/token", instance_url.trim_end_matches('/'));
 
     let params = [
@@ -31,12 +30,14 @@ pub async fn refresh_access_token(
         ("client_secret", client_secret),
     ];
 
-    let response = client
-        .post(&token_url)
-        .form(&params)
-        .header("Accept", "application/json")
-        .header("Content-Type", "application/x-www-form-urlencoded")
-        .send()
+    let response = HttpClient::shared()
+        .send(|client| {
+            client
+                .post(&token_url)
+                .form(&params)
+                .header("Accept", "application/json")
+                .header("Content-Type", "application/x-www-form-urlencoded")
+        })
         .await
         .map_err(|e| format!("Token refresh request failed: {}", e))?;
 
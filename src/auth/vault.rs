@@ -1,19 +1,45 @@
+use crate::auth::credential_store::{self, CredentialStore};
+use crate::environment::native::HttpClient;
 use anyhow::{Context, Result};
 use chrono::{Duration, Utc};
-use keyring::Entry;
-use reqwest::Client;
 use serde::{Deserialize, Serialize};
 use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
 
-/// Simple keyring-based credential vault
+/// Credential vault over a [`CredentialStore`]
 ///
-/// This provides a secure credential storage system using the OS keyring.
+/// Entries live in the OS keyring, or in an encrypted file where no keyring
+/// is available (see [`credential_store::default_store`]).
 pub struct CredentialVault {
-    service_name: String,
-    http_client: Client,
+    store: Arc<dyn CredentialStore>,
     provider_registry_key: String,
 }
 
+/// Keyring entry holding the active account's key
+const ACTIVE_ACCOUNT_KEY: &str = "active_account";
+
+/// Credentials kept per signed-in account
+const ACCOUNT_CREDENTIALS: [CredentialType; 4] = [
+    CredentialType::AccessToken,
+    CredentialType::RefreshToken,
+    CredentialType::UserInfo,
+    CredentialType::ClientCredentials,
+];
+
+/// Vault key for one signed-in account: `{provider}:{account_id}`
+///
+/// Provider names never contain `:`. Provider-only keys (`google`) hold
+/// application secrets, and the credentials of the single account that could
+/// be signed in before accounts were keyed separately.
+pub fn account_key(provider: &str, account_id: &str) -> String {
+    format!("{}:{}", provider, account_id)
+}
+
+/// Provider part of a vault key (`google:1234` → `google`)
+pub fn provider_of(key: &str) -> &str {
+    key.split_once(':').map_or(key, |(provider, _)| provider)
+}
+
 /// Types of credentials that can be stored in the vault
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum CredentialType {
@@ -27,6 +53,8 @@ pub enum CredentialType {
     ClientCredentials,
     /// Application secret
     ApplicationSecret,
+    /// Inference backend API key (Anthropic, OpenAI)
+    ApiKey,
 }
 
 impl CredentialType {
@@ -38,6 +66,7 @@ impl CredentialType {
             CredentialType::UserInfo => "user_info",
             CredentialType::ClientCredentials => "client_credentials",
             CredentialType::ApplicationSecret => "app_secret",
+            CredentialType::ApiKey => "api_key",
         }
     }
 }
@@ -60,6 +89,12 @@ pub struct UserInfo {
     pub provider: String,
 }
 
+/// The provider rejected a refresh token: access was revoked or the token
+/// expired, so the account has to sign in again
+#[derive(Debug, thiserror::Error)]
+#[error("Refresh token for '{0}' is no longer valid")]
+pub struct RefreshTokenRevoked(pub String);
+
 /// OAuth token refresh response
 #[derive(Debug, Deserialize)]
 struct TokenRefreshResponse {
@@ -69,31 +104,33 @@ struct TokenRefreshResponse {
 }
 
 impl CredentialVault {
-    /// Create a new CredentialVault instance with keyring storage
+    /// Create a new CredentialVault instance
+    ///
+    /// Entries are stored under the active profile's keyring service name.
     pub async fn new() -> Result<Self> {
-        let service_name = "cyrup-chat".to_string();
-        let http_client = Client::new();
-
-        log::info!("CredentialVault initialized successfully with keyring storage");
-
-        Ok(Self {
-            service_name,
-            http_client,
-            provider_registry_key: "providers_index".to_string(),
-        })
+        let service_name = crate::config::profile::active_profile()
+            .map_err(anyhow::Error::msg)?
+            .vault_service_name();
+        Self::with_config(&service_name).await
     }
 
     /// Create a vault with custom service name (for testing)
     pub async fn with_config(service_name: &str) -> Result<Self> {
         log::debug!("Initializing vault with service name: {}", service_name);
+        Ok(Self::with_store(credential_store::default_store(service_name)?))
+    }
 
-        let http_client = Client::new();
-
-        Ok(Self {
-            service_name: service_name.to_string(),
-            http_client,
+    /// Create a vault over a specific store (headless tests)
+    pub fn with_store(store: Arc<dyn CredentialStore>) -> Self {
+        Self {
+            store,
             provider_registry_key: "providers_index".to_string(),
-        })
+        }
+    }
+
+    /// Name of the storage backend in use
+    pub fn backend(&self) -> &'static str {
+        self.store.backend()
     }
 
     /// Generate a credential key for the given provider and credential type
@@ -119,12 +156,9 @@ impl CredentialVault {
         let serialized =
             serde_json::to_string(&stored_credential).context("Failed to serialize credential")?;
 
-        let entry =
-            Entry::new(&self.service_name, &key).context("Failed to create keyring entry")?;
-
-        entry
-            .set_password(&serialized)
-            .context("Failed to store credential in keyring")?;
+        self.store
+            .set(&key, &serialized)
+            .context("Failed to store credential")?;
 
         log::debug!(
             "Stored credential for provider '{}', type '{:?}'",
@@ -145,11 +179,8 @@ impl CredentialVault {
     ) -> Result<Option<String>> {
         let key = self.credential_key(provider, credential_type);
 
-        let entry =
-            Entry::new(&self.service_name, &key).context("Failed to create keyring entry")?;
-
-        match entry.get_password() {
-            Ok(serialized) => {
+        match self.store.get(&key)? {
+            Some(serialized) => {
                 let stored_credential: StoredCredential = serde_json::from_str(&serialized)
                     .context("Failed to deserialize stored credential")?;
 
@@ -167,7 +198,7 @@ impl CredentialVault {
 
                 Ok(Some(stored_credential.value))
             }
-            Err(keyring::Error::NoEntry) => {
+            None => {
                 log::debug!(
                     "No credential found for provider '{}', type '{:?}'",
                     provider,
@@ -175,7 +206,27 @@ impl CredentialVault {
                 );
                 Ok(None)
             }
-            Err(e) => Err(anyhow::anyhow!("Failed to retrieve credential: {}", e)),
+        }
+    }
+
+    /// Expiry of a stored credential, whether or not it has passed
+    ///
+    /// Unlike [`Self::get_credential`], which hides expired credentials, this
+    /// lets the refresh scheduler see when each access token runs out.
+    pub async fn credential_expires_at(
+        &self,
+        provider: &str,
+        credential_type: &CredentialType,
+    ) -> Result<Option<chrono::DateTime<Utc>>> {
+        let key = self.credential_key(provider, credential_type);
+
+        match self.store.get(&key)? {
+            Some(serialized) => {
+                let stored_credential: StoredCredential = serde_json::from_str(&serialized)
+                    .context("Failed to deserialize stored credential")?;
+                Ok(stored_credential.expires_at)
+            }
+            None => Ok(None),
         }
     }
 
@@ -187,28 +238,24 @@ impl CredentialVault {
     ) -> Result<bool> {
         let key = self.credential_key(provider, credential_type);
 
-        let entry =
-            Entry::new(&self.service_name, &key).context("Failed to create keyring entry")?;
-
-        match entry.delete_credential() {
-            Ok(_) => {
-                log::debug!(
-                    "Deleted credential for provider '{}', type '{:?}'",
-                    provider,
-                    credential_type
-                );
-                Ok(true)
-            }
-            Err(keyring::Error::NoEntry) => {
-                log::debug!(
-                    "No credential to delete for provider '{}', type '{:?}'",
-                    provider,
-                    credential_type
-                );
-                Ok(false)
-            }
-            Err(e) => Err(anyhow::anyhow!("Failed to delete credential: {}", e)),
+        let deleted = self
+            .store
+            .delete(&key)
+            .context("Failed to delete credential")?;
+        if deleted {
+            log::debug!(
+                "Deleted credential for provider '{}', type '{:?}'",
+                provider,
+                credential_type
+            );
+        } else {
+            log::debug!(
+                "No credential to delete for provider '{}', type '{:?}'",
+                provider,
+                credential_type
+            );
         }
+        Ok(deleted)
     }
 
     /// Delete all credentials for a provider
@@ -219,6 +266,7 @@ impl CredentialVault {
             CredentialType::UserInfo,
             CredentialType::ClientCredentials,
             CredentialType::ApplicationSecret,
+            CredentialType::ApiKey,
         ];
 
         let mut deleted_count = 0;
@@ -238,6 +286,9 @@ impl CredentialVault {
         {
             log::warn!("Failed to update provider registry after delete: {}", err);
         }
+        if self.active_account()?.as_deref() == Some(provider) {
+            self.set_active_account(None)?;
+        }
         Ok(deleted_count)
     }
 
@@ -264,6 +315,73 @@ impl CredentialVault {
         Ok(providers)
     }
 
+    /// Keys of all accounts with credentials in the vault, sorted
+    pub fn list_account_keys(&self) -> Result<Vec<String>> {
+        let mut keys: Vec<_> = self
+            .read_provider_registry()?
+            .into_iter()
+            .filter(|key| key.contains(':'))
+            .collect();
+        keys.sort();
+        Ok(keys)
+    }
+
+    /// Key of the account the user last signed in with or switched to
+    pub fn active_account(&self) -> Result<Option<String>> {
+        self.store
+            .get(ACTIVE_ACCOUNT_KEY)
+            .context("Failed to read active account")
+    }
+
+    /// Remember the active account, or forget it with `None`
+    pub fn set_active_account(&self, key: Option<&str>) -> Result<()> {
+        match key {
+            Some(key) => self
+                .store
+                .set(ACTIVE_ACCOUNT_KEY, key)
+                .context("Failed to store active account"),
+            None => self
+                .store
+                .delete(ACTIVE_ACCOUNT_KEY)
+                .map(|_| ())
+                .context("Failed to clear active account"),
+        }
+    }
+
+    /// Move credentials stored under a bare provider key to an account key
+    ///
+    /// Credentials saved before accounts were keyed separately live under
+    /// `google`, `github`, ... Returns the new account key if there was
+    /// anything to move.
+    pub async fn migrate_legacy_credentials(&self, provider: &str) -> Result<Option<String>> {
+        let Some(user_info) = self.get_user_info(provider).await? else {
+            return Ok(None);
+        };
+        let key = account_key(provider, &user_info.id);
+
+        for credential_type in &ACCOUNT_CREDENTIALS {
+            let from = self.credential_key(provider, credential_type);
+            // Copy the raw entry so expiry metadata survives
+            let Some(serialized) = self.store.get(&from).context("Failed to read credential")?
+            else {
+                continue;
+            };
+            self.store
+                .set(&self.credential_key(&key, credential_type), &serialized)
+                .context("Failed to store credential")?;
+            self.store
+                .delete(&from)
+                .context("Failed to delete credential")?;
+        }
+
+        self.register_provider(&key)?;
+        if self.active_account()?.is_none() {
+            self.set_active_account(Some(&key))?;
+        }
+        log::info!("Moved '{}' credentials to account key '{}'", provider, key);
+        Ok(Some(key))
+    }
+
     /// Get a valid access token for the provider, refreshing if necessary
     pub async fn get_valid_access_token(&self, provider: &str) -> Result<String> {
         // First check if we have a valid access token
@@ -295,6 +413,37 @@ impl CredentialVault {
         }
     }
 
+    /// OAuth client an account's tokens were issued to
+    ///
+    /// Accounts store their client with their tokens. Google and GitHub
+    /// accounts signed in before that fall back to the application's client
+    /// secrets; `None` means the account's tokens can't be refreshed.
+    pub async fn client_credentials(&self, provider: &str) -> Result<Option<serde_json::Value>> {
+        if let Some(creds) = self
+            .get_credential(provider, &CredentialType::ClientCredentials)
+            .await?
+        {
+            let creds =
+                serde_json::from_str(&creds).context("Failed to parse client credentials")?;
+            return Ok(Some(creds));
+        }
+
+        let app = provider_of(provider);
+        if !matches!(app, "google" | "github") {
+            return Ok(None);
+        }
+        let client_id = self.get_application_secret(app, "client_id").await?;
+        let client_secret = self.get_application_secret(app, "client_secret").await?;
+        Ok(client_id
+            .zip(client_secret)
+            .map(|(client_id, client_secret)| {
+                serde_json::json!({
+                    "client_id": client_id,
+                    "client_secret": client_secret,
+                })
+            }))
+    }
+
     /// Refresh the access token using the stored refresh token
     pub async fn refresh_token_if_needed(&self, provider: &str) -> Result<()> {
         // Get the refresh token
@@ -312,33 +461,29 @@ impl CredentialVault {
         };
 
         // Get client credentials for the token refresh
-        let (client_id, client_secret) = match self
-            .get_credential(provider, &CredentialType::ClientCredentials)
-            .await?
-        {
-            Some(creds_str) => {
-                let creds: serde_json::Value = serde_json::from_str(&creds_str)
-                    .context("Failed to parse client credentials")?;
-                let client_id = creds["client_id"]
-                    .as_str()
-                    .ok_or_else(|| anyhow::anyhow!("Missing client_id in credentials"))?;
-                let client_secret = creds["client_secret"]
-                    .as_str()
-                    .ok_or_else(|| anyhow::anyhow!("Missing client_secret in credentials"))?;
-                (client_id.to_string(), client_secret.to_string())
-            }
-            None => {
-                return Err(anyhow::anyhow!(
-                    "No client credentials available for provider '{}'",
-                    provider
-                ));
-            }
-        };
-
-        // Determine the token endpoint based on provider
-        let token_endpoint = match provider {
-            "google" => "https://oauth2.googleapis.com/token",
-            "github" => "https://github.com/login/oauth/access_token",
+        let creds = self.client_credentials(provider).await?.ok_or_else(|| {
+            anyhow::anyhow!(
+                "No client credentials available for provider '{}'",
+                provider
+            )
+        })?;
+        let client_id = creds["client_id"]
+            .as_str()
+            .ok_or_else(|| anyhow::anyhow!("Missing client_id in credentials"))?
+            .to_string();
+        // Public clients (OIDC with PKCE) have no secret
+        let client_secret = creds["client_secret"]
+            .as_str()
+            .unwrap_or_default()
+            .to_string();
+        let stored_endpoint = creds["token_endpoint"].as_str().map(str::to_string);
+
+        // Determine the token endpoint: stored with the credentials
+        // (OpenID Connect providers) or based on provider
+        let token_endpoint = match (stored_endpoint.as_deref(), provider_of(provider)) {
+            (Some(endpoint), _) => endpoint,
+            (None, "google") => "https://oauth2.googleapis.com/token",
+            (None, "github") => "https://github.com/login/oauth/access_token",
             _ => return Err(anyhow::anyhow!("Unsupported provider: {}", provider)),
         };
 
@@ -347,33 +492,37 @@ impl CredentialVault {
         params.insert("grant_type", "refresh_token");
         params.insert("refresh_token", &refresh_token);
         params.insert("client_id", &client_id);
-        params.insert("client_secret", &client_secret);
+        if !client_secret.is_empty() {
+            params.insert("client_secret", &client_secret);
+        }
 
         log::debug!("Refreshing access token for provider '{}'", provider);
 
         // Make the refresh request
-        let response = self
-            .http_client
-            .post(token_endpoint)
-            .form(&params)
-            .send()
+        let response = HttpClient::shared()
+            .send(|client| client.post(token_endpoint).form(&params))
             .await
             .context("Failed to send token refresh request")?;
 
-        if !response.status().is_success() {
-            let status = response.status();
-            let error_text = response.text().await.unwrap_or_default();
+        let status = response.status();
+        let body = response
+            .text()
+            .await
+            .context("Failed to read token refresh response")?;
+
+        if is_refresh_token_rejected(&body) {
+            return Err(RefreshTokenRevoked(provider.to_string()).into());
+        }
+        if !status.is_success() {
             return Err(anyhow::anyhow!(
                 "Token refresh failed with status {}: {}",
                 status,
-                error_text
+                body
             ));
         }
 
-        let token_response: TokenRefreshResponse = response
-            .json()
-            .await
-            .context("Failed to parse token refresh response")?;
+        let token_response: TokenRefreshResponse =
+            serde_json::from_str(&body).context("Failed to parse token refresh response")?;
 
         // Calculate expiration time
         let expires_at = token_response.expires_in.map(|expires_in| {
@@ -454,12 +603,9 @@ impl CredentialVault {
         secret_value: &str,
     ) -> Result<()> {
         let key = format!("{}_{}", provider, secret_name);
-        let entry = Entry::new(&self.service_name, &key)
-            .context("Failed to create keyring entry for application secret")?;
-
-        entry
-            .set_password(secret_value)
-            .context("Failed to store application secret in keyring")?;
+        self.store
+            .set(&key, secret_value)
+            .context("Failed to store application secret")?;
 
         log::debug!(
             "Stored application secret '{}' for provider '{}'",
@@ -477,17 +623,21 @@ impl CredentialVault {
         secret_name: &str,
     ) -> Result<Option<String>> {
         let key = format!("{}_{}", provider, secret_name);
-        let entry = Entry::new(&self.service_name, &key)
-            .context("Failed to create keyring entry for application secret")?;
-
-        match entry.get_password() {
-            Ok(secret) => Ok(Some(secret)),
-            Err(keyring::Error::NoEntry) => Ok(None),
-            Err(e) => Err(anyhow::anyhow!(
-                "Failed to retrieve application secret: {}",
-                e
-            )),
-        }
+        self.store
+            .get(&key)
+            .context("Failed to retrieve application secret")
+    }
+
+    /// Delete an application secret; returns whether it existed
+    pub async fn delete_application_secret(
+        &self,
+        provider: &str,
+        secret_name: &str,
+    ) -> Result<bool> {
+        let key = format!("{}_{}", provider, secret_name);
+        self.store
+            .delete(&key)
+            .context("Failed to delete application secret")
     }
 
     /// Seed application secrets from environment variables for known OAuth providers.
@@ -593,49 +743,133 @@ impl CredentialVault {
     }
 
     fn read_provider_registry(&self) -> Result<HashSet<String>> {
-        let entry = Entry::new(&self.service_name, &self.provider_registry_key)
-            .context("Failed to create keyring entry for provider registry")?;
-
-        match entry.get_password() {
-            Ok(serialized) => {
+        match self
+            .store
+            .get(&self.provider_registry_key)
+            .context("Failed to read provider registry")?
+        {
+            Some(serialized) => {
                 let providers: HashSet<String> = serde_json::from_str(&serialized)
                     .context("Failed to deserialize provider registry")?;
                 Ok(providers)
             }
-            Err(keyring::Error::NoEntry) => Ok(HashSet::new()),
-            Err(e) => Err(anyhow::anyhow!("Failed to read provider registry: {}", e)),
+            None => Ok(HashSet::new()),
         }
     }
 
     fn write_provider_registry(&self, providers: &HashSet<String>) -> Result<()> {
-        let entry = Entry::new(&self.service_name, &self.provider_registry_key)
-            .context("Failed to create keyring entry for provider registry")?;
-
         if providers.is_empty() {
-            match entry.delete_credential() {
-                Ok(_) | Err(keyring::Error::NoEntry) => {
-                    return Ok(());
-                }
-                Err(e) => {
-                    return Err(anyhow::anyhow!(
-                        "Failed to delete provider registry entry: {}",
-                        e
-                    ));
-                }
-            }
+            return self
+                .store
+                .delete(&self.provider_registry_key)
+                .map(|_| ())
+                .context("Failed to delete provider registry entry");
         }
 
         let serialized =
             serde_json::to_string(providers).context("Failed to serialize provider registry")?;
-        entry
-            .set_password(&serialized)
+        self.store
+            .set(&self.provider_registry_key, &serialized)
             .context("Failed to store provider registry")
     }
 
     /// Close the vault connection
     pub async fn close(&self) -> Result<()> {
-        // No explicit cleanup needed for keyring storage
+        // No explicit cleanup needed; stores write through on every change
         log::info!("Vault connection closed");
         Ok(())
     }
 }
+
+/// Whether a token endpoint response says the refresh token itself is dead
+///
+/// RFC 6749 §5.2 providers (Google, OpenID Connect) answer 400 `invalid_grant`;
+/// GitHub answers 200 with `bad_refresh_token`. Other errors may be transient.
+fn is_refresh_token_rejected(body: &str) -> bool {
+    serde_json::from_str::<serde_json::Value>(body)
+        .ok()
+        .and_then(|json| json.get("error")?.as_str().map(str::to_string))
+        .is_some_and(|error| matches!(error.as_str(), "invalid_grant" | "bad_refresh_token"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_refresh_token_rejection() {
+        assert!(is_refresh_token_rejected(
+            r#"{"error":"invalid_grant","error_description":"Token has been expired or revoked."}"#
+        ));
+        assert!(is_refresh_token_rejected(
+            r#"{"error":"bad_refresh_token","error_description":"The refresh token passed is incorrect or expired."}"#
+        ));
+        assert!(!is_refresh_token_rejected(r#"{"error":"temporarily_unavailable"}"#));
+        assert!(!is_refresh_token_rejected(r#"{"access_token":"abc","expires_in":3600}"#));
+        assert!(!is_refresh_token_rejected("<html>Bad Gateway</html>"));
+    }
+
+    #[test]
+    fn test_account_keys() {
+        assert_eq!(account_key("google", "1234"), "google:1234");
+        assert_eq!(provider_of("google:1234"), "google");
+        assert_eq!(provider_of("oidc_corp:a:b"), "oidc_corp");
+        assert_eq!(provider_of("github"), "github");
+    }
+
+    #[tokio::test]
+    async fn test_vault_over_encrypted_file_store() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = credential_store::EncryptedFileStore::open(
+            dir.path().join("vault.credentials"),
+            b"test passphrase",
+        )
+        .unwrap();
+        let vault = CredentialVault::with_store(Arc::new(store));
+        assert_eq!(vault.backend(), "encrypted-file");
+
+        let key = account_key("google", "1234");
+        vault
+            .store_credential(&key, CredentialType::AccessToken, "access".to_string(), None)
+            .await
+            .unwrap();
+        let expired = Utc::now() - Duration::minutes(1);
+        vault
+            .store_credential(
+                &key,
+                CredentialType::RefreshToken,
+                "refresh".to_string(),
+                Some(expired),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(
+            vault
+                .get_credential(&key, &CredentialType::AccessToken)
+                .await
+                .unwrap()
+                .as_deref(),
+            Some("access")
+        );
+        // Expired credentials are hidden but keep their expiry
+        assert_eq!(
+            vault
+                .get_credential(&key, &CredentialType::RefreshToken)
+                .await
+                .unwrap(),
+            None
+        );
+        assert_eq!(
+            vault
+                .credential_expires_at(&key, &CredentialType::RefreshToken)
+                .await
+                .unwrap(),
+            Some(expired)
+        );
+        assert_eq!(vault.list_account_keys().unwrap(), [key.clone()]);
+
+        assert!(vault.delete_provider_credentials(&key).await.unwrap() > 0);
+        assert!(vault.list_account_keys().unwrap().is_empty());
+    }
+}
@@ -3,7 +3,9 @@
 //! VaultAuthManager provides a convenient interface for all authentication operations
 //! while ensuring credentials are always accessed from the secure vault when needed.
 
-use crate::auth::vault::UserInfo as VaultUserInfo;
+use crate::auth::refresh;
+use crate::auth::revocation::RevocationClient;
+use crate::auth::vault::{self, UserInfo as VaultUserInfo};
 use crate::auth::{AuthState, CredentialVault, Provider, UserInfo};
 use anyhow::Result;
 use serde_json::{Map, Value};
@@ -15,6 +17,8 @@ use serde_json::{Map, Value};
 /// No credentials are stored in memory - everything goes through the vault.
 pub struct VaultAuthManager {
     vault: CredentialVault,
+    /// Revokes tokens at the provider on sign-out; `None` only signs out locally
+    revocation: Option<RevocationClient>,
 }
 
 /// Authentication status for a provider
@@ -33,6 +37,19 @@ pub struct AuthResult {
     pub was_refreshed: bool,
 }
 
+/// Outcome of signing out one account
+#[derive(Debug, Clone, Default)]
+pub struct SignOutResult {
+    /// The account signed out, if one was active
+    pub account: Option<AuthState>,
+    /// The account active afterwards, if any is left
+    pub next: Option<AuthState>,
+    /// Whether the provider confirmed the tokens are revoked
+    pub tokens_revoked: bool,
+    /// Why revocation failed, if it did
+    pub revocation_error: Option<String>,
+}
+
 // Note: Default implementation removed since VaultAuthManager::new() is now async
 
 impl VaultAuthManager {
@@ -40,12 +57,25 @@ impl VaultAuthManager {
     pub async fn new() -> Result<Self> {
         Ok(Self {
             vault: CredentialVault::new().await?,
+            revocation: Some(RevocationClient::new()),
         })
     }
 
     /// Create a VaultAuthManager with a custom vault (for testing)
+    ///
+    /// Sign-out stays local unless a client is given with
+    /// [`with_revocation`](Self::with_revocation).
     pub fn with_vault(vault: CredentialVault) -> Self {
-        Self { vault }
+        Self {
+            vault,
+            revocation: None,
+        }
+    }
+
+    /// Revoke tokens at the provider through `client` on sign-out
+    pub fn with_revocation(mut self, client: RevocationClient) -> Self {
+        self.revocation = Some(client);
+        self
     }
 
     /// Initialize application secrets from embedded data (call on first run)
@@ -56,52 +86,30 @@ impl VaultAuthManager {
 
     /// Check if any user is currently authenticated
     pub async fn is_any_user_authenticated(&self) -> Result<bool> {
-        for provider in [Provider::Google, Provider::GitHub] {
-            let provider_name = provider.to_string().to_lowercase();
-            if self.vault.is_authenticated(&provider_name).await? {
-                return Ok(true);
-            }
-        }
-        Ok(false)
+        Ok(!self.list_accounts().await?.is_empty())
     }
 
     /// Get authentication status for a specific provider
+    ///
+    /// With several accounts of the provider signed in, this reports the
+    /// active one if it belongs to the provider, otherwise the first.
     pub async fn get_auth_status(&self, provider: Provider) -> Result<Option<AuthStatus>> {
-        let provider_name = provider.to_string().to_lowercase();
-
-        if !self.vault.is_authenticated(&provider_name).await? {
+        let Some(account) = self.provider_account(&provider).await? else {
             return Ok(None);
-        }
-
-        // Get user info from vault
-        let vault_user_info = self
-            .vault
-            .get_user_info(&provider_name)
-            .await?
-            .ok_or_else(|| {
-                anyhow::anyhow!(
-                    "User authenticated but no user info found for {}",
-                    provider_name
-                )
-            })?;
-
-        let user_info = UserInfo {
-            id: vault_user_info.id,
-            email: vault_user_info.email,
-            name: vault_user_info.name,
-            picture: vault_user_info.avatar_url.unwrap_or_default(),
-            username: None, // Will be populated by provider-specific logic if needed
         };
 
         // Check if we have a valid access token
         let has_valid_token = self
             .vault
-            .is_credential_valid(&provider_name, &crate::auth::CredentialType::AccessToken)
+            .is_credential_valid(
+                &account.account_key(),
+                &crate::auth::CredentialType::AccessToken,
+            )
             .await?;
 
         Ok(Some(AuthStatus {
             provider,
-            user: user_info,
+            user: account.user,
             is_authenticated: true,
             has_valid_token,
         }))
@@ -111,7 +119,7 @@ impl VaultAuthManager {
     pub async fn get_all_auth_status(&self) -> Result<Vec<AuthStatus>> {
         let mut statuses = Vec::new();
 
-        for provider in [Provider::Google, Provider::GitHub] {
+        for provider in Provider::configured().await {
             if let Some(status) = self.get_auth_status(provider).await? {
                 statuses.push(status);
             }
@@ -120,42 +128,136 @@ impl VaultAuthManager {
         Ok(statuses)
     }
 
-    /// Get the currently authenticated user (first one found)
-    pub async fn get_current_user(&self) -> Result<Option<AuthState>> {
-        for provider in [Provider::Google, Provider::GitHub] {
+    /// All signed-in accounts, including several of the same provider
+    ///
+    /// Credentials saved before accounts were keyed separately are moved to
+    /// their account key first.
+    pub async fn list_accounts(&self) -> Result<Vec<AuthState>> {
+        for provider in Provider::configured().await {
             let provider_name = provider.to_string().to_lowercase();
+            if let Err(e) = self.vault.migrate_legacy_credentials(&provider_name).await {
+                log::warn!("Could not migrate {} credentials: {}", provider_name, e);
+            }
+        }
 
-            if self.vault.is_authenticated(&provider_name).await?
-                && let Some(vault_user_info) = self.vault.get_user_info(&provider_name).await?
+        let mut accounts = Vec::new();
+        for key in self.vault.list_account_keys()? {
+            if !self.vault.is_authenticated(&key).await? {
+                continue;
+            }
+            if let Some(account) = self
+                .vault
+                .get_user_info(&key)
+                .await?
+                .and_then(account_from_vault)
             {
-                let user_info = UserInfo {
-                    id: vault_user_info.id,
-                    email: vault_user_info.email,
-                    name: vault_user_info.name,
-                    picture: vault_user_info.avatar_url.unwrap_or_default(),
-                    username: None,
-                };
-
-                return Ok(Some(AuthState {
-                    provider,
-                    user: user_info,
-                }));
+                accounts.push(account);
             }
         }
+        Ok(accounts)
+    }
+
+    /// Get the active account, or the first signed-in one if none is active
+    pub async fn get_current_user(&self) -> Result<Option<AuthState>> {
+        let mut accounts = self.list_accounts().await?;
+        let active_key = self.vault.active_account()?;
+        let index = accounts
+            .iter()
+            .position(|account| Some(account.account_key()) == active_key)
+            .unwrap_or(0);
+
+        if index < accounts.len() {
+            Ok(Some(accounts.swap_remove(index)))
+        } else {
+            Ok(None)
+        }
+    }
+
+    /// Make a signed-in account the active one
+    pub async fn switch_account(&self, account: &AuthState) -> Result<()> {
+        let account_key = account.account_key();
+        self.vault.set_active_account(Some(&account_key))?;
+        log::info!("Switched active account to {}", account_key);
+        Ok(())
+    }
+
+    /// Sign out one account and switch to the next signed-in one
+    ///
+    /// The account's tokens are revoked at the provider first (best effort),
+    /// then deleted from the vault whether or not revocation succeeded.
+    pub async fn sign_out_account(&self, account: &AuthState) -> Result<SignOutResult> {
+        let account_key = account.account_key();
+        let revocation = self.revoke_tokens(&account_key).await;
+        self.vault.delete_provider_credentials(&account_key).await?;
+        log::info!("Signed out account: {}", account_key);
+
+        let next = self.get_current_user().await?;
+        if let Some(next) = &next {
+            self.switch_account(next).await?;
+        }
+        Ok(SignOutResult {
+            account: Some(account.clone()),
+            next,
+            tokens_revoked: revocation.as_ref().is_ok_and(|revoked| *revoked),
+            revocation_error: revocation.err(),
+        })
+    }
+
+    /// Revoke an account's tokens at its provider, logging failures
+    async fn revoke_tokens(&self, account_key: &str) -> Result<bool, String> {
+        let Some(revocation) = &self.revocation else {
+            return Ok(false);
+        };
+        revocation
+            .revoke_account(&self.vault, account_key)
+            .await
+            .map_err(|e| {
+                log::warn!("Could not revoke tokens of {}: {}", account_key, e);
+                e.to_string()
+            })
+    }
+
+    /// The account used for provider-level operations
+    ///
+    /// Prefers the active account when it belongs to `provider`.
+    async fn provider_account(&self, provider: &Provider) -> Result<Option<AuthState>> {
+        let active_key = self.vault.active_account()?;
+        let mut accounts: Vec<_> = self
+            .list_accounts()
+            .await?
+            .into_iter()
+            .filter(|account| &account.provider == provider)
+            .collect();
+        let index = accounts
+            .iter()
+            .position(|account| Some(account.account_key()) == active_key)
+            .unwrap_or(0);
+
+        if index < accounts.len() {
+            Ok(Some(accounts.swap_remove(index)))
+        } else {
+            Ok(None)
+        }
+    }
 
-        Ok(None)
+    /// Vault key of the account used for provider-level operations
+    async fn provider_account_key(&self, provider: &Provider) -> Result<String> {
+        self.provider_account(provider)
+            .await?
+            .map(|account| account.account_key())
+            .ok_or_else(|| anyhow::anyhow!("No signed-in {} account", provider.name()))
     }
 
     /// Get a valid access token for the provider (refreshing if necessary)
     pub async fn get_access_token(&self, provider: Provider) -> Result<String> {
-        let provider_name = provider.to_string().to_lowercase();
-        self.vault.get_valid_access_token(&provider_name).await
+        let account_key = self.provider_account_key(&provider).await?;
+        self.vault.get_valid_access_token(&account_key).await
     }
 
     /// Refresh tokens for a provider if needed
     pub async fn refresh_tokens(&self, provider: Provider) -> Result<()> {
-        let provider_name = provider.to_string().to_lowercase();
-        self.vault.refresh_token_if_needed(&provider_name).await
+        let account_key = self.provider_account_key(&provider).await?;
+        self.vault.refresh_token_if_needed(&account_key).await
     }
 
     /// Store authentication result after successful OAuth login
@@ -167,12 +269,16 @@ impl VaultAuthManager {
         refresh_token: Option<&str>,
         expires_at: Option<chrono::DateTime<chrono::Utc>>,
     ) -> Result<AuthState> {
-        let provider_name = provider.to_string().to_lowercase();
+        let auth_state = AuthState {
+            provider,
+            user: user_info.clone(),
+        };
+        let account_key = auth_state.account_key();
 
         // Store access token
         self.vault
             .store_credential(
-                &provider_name,
+                &account_key,
                 crate::auth::CredentialType::AccessToken,
                 access_token.to_string(),
                 expires_at,
@@ -183,7 +289,7 @@ impl VaultAuthManager {
         if let Some(refresh_token) = refresh_token {
             self.vault
                 .store_credential(
-                    &provider_name,
+                    &account_key,
                     crate::auth::CredentialType::RefreshToken,
                     refresh_token.to_string(),
                     None,
@@ -201,27 +307,32 @@ impl VaultAuthManager {
             } else {
                 Some(user_info.picture.clone())
             },
-            provider: provider_name,
+            provider: auth_state.provider.to_string().to_lowercase(),
         };
 
         self.vault
-            .store_user_info(&provider.to_string().to_lowercase(), &vault_user_info)
+            .store_user_info(&account_key, &vault_user_info)
             .await?;
+        self.switch_account(&auth_state).await?;
 
         log::info!(
-            "Successfully stored authentication result for provider: {:?}",
-            provider
+            "Successfully stored authentication result for account: {}",
+            account_key
         );
 
-        Ok(AuthState {
-            provider,
-            user: user_info.clone(),
-        })
+        Ok(auth_state)
     }
 
-    /// Logout from a specific provider
+    /// Logout every account of a specific provider
     pub async fn logout(&self, provider: Provider) -> Result<()> {
         let provider_name = provider.to_string().to_lowercase();
+        for account_key in self.vault.list_account_keys()? {
+            if vault::provider_of(&account_key) == provider_name {
+                let _ = self.revoke_tokens(&account_key).await;
+                self.vault.delete_provider_credentials(&account_key).await?;
+            }
+        }
+        // Credentials saved before accounts were keyed separately
         self.vault
             .delete_provider_credentials(&provider_name)
             .await?;
@@ -231,7 +342,7 @@ impl VaultAuthManager {
 
     /// Logout from all providers
     pub async fn logout_all(&self) -> Result<()> {
-        for provider in [Provider::Google, Provider::GitHub] {
+        for provider in Provider::configured().await {
             let _ = self.logout(provider).await; // Continue even if one fails
         }
         log::info!("Logged out from all providers");
@@ -240,10 +351,10 @@ impl VaultAuthManager {
 
     /// Check if tokens need refresh for a provider
     pub async fn needs_token_refresh(&self, provider: Provider) -> Result<bool> {
-        let provider_name = provider.to_string().to_lowercase();
+        let account_key = self.provider_account_key(&provider).await?;
         let is_valid = self
             .vault
-            .is_credential_valid(&provider_name, &crate::auth::CredentialType::AccessToken)
+            .is_credential_valid(&account_key, &crate::auth::CredentialType::AccessToken)
             .await?;
         Ok(!is_valid)
     }
@@ -278,7 +389,7 @@ impl VaultAuthManager {
 
     /// Get OAuth client credentials for a provider
     pub async fn get_oauth_credentials(&self, provider: Provider) -> Result<(String, String)> {
-        let client_id = self.get_app_secret(provider, "client_id").await?;
+        let client_id = self.get_app_secret(provider.clone(), "client_id").await?;
         let client_secret = self.get_app_secret(provider, "client_secret").await?;
         Ok((client_id, client_secret))
     }
@@ -340,31 +451,37 @@ impl VaultAuthManager {
         );
 
         // Get status for each provider
+        let accounts = self.list_accounts().await?;
         let mut providers = Map::new();
-        for provider in [Provider::Google, Provider::GitHub] {
+        for provider in Provider::configured().await {
             let provider_name = provider.to_string().to_lowercase();
+            let account_count = accounts
+                .iter()
+                .filter(|account| account.provider == provider)
+                .count();
+            let status = self.get_auth_status(provider).await?;
             let mut provider_info = Map::new();
 
             provider_info.insert(
                 "is_authenticated".to_string(),
-                Value::Bool(self.vault.is_authenticated(&provider_name).await?),
+                Value::Bool(status.is_some()),
             );
-
-            if let Some(user_info) = self.vault.get_user_info(&provider_name).await? {
-                provider_info.insert("user_email".to_string(), Value::String(user_info.email));
-                provider_info.insert("user_name".to_string(), Value::String(user_info.name));
+            provider_info.insert("accounts".to_string(), Value::from(account_count));
+
+            if let Some(status) = &status {
+                provider_info.insert(
+                    "user_email".to_string(),
+                    Value::String(status.user.email.clone()),
+                );
+                provider_info.insert(
+                    "user_name".to_string(),
+                    Value::String(status.user.name.clone()),
+                );
             }
 
             provider_info.insert(
                 "has_valid_token".to_string(),
-                Value::Bool(
-                    self.vault
-                        .is_credential_valid(
-                            &provider_name,
-                            &crate::auth::CredentialType::AccessToken,
-                        )
-                        .await?,
-                ),
+                Value::Bool(status.is_some_and(|status| status.has_valid_token)),
             );
 
             providers.insert(provider_name, Value::Object(provider_info));
@@ -387,10 +504,11 @@ impl VaultAuthManager {
         let mut health = Map::new();
 
         // Check vault accessibility
-        let vault_health = "healthy"; // Assume healthy if we can create the manager
+        let vault_accessible = self.vault.list_providers().is_ok();
+        health.insert("vault_accessible".to_string(), Value::Bool(vault_accessible));
         health.insert(
-            "vault_health".to_string(),
-            Value::String(vault_health.to_string()),
+            "credential_store".to_string(),
+            Value::String(self.vault.backend().to_string()),
         );
 
         // Check application secrets
@@ -404,24 +522,49 @@ impl VaultAuthManager {
         let auth_status = self.is_any_user_authenticated().await.unwrap_or(false);
         health.insert("user_authenticated".to_string(), Value::Bool(auth_status));
 
+        // Background token refresh
+        let refresh = refresh::refresh_status();
+        let reauth_required = !refresh.reauth_required.is_empty();
+        health.insert("token_refresh".to_string(), serde_json::to_value(&refresh)?);
+
         // Overall health
-        let overall_healthy = vault_health == "healthy" && secrets_configured;
+        let overall_healthy = vault_accessible && secrets_configured && !reauth_required;
         health.insert("overall_healthy".to_string(), Value::Bool(overall_healthy));
 
         Ok(health)
     }
 }
 
+/// Rebuild an account from the user info stored under its key
+pub(crate) fn account_from_vault(user_info: VaultUserInfo) -> Option<AuthState> {
+    let provider = Provider::from_vault_name(&user_info.provider)?;
+    Some(AuthState {
+        provider,
+        user: UserInfo {
+            id: user_info.id,
+            email: user_info.email,
+            name: user_info.name,
+            picture: user_info.avatar_url.unwrap_or_default(),
+            username: None, // Will be populated by provider-specific logic if needed
+        },
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::auth::CredentialVault;
+    use crate::auth::credential_store::{KeyringStore, mock_keyring};
+    use std::sync::Arc;
+
+    /// Vault over the in-memory keyring, so tests never need an unlock
+    fn test_vault(service_name: &str) -> CredentialVault {
+        mock_keyring::install();
+        CredentialVault::with_store(Arc::new(KeyringStore::new(service_name)))
+    }
 
     async fn test_manager() -> VaultAuthManager {
-        let vault = CredentialVault::with_config("ai.cyrup.chat.test.manager")
-            .await
-            .unwrap();
-        VaultAuthManager::with_vault(vault)
+        VaultAuthManager::with_vault(test_vault("ai.cyrup.chat.test.manager"))
     }
 
     #[tokio::test]
@@ -528,6 +671,102 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn test_accounts_of_one_provider_coexist() -> Result<()> {
+        let vault = test_vault("ai.cyrup.chat.test.accounts");
+        let manager = VaultAuthManager::with_vault(vault);
+        let _ = manager.logout_all().await;
+
+        let user = |id: &str| UserInfo {
+            id: id.to_string(),
+            email: format!("{id}@example.com"),
+            name: id.to_string(),
+            picture: String::new(),
+            username: None,
+        };
+
+        let work = manager
+            .store_auth_result(Provider::Google, &user("work"), "work-token", None, None)
+            .await?;
+        let home = manager
+            .store_auth_result(Provider::Google, &user("home"), "home-token", None, None)
+            .await?;
+
+        // Signing in a second Google account keeps the first one
+        assert_eq!(manager.list_accounts().await?.len(), 2);
+        assert_eq!(manager.get_current_user().await?, Some(home.clone()));
+        assert_eq!(manager.get_access_token(Provider::Google).await?, "home-token");
+
+        manager.switch_account(&work).await?;
+        assert_eq!(manager.get_current_user().await?, Some(work.clone()));
+        assert_eq!(manager.get_access_token(Provider::Google).await?, "work-token");
+
+        // Signing out the active account moves to the other one
+        assert_eq!(manager.sign_out_account(&work).await?.next, Some(home.clone()));
+        assert_eq!(manager.list_accounts().await?, vec![home.clone()]);
+        assert_eq!(manager.sign_out_account(&home).await?.next, None);
+        assert!(!manager.is_any_user_authenticated().await?);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_sign_out_revokes_at_provider_best_effort() -> Result<()> {
+        use crate::auth::credential_store::EncryptedFileStore;
+        use crate::auth::revocation::RevocationEndpoints;
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/revoke"))
+            .respond_with(ResponseTemplate::new(200))
+            .up_to_n_times(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/revoke"))
+            .respond_with(ResponseTemplate::new(401))
+            .mount(&server)
+            .await;
+
+        let dir = tempfile::tempdir()?;
+        let store = EncryptedFileStore::open(dir.path().join("vault.credentials"), b"test")?;
+        let manager = VaultAuthManager::with_vault(CredentialVault::with_store(
+            Arc::new(store),
+        ))
+        .with_revocation(RevocationClient::with_endpoints(RevocationEndpoints {
+            google: format!("{}/revoke", server.uri()),
+            github_api: server.uri(),
+        }));
+
+        let user = |id: &str| UserInfo {
+            id: id.to_string(),
+            email: format!("{id}@example.com"),
+            name: id.to_string(),
+            picture: String::new(),
+            username: None,
+        };
+        let first = manager
+            .store_auth_result(Provider::Google, &user("a"), "a-token", Some("a-refresh"), None)
+            .await?;
+        let second = manager
+            .store_auth_result(Provider::Google, &user("b"), "b-token", None, None)
+            .await?;
+
+        let signed_out = manager.sign_out_account(&first).await?;
+        assert!(signed_out.tokens_revoked);
+        assert_eq!(signed_out.next, Some(second.clone()));
+
+        // A refused revocation still signs the account out locally
+        let signed_out = manager.sign_out_account(&second).await?;
+        assert!(!signed_out.tokens_revoked);
+        assert!(signed_out.revocation_error.is_some());
+        assert!(!manager.is_any_user_authenticated().await?);
+
+        Ok(())
+    }
+
     #[tokio::test]
     async fn test_auth_summary() -> Result<()> {
         let manager = test_manager().await;
@@ -561,6 +800,7 @@ mod tests {
         assert!(health.contains_key("app_secrets_configured"));
         assert!(health.contains_key("user_authenticated"));
         assert!(health.contains_key("overall_healthy"));
+        assert!(health["token_refresh"].get("reauth_required").is_some());
 
         // Vault should be accessible
         assert_eq!(
@@ -0,0 +1,596 @@
+//! Headless command line front-end for CYRUP Chat
+//!
+//! Works on the same profile database as the desktop app through
+//! `Database` and `services::agent_chat`, so scripts and CI can read and
+//! drive the same history. SurrealKV holds an exclusive lock on the data
+//! directory: quit the desktop app (or use another `--profile` /
+//! `CYRUP_DATA_DIR`) while the CLI runs.
+
+use cyrup::auth::oidc::{self, OidcProviderConfig, OidcRegistry};
+use cyrup::config::profile::{Profile, active_profile, init_active_profile};
+use cyrup::database::backup::list_backups;
+use cyrup::database::{BackupConfig, Database};
+use cyrup::services::api_server::{self, ApiServer};
+use cyrup::services::{agent_chat, mcp_server};
+use cyrup::view_model::conversation::Conversation;
+use cyrup::view_model::message::{AuthorType, Message};
+use cyrup::view_model::transcript::Transcript;
+use futures_util::StreamExt;
+use std::collections::HashMap;
+use std::io::{Read, Write};
+use std::path::PathBuf;
+use std::sync::Arc;
+use surrealdb::Notification;
+use surrealdb_types::{Action, RecordId, ToSql};
+
+const USAGE: &str = "\
+Usage: cyrup-cli [--profile <name>] <command> [args]
+
+Commands:
+  conversations                     List conversations, most recent first
+  templates                         List agent templates
+  new <agent>... [--title <title>]  Start a conversation, print its ID
+  send <conversation> <message|->   Send a message and stream the reply
+       [--agent <agent>]...         Only ask these participants
+  tail <conversation> [-n <count>]  Print recent messages and follow new ones
+  export <conversation>             Write a transcript to stdout or a file
+       [--format json|markdown] [--output <file>]
+  import <file|->                   Import a JSON transcript as a new conversation
+  backups                           List database snapshots, oldest first
+  restore <snapshot>                Replace the database with a snapshot (file
+                                    name or path); the current state is
+                                    snapshotted first
+  mcp                               Serve MCP over stdin/stdout
+  serve [--port <port>]             Run the local API (REST, WebSocket and MCP
+                                    over HTTP) until interrupted
+  sso list                          List OpenID Connect sign-in providers
+  sso add <id> --issuer <url>       Add or update a provider
+       --client-id <id> [--name <label>] [--scope <scope>]...
+       [--port <port>] [--secret-stdin]
+                                    --secret-stdin reads the client secret
+                                    from stdin
+  sso remove <id>                   Remove a provider
+  sso login <id>                    Sign in with a provider in the browser
+
+Conversations and agents may be given by record ID (with or without the
+table prefix) or, for agents, by template name.";
+
+/// Messages shown by `tail` before following
+const DEFAULT_TAIL: usize = 20;
+
+#[tokio::main]
+async fn main() {
+    env_logger::Builder::from_env(env_logger::Env::default().default_filter_or("warn")).init();
+
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if let Err(e) = run(args).await {
+        eprintln!("cyrup-cli: {}", e);
+        std::process::exit(1);
+    }
+}
+
+async fn run(args: Vec<String>) -> Result<(), String> {
+    let profile = Profile::resolve(args.clone())?;
+    let args = strip_profile_args(args);
+
+    let Some((command, rest)) = args.split_first() else {
+        println!("{}", USAGE);
+        return Ok(());
+    };
+    if matches!(command.as_str(), "help" | "-h" | "--help") {
+        println!("{}", USAGE);
+        return Ok(());
+    }
+
+    init_active_profile(profile);
+    // Sign-in settings live outside the database
+    if command == "sso" {
+        return sso(rest).await;
+    }
+    let database = Arc::new(Database::new().await?);
+
+    match command.as_str() {
+        "conversations" | "ls" => list_conversations(&database).await,
+        "templates" => list_templates(&database).await,
+        "new" => new_conversation(&database, rest).await,
+        "send" => send(&database, rest).await,
+        "tail" => tail(&database, rest).await,
+        "export" => export(&database, rest).await,
+        "import" => import(&database, rest).await,
+        "backups" => backups().await,
+        "restore" => restore(&database, rest).await,
+        "mcp" => mcp_server::serve_stdio(database).await,
+        "serve" => serve(database, rest).await,
+        other => Err(format!("Unknown command '{}'\n\n{}", other, USAGE)),
+    }
+}
+
+/// Remove `--profile <name>` / `--profile=<name>`, already consumed by [`Profile::resolve`]
+fn strip_profile_args(args: Vec<String>) -> Vec<String> {
+    let mut out = Vec::with_capacity(args.len());
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        if arg == "--profile" {
+            args.next();
+        } else if !arg.starts_with("--profile=") {
+            out.push(arg);
+        }
+    }
+    out
+}
+
+/// Take the value of `--flag <value>` out of `args`, wherever it appears
+fn take_option(args: &mut Vec<String>, flags: &[&str]) -> Result<Option<String>, String> {
+    let Some(index) = args.iter().position(|a| flags.contains(&a.as_str())) else {
+        return Ok(None);
+    };
+    if index + 1 >= args.len() {
+        return Err(format!("{} needs a value", args[index]));
+    }
+    let value = args.remove(index + 1);
+    args.remove(index);
+    Ok(Some(value))
+}
+
+/// Take a bare `--flag` out of `args`, reporting whether it was present
+fn take_flag(args: &mut Vec<String>, flag: &str) -> bool {
+    let Some(index) = args.iter().position(|a| a == flag) else {
+        return false;
+    };
+    args.remove(index);
+    true
+}
+
+fn parse_record_id(table: &str, input: &str) -> Result<RecordId, String> {
+    if input.contains(':') {
+        RecordId::parse_simple(input).map_err(|e| format!("Invalid ID '{}': {}", input, e))
+    } else {
+        Ok(RecordId::new(table, input))
+    }
+}
+
+async fn sso(args: &[String]) -> Result<(), String> {
+    let registry = OidcRegistry::new().map_err(|e| e.to_string())?;
+    let mut args = args.to_vec();
+    let subcommand = if args.is_empty() {
+        "list".to_string()
+    } else {
+        args.remove(0)
+    };
+
+    match subcommand.as_str() {
+        "list" | "ls" => {
+            for provider in registry.list().await.map_err(|e| e.to_string())? {
+                println!(
+                    "{}\t{}\t{}\t{}",
+                    provider.id, provider.name, provider.issuer, provider.client_id
+                );
+            }
+            Ok(())
+        }
+        "add" => {
+            let issuer =
+                take_option(&mut args, &["--issuer"])?.ok_or("sso add needs --issuer <url>")?;
+            let client_id = take_option(&mut args, &["--client-id"])?
+                .ok_or("sso add needs --client-id <id>")?;
+            let name = take_option(&mut args, &["--name"])?;
+            let secret_stdin = take_flag(&mut args, "--secret-stdin");
+            let redirect_port = match take_option(&mut args, &["--port"])? {
+                Some(port) => port
+                    .parse()
+                    .map_err(|_| format!("Invalid port '{}'", port))?,
+                None => 0,
+            };
+            let mut scopes = Vec::new();
+            while let Some(scope) = take_option(&mut args, &["--scope"])? {
+                scopes.push(scope);
+            }
+            let [id] = args.as_slice() else {
+                return Err("sso add needs exactly one provider ID".to_string());
+            };
+            // Never on the command line, where `ps` and shell history would show it
+            let secret = if secret_stdin {
+                let secret = read_stdin()?.trim().to_string();
+                if secret.is_empty() {
+                    return Err("--secret-stdin got an empty secret".to_string());
+                }
+                Some(secret)
+            } else {
+                None
+            };
+
+            let provider = OidcProviderConfig {
+                id: id.clone(),
+                name: name.unwrap_or_else(|| id.clone()),
+                issuer,
+                client_id,
+                scopes: if scopes.is_empty() {
+                    vec!["openid".into(), "email".into(), "profile".into()]
+                } else {
+                    scopes
+                },
+                redirect_port,
+            };
+            registry.save(provider).await.map_err(|e| e.to_string())?;
+            if let Some(secret) = secret {
+                oidc::store_client_secret(id, &secret)
+                    .await
+                    .map_err(|e| e.to_string())?;
+            }
+            println!("Saved {}", id);
+            Ok(())
+        }
+        "remove" | "rm" => {
+            let [id] = args.as_slice() else {
+                return Err("sso remove needs a provider ID".to_string());
+            };
+            if !registry.remove(id).await.map_err(|e| e.to_string())? {
+                return Err(format!("No provider '{}'", id));
+            }
+            if let Err(e) = oidc::store_client_secret(id, "").await {
+                log::warn!("Could not delete the client secret: {}", e);
+            }
+            Ok(())
+        }
+        "login" => {
+            let [id] = args.as_slice() else {
+                return Err("sso login needs a provider ID".to_string());
+            };
+            let state = oidc::login(id).await.map_err(|e| e.to_string())?;
+            println!("Signed in as {} <{}>", state.user.name, state.user.email);
+            Ok(())
+        }
+        other => Err(format!("Unknown sso command '{}'\n\n{}", other, USAGE)),
+    }
+}
+
+/// Resolve an agent by template name (case-insensitive) or record ID
+async fn resolve_agent(database: &Database, input: &str) -> Result<RecordId, String> {
+    Ok(database.find_template(input).await?.id)
+}
+
+async fn list_conversations(database: &Database) -> Result<(), String> {
+    for conversation in database.list_conversations().await? {
+        let preview: String = conversation
+            .last_message_preview
+            .lines()
+            .next()
+            .unwrap_or_default()
+            .chars()
+            .take(60)
+            .collect();
+        println!(
+            "{}\t{}\t{}\t{}",
+            conversation.id.to_sql(),
+            conversation.last_message_timestamp.format("%Y-%m-%d %H:%M"),
+            conversation.title,
+            preview
+        );
+    }
+    Ok(())
+}
+
+async fn list_templates(database: &Database) -> Result<(), String> {
+    for template in database.list_templates().await? {
+        println!(
+            "{}\t{}\t{}\t{}",
+            template.id.to_sql(),
+            template.name,
+            template.backend,
+            template.model_name()
+        );
+    }
+    Ok(())
+}
+
+async fn new_conversation(database: &Database, args: &[String]) -> Result<(), String> {
+    let mut args = args.to_vec();
+    let title = take_option(&mut args, &["--title", "-t"])?;
+    if args.is_empty() {
+        return Err("new needs at least one agent".to_string());
+    }
+
+    let mut participants = Vec::with_capacity(args.len());
+    for agent in &args {
+        participants.push(resolve_agent(database, agent).await?);
+    }
+
+    let id = database
+        .create_conversation(&Conversation {
+            title: title.unwrap_or_else(|| "New conversation".to_string()),
+            participants,
+            last_message_at: chrono::Utc::now().into(),
+            ..Default::default()
+        })
+        .await?;
+
+    println!("{}", id.to_sql());
+    Ok(())
+}
+
+async fn send(database: &Arc<Database>, args: &[String]) -> Result<(), String> {
+    let mut args = args.to_vec();
+    let mut agents = Vec::new();
+    while let Some(agent) = take_option(&mut args, &["--agent", "-a"])? {
+        agents.push(resolve_agent(database, &agent).await?);
+    }
+    let [conversation, message @ ..] = args.as_slice() else {
+        return Err("send needs a conversation and a message".to_string());
+    };
+    let conversation_id = parse_record_id("conversation", conversation)?;
+    let message = match message {
+        [dash] if dash == "-" => read_stdin()?,
+        words => words.join(" "),
+    };
+    if message.trim().is_empty() {
+        return Err("send needs a message".to_string());
+    }
+
+    let conversation = database.get_conversation(&conversation_id).await?;
+    if let Some(agent) = agents
+        .iter()
+        .find(|a| !conversation.participants.contains(a))
+    {
+        return Err(format!("{} is not in this conversation", agent.to_sql()));
+    }
+
+    // Subscribe before sending so no reply chunk is missed
+    let mut stream = database.live_messages(&conversation_id).await?;
+    let mut printer = StreamPrinter::default();
+
+    let mut sending = tokio::spawn(agent_chat::send_message(
+        Arc::clone(database),
+        conversation_id,
+        message,
+        (!agents.is_empty()).then_some(agents),
+        false,
+        None,
+    ));
+
+    let result = loop {
+        tokio::select! {
+            notification = stream.next() => match notification {
+                Some(Ok(notification)) => printer.print(&notification),
+                Some(Err(e)) => log::warn!("[CLI] Live query error: {}", e),
+                None => break (&mut sending).await,
+            },
+            result = &mut sending => break result,
+        }
+    };
+
+    // Final content updates can land just after the send completes
+    while let Ok(Some(Ok(notification))) =
+        tokio::time::timeout(std::time::Duration::from_millis(250), stream.next()).await
+    {
+        printer.print(&notification);
+    }
+    printer.finish();
+
+    result.map_err(|e| format!("Send task failed: {}", e))?
+}
+
+async fn tail(database: &Arc<Database>, args: &[String]) -> Result<(), String> {
+    let mut args = args.to_vec();
+    let count = match take_option(&mut args, &["-n", "--lines"])? {
+        Some(n) => n.parse().map_err(|_| format!("Invalid count '{}'", n))?,
+        None => DEFAULT_TAIL,
+    };
+    let [conversation] = args.as_slice() else {
+        return Err("tail needs a conversation".to_string());
+    };
+    let conversation_id = parse_record_id("conversation", conversation)?;
+    database.get_conversation(&conversation_id).await?;
+
+    let mut stream = database.live_messages(&conversation_id).await?;
+    let mut printer = StreamPrinter {
+        echo_human: true,
+        ..Default::default()
+    };
+
+    let page = database
+        .get_messages_before(&conversation_id, None, count)
+        .await?;
+    for message in &page.messages {
+        printer.print_message(message);
+    }
+
+    while let Some(notification) = stream.next().await {
+        match notification {
+            Ok(notification) => printer.print(&notification),
+            Err(e) => log::warn!("[CLI] Live query error: {}", e),
+        }
+    }
+    printer.finish();
+    Ok(())
+}
+
+async fn export(database: &Database, args: &[String]) -> Result<(), String> {
+    let mut args = args.to_vec();
+    let format = take_option(&mut args, &["--format", "-f"])?;
+    let output = take_option(&mut args, &["--output", "-o"])?.map(PathBuf::from);
+    let [conversation] = args.as_slice() else {
+        return Err("export needs a conversation".to_string());
+    };
+    let conversation_id = parse_record_id("conversation", conversation)?;
+
+    let transcript = database.export_transcript(&conversation_id).await?;
+
+    // Default to Markdown when the output file says so
+    let markdown = match format.as_deref() {
+        Some("json") => false,
+        Some("markdown" | "md") => true,
+        Some(other) => return Err(format!("Unknown format '{}'", other)),
+        None => output
+            .as_ref()
+            .and_then(|p| p.extension())
+            .is_some_and(|ext| ext == "md"),
+    };
+    let contents = if markdown {
+        transcript.to_markdown()
+    } else {
+        serde_json::to_string_pretty(&transcript)
+            .map_err(|e| format!("Failed to serialize transcript: {}", e))?
+    };
+
+    match output {
+        Some(path) => tokio::fs::write(&path, contents)
+            .await
+            .map_err(|e| format!("Failed to write {}: {}", path.display(), e)),
+        None => std::io::stdout()
+            .write_all(contents.as_bytes())
+            .map_err(|e| format!("Failed to write output: {}", e)),
+    }
+}
+
+async fn import(database: &Database, args: &[String]) -> Result<(), String> {
+    let [source] = args else {
+        return Err("import needs a file (or - for stdin)".to_string());
+    };
+
+    let json = if source == "-" {
+        read_stdin()?
+    } else {
+        tokio::fs::read_to_string(source)
+            .await
+            .map_err(|e| format!("Failed to read {}: {}", source, e))?
+    };
+
+    let transcript: Transcript =
+        serde_json::from_str(&json).map_err(|e| format!("Invalid transcript: {}", e))?;
+    let id = database.import_transcript(&transcript).await?;
+
+    println!("{}", id.to_sql());
+    Ok(())
+}
+
+async fn backups() -> Result<(), String> {
+    let directory = active_profile()?.backup_dir();
+    for snapshot in list_backups(&directory).await? {
+        let size = tokio::fs::metadata(&snapshot)
+            .await
+            .map(|m| m.len())
+            .unwrap_or_default();
+        let name = snapshot.file_name().unwrap_or_default().to_string_lossy();
+        println!("{}\t{}", name, size);
+    }
+    Ok(())
+}
+
+async fn restore(database: &Database, args: &[String]) -> Result<(), String> {
+    let [snapshot] = args else {
+        return Err("restore needs a snapshot (see `cyrup-cli backups`)".to_string());
+    };
+    let config = BackupConfig::new(active_profile()?.backup_dir());
+
+    // A bare file name refers to this profile's backup directory
+    let path = PathBuf::from(snapshot);
+    let path = if path.exists() {
+        path
+    } else {
+        config.directory.join(snapshot)
+    };
+    if !path.exists() {
+        return Err(format!("No snapshot '{}'", snapshot));
+    }
+
+    let safety = database.restore_backup(&path, &config).await?;
+    println!(
+        "Restored {} (previous state saved to {})",
+        path.display(),
+        safety.display()
+    );
+    Ok(())
+}
+
+async fn serve(database: Arc<Database>, args: &[String]) -> Result<(), String> {
+    let mut args = args.to_vec();
+    let port = match take_option(&mut args, &["--port", "-p"])? {
+        Some(port) => port
+            .parse()
+            .map_err(|_| format!("Invalid port '{}'", port))?,
+        None => api_server::load_config().await.port,
+    };
+
+    let token = api_server::load_or_create_token().await?;
+    let server = ApiServer::start(database, port, token).await?;
+    eprintln!(
+        "Listening on http://{} (token in {})",
+        server.addr(),
+        api_server::token_path()?.display()
+    );
+
+    tokio::signal::ctrl_c()
+        .await
+        .map_err(|e| format!("Could not wait for Ctrl-C: {}", e))?;
+    server.stop().await;
+    Ok(())
+}
+
+fn read_stdin() -> Result<String, String> {
+    let mut input = String::new();
+    std::io::stdin()
+        .read_to_string(&mut input)
+        .map_err(|e| format!("Failed to read stdin: {}", e))?;
+    Ok(input)
+}
+
+/// Prints messages as they stream in
+///
+/// Agent replies are written incrementally: each update prints only the
+/// text added since the previous one. A header is printed whenever output
+/// switches to a different message.
+#[derive(Default)]
+struct StreamPrinter {
+    /// Also print the user's own messages (off for `send`, which echoes nothing)
+    echo_human: bool,
+    printed: HashMap<String, usize>,
+    current: Option<String>,
+}
+
+impl StreamPrinter {
+    fn print(&mut self, notification: &Notification<Message>) {
+        match notification.action {
+            Action::Create | Action::Update => self.print_message(&notification.data),
+            _ => {}
+        }
+    }
+
+    fn print_message(&mut self, message: &Message) {
+        if message.deleted || (message.author_type == AuthorType::Human && !self.echo_human) {
+            return;
+        }
+
+        // Content only grows while streaming; print the part not yet shown
+        let key = message.id.to_sql();
+        let printed = self.printed.get(&key).copied().unwrap_or(0);
+        let Some(new_text) = message.content.get(printed..) else {
+            return;
+        };
+        if new_text.is_empty() {
+            return;
+        }
+
+        let mut stdout = std::io::stdout().lock();
+        if self.current.as_deref() != Some(key.as_str()) {
+            if self.current.is_some() {
+                let _ = writeln!(stdout);
+            }
+            let label = match message.author_type {
+                AuthorType::Human => "you".to_string(),
+                _ => message.author.clone(),
+            };
+            let _ = writeln!(stdout, "[{}] {}", message.timestamp.format("%H:%M"), label);
+            self.current = Some(key.clone());
+        }
+        let _ = write!(stdout, "{}", new_text);
+        let _ = stdout.flush();
+
+        self.printed.insert(key, message.content.len());
+    }
+
+    fn finish(&self) {
+        if self.current.is_some() {
+            println!();
+        }
+    }
+}
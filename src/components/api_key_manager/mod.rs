@@ -0,0 +1,5 @@
+//! API key manager component for the agents' inference backend keys
+
+pub mod view;
+
+pub use view::ApiKeyManagerComponent;
@@ -0,0 +1,164 @@
+//! API key manager view component
+//!
+//! One card per service (Anthropic, OpenAI) to store, check and remove the
+//! default key agents use. Keys are written to the credential vault and
+//! never shown again; templates can override them in the template editor.
+
+use crate::services::llm::api_keys::{self, KeyService};
+use dioxus::prelude::*;
+
+/// Main API key manager component
+#[component]
+pub fn ApiKeyManagerComponent() -> Element {
+    rsx! {
+        div {
+            class: "api-key-manager p-4",
+
+            h2 { "API Keys" }
+            p {
+                class: "text-muted small mb-3",
+                "Default keys for agent backends. A template's own key takes precedence, so teams can bill separately."
+            }
+
+            for service in KeyService::ALL {
+                ApiKeyCard { key: "{service.vault_provider()}", service }
+            }
+        }
+    }
+}
+
+/// Default key of one service
+///
+/// # State Management
+/// - stored: whether the vault holds a key for the service
+/// - input: key being entered (never pre-filled)
+/// - status: result of the last check or save
+#[component]
+fn ApiKeyCard(service: KeyService) -> Element {
+    let mut stored = use_signal(|| false);
+    let mut input = use_signal(String::new);
+    let mut status = use_signal(|| Option::<Result<String, String>>::None);
+    let mut busy = use_signal(|| false);
+
+    use_effect(move || {
+        spawn(async move {
+            stored.set(api_keys::has_default_key(service).await);
+        });
+    });
+
+    // Checks the entered key, or the key agents would use now
+    let handle_check = move |_| {
+        let entered = input.read().trim().to_string();
+        busy.set(true);
+        spawn(async move {
+            let key = if entered.is_empty() {
+                api_keys::resolve_key(None, Some(service)).await
+            } else {
+                Some(entered)
+            };
+            let result = match key {
+                Some(key) => api_keys::validate_key(service, &key, None)
+                    .await
+                    .map(|()| format!("{} accepted the key", service.label())),
+                None => Err("No key stored or set in the environment".to_string()),
+            };
+            status.set(Some(result));
+            busy.set(false);
+        });
+    };
+
+    let handle_save = move |_| {
+        let key = input.read().trim().to_string();
+        if key.is_empty() {
+            return;
+        }
+        busy.set(true);
+        spawn(async move {
+            let result = match api_keys::validate_key(service, &key, None).await {
+                Ok(()) => api_keys::store_default_key(service, &key)
+                    .await
+                    .map(|()| "Key checked and saved".to_string()),
+                Err(e) => Err(e),
+            };
+            if result.is_ok() {
+                input.set(String::new());
+                stored.set(true);
+            }
+            status.set(Some(result));
+            busy.set(false);
+        });
+    };
+
+    let handle_remove = move |_| {
+        spawn(async move {
+            let result = api_keys::store_default_key(service, "")
+                .await
+                .map(|()| "Key removed".to_string());
+            if result.is_ok() {
+                stored.set(false);
+            }
+            status.set(Some(result));
+        });
+    };
+
+    let placeholder = if stored() {
+        "Stored - enter a new key to replace it".to_string()
+    } else {
+        format!("Uses {} if left blank", service.env_var())
+    };
+
+    rsx! {
+        div {
+            class: "api-key-card border rounded p-3 mb-2",
+
+            h4 {
+                class: "mb-2",
+                "{service.label()}"
+                if stored() {
+                    span { class: "text-muted small ml-2", "(stored)" }
+                }
+            }
+
+            input {
+                class: "form-control mb-2",
+                r#type: "password",
+                value: "{input.read()}",
+                oninput: move |evt| input.set(evt.value().clone()),
+                placeholder: "{placeholder}"
+            }
+
+            div {
+                class: "btn-group",
+                button {
+                    class: "btn btn-sm btn-outline-secondary",
+                    disabled: busy(),
+                    onclick: handle_check,
+                    if busy() { "Checking…" } else { "Check" }
+                }
+                button {
+                    class: "btn btn-sm btn-primary",
+                    disabled: busy() || input.read().trim().is_empty(),
+                    onclick: handle_save,
+                    "Save"
+                }
+                if stored() {
+                    button {
+                        class: "btn btn-sm btn-outline-danger",
+                        onclick: handle_remove,
+                        "Remove"
+                    }
+                }
+            }
+
+            match status() {
+                Some(Ok(message)) => rsx! {
+                    p { class: "small mt-2 mb-0", "✓ {message}" }
+                },
+                Some(Err(e)) => rsx! {
+                    p { class: "text-danger small mt-2 mb-0", "✗ {e}" }
+                },
+                None => rsx! {},
+            }
+        }
+    }
+}
@@ -0,0 +1,105 @@
+//! Slash command palette for the chat input
+//!
+//! Features:
+//! - Opens while the input is a bare `/name` (no arguments yet)
+//! - Lists registered commands and saved prompt snippets by prefix
+//! - Inserts `/name ` on selection so arguments can be typed
+//!
+//! Render inside a `relative` container; the dropdown sits above it.
+
+use crate::environment::Environment;
+use crate::services::commands::{CommandInfo, CommandRegistry};
+use dioxus::prelude::*;
+
+#[component]
+pub fn CommandPalette(value: Signal<String>) -> Element {
+    let environment = use_context::<Environment>();
+
+    // Reload snippets each time the palette opens, so /snippet add shows up
+    let is_open = use_memo(move || palette_filter(&value.read()).is_some());
+    let snippets = use_resource(move || {
+        let database = environment.database.clone();
+        let open = is_open();
+        async move {
+            if !open {
+                return Vec::new();
+            }
+            database.list_snippets().await.unwrap_or_else(|e| {
+                log::warn!("[CommandPalette] Failed to load snippets: {}", e);
+                Vec::new()
+            })
+        }
+    });
+
+    let Some(filter) = palette_filter(&value.read()).map(str::to_string) else {
+        return rsx! {};
+    };
+
+    let mut entries: Vec<CommandInfo> = CommandRegistry::global().matching(&filter);
+    if let Some(snippets) = snippets.read().as_ref() {
+        entries.extend(
+            snippets
+                .iter()
+                .filter(|s| s.name.starts_with(&filter))
+                .filter(|s| entries.iter().all(|c| c.name != s.name))
+                .map(|s| CommandInfo {
+                    name: s.name.clone(),
+                    description: if s.description.is_empty() {
+                        "Prompt snippet".to_string()
+                    } else {
+                        s.description.clone()
+                    },
+                    usage: s
+                        .parameters()
+                        .iter()
+                        .map(|p| format!("[{}]", p.name))
+                        .collect::<Vec<_>>()
+                        .join(" "),
+                })
+                .collect::<Vec<_>>(),
+        );
+    }
+
+    if entries.is_empty() {
+        return rsx! {};
+    }
+
+    rsx! {
+        div {
+            class: "absolute bottom-full mb-2 left-0 w-full bg-[#1a1a2e] border border-white/10 rounded-lg shadow-lg max-h-64 overflow-y-auto z-10",
+            {entries.into_iter().map(|entry| {
+                let name = entry.name.clone();
+
+                rsx! {
+                    div {
+                        key: "{entry.name}",
+                        class: "px-4 py-2 hover:bg-white/10 cursor-pointer transition-colors",
+                        onclick: move |_| {
+                            log::debug!("[CommandPalette] Selected /{}", name);
+                            value.set(format!("/{} ", name));
+                        },
+
+                        div {
+                            class: "flex items-baseline gap-2",
+                            span { class: "font-semibold text-white", "/{entry.name}" }
+                            span { class: "text-xs text-gray-500 font-mono", "{entry.usage}" }
+                        }
+                        div {
+                            class: "text-xs text-gray-400",
+                            "{entry.description}"
+                        }
+                    }
+                }
+            })}
+        }
+    }
+}
+
+/// Command-name prefix being typed, if the palette should be open
+fn palette_filter(text: &str) -> Option<&str> {
+    let name = text.strip_prefix('/')?;
+    if name.contains(|c: char| c.is_whitespace() || c == '/') {
+        return None;
+    }
+    Some(name)
+}
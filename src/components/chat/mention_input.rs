@@ -6,7 +6,9 @@
 //! - Dropdown UI with agent selection
 //! - Inserts @agent-name on selection
 //! - Only shows agents in current room
+//! - Opens the slash command palette for `/` input
 
+use crate::components::chat::command_palette::CommandPalette;
 use crate::environment::Environment;
 use crate::view_model::agent::AgentTemplate;
 use dioxus::prelude::*;
@@ -134,6 +136,8 @@ pub fn MentionInput(
                         }
                     })}
                 }
+            } else {
+                CommandPalette { value }
             }
 
             // Input form
@@ -144,7 +148,7 @@ pub fn MentionInput(
                 input {
                     class: "flex-1 px-4 py-3 bg-white/5 border border-white/10 rounded-lg text-white placeholder-gray-500 focus:outline-none focus:border-blue-500",
                     r#type: "text",
-                    placeholder: "Type @ to mention agents...",
+                    placeholder: "Type @ to mention agents or / for commands...",
                     value: "{value.read()}",
                     oninput: handle_input,
                     disabled: disabled,
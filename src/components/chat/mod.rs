@@ -1,4 +1,6 @@
+mod command_palette;
 mod view;
+mod virtual_list;
 pub mod mention_input;
 
 pub use view::ChatComponent;
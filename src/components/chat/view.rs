@@ -1,7 +1,14 @@
+use super::virtual_list::{
+    ESTIMATED_ROW_HEIGHT, JUMP_RADIUS, PAGE_SIZE, SCROLL_CONTAINER_ID, Viewport, VisibleRange,
+};
 use super::{ChatMessage, MessageSender, ReactionSummary};
+use super::command_palette::CommandPalette;
 use crate::components::chat::mention_input::MentionInput;
 use crate::constants::ui_text;
+use crate::database::messages::MessageCursor;
 use crate::environment::Environment;
+use crate::notifications::NotificationPolicy;
+use crate::services::commands::{self, CommandOutcome, CommandRegistry};
 use crate::services::{agent_chat, mention_parser};
 use crate::view_model::agent::{AgentModel, AgentTemplate};
 use crate::view_model::conversation::Conversation;
@@ -13,8 +20,44 @@ use std::collections::HashSet;
 use surrealdb::Notification;
 use surrealdb_types::{Action, RecordId, ToSql};
 
+/// Scroll a message into view and briefly highlight it
+///
+/// If the message is outside the rendered window, the container is first
+/// scrolled to its estimated offset so the virtual list renders it, then the
+/// lookup is retried for a few frames.
+async fn scroll_to_message(message_id: &str, index: usize) {
+    use dioxus::document;
+
+    let script = format!(
+        r#"
+        (function jump(attempt) {{
+            const element = document.getElementById('message-{message_id}');
+            if (element) {{
+                element.scrollIntoView({{ behavior: 'smooth', block: 'center' }});
+                // Highlight briefly
+                element.style.backgroundColor = 'rgba(59, 130, 246, 0.2)';
+                setTimeout(() => element.style.backgroundColor = '', 2000);
+                return;
+            }}
+            const container = document.getElementById('{SCROLL_CONTAINER_ID}');
+            if (container && attempt === 0) {{
+                container.scrollTop = {offset};
+            }}
+            if (attempt < 10) {{
+                setTimeout(() => jump(attempt + 1), 50);
+            }}
+        }})(0);
+        "#,
+        offset = index as f64 * ESTIMATED_ROW_HEIGHT,
+    );
+
+    if let Err(e) = document::eval(&script).await {
+        log::warn!("[Chat] Failed to scroll to message {}: {}", message_id, e);
+    }
+}
+
 #[component]
-fn PinnedBanner(conversation_id: RecordId) -> Element {
+fn PinnedBanner(conversation_id: RecordId, on_jump: EventHandler<String>) -> Element {
     let environment = use_context::<Environment>();
     let database = environment.database.clone();
 
@@ -56,7 +99,7 @@ fn PinnedBanner(conversation_id: RecordId) -> Element {
                                     key: "{msg_id}",
                                     class: "p-3 bg-white/5 border border-white/10 rounded-lg cursor-pointer hover:bg-white/8 transition-all duration-200",
                                     onclick: move |_| {
-                                        log::info!("Clicked pinned message: {}", msg_id);
+                                        on_jump.call(msg_id.clone());
                                     },
                                     div {
                                         class: "text-xs text-[var(--g-secondaryLabelColor)] mb-1",
@@ -77,6 +120,56 @@ fn PinnedBanner(conversation_id: RecordId) -> Element {
     }
 }
 
+/// Per-conversation choice of when agent replies raise a notification
+#[component]
+fn NotificationPolicyPicker(conversation_id: RecordId) -> Element {
+    let environment = use_context::<Environment>();
+    let database = environment.database.clone();
+    let mut policy = use_signal(NotificationPolicy::default);
+
+    let load_database = database.clone();
+    let load_id = conversation_id.clone();
+    use_effect(use_reactive!(|load_id| {
+        let database = load_database.clone();
+        spawn(async move {
+            match database.get_notification_policy(&load_id).await {
+                Ok(stored) => policy.set(stored),
+                Err(e) => log::warn!("[Chat] Failed to load notification policy: {}", e),
+            }
+        });
+    }));
+
+    rsx! {
+        div {
+            class: "flex items-center justify-end gap-2 px-6 pt-2 text-xs text-[var(--g-secondaryLabelColor)]",
+            span { "Notify:" }
+            select {
+                class: "bg-transparent border border-white/10 rounded px-1",
+                value: "{policy().as_str()}",
+                onchange: move |e| {
+                    let selected = NotificationPolicy::from_key(&e.value());
+                    policy.set(selected);
+                    let database = database.clone();
+                    let conversation_id = conversation_id.clone();
+                    spawn(async move {
+                        if let Err(e) = database.set_notification_policy(&conversation_id, selected).await {
+                            log::error!("[Chat] Failed to save notification policy: {}", e);
+                        }
+                    });
+                },
+                for choice in NotificationPolicy::ALL {
+                    option {
+                        key: "{choice.as_str()}",
+                        value: "{choice.as_str()}",
+                        selected: choice == policy(),
+                        "{choice.label()}"
+                    }
+                }
+            }
+        }
+    }
+}
+
 #[component]
 pub fn ChatComponent() -> Element {
     // Get environment from context (provided by App component)
@@ -95,6 +188,7 @@ pub fn ChatComponent() -> Element {
     let mut input_value = use_signal(String::new);
     let mut is_sending = use_signal(|| false);
     let mut send_error = use_signal(|| Option::<String>::None);
+    let mut command_status = use_signal(|| Option::<String>::None);
     let mut active_tool = use_signal(|| Option::<String>::None);
 
     // Reply state tracking
@@ -109,6 +203,174 @@ pub fn ChatComponent() -> Element {
     // Track whether we've scrolled to first unread (one-time per conversation open)
     let mut has_scrolled_to_unread = use_signal(|| false);
 
+    // Keyset pagination state for the loaded message window
+    let mut oldest_cursor = use_signal(|| Option::<MessageCursor>::None);
+    let mut newest_cursor = use_signal(|| Option::<MessageCursor>::None);
+    let mut has_older = use_signal(|| false);
+    let mut has_newer = use_signal(|| false);
+    let mut loading_page = use_signal(|| false);
+
+    // Scroll geometry driving the virtualized message list
+    let mut viewport = use_signal(|| Option::<Viewport>::None);
+
+    // Load the previous page when the user scrolls near the top.
+    // Argument is the container scrollHeight before the prepend, used to keep
+    // the visible messages anchored in place.
+    let load_older = use_callback({
+        let database = environment.database.clone();
+        move |previous_scroll_height: f64| {
+            if *loading_page.peek() || !*has_older.peek() {
+                return;
+            }
+            let Some(cursor) = oldest_cursor.peek().clone() else {
+                return;
+            };
+
+            loading_page.set(true);
+            let database = database.clone();
+            let current_id = conversation_id.peek().clone();
+
+            spawn(async move {
+                match database
+                    .get_messages_before(&current_id, Some(&cursor), PAGE_SIZE)
+                    .await
+                {
+                    Ok(page) => {
+                        has_older.set(page.has_more);
+                        if let Some(first) = page.first_cursor() {
+                            oldest_cursor.set(Some(first));
+                        }
+
+                        let older: Vec<ChatMessage> = page
+                            .messages
+                            .into_iter()
+                            .map(ChatMessage::from_db_message)
+                            .collect();
+                        log::debug!("[Chat] Loaded {} older messages", older.len());
+
+                        if !older.is_empty() {
+                            messages.write().splice(0..0, older);
+
+                            let script = format!(
+                                r#"
+                                requestAnimationFrame(() => {{
+                                    const container = document.getElementById('{SCROLL_CONTAINER_ID}');
+                                    if (container) {{
+                                        container.scrollTop += container.scrollHeight - {previous_scroll_height};
+                                    }}
+                                }});
+                                "#
+                            );
+                            if let Err(e) = dioxus::document::eval(&script).await {
+                                log::warn!("[Chat] Failed to restore scroll position: {}", e);
+                            }
+                        }
+                    }
+                    Err(e) => {
+                        log::error!("[Chat] Failed to load older messages: {}", e);
+                    }
+                }
+                loading_page.set(false);
+            });
+        }
+    });
+
+    // Load the next page when the user scrolls near the bottom of a window
+    // that does not reach the newest message (after a jump)
+    let load_newer = use_callback({
+        let database = environment.database.clone();
+        move |_: ()| {
+            if *loading_page.peek() || !*has_newer.peek() {
+                return;
+            }
+            let Some(cursor) = newest_cursor.peek().clone() else {
+                return;
+            };
+
+            loading_page.set(true);
+            let database = database.clone();
+            let current_id = conversation_id.peek().clone();
+
+            spawn(async move {
+                match database
+                    .get_messages_after(&current_id, Some(&cursor), PAGE_SIZE)
+                    .await
+                {
+                    Ok(page) => {
+                        has_newer.set(page.has_more);
+                        if let Some(last) = page.last_cursor() {
+                            newest_cursor.set(Some(last));
+                        }
+
+                        let mut msgs = messages.write();
+                        for chat_msg in page.messages.into_iter().map(ChatMessage::from_db_message) {
+                            if !msgs.iter().any(|m| m.id == chat_msg.id) {
+                                msgs.push(chat_msg);
+                            }
+                        }
+                    }
+                    Err(e) => {
+                        log::error!("[Chat] Failed to load newer messages: {}", e);
+                    }
+                }
+                loading_page.set(false);
+            });
+        }
+    });
+
+    // Jump to a message (pinned banner, search hits), loading the
+    // surrounding window if it is not part of the loaded messages
+    let jump_to_message = use_callback({
+        let database = environment.database.clone();
+        move |message_id: String| {
+            let database = database.clone();
+
+            spawn(async move {
+                let loaded_index = messages.peek().iter().position(|m| m.id == message_id);
+
+                let index = match loaded_index {
+                    Some(index) => index,
+                    None => {
+                        let Ok(target_id) = RecordId::parse_simple(&message_id) else {
+                            log::warn!("[Chat] Invalid jump target: {}", message_id);
+                            return;
+                        };
+
+                        let (older, newer) =
+                            match database.get_messages_around(&target_id, JUMP_RADIUS).await {
+                                Ok(pages) => pages,
+                                Err(e) => {
+                                    log::error!("[Chat] Failed to load jump window: {}", e);
+                                    return;
+                                }
+                            };
+
+                        oldest_cursor.set(older.first_cursor());
+                        has_older.set(older.has_more);
+                        newest_cursor.set(newer.last_cursor().or_else(|| older.last_cursor()));
+                        has_newer.set(newer.has_more);
+
+                        // Target is the last message of the older page
+                        let index = older.messages.len().saturating_sub(1);
+                        let window: Vec<ChatMessage> = older
+                            .messages
+                            .into_iter()
+                            .chain(newer.messages)
+                            .map(ChatMessage::from_db_message)
+                            .collect();
+
+                        log::debug!("[Chat] Loaded {} messages around jump target", window.len());
+                        messages.set(window);
+                        viewport.set(None);
+                        index
+                    }
+                };
+
+                scroll_to_message(&message_id, index).await;
+            });
+        }
+    });
+
     // Load conversation to check participant count for conditional input rendering
     let conversation_for_input = {
         let database = environment.database.clone();
@@ -175,6 +437,7 @@ pub fn ChatComponent() -> Element {
                             last_summarized_message_id: None,
                             last_message_at: now.into(),
                             created_at: now.into(),
+                            user_id: crate::auth::active_user_id(),
                         };
 
                         match database.create_conversation(&conversation).await {
@@ -198,6 +461,10 @@ pub fn ChatComponent() -> Element {
     use_effect({
         let database = environment.database.clone();
         let mut messages = messages;
+        let mut oldest_cursor = oldest_cursor;
+        let mut newest_cursor = newest_cursor;
+        let mut has_older = has_older;
+        let mut has_newer = has_newer;
         move || {
             let database = database.clone();
             let current_id = conversation_id.read().clone();
@@ -240,14 +507,21 @@ pub fn ChatComponent() -> Element {
 
                 log::info!("[Chat] LIVE QUERY subscription active");
 
-                // STEP 2: NOW load existing messages (after LIVE QUERY is active)
+                // STEP 2: NOW load the newest page (after LIVE QUERY is active)
+                // Older pages are fetched on demand as the user scrolls up
                 log::info!(
-                    "[Chat] Loading existing messages for conversation: {}",
+                    "[Chat] Loading latest messages for conversation: {}",
                     current_id.to_sql()
                 );
-                match database.get_all_messages(&current_id).await {
-                    Ok(db_messages) => {
-                        let chat_messages: Vec<ChatMessage> = db_messages
+                match database.get_messages_before(&current_id, None, PAGE_SIZE).await {
+                    Ok(page) => {
+                        oldest_cursor.set(page.first_cursor());
+                        newest_cursor.set(page.last_cursor());
+                        has_older.set(page.has_more);
+                        has_newer.set(false);
+
+                        let chat_messages: Vec<ChatMessage> = page
+                            .messages
                             .into_iter()
                             .map(ChatMessage::from_db_message)
                             .collect();
@@ -255,8 +529,26 @@ pub fn ChatComponent() -> Element {
                         // Display messages immediately without reactions
                         // Reactions will be populated by LIVE QUERY subscription
                         let count = chat_messages.len();
+                        let any_unread = chat_messages.iter().any(|m| m.unread);
                         messages.set(chat_messages);
-                        log::info!("[Chat] Loaded {} existing messages", count);
+                        log::info!("[Chat] Loaded {} latest messages", count);
+
+                        // Start at the bottom unless the unread effect will position the view
+                        if !any_unread {
+                            let script = format!(
+                                r#"
+                                requestAnimationFrame(() => {{
+                                    const container = document.getElementById('{SCROLL_CONTAINER_ID}');
+                                    if (container) {{
+                                        container.scrollTop = container.scrollHeight;
+                                    }}
+                                }});
+                                "#
+                            );
+                            if let Err(e) = dioxus::document::eval(&script).await {
+                                log::warn!("[Chat] Failed to scroll to latest message: {}", e);
+                            }
+                        }
                     }
                     Err(e) => {
                         log::error!("[Chat] Failed to load existing messages: {}", e);
@@ -271,6 +563,15 @@ pub fn ChatComponent() -> Element {
 
                             match notif.action {
                                 Action::Create => {
+                                    // Viewing an older window (after a jump): the new message
+                                    // is picked up by load_newer when the user scrolls down
+                                    if *has_newer.peek() {
+                                        log::trace!("[Chat] New message outside loaded window");
+                                        continue;
+                                    }
+
+                                    newest_cursor.set(Some(MessageCursor::from(&message_data)));
+
                                     // New message - check for duplicates before adding
                                     let chat_msg = ChatMessage::from_db_message(message_data);
 
@@ -298,12 +599,12 @@ pub fn ChatComponent() -> Element {
                                     {
                                         msgs[pos] = chat_msg;
                                     } else {
-                                        log::warn!(
-                                            "[Chat] Update for unknown message ID: {}",
+                                        // Message not in the loaded page window - it will be
+                                        // fetched with current content when paged in
+                                        log::trace!(
+                                            "[Chat] Update for message outside loaded window: {}",
                                             chat_msg.id
                                         );
-                                        // Message not found - may have been deleted or not yet loaded
-                                        // Don't crash, just log warning
                                     }
                                 }
                                 Action::Delete => {
@@ -395,7 +696,7 @@ pub fn ChatComponent() -> Element {
                                         .await
                                         .unwrap_or_default();
 
-                                    let user_id = "hardcoded-david-maple";
+                                    let user_id = crate::auth::active_user_id().unwrap_or_default();
 
                                     // Convert to ReactionSummary
                                     let reaction_summaries: Vec<ReactionSummary> = counts
@@ -442,8 +743,10 @@ pub fn ChatComponent() -> Element {
         move || {
             let database = database.clone();
             spawn(async move {
-                let user_id = "hardcoded-david-maple";
-                match database.get_bookmarked_messages(user_id).await {
+                let Some(user_id) = crate::auth::active_user_id() else {
+                    return;
+                };
+                match database.get_bookmarked_messages(&user_id).await {
                     Ok(messages) => {
                         let ids: HashSet<String> =
                             messages.into_iter().map(|msg| msg.id.to_sql()).collect();
@@ -468,8 +771,13 @@ pub fn ChatComponent() -> Element {
         // Reset scroll-to-unread flag when conversation changes
         has_scrolled_to_unread.set(false);
 
-        // Reset messages (they will be reloaded by LIVE QUERY)
+        // Reset messages and paging state (they will be reloaded by LIVE QUERY)
         messages.set(Vec::new());
+        oldest_cursor.set(None);
+        newest_cursor.set(None);
+        has_older.set(false);
+        has_newer.set(false);
+        viewport.set(None);
     });
 
     // Scroll to first unread message on conversation load
@@ -480,33 +788,15 @@ pub fn ChatComponent() -> Element {
         // Only scroll once per conversation open, and only if there are unreads
         if !scrolled
             && !messages_list.is_empty()
-            && let Some(first_unread) = messages_list.iter().find(|msg| msg.unread)
+            && let Some(index) = messages_list.iter().position(|msg| msg.unread)
         {
-            let message_id = first_unread.id.clone();
+            let message_id = messages_list[index].id.clone();
             has_scrolled_to_unread.set(true);
 
             log::debug!("[Chat] Scrolling to first unread: {}", message_id);
 
             spawn(async move {
-                use dioxus::document;
-
-                let script = format!(
-                    r#"
-                    const element = document.getElementById('message-{}');
-                    if (element) {{
-                        element.scrollIntoView({{ behavior: 'smooth', block: 'center' }});
-                        // Highlight briefly
-                        element.style.backgroundColor = 'rgba(59, 130, 246, 0.2)';
-                        setTimeout(() => element.style.backgroundColor = '', 2000);
-                    }}
-                    "#,
-                    message_id
-                );
-
-                let eval_result = document::eval(&script);
-                if let Err(e) = eval_result.await {
-                    log::warn!("[Chat] Failed to scroll to unread: {}", e);
-                }
+                scroll_to_message(&message_id, index).await;
             });
         }
     });
@@ -514,8 +804,75 @@ pub fn ChatComponent() -> Element {
     // Clone database for use in MentionInput on_submit handler (needed before send_message captures environment)
     let database_for_mention_input = environment.database.clone();
 
+    // Slash commands: returns true when the text was a command and was handled
+    let run_command = use_callback({
+        let database = environment.database.clone();
+        move |text: String| -> bool {
+            if commands::parse_command(&text).is_none() {
+                return false;
+            }
+
+            spawn({
+                let database = database.clone();
+                let current_conversation_id = conversation_id.read().clone();
+                let mut is_sending = is_sending;
+                let mut send_error = send_error;
+                let mut command_status = command_status;
+                let mut input_value = input_value;
+
+                async move {
+                    let outcome = commands::execute(
+                        CommandRegistry::global(),
+                        database.clone(),
+                        current_conversation_id.clone(),
+                        &text,
+                    )
+                    .await;
+
+                    match outcome {
+                        Some(Ok(CommandOutcome::Status(status))) => {
+                            send_error.set(None);
+                            command_status.set(Some(status));
+                        }
+                        Some(Ok(CommandOutcome::Insert(prompt))) => input_value.set(prompt),
+                        Some(Ok(CommandOutcome::Send(prompt))) => {
+                            is_sending.set(true);
+                            if let Err(e) = agent_chat::send_message(
+                                database,
+                                current_conversation_id,
+                                prompt,
+                                None,
+                                false,
+                                None,
+                            )
+                            .await
+                            {
+                                log::error!("[Chat] Command message failed: {}", e);
+                                send_error.set(Some(
+                                    "Failed to send message. Please try again.".to_string(),
+                                ));
+                            }
+                            is_sending.set(false);
+                        }
+                        Some(Err(e)) => {
+                            log::warn!("[Chat] Command failed: {}", e);
+                            command_status.set(None);
+                            send_error.set(Some(e));
+                        }
+                        None => {}
+                    }
+                }
+            });
+            true
+        }
+    });
+
     let mut send_message = move |_| {
         let content = input_value.read().trim().to_string();
+        if run_command.call(content.clone()) {
+            input_value.set(String::new());
+            return;
+        }
         if !content.is_empty() && !*is_sending.read() {
             input_value.set(String::new());
             is_sending.set(true);
@@ -578,6 +935,7 @@ pub fn ChatComponent() -> Element {
                             last_summarized_message_id: None,
                             last_message_at: now.into(),
                             created_at: now.into(),
+                            user_id: crate::auth::active_user_id(),
                         };
 
                         match database.create_conversation(&conversation).await {
@@ -602,7 +960,8 @@ pub fn ChatComponent() -> Element {
                         database,
                         current_conversation_id,
                         content,
-                        None, // mentioned_agents (None = use all participants)
+                        None, // target_agents (None = use all participants)
+                        false,
                         parent_message_id,
                     )
                     .await
@@ -704,18 +1063,64 @@ pub fn ChatComponent() -> Element {
         div {
             class: "flex flex-col h-screen bg-transparent",
 
-            PinnedBanner { conversation_id: conversation_id.read().clone() }
+            PinnedBanner {
+                conversation_id: conversation_id.read().clone(),
+                on_jump: move |message_id: String| jump_to_message.call(message_id),
+            }
+
+            NotificationPolicyPicker {
+                conversation_id: conversation_id.read().clone(),
+            }
 
             div {
+                id: SCROLL_CONTAINER_ID,
                 class: "flex-1 overflow-y-auto px-6 py-4 flex flex-col gap-2",
-                for message in messages.read().iter() {
-                    ChatMessageView {
-                        message: message.clone(),
-                        on_reply: start_reply,
-                        bookmarked_msg_ids: bookmarked_msg_ids,
-                        show_delete_confirmation: show_delete_confirmation
+                onscroll: move |evt: Event<ScrollData>| {
+                    let current = Viewport {
+                        scroll_top: evt.data().scroll_top(),
+                        scroll_height: evt.data().scroll_height() as f64,
+                        client_height: evt.data().client_height() as f64,
+                    };
+                    viewport.set(Some(current));
+
+                    if current.near_top() {
+                        load_older.call(current.scroll_height);
+                    }
+                    if current.near_bottom() {
+                        load_newer.call(());
+                    }
+                },
+
+                if *loading_page.read() && *has_older.read() {
+                    div {
+                        class: "self-center text-xs text-white/40 py-2",
+                        "Loading earlier messages..."
                     }
                 }
+
+                // Virtualized window: spacers stand in for rows outside the viewport
+                {
+                    let msgs = messages.read();
+                    let range = VisibleRange::compute(*viewport.read(), msgs.len());
+                    let top_spacer = range.top_spacer_px();
+                    let bottom_spacer = range.bottom_spacer_px(msgs.len());
+                    let visible: Vec<ChatMessage> = msgs[range.start..range.end].to_vec();
+
+                    rsx! {
+                        div { class: "shrink-0", style: "height: {top_spacer}px;" }
+                        for message in visible {
+                            ChatMessageView {
+                                key: "{message.id}",
+                                message: message,
+                                on_reply: start_reply,
+                                bookmarked_msg_ids: bookmarked_msg_ids,
+                                show_delete_confirmation: show_delete_confirmation
+                            }
+                        }
+                        div { class: "shrink-0", style: "height: {bottom_spacer}px;" }
+                    }
+                }
+
                 if *is_sending.read() {
                     div {
                         class: "self-start px-4 py-3 bg-white/5 border border-white/10 rounded-lg opacity-70 italic animate-[pulse_1.5s_infinite]",
@@ -768,6 +1173,10 @@ pub fn ChatComponent() -> Element {
                             MentionInput {
                                 value: input_value,
                                 on_submit: move |msg: String| {
+                                    if run_command.call(msg.trim().to_string()) {
+                                        return;
+                                    }
+
                                     // Parse @mentions from message
                                     let mentioned = mention_parser::parse_mentions(&msg);
 
@@ -778,7 +1187,7 @@ pub fn ChatComponent() -> Element {
                                         .filter_map(|agent_id| RecordId::parse_simple(&agent_id).ok())
                                         .collect();
 
-                                    // Send with mentioned_agents parameter
+                                    // Send with the mentions as targets
                                     input_value.set(String::new());
                                     is_sending.set(true);
 
@@ -793,6 +1202,7 @@ pub fn ChatComponent() -> Element {
                                                 current_conversation_id,
                                                 msg,
                                                 Some(valid_mentions),
+                                                true, // @mentioned in the composer
                                                 None,
                                             ).await {
                                                 Ok(_) => log::debug!("[Chat] Message sent to mentioned agents"),
@@ -811,7 +1221,8 @@ pub fn ChatComponent() -> Element {
                     rsx! {
                         // Single-agent: Use regular input with professional styling
                         div {
-                            class: "p-6 bg-gradient-to-r from-[#1a1a2e]/95 to-[#16213e]/95 backdrop-blur-xl glass border-t border-white/20 shadow-[0_-4px_20px_rgba(0,0,0,0.3)]",
+                            class: "relative p-6 bg-gradient-to-r from-[#1a1a2e]/95 to-[#16213e]/95 backdrop-blur-xl glass border-t border-white/20 shadow-[0_-4px_20px_rgba(0,0,0,0.3)]",
+                            CommandPalette { value: input_value }
                             form {
                                 class: "flex items-center gap-3",
                                 onsubmit: move |evt| {
@@ -838,6 +1249,19 @@ pub fn ChatComponent() -> Element {
                 }
             }
 
+            // Result of the last slash command
+            if let Some(status) = command_status.read().as_ref() {
+                div {
+                    class: "mx-6 mb-2 px-4 py-2 flex items-center justify-between gap-3 bg-white/5 border border-white/10 rounded-lg text-sm text-white/80",
+                    span { "{status}" }
+                    button {
+                        class: "text-white/50 hover:text-white transition-colors",
+                        onclick: move |_| command_status.set(None),
+                        "✕"
+                    }
+                }
+            }
+
             // Display error message if present
             if let Some(error_msg) = send_error.read().as_ref() {
                 ErrorBox {
@@ -1037,16 +1461,18 @@ fn ChatMessageView(
                                         let database = database.clone();
 
                                         spawn(async move {
-                                            let user_id = "hardcoded-david-maple";
+                                            let Some(user_id) = crate::auth::active_user_id() else {
+                                                return;
+                                            };
 
                                             if user_reacted {
                                                 // Remove reaction
-                                                if let Err(e) = database.remove_reaction(&message_id, user_id, &emoji_str).await {
+                                                if let Err(e) = database.remove_reaction(&message_id, &user_id, &emoji_str).await {
                                                     log::error!("Failed to remove reaction: {}", e);
                                                 }
                                             } else {
                                                 // Add reaction
-                                                if let Err(e) = database.add_reaction(&message_id, user_id, &emoji_str).await {
+                                                if let Err(e) = database.add_reaction(&message_id, &user_id, &emoji_str).await {
                                                     log::error!("Failed to add reaction: {}", e);
                                                 }
                                             }
@@ -1095,6 +1521,7 @@ async fn ensure_default_template(
         icon: None,
         color: None,
         created_at: chrono::Utc::now(),
+        ..Default::default()
     };
 
     let created_id = database.create_template(&default_template).await?;
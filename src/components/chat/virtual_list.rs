@@ -0,0 +1,123 @@
+//! Windowed rendering for the chat message list
+//!
+//! Long conversations only render the messages intersecting the viewport
+//! (plus an overscan margin). Rows outside the window are replaced by
+//! spacers sized from an estimated row height so the scrollbar stays stable.
+
+/// Estimated rendered height of one message row in pixels
+pub const ESTIMATED_ROW_HEIGHT: f64 = 96.0;
+
+/// Extra rows rendered above and below the viewport
+pub const OVERSCAN_ROWS: usize = 10;
+
+/// Distance from either edge (px) at which the next page is requested
+pub const LOAD_THRESHOLD_PX: f64 = 200.0;
+
+/// Messages fetched per page when scrolling through history
+pub const PAGE_SIZE: usize = 50;
+
+/// Messages loaded on each side of a jump-to-message target
+pub const JUMP_RADIUS: usize = 25;
+
+/// DOM id of the scrollable message container
+pub const SCROLL_CONTAINER_ID: &str = "chat-scroll";
+
+/// Scroll geometry reported by the message container
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Viewport {
+    pub scroll_top: f64,
+    pub scroll_height: f64,
+    pub client_height: f64,
+}
+
+impl Viewport {
+    /// True when the user has scrolled close enough to the top to load older messages
+    pub fn near_top(&self) -> bool {
+        self.scroll_top <= LOAD_THRESHOLD_PX
+    }
+
+    /// True when the user has scrolled close enough to the bottom to load newer messages
+    pub fn near_bottom(&self) -> bool {
+        self.scroll_height - (self.scroll_top + self.client_height) <= LOAD_THRESHOLD_PX
+    }
+}
+
+/// Half-open range `[start, end)` of message indices to render
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct VisibleRange {
+    pub start: usize,
+    pub end: usize,
+}
+
+impl VisibleRange {
+    /// Compute the rendered range for the given viewport
+    ///
+    /// Without a viewport (before the first scroll event) every loaded
+    /// message is rendered; the initial page is small enough for that.
+    pub fn compute(viewport: Option<Viewport>, total: usize) -> Self {
+        let Some(viewport) = viewport else {
+            return Self { start: 0, end: total };
+        };
+
+        let first_visible = (viewport.scroll_top / ESTIMATED_ROW_HEIGHT).floor() as usize;
+        let visible_rows = (viewport.client_height / ESTIMATED_ROW_HEIGHT).ceil() as usize + 1;
+
+        let start = first_visible.saturating_sub(OVERSCAN_ROWS).min(total);
+        let end = (first_visible + visible_rows + OVERSCAN_ROWS).min(total);
+
+        Self { start, end }
+    }
+
+    /// Height of the spacer standing in for rows before `start`
+    pub fn top_spacer_px(&self) -> f64 {
+        self.start as f64 * ESTIMATED_ROW_HEIGHT
+    }
+
+    /// Height of the spacer standing in for rows after `end`
+    pub fn bottom_spacer_px(&self, total: usize) -> f64 {
+        total.saturating_sub(self.end) as f64 * ESTIMATED_ROW_HEIGHT
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn viewport(scroll_top: f64) -> Viewport {
+        Viewport {
+            scroll_top,
+            scroll_height: 100_000.0,
+            client_height: 960.0,
+        }
+    }
+
+    #[test]
+    fn test_no_viewport_renders_everything() {
+        let range = VisibleRange::compute(None, 42);
+        assert_eq!(range, VisibleRange { start: 0, end: 42 });
+    }
+
+    #[test]
+    fn test_window_is_clamped_to_total() {
+        let range = VisibleRange::compute(Some(viewport(0.0)), 5);
+        assert_eq!(range, VisibleRange { start: 0, end: 5 });
+        assert_eq!(range.bottom_spacer_px(5), 0.0);
+    }
+
+    #[test]
+    fn test_window_includes_overscan() {
+        // Row 100 is the first visible row
+        let range = VisibleRange::compute(Some(viewport(100.0 * ESTIMATED_ROW_HEIGHT)), 1000);
+        assert_eq!(range.start, 100 - OVERSCAN_ROWS);
+        assert_eq!(range.end, 100 + 11 + OVERSCAN_ROWS);
+        assert_eq!(range.top_spacer_px(), 90.0 * ESTIMATED_ROW_HEIGHT);
+    }
+
+    #[test]
+    fn test_edge_detection() {
+        assert!(viewport(0.0).near_top());
+        assert!(!viewport(5_000.0).near_top());
+        assert!(viewport(100_000.0 - 960.0).near_bottom());
+        assert!(!viewport(5_000.0).near_bottom());
+    }
+}
@@ -0,0 +1,5 @@
+//! HTTP diagnostics component for the shared client's hosts
+
+pub mod view;
+
+pub use view::HttpDiagnosticsComponent;
@@ -0,0 +1,89 @@
+//! HTTP diagnostics view component
+//!
+//! Polls [`HttpClient::shared`] and shows each host it has contacted: circuit
+//! breaker state, request and failure counts, and latency percentiles over
+//! recent requests. Breaker states update on the host's next request.
+
+use crate::environment::native::HttpClient;
+use crate::environment::native::http_client::{CircuitBreakerState, HostStats, PoolStats};
+use dioxus::prelude::*;
+use std::time::Duration;
+
+/// How often the panel re-reads the statistics
+const REFRESH_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Main HTTP diagnostics component
+#[component]
+pub fn HttpDiagnosticsComponent() -> Element {
+    let mut stats = use_signal(|| HttpClient::shared().get_stats());
+
+    use_effect(move || {
+        spawn(async move {
+            loop {
+                tokio::time::sleep(REFRESH_INTERVAL).await;
+                stats.set(HttpClient::shared().get_stats());
+            }
+        });
+    });
+
+    let PoolStats {
+        active_connections,
+        total_requests,
+        failed_requests,
+        average_response_time_ms,
+        hosts,
+        ..
+    } = stats();
+
+    rsx! {
+        div {
+            class: "http-diagnostics p-4",
+
+            h2 { "Network" }
+            p {
+                class: "text-muted small mb-3",
+                "{total_requests} requests, {failed_requests} failed, {average_response_time_ms:.0} ms average, {active_connections} in flight"
+            }
+
+            if hosts.is_empty() {
+                p { class: "text-muted", "No requests yet." }
+            } else {
+                div {
+                    class: "host-stats-list",
+                    div {
+                        class: "host-stats-header d-flex small text-muted border-bottom pb-1 mb-1",
+                        span { class: "flex-grow-1", "Host" }
+                        span { class: "px-2", "Breaker" }
+                        span { class: "px-2", "Requests" }
+                        span { class: "px-2", "Failed" }
+                        span { class: "px-2", "p50 / p90 / p99" }
+                    }
+                    for host in hosts {
+                        HostRow { key: "{host.host}", host }
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Statistics of one host
+#[component]
+fn HostRow(host: HostStats) -> Element {
+    let (label, class) = match host.breaker_state {
+        CircuitBreakerState::Closed => ("closed", "text-success"),
+        CircuitBreakerState::HalfOpen => ("half-open", "text-warning"),
+        CircuitBreakerState::Open => ("open", "text-danger"),
+    };
+
+    rsx! {
+        div {
+            class: "host-stats-row d-flex small py-1",
+            span { class: "flex-grow-1", "{host.host}" }
+            span { class: "px-2 {class}", "{label}" }
+            span { class: "px-2", "{host.total_requests}" }
+            span { class: "px-2", "{host.failed_requests}" }
+            span { class: "px-2", "{host.p50_ms} / {host.p90_ms} / {host.p99_ms} ms" }
+        }
+    }
+}
@@ -55,11 +55,15 @@ pub fn handle_logout(
                 return;
             }
 
-            let result = environment
-                .model
-                .logout(user.id.clone())
-                .await
-                .map_err(|e| format!("Model logout failed: {e}"));
+            let result = match crate::auth::active_user_id() {
+                Some(owner) => environment
+                    .model
+                    .logout(owner)
+                    .await
+                    .map(|_| ())
+                    .map_err(|e| format!("Model logout failed: {e}")),
+                None => Ok(()),
+            };
 
             // Direct function call for performance
             if let Err(auth_error) = handle_logout_done(signal, result, &environment) {
@@ -47,6 +47,10 @@ pub fn LoggedInApp(environment: Signal<Environment>, should_show_login: Signal<b
                             }
                             crate::environment::types::AppEvent::FocusChange(focus) => {
                                 log::debug!("Focus change in loggedin view: {:?}", focus);
+                                crate::notifications::set_app_focused(matches!(
+                                    focus,
+                                    crate::environment::types::FocusChange::Gained
+                                ));
                                 // Focus changes may affect UI state
                                 update_signal.set(update_signal() + 1);
                             }
@@ -98,9 +102,18 @@ pub fn LoggedInApp(environment: Signal<Environment>, should_show_login: Signal<b
     use_context_provider(|| environment);
     
     // Provide selected conversation ID as context for chat component
-    let selected_conversation_id = use_signal(|| RecordId::new("conversation", "default_chat"));
+    let mut selected_conversation_id = use_signal(|| RecordId::new("conversation", "default_chat"));
     use_context_provider(|| selected_conversation_id);
 
+    // Open conversations from notification clicks; inline replies are sent by the router
+    use_future(move || async move {
+        let database = environment.read().database.clone();
+        let open_requests = crate::notifications::agent_completion::spawn_action_router(database);
+        while let Ok(conversation_id) = open_requests.recv_async().await {
+            selected_conversation_id.set(conversation_id);
+        }
+    });
+
     rsx! {
         div {
             MainComponent { store: state }
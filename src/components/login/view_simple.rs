@@ -1,20 +1,63 @@
-use crate::auth::{Provider, login_with_provider};
+use crate::auth::credential_store;
+use crate::auth::oidc::OidcRegistry;
+use crate::auth::{Provider, login_with_device_code, login_with_provider};
 use crate::environment::Environment;
 use dioxus::prelude::*;
+use oauth_core::DeviceCode;
 
 #[component]
 pub fn LoginApp(environment: Signal<Environment>, should_show_login: Signal<bool>) -> Element {
     let mut is_loading = use_signal(|| false);
     let mut error_message = use_signal(|| None::<String>);
+    // Sign in by entering a code on another device (SSH sessions, containers)
+    let mut use_device_code = use_signal(|| false);
+    let mut device_code = use_signal(|| None::<DeviceCode>);
+    // The encrypted credential file waits for the vault passphrase
+    let mut locked = use_signal(credential_store::needs_unlock);
+    let mut passphrase = use_signal(String::new);
+    // Company SSO providers configured for this profile
+    let oidc_providers = use_resource(|| async {
+        let configs = match OidcRegistry::new() {
+            Ok(registry) => registry.list().await,
+            Err(e) => Err(e),
+        };
+        configs.unwrap_or_else(|e| {
+            log::warn!("Could not load OpenID Connect providers: {}", e);
+            Vec::new()
+        })
+    });
 
     let mut handle_oauth_login = move |provider: Provider| {
         // Clear previous errors
         error_message.set(None);
         is_loading.set(true);
 
+        // Company SSO providers only support the loopback redirect
+        let device = *use_device_code.read() && Provider::all().contains(&provider);
+
         // Start real OAuth authentication
-        use_future(move || async move {
-            match login_with_provider(provider).await {
+        spawn(async move {
+            let result = if device {
+                // The login future is Send, so the code reaches the view over a channel
+                let (tx, rx) = tokio::sync::oneshot::channel();
+                spawn(async move {
+                    if let Ok(code) = rx.await {
+                        device_code.set(Some(code));
+                    }
+                });
+                login_with_device_code(
+                    provider.clone(),
+                    Box::new(move |code: &DeviceCode| {
+                        let _ = tx.send(code.clone());
+                    }),
+                )
+                .await
+            } else {
+                login_with_provider(provider.clone()).await
+            };
+            device_code.set(None);
+
+            match result {
                 Ok(_auth_state) => {
                     log::info!("OAuth login successful for provider: {:?}", provider);
 
@@ -38,6 +81,18 @@ pub fn LoginApp(environment: Signal<Environment>, should_show_login: Signal<bool
         });
     };
 
+    let mut handle_unlock = move || {
+        error_message.set(None);
+        match credential_store::unlock(&passphrase.read()) {
+            Ok(()) => locked.set(false),
+            Err(e) => {
+                log::warn!("Vault unlock failed: {}", e);
+                error_message.set(Some(format!("Unlock failed: {}", e)));
+            }
+        }
+        passphrase.set(String::new());
+    };
+
     rsx! {
         div {
             class: "login-container",
@@ -53,28 +108,96 @@ pub fn LoginApp(environment: Signal<Environment>, should_show_login: Signal<bool
                     }
                 }
 
-                div {
-                    class: "oauth-buttons",
-
-                    button {
-                        class: "oauth-button google-login-button",
-                        disabled: *is_loading.read(),
-                        onclick: move |_| { handle_oauth_login(Provider::Google); },
-                        if *is_loading.read() {
-                            "Signing In..."
-                        } else {
-                            "Sign in with Google"
+                if *locked.read() {
+                    form {
+                        class: "vault-unlock",
+                        onsubmit: move |evt| {
+                            evt.prevent_default();
+                            handle_unlock();
+                        },
+                        p { "Enter your vault passphrase to unlock saved credentials" }
+                        input {
+                            r#type: "password",
+                            autofocus: true,
+                            value: "{passphrase}",
+                            oninput: move |evt| passphrase.set(evt.value()),
+                        }
+                        button {
+                            class: "oauth-button",
+                            r#type: "submit",
+                            disabled: passphrase.read().is_empty(),
+                            "Unlock"
+                        }
+                    }
+                } else {
+                    if let Some(code) = device_code.read().as_ref() {
+                        div {
+                            class: "device-code",
+                            p {
+                                "Open "
+                                a {
+                                    href: "{code.verification_uri_complete.as_ref().unwrap_or(&code.verification_uri)}",
+                                    target: "_blank",
+                                    "{code.verification_uri}"
+                                }
+                                " on any device and enter:"
+                            }
+                            div { class: "device-user-code", "{code.user_code}" }
+                            p { class: "device-code-hint", "Waiting for approval..." }
+                        }
+                    }
+
+                    label {
+                        class: "device-code-toggle",
+                        input {
+                            r#type: "checkbox",
+                            checked: *use_device_code.read(),
+                            disabled: *is_loading.read(),
+                            onchange: move |evt| use_device_code.set(evt.checked()),
                         }
+                        " Sign in with a code on another device"
                     }
 
-                    button {
-                        class: "oauth-button github-login-button",
-                        disabled: *is_loading.read(),
-                        onclick: move |_| { handle_oauth_login(Provider::GitHub); },
-                        if *is_loading.read() {
-                            "Signing In..."
-                        } else {
-                            "Sign in with GitHub"
+                    div {
+                        class: "oauth-buttons",
+
+                        button {
+                            class: "oauth-button google-login-button",
+                            disabled: *is_loading.read(),
+                            onclick: move |_| { handle_oauth_login(Provider::Google); },
+                            if *is_loading.read() {
+                                "Signing In..."
+                            } else {
+                                "Sign in with Google"
+                            }
+                        }
+
+                        button {
+                            class: "oauth-button github-login-button",
+                            disabled: *is_loading.read(),
+                            onclick: move |_| { handle_oauth_login(Provider::GitHub); },
+                            if *is_loading.read() {
+                                "Signing In..."
+                            } else {
+                                "Sign in with GitHub"
+                            }
+                        }
+
+                        for config in oidc_providers.read().clone().unwrap_or_default() {
+                            button {
+                                key: "{config.id}",
+                                class: "oauth-button oidc-login-button",
+                                disabled: *is_loading.read(),
+                                onclick: {
+                                    let provider = config.provider();
+                                    move |_| { handle_oauth_login(provider.clone()); }
+                                },
+                                if *is_loading.read() {
+                                    "Signing In..."
+                                } else {
+                                    "Sign in with {config.name}"
+                                }
+                            }
                         }
                     }
                 }
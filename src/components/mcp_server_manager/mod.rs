@@ -0,0 +1,5 @@
+//! MCP server manager component for registering external tool servers
+
+pub mod view;
+
+pub use view::McpServerManagerComponent;
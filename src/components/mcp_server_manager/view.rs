@@ -0,0 +1,481 @@
+//! MCP server manager view component
+//!
+//! Lists user-registered MCP servers with check, edit and delete actions,
+//! and an editor for the transport, launch settings and secrets.
+//!
+//! Servers are enabled per agent in the template editor. Secret values are
+//! written to the credential vault and never shown again: the editor lists
+//! their names, and an empty value keeps the stored one.
+
+use crate::app::context::use_environment;
+use crate::services::external_mcp::{self, McpToolInfo};
+use crate::view_model::mcp_server::{McpServer, McpTransport, format_key_values, parse_key_values};
+use dioxus::prelude::*;
+use std::collections::HashMap;
+use surrealdb_types::{RecordId, ToSql};
+
+/// Main MCP server manager component
+///
+/// # State Management
+/// - servers: Vec<McpServer> - loaded from database
+/// - editing: Option<McpServer> - server being edited (id "new" = creating)
+/// - health: last health check result per server ID
+/// - form_*: Individual form field signals
+#[component]
+pub fn McpServerManagerComponent() -> Element {
+    let mut servers = use_signal(Vec::<McpServer>::new);
+    let mut error = use_signal(|| Option::<String>::None);
+    let mut health = use_signal(HashMap::<String, Result<Vec<McpToolInfo>, String>>::new);
+    let mut checking = use_signal(|| Option::<String>::None);
+
+    let mut editing = use_signal(|| Option::<McpServer>::None);
+    let mut form_name = use_signal(String::new);
+    let mut form_transport = use_signal(|| McpTransport::Stdio);
+    let mut form_command = use_signal(String::new);
+    let mut form_args = use_signal(String::new);
+    let mut form_env = use_signal(String::new);
+    let mut form_url = use_signal(String::new);
+    let mut form_secrets = use_signal(String::new);
+
+    let reload = move || {
+        let env = use_environment();
+        let db = env.read().model.database().clone();
+        spawn(async move {
+            match db.list_mcp_servers().await {
+                Ok(loaded) => servers.set(loaded),
+                Err(e) => error.set(Some(e)),
+            }
+        });
+    };
+
+    // Load servers on mount
+    use_effect(reload);
+
+    let handle_new = move |_| {
+        form_name.set(String::new());
+        form_transport.set(McpTransport::Stdio);
+        form_command.set(String::new());
+        form_args.set(String::new());
+        form_env.set(String::new());
+        form_url.set(String::new());
+        form_secrets.set(String::new());
+        editing.set(Some(McpServer {
+            id: RecordId::new("mcp_server", "new"),
+            ..Default::default()
+        }));
+    };
+
+    let handle_edit = move |server: McpServer| {
+        form_name.set(server.name.clone());
+        form_transport.set(server.transport);
+        form_command.set(server.command.clone());
+        form_args.set(server.args.join("\n"));
+        form_env.set(format_key_values(&server.env));
+        form_url.set(server.url.clone());
+        form_secrets.set(
+            server
+                .secret_keys
+                .iter()
+                .map(|key| format!("{}=", key))
+                .collect::<Vec<_>>()
+                .join("\n"),
+        );
+        editing.set(Some(server));
+    };
+
+    let handle_save = move |_| {
+        let Some(mut server) = editing.read().clone() else {
+            return;
+        };
+
+        let env_vars = match parse_key_values(&form_env.read()) {
+            Ok(values) => values,
+            Err(e) => {
+                error.set(Some(format!("Environment: {}", e)));
+                return;
+            }
+        };
+        let secrets = match parse_key_values(&form_secrets.read()) {
+            Ok(values) => values,
+            Err(e) => {
+                error.set(Some(format!("Secrets: {}", e)));
+                return;
+            }
+        };
+
+        server.name = form_name.read().trim().to_string();
+        server.transport = form_transport();
+        server.command = form_command.read().trim().to_string();
+        server.args = form_args
+            .read()
+            .lines()
+            .map(str::trim)
+            .filter(|arg| !arg.is_empty())
+            .map(str::to_string)
+            .collect();
+        server.env = env_vars.into_iter().collect();
+        server.url = form_url.read().trim().to_string();
+        server.secret_keys = secrets.keys().cloned().collect();
+
+        let env = use_environment();
+        let db = env.read().model.database().clone();
+        spawn(async move {
+            let is_new = server.id == RecordId::new("mcp_server", "new");
+            let result = async {
+                // Empty values keep what the vault already holds
+                let mut values = secrets;
+                if !is_new && values.values().any(String::is_empty) {
+                    let stored =
+                        external_mcp::load_secrets(&db.get_mcp_server(&server.id).await?).await?;
+                    for (key, value) in values.iter_mut() {
+                        if value.is_empty()
+                            && let Some(previous) = stored.get(key)
+                        {
+                            *value = previous.clone();
+                        }
+                    }
+                }
+
+                let id = if is_new {
+                    db.create_mcp_server(&server).await?
+                } else {
+                    db.update_mcp_server(&server).await?;
+                    server.id.clone()
+                };
+                values.retain(|_, value| !value.is_empty());
+                external_mcp::store_secrets(&id, &values).await
+            }
+            .await;
+
+            match result {
+                Ok(()) => {
+                    editing.set(None);
+                    error.set(None);
+                    reload();
+                }
+                Err(e) => error.set(Some(e)),
+            }
+        });
+    };
+
+    let handle_delete = move |server: McpServer| {
+        let env = use_environment();
+        let db = env.read().model.database().clone();
+        spawn(async move {
+            match db.delete_mcp_server(&server.id).await {
+                Ok(()) => {
+                    if let Err(e) = external_mcp::delete_secrets(&server.id).await {
+                        log::warn!("[MCP] {}", e);
+                    }
+                    health.write().remove(&server.id.to_sql());
+                }
+                Err(e) => error.set(Some(e)),
+            }
+            reload();
+        });
+    };
+
+    let handle_check = move |server: McpServer| {
+        let key = server.id.to_sql();
+        checking.set(Some(key.clone()));
+        spawn(async move {
+            let result = match external_mcp::load_secrets(&server).await {
+                Ok(secrets) => external_mcp::health_check(&server, &secrets).await,
+                Err(e) => Err(e),
+            };
+            health.write().insert(key, result);
+            checking.set(None);
+        });
+    };
+
+    rsx! {
+        div {
+            class: "mcp-server-manager p-4",
+
+            h2 { "MCP Servers" }
+            p {
+                class: "text-muted small mb-3",
+                "Tool servers agents can use. Enable them per agent in the template editor."
+            }
+
+            if let Some(message) = error() {
+                crate::widgets::ErrorBox {
+                    content: message,
+                    onclick: move |_| error.set(None)
+                }
+            }
+
+            if editing.read().is_some() {
+                McpServerEditor {
+                    name: form_name,
+                    transport: form_transport,
+                    command: form_command,
+                    args: form_args,
+                    env: form_env,
+                    url: form_url,
+                    secrets: form_secrets,
+                    on_save: handle_save,
+                    on_cancel: move |_| editing.set(None),
+                }
+            } else {
+                div {
+                    button {
+                        class: "btn btn-primary mb-3",
+                        onclick: handle_new,
+                        "+ New MCP Server"
+                    }
+
+                    if servers.read().is_empty() {
+                        p {
+                            class: "text-muted",
+                            "No MCP servers registered yet."
+                        }
+                    }
+
+                    div {
+                        class: "mcp-server-list",
+                        for server in servers.read().iter() {
+                            McpServerCard {
+                                key: "{server.id.to_sql()}",
+                                server: server.clone(),
+                                health: health.read().get(&server.id.to_sql()).cloned(),
+                                checking: checking.read().as_deref() == Some(server.id.to_sql().as_str()),
+                                on_check: handle_check,
+                                on_edit: handle_edit,
+                                on_delete: handle_delete,
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Server card for the list view, with the last health check result
+#[component]
+fn McpServerCard(
+    server: McpServer,
+    health: Option<Result<Vec<McpToolInfo>, String>>,
+    checking: bool,
+    on_check: EventHandler<McpServer>,
+    on_edit: EventHandler<McpServer>,
+    on_delete: EventHandler<McpServer>,
+) -> Element {
+    let target = match server.transport {
+        McpTransport::Stdio => format!("{} {}", server.command, server.args.join(" ")),
+        McpTransport::Http => server.url.clone(),
+    };
+    let for_check = server.clone();
+    let for_edit = server.clone();
+    let for_delete = server.clone();
+
+    rsx! {
+        div {
+            class: "mcp-server-card border rounded p-3 mb-2",
+
+            div {
+                class: "d-flex justify-between items-start",
+
+                div {
+                    h4 {
+                        class: "mb-1",
+                        "{server.name}"
+                        span { class: "text-muted small ml-2", "({server.transport})" }
+                    }
+                    p {
+                        class: "text-truncate text-muted small mb-1",
+                        style: "max-width: 400px;",
+                        "{target}"
+                    }
+                    if !server.secret_keys.is_empty() {
+                        p {
+                            class: "text-muted small mb-1",
+                            "Secrets: {server.secret_keys.join(\", \")}"
+                        }
+                    }
+                    match health {
+                        Some(Ok(tools)) => rsx! {
+                            p {
+                                class: "small mb-0",
+                                "✓ {tools.len()} tools"
+                            }
+                            ul {
+                                class: "small mb-0",
+                                for tool in tools {
+                                    li {
+                                        key: "{tool.name}",
+                                        title: "{tool.description}",
+                                        "{tool.name}"
+                                    }
+                                }
+                            }
+                        },
+                        Some(Err(e)) => rsx! {
+                            p { class: "text-danger small mb-0", "✗ {e}" }
+                        },
+                        None => rsx! {},
+                    }
+                }
+
+                div {
+                    class: "btn-group",
+                    button {
+                        class: "btn btn-sm btn-outline-secondary",
+                        disabled: checking,
+                        onclick: move |_| on_check.call(for_check.clone()),
+                        if checking { "Checking…" } else { "Check" }
+                    }
+                    button {
+                        class: "btn btn-sm btn-outline-primary",
+                        onclick: move |_| on_edit.call(for_edit.clone()),
+                        "Edit"
+                    }
+                    button {
+                        class: "btn btn-sm btn-outline-danger",
+                        onclick: move |_| on_delete.call(for_delete.clone()),
+                        "Delete"
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// MCP server editor form
+///
+/// All fields are controlled by parent signals.
+#[allow(clippy::too_many_arguments)]
+#[component]
+fn McpServerEditor(
+    name: Signal<String>,
+    transport: Signal<McpTransport>,
+    command: Signal<String>,
+    args: Signal<String>,
+    env: Signal<String>,
+    url: Signal<String>,
+    secrets: Signal<String>,
+    on_save: EventHandler<()>,
+    on_cancel: EventHandler<()>,
+) -> Element {
+    let secret_label = match transport() {
+        McpTransport::Stdio => "Secret environment variables (KEY=value, one per line)",
+        McpTransport::Http => "Secret headers (Name=value, one per line)",
+    };
+
+    rsx! {
+        div {
+            class: "mcp-server-editor border rounded p-4",
+
+            h3 { class: "mb-4", "MCP Server" }
+
+            div {
+                class: "mb-3",
+                label { class: "form-label", "Name (letters, digits, '-' and '_')" }
+                input {
+                    class: "form-control",
+                    r#type: "text",
+                    value: "{name.read()}",
+                    oninput: move |evt| name.set(evt.value()),
+                    placeholder: "github"
+                }
+            }
+
+            div {
+                class: "mb-3",
+                label { class: "form-label", "Transport" }
+                select {
+                    class: "form-select",
+                    value: "{transport}",
+                    onchange: move |evt| {
+                        transport.set(if evt.value() == "http" {
+                            McpTransport::Http
+                        } else {
+                            McpTransport::Stdio
+                        });
+                    },
+                    option { value: "stdio", "Command (stdio)" }
+                    option { value: "http", "HTTP URL" }
+                }
+            }
+
+            if transport() == McpTransport::Stdio {
+                div {
+                    class: "mb-3",
+                    label { class: "form-label", "Command" }
+                    input {
+                        class: "form-control",
+                        r#type: "text",
+                        value: "{command.read()}",
+                        oninput: move |evt| command.set(evt.value()),
+                        placeholder: "npx"
+                    }
+                }
+                div {
+                    class: "mb-3",
+                    label { class: "form-label", "Arguments (one per line)" }
+                    textarea {
+                        class: "form-control",
+                        value: "{args.read()}",
+                        oninput: move |evt| args.set(evt.value()),
+                        rows: "3",
+                        placeholder: "-y\n@modelcontextprotocol/server-github"
+                    }
+                }
+                div {
+                    class: "mb-3",
+                    label { class: "form-label", "Environment (KEY=value, one per line)" }
+                    textarea {
+                        class: "form-control",
+                        value: "{env.read()}",
+                        oninput: move |evt| env.set(evt.value()),
+                        rows: "3"
+                    }
+                }
+            } else {
+                div {
+                    class: "mb-3",
+                    label { class: "form-label", "URL" }
+                    input {
+                        class: "form-control",
+                        r#type: "text",
+                        value: "{url.read()}",
+                        oninput: move |evt| url.set(evt.value()),
+                        placeholder: "https://example.com/mcp"
+                    }
+                }
+            }
+
+            div {
+                class: "mb-3",
+                label { class: "form-label", "{secret_label}" }
+                textarea {
+                    class: "form-control",
+                    value: "{secrets.read()}",
+                    oninput: move |evt| secrets.set(evt.value()),
+                    rows: "3",
+                    placeholder: "GITHUB_TOKEN=ghp_..."
+                }
+                p {
+                    class: "text-muted small mb-0",
+                    "Stored in the system keychain. Leave a value empty to keep the saved one."
+                }
+            }
+
+            div {
+                class: "d-flex gap-2",
+                button {
+                    class: "btn btn-primary",
+                    disabled: name.read().trim().is_empty(),
+                    onclick: move |_| on_save.call(()),
+                    "Save"
+                }
+                button {
+                    class: "btn btn-secondary",
+                    onclick: move |_| on_cancel.call(()),
+                    "Cancel"
+                }
+            }
+        }
+    }
+}
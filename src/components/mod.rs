@@ -1,12 +1,16 @@
+pub mod api_key_manager;
 pub mod background;
 pub mod bookmarks;
 pub mod chat;
 pub mod component_stack;
 pub mod conversation;
+pub mod http_diagnostics;
 pub mod loggedin;
+pub mod mcp_server_manager;
 pub mod login;
 pub mod more;
 pub mod post;
+pub mod schedule_manager;
 pub mod shader_background;
 pub mod sidebar;
 pub mod status_timeline;
@@ -0,0 +1,5 @@
+//! Schedule manager component for scheduled and recurring prompts
+
+pub mod view;
+
+pub use view::ScheduleManagerComponent;
@@ -0,0 +1,484 @@
+//! Schedule manager view component
+//!
+//! Lists scheduled prompts with pause/resume, edit and delete actions, and
+//! an editor for the target conversation, agent, prompt and trigger.
+//!
+//! Runs themselves are performed by the background scheduler in
+//! [src/services/scheduler.rs](../../services/scheduler.rs).
+
+use crate::app::context::use_environment;
+use crate::view_model::agent::AgentTemplate;
+use crate::view_model::conversation::ConversationSummary;
+use crate::view_model::schedule::{Schedule, ScheduleTrigger};
+use chrono::Local;
+use dioxus::prelude::*;
+use surrealdb_types::{RecordId, ToSql};
+
+/// Trigger kinds offered by the editor
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum TriggerKind {
+    Cron,
+    Interval,
+}
+
+/// Main schedule manager component
+///
+/// # State Management
+/// - schedules: Vec<Schedule> - loaded from database
+/// - editing: Option<Schedule> - schedule being edited (id "new" = creating)
+/// - form_*: Individual form field signals
+#[component]
+pub fn ScheduleManagerComponent() -> Element {
+    let mut schedules = use_signal(Vec::<Schedule>::new);
+    let mut conversations = use_signal(Vec::<ConversationSummary>::new);
+    let mut templates = use_signal(Vec::<AgentTemplate>::new);
+    let mut error = use_signal(|| Option::<String>::None);
+
+    let mut editing = use_signal(|| Option::<Schedule>::None);
+    let mut form_conversation = use_signal(String::new);
+    let mut form_agent = use_signal(String::new);
+    let mut form_prompt = use_signal(String::new);
+    let mut form_kind = use_signal(|| TriggerKind::Cron);
+    let mut form_cron = use_signal(|| "0 9 * * MON-FRI".to_string());
+    let mut form_interval_minutes = use_signal(|| 60i64);
+
+    let reload = move || {
+        let env = use_environment();
+        let db = env.read().model.database().clone();
+        spawn(async move {
+            match db.list_schedules().await {
+                Ok(loaded) => schedules.set(loaded),
+                Err(e) => error.set(Some(e)),
+            }
+        });
+    };
+
+    // Load schedules, conversations and agents on mount
+    use_effect(move || {
+        let env = use_environment();
+        let db = env.read().model.database().clone();
+
+        let user_id = crate::auth::active_user_id().unwrap_or_default();
+
+        spawn(async move {
+            match db.list_conversations_for_user(&user_id).await {
+                Ok(loaded) => conversations.set(loaded),
+                Err(e) => log::error!("Failed to load conversations: {}", e),
+            }
+            match db.list_templates().await {
+                Ok(loaded) => templates.set(loaded),
+                Err(e) => log::error!("Failed to load templates: {}", e),
+            }
+        });
+        reload();
+    });
+
+    let handle_new = move |_| {
+        form_conversation.set(
+            conversations
+                .read()
+                .first()
+                .map(|c| c.id.to_sql())
+                .unwrap_or_default(),
+        );
+        form_agent.set(String::new());
+        form_prompt.set(String::new());
+        form_kind.set(TriggerKind::Cron);
+        form_cron.set("0 9 * * MON-FRI".to_string());
+        form_interval_minutes.set(60);
+        editing.set(Some(Schedule {
+            id: RecordId::new("schedule", "new"),
+            ..Default::default()
+        }));
+    };
+
+    let handle_edit = move |schedule: Schedule| {
+        form_conversation.set(schedule.conversation_id.to_sql());
+        form_agent.set(
+            schedule
+                .agent_id
+                .as_ref()
+                .map(|a| a.to_sql())
+                .unwrap_or_default(),
+        );
+        form_prompt.set(schedule.prompt.clone());
+        match schedule.trigger() {
+            Some(ScheduleTrigger::Interval(secs)) => {
+                form_kind.set(TriggerKind::Interval);
+                form_interval_minutes.set(secs / 60);
+            }
+            Some(ScheduleTrigger::Cron(expr)) => {
+                form_kind.set(TriggerKind::Cron);
+                form_cron.set(expr);
+            }
+            None => form_kind.set(TriggerKind::Cron),
+        }
+        editing.set(Some(schedule));
+    };
+
+    let handle_save = move |_| {
+        let Some(mut schedule) = editing.read().clone() else {
+            return;
+        };
+
+        let Ok(conversation_id) = RecordId::parse_simple(&form_conversation.read()) else {
+            error.set(Some("Choose a conversation".to_string()));
+            return;
+        };
+        schedule.conversation_id = conversation_id;
+        schedule.agent_id = RecordId::parse_simple(&form_agent.read()).ok();
+        schedule.prompt = form_prompt.read().trim().to_string();
+        schedule.set_trigger(match form_kind() {
+            TriggerKind::Cron => ScheduleTrigger::Cron(form_cron.read().trim().to_string()),
+            TriggerKind::Interval => ScheduleTrigger::Interval(form_interval_minutes() * 60),
+        });
+
+        let env = use_environment();
+        let db = env.read().model.database().clone();
+        spawn(async move {
+            let is_new = schedule.id == RecordId::new("schedule", "new");
+            let result = if is_new {
+                db.create_schedule(&schedule).await.map(|_| ())
+            } else {
+                db.update_schedule(&schedule).await
+            };
+
+            match result {
+                Ok(()) => {
+                    editing.set(None);
+                    error.set(None);
+                    reload();
+                }
+                Err(e) => error.set(Some(e)),
+            }
+        });
+    };
+
+    let handle_toggle = move |schedule: Schedule| {
+        let env = use_environment();
+        let db = env.read().model.database().clone();
+        spawn(async move {
+            if let Err(e) = db.set_schedule_paused(&schedule.id, !schedule.paused).await {
+                error.set(Some(e));
+            }
+            reload();
+        });
+    };
+
+    let handle_delete = move |schedule: Schedule| {
+        let env = use_environment();
+        let db = env.read().model.database().clone();
+        spawn(async move {
+            if let Err(e) = db.delete_schedule(&schedule.id).await {
+                error.set(Some(e));
+            }
+            reload();
+        });
+    };
+
+    // Conversation title lookup for the list
+    let conversation_title = move |id: &RecordId| {
+        conversations
+            .read()
+            .iter()
+            .find(|c| &c.id == id)
+            .map(|c| c.title.clone())
+            .unwrap_or_else(|| id.to_sql())
+    };
+
+    rsx! {
+        div {
+            class: "schedule-manager p-4",
+
+            h2 { "Scheduled Prompts" }
+
+            if let Some(message) = error() {
+                crate::widgets::ErrorBox {
+                    content: message,
+                    onclick: move |_| error.set(None)
+                }
+            }
+
+            if editing.read().is_some() {
+                ScheduleEditor {
+                    conversations: conversations.read().clone(),
+                    templates: templates.read().clone(),
+                    conversation: form_conversation,
+                    agent: form_agent,
+                    prompt: form_prompt,
+                    kind: form_kind,
+                    cron: form_cron,
+                    interval_minutes: form_interval_minutes,
+                    on_save: handle_save,
+                    on_cancel: move |_| editing.set(None),
+                }
+            } else {
+                div {
+                    button {
+                        class: "btn btn-primary mb-3",
+                        onclick: handle_new,
+                        "+ New Schedule"
+                    }
+
+                    if schedules.read().is_empty() {
+                        p {
+                            class: "text-muted",
+                            "No scheduled prompts yet."
+                        }
+                    }
+
+                    div {
+                        class: "schedule-list",
+                        for schedule in schedules.read().iter() {
+                            ScheduleCard {
+                                key: "{schedule.id.to_sql()}",
+                                schedule: schedule.clone(),
+                                conversation_title: conversation_title(&schedule.conversation_id),
+                                on_edit: handle_edit,
+                                on_toggle: handle_toggle,
+                                on_delete: handle_delete,
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Schedule card for the list view
+#[component]
+fn ScheduleCard(
+    schedule: Schedule,
+    conversation_title: String,
+    on_edit: EventHandler<Schedule>,
+    on_toggle: EventHandler<Schedule>,
+    on_delete: EventHandler<Schedule>,
+) -> Element {
+    let next_run = schedule
+        .next_run_at
+        .with_timezone(&Local)
+        .format("%a %d %b %H:%M")
+        .to_string();
+    let last_run = schedule
+        .last_run_at
+        .map(|t| t.with_timezone(&Local).format("%a %d %b %H:%M").to_string());
+    let trigger = schedule.describe_trigger();
+    let for_edit = schedule.clone();
+    let for_toggle = schedule.clone();
+    let for_delete = schedule.clone();
+
+    rsx! {
+        div {
+            class: "schedule-card border rounded p-3 mb-2",
+
+            div {
+                class: "d-flex justify-between items-start",
+
+                div {
+                    h4 {
+                        class: "mb-1",
+                        "{conversation_title}"
+                        if schedule.paused {
+                            span { class: "text-muted small ml-2", "(paused)" }
+                        }
+                    }
+                    p {
+                        class: "text-muted small mb-1",
+                        "{trigger}"
+                        if !schedule.paused {
+                            " · next {next_run}"
+                        }
+                        if let Some(last) = last_run {
+                            " · last {last}"
+                        }
+                    }
+                    p {
+                        class: "text-truncate mb-0",
+                        style: "max-width: 400px;",
+                        "{schedule.prompt}"
+                    }
+                    if let Some(last_error) = &schedule.last_error {
+                        p {
+                            class: "text-danger small mb-0",
+                            "Last run failed: {last_error}"
+                        }
+                    }
+                }
+
+                div {
+                    class: "btn-group",
+                    button {
+                        class: "btn btn-sm btn-outline-secondary",
+                        onclick: move |_| on_toggle.call(for_toggle.clone()),
+                        if schedule.paused { "Resume" } else { "Pause" }
+                    }
+                    button {
+                        class: "btn btn-sm btn-outline-primary",
+                        onclick: move |_| on_edit.call(for_edit.clone()),
+                        "Edit"
+                    }
+                    button {
+                        class: "btn btn-sm btn-outline-danger",
+                        onclick: move |_| on_delete.call(for_delete.clone()),
+                        "Delete"
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Schedule editor form
+///
+/// All fields are controlled by parent signals.
+#[allow(clippy::too_many_arguments)]
+#[component]
+fn ScheduleEditor(
+    conversations: Vec<ConversationSummary>,
+    templates: Vec<AgentTemplate>,
+    conversation: Signal<String>,
+    agent: Signal<String>,
+    prompt: Signal<String>,
+    kind: Signal<TriggerKind>,
+    cron: Signal<String>,
+    interval_minutes: Signal<i64>,
+    on_save: EventHandler<()>,
+    on_cancel: EventHandler<()>,
+) -> Element {
+    // Only agents participating in the selected conversation can be targeted
+    let participants: Vec<AgentTemplate> = conversations
+        .iter()
+        .find(|c| c.id.to_sql() == *conversation.read())
+        .map(|c| {
+            templates
+                .iter()
+                .filter(|t| c.participants.contains(&t.id))
+                .cloned()
+                .collect()
+        })
+        .unwrap_or_default();
+
+    rsx! {
+        div {
+            class: "schedule-editor border rounded p-4",
+
+            h3 { class: "mb-4", "Schedule Editor" }
+
+            div {
+                class: "mb-3",
+                label { class: "form-label", "Conversation" }
+                select {
+                    class: "form-select",
+                    value: "{conversation.read()}",
+                    onchange: move |evt| {
+                        conversation.set(evt.value());
+                        agent.set(String::new());
+                    },
+                    for c in conversations.iter() {
+                        option {
+                            key: "{c.id.to_sql()}",
+                            value: "{c.id.to_sql()}",
+                            selected: c.id.to_sql() == *conversation.read(),
+                            "{c.title}"
+                        }
+                    }
+                }
+            }
+
+            div {
+                class: "mb-3",
+                label { class: "form-label", "Agent" }
+                select {
+                    class: "form-select",
+                    value: "{agent.read()}",
+                    onchange: move |evt| agent.set(evt.value()),
+                    option { value: "", "All participants" }
+                    for t in participants.iter() {
+                        option {
+                            key: "{t.id.to_sql()}",
+                            value: "{t.id.to_sql()}",
+                            selected: t.id.to_sql() == *agent.read(),
+                            "{t.name}"
+                        }
+                    }
+                }
+            }
+
+            div {
+                class: "mb-3",
+                label { class: "form-label", "Prompt" }
+                textarea {
+                    class: "form-control",
+                    value: "{prompt.read()}",
+                    oninput: move |evt| prompt.set(evt.value()),
+                    rows: "4",
+                    placeholder: "Summarize yesterday's repository changes..."
+                }
+            }
+
+            div {
+                class: "mb-3",
+                label { class: "form-label", "Repeat" }
+                select {
+                    class: "form-select",
+                    value: if kind() == TriggerKind::Cron { "cron" } else { "interval" },
+                    onchange: move |evt| {
+                        kind.set(if evt.value() == "interval" {
+                            TriggerKind::Interval
+                        } else {
+                            TriggerKind::Cron
+                        });
+                    },
+                    option { value: "cron", "On a cron schedule" }
+                    option { value: "interval", "Every N minutes" }
+                }
+            }
+
+            if kind() == TriggerKind::Cron {
+                div {
+                    class: "mb-3",
+                    label { class: "form-label", "Cron expression (minute hour day month weekday, local time)" }
+                    input {
+                        class: "form-control",
+                        r#type: "text",
+                        value: "{cron.read()}",
+                        oninput: move |evt| cron.set(evt.value()),
+                        placeholder: "0 9 * * MON-FRI"
+                    }
+                }
+            } else {
+                div {
+                    class: "mb-3",
+                    label { class: "form-label", "Interval (minutes)" }
+                    input {
+                        class: "form-control",
+                        r#type: "number",
+                        value: "{interval_minutes.read()}",
+                        oninput: move |evt| {
+                            if let Ok(val) = evt.value().parse::<i64>() {
+                                interval_minutes.set(val);
+                            }
+                        },
+                        min: "1"
+                    }
+                }
+            }
+
+            div {
+                class: "d-flex gap-2",
+                button {
+                    class: "btn btn-primary",
+                    disabled: prompt.read().trim().is_empty(),
+                    onclick: move |_| on_save.call(()),
+                    "Save"
+                }
+                button {
+                    class: "btn btn-secondary",
+                    onclick: move |_| on_cancel.call(()),
+                    "Cancel"
+                }
+            }
+        }
+    }
+}
@@ -203,8 +203,9 @@ pub fn handle_action(mut signal: SidebarSignal, action: SidebarAction, environme
         // Load conversation summaries and calculate total unread
         let database = environment.database.clone();
         let mut signal_clone = signal;
+        let user_id = crate::auth::active_user_id().unwrap_or_default();
         spawn(async move {
-            match database.list_conversations().await {
+            match database.list_conversations_for_user(&user_id).await {
                 Ok(summaries) => {
                     let total_unread: u32 = summaries.iter().map(|s| s.unread_count).sum();
 
@@ -64,9 +64,10 @@ impl TimelineProvider for BookmarkTimelineProvider {
     ) -> Pin<Box<dyn Future<Output = Result<Vec<Status>, String>> + Send>> {
         // Get bookmarked messages from database and convert to Status
         let db = self.environment.database.clone();
-        let user_id = "hardcoded-david-maple"; // Q39: MVP hardcoded user
+        let user_id = crate::auth::active_user_id();
         Box::pin(async move {
-            let messages = db.get_bookmarked_messages(user_id).await?;
+            let user_id = user_id.ok_or_else(|| "No account is signed in".to_string())?;
+            let messages = db.get_bookmarked_messages(&user_id).await?;
             // Convert Message to Status for timeline system
             Ok(messages
                 .iter()
@@ -71,8 +71,9 @@ impl TimelineProvider for ConversationListProvider {
         // Note: Database::list_conversations() doesn't support pagination yet
         // Always returns full list ordered by last_message_at DESC
         let db = self.environment.database.clone();
+        let user_id = crate::auth::active_user_id().unwrap_or_default();
         Box::pin(async move {
-            let summaries = db.list_conversations().await?;
+            let summaries = db.list_conversations_for_user(&user_id).await?;
             // Convert ConversationSummary to Status for timeline system
             Ok(summaries
                 .iter()
@@ -73,8 +73,9 @@ impl TimelineProvider for RoomListProvider {
     {
         // Load conversations as "rooms"
         let db = self.environment.database.clone();
+        let user_id = crate::auth::active_user_id().unwrap_or_default();
         Box::pin(async move {
-            let summaries = db.list_conversations().await?;
+            let summaries = db.list_conversations_for_user(&user_id).await?;
             // Convert ConversationSummary to Status for timeline system
             Ok(summaries
                 .iter()
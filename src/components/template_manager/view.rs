@@ -10,9 +10,13 @@
 //! - Q19: User chooses agent model (Sonnet/Haiku/Opus)
 //! - Q20: System prompt is core configuration
 //! - Q46: Templates have icon and color customization
+//! - External MCP servers are enabled per template (see mcp_server_manager)
+//! - Each template picks its inference backend (see src/services/llm)
 
 use crate::app::context::use_environment;
-use crate::view_model::agent::{AgentModel, AgentTemplate};
+use crate::services::llm::{api_keys, openai};
+use crate::view_model::agent::{AgentBackend, AgentModel, AgentTemplate};
+use crate::view_model::mcp_server::McpServer;
 use chrono::Utc;
 use dioxus::prelude::*;
 use surrealdb_types::{RecordId, ToSql};
@@ -58,6 +62,16 @@ pub fn TemplateManagerComponent() -> Element {
     let mut form_max_turns = use_signal(|| 50u32);
     let mut form_icon = use_signal(String::new);
     let mut form_color = use_signal(String::new);
+    let mut form_mcp_servers = use_signal(Vec::<RecordId>::new);
+    let mut form_backend = use_signal(AgentBackend::default);
+    let mut form_backend_url = use_signal(String::new);
+    let mut form_backend_model = use_signal(String::new);
+    // API key to store on save (blank keeps the stored key)
+    let mut form_api_key = use_signal(String::new);
+    let mut has_api_key = use_signal(|| false);
+
+    // Registered MCP servers offered in the editor
+    let mut mcp_servers = use_signal(Vec::<McpServer>::new);
 
     // Load templates on mount
     use_effect(move || {
@@ -74,6 +88,10 @@ pub fn TemplateManagerComponent() -> Element {
                     log::error!("Failed to load templates: {}", e);
                 }
             }
+            match db.list_mcp_servers().await {
+                Ok(loaded) => mcp_servers.set(loaded),
+                Err(e) => log::error!("Failed to load MCP servers: {}", e),
+            }
         });
     });
 
@@ -82,6 +100,8 @@ pub fn TemplateManagerComponent() -> Element {
         let env = use_environment();
         let db = env.read().model.database().clone();
         let current_editing_id = editing_id.read().clone();
+        let selected_servers = form_mcp_servers.read().clone();
+        let api_key = form_api_key.read().clone();
 
         // Build template from form
         let template = AgentTemplate {
@@ -102,6 +122,11 @@ pub fn TemplateManagerComponent() -> Element {
             } else {
                 Some(form_color.read().clone())
             },
+            backend: *form_backend.read(),
+            backend_url: Some(form_backend_url.read().trim().to_string())
+                .filter(|url| !url.is_empty()),
+            backend_model: Some(form_backend_model.read().trim().to_string())
+                .filter(|model| !model.is_empty()),
             created_at: Utc::now(),
         };
 
@@ -112,14 +137,26 @@ pub fn TemplateManagerComponent() -> Element {
                 .unwrap_or(true)
             {
                 // Creating new template
-                db.create_template(&template)
-                    .await
-                    .map(|id| log::info!("Created template: {}", id.to_sql()))
+                db.create_template(&template).await.inspect(|id| {
+                    log::info!("Created template: {}", id.to_sql());
+                })
             } else {
                 // Updating existing template
-                db.update_template(&template)
-                    .await
-                    .map(|_| log::info!("Updated template: {}", template.id.to_sql()))
+                db.update_template(&template).await.map(|_| {
+                    log::info!("Updated template: {}", template.id.to_sql());
+                    template.id.clone()
+                })
+            };
+
+            // Enabled MCP servers live in their own table, the API key in the vault
+            let result = match result {
+                Ok(id) => match db.set_template_mcp_servers(&id, &selected_servers).await {
+                    Ok(()) if !api_key.trim().is_empty() => {
+                        api_keys::store_template_key(&id, &api_key).await
+                    }
+                    other => other,
+                },
+                Err(e) => Err(e),
             };
 
             if let Err(e) = result {
@@ -150,6 +187,23 @@ pub fn TemplateManagerComponent() -> Element {
         form_max_turns.set(template.max_turns);
         form_icon.set(template.icon.clone().unwrap_or_default());
         form_color.set(template.color.clone().unwrap_or_default());
+        form_mcp_servers.set(Vec::new());
+        form_backend.set(template.backend);
+        form_backend_url.set(template.backend_url.clone().unwrap_or_default());
+        form_backend_model.set(template.backend_model.clone().unwrap_or_default());
+        form_api_key.set(String::new());
+        has_api_key.set(false);
+
+        let env = use_environment();
+        let db = env.read().model.database().clone();
+        let template_id = template.id.clone();
+        spawn(async move {
+            match db.list_template_mcp_servers(&template_id).await {
+                Ok(enabled) => form_mcp_servers.set(enabled.into_iter().map(|s| s.id).collect()),
+                Err(e) => log::error!("Failed to load template MCP servers: {}", e),
+            }
+            has_api_key.set(api_keys::has_template_key(&template_id).await);
+        });
 
         editing_id.set(Some(template.id.to_sql()));
     };
@@ -191,6 +245,12 @@ pub fn TemplateManagerComponent() -> Element {
         form_max_turns.set(50);
         form_icon.set(String::new());
         form_color.set(String::new());
+        form_mcp_servers.set(Vec::new());
+        form_backend.set(AgentBackend::default());
+        form_backend_url.set(String::new());
+        form_backend_model.set(String::new());
+        form_api_key.set(String::new());
+        has_api_key.set(false);
 
         editing_id.set(Some(String::new())); // Empty string = creating new
     };
@@ -208,9 +268,16 @@ pub fn TemplateManagerComponent() -> Element {
                     name: form_name,
                     system_prompt: form_system_prompt,
                     model: form_model,
+                    backend: form_backend,
+                    backend_url: form_backend_url,
+                    backend_model: form_backend_model,
+                    api_key: form_api_key,
+                    has_api_key: *has_api_key.read(),
                     max_turns: form_max_turns,
                     icon: form_icon,
                     color: form_color,
+                    available_mcp_servers: mcp_servers.read().clone(),
+                    mcp_servers: form_mcp_servers,
                     on_save: handle_save,
                     on_cancel: handle_cancel,
                 }
@@ -252,6 +319,10 @@ fn TemplateCard(
     // Clone template for use in both closures
     let template_for_edit = template.clone();
     let template_id = template.id.to_sql();
+    let model_label = match template.backend {
+        AgentBackend::Claude => template.model.to_string(),
+        backend => format!("{} ({})", template.model_name(), backend),
+    };
 
     rsx! {
         div {
@@ -270,7 +341,7 @@ fn TemplateCard(
                     }
                     p {
                         class: "text-muted small mb-1",
-                        "Model: {template.model}"
+                        "Model: {model_label}"
                     }
                     p {
                         class: "text-truncate mb-0",
@@ -301,17 +372,41 @@ fn TemplateCard(
 ///
 /// Form for creating or editing templates.
 /// All fields are controlled by parent signals.
+#[allow(clippy::too_many_arguments)]
 #[component]
 fn TemplateEditor(
     name: Signal<String>,
     system_prompt: Signal<String>,
     model: Signal<AgentModel>,
+    backend: Signal<AgentBackend>,
+    backend_url: Signal<String>,
+    backend_model: Signal<String>,
+    api_key: Signal<String>,
+    has_api_key: bool,
     max_turns: Signal<u32>,
     icon: Signal<String>,
     color: Signal<String>,
+    available_mcp_servers: Vec<McpServer>,
+    mcp_servers: Signal<Vec<RecordId>>,
     on_save: EventHandler<()>,
     on_cancel: EventHandler<()>,
 ) -> Element {
+    let local = *backend.read() == AgentBackend::Local;
+    let (model_placeholder, url_placeholder) = if local {
+        ("llama3.1", openai::DEFAULT_LOCAL_URL)
+    } else {
+        ("gpt-4o", openai::DEFAULT_OPENAI_URL)
+    };
+    let key_placeholder = if has_api_key {
+        "Stored - leave blank to keep it"
+    } else {
+        match *backend.read() {
+            AgentBackend::Claude => "Uses the default Anthropic key if left blank",
+            AgentBackend::Openai => "Uses the default OpenAI key if left blank",
+            AgentBackend::Local => "Optional",
+        }
+    };
+
     rsx! {
         div {
             class: "template-editor border rounded p-4",
@@ -352,26 +447,105 @@ fn TemplateEditor(
                 class: "mb-3",
                 label {
                     class: "form-label",
-                    "Model"
+                    "Backend"
                 }
                 select {
                     class: "form-select",
-                    value: match *model.read() {
-                        AgentModel::Sonnet => "sonnet",
-                        AgentModel::Haiku => "haiku",
-                        AgentModel::Opus => "opus",
-                    },
+                    value: "{backend.read()}",
                     onchange: move |evt| {
                         let selected = match evt.value().as_str() {
-                            "haiku" => AgentModel::Haiku,
-                            "opus" => AgentModel::Opus,
-                            _ => AgentModel::Sonnet,
+                            "openai" => AgentBackend::Openai,
+                            "local" => AgentBackend::Local,
+                            _ => AgentBackend::Claude,
                         };
-                        model.set(selected);
+                        backend.set(selected);
                     },
-                    option { value: "sonnet", "Claude 3.5 Sonnet (Balanced)" }
-                    option { value: "haiku", "Claude 3 Haiku (Fast)" }
-                    option { value: "opus", "Claude 3 Opus (Capable)" }
+                    option { value: "claude", "Claude Code (built-in tools)" }
+                    option { value: "openai", "OpenAI-compatible API" }
+                    option { value: "local", "Local server (Ollama, llama.cpp)" }
+                }
+            }
+
+            if *backend.read() != AgentBackend::Claude {
+                div {
+                    class: "row mb-3",
+                    div {
+                        class: "col-md-6",
+                        label {
+                            class: "form-label",
+                            "Model name"
+                        }
+                        input {
+                            class: "form-control",
+                            r#type: "text",
+                            value: "{backend_model.read()}",
+                            oninput: move |evt| backend_model.set(evt.value().clone()),
+                            placeholder: model_placeholder
+                        }
+                    }
+                    div {
+                        class: "col-md-6",
+                        label {
+                            class: "form-label",
+                            "Base URL"
+                        }
+                        input {
+                            class: "form-control",
+                            r#type: "text",
+                            value: "{backend_url.read()}",
+                            oninput: move |evt| backend_url.set(evt.value().clone()),
+                            placeholder: url_placeholder
+                        }
+                    }
+                }
+                p {
+                    class: "text-muted small",
+                    "Built-in Claude Code tools are unavailable on this backend; enabled MCP servers still provide tools."
+                }
+            }
+
+            if *backend.read() == AgentBackend::Claude {
+                div {
+                    class: "mb-3",
+                    label {
+                        class: "form-label",
+                        "Model"
+                    }
+                    select {
+                        class: "form-select",
+                        value: match *model.read() {
+                            AgentModel::Sonnet => "sonnet",
+                            AgentModel::Haiku => "haiku",
+                            AgentModel::Opus => "opus",
+                        },
+                        onchange: move |evt| {
+                            let selected = match evt.value().as_str() {
+                                "haiku" => AgentModel::Haiku,
+                                "opus" => AgentModel::Opus,
+                                _ => AgentModel::Sonnet,
+                            };
+                            model.set(selected);
+                        },
+                        option { value: "sonnet", "Claude 3.5 Sonnet (Balanced)" }
+                        option { value: "haiku", "Claude 3 Haiku (Fast)" }
+                        option { value: "opus", "Claude 3 Opus (Capable)" }
+                    }
+                }
+            }
+
+            // Per-template key, so a team's agents bill to its own account
+            div {
+                class: "mb-3",
+                label {
+                    class: "form-label",
+                    "API key"
+                }
+                input {
+                    class: "form-control",
+                    r#type: "password",
+                    value: "{api_key.read()}",
+                    oninput: move |evt| api_key.set(evt.value().clone()),
+                    placeholder: key_placeholder
                 }
             }
 
@@ -427,6 +601,46 @@ fn TemplateEditor(
                 }
             }
 
+            div {
+                class: "mb-3",
+                label {
+                    class: "form-label",
+                    "MCP Servers"
+                }
+                if available_mcp_servers.is_empty() {
+                    p {
+                        class: "text-muted small mb-0",
+                        "No MCP servers registered. Add them under MCP Servers."
+                    }
+                }
+                for server in available_mcp_servers {
+                    div {
+                        key: "{server.id.to_sql()}",
+                        class: "form-check",
+                        input {
+                            class: "form-check-input",
+                            r#type: "checkbox",
+                            checked: mcp_servers.read().contains(&server.id),
+                            onchange: {
+                                let server_id = server.id.clone();
+                                move |evt: Event<FormData>| {
+                                    let mut selected = mcp_servers.write();
+                                    selected.retain(|id| id != &server_id);
+                                    if evt.checked() {
+                                        selected.push(server_id.clone());
+                                    }
+                                }
+                            }
+                        }
+                        label {
+                            class: "form-check-label",
+                            "{server.name} "
+                            span { class: "text-muted small", "({server.transport})" }
+                        }
+                    }
+                }
+            }
+
             div {
                 class: "d-flex gap-2",
                 button {
@@ -5,6 +5,8 @@
 use dioxus::prelude::*;
 use serde::{Deserialize, Serialize};
 
+pub mod profile;
+
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct AppConfig {
     /// Debug mode enabled (from CYRUP_DEV_MODE environment variable)
@@ -0,0 +1,437 @@
+//! Profile selection and per-profile storage locations
+//!
+//! A profile isolates one chat history together with its settings files,
+//! token budget and keyring entries, so work and personal use (or test
+//! fixtures) never mix.
+//!
+//! Resolution order for the active profile:
+//! 1. `--profile <name>` command line argument
+//! 2. `CYRUP_PROFILE` environment variable
+//! 3. Last profile chosen in preferences (`profiles.json`)
+//! 4. `default`
+//!
+//! `CYRUP_DATA_DIR` overrides the base data directory for every profile.
+
+use crate::view_model::TokenBudgetConfig;
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use std::sync::OnceLock;
+
+/// Name of the profile used when none is selected
+pub const DEFAULT_PROFILE: &str = "default";
+
+/// Environment variable overriding the base data directory
+pub const DATA_DIR_ENV: &str = "CYRUP_DATA_DIR";
+
+/// Environment variable selecting the profile
+pub const PROFILE_ENV: &str = "CYRUP_PROFILE";
+
+const PROFILES_FILE: &str = "profiles.json";
+const TOKEN_BUDGET_FILE: &str = "token_budget.json";
+
+static ACTIVE_PROFILE: OnceLock<Profile> = OnceLock::new();
+
+/// Persisted profile registry
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct ProfilesFile {
+    active: Option<String>,
+    #[serde(default)]
+    known: Vec<String>,
+}
+
+/// A named, isolated set of chat data and settings
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Profile {
+    name: String,
+    base_dir: PathBuf,
+    /// Settings root before the per-profile suffix is applied
+    settings_root: PathBuf,
+}
+
+impl Profile {
+    /// Build a profile rooted at the standard (or overridden) directories
+    ///
+    /// # Errors
+    /// Returns error if the name is invalid or no data directory can be determined
+    pub fn new(name: &str) -> Result<Self, String> {
+        validate_name(name)?;
+
+        let (base_dir, settings_root) = match std::env::var_os(DATA_DIR_ENV) {
+            Some(dir) => {
+                let base = PathBuf::from(dir);
+                let settings = base.join("settings");
+                (base, settings)
+            }
+            None => {
+                let base = dirs::data_local_dir()
+                    .ok_or("Could not determine data directory")?
+                    .join("cyrup");
+                let settings = directories_next::ProjectDirs::from("com", "stylemac", "cyrup")
+                    .ok_or("Unable to determine system project directories")?
+                    .config_dir()
+                    .to_path_buf();
+                (base, settings)
+            }
+        };
+
+        Ok(Self {
+            name: name.to_string(),
+            base_dir,
+            settings_root,
+        })
+    }
+
+    /// Resolve the active profile from arguments, environment and saved choice
+    ///
+    /// # Errors
+    /// Returns error if the selected name is invalid
+    pub fn resolve<I: IntoIterator<Item = String>>(args: I) -> Result<Self, String> {
+        let from_args = profile_from_args(args);
+        let from_env = std::env::var(PROFILE_ENV).ok().filter(|p| !p.is_empty());
+
+        let name = match from_args.or(from_env) {
+            Some(name) => name,
+            None => Self::new(DEFAULT_PROFILE)?.saved_choice(),
+        };
+
+        Self::new(&name)
+    }
+
+    /// Profile name
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// True for the default profile (which keeps the pre-profile storage layout)
+    pub fn is_default(&self) -> bool {
+        self.name == DEFAULT_PROFILE
+    }
+
+    /// Base data directory shared by all profiles
+    pub fn base_dir(&self) -> &std::path::Path {
+        &self.base_dir
+    }
+
+    /// SurrealKV file holding every profile's database
+    pub fn database_path(&self) -> PathBuf {
+        self.base_dir.join("chat.db")
+    }
+
+    /// SurrealDB database name for this profile (namespace is always `cyrup`)
+    pub fn database_name(&self) -> String {
+        if self.is_default() {
+            "chat".to_string()
+        } else {
+            format!("chat_{}", self.name)
+        }
+    }
+
+    /// Directory for settings JSON files (markers, UI config, token budget)
+    pub fn settings_dir(&self) -> PathBuf {
+        if self.is_default() {
+            self.settings_root.clone()
+        } else {
+            self.settings_root.join("profiles").join(&self.name)
+        }
+    }
+
+    /// Directory for this profile's database snapshots
+    pub fn backup_dir(&self) -> PathBuf {
+        self.base_dir.join("backups").join(&self.name)
+    }
+
+    /// Keyring service name for this profile's credentials
+    pub fn vault_service_name(&self) -> String {
+        if self.is_default() {
+            "cyrup-chat".to_string()
+        } else {
+            format!("cyrup-chat-{}", self.name)
+        }
+    }
+
+    /// Load this profile's token budget, if one was saved
+    pub async fn load_token_budget(&self) -> Option<TokenBudgetConfig> {
+        let path = self.settings_dir().join(TOKEN_BUDGET_FILE);
+        let data = tokio::fs::read(&path).await.ok()?;
+        match serde_json::from_slice(&data) {
+            Ok(config) => Some(config),
+            Err(e) => {
+                log::warn!("Could not parse {}: {e}", path.display());
+                None
+            }
+        }
+    }
+
+    /// Save this profile's token budget
+    pub async fn save_token_budget(&self, config: &TokenBudgetConfig) -> Result<(), String> {
+        let dir = self.settings_dir();
+        tokio::fs::create_dir_all(&dir)
+            .await
+            .map_err(|e| format!("Could not create {}: {e}", dir.display()))?;
+        let data = serde_json::to_string_pretty(config)
+            .map_err(|e| format!("Could not serialize token budget: {e}"))?;
+        tokio::fs::write(dir.join(TOKEN_BUDGET_FILE), data)
+            .await
+            .map_err(|e| format!("Could not write token budget: {e}"))
+    }
+
+    /// All profiles that have been used on this machine (always includes `default`)
+    pub fn known_profiles(&self) -> Vec<String> {
+        let mut known = self.read_profiles_file().known;
+        for name in [DEFAULT_PROFILE, self.name.as_str()] {
+            if !known.iter().any(|k| k == name) {
+                known.push(name.to_string());
+            }
+        }
+        known.sort();
+        known
+    }
+
+    /// Record this profile as the one to open on next launch
+    ///
+    /// # Errors
+    /// Returns error if `profiles.json` cannot be written
+    pub fn remember(&self) -> Result<(), String> {
+        let mut file = self.read_profiles_file();
+        file.active = Some(self.name.clone());
+        if !file.known.contains(&self.name) {
+            file.known.push(self.name.clone());
+        }
+
+        std::fs::create_dir_all(&self.base_dir)
+            .map_err(|e| format!("Could not create {}: {e}", self.base_dir.display()))?;
+        let data = serde_json::to_string_pretty(&file)
+            .map_err(|e| format!("Could not serialize profiles: {e}"))?;
+        std::fs::write(self.base_dir.join(PROFILES_FILE), data)
+            .map_err(|e| format!("Could not write profiles: {e}"))
+    }
+
+    /// Profile remembered by [`Profile::remember`], or `default`
+    fn saved_choice(&self) -> String {
+        self.read_profiles_file()
+            .active
+            .unwrap_or_else(|| DEFAULT_PROFILE.to_string())
+    }
+
+    fn read_profiles_file(&self) -> ProfilesFile {
+        std::fs::read(self.base_dir.join(PROFILES_FILE))
+            .ok()
+            .and_then(|data| serde_json::from_slice(&data).ok())
+            .unwrap_or_default()
+    }
+}
+
+/// Set the active profile for this process
+///
+/// Must be called before the database, settings or vault are first used.
+/// Later calls are ignored.
+pub fn init_active_profile(profile: Profile) {
+    log::info!("Using profile '{}'", profile.name());
+    if ACTIVE_PROFILE.set(profile).is_err() {
+        log::warn!("Active profile already initialized; ignoring");
+    }
+}
+
+/// The profile this process is running with
+///
+/// Falls back to resolving from the environment if [`init_active_profile`]
+/// was never called (tests, tools).
+///
+/// # Errors
+/// Returns error if no profile was set and not even the default profile has
+/// a data directory
+pub fn active_profile() -> Result<&'static Profile, String> {
+    if let Some(profile) = ACTIVE_PROFILE.get() {
+        return Ok(profile);
+    }
+    let profile = Profile::resolve(std::env::args())
+        .or_else(|_| Profile::new(DEFAULT_PROFILE))
+        .map_err(|e| format!("Cannot resolve profile: {e}"))?;
+    Ok(ACTIVE_PROFILE.get_or_init(|| profile))
+}
+
+/// Switch profiles by saving the choice and relaunching the application
+///
+/// Database, settings and vault are bound to the profile at startup, so a
+/// switch requires a fresh process. Every profile shares one SurrealKV store,
+/// so the background tasks, the API server and the database are shut down
+/// before the new process starts. On Unix the new process replaces this one
+/// (`exec`), which releases the store lock before it opens the store;
+/// elsewhere it waits for the lock (see [`Database::connect`]).
+///
+/// [`Database::connect`]: crate::database::Database::connect
+///
+/// # Errors
+/// Returns error if the name is invalid or the new process cannot be started
+pub async fn switch_profile(name: &str) -> Result<(), String> {
+    let profile = Profile::new(name)?;
+    profile.remember()?;
+
+    let exe = std::env::current_exe().map_err(|e| format!("Cannot locate executable: {e}"))?;
+    let mut command = std::process::Command::new(exe);
+    command
+        .args(relaunch_args(std::env::args().skip(1)))
+        .arg("--profile")
+        .arg(name);
+
+    crate::services::shutdown::shutdown().await;
+    log::info!("Relaunching with profile '{}'", name);
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::process::CommandExt;
+        // Only returns if the executable could not be started
+        let e = command.exec();
+        Err(format!("Failed to relaunch: {e}"))
+    }
+
+    #[cfg(not(unix))]
+    {
+        command
+            .spawn()
+            .map_err(|e| format!("Failed to relaunch: {e}"))?;
+        std::process::exit(0);
+    }
+}
+
+/// Command line arguments to relaunch with, minus any `--profile` option
+fn relaunch_args<I: IntoIterator<Item = String>>(args: I) -> Vec<String> {
+    let mut kept = Vec::new();
+    let mut skip_next = false;
+    for arg in args {
+        if skip_next {
+            skip_next = false;
+        } else if arg == "--profile" {
+            skip_next = true;
+        } else if !arg.starts_with("--profile=") {
+            kept.push(arg);
+        }
+    }
+    kept
+}
+
+/// Extract `--profile <name>` or `--profile=<name>` from command line arguments
+pub fn profile_from_args<I: IntoIterator<Item = String>>(args: I) -> Option<String> {
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        if arg == "--profile" {
+            return args.next();
+        }
+        if let Some(name) = arg.strip_prefix("--profile=") {
+            return Some(name.to_string());
+        }
+    }
+    None
+}
+
+/// Profile names become SurrealDB database names, directory names and
+/// keyring service suffixes, so keep them to a portable character set
+///
+/// # Errors
+/// Returns error describing why the name is invalid
+pub fn validate_name(name: &str) -> Result<(), String> {
+    if name.is_empty() || name.len() > 32 {
+        return Err("Profile name must be 1-32 characters".to_string());
+    }
+    if !name
+        .chars()
+        .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_' || c == '-')
+    {
+        return Err(
+            "Profile name may only contain lowercase letters, digits, '-' and '_'".to_string(),
+        );
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn profile(name: &str, base_dir: impl Into<PathBuf>) -> Profile {
+        Profile {
+            name: name.to_string(),
+            base_dir: base_dir.into(),
+            settings_root: PathBuf::from("/settings"),
+        }
+    }
+
+    fn args(args: &[&str]) -> Vec<String> {
+        args.iter().map(|a| a.to_string()).collect()
+    }
+
+    #[test]
+    fn test_relaunch_args_drop_profile_option() {
+        assert_eq!(
+            relaunch_args(args(&["--profile", "work", "--verbose", "--profile=ci"])),
+            args(&["--verbose"])
+        );
+        assert!(relaunch_args(Vec::new()).is_empty());
+    }
+
+    #[test]
+    fn test_profile_from_args() {
+        assert_eq!(
+            profile_from_args(args(&["cyrup", "--profile", "work"])),
+            Some("work".to_string())
+        );
+        assert_eq!(
+            profile_from_args(args(&["cyrup", "send", "--profile=ci", "x"])),
+            Some("ci".to_string())
+        );
+        assert_eq!(profile_from_args(args(&["cyrup", "send"])), None);
+        assert_eq!(profile_from_args(args(&["cyrup", "--profile"])), None);
+    }
+
+    #[test]
+    fn test_argument_wins_over_environment_and_saved_choice() {
+        let resolved = Profile::resolve(args(&["cyrup", "--profile", "fixtures"])).unwrap();
+        assert_eq!(resolved.name(), "fixtures");
+
+        assert!(Profile::resolve(args(&["cyrup", "--profile", "Not Valid"])).is_err());
+    }
+
+    #[test]
+    fn test_saved_choice_is_remembered() {
+        let dir = tempfile::tempdir().unwrap();
+        assert_eq!(profile("default", dir.path()).saved_choice(), DEFAULT_PROFILE);
+
+        profile("work", dir.path()).remember().unwrap();
+        let default = profile("default", dir.path());
+        assert_eq!(default.saved_choice(), "work");
+        assert_eq!(default.known_profiles(), ["default", "work"]);
+    }
+
+    #[test]
+    fn test_database_name() {
+        assert_eq!(profile("default", "/data").database_name(), "chat");
+        assert_eq!(profile("work", "/data").database_name(), "chat_work");
+        // Every profile shares one SurrealKV directory
+        assert_eq!(
+            profile("work", "/data").database_path(),
+            profile("default", "/data").database_path()
+        );
+    }
+
+    #[test]
+    fn test_per_profile_directories() {
+        let default = profile("default", "/data");
+        let work = profile("work", "/data");
+
+        assert_eq!(default.settings_dir(), PathBuf::from("/settings"));
+        assert_eq!(work.settings_dir(), PathBuf::from("/settings/profiles/work"));
+        assert_eq!(default.backup_dir(), PathBuf::from("/data/backups/default"));
+        assert_eq!(work.backup_dir(), PathBuf::from("/data/backups/work"));
+        assert_eq!(default.vault_service_name(), "cyrup-chat");
+        assert_eq!(work.vault_service_name(), "cyrup-chat-work");
+    }
+
+    #[test]
+    fn test_validate_name() {
+        assert!(validate_name("work_2-b").is_ok());
+        assert!(validate_name("").is_err());
+        assert!(validate_name("Work").is_err());
+        assert!(validate_name("../etc").is_err());
+        assert!(validate_name(&"a".repeat(33)).is_err());
+    }
+}
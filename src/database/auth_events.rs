@@ -0,0 +1,81 @@
+//! Account audit trail database operations
+//!
+//! Appends to and reads the auth_event table defined in
+//! src/database/schema.rs. Events are never updated once written.
+
+use super::Database;
+use crate::view_model::auth_event::{AuthEvent, AuthEventKind};
+use serde::Serialize;
+use surrealdb_types::{RecordId, SurrealValue};
+
+impl Database {
+    /// Record a sign-in or sign-out
+    ///
+    /// # Arguments
+    /// * `tokens_revoked` - Whether the provider confirmed the tokens are revoked
+    /// * `detail` - Why revocation failed, if it did
+    ///
+    /// # Returns
+    /// * `Ok(RecordId)` - Database-generated event ID
+    /// * `Err(String)` - Insert failure
+    pub async fn record_auth_event(
+        &self,
+        user_id: &str,
+        provider: &str,
+        kind: AuthEventKind,
+        tokens_revoked: bool,
+        detail: Option<String>,
+    ) -> Result<RecordId, String> {
+        #[derive(Serialize, SurrealValue)]
+        struct AuthEventInsert {
+            user_id: String,
+            provider: String,
+            kind: AuthEventKind,
+            tokens_revoked: bool,
+            detail: Option<String>,
+        }
+
+        let created: Option<AuthEvent> = self
+            .client()
+            .create("auth_event")
+            .content(AuthEventInsert {
+                user_id: user_id.to_string(),
+                provider: provider.to_string(),
+                kind,
+                tokens_revoked,
+                detail,
+            })
+            .await
+            .map_err(|e| format!("Failed to record auth event: {}", e))?;
+
+        created
+            .map(|event| event.id)
+            .ok_or_else(|| "Create returned empty result".to_string())
+    }
+
+    /// An account's most recent events, newest first
+    pub async fn list_auth_events(
+        &self,
+        user_id: &str,
+        limit: usize,
+    ) -> Result<Vec<AuthEvent>, String> {
+        let query = r"
+            SELECT * FROM auth_event
+            WHERE user_id = $user
+            ORDER BY created_at DESC
+            LIMIT $limit
+        ";
+
+        let mut response = self
+            .client()
+            .query(query)
+            .bind(("user", user_id.to_string()))
+            .bind(("limit", limit as i64))
+            .await
+            .map_err(|e| format!("Failed to list auth events: {}", e))?;
+
+        response
+            .take(0)
+            .map_err(|e| format!("Failed to parse auth events: {}", e))
+    }
+}
@@ -0,0 +1,290 @@
+//! Snapshot backup and restore for the chat database
+//!
+//! Backups are SurrealQL exports compressed with Snappy framing and written
+//! as `chat-<timestamp>.surql.sz` into a backup directory. Only the newest
+//! `keep` snapshots are retained.
+//!
+//! Restore replaces every application table with the snapshot contents after
+//! first taking a safety snapshot of the current state. Clearing the tables
+//! and importing the snapshot run as one transaction, so a snapshot that
+//! fails to import leaves the database untouched.
+
+use super::Database;
+use super::schema::TABLES;
+use futures_util::StreamExt;
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tokio::time::Duration;
+
+const BACKUP_PREFIX: &str = "chat-";
+const BACKUP_SUFFIX: &str = ".surql.sz";
+
+/// Snapshot schedule and retention settings
+#[derive(Debug, Clone)]
+pub struct BackupConfig {
+    /// Directory holding snapshot files
+    pub directory: PathBuf,
+    /// Number of snapshots to retain (oldest are deleted first)
+    pub keep: usize,
+    /// Minimum age of the newest snapshot before a new one is taken
+    pub interval: Duration,
+}
+
+impl BackupConfig {
+    /// Daily snapshots, one week of retention
+    pub fn new(directory: PathBuf) -> Self {
+        Self {
+            directory,
+            keep: 7,
+            interval: Duration::from_secs(24 * 60 * 60),
+        }
+    }
+}
+
+impl Database {
+    /// Export the database to a compressed snapshot file
+    ///
+    /// # Arguments
+    /// * `config` - Target directory and retention policy
+    ///
+    /// # Returns
+    /// * `Ok(PathBuf)` - Path of the new snapshot
+    /// * `Err(String)` - Export, compression or write failed
+    ///
+    /// # Design Note
+    /// The snapshot is written to a temporary file and renamed into place,
+    /// so an interrupted backup never leaves a truncated `.surql.sz` behind.
+    pub async fn create_backup(&self, config: &BackupConfig) -> Result<PathBuf, String> {
+        let path = self.write_backup(config).await?;
+        rotate_backups(config, None).await?;
+        Ok(path)
+    }
+
+    /// Export the database to a new snapshot without rotating old ones
+    async fn write_backup(&self, config: &BackupConfig) -> Result<PathBuf, String> {
+        tokio::fs::create_dir_all(&config.directory)
+            .await
+            .map_err(|e| format!("Failed to create backup directory: {}", e))?;
+
+        let mut export = self
+            .client()
+            .export(())
+            .await
+            .map_err(|e| format!("Failed to start export: {}", e))?;
+
+        let mut sql = Vec::new();
+        while let Some(chunk) = export.next().await {
+            let chunk = chunk.map_err(|e| format!("Export failed: {}", e))?;
+            sql.extend_from_slice(&chunk);
+        }
+
+        let file_name = format!(
+            "{}{}{}",
+            BACKUP_PREFIX,
+            chrono::Utc::now().format("%Y%m%d%H%M%S"),
+            BACKUP_SUFFIX
+        );
+        let path = config.directory.join(file_name);
+
+        let destination = path.clone();
+        tokio::task::spawn_blocking(move || write_compressed(&destination, &sql))
+            .await
+            .map_err(|e| format!("Backup task failed: {}", e))?
+            .map_err(|e| format!("Failed to write backup: {}", e))?;
+
+        log::info!("[Backup] Created snapshot {}", path.display());
+
+        Ok(path)
+    }
+
+    /// Replace the database contents with a snapshot
+    ///
+    /// # Arguments
+    /// * `snapshot` - Path to a `.surql.sz` file created by [`Database::create_backup`]
+    /// * `config` - Where to write the safety snapshot of the current state
+    ///
+    /// # Returns
+    /// * `Ok(PathBuf)` - Path of the safety snapshot taken before restoring
+    /// * `Err(String)` - Snapshot unreadable or import failed (nothing was changed)
+    pub async fn restore_backup(
+        &self,
+        snapshot: &Path,
+        config: &BackupConfig,
+    ) -> Result<PathBuf, String> {
+        let source = snapshot.to_path_buf();
+        let sql = tokio::task::spawn_blocking(move || read_compressed(&source))
+            .await
+            .map_err(|e| format!("Restore task failed: {}", e))?
+            .map_err(|e| format!("Failed to read backup {}: {}", snapshot.display(), e))?;
+        let sql = String::from_utf8(sql)
+            .map_err(|_| format!("Backup {} is not a SurrealQL export", snapshot.display()))?;
+
+        // Never destroy data without a way back. Rotation waits until the
+        // import is done and spares the snapshot being restored.
+        let safety = self.write_backup(config).await?;
+
+        self.client()
+            .query(restore_script(&sql))
+            .await
+            .map_err(|e| format!("Failed to import backup: {}", e))?
+            .check()
+            .map_err(|e| format!("Failed to import backup: {}", e))?;
+
+        rotate_backups(config, Some(snapshot)).await?;
+
+        log::info!(
+            "[Backup] Restored {} (previous state saved to {})",
+            snapshot.display(),
+            safety.display()
+        );
+
+        Ok(safety)
+    }
+}
+
+/// List snapshot files in a backup directory, oldest first
+pub async fn list_backups(directory: &Path) -> Result<Vec<PathBuf>, String> {
+    let mut entries = match tokio::fs::read_dir(directory).await {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(format!("Failed to read backup directory: {}", e)),
+    };
+
+    let mut backups = Vec::new();
+    while let Some(entry) = entries
+        .next_entry()
+        .await
+        .map_err(|e| format!("Failed to read backup directory: {}", e))?
+    {
+        let name = entry.file_name().to_string_lossy().to_string();
+        if name.starts_with(BACKUP_PREFIX) && name.ends_with(BACKUP_SUFFIX) {
+            backups.push(entry.path());
+        }
+    }
+
+    // Timestamped names sort chronologically
+    backups.sort();
+    Ok(backups)
+}
+
+/// Delete the oldest snapshots beyond the retention limit, except `spare`
+async fn rotate_backups(config: &BackupConfig, spare: Option<&Path>) -> Result<(), String> {
+    let backups = list_backups(&config.directory).await?;
+    let excess = backups.len().saturating_sub(config.keep);
+    let spare = match spare {
+        Some(path) => tokio::fs::canonicalize(path).await.ok(),
+        None => None,
+    };
+
+    let mut candidates = Vec::new();
+    for backup in backups {
+        if spare.is_none() || tokio::fs::canonicalize(&backup).await.ok() != spare {
+            candidates.push(backup);
+        }
+    }
+
+    for old in candidates.iter().take(excess) {
+        match tokio::fs::remove_file(old).await {
+            Ok(_) => log::debug!("[Backup] Rotated out {}", old.display()),
+            Err(e) => log::warn!("[Backup] Failed to remove {}: {}", old.display(), e),
+        }
+    }
+
+    Ok(())
+}
+
+/// Spawn the background snapshot task
+///
+/// Checks hourly and takes a snapshot whenever the newest one is older than
+/// `config.interval`, so missed snapshots are caught up after sleep or restart.
+pub fn spawn_backup_schedule(
+    database: Arc<Database>,
+    config: BackupConfig,
+) -> tokio::task::JoinHandle<()> {
+    tokio::spawn(async move {
+        let check_every = config.interval.min(Duration::from_secs(60 * 60));
+
+        loop {
+            match newest_backup_age(&config.directory).await {
+                Some(age) if age < config.interval => {}
+                _ => {
+                    if let Err(e) = database.create_backup(&config).await {
+                        log::error!("[Backup] Scheduled snapshot failed: {}", e);
+                    }
+                }
+            }
+
+            tokio::time::sleep(check_every).await;
+        }
+    })
+}
+
+/// Age of the most recent snapshot, if any
+async fn newest_backup_age(directory: &Path) -> Option<Duration> {
+    let newest = list_backups(directory).await.ok()?.pop()?;
+    let modified = tokio::fs::metadata(&newest).await.ok()?.modified().ok()?;
+    modified.elapsed().ok()
+}
+
+/// Clear every application table and replay an export, as one transaction
+///
+/// Transaction statements inside the export are dropped: SurrealDB does not
+/// nest transactions, and the surrounding one already covers the import.
+fn restore_script(export: &str) -> String {
+    let mut script = String::from("BEGIN TRANSACTION;\n");
+    for table in TABLES {
+        script.push_str(&format!("REMOVE TABLE IF EXISTS {};\n", table));
+    }
+    for line in export.lines() {
+        let statement = line.trim();
+        if statement.eq_ignore_ascii_case("BEGIN TRANSACTION;")
+            || statement.eq_ignore_ascii_case("COMMIT TRANSACTION;")
+        {
+            continue;
+        }
+        script.push_str(line);
+        script.push('\n');
+    }
+    script.push_str("COMMIT TRANSACTION;\n");
+    script
+}
+
+fn write_compressed(path: &Path, data: &[u8]) -> std::io::Result<()> {
+    let tmp = path.with_extension("partial");
+    {
+        let file = std::fs::File::create(&tmp)?;
+        let mut encoder = snap::write::FrameEncoder::new(file);
+        encoder.write_all(data)?;
+        encoder
+            .into_inner()
+            .map_err(|e| std::io::Error::other(e.to_string()))?
+            .sync_all()?;
+    }
+    std::fs::rename(&tmp, path)
+}
+
+fn read_compressed(path: &Path) -> std::io::Result<Vec<u8>> {
+    let file = std::fs::File::open(path)?;
+    let mut decoder = snap::read::FrameDecoder::new(file);
+    let mut data = Vec::new();
+    decoder.read_to_end(&mut data)?;
+    Ok(data)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_restore_script_wraps_export_in_one_transaction() {
+        let export = "OPTION IMPORT;\nBEGIN TRANSACTION;\nINSERT [{ id: message:a }];\nCOMMIT TRANSACTION;\n";
+        let script = restore_script(export);
+
+        assert!(script.starts_with("BEGIN TRANSACTION;\nREMOVE TABLE IF EXISTS mcp_server;"));
+        assert!(script.ends_with("INSERT [{ id: message:a }];\nCOMMIT TRANSACTION;\n"));
+        assert_eq!(script.matches("BEGIN TRANSACTION;").count(), 1);
+        assert_eq!(script.matches("COMMIT TRANSACTION;").count(), 1);
+        assert_eq!(script.matches("REMOVE TABLE").count(), TABLES.len());
+    }
+}
@@ -8,6 +8,10 @@ use crate::view_model::message::Message;
 use serde::{Deserialize, Serialize};
 use surrealdb_types::SurrealValue;
 
+/// User id bookmarks and reactions were stored under before sign-in accounts
+/// scoped them (Q39 MVP)
+pub const LEGACY_USER_ID: &str = "hardcoded-david-maple";
+
 impl Database {
     /// Bookmark a message for a user
     ///
@@ -165,4 +169,45 @@ impl Database {
     pub async fn remove_bookmark(&self, user_id: &str, message_id: &str) -> Result<(), String> {
         self.unbookmark_message(user_id, message_id).await
     }
+
+    /// Hand bookmarks and reactions saved under [`LEGACY_USER_ID`] to an account
+    ///
+    /// Run when an account signs in so data from before accounts existed is not
+    /// orphaned. The first account to sign in adopts it; later runs are no-ops.
+    /// Rows still keyed by the account's provider-local `local_id` move to
+    /// `user_id` too, but only when the earliest recorded sign-in for that id
+    /// was this account's: two providers can share a local id, and migration
+    /// 10 gives such rows to the earliest sign-in the same way.
+    pub async fn adopt_legacy_user_data(
+        &self,
+        user_id: &str,
+        local_id: &str,
+    ) -> Result<(), String> {
+        let query = r#"
+            UPDATE bookmark SET user_id = $user WHERE user_id = $legacy;
+            UPDATE reaction SET user_id = $user WHERE user_id = $legacy;
+
+            LET $owner = (
+                SELECT VALUE string::concat(provider, ":", user_id) FROM auth_event
+                WHERE user_id = $local ORDER BY created_at ASC LIMIT 1
+            )[0];
+            IF $owner = $user {
+                UPDATE bookmark SET user_id = $user WHERE user_id = $local;
+                UPDATE reaction SET user_id = $user WHERE user_id = $local;
+                UPDATE conversation SET user_id = $user WHERE user_id = $local;
+            };
+        "#;
+
+        self.client()
+            .query(query)
+            .bind(("user", user_id.to_string()))
+            .bind(("legacy", LEGACY_USER_ID))
+            .bind(("local", local_id.to_string()))
+            .await
+            .map_err(|e| format!("Failed to adopt legacy user data: {}", e))?
+            .check()
+            .map_err(|e| format!("Failed to adopt legacy user data: {}", e))?;
+
+        Ok(())
+    }
 }
@@ -45,6 +45,7 @@ impl Database {
             summary: String,
             agent_sessions: HashMap<String, String>,
             last_message_at: Datetime,
+            user_id: Option<String>,
         }
 
         let insert_data = ConversationInsert {
@@ -53,6 +54,7 @@ impl Database {
             summary: conversation.summary.clone(),
             agent_sessions: conversation.agent_sessions.clone(),
             last_message_at: conversation.last_message_at,
+            user_id: conversation.user_id.clone(),
         };
 
         // .create() returns Option<T>, not Vec<Thing>
@@ -92,6 +94,7 @@ impl Database {
             last_summarized_message_id: Option<RecordId>,
             last_message_at: Datetime,
             created_at: Datetime,
+            user_id: Option<String>,
         }
 
         let record: Option<ConversationRecord> = self
@@ -112,12 +115,14 @@ impl Database {
             last_summarized_message_id: record.last_summarized_message_id,
             last_message_at: record.last_message_at,
             created_at: record.created_at,
+            user_id: record.user_id,
         })
     }
 
     /// List recent conversations with summaries for sidebar display
     ///
     /// # Arguments
+    /// * `user_id` - Active account; its conversations and unowned ones are listed
     /// * `limit` - Maximum number of conversations to return
     ///
     /// # Returns
@@ -134,12 +139,14 @@ impl Database {
     /// This is acceptable for small limits and avoids SurrealDB 3.0 subquery limitations.
     pub async fn list_recent_conversations(
         &self,
+        user_id: &str,
         limit: usize,
     ) -> Result<Vec<ConversationSummary>, String> {
         // Step 1: Get recent conversations (basic fields only)
         let query = r"
             SELECT id, title, participants, last_message_at
             FROM conversation
+            WHERE user_id = $user OR user_id = NONE
             ORDER BY last_message_at DESC
             LIMIT $limit
         ";
@@ -147,6 +154,7 @@ impl Database {
         let mut response = self
             .client()
             .query(query)
+            .bind(("user", user_id.to_string()))
             .bind(("limit", limit as i64))
             .await
             .map_err(|e| format!("Failed to list conversations: {}", e))?;
@@ -236,19 +244,51 @@ impl Database {
         Ok(summaries)
     }
 
-    /// List all conversations with summaries for sidebar display
+    /// List all conversations with summaries, regardless of owner
+    ///
+    /// Used by headless surfaces (CLI, API, MCP) that act for no account.
+    ///
+    /// # Returns
+    /// * `Ok(Vec<ConversationSummary>)` - Conversation list ordered by last_message_at DESC
+    /// * `Err(String)` - Error if query fails
+    pub async fn list_conversations(&self) -> Result<Vec<ConversationSummary>, String> {
+        self.query_conversations(None).await
+    }
+
+    /// List the conversations an account can see: its own plus unowned ones
+    ///
+    /// # Arguments
+    /// * `user_id` - Active account's key (`google:1234`)
     ///
     /// # Returns
     /// * `Ok(Vec<ConversationSummary>)` - Conversation list ordered by last_message_at DESC
     /// * `Err(String)` - Error if query fails
+    pub async fn list_conversations_for_user(
+        &self,
+        user_id: &str,
+    ) -> Result<Vec<ConversationSummary>, String> {
+        self.query_conversations(Some(user_id)).await
+    }
+
+    /// Conversation summaries, optionally scoped to one account
     ///
     /// # Database Operation
     /// Uses FOR loop pattern for correlated subqueries since SurrealDB 3.0
     /// doesn't support $parent variable. Iterates over conversations and computes
     /// related message data using LET statements with $conv.id reference.
-    pub async fn list_conversations(&self) -> Result<Vec<ConversationSummary>, String> {
+    async fn query_conversations(
+        &self,
+        user_id: Option<&str>,
+    ) -> Result<Vec<ConversationSummary>, String> {
+        let owner_filter = if user_id.is_some() {
+            "WHERE user_id = $user OR user_id = NONE"
+        } else {
+            ""
+        };
+
         // Query using SELECT with inline subqueries
-        let query = r"
+        let query = format!(
+            r"
             SELECT
                 id,
                 title,
@@ -270,13 +310,17 @@ impl Database {
                       AND deleted = false
                 )[0].total OR 0 AS unread_count
             FROM conversation
+            {owner_filter}
             ORDER BY last_message_at DESC
-        ";
+        "
+        );
 
         // Execute query and extract result set
-        let mut response = self
-            .client()
-            .query(query)
+        let mut request = self.client().query(query);
+        if let Some(user_id) = user_id {
+            request = request.bind(("user", user_id.to_string()));
+        }
+        let mut response = request
             .await
             .map_err(|e| format!("Failed to list conversations: {}", e))?;
 
@@ -400,6 +444,62 @@ impl Database {
         Ok(())
     }
 
+    /// Forget every agent session in a conversation
+    ///
+    /// The next message spawns fresh agent sessions, so agents no longer see
+    /// earlier turns. Messages themselves are kept.
+    ///
+    /// # Database Operation
+    /// UPDATE $conversation_id SET agent_sessions = {}
+    pub async fn clear_agent_sessions(&self, conversation_id: &RecordId) -> Result<(), String> {
+        self.client()
+            .query("UPDATE $conversation_id SET agent_sessions = {}")
+            .bind(("conversation_id", conversation_id.clone()))
+            .await
+            .map_err(|e| format!("Failed to clear agent sessions: {}", e))?
+            .check()
+            .map_err(|e| format!("Failed to clear agent sessions: {}", e))?;
+
+        Ok(())
+    }
+
+    /// Forget the agent sessions of every conversation an account owns
+    ///
+    /// Used at sign-out. Conversations shared by every account (no owner)
+    /// keep their sessions.
+    ///
+    /// # Returns
+    /// * `Ok(Vec<RecordId>)` - Conversations whose sessions were cleared
+    /// * `Err(String)` - Query failure
+    pub async fn clear_agent_sessions_for_user(
+        &self,
+        user_id: &str,
+    ) -> Result<Vec<RecordId>, String> {
+        let query = r"
+            UPDATE conversation SET agent_sessions = {}
+            WHERE user_id = $user AND agent_sessions != {}
+            RETURN id
+        ";
+
+        let mut response = self
+            .client()
+            .query(query)
+            .bind(("user", user_id.to_string()))
+            .await
+            .map_err(|e| format!("Failed to clear agent sessions: {}", e))?;
+
+        #[derive(Deserialize, SurrealValue)]
+        struct Cleared {
+            id: RecordId,
+        }
+
+        let cleared: Vec<Cleared> = response
+            .take(0)
+            .map_err(|e| format!("Failed to clear agent sessions: {}", e))?;
+
+        Ok(cleared.into_iter().map(|c| c.id).collect())
+    }
+
     /// Add a new participant to an existing conversation
     ///
     /// Uses array::union() to prevent duplicates automatically.
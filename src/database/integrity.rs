@@ -0,0 +1,272 @@
+//! Referential integrity checks and repairs
+//!
+//! SurrealDB enforces `REFERENCE ON DELETE CASCADE` for message → conversation
+//! links, but several relations are only maintained by application code:
+//! - message.in_reply_to → message
+//! - conversation.agent_sessions keys → conversation.participants
+//! - conversation.participants → agent_template
+//! - at most 5 pinned messages per conversation (Q37)
+//!
+//! [`Database::check_integrity`] finds violations without modifying anything;
+//! [`Database::repair_integrity`] fixes the violations from a report, and
+//! [`Database::backup_and_repair`] snapshots the database before doing so.
+
+use super::{BackupConfig, Database};
+use serde::Deserialize;
+use std::collections::{HashMap, HashSet};
+use surrealdb_types::{RecordId, SurrealValue, ToSql};
+
+/// Maximum pinned messages per conversation (mirrors toggle_pin_message)
+const MAX_PINS: usize = 5;
+
+/// Integrity violations found in the database
+#[derive(Debug, Clone, Default)]
+pub struct IntegrityReport {
+    /// Messages whose `in_reply_to` points at a message that no longer exists
+    pub dangling_replies: Vec<RecordId>,
+    /// `(conversation, agent_sessions key)` pairs with no matching participant
+    pub orphaned_sessions: Vec<(RecordId, String)>,
+    /// `(conversation, template)` pairs where the template was deleted
+    pub missing_participants: Vec<(RecordId, RecordId)>,
+    /// Oldest pins beyond the per-conversation limit
+    pub excess_pins: Vec<RecordId>,
+}
+
+impl IntegrityReport {
+    /// True if no violations were found
+    pub fn is_clean(&self) -> bool {
+        self.dangling_replies.is_empty()
+            && self.orphaned_sessions.is_empty()
+            && self.missing_participants.is_empty()
+            && self.excess_pins.is_empty()
+    }
+
+    /// Total number of violations
+    pub fn issue_count(&self) -> usize {
+        self.dangling_replies.len()
+            + self.orphaned_sessions.len()
+            + self.missing_participants.len()
+            + self.excess_pins.len()
+    }
+}
+
+#[derive(Deserialize, SurrealValue)]
+struct ConversationLinks {
+    id: RecordId,
+    participants: Vec<RecordId>,
+    agent_sessions: HashMap<String, String>,
+}
+
+impl Database {
+    /// Scan the database for integrity violations
+    ///
+    /// # Returns
+    /// * `Ok(IntegrityReport)` - Violations found (possibly none)
+    /// * `Err(String)` - A scan query failed
+    pub async fn check_integrity(&self) -> Result<IntegrityReport, String> {
+        let mut report = IntegrityReport::default();
+
+        // Replies to messages that were hard-deleted (soft deletes keep the record)
+        let mut response = self
+            .client()
+            .query(
+                "SELECT VALUE id FROM message
+                 WHERE in_reply_to != NONE AND !record::exists(in_reply_to)",
+            )
+            .await
+            .map_err(|e| format!("Failed to scan replies: {}", e))?;
+        report.dangling_replies = response
+            .take(0)
+            .map_err(|e| format!("Failed to parse reply scan: {}", e))?;
+
+        // Participants and sessions are checked against the live template set
+        let mut response = self
+            .client()
+            .query("SELECT VALUE id FROM agent_template")
+            .await
+            .map_err(|e| format!("Failed to list templates: {}", e))?;
+        let template_ids: Vec<RecordId> = response
+            .take(0)
+            .map_err(|e| format!("Failed to parse templates: {}", e))?;
+        let templates: HashSet<RecordId> = template_ids.into_iter().collect();
+
+        for conversation in self.conversation_links().await? {
+            let participant_keys: HashSet<String> =
+                conversation.participants.iter().map(|p| p.to_sql()).collect();
+
+            for key in conversation.agent_sessions.keys() {
+                if !participant_keys.contains(key) {
+                    report
+                        .orphaned_sessions
+                        .push((conversation.id.clone(), key.clone()));
+                }
+            }
+
+            for participant in &conversation.participants {
+                if !templates.contains(participant) {
+                    report
+                        .missing_participants
+                        .push((conversation.id.clone(), participant.clone()));
+                }
+            }
+        }
+
+        // Pin limit: keep the newest MAX_PINS per conversation
+        #[derive(Deserialize, SurrealValue)]
+        struct PinnedMessage {
+            id: RecordId,
+            conversation_id: RecordId,
+        }
+
+        let mut response = self
+            .client()
+            .query(
+                "SELECT id, conversation_id, timestamp FROM message
+                 WHERE pinned = true
+                 ORDER BY timestamp DESC",
+            )
+            .await
+            .map_err(|e| format!("Failed to scan pins: {}", e))?;
+        let pinned: Vec<PinnedMessage> = response
+            .take(0)
+            .map_err(|e| format!("Failed to parse pin scan: {}", e))?;
+
+        let mut pins_per_conversation: HashMap<RecordId, usize> = HashMap::new();
+        for pin in pinned {
+            let count = pins_per_conversation.entry(pin.conversation_id).or_insert(0);
+            *count += 1;
+            if *count > MAX_PINS {
+                report.excess_pins.push(pin.id);
+            }
+        }
+
+        if report.is_clean() {
+            log::debug!("[Integrity] No issues found");
+        } else {
+            log::warn!("[Integrity] Found {} issue(s): {:?}", report.issue_count(), report);
+        }
+
+        Ok(report)
+    }
+
+    /// Repair the violations listed in a report
+    ///
+    /// # Arguments
+    /// * `report` - Report from [`Database::check_integrity`]
+    ///
+    /// # Returns
+    /// * `Ok(usize)` - Number of violations repaired
+    /// * `Err(String)` - A repair query failed
+    ///
+    /// # Repairs
+    /// - Dangling replies: `in_reply_to` cleared (message becomes top-level)
+    /// - Orphaned sessions: key removed from `agent_sessions`
+    /// - Missing participants: template removed from `participants`, unless it is
+    ///   the last one (the schema requires at least one participant)
+    /// - Excess pins: oldest pins unpinned
+    pub async fn repair_integrity(&self, report: &IntegrityReport) -> Result<usize, String> {
+        let mut repaired = 0;
+
+        if !report.dangling_replies.is_empty() {
+            self.client()
+                .query("UPDATE message SET in_reply_to = NONE WHERE id IN $ids")
+                .bind(("ids", report.dangling_replies.clone()))
+                .await
+                .and_then(|response| response.check())
+                .map_err(|e| format!("Failed to repair replies: {}", e))?;
+            repaired += report.dangling_replies.len();
+        }
+
+        for (conversation_id, key) in &report.orphaned_sessions {
+            self.client()
+                .query("UPDATE $conversation_id SET agent_sessions[$key] = NONE")
+                .bind(("conversation_id", conversation_id.clone()))
+                .bind(("key", key.clone()))
+                .await
+                .and_then(|response| response.check())
+                .map_err(|e| format!("Failed to remove orphaned session: {}", e))?;
+            repaired += 1;
+        }
+
+        let mut missing_by_conversation: HashMap<&RecordId, Vec<RecordId>> = HashMap::new();
+        for (conversation_id, template_id) in &report.missing_participants {
+            missing_by_conversation
+                .entry(conversation_id)
+                .or_default()
+                .push(template_id.clone());
+        }
+
+        for (conversation_id, missing) in missing_by_conversation {
+            let conversation = self.get_conversation(conversation_id).await?;
+            let remaining: Vec<RecordId> = conversation
+                .participants
+                .into_iter()
+                .filter(|p| !missing.contains(p))
+                .collect();
+
+            if remaining.is_empty() {
+                log::warn!(
+                    "[Integrity] Conversation {} has no surviving participants; leaving as-is",
+                    conversation_id.to_sql()
+                );
+                continue;
+            }
+
+            self.client()
+                .query("UPDATE $conversation_id SET participants = $participants")
+                .bind(("conversation_id", conversation_id.clone()))
+                .bind(("participants", remaining))
+                .await
+                .and_then(|response| response.check())
+                .map_err(|e| format!("Failed to repair participants: {}", e))?;
+            repaired += missing.len();
+        }
+
+        if !report.excess_pins.is_empty() {
+            self.client()
+                .query("UPDATE message SET pinned = false WHERE id IN $ids")
+                .bind(("ids", report.excess_pins.clone()))
+                .await
+                .and_then(|response| response.check())
+                .map_err(|e| format!("Failed to repair pins: {}", e))?;
+            repaired += report.excess_pins.len();
+        }
+
+        log::info!("[Integrity] Repaired {} issue(s)", repaired);
+        Ok(repaired)
+    }
+
+    /// Take a snapshot, then repair the violations from a report
+    ///
+    /// Repairs unpin messages and drop participants, so nothing is changed
+    /// unless the snapshot was written.
+    ///
+    /// # Returns
+    /// * `Ok(usize)` - Number of violations repaired
+    /// * `Err(String)` - The snapshot or a repair query failed
+    pub async fn backup_and_repair(
+        &self,
+        report: &IntegrityReport,
+        config: &BackupConfig,
+    ) -> Result<usize, String> {
+        let snapshot = self
+            .create_backup(config)
+            .await
+            .map_err(|e| format!("Repair skipped, no snapshot could be taken: {}", e))?;
+        log::info!("[Integrity] Saved {} before repairing", snapshot.display());
+        self.repair_integrity(report).await
+    }
+
+    /// Load participant and session links for every conversation
+    async fn conversation_links(&self) -> Result<Vec<ConversationLinks>, String> {
+        let mut response = self
+            .client()
+            .query("SELECT id, participants, agent_sessions FROM conversation")
+            .await
+            .map_err(|e| format!("Failed to scan conversations: {}", e))?;
+
+        response
+            .take(0)
+            .map_err(|e| format!("Failed to parse conversation scan: {}", e))
+    }
+}
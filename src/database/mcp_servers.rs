@@ -0,0 +1,191 @@
+//! External MCP server database operations
+//!
+//! Provides CRUD operations for the mcp_server table and the
+//! template_mcp_server link table defined in src/database/schema.rs.
+//! Deleting a server or a template removes its links by cascade.
+
+use super::Database;
+use crate::view_model::mcp_server::{McpServer, McpTransport};
+use serde::Serialize;
+use std::collections::HashMap;
+use surrealdb_types::{RecordId, SurrealValue, ToSql};
+
+impl Database {
+    /// Register an MCP server
+    ///
+    /// # Returns
+    /// * `Ok(RecordId)` - Database-generated server ID
+    /// * `Err(String)` - Invalid fields, duplicate name, or insert failure
+    pub async fn create_mcp_server(&self, server: &McpServer) -> Result<RecordId, String> {
+        server.validate()?;
+
+        #[derive(Serialize, SurrealValue)]
+        struct McpServerInsert {
+            name: String,
+            transport: McpTransport,
+            command: String,
+            args: Vec<String>,
+            env: HashMap<String, String>,
+            url: String,
+            secret_keys: Vec<String>,
+        }
+
+        let result: Result<Option<McpServer>, _> = self
+            .client()
+            .create("mcp_server")
+            .content(McpServerInsert {
+                name: server.name.clone(),
+                transport: server.transport,
+                command: server.command.trim().to_string(),
+                args: server.args.clone(),
+                env: server.env.clone(),
+                url: server.url.trim().to_string(),
+                secret_keys: server.secret_keys.clone(),
+            })
+            .await;
+
+        match result {
+            Ok(created) => created
+                .map(|s| s.id)
+                .ok_or_else(|| "Create returned empty result".to_string()),
+            Err(e) if e.to_string().contains("already contains") => Err(format!(
+                "An MCP server named '{}' already exists",
+                server.name
+            )),
+            Err(e) => Err(format!("Failed to create MCP server: {}", e)),
+        }
+    }
+
+    /// Retrieve a single MCP server by ID
+    pub async fn get_mcp_server(&self, id: &RecordId) -> Result<McpServer, String> {
+        let server: Option<McpServer> = self
+            .client()
+            .select(id)
+            .await
+            .map_err(|e| format!("Failed to get MCP server: {}", e))?;
+
+        server.ok_or_else(|| format!("MCP server not found: {}", id.to_sql()))
+    }
+
+    /// List all MCP servers ordered by name
+    pub async fn list_mcp_servers(&self) -> Result<Vec<McpServer>, String> {
+        let mut response = self
+            .client()
+            .query("SELECT * FROM mcp_server ORDER BY name ASC")
+            .await
+            .map_err(|e| format!("Failed to list MCP servers: {}", e))?;
+
+        response
+            .take(0)
+            .map_err(|e| format!("Failed to parse MCP servers: {}", e))
+    }
+
+    /// Replace a server's name, transport and launch settings
+    pub async fn update_mcp_server(&self, server: &McpServer) -> Result<(), String> {
+        server.validate()?;
+
+        let query = r"
+            UPDATE $id SET
+                name = $name,
+                transport = $transport,
+                command = $command,
+                args = $args,
+                env = $env,
+                url = $url,
+                secret_keys = $secret_keys
+        ";
+
+        let result = self
+            .client()
+            .query(query)
+            .bind(("id", server.id.clone()))
+            .bind(("name", server.name.clone()))
+            .bind(("transport", server.transport))
+            .bind(("command", server.command.trim().to_string()))
+            .bind(("args", server.args.clone()))
+            .bind(("env", server.env.clone()))
+            .bind(("url", server.url.trim().to_string()))
+            .bind(("secret_keys", server.secret_keys.clone()))
+            .await
+            .map_err(|e| format!("Failed to update MCP server: {}", e))?
+            .take::<Vec<McpServer>>(0);
+
+        match result {
+            Ok(updated) if updated.is_empty() => {
+                Err(format!("MCP server not found: {}", server.id.to_sql()))
+            }
+            Ok(_) => Ok(()),
+            Err(e) if e.to_string().contains("already contains") => Err(format!(
+                "An MCP server named '{}' already exists",
+                server.name
+            )),
+            Err(e) => Err(format!("Failed to update MCP server: {}", e)),
+        }
+    }
+
+    /// Delete an MCP server (templates using it lose the link)
+    pub async fn delete_mcp_server(&self, id: &RecordId) -> Result<(), String> {
+        let deleted: Option<McpServer> = self
+            .client()
+            .delete(id)
+            .await
+            .map_err(|e| format!("Failed to delete MCP server: {}", e))?;
+
+        deleted
+            .map(|_| ())
+            .ok_or_else(|| format!("MCP server not found: {}", id.to_sql()))
+    }
+
+    /// Servers enabled for an agent template, ordered by name
+    pub async fn list_template_mcp_servers(
+        &self,
+        template_id: &RecordId,
+    ) -> Result<Vec<McpServer>, String> {
+        let query = r"
+            SELECT * FROM mcp_server
+            WHERE id IN (SELECT VALUE server_id FROM template_mcp_server WHERE template_id = $template_id)
+            ORDER BY name ASC
+        ";
+
+        let mut response = self
+            .client()
+            .query(query)
+            .bind(("template_id", template_id.clone()))
+            .await
+            .map_err(|e| format!("Failed to list template MCP servers: {}", e))?;
+
+        response
+            .take(0)
+            .map_err(|e| format!("Failed to parse template MCP servers: {}", e))
+    }
+
+    /// Replace the set of servers enabled for an agent template
+    pub async fn set_template_mcp_servers(
+        &self,
+        template_id: &RecordId,
+        server_ids: &[RecordId],
+    ) -> Result<(), String> {
+        let query = r"
+            BEGIN TRANSACTION;
+            DELETE template_mcp_server WHERE template_id = $template_id;
+            FOR $server_id IN $server_ids {
+                CREATE template_mcp_server CONTENT {
+                    template_id: $template_id,
+                    server_id: $server_id
+                };
+            };
+            COMMIT TRANSACTION;
+        ";
+
+        self.client()
+            .query(query)
+            .bind(("template_id", template_id.clone()))
+            .bind(("server_ids", server_ids.to_vec()))
+            .await
+            .map_err(|e| format!("Failed to set template MCP servers: {}", e))?
+            .check()
+            .map_err(|e| format!("Failed to set template MCP servers: {}", e))?;
+
+        Ok(())
+    }
+}
@@ -1,16 +1,61 @@
 //! Message database operations
 //!
 //! Provides CRUD operations for message table including insert, retrieve,
-//! search, mark read, delete, and pin operations.
+//! keyset pagination, search, mark read, delete, and pin operations.
 //!
 //! Aligns with src/database/schema.rs message table (lines 57-74)
 
 use super::Database;
 use crate::view_model::message::{AuthorType, Message, MessageType};
 use chrono::{DateTime, Utc};
+use futures_util::{Stream, StreamExt};
 use serde::{Deserialize, Serialize};
+use surrealdb::Notification;
 use surrealdb::types::RecordId;
-use surrealdb_types::{SurrealValue, ToSql};
+use surrealdb_types::{Datetime, SurrealValue, ToSql};
+
+/// Keyset pagination cursor for message history
+///
+/// Messages are ordered by `(timestamp, id)`. The id breaks ties between
+/// messages written within the same timestamp (e.g. tool messages), so a
+/// cursor always identifies exactly one position in the conversation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MessageCursor {
+    pub timestamp: Datetime,
+    pub id: RecordId,
+}
+
+impl From<&Message> for MessageCursor {
+    fn from(message: &Message) -> Self {
+        Self {
+            timestamp: message.timestamp,
+            id: message.id.clone(),
+        }
+    }
+}
+
+/// One page of messages returned by keyset pagination
+///
+/// `messages` are always in chronological order (oldest first), regardless
+/// of the direction the page was fetched in.
+#[derive(Debug, Clone, Default)]
+pub struct MessagePage {
+    pub messages: Vec<Message>,
+    /// More messages exist beyond this page in the direction it was fetched
+    pub has_more: bool,
+}
+
+impl MessagePage {
+    /// Cursor for the oldest message in this page
+    pub fn first_cursor(&self) -> Option<MessageCursor> {
+        self.messages.first().map(MessageCursor::from)
+    }
+
+    /// Cursor for the newest message in this page
+    pub fn last_cursor(&self) -> Option<MessageCursor> {
+        self.messages.last().map(MessageCursor::from)
+    }
+}
 
 impl Database {
     /// Insert a new message and update conversation timestamp
@@ -104,7 +149,7 @@ impl Database {
     /// Uses token-aware dynamic limit based on configured token budget.
     pub async fn get_recent_messages(&self, conversation_id: &RecordId) -> Result<Vec<Message>, String> {
         // Calculate dynamic limit based on token budget
-        let message_limit = self.token_budget_config.calculate_message_limit();
+        let message_limit = self.token_budget_config().calculate_message_limit();
 
         // Build query with dynamic limit
         let query = format!(
@@ -168,6 +213,173 @@ impl Database {
         Ok(messages)
     }
 
+    /// Get a page of messages older than the cursor
+    ///
+    /// # Arguments
+    /// * `conversation_id` - Conversation ID to page through
+    /// * `before` - Exclusive upper bound; `None` starts from the newest message
+    /// * `limit` - Maximum number of messages in the page
+    ///
+    /// # Returns
+    /// * `Ok(MessagePage)` - Messages in chronological order (oldest first)
+    /// * `Err(String)` - Error message if retrieval fails
+    ///
+    /// # Design Note
+    /// Keyset pagination on `(timestamp, id)` backed by idx_msg_conv, so cost
+    /// does not grow with scroll depth the way OFFSET would. Fetches
+    /// `limit + 1` rows to detect whether an older page exists.
+    pub async fn get_messages_before(
+        &self,
+        conversation_id: &RecordId,
+        before: Option<&MessageCursor>,
+        limit: usize,
+    ) -> Result<MessagePage, String> {
+        let query = if before.is_some() {
+            r"
+            SELECT *
+            FROM message
+            WHERE conversation_id = $conversation_id
+              AND deleted = false
+              AND (timestamp < $timestamp OR (timestamp = $timestamp AND id < $id))
+            ORDER BY timestamp DESC, id DESC
+            LIMIT $limit
+            "
+        } else {
+            r"
+            SELECT *
+            FROM message
+            WHERE conversation_id = $conversation_id
+              AND deleted = false
+            ORDER BY timestamp DESC, id DESC
+            LIMIT $limit
+            "
+        };
+
+        let mut messages = self
+            .query_message_page(query, conversation_id, before, limit)
+            .await?;
+
+        let has_more = messages.len() > limit;
+        messages.truncate(limit);
+        messages.reverse();
+
+        Ok(MessagePage { messages, has_more })
+    }
+
+    /// Get a page of messages newer than the cursor
+    ///
+    /// # Arguments
+    /// * `conversation_id` - Conversation ID to page through
+    /// * `after` - Exclusive lower bound; `None` starts from the oldest message
+    /// * `limit` - Maximum number of messages in the page
+    ///
+    /// # Returns
+    /// * `Ok(MessagePage)` - Messages in chronological order (oldest first)
+    /// * `Err(String)` - Error message if retrieval fails
+    pub async fn get_messages_after(
+        &self,
+        conversation_id: &RecordId,
+        after: Option<&MessageCursor>,
+        limit: usize,
+    ) -> Result<MessagePage, String> {
+        let query = if after.is_some() {
+            r"
+            SELECT *
+            FROM message
+            WHERE conversation_id = $conversation_id
+              AND deleted = false
+              AND (timestamp > $timestamp OR (timestamp = $timestamp AND id > $id))
+            ORDER BY timestamp ASC, id ASC
+            LIMIT $limit
+            "
+        } else {
+            r"
+            SELECT *
+            FROM message
+            WHERE conversation_id = $conversation_id
+              AND deleted = false
+            ORDER BY timestamp ASC, id ASC
+            LIMIT $limit
+            "
+        };
+
+        let mut messages = self
+            .query_message_page(query, conversation_id, after, limit)
+            .await?;
+
+        let has_more = messages.len() > limit;
+        messages.truncate(limit);
+
+        Ok(MessagePage { messages, has_more })
+    }
+
+    /// Get the window of messages surrounding a target message
+    ///
+    /// # Arguments
+    /// * `message_id` - Message to center the window on
+    /// * `radius` - Number of messages to load on each side of the target
+    ///
+    /// # Returns
+    /// * `Ok((MessagePage, MessagePage))` - `(older, newer)` pages; the older page
+    ///   ends with the target message itself so callers can concatenate both
+    /// * `Err(String)` - Message not found or deleted, or query failed
+    ///
+    /// # Design Note
+    /// Used for jump-to-message (pinned banner, search hits). The returned
+    /// `has_more` flags tell the caller whether to keep paging in either direction.
+    pub async fn get_messages_around(
+        &self,
+        message_id: &RecordId,
+        radius: usize,
+    ) -> Result<(MessagePage, MessagePage), String> {
+        let target = self.get_message(message_id).await?;
+        // Soft-deleted messages stay hidden, as in every other page query
+        if target.deleted {
+            return Err(format!("Message {} has been deleted", message_id.to_sql()));
+        }
+        let cursor = MessageCursor::from(&target);
+
+        let mut older = self
+            .get_messages_before(&target.conversation_id, Some(&cursor), radius)
+            .await?;
+        let newer = self
+            .get_messages_after(&target.conversation_id, Some(&cursor), radius)
+            .await?;
+
+        older.messages.push(target);
+
+        Ok((older, newer))
+    }
+
+    /// Execute a keyset page query with cursor bindings
+    async fn query_message_page(
+        &self,
+        query: &str,
+        conversation_id: &RecordId,
+        cursor: Option<&MessageCursor>,
+        limit: usize,
+    ) -> Result<Vec<Message>, String> {
+        let mut request = self
+            .client()
+            .query(query)
+            .bind(("conversation_id", conversation_id.clone()))
+            .bind(("limit", (limit + 1) as i64));
+
+        if let Some(cursor) = cursor {
+            request = request
+                .bind(("timestamp", cursor.timestamp))
+                .bind(("id", cursor.id.clone()));
+        }
+
+        let mut response = request
+            .await
+            .map_err(|e| format!("Failed to get message page: {}", e))?;
+
+        response
+            .take(0)
+            .map_err(|e| format!("Failed to parse message page: {}", e))
+    }
+
     /// Search messages by content within a conversation
     ///
     /// # Arguments
@@ -210,6 +422,59 @@ impl Database {
         Ok(messages)
     }
 
+    /// Search message content, newest first
+    ///
+    /// # Arguments
+    /// * `conversation_id` - Conversation to search within, or `None` for all
+    /// * `search_term` - Text to search for in message content
+    /// * `limit` - Maximum number of messages to return
+    ///
+    /// # Returns
+    /// * `Ok(Vec<Message>)` - Up to `limit` matching messages, newest first
+    /// * `Err(String)` - Error message if search fails
+    pub async fn search_recent_messages(
+        &self,
+        conversation_id: Option<&RecordId>,
+        search_term: &str,
+        limit: usize,
+    ) -> Result<Vec<Message>, String> {
+        let conversation_filter = if conversation_id.is_some() {
+            "AND conversation_id = $conversation_id"
+        } else {
+            ""
+        };
+
+        let query = format!(
+            r"
+            SELECT *
+            FROM message
+            WHERE content CONTAINS $search_term
+              AND deleted = false
+              {conversation_filter}
+            ORDER BY timestamp DESC
+            LIMIT $limit
+        "
+        );
+
+        let mut request = self
+            .client()
+            .query(query)
+            .bind(("search_term", search_term.to_string()))
+            .bind(("limit", limit as i64));
+        if let Some(conversation_id) = conversation_id {
+            request = request.bind(("conversation_id", conversation_id.clone()));
+        }
+        let mut response = request
+            .await
+            .map_err(|e| format!("Failed to search messages: {}", e))?;
+
+        let messages: Vec<Message> = response
+            .take(0)
+            .map_err(|e| format!("Failed to parse search results: {}", e))?;
+
+        Ok(messages)
+    }
+
     /// Mark all messages in a conversation as read
     ///
     /// # Arguments
@@ -552,7 +817,7 @@ impl Database {
     /// Get all unread messages for a user across all conversations
     ///
     /// # Arguments
-    /// * `user_id` - User ID (unused for MVP, all messages returned)
+    /// * `user_id` - Account whose conversations (plus unowned ones) are searched
     ///
     /// # Returns
     /// * `Ok(Vec<Message>)` - Unread messages (newest first)
@@ -561,13 +826,14 @@ impl Database {
     /// # Design Note (Q28-Q30)
     /// Used for notifications - shows agent responses user hasn't seen.
     /// Filters for agent messages only (author_type="agent").
-    pub async fn get_unread_messages(&self, _user_id: &str) -> Result<Vec<Message>, String> {
+    pub async fn get_unread_messages(&self, user_id: &str) -> Result<Vec<Message>, String> {
         let query = r#"
             SELECT *
             FROM message
             WHERE unread = true
               AND deleted = false
               AND author_type = "agent"
+              AND (conversation_id.user_id = $user OR conversation_id.user_id = NONE)
             ORDER BY timestamp DESC
             LIMIT 50
         "#;
@@ -575,6 +841,7 @@ impl Database {
         let mut response = self
             .client()
             .query(query)
+            .bind(("user", user_id.to_string()))
             .await
             .map_err(|e| format!("Failed to get unread messages: {}", e))?;
 
@@ -613,4 +880,30 @@ impl Database {
 
         Ok(())
     }
+
+    /// Subscribe to message changes in a conversation
+    ///
+    /// # Returns
+    /// * `Ok(stream)` - LIVE SELECT notifications (create, update, delete)
+    /// * `Err(String)` - Live query could not be started
+    ///
+    /// # Design Note
+    /// Start the subscription before loading existing messages, so nothing
+    /// written in between is missed; callers deduplicate by message ID.
+    pub async fn live_messages(
+        &self,
+        conversation_id: &RecordId,
+    ) -> Result<impl Stream<Item = Result<Notification<Message>, String>> + Send + Unpin, String>
+    {
+        let stream = self
+            .client()
+            .query("LIVE SELECT * FROM message WHERE conversation_id = $id")
+            .bind(("id", conversation_id.clone()))
+            .await
+            .map_err(|e| format!("Failed to start live query: {}", e))?
+            .stream::<Notification<Message>>(0)
+            .map_err(|e| format!("Failed to open message stream: {}", e))?;
+
+        Ok(stream.map(|notification| notification.map_err(|e| e.to_string())))
+    }
 }
@@ -1,35 +1,141 @@
-//! Database migration logic for conversation/room unification
+//! Versioned schema migrations
 //!
-//! Migrates existing data from old schema to unified schema:
-//! - Old: conversation.template_id (single agent)
-//! - Old: conversation.agent_session_id (single session)
-//! - Old: room.participants (multi-agent, no sessions)
-//! - New: conversation.participants (1:N agents)
-//! - New: conversation.agent_sessions (HashMap<agent_id, session_id>)
+//! Migrations are registered in [`MIGRATIONS`] as ordered, numbered entries
+//! with `up` and (optionally) `down` SurrealQL. Each migration runs inside a
+//! single transaction together with its `schema_version` bookkeeping, so a
+//! failed migration leaves no partial state behind.
+//!
+//! Applied migrations record a checksum of their SurrealQL. On startup the
+//! recorded checksums are compared against the registry to detect migrations
+//! that were edited after being shipped.
+//!
+//! The registry owns the whole schema: [`BASELINE`] defines the version 0
+//! tables and each migration everything it adds, so a database at version N
+//! has exactly the schema of N, whether it was migrated up or down to it.
+//!
+//! # Adding a migration
+//! Append a new [`Migration`] with the next version number, its SurrealQL
+//! written out as a literal in the entry. Never edit or reorder an existing
+//! entry - write a follow-up migration instead.
 
 use super::Database;
+use serde::Deserialize;
+use std::path::{Path, PathBuf};
+use surrealdb_types::SurrealValue;
 
-impl Database {
-    /// Migrate all conversations from old schema to new unified schema
-    ///
-    /// Wrapped in SurrealDB transaction for atomicity.
-    /// If migration fails partway through, ALL changes are rolled back.
+/// A single numbered schema migration
+#[derive(Debug, Clone, Copy)]
+pub struct Migration {
+    /// Strictly increasing version number (first migration is 1)
+    pub version: i64,
+    /// Short human-readable name, stored alongside the version
+    pub name: &'static str,
+    /// SurrealQL applied when migrating up (no BEGIN/COMMIT - the runner wraps it)
+    pub up: &'static str,
+    /// SurrealQL that reverses `up`, or `None` if the migration is irreversible
+    pub down: Option<&'static str>,
+}
+
+impl Migration {
+    /// Stable checksum of the migration's SurrealQL (FNV-1a, 64-bit, hex)
     ///
-    /// # Returns
-    /// * `Ok((migrated_conversations, migrated_rooms))` - Count of migrated records
-    /// * `Err(String)` - Error if migration fails
-    pub async fn migrate_to_unified_conversations(&self) -> Result<(usize, usize), String> {
-        log::info!("[Migration] Starting conversation/room unification migration");
+    /// Uses a fixed algorithm rather than `std::hash` so the value is stable
+    /// across Rust releases and can be persisted.
+    pub fn checksum(&self) -> String {
+        const FNV_OFFSET: u64 = 0xcbf2_9ce4_8422_2325;
+        const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
 
-        // ✅ Execute entire migration as single query with transaction
-        let query = r"
-            BEGIN TRANSACTION;
+        let mut hash = FNV_OFFSET;
+        let parts = [self.name, self.up, self.down.unwrap_or("")];
+        for part in parts {
+            for byte in part.bytes().chain(std::iter::once(0)) {
+                hash ^= u64::from(byte);
+                hash = hash.wrapping_mul(FNV_PRIME);
+            }
+        }
+
+        format!("{:016x}", hash)
+    }
+}
+
+/// Version 0 schema: the core chat tables as they stood before migration 1
+///
+/// Applied to databases still at version 0 (fresh or pre-registry ones);
+/// every later table, field and index comes from [`MIGRATIONS`]. Like a
+/// migration, this must never be edited once shipped.
+pub const BASELINE: &str = r#"
+    DEFINE TABLE IF NOT EXISTS agent_template SCHEMAFULL;
+    DEFINE FIELD IF NOT EXISTS name ON agent_template TYPE string;
+    DEFINE FIELD IF NOT EXISTS system_prompt ON agent_template TYPE string;
+    DEFINE FIELD IF NOT EXISTS model ON agent_template TYPE string;
+    DEFINE FIELD IF NOT EXISTS max_turns ON agent_template TYPE int DEFAULT 50;
+    DEFINE FIELD IF NOT EXISTS icon ON agent_template TYPE option<string>;
+    DEFINE FIELD IF NOT EXISTS color ON agent_template TYPE option<string>;
+    DEFINE FIELD IF NOT EXISTS created_at ON agent_template TYPE datetime DEFAULT time::now();
+    DEFINE INDEX IF NOT EXISTS idx_template_name ON agent_template COLUMNS name;
+
+    DEFINE TABLE IF NOT EXISTS conversation SCHEMAFULL;
+    DEFINE FIELD IF NOT EXISTS title ON conversation TYPE string;
+    DEFINE FIELD IF NOT EXISTS participants ON conversation TYPE array<record<agent_template>> ASSERT array::len($value) > 0 AND array::len($value) <= 50;
+    DEFINE FIELD IF NOT EXISTS summary ON conversation TYPE string DEFAULT "";
+    DEFINE FIELD IF NOT EXISTS agent_sessions ON conversation TYPE object DEFAULT {};
+    DEFINE FIELD IF NOT EXISTS last_summarized_message_id ON conversation TYPE option<record<message>>;
+    DEFINE FIELD IF NOT EXISTS last_message_at ON conversation TYPE datetime;
+    DEFINE FIELD IF NOT EXISTS created_at ON conversation TYPE datetime DEFAULT time::now();
+    DEFINE INDEX IF NOT EXISTS idx_conv_updated ON conversation COLUMNS last_message_at;
 
+    DEFINE TABLE IF NOT EXISTS message SCHEMAFULL;
+    DEFINE FIELD IF NOT EXISTS conversation_id ON message TYPE record<conversation> REFERENCE ON DELETE CASCADE;
+    DEFINE FIELD IF NOT EXISTS author ON message TYPE string;
+    DEFINE FIELD IF NOT EXISTS author_type ON message TYPE string ASSERT $value IN ["human", "agent", "system", "tool"];
+    DEFINE FIELD IF NOT EXISTS content ON message TYPE string ASSERT string::len($value) > 0;
+    DEFINE FIELD IF NOT EXISTS timestamp ON message TYPE datetime DEFAULT time::now();
+    DEFINE FIELD IF NOT EXISTS in_reply_to ON message TYPE option<record<message>>;
+    DEFINE FIELD IF NOT EXISTS attachments ON message TYPE array DEFAULT [];
+    DEFINE FIELD IF NOT EXISTS message_type ON message TYPE string DEFAULT "normal" ASSERT $value IN ["normal", "error", "system", "tool"];
+    DEFINE FIELD IF NOT EXISTS unread ON message TYPE bool DEFAULT false;
+    DEFINE FIELD IF NOT EXISTS deleted ON message TYPE bool DEFAULT false;
+    DEFINE FIELD IF NOT EXISTS pinned ON message TYPE bool DEFAULT false;
+    DEFINE INDEX IF NOT EXISTS idx_msg_conv ON message COLUMNS conversation_id, timestamp;
+    DEFINE INDEX IF NOT EXISTS idx_msg_unread ON message COLUMNS conversation_id, unread;
+    DEFINE INDEX IF NOT EXISTS idx_msg_pinned ON message COLUMNS conversation_id, pinned;
+
+    DEFINE TABLE IF NOT EXISTS bookmark SCHEMAFULL;
+    DEFINE FIELD IF NOT EXISTS user_id ON bookmark TYPE string;
+    DEFINE FIELD IF NOT EXISTS message_id ON bookmark TYPE record<message> REFERENCE ON DELETE CASCADE;
+    DEFINE FIELD IF NOT EXISTS created_at ON bookmark TYPE datetime DEFAULT time::now();
+    DEFINE INDEX IF NOT EXISTS idx_bookmark_user ON bookmark COLUMNS user_id, created_at;
+
+    DEFINE TABLE IF NOT EXISTS reaction SCHEMAFULL;
+    DEFINE FIELD IF NOT EXISTS message_id ON reaction TYPE record<message> REFERENCE ON DELETE CASCADE;
+    DEFINE FIELD IF NOT EXISTS user_id ON reaction TYPE string;
+    DEFINE FIELD IF NOT EXISTS emoji ON reaction TYPE string ASSERT string::len($value) > 0 AND string::len($value) <= 10;
+    DEFINE FIELD IF NOT EXISTS created_at ON reaction TYPE datetime DEFAULT time::now();
+    DEFINE INDEX IF NOT EXISTS idx_reaction_msg ON reaction COLUMNS message_id;
+    DEFINE INDEX IF NOT EXISTS idx_reaction_unique ON reaction COLUMNS message_id, user_id, emoji UNIQUE;
+"#;
+
+/// Registry of all schema migrations, ordered by version
+pub const MIGRATIONS: &[Migration] = &[
+    // Migration 1: Unify conversations and rooms
+    // - Old: conversation.template_id (single agent)
+    // - Old: conversation.agent_session_id (single session)
+    // - Old: room.participants (multi-agent, no sessions)
+    // - New: conversation.participants (1:N agents)
+    // - New: conversation.agent_sessions (HashMap<agent_id, session_id>)
+    Migration {
+        version: 1,
+        name: "unify_conversations_and_rooms",
+        up: r"
             -- Step 1: Migrate old conversations (template_id → participants)
+            LET $old_convs = (
+                SELECT id, template_id, agent_session_id
+                FROM conversation
+                WHERE template_id != NONE
+            );
+
             FOR $conv IN $old_convs {
-                -- Start with basic participants update
                 UPDATE $conv.id SET participants = [$conv.template_id];
-                -- Build agent_sessions properly using conditional logic
                 IF $conv.agent_session_id != NONE {
                     -- Use object::from_entries to build dynamic key-value pair
                     UPDATE $conv.id SET agent_sessions = object::from_entries([[$conv.template_id, $conv.agent_session_id]]);
@@ -60,52 +166,562 @@ impl Database {
             };
 
             -- Step 3: Drop old room table
-            REMOVE TABLE room;
+            REMOVE TABLE IF EXISTS room;
+        ",
+        // Rooms are merged into conversations and the room table is dropped
+        down: None,
+    },
+    // Migration 2: Performance indexes for notification author filtering,
+    // bookmark existence checks and reaction grouping (DEFECT_008)
+    Migration {
+        version: 2,
+        name: "add_performance_indexes",
+        up: r"
+            DEFINE INDEX IF NOT EXISTS idx_message_author_unread ON message
+                COLUMNS author_type, unread, timestamp;
+            DEFINE INDEX IF NOT EXISTS idx_bookmark_lookup ON bookmark
+                COLUMNS user_id, message_id;
+            DEFINE INDEX IF NOT EXISTS idx_reaction_msg_emoji ON reaction
+                COLUMNS message_id, emoji;
+        ",
+        down: Some(
+            r"
+            REMOVE INDEX IF EXISTS idx_message_author_unread ON message;
+            REMOVE INDEX IF EXISTS idx_bookmark_lookup ON bookmark;
+            REMOVE INDEX IF EXISTS idx_reaction_msg_emoji ON reaction;
+        ",
+        ),
+    },
+    // Migration 3: Notification policy, quiet hours and delivery receipts
+    Migration {
+        version: 3,
+        name: "add_notification_tables",
+        up: r#"
+    DEFINE TABLE IF NOT EXISTS notification_preference SCHEMAFULL;
+    DEFINE FIELD IF NOT EXISTS conversation_id ON notification_preference TYPE record<conversation> REFERENCE ON DELETE CASCADE;
+    DEFINE FIELD IF NOT EXISTS policy ON notification_preference TYPE string DEFAULT "always" ASSERT $value IN ["always", "when_unfocused", "mentions_only", "muted"];
+    DEFINE INDEX IF NOT EXISTS idx_notification_preference_conv ON notification_preference COLUMNS conversation_id UNIQUE;
 
-            COMMIT TRANSACTION;
-        ";
+    DEFINE TABLE IF NOT EXISTS notification_settings SCHEMAFULL;
+    DEFINE FIELD IF NOT EXISTS quiet_start_minute ON notification_settings TYPE option<int> ASSERT $value = NONE OR ($value >= 0 AND $value < 1440);
+    DEFINE FIELD IF NOT EXISTS quiet_end_minute ON notification_settings TYPE option<int> ASSERT $value = NONE OR ($value >= 0 AND $value < 1440);
+
+    DEFINE TABLE IF NOT EXISTS notification_receipt SCHEMAFULL;
+    DEFINE FIELD IF NOT EXISTS message_id ON notification_receipt TYPE record<message> REFERENCE ON DELETE CASCADE;
+    DEFINE FIELD IF NOT EXISTS conversation_id ON notification_receipt TYPE record<conversation> REFERENCE ON DELETE CASCADE;
+    DEFINE FIELD IF NOT EXISTS platform ON notification_receipt TYPE option<string>;
+    DEFINE FIELD IF NOT EXISTS delivered_at ON notification_receipt TYPE option<datetime>;
+    DEFINE FIELD IF NOT EXISTS created_at ON notification_receipt TYPE datetime DEFAULT time::now();
+    DEFINE INDEX IF NOT EXISTS idx_notification_receipt_msg ON notification_receipt COLUMNS message_id UNIQUE;
+"#,
+        down: Some(
+            r"
+            REMOVE TABLE IF EXISTS notification_receipt;
+            REMOVE TABLE IF EXISTS notification_settings;
+            REMOVE TABLE IF EXISTS notification_preference;
+        ",
+        ),
+    },
+    // Migration 4: Scheduled and recurring prompts
+    Migration {
+        version: 4,
+        name: "add_schedule_table",
+        up: r#"
+    DEFINE TABLE IF NOT EXISTS schedule SCHEMAFULL;
+    DEFINE FIELD IF NOT EXISTS conversation_id ON schedule TYPE record<conversation> REFERENCE ON DELETE CASCADE;
+    DEFINE FIELD IF NOT EXISTS agent_id ON schedule TYPE option<record<agent_template>> REFERENCE ON DELETE UNSET;
+    DEFINE FIELD IF NOT EXISTS prompt ON schedule TYPE string ASSERT string::len($value) > 0;
+    DEFINE FIELD IF NOT EXISTS cron ON schedule TYPE option<string>;
+    DEFINE FIELD IF NOT EXISTS interval_secs ON schedule TYPE option<int> ASSERT $value = NONE OR $value >= 60;
+    DEFINE FIELD IF NOT EXISTS paused ON schedule TYPE bool DEFAULT false;
+    DEFINE FIELD IF NOT EXISTS next_run_at ON schedule TYPE datetime;
+    DEFINE FIELD IF NOT EXISTS last_run_at ON schedule TYPE option<datetime>;
+    DEFINE FIELD IF NOT EXISTS last_error ON schedule TYPE option<string>;
+    DEFINE FIELD IF NOT EXISTS created_at ON schedule TYPE datetime DEFAULT time::now();
+    DEFINE INDEX IF NOT EXISTS idx_schedule_due ON schedule COLUMNS paused, next_run_at;
+"#,
+        down: Some("REMOVE TABLE IF EXISTS schedule;"),
+    },
+    // Migration 5: Prompt snippets for slash commands
+    Migration {
+        version: 5,
+        name: "add_prompt_snippet_table",
+        up: r#"
+    DEFINE TABLE IF NOT EXISTS prompt_snippet SCHEMAFULL;
+    DEFINE FIELD IF NOT EXISTS name ON prompt_snippet TYPE string ASSERT string::len($value) > 0;
+    DEFINE FIELD IF NOT EXISTS description ON prompt_snippet TYPE string DEFAULT "";
+    DEFINE FIELD IF NOT EXISTS body ON prompt_snippet TYPE string;
+    DEFINE FIELD IF NOT EXISTS created_at ON prompt_snippet TYPE datetime DEFAULT time::now();
+    DEFINE INDEX IF NOT EXISTS idx_prompt_snippet_name ON prompt_snippet COLUMNS name UNIQUE;
+"#,
+        down: Some("REMOVE TABLE IF EXISTS prompt_snippet;"),
+    },
+    // Migration 6: External MCP servers enabled per agent template
+    Migration {
+        version: 6,
+        name: "add_mcp_server_tables",
+        up: r#"
+    DEFINE TABLE IF NOT EXISTS mcp_server SCHEMAFULL;
+    DEFINE FIELD IF NOT EXISTS name ON mcp_server TYPE string ASSERT string::len($value) > 0;
+    DEFINE FIELD IF NOT EXISTS transport ON mcp_server TYPE string DEFAULT "stdio" ASSERT $value IN ["stdio", "http"];
+    DEFINE FIELD IF NOT EXISTS command ON mcp_server TYPE string DEFAULT "";
+    DEFINE FIELD IF NOT EXISTS args ON mcp_server TYPE array<string> DEFAULT [];
+    DEFINE FIELD IF NOT EXISTS env ON mcp_server TYPE object DEFAULT {};
+    DEFINE FIELD IF NOT EXISTS url ON mcp_server TYPE string DEFAULT "";
+    DEFINE FIELD IF NOT EXISTS secret_keys ON mcp_server TYPE array<string> DEFAULT [];
+    DEFINE FIELD IF NOT EXISTS created_at ON mcp_server TYPE datetime DEFAULT time::now();
+    DEFINE INDEX IF NOT EXISTS idx_mcp_server_name ON mcp_server COLUMNS name UNIQUE;
+
+    DEFINE TABLE IF NOT EXISTS template_mcp_server SCHEMAFULL;
+    DEFINE FIELD IF NOT EXISTS template_id ON template_mcp_server TYPE record<agent_template> REFERENCE ON DELETE CASCADE;
+    DEFINE FIELD IF NOT EXISTS server_id ON template_mcp_server TYPE record<mcp_server> REFERENCE ON DELETE CASCADE;
+    DEFINE INDEX IF NOT EXISTS idx_template_mcp_server ON template_mcp_server COLUMNS template_id, server_id UNIQUE;
+"#,
+        down: Some(
+            r"
+            REMOVE TABLE IF EXISTS template_mcp_server;
+            REMOVE TABLE IF EXISTS mcp_server;
+        ",
+        ),
+    },
+    // Migration 7: Per-template inference backend (existing templates stay on Claude)
+    Migration {
+        version: 7,
+        name: "add_template_backend",
+        up: r#"
+            DEFINE FIELD IF NOT EXISTS backend ON agent_template TYPE string DEFAULT "claude" ASSERT $value IN ["claude", "openai", "local"];
+            DEFINE FIELD IF NOT EXISTS backend_url ON agent_template TYPE option<string>;
+            DEFINE FIELD IF NOT EXISTS backend_model ON agent_template TYPE option<string>;
+            UPDATE agent_template SET backend = "claude" WHERE backend = NONE;
+        "#,
+        down: Some(
+            r"
+            REMOVE FIELD IF EXISTS backend_model ON agent_template;
+            REMOVE FIELD IF EXISTS backend_url ON agent_template;
+            REMOVE FIELD IF EXISTS backend ON agent_template;
+            UPDATE agent_template UNSET backend, backend_url, backend_model;
+        ",
+        ),
+    },
+    // Migration 8: Conversations owned by signed-in accounts (existing ones
+    // stay shared)
+    Migration {
+        version: 8,
+        name: "add_conversation_owner",
+        up: r#"
+    DEFINE FIELD IF NOT EXISTS user_id ON conversation TYPE option<string>;
+    DEFINE INDEX IF NOT EXISTS idx_conv_user ON conversation COLUMNS user_id, last_message_at;
+"#,
+        down: Some(
+            r"
+            REMOVE INDEX IF EXISTS idx_conv_user ON conversation;
+            REMOVE FIELD IF EXISTS user_id ON conversation;
+            UPDATE conversation UNSET user_id;
+        ",
+        ),
+    },
+    // Migration 9: Sign-in/sign-out audit trail
+    Migration {
+        version: 9,
+        name: "add_auth_event_table",
+        up: r#"
+    DEFINE TABLE IF NOT EXISTS auth_event SCHEMAFULL;
+    DEFINE FIELD IF NOT EXISTS user_id ON auth_event TYPE string;
+    DEFINE FIELD IF NOT EXISTS provider ON auth_event TYPE string;
+    DEFINE FIELD IF NOT EXISTS kind ON auth_event TYPE string ASSERT $value IN ["signin", "signout"];
+    DEFINE FIELD IF NOT EXISTS tokens_revoked ON auth_event TYPE bool DEFAULT false;
+    DEFINE FIELD IF NOT EXISTS detail ON auth_event TYPE option<string>;
+    DEFINE FIELD IF NOT EXISTS created_at ON auth_event TYPE datetime DEFAULT time::now();
+    DEFINE INDEX IF NOT EXISTS idx_auth_event_user ON auth_event COLUMNS user_id, created_at;
+"#,
+        down: Some("REMOVE TABLE IF EXISTS auth_event;"),
+    },
+    // Migration 10: Scope per-user rows by account key (`google:1234`) rather
+    // than the provider-local user id, which two providers can share. The
+    // provider comes from the account's recorded sign-ins; rows of accounts
+    // with none are re-keyed when the account next signs in.
+    Migration {
+        version: 10,
+        name: "scope_user_data_by_account_key",
+        up: r#"
+            LET $events = (SELECT user_id, provider, created_at FROM auth_event ORDER BY created_at ASC);
+
+            FOR $event IN $events {
+                LET $key = string::concat($event.provider, ":", $event.user_id);
+                UPDATE conversation SET user_id = $key WHERE user_id = $event.user_id;
+                UPDATE bookmark SET user_id = $key WHERE user_id = $event.user_id;
+                UPDATE reaction SET user_id = $key WHERE user_id = $event.user_id;
+            };
+        "#,
+        down: Some(
+            r#"
+            LET $events = (SELECT user_id, provider, created_at FROM auth_event ORDER BY created_at ASC);
+
+            FOR $event IN $events {
+                LET $key = string::concat($event.provider, ":", $event.user_id);
+                UPDATE conversation SET user_id = $event.user_id WHERE user_id = $key;
+                UPDATE bookmark SET user_id = $event.user_id WHERE user_id = $key;
+                UPDATE reaction SET user_id = $event.user_id WHERE user_id = $key;
+            };
+        "#,
+        ),
+    },
+];
+
+/// Latest schema version known to this build
+pub fn latest_version() -> i64 {
+    MIGRATIONS.last().map(|m| m.version).unwrap_or(0)
+}
+
+/// Options controlling a migration run
+#[derive(Debug, Clone, Copy)]
+pub struct MigrationOptions {
+    /// Plan the run and report it without touching the database
+    pub dry_run: bool,
+    /// Export the database next to its data directory before changing anything
+    pub backup: bool,
+}
+
+impl Default for MigrationOptions {
+    fn default() -> Self {
+        Self {
+            dry_run: false,
+            backup: true,
+        }
+    }
+}
+
+/// Outcome of a migration run
+#[derive(Debug, Clone, Default)]
+pub struct MigrationReport {
+    /// Schema version before the run
+    pub from_version: i64,
+    /// Schema version after the run (target version for dry runs)
+    pub to_version: i64,
+    /// Versions migrated up, in execution order
+    pub applied: Vec<i64>,
+    /// Versions migrated down, in execution order
+    pub reverted: Vec<i64>,
+    /// Applied versions whose recorded checksum differs from the registry
+    pub checksum_mismatches: Vec<i64>,
+    /// Location of the pre-migration backup, if one was taken
+    pub backup_path: Option<PathBuf>,
+    /// True if nothing was executed
+    pub dry_run: bool,
+}
+
+#[derive(Deserialize, SurrealValue)]
+struct AppliedMigration {
+    version: i64,
+    checksum: Option<String>,
+}
 
-        let _response = self.client()
+impl Database {
+    /// Get current schema version (0 if no version set)
+    pub async fn get_schema_version(&self) -> Result<i64, String> {
+        let query = "SELECT version FROM schema_version ORDER BY version DESC LIMIT 1";
+
+        let mut response = self
+            .client()
             .query(query)
             .await
-            .map_err(|e| format!("Migration failed: {}", e))?;
+            .map_err(|e| format!("Failed to get schema version: {}", e))?;
 
-        // Since we're using a single transaction query, we don't get individual counts
-        // The migration either succeeds completely or fails completely
-        // For now, return placeholder counts - in production we might want to query counts separately
-        let conv_count = 0; // Placeholder - would need separate query to get actual count
-        let room_count = 0; // Placeholder - would need separate query to get actual count
+        #[derive(Deserialize, SurrealValue)]
+        struct VersionRecord {
+            version: i64,
+        }
 
-        log::info!("[Migration] Migration completed successfully");
+        let versions: Vec<VersionRecord> = response
+            .take(0)
+            .map_err(|e| format!("Failed to parse schema version: {}", e))?;
 
-        Ok((conv_count, room_count))
+        Ok(versions.first().map(|v| v.version).unwrap_or(0))
     }
 
-    /// Add performance indexes for message author filtering, bookmark lookups, and reaction grouping
-    /// 
+    /// Migrate the schema to the latest registered version
+    ///
+    /// # Arguments
+    /// * `options` - Dry-run and backup behaviour
+    ///
     /// # Returns
-    /// * `Ok(())` - Indexes created successfully
-    /// * `Err(String)` - Error if index creation fails
-    pub async fn migrate_add_performance_indexes(&self) -> Result<(), String> {
-        log::info!("[Migration] Adding performance indexes");
+    /// * `Ok(MigrationReport)` - What was (or would be) executed
+    /// * `Err(String)` - A migration failed; its transaction was rolled back
+    pub async fn migrate(&self, options: MigrationOptions) -> Result<MigrationReport, String> {
+        self.migrate_to(latest_version(), options).await
+    }
 
-        self.client().query(r"
-            -- Author filtering for notifications
-            DEFINE INDEX IF NOT EXISTS idx_message_author_unread ON message
-                COLUMNS author_type, unread, timestamp;
+    /// Migrate the schema up or down to a specific version
+    ///
+    /// # Arguments
+    /// * `target` - Version to end at (0 reverts every reversible migration)
+    /// * `options` - Dry-run and backup behaviour
+    ///
+    /// # Returns
+    /// * `Ok(MigrationReport)` - What was (or would be) executed
+    /// * `Err(String)` - Unknown target, irreversible step, or a failed migration
+    ///
+    /// # Design Note
+    /// Migrations run one transaction each, so a failure part-way leaves the
+    /// schema at the last successfully applied version.
+    pub async fn migrate_to(
+        &self,
+        target: i64,
+        options: MigrationOptions,
+    ) -> Result<MigrationReport, String> {
+        if target < 0 || target > latest_version() {
+            return Err(format!(
+                "Unknown schema version {} (latest is {})",
+                target,
+                latest_version()
+            ));
+        }
 
-            -- Bookmark existence check optimization  
-            DEFINE INDEX IF NOT EXISTS idx_bookmark_lookup ON bookmark
-                COLUMNS user_id, message_id;
+        let current = self.get_schema_version().await?;
+        let checksum_mismatches = self.verify_checksums(options.dry_run).await?;
 
-            -- Reaction grouping optimization
-            DEFINE INDEX IF NOT EXISTS idx_reaction_msg_emoji ON reaction
-                COLUMNS message_id, emoji;
-        ").await.map_err(|e| format!("Index creation failed: {}", e))?;
+        let mut report = MigrationReport {
+            from_version: current,
+            to_version: current,
+            checksum_mismatches,
+            dry_run: options.dry_run,
+            ..Default::default()
+        };
+
+        let pending_up: Vec<&Migration> = MIGRATIONS
+            .iter()
+            .filter(|m| m.version > current && m.version <= target)
+            .collect();
+        let pending_down: Vec<&Migration> = MIGRATIONS
+            .iter()
+            .rev()
+            .filter(|m| m.version <= current && m.version > target)
+            .collect();
+
+        if let Some(irreversible) = pending_down.iter().find(|m| m.down.is_none()) {
+            return Err(format!(
+                "Migration {} ({}) is irreversible",
+                irreversible.version, irreversible.name
+            ));
+        }
+
+        if pending_up.is_empty() && pending_down.is_empty() {
+            log::info!("[Migration] Schema up to date (version {})", current);
+            return Ok(report);
+        }
+
+        if options.dry_run {
+            report.applied = pending_up.iter().map(|m| m.version).collect();
+            report.reverted = pending_down.iter().map(|m| m.version).collect();
+            report.to_version = target;
+            log::info!(
+                "[Migration] Dry run: {} → {} (up: {:?}, down: {:?})",
+                current,
+                target,
+                report.applied,
+                report.reverted
+            );
+            return Ok(report);
+        }
+
+        if options.backup
+            && let Some(path) = self.data_path()
+        {
+            report.backup_path = Some(self.export_backup(path, current).await?);
+        }
+
+        for migration in pending_up {
+            log::info!(
+                "[Migration] Applying {}: {}",
+                migration.version,
+                migration.name
+            );
+            self.apply_up(migration).await?;
+            report.applied.push(migration.version);
+            report.to_version = migration.version;
+        }
+
+        for migration in pending_down {
+            log::info!(
+                "[Migration] Reverting {}: {}",
+                migration.version,
+                migration.name
+            );
+            self.apply_down(migration).await?;
+            report.reverted.push(migration.version);
+            report.to_version = migration.version - 1;
+        }
+
+        log::info!(
+            "[Migration] Schema migrated {} → {}",
+            report.from_version,
+            report.to_version
+        );
+
+        Ok(report)
+    }
+
+    /// Define the [`BASELINE`] tables if the database is still at version 0
+    ///
+    /// Versions above 0 already went through migration 1, which cannot be
+    /// reverted, so their schema is left to the registry.
+    pub(super) async fn ensure_baseline(&self) -> Result<(), String> {
+        if self.get_schema_version().await? > 0 {
+            return Ok(());
+        }
+
+        self.client()
+            .query(BASELINE)
+            .await
+            .map_err(|e| format!("Baseline schema failed: {}", e))?
+            .check()
+            .map_err(|e| format!("Baseline schema failed: {}", e))?;
+
+        Ok(())
+    }
+
+    /// Auto-run pending migrations on startup
+    pub(super) async fn auto_migrate(&self) -> Result<(), String> {
+        let report = self.migrate(MigrationOptions::default()).await?;
+
+        if !report.checksum_mismatches.is_empty() {
+            log::warn!(
+                "[Migration] Applied migrations changed since they ran: {:?}",
+                report.checksum_mismatches
+            );
+        }
+
+        Ok(())
+    }
+
+    /// Run one migration up and record it, in a single transaction
+    async fn apply_up(&self, migration: &Migration) -> Result<(), String> {
+        let query = format!(
+            r"
+            BEGIN TRANSACTION;
+            {}
+            CREATE schema_version CONTENT {{
+                version: $version,
+                name: $name,
+                checksum: $checksum
+            }};
+            COMMIT TRANSACTION;
+            ",
+            migration.up
+        );
+
+        self.client()
+            .query(query)
+            .bind(("version", migration.version))
+            .bind(("name", migration.name.to_string()))
+            .bind(("checksum", migration.checksum()))
+            .await
+            .map_err(|e| format!("Migration {} failed: {}", migration.version, e))?
+            .check()
+            .map_err(|e| format!("Migration {} failed: {}", migration.version, e))?;
+
+        Ok(())
+    }
+
+    /// Run one migration down and remove its record, in a single transaction
+    async fn apply_down(&self, migration: &Migration) -> Result<(), String> {
+        let down = migration
+            .down
+            .ok_or_else(|| format!("Migration {} is irreversible", migration.version))?;
+
+        let query = format!(
+            r"
+            BEGIN TRANSACTION;
+            {}
+            DELETE schema_version WHERE version = $version;
+            COMMIT TRANSACTION;
+            ",
+            down
+        );
+
+        self.client()
+            .query(query)
+            .bind(("version", migration.version))
+            .await
+            .map_err(|e| format!("Revert of migration {} failed: {}", migration.version, e))?
+            .check()
+            .map_err(|e| format!("Revert of migration {} failed: {}", migration.version, e))?;
 
-        log::info!("[Migration] Performance indexes created successfully");
         Ok(())
     }
 
+    /// Compare recorded checksums against the registry
+    ///
+    /// Versions recorded before checksums existed are backfilled (unless
+    /// `read_only`). Returns the versions whose checksum no longer matches.
+    async fn verify_checksums(&self, read_only: bool) -> Result<Vec<i64>, String> {
+        let mut response = self
+            .client()
+            .query("SELECT version, checksum FROM schema_version")
+            .await
+            .map_err(|e| format!("Failed to read applied migrations: {}", e))?;
+
+        let applied: Vec<AppliedMigration> = response
+            .take(0)
+            .map_err(|e| format!("Failed to parse applied migrations: {}", e))?;
+
+        let mut mismatches = Vec::new();
+        for record in applied {
+            let Some(migration) = MIGRATIONS.iter().find(|m| m.version == record.version) else {
+                mismatches.push(record.version);
+                continue;
+            };
+
+            match record.checksum {
+                Some(checksum) if checksum != migration.checksum() => {
+                    mismatches.push(record.version);
+                }
+                Some(_) => {}
+                None if !read_only => {
+                    self.client()
+                        .query(
+                            "UPDATE schema_version SET checksum = $checksum, name = $name
+                             WHERE version = $version AND checksum = NONE",
+                        )
+                        .bind(("version", migration.version))
+                        .bind(("name", migration.name.to_string()))
+                        .bind(("checksum", migration.checksum()))
+                        .await
+                        .map_err(|e| format!("Failed to backfill checksum: {}", e))?;
+                }
+                None => {}
+            }
+        }
+
+        Ok(mismatches)
+    }
+
+    /// Export the database to a timestamped sibling of its data directory
+    ///
+    /// The export goes through the open handle, so it is a consistent
+    /// snapshot rather than a copy of SurrealKV files that are being written.
+    ///
+    /// # Returns
+    /// * `Ok(PathBuf)` - Path of the `.surql` export
+    /// * `Err(String)` - Export failed; migration must not proceed
+    async fn export_backup(&self, path: &Path, version: i64) -> Result<PathBuf, String> {
+        let file_name = path
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_else(|| "chat.db".to_string());
+        let backup_path = path.with_file_name(format!(
+            "{}.pre-migration-v{}-{}.surql",
+            file_name,
+            version,
+            chrono::Utc::now().format("%Y%m%d%H%M%S")
+        ));
 
+        self.client()
+            .export(&backup_path)
+            .await
+            .map_err(|e| format!("Failed to back up database before migrating: {}", e))?;
+
+        log::info!("[Migration] Backed up database to {}", backup_path.display());
+        Ok(backup_path)
+    }
 }
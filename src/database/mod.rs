@@ -2,44 +2,99 @@
 //!
 //! Uses forked SurrealDB with SurrealKV embedded storage engine.
 //! Database file: ~/.local/share/cyrup/chat.db (macOS: ~/Library/Application Support/cyrup/chat.db)
+//! Each profile uses its own SurrealDB database inside the `cyrup` namespace.
 
 use surrealdb::{
     Surreal,
-    engine::local::{Db, SurrealKv},
+    engine::local::{Db, Mem, SurrealKv},
     opt::{capabilities::{Capabilities, ExperimentalFeature}, Config},
 };
-use surrealdb_types::SurrealValue;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
 
 // Module declarations for database operations (created in later tasks)
+pub mod auth_events;
+pub mod backup;
 pub mod bookmarks;
 pub mod conversations;
+pub mod integrity;
+pub mod mcp_servers;
 pub mod messages;
 pub mod migration;
+pub mod notifications;
 pub mod reactions;
+pub mod schedules;
+pub mod snippets;
 pub mod templates;
+pub mod transcripts;
 
 // Re-export schema initialization
 pub mod schema;
 pub use schema::init_schema;
 
+// Re-export backup and integrity APIs
+pub use backup::{BackupConfig, spawn_backup_schedule};
+pub use integrity::IntegrityReport;
+
+// Re-export migration framework
+pub use migration::{MIGRATIONS, Migration, MigrationOptions, MigrationReport};
+
 // Re-export token budget configuration
 pub use crate::view_model::TokenBudgetConfig;
 
+/// How long [`Database::connect`] waits for another process to release the store
+///
+/// A process relaunched for a profile switch can start before the old one
+/// has closed the SurrealKV directory.
+const LOCK_WAIT: Duration = Duration::from_secs(10);
+
+/// Pause between attempts to open a store that is still locked
+const LOCK_RETRY: Duration = Duration::from_millis(100);
+
+/// File-backed databases with at least one live handle
+static OPEN_FILES: AtomicUsize = AtomicUsize::new(0);
+
+/// Counts a file-backed database as open until its last clone is dropped
+struct OpenFile;
+
+impl OpenFile {
+    fn new() -> Arc<Self> {
+        OPEN_FILES.fetch_add(1, Ordering::SeqCst);
+        Arc::new(Self)
+    }
+}
+
+impl Drop for OpenFile {
+    fn drop(&mut self) {
+        OPEN_FILES.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
 /// Database connection wrapper for SurrealKV embedded database
 #[derive(Clone)]
 pub struct Database {
     client: Surreal<Db>,
-    token_budget_config: crate::view_model::TokenBudgetConfig,
+    /// Shared by clones, so an edit applies to every holder of the database
+    token_budget_config: Arc<RwLock<crate::view_model::TokenBudgetConfig>>,
+    /// On-disk SurrealKV directory (used for pre-migration backups)
+    data_path: Option<PathBuf>,
+    /// Held by every clone of a file-backed database
+    _open_file: Option<Arc<OpenFile>>,
 }
 
 impl Database {
-    /// Create new database connection with SurrealKV engine
+    /// Create new database connection for the active profile
     ///
     /// # Platform-specific paths
     /// - macOS: `~/Library/Application Support/cyrup/chat.db`
     /// - Linux: `~/.local/share/cyrup/chat.db`
     /// - Windows: `%LOCALAPPDATA%\cyrup\chat.db`
     ///
+    /// `CYRUP_DATA_DIR` replaces the `cyrup` directory. The profile selects the
+    /// SurrealDB database and supplies a saved token budget, if any.
+    ///
     /// # Errors
     /// Returns error if:
     /// - Cannot determine data directory
@@ -47,28 +102,107 @@ impl Database {
     /// - Database connection fails
     /// - Authentication fails
     pub async fn new() -> Result<Self, String> {
-        // Get platform-specific data directory using `dirs` crate
-        let data_dir = dirs::data_local_dir()
-            .ok_or("Could not determine data directory")?
-            .join("cyrup");
+        let profile = crate::config::profile::active_profile()?;
 
         // Create directory if it doesn't exist
-        tokio::fs::create_dir_all(&data_dir)
+        tokio::fs::create_dir_all(profile.base_dir())
             .await
             .map_err(|e| format!("Failed to create data directory: {}", e))?;
 
-        let db_path = data_dir.join("chat.db");
+        let db = Self::open(profile.database_path(), &profile.database_name()).await?;
 
-        // Enable experimental features (record references) for schema constraints
-        let capabilities = Capabilities::new()
-            .with_experimental_feature_allowed(ExperimentalFeature::RecordReferences);
-        let config = Config::new().capabilities(capabilities);
+        if let Some(config) = profile.load_token_budget().await {
+            db.set_token_budget_config(config);
+        }
+
+        Ok(db)
+    }
+
+    /// Open a database at a specific path and run pending migrations
+    ///
+    /// # Arguments
+    /// * `db_path` - SurrealKV directory
+    /// * `database` - SurrealDB database name within the `cyrup` namespace
+    ///
+    /// # Errors
+    /// Returns error if the connection, schema initialization or a migration fails
+    pub async fn open(db_path: PathBuf, database: &str) -> Result<Self, String> {
+        let db = Self::connect(db_path, database).await?;
 
-        // IMPORTANT: Use SurrealKv (not RocksDb) from forked SurrealDB
-        let client = Surreal::new::<SurrealKv>((db_path, config))
+        // Auto-run migrations if needed
+        db.auto_migrate().await?;
+
+        Ok(db)
+    }
+
+    /// Open the database at a specific path without running migrations
+    ///
+    /// Used by migration tooling and tests that need to control the schema
+    /// version explicitly. Application code should use [`Database::open`].
+    ///
+    /// Waits up to [`LOCK_WAIT`] for a process that still holds the store
+    /// (the one being replaced after a profile switch) to release it.
+    ///
+    /// # Errors
+    /// Returns error if the connection or schema initialization fails
+    pub async fn connect(db_path: PathBuf, database: &str) -> Result<Self, String> {
+        let deadline = tokio::time::Instant::now() + LOCK_WAIT;
+        let client = loop {
+            // IMPORTANT: Use SurrealKv (not RocksDb) from forked SurrealDB
+            match Surreal::new::<SurrealKv>((db_path.clone(), Self::engine_config())).await {
+                Ok(client) => break client,
+                Err(e) if tokio::time::Instant::now() < deadline => {
+                    log::debug!("Database at {} not available yet: {}", db_path.display(), e);
+                    tokio::time::sleep(LOCK_RETRY).await;
+                }
+                Err(e) => return Err(format!("Database connection failed: {}", e)),
+            }
+        };
+
+        let mut db = Self::select_and_init(client, database, Some(db_path)).await?;
+        db._open_file = Some(OpenFile::new());
+        Ok(db)
+    }
+
+    /// Number of file-backed databases this process still holds open
+    ///
+    /// Zero once every clone of every handle has been dropped.
+    pub fn open_files() -> usize {
+        OPEN_FILES.load(Ordering::SeqCst)
+    }
+
+    /// Open a fresh, fully migrated database held entirely in memory
+    ///
+    /// Nothing is written to disk and the data is dropped with the last
+    /// clone of the returned handle. Used by tests and throwaway sessions.
+    ///
+    /// # Errors
+    /// Returns error if the engine, schema initialization or a migration fails
+    pub async fn in_memory() -> Result<Self, String> {
+        let client = Surreal::new::<Mem>(Self::engine_config())
             .await
             .map_err(|e| format!("Database connection failed: {}", e))?;
 
+        let db = Self::select_and_init(client, "chat", None).await?;
+        db.auto_migrate().await?;
+
+        Ok(db)
+    }
+
+    /// Engine configuration shared by the file-backed and in-memory engines
+    fn engine_config() -> Config {
+        // Enable experimental features (record references) for schema constraints
+        let capabilities = Capabilities::new()
+            .with_experimental_feature_allowed(ExperimentalFeature::RecordReferences);
+        Config::new().capabilities(capabilities)
+    }
+
+    /// Select the namespace/database and initialize the schema
+    async fn select_and_init(
+        client: Surreal<Db>,
+        database: &str,
+        data_path: Option<PathBuf>,
+    ) -> Result<Self, String> {
         // NOTE: Embedded databases (SurrealKV, RocksDB, Mem) don't support authentication
         // Authentication is only for remote SurrealDB server connections
         // For local embedded databases, skip signin and go directly to namespace/database selection
@@ -76,20 +210,20 @@ impl Database {
         // Select namespace and database
         client
             .use_ns("cyrup")
-            .use_db("chat")
+            .use_db(database)
             .await
             .map_err(|e| format!("Database selection failed: {}", e))?;
 
         let db = Self {
             client,
-            token_budget_config: crate::view_model::TokenBudgetConfig::default(),
+            token_budget_config: Arc::default(),
+            data_path,
+            _open_file: None,
         };
 
-        // Initialize schema (safe to call multiple times)
+        // Bootstrap schema_version, then the version 0 tables if nothing ran yet
         init_schema(db.client()).await?;
-
-        // Auto-run migrations if needed
-        db.auto_migrate().await?;
+        db.ensure_baseline().await?;
 
         Ok(db)
     }
@@ -99,9 +233,17 @@ impl Database {
         &self.client
     }
 
-    /// Get reference to token budget configuration
-    pub fn token_budget_config(&self) -> &crate::view_model::TokenBudgetConfig {
-        &self.token_budget_config
+    /// On-disk location of the database, if it is file-backed
+    pub fn data_path(&self) -> Option<&Path> {
+        self.data_path.as_deref()
+    }
+
+    /// Get the token budget configuration
+    pub fn token_budget_config(&self) -> crate::view_model::TokenBudgetConfig {
+        self.token_budget_config
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone()
     }
 
     /// Update token budget configuration
@@ -111,63 +253,10 @@ impl Database {
     ///
     /// # Design Note
     /// Allows runtime tuning of token budget parameters without recompilation
-    pub fn set_token_budget_config(&mut self, config: crate::view_model::TokenBudgetConfig) {
-        self.token_budget_config = config;
-    }
-
-    /// Get current schema version (0 if no version set)
-    async fn get_schema_version(&self) -> Result<i64, String> {
-        let query = "SELECT version FROM schema_version ORDER BY applied_at DESC LIMIT 1";
-
-        let mut response = self.client()
-            .query(query)
-            .await
-            .map_err(|e| format!("Failed to get schema version: {}", e))?;
-
-        #[derive(serde::Deserialize, surrealdb_types::SurrealValue)]
-        struct VersionRecord {
-            version: i64,
-        }
-
-        let versions: Vec<VersionRecord> = response
-            .take(0)
-            .unwrap_or_else(|_| Vec::new());
-
-        Ok(versions.first().map(|v| v.version).unwrap_or(0))
-    }
-
-    /// Set schema version after successful migration
-    async fn set_schema_version(&self, version: i64) -> Result<(), String> {
-        let query = "CREATE schema_version CONTENT { version: $version }";
-
-        self.client()
-            .query(query)
-            .bind(("version", version))
-            .await
-            .map_err(|e| format!("Failed to set schema version: {}", e))?;
-
-        Ok(())
-    }
-
-    /// Auto-run migrations based on schema version
-    async fn auto_migrate(&self) -> Result<(), String> {
-        let current_version = self.get_schema_version().await.unwrap_or(0);
-
-        log::info!("[Database] Current schema version: {}", current_version);
-
-        // Migration 1: Unify conversations and rooms
-        if current_version < 1 {
-            log::info!("[Database] Running migration 1: Unify conversations/rooms");
-
-            let (convos, rooms) = self.migrate_to_unified_conversations().await?;
-
-            log::info!("[Database] Migrated {} conversations, {} rooms", convos, rooms);
-
-            self.set_schema_version(1).await?;
-        } else {
-            log::info!("[Database] Schema up to date (version {})", current_version);
-        }
-
-        Ok(())
+    pub fn set_token_budget_config(&self, config: crate::view_model::TokenBudgetConfig) {
+        *self
+            .token_budget_config
+            .write()
+            .unwrap_or_else(|e| e.into_inner()) = config;
     }
 }
@@ -0,0 +1,211 @@
+//! Notification preferences and delivery receipts
+//!
+//! Aligns with src/database/schema.rs notification_preference,
+//! notification_settings and notification_receipt tables.
+//!
+//! Receipts are keyed by message with a UNIQUE index, so claiming a receipt
+//! before delivery is what suppresses duplicate notifications for the same
+//! agent reply (for example when a stream is replayed or flushed twice).
+
+use super::Database;
+use crate::notifications::policy::{NotificationPolicy, QuietHours};
+use chrono::{NaiveTime, Timelike};
+use serde::Deserialize;
+use surrealdb_types::{RecordId, SurrealValue};
+
+impl Database {
+    /// Get the notification policy for a conversation
+    ///
+    /// # Returns
+    /// * `Ok(NotificationPolicy)` - Stored policy, or the default if none was set
+    /// * `Err(String)` - Query failed
+    pub async fn get_notification_policy(
+        &self,
+        conversation_id: &RecordId,
+    ) -> Result<NotificationPolicy, String> {
+        let query = r"
+            SELECT VALUE policy
+            FROM notification_preference
+            WHERE conversation_id = $conversation_id
+            LIMIT 1
+        ";
+
+        let mut response = self
+            .client()
+            .query(query)
+            .bind(("conversation_id", conversation_id.clone()))
+            .await
+            .map_err(|e| format!("Failed to get notification policy: {}", e))?;
+
+        let policies: Vec<String> = response
+            .take(0)
+            .map_err(|e| format!("Failed to parse notification policy: {}", e))?;
+
+        Ok(policies
+            .first()
+            .map(|key| NotificationPolicy::from_key(key))
+            .unwrap_or_default())
+    }
+
+    /// Set the notification policy for a conversation
+    ///
+    /// # Database Operation
+    /// Upserts the conversation's notification_preference record. The record
+    /// is removed with the conversation (REFERENCE ON DELETE CASCADE).
+    pub async fn set_notification_policy(
+        &self,
+        conversation_id: &RecordId,
+        policy: NotificationPolicy,
+    ) -> Result<(), String> {
+        let query = r"
+            LET $existing = (
+                SELECT VALUE id FROM notification_preference
+                WHERE conversation_id = $conversation_id
+                LIMIT 1
+            )[0];
+            IF $existing {
+                UPDATE $existing SET policy = $policy;
+            } ELSE {
+                CREATE notification_preference CONTENT {
+                    conversation_id: $conversation_id,
+                    policy: $policy
+                };
+            };
+        ";
+
+        self.client()
+            .query(query)
+            .bind(("conversation_id", conversation_id.clone()))
+            .bind(("policy", policy.as_str().to_string()))
+            .await
+            .map_err(|e| format!("Failed to set notification policy: {}", e))?
+            .check()
+            .map_err(|e| format!("Failed to set notification policy: {}", e))?;
+
+        Ok(())
+    }
+
+    /// Get the global do-not-disturb window, if enabled
+    pub async fn get_quiet_hours(&self) -> Result<Option<QuietHours>, String> {
+        #[derive(Deserialize, SurrealValue)]
+        struct QuietHoursRecord {
+            quiet_start_minute: Option<u32>,
+            quiet_end_minute: Option<u32>,
+        }
+
+        let record: Option<QuietHoursRecord> = self
+            .client()
+            .select(("notification_settings", "global"))
+            .await
+            .map_err(|e| format!("Failed to get quiet hours: {}", e))?;
+
+        let Some(QuietHoursRecord {
+            quiet_start_minute: Some(start),
+            quiet_end_minute: Some(end),
+        }) = record
+        else {
+            return Ok(None);
+        };
+
+        Ok(Some(QuietHours {
+            start: time_from_minute(start),
+            end: time_from_minute(end),
+        }))
+    }
+
+    /// Enable (or with `None`, disable) the global do-not-disturb window
+    pub async fn set_quiet_hours(&self, quiet_hours: Option<QuietHours>) -> Result<(), String> {
+        let query = r"
+            UPSERT notification_settings:global
+            SET quiet_start_minute = $start, quiet_end_minute = $end
+        ";
+
+        self.client()
+            .query(query)
+            .bind(("start", quiet_hours.map(|q| minute_of_day(q.start))))
+            .bind(("end", quiet_hours.map(|q| minute_of_day(q.end))))
+            .await
+            .map_err(|e| format!("Failed to set quiet hours: {}", e))?;
+
+        Ok(())
+    }
+
+    /// Claim the notification for an agent reply
+    ///
+    /// # Returns
+    /// * `Ok(true)` - No notification was sent for this message yet; caller should deliver
+    /// * `Ok(false)` - Already claimed; the notification is a duplicate
+    /// * `Err(String)` - Query failed
+    pub async fn claim_notification(
+        &self,
+        message_id: &RecordId,
+        conversation_id: &RecordId,
+    ) -> Result<bool, String> {
+        let result = self
+            .client()
+            .query(
+                "CREATE notification_receipt CONTENT {
+                    message_id: $message_id,
+                    conversation_id: $conversation_id
+                }",
+            )
+            .bind(("message_id", message_id.clone()))
+            .bind(("conversation_id", conversation_id.clone()))
+            .await
+            .map_err(|e| format!("Failed to claim notification: {}", e))?
+            .check();
+
+        match result {
+            Ok(_) => Ok(true),
+            Err(e) => {
+                let error_msg = e.to_string();
+                if error_msg.contains("already contains") || error_msg.contains("unique") {
+                    Ok(false)
+                } else {
+                    Err(format!("Failed to claim notification: {}", error_msg))
+                }
+            }
+        }
+    }
+
+    /// Record the platform delivery of a claimed notification
+    pub async fn complete_notification_receipt(
+        &self,
+        message_id: &RecordId,
+        platform: &str,
+    ) -> Result<(), String> {
+        let query = r"
+            UPDATE notification_receipt
+            SET platform = $platform, delivered_at = time::now()
+            WHERE message_id = $message_id
+        ";
+
+        self.client()
+            .query(query)
+            .bind(("message_id", message_id.clone()))
+            .bind(("platform", platform.to_string()))
+            .await
+            .map_err(|e| format!("Failed to record notification receipt: {}", e))?;
+
+        Ok(())
+    }
+
+    /// Release a claim after a failed delivery so a later attempt can retry
+    pub async fn release_notification(&self, message_id: &RecordId) -> Result<(), String> {
+        self.client()
+            .query("DELETE notification_receipt WHERE message_id = $message_id")
+            .bind(("message_id", message_id.clone()))
+            .await
+            .map_err(|e| format!("Failed to release notification claim: {}", e))?;
+
+        Ok(())
+    }
+}
+
+fn minute_of_day(time: NaiveTime) -> u32 {
+    time.hour() * 60 + time.minute()
+}
+
+fn time_from_minute(minute: u32) -> NaiveTime {
+    NaiveTime::from_hms_opt((minute / 60) % 24, minute % 60, 0).unwrap_or(NaiveTime::MIN)
+}
@@ -0,0 +1,237 @@
+//! Scheduled prompt database operations
+//!
+//! Provides CRUD and due-run queries for the schedule table defined in
+//! src/database/schema.rs. `next_run_at` is always computed here from the
+//! trigger, so callers never persist a stale or hand-written run time.
+
+use super::Database;
+use crate::view_model::schedule::{Schedule, ScheduleTrigger};
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use surrealdb_types::{Datetime, RecordId, SurrealValue, ToSql};
+
+impl Database {
+    /// Create a new schedule
+    ///
+    /// # Arguments
+    /// * `schedule` - Schedule to create (id, next_run_at and run history are ignored)
+    ///
+    /// # Returns
+    /// * `Ok(RecordId)` - Database-generated schedule ID
+    /// * `Err(String)` - Invalid trigger, empty prompt, or insert failure
+    pub async fn create_schedule(&self, schedule: &Schedule) -> Result<RecordId, String> {
+        let next_run_at = first_run(schedule, Utc::now())?;
+
+        #[derive(Serialize, SurrealValue)]
+        struct ScheduleInsert {
+            conversation_id: RecordId,
+            agent_id: Option<RecordId>,
+            prompt: String,
+            cron: Option<String>,
+            interval_secs: Option<i64>,
+            paused: bool,
+            next_run_at: Datetime,
+        }
+
+        let result: Option<Schedule> = self
+            .client()
+            .create("schedule")
+            .content(ScheduleInsert {
+                conversation_id: schedule.conversation_id.clone(),
+                agent_id: schedule.agent_id.clone(),
+                prompt: schedule.prompt.clone(),
+                cron: schedule.cron.clone(),
+                interval_secs: schedule.interval_secs,
+                paused: schedule.paused,
+                next_run_at: next_run_at.into(),
+            })
+            .await
+            .map_err(|e| format!("Failed to create schedule: {}", e))?;
+
+        result
+            .map(|s| s.id)
+            .ok_or_else(|| "Create returned empty result".to_string())
+    }
+
+    /// Retrieve a single schedule by ID
+    pub async fn get_schedule(&self, id: &RecordId) -> Result<Schedule, String> {
+        let schedule: Option<Schedule> = self
+            .client()
+            .select(id)
+            .await
+            .map_err(|e| format!("Failed to get schedule: {}", e))?;
+
+        schedule.ok_or_else(|| format!("Schedule not found: {}", id.to_sql()))
+    }
+
+    /// List all schedules, oldest first
+    pub async fn list_schedules(&self) -> Result<Vec<Schedule>, String> {
+        let mut response = self
+            .client()
+            .query("SELECT * FROM schedule ORDER BY created_at ASC")
+            .await
+            .map_err(|e| format!("Failed to list schedules: {}", e))?;
+
+        response
+            .take(0)
+            .map_err(|e| format!("Failed to parse schedules: {}", e))
+    }
+
+    /// Update a schedule's prompt, target and trigger
+    ///
+    /// # Database Operation
+    /// Replaces the editable fields and recomputes `next_run_at` from now, so
+    /// an edited trigger takes effect immediately. Run history is kept.
+    pub async fn update_schedule(&self, schedule: &Schedule) -> Result<(), String> {
+        let next_run_at = first_run(schedule, Utc::now())?;
+
+        let query = r"
+            UPDATE $id SET
+                conversation_id = $conversation_id,
+                agent_id = $agent_id,
+                prompt = $prompt,
+                cron = $cron,
+                interval_secs = $interval_secs,
+                paused = $paused,
+                next_run_at = $next_run_at
+        ";
+
+        let mut response = self
+            .client()
+            .query(query)
+            .bind(("id", schedule.id.clone()))
+            .bind(("conversation_id", schedule.conversation_id.clone()))
+            .bind(("agent_id", schedule.agent_id.clone()))
+            .bind(("prompt", schedule.prompt.clone()))
+            .bind(("cron", schedule.cron.clone()))
+            .bind(("interval_secs", schedule.interval_secs))
+            .bind(("paused", schedule.paused))
+            .bind(("next_run_at", Datetime::from(next_run_at)))
+            .await
+            .map_err(|e| format!("Failed to update schedule: {}", e))?;
+
+        let updated: Vec<Schedule> = response
+            .take(0)
+            .map_err(|e| format!("Failed to update schedule: {}", e))?;
+
+        if updated.is_empty() {
+            return Err(format!("Schedule not found: {}", schedule.id.to_sql()));
+        }
+        Ok(())
+    }
+
+    /// Pause or resume a schedule
+    ///
+    /// Resuming reschedules from now, so runs missed while paused are skipped
+    /// rather than caught up.
+    pub async fn set_schedule_paused(&self, id: &RecordId, paused: bool) -> Result<(), String> {
+        let mut schedule = self.get_schedule(id).await?;
+        schedule.paused = paused;
+        self.update_schedule(&schedule).await
+    }
+
+    /// Delete a schedule
+    pub async fn delete_schedule(&self, id: &RecordId) -> Result<(), String> {
+        let deleted: Option<Schedule> = self
+            .client()
+            .delete(id)
+            .await
+            .map_err(|e| format!("Failed to delete schedule: {}", e))?;
+
+        deleted
+            .map(|_| ())
+            .ok_or_else(|| format!("Schedule not found: {}", id.to_sql()))
+    }
+
+    /// Active schedules whose next run is at or before `now`
+    ///
+    /// # Database Operation
+    /// Uses idx_schedule_due (paused, next_run_at).
+    pub async fn due_schedules(&self, now: DateTime<Utc>) -> Result<Vec<Schedule>, String> {
+        let query = r"
+            SELECT * FROM schedule
+            WHERE paused = false AND next_run_at <= $now
+            ORDER BY next_run_at ASC
+        ";
+
+        let mut response = self
+            .client()
+            .query(query)
+            .bind(("now", Datetime::from(now)))
+            .await
+            .map_err(|e| format!("Failed to query due schedules: {}", e))?;
+
+        response
+            .take(0)
+            .map_err(|e| format!("Failed to parse due schedules: {}", e))
+    }
+
+    /// Record that a run started and advance the schedule
+    ///
+    /// `next_run_at` is computed from `ran_at`, so any number of missed runs
+    /// collapse into the one being recorded. A schedule with no future run
+    /// (a cron expression that can never fire again) is paused instead.
+    pub async fn record_schedule_run(
+        &self,
+        schedule: &Schedule,
+        ran_at: DateTime<Utc>,
+    ) -> Result<(), String> {
+        let next_run_at = schedule
+            .trigger()
+            .and_then(|trigger| trigger.next_after(ran_at));
+
+        let query = r"
+            UPDATE $id SET
+                last_run_at = $ran_at,
+                next_run_at = $next_run_at ?? next_run_at,
+                paused = $paused OR paused
+        ";
+
+        self.client()
+            .query(query)
+            .bind(("id", schedule.id.clone()))
+            .bind(("ran_at", Datetime::from(ran_at)))
+            .bind(("next_run_at", next_run_at.map(Datetime::from)))
+            .bind(("paused", next_run_at.is_none()))
+            .await
+            .map_err(|e| format!("Failed to record schedule run: {}", e))?
+            .check()
+            .map_err(|e| format!("Failed to record schedule run: {}", e))?;
+
+        Ok(())
+    }
+
+    /// Store the outcome of the latest run (`None` clears a previous error)
+    pub async fn set_schedule_error(
+        &self,
+        id: &RecordId,
+        error: Option<String>,
+    ) -> Result<(), String> {
+        self.client()
+            .query("UPDATE $id SET last_error = $error")
+            .bind(("id", id.clone()))
+            .bind(("error", error))
+            .await
+            .map_err(|e| format!("Failed to record schedule result: {}", e))?
+            .check()
+            .map_err(|e| format!("Failed to record schedule result: {}", e))?;
+
+        Ok(())
+    }
+}
+
+/// Validate a schedule and compute its first run after `now`
+fn first_run(schedule: &Schedule, now: DateTime<Utc>) -> Result<DateTime<Utc>, String> {
+    if schedule.prompt.trim().is_empty() {
+        return Err("Schedule prompt cannot be empty".to_string());
+    }
+
+    let trigger: ScheduleTrigger = schedule
+        .trigger()
+        .ok_or_else(|| "Schedule needs a cron expression or an interval".to_string())?;
+    trigger.validate()?;
+
+    trigger
+        .next_after(now)
+        .ok_or_else(|| "Schedule never fires".to_string())
+}
@@ -0,0 +1,134 @@
+//! Prompt snippet database operations
+//!
+//! Provides CRUD operations for the prompt_snippet table defined in
+//! src/database/schema.rs. Snippet names are unique and double as their
+//! slash command (`/name`).
+
+use super::Database;
+use crate::view_model::snippet::{PromptSnippet, is_param_name};
+use serde::Serialize;
+use surrealdb_types::{RecordId, SurrealValue, ToSql};
+
+impl Database {
+    /// Create a prompt snippet
+    ///
+    /// # Returns
+    /// * `Ok(RecordId)` - Database-generated snippet ID
+    /// * `Err(String)` - Invalid or duplicate name, empty body, or insert failure
+    pub async fn create_snippet(&self, snippet: &PromptSnippet) -> Result<RecordId, String> {
+        validate_snippet(snippet)?;
+
+        #[derive(Serialize, SurrealValue)]
+        struct SnippetInsert {
+            name: String,
+            description: String,
+            body: String,
+        }
+
+        let result: Result<Option<PromptSnippet>, _> = self
+            .client()
+            .create("prompt_snippet")
+            .content(SnippetInsert {
+                name: snippet.name.clone(),
+                description: snippet.description.clone(),
+                body: snippet.body.clone(),
+            })
+            .await;
+
+        match result {
+            Ok(created) => created
+                .map(|s| s.id)
+                .ok_or_else(|| "Create returned empty result".to_string()),
+            Err(e) if e.to_string().contains("already contains") => {
+                Err(format!("A snippet named /{} already exists", snippet.name))
+            }
+            Err(e) => Err(format!("Failed to create snippet: {}", e)),
+        }
+    }
+
+    /// Look up a snippet by its command name
+    pub async fn get_snippet_by_name(&self, name: &str) -> Result<Option<PromptSnippet>, String> {
+        let mut response = self
+            .client()
+            .query("SELECT * FROM prompt_snippet WHERE name = $name LIMIT 1")
+            .bind(("name", name.to_string()))
+            .await
+            .map_err(|e| format!("Failed to get snippet: {}", e))?;
+
+        let mut snippets: Vec<PromptSnippet> = response
+            .take(0)
+            .map_err(|e| format!("Failed to parse snippet: {}", e))?;
+
+        Ok(snippets.pop())
+    }
+
+    /// List all snippets ordered by name
+    pub async fn list_snippets(&self) -> Result<Vec<PromptSnippet>, String> {
+        let mut response = self
+            .client()
+            .query("SELECT * FROM prompt_snippet ORDER BY name ASC")
+            .await
+            .map_err(|e| format!("Failed to list snippets: {}", e))?;
+
+        response
+            .take(0)
+            .map_err(|e| format!("Failed to parse snippets: {}", e))
+    }
+
+    /// Replace a snippet's name, description and body
+    pub async fn update_snippet(&self, snippet: &PromptSnippet) -> Result<(), String> {
+        validate_snippet(snippet)?;
+
+        let mut response = self
+            .client()
+            .query("UPDATE $id SET name = $name, description = $description, body = $body")
+            .bind(("id", snippet.id.clone()))
+            .bind(("name", snippet.name.clone()))
+            .bind(("description", snippet.description.clone()))
+            .bind(("body", snippet.body.clone()))
+            .await
+            .map_err(|e| format!("Failed to update snippet: {}", e))?;
+
+        let updated: Vec<PromptSnippet> = response
+            .take(0)
+            .map_err(|e| format!("Failed to update snippet: {}", e))?;
+
+        if updated.is_empty() {
+            return Err(format!("Snippet not found: {}", snippet.id.to_sql()));
+        }
+        Ok(())
+    }
+
+    /// Delete a snippet by name
+    ///
+    /// # Returns
+    /// * `Ok(true)` - Snippet deleted
+    /// * `Ok(false)` - No snippet with that name
+    pub async fn delete_snippet(&self, name: &str) -> Result<bool, String> {
+        let mut response = self
+            .client()
+            .query("DELETE prompt_snippet WHERE name = $name RETURN BEFORE")
+            .bind(("name", name.to_string()))
+            .await
+            .map_err(|e| format!("Failed to delete snippet: {}", e))?;
+
+        let deleted: Vec<PromptSnippet> = response
+            .take(0)
+            .map_err(|e| format!("Failed to delete snippet: {}", e))?;
+
+        Ok(!deleted.is_empty())
+    }
+}
+
+fn validate_snippet(snippet: &PromptSnippet) -> Result<(), String> {
+    if !is_param_name(&snippet.name) {
+        return Err(format!(
+            "Invalid snippet name '{}': use letters, digits, '-' or '_'",
+            snippet.name
+        ));
+    }
+    if snippet.body.trim().is_empty() {
+        return Err("Snippet body cannot be empty".to_string());
+    }
+    Ok(())
+}
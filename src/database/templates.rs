@@ -189,4 +189,32 @@ impl Database {
     pub async fn list_agent_templates(&self) -> Result<Vec<AgentTemplate>, String> {
         self.list_templates().await
     }
+
+    /// Find a template by name (case-insensitive) or record ID
+    ///
+    /// # Arguments
+    /// * `name_or_id` - Template name, `@name`, `agent_template:key` or bare key
+    ///
+    /// # Returns
+    /// * `Ok(AgentTemplate)` - First template whose name or ID matches
+    /// * `Err(String)` - No match, or the query failed
+    pub async fn find_template(&self, name_or_id: &str) -> Result<AgentTemplate, String> {
+        let needle = name_or_id.trim().trim_start_matches('@');
+        let templates = self.list_templates().await?;
+
+        let by_id = |t: &AgentTemplate| {
+            let id = t.id.to_sql();
+            let key = id
+                .split_once(':')
+                .map(|(_, key)| key.trim_matches(['⟨', '⟩', '`']));
+            id == needle || key == Some(needle)
+        };
+
+        templates
+            .iter()
+            .position(|t| t.name.eq_ignore_ascii_case(needle))
+            .or_else(|| templates.iter().position(by_id))
+            .map(|i| templates[i].clone())
+            .ok_or_else(|| format!("Template not found: {}", needle))
+    }
 }
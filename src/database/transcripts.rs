@@ -0,0 +1,146 @@
+//! Transcript export and import
+//!
+//! Converts between stored conversations and the portable
+//! [`Transcript`] format in src/view_model/transcript.rs.
+
+use super::Database;
+use crate::view_model::agent::AgentTemplate;
+use crate::view_model::conversation::Conversation;
+use crate::view_model::message::Message;
+use crate::view_model::transcript::{
+    TRANSCRIPT_VERSION, Transcript, TranscriptAgent, TranscriptMessage,
+};
+use chrono::Utc;
+use std::collections::HashMap;
+use surrealdb_types::RecordId;
+
+impl Database {
+    /// Export a conversation with its messages and agents
+    ///
+    /// Soft-deleted messages are left out.
+    pub async fn export_transcript(
+        &self,
+        conversation_id: &RecordId,
+    ) -> Result<Transcript, String> {
+        let conversation = self.get_conversation(conversation_id).await?;
+
+        let mut agents = Vec::with_capacity(conversation.participants.len());
+        for id in &conversation.participants {
+            let template = self.get_template(id).await?;
+            agents.push(TranscriptAgent {
+                name: template.name,
+                system_prompt: template.system_prompt,
+                model: template.model,
+                max_turns: template.max_turns,
+            });
+        }
+
+        let messages = self
+            .get_all_messages(conversation_id)
+            .await?
+            .into_iter()
+            .filter(|m| !m.deleted)
+            .map(|m| TranscriptMessage {
+                author: m.author,
+                author_type: m.author_type,
+                message_type: m.message_type,
+                content: m.content,
+                timestamp: *m.timestamp,
+                pinned: m.pinned,
+            })
+            .collect();
+
+        Ok(Transcript {
+            version: TRANSCRIPT_VERSION,
+            title: conversation.title,
+            summary: conversation.summary,
+            agents,
+            messages,
+        })
+    }
+
+    /// Import a transcript as a new conversation
+    ///
+    /// Agents are matched to existing templates by name; missing ones are
+    /// created from the transcript. Agent sessions are not carried over, so
+    /// agents start fresh with the imported history.
+    ///
+    /// # Returns
+    /// * `Ok(RecordId)` - ID of the new conversation
+    /// * `Err(String)` - Unsupported version, no agents, or a write failed
+    pub async fn import_transcript(&self, transcript: &Transcript) -> Result<RecordId, String> {
+        if transcript.version > TRANSCRIPT_VERSION {
+            return Err(format!(
+                "Transcript version {} is newer than supported version {}",
+                transcript.version, TRANSCRIPT_VERSION
+            ));
+        }
+        if transcript.agents.is_empty() {
+            return Err("Transcript has no agents".to_string());
+        }
+
+        let existing: HashMap<String, RecordId> = self
+            .list_templates()
+            .await?
+            .into_iter()
+            .map(|t| (t.name, t.id))
+            .collect();
+
+        let mut participants = Vec::with_capacity(transcript.agents.len());
+        for agent in &transcript.agents {
+            let id = match existing.get(&agent.name) {
+                Some(id) => id.clone(),
+                None => {
+                    log::info!("[Transcript] Creating agent template '{}'", agent.name);
+                    self.create_template(&AgentTemplate {
+                        id: RecordId::new(
+                            "agent_template",
+                            uuid::Uuid::new_v4().simple().to_string(),
+                        ),
+                        name: agent.name.clone(),
+                        system_prompt: agent.system_prompt.clone(),
+                        model: agent.model,
+                        max_turns: agent.max_turns,
+                        ..Default::default()
+                    })
+                    .await?
+                }
+            };
+            participants.push(id);
+        }
+
+        let last_message_at = transcript
+            .messages
+            .iter()
+            .map(|m| m.timestamp)
+            .max()
+            .unwrap_or_else(Utc::now);
+
+        let conversation_id = self
+            .create_conversation(&Conversation {
+                title: transcript.title.clone(),
+                summary: transcript.summary.clone(),
+                participants,
+                last_message_at: last_message_at.into(),
+                ..Default::default()
+            })
+            .await?;
+
+        for message in &transcript.messages {
+            self.insert_message(&Message {
+                conversation_id: conversation_id.clone(),
+                author: message.author.clone(),
+                author_type: message.author_type,
+                message_type: message.message_type,
+                content: message.content.clone(),
+                timestamp: message.timestamp.into(),
+                pinned: message.pinned,
+                unread: false,
+                ..Default::default()
+            })
+            .await?;
+        }
+
+        Ok(conversation_id)
+    }
+}
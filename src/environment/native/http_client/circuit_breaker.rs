@@ -39,7 +39,7 @@ pub enum CircuitBreakerError {
 
 impl From<CircuitBreakerError> for UiError {
     fn from(error: CircuitBreakerError) -> Self {
-        UiError::platform_error(&error.to_string())
+        UiError::platform_error(error.to_string())
     }
 }
 
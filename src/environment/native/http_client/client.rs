@@ -1,11 +1,17 @@
 //! HTTP client core implementation
 
-use super::{circuit_breaker::CircuitBreaker, errors::HttpClientError, stats::PoolStats};
+use super::{
+    circuit_breaker::CircuitBreaker,
+    errors::HttpClientError,
+    stats::{HostMetrics, PoolStats},
+};
 use std::collections::HashMap;
-use std::sync::{Arc, Mutex};
+use std::sync::{Arc, Mutex, OnceLock};
 use std::time::Duration;
 use tokio::sync::Semaphore;
 
+static SHARED: OnceLock<HttpClient> = OnceLock::new();
+
 /// HTTP client with connection pooling and resilience patterns
 #[derive(Clone)]
 pub struct HttpClient {
@@ -13,9 +19,13 @@ pub struct HttpClient {
     pub(super) pool_semaphore: Arc<Semaphore>,
     pub(super) circuit_breakers: Arc<Mutex<HashMap<String, CircuitBreaker>>>,
     pub(super) stats: Arc<Mutex<PoolStats>>,
+    pub(super) hosts: Arc<Mutex<HashMap<String, HostMetrics>>>,
+    pub(super) max_connections: usize,
     pub(super) max_retries: u32,
     pub(super) base_delay_ms: u64,
     pub(super) max_delay_ms: u64,
+    /// Longest `Retry-After` waited for; longer ones return the response
+    pub(super) max_retry_after: Duration,
 }
 
 impl HttpClient {
@@ -33,24 +43,36 @@ impl HttpClient {
             .build()
             .map_err(|e| HttpClientError::NetworkError(format!("Failed to create client: {e}")))?;
 
-        let stats = PoolStats {
-            active_connections: 0,
-            idle_connections: 0,
-            total_requests: 0,
-            failed_requests: 0,
-            average_response_time_ms: 0.0,
-        };
-
         Ok(Self {
             client,
             pool_semaphore: Arc::new(Semaphore::new(max_connections)),
             circuit_breakers: Arc::new(Mutex::new(HashMap::new())),
-            stats: Arc::new(Mutex::new(stats)),
+            stats: Arc::new(Mutex::new(PoolStats::empty())),
+            hosts: Arc::new(Mutex::new(HashMap::new())),
+            max_connections,
             max_retries: 3,
             base_delay_ms: 100,
             max_delay_ms: 5000,
+            max_retry_after: Duration::from_secs(30),
         })
     }
+
+    /// The process-wide client all outbound HTTP goes through
+    ///
+    /// Sharing one client shares its connection pool, circuit breakers and
+    /// statistics, which the diagnostics panel reads.
+    pub fn shared() -> &'static HttpClient {
+        SHARED.get_or_init(Self::default)
+    }
+
+    /// The pooled `reqwest` client, for libraries that issue requests
+    /// themselves (such as MCP transports)
+    ///
+    /// Requests sent through it share the pool but skip the retries,
+    /// circuit breakers and statistics of [`Self::send`].
+    pub fn client(&self) -> &reqwest::Client {
+        &self.client
+    }
 }
 
 impl Default for HttpClient {
@@ -1,4 +1,6 @@
 //! HTTP method implementations
+//!
+//! Unlike [`HttpClient::send`], these treat 4xx and 5xx responses as errors.
 
 use super::{client::HttpClient, errors::HttpClientError};
 
@@ -6,24 +8,36 @@ impl HttpClient {
     /// Execute HTTP GET request with resilience patterns
     #[inline]
     pub async fn get(&self, url: &str) -> Result<reqwest::Response, HttpClientError> {
-        self.execute_with_resilience(url, || self.client.get(url)).await
+        self.send(|client| client.get(url)).await.and_then(error_for_status)
     }
 
     /// Execute HTTP POST request with resilience patterns
     #[inline]
-    pub async fn post(&self, url: &str, body: reqwest::Body) -> Result<reqwest::Response, HttpClientError> {
-        self.execute_with_resilience(url, || self.client.post(url).body(body.clone())).await
+    pub async fn post(&self, url: &str, body: impl Into<reqwest::Body>) -> Result<reqwest::Response, HttpClientError> {
+        self.send(|client| client.post(url).body(body)).await.and_then(error_for_status)
     }
 
     /// Execute HTTP PUT request with resilience patterns
     #[inline]
-    pub async fn put(&self, url: &str, body: reqwest::Body) -> Result<reqwest::Response, HttpClientError> {
-        self.execute_with_resilience(url, || self.client.put(url).body(body.clone())).await
+    pub async fn put(&self, url: &str, body: impl Into<reqwest::Body>) -> Result<reqwest::Response, HttpClientError> {
+        self.send(|client| client.put(url).body(body)).await.and_then(error_for_status)
     }
 
     /// Execute HTTP DELETE request with resilience patterns
     #[inline]
     pub async fn delete(&self, url: &str) -> Result<reqwest::Response, HttpClientError> {
-        self.execute_with_resilience(url, || self.client.delete(url)).await
+        self.send(|client| client.delete(url)).await.and_then(error_for_status)
     }
-}
\ No newline at end of file
+}
+
+/// Turn 4xx and 5xx responses into errors
+fn error_for_status(response: reqwest::Response) -> Result<reqwest::Response, HttpClientError> {
+    let status = response.status();
+    if status.is_client_error() {
+        Err(HttpClientError::ClientError(status.as_u16(), format!("Client error: {status}")))
+    } else if status.is_server_error() {
+        Err(HttpClientError::ServerError(status.as_u16(), format!("Server error: {status}")))
+    } else {
+        Ok(response)
+    }
+}
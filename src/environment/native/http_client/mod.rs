@@ -5,19 +5,22 @@
 //! - Automatic retry logic with exponential backoff
 //! - Circuit breaker pattern for resilience
 //! - Request/response logging for observability
+//! - Per-host latency percentiles and breaker state in [`PoolStats`]
 //! - Zero-allocation optimizations where possible
 
 pub mod circuit_breaker;
 pub mod client;
 pub mod errors;
 pub mod methods;
+pub mod oauth;
 pub mod resilience;
 pub mod stats;
 pub mod utils;
-pub mod circuit_breaker;
 pub mod url_extractor;
 
 // Re-export main types for easy access
 pub use client::HttpClient;
 pub use errors::HttpClientError;
-pub use stats::PoolStats;
\ No newline at end of file
+pub use oauth::install_oauth_transport;
+pub use circuit_breaker::CircuitBreakerState;
+pub use stats::{HostStats, PoolStats};
@@ -0,0 +1,28 @@
+//! oauth-core transport over the shared client
+//!
+//! The OAuth packages build their token, device code and user info requests
+//! themselves; installing [`HttpClient`] as their transport gives those
+//! requests the same retries, breakers and statistics as the rest of the app.
+
+use super::client::HttpClient;
+use oauth_core::{HttpTransport, OAuthError, TransportFuture};
+use std::sync::Arc;
+
+impl HttpTransport for HttpClient {
+    fn execute(&self, request: reqwest::Request) -> TransportFuture<'_> {
+        Box::pin(async move {
+            HttpClient::execute(self, request)
+                .await
+                .map_err(|e| OAuthError::Transport(e.to_string()))
+        })
+    }
+}
+
+/// Route the OAuth packages' requests through [`HttpClient::shared`]
+///
+/// Call once at startup, before the first sign-in or token refresh.
+pub fn install_oauth_transport() {
+    if !oauth_core::set_transport(Arc::new(HttpClient::shared().clone())) {
+        log::warn!("OAuth transport already installed; keeping it");
+    }
+}
@@ -1,98 +1,385 @@
 //! Request execution with resilience patterns
 
-use super::{client::HttpClient, errors::HttpClientError};
+use super::{
+    circuit_breaker::{CircuitBreaker, CircuitBreakerConfig},
+    client::HttpClient,
+    errors::HttpClientError,
+};
+use chrono::{DateTime, Utc};
+use reqwest::{Request, RequestBuilder, Response, StatusCode, header::RETRY_AFTER};
 use std::time::{Duration, Instant};
 
 impl HttpClient {
+    /// Build a request with the pooled client and execute it
+    ///
+    /// Any HTTP status comes back as a response so callers can read error
+    /// bodies (OAuth errors, rate limit details); see [`Self::execute`].
+    pub async fn send<F>(&self, build: F) -> Result<Response, HttpClientError>
+    where
+        F: FnOnce(&reqwest::Client) -> RequestBuilder,
+    {
+        self.execute(self.build(build)?).await
+    }
+
+    /// Like [`Self::send`], but retries whatever the method
+    ///
+    /// For requests the caller knows are safe to repeat although their method
+    /// isn't idempotent, such as token revocation.
+    pub async fn send_idempotent<F>(&self, build: F) -> Result<Response, HttpClientError>
+    where
+        F: FnOnce(&reqwest::Client) -> RequestBuilder,
+    {
+        self.execute_with_retries(self.build(build)?, true).await
+    }
+
     /// Execute request with full resilience patterns
-    pub(super) async fn execute_with_resilience<F>(&self, url: &str, request_builder: F) -> Result<reqwest::Response, HttpClientError>
+    ///
+    /// Requests with an idempotent method (GET, HEAD, PUT, DELETE, OPTIONS,
+    /// TRACE) are retried on network errors, 5xx and 429 responses; others
+    /// are sent once, see [`Self::send_idempotent`].
+    pub async fn execute(&self, request: Request) -> Result<Response, HttpClientError> {
+        let retry = request.method().is_idempotent();
+        self.execute_with_retries(request, retry).await
+    }
+
+    fn build<F>(&self, build: F) -> Result<Request, HttpClientError>
     where
-        F: Fn() -> reqwest::RequestBuilder + Clone,
+        F: FnOnce(&reqwest::Client) -> RequestBuilder,
     {
-        let start_time = Instant::now();
-        
-        // Acquire connection from pool
-        let _permit = self.pool_semaphore
-            .acquire()
-            .await
-            .map_err(|_| HttpClientError::PoolExhausted)?;
+        build(&self.client)
+            .build()
+            .map_err(|e| HttpClientError::InvalidRequest(e.to_string()))
+    }
 
+    /// Send a request, retrying it if `retry` allows
+    ///
+    /// Retries use jittered exponential backoff, or the server's
+    /// `Retry-After` when it sends one. A `Retry-After` longer than the client
+    /// waits for, or exhausted retries, return the last response. Requests
+    /// with streaming bodies are not retried. Each attempt holds a pool
+    /// permit only while in flight and counts in the statistics on its own.
+    ///
+    /// # Returns
+    /// * `Ok(Response)` - The final response, whatever its status
+    /// * `Err(HttpClientError)` - Open circuit breaker or transport failure
+    async fn execute_with_retries(
+        &self,
+        request: Request,
+        retry: bool,
+    ) -> Result<Response, HttpClientError> {
         // Extract host for circuit breaker
-        let host = super::circuit_breaker::utils::extract_host_with_fallback(url).to_string();
-        
+        let host =
+            super::circuit_breaker::utils::extract_host_with_fallback(request.url().as_str())
+                .to_string();
+
         // Check circuit breaker
         {
             let mut breakers = self.circuit_breakers.lock().unwrap_or_else(|e| {
                 log::error!("Circuit breaker lock poisoned: {e}");
                 panic!("Circuit breaker lock poisoned");
             });
-            let breaker = breakers.entry(host.clone()).or_insert_with(|| {
-                super::circuit_breaker::CircuitBreaker::new(5, Duration::from_secs(60))
-            });
-            
-            if !breaker.can_execute() {
+            let breaker = breakers
+                .entry(host.clone())
+                .or_insert_with(|| CircuitBreaker::new(CircuitBreakerConfig::default()));
+
+            if !breaker.should_allow_request() {
                 return Err(HttpClientError::CircuitBreakerOpen);
             }
         }
 
-        // Execute with retry logic
-        let mut last_error = None;
-        for attempt in 0..=self.max_retries {
-            let request = request_builder().build()
-                .map_err(|e| HttpClientError::InvalidRequest(e.to_string()))?;
-                
-            log::debug!("HTTP request attempt {}: {} {}", 
-                       attempt + 1, request.method(), request.url());
+        let mut request = request;
+        let mut attempt = 0;
+        loop {
+            // Keep a copy for the next attempt
+            let next = if retry && attempt < self.max_retries {
+                request.try_clone()
+            } else {
+                None
+            };
+
+            log::debug!(
+                "HTTP request attempt {}: {} {}",
+                attempt + 1,
+                request.method(),
+                request.url()
+            );
 
-            match self.client.execute(request).await {
+            // Acquire connection from pool, for this attempt only
+            let permit = self
+                .pool_semaphore
+                .acquire()
+                .await
+                .map_err(|_| HttpClientError::PoolExhausted)?;
+            let start_time = Instant::now();
+            let result = self.client.execute(request).await;
+            let elapsed = start_time.elapsed();
+            drop(permit);
+
+            let delay = match result {
                 Ok(response) => {
                     let status = response.status();
-                    let elapsed = start_time.elapsed();
-                    
-                    // Update statistics
-                    self.update_stats(elapsed, status.is_success());
-                    
-                    // Update circuit breaker
-                    if status.is_success() || status.is_client_error() {
+                    self.update_stats(&host, elapsed, status.is_success());
+
+                    // Rate limiting says nothing about the host's health
+                    if status.is_server_error() {
+                        self.record_circuit_breaker_failure(&host);
+                    } else if status != StatusCode::TOO_MANY_REQUESTS {
                         self.record_circuit_breaker_success(&host);
-                        
-                        if status.is_client_error() {
-                            return Err(HttpClientError::ClientError(
-                                status.as_u16(),
-                                format!("Client error: {status}")
-                            ));
+                    }
+
+                    let delay =
+                        if status.is_server_error() || status == StatusCode::TOO_MANY_REQUESTS {
+                            self.retry_delay(attempt, &response)
+                        } else {
+                            None
+                        };
+
+                    match (delay, next) {
+                        (Some(delay), Some(next)) => {
+                            log::debug!("HTTP {status} from {host}, retrying in {delay:?}");
+                            request = next;
+                            delay
+                        }
+                        _ => {
+                            log::debug!("HTTP request finished: {} in {:?}", status, elapsed);
+                            return Ok(response);
                         }
-                        
-                        log::debug!("HTTP request successful: {} in {:?}", status, elapsed);
-                        return Ok(response);
-                    } else if status.is_server_error() {
-                        self.record_circuit_breaker_failure(&host);
-                        last_error = Some(HttpClientError::ServerError(
-                            status.as_u16(),
-                            format!("Server error: {status}")
-                        ));
                     }
                 }
                 Err(e) => {
+                    self.update_stats(&host, elapsed, false);
                     self.record_circuit_breaker_failure(&host);
-                    last_error = Some(HttpClientError::NetworkError(e.to_string()));
                     log::warn!("HTTP request failed (attempt {}): {e}", attempt + 1);
+
+                    let Some(next) = next else {
+                        return Err(if e.is_timeout() {
+                            HttpClientError::Timeout
+                        } else {
+                            HttpClientError::NetworkError(e.to_string())
+                        });
+                    };
+                    request = next;
+                    self.backoff(attempt)
                 }
-            }
+            };
+
+            tokio::time::sleep(delay).await;
+            attempt += 1;
+        }
+    }
+
+    /// Delay before retrying a 5xx or 429 response; `None` to give up
+    fn retry_delay(&self, attempt: u32, response: &Response) -> Option<Duration> {
+        let retry_after = response
+            .headers()
+            .get(RETRY_AFTER)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| parse_retry_after(value, Utc::now()));
 
-            // Exponential backoff delay
-            if attempt < self.max_retries {
-                let delay_ms = std::cmp::min(
-                    self.base_delay_ms * 2_u64.pow(attempt),
-                    self.max_delay_ms
-                );
-                log::debug!("Retrying in {}ms", delay_ms);
-                tokio::time::sleep(Duration::from_millis(delay_ms)).await;
+        match retry_after {
+            Some(delay) if delay > self.max_retry_after => {
+                log::debug!("Retry-After of {delay:?} exceeds the client's limit, not retrying");
+                None
             }
+            Some(delay) => Some(delay),
+            None => Some(self.backoff(attempt)),
+        }
+    }
+
+    /// Exponential backoff with jitter
+    ///
+    /// Waits between half and all of `min(base * 2^attempt, max)`, so clients
+    /// that failed together don't retry together.
+    fn backoff(&self, attempt: u32) -> Duration {
+        let delay_ms = self
+            .base_delay_ms
+            .saturating_mul(2_u64.saturating_pow(attempt))
+            .min(self.max_delay_ms);
+        Duration::from_millis(delay_ms / 2 + jitter_ms(delay_ms - delay_ms / 2))
+    }
+}
+
+/// Random delay in `0..=max_ms`
+fn jitter_ms(max_ms: u64) -> u64 {
+    (uuid::Uuid::new_v4().as_u128() % (max_ms as u128 + 1)) as u64
+}
+
+/// Parse a `Retry-After` header: delay in seconds or an HTTP date
+///
+/// Dates in the past mean "retry now".
+fn parse_retry_after(value: &str, now: DateTime<Utc>) -> Option<Duration> {
+    let value = value.trim();
+    if let Ok(seconds) = value.parse::<u64>() {
+        return Some(Duration::from_secs(seconds));
+    }
+    let at = DateTime::parse_from_rfc2822(value).ok()?;
+    Some((at.with_timezone(&Utc) - now).to_std().unwrap_or_default())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wiremock::matchers::method;
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    fn client() -> HttpClient {
+        let mut client = HttpClient::new(4).unwrap();
+        client.base_delay_ms = 10;
+        client.max_delay_ms = 40;
+        client
+    }
+
+    #[test]
+    fn test_parse_retry_after() {
+        let now = DateTime::parse_from_rfc3339("2015-10-21T07:28:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        assert_eq!(
+            parse_retry_after("120", now),
+            Some(Duration::from_secs(120))
+        );
+        assert_eq!(
+            parse_retry_after("Wed, 21 Oct 2015 07:28:30 GMT", now),
+            Some(Duration::from_secs(30))
+        );
+        assert_eq!(
+            parse_retry_after("Wed, 21 Oct 2015 07:27:00 GMT", now),
+            Some(Duration::ZERO)
+        );
+        assert_eq!(parse_retry_after("soon", now), None);
+    }
+
+    #[test]
+    fn test_backoff_is_jittered_within_bounds() {
+        let client = client();
+        for attempt in 0..6 {
+            let cap = (10 * 2_u64.pow(attempt)).min(40);
+            let delay = client.backoff(attempt).as_millis() as u64;
+            assert!(
+                (cap / 2..=cap).contains(&delay),
+                "attempt {attempt}: {delay}ms"
+            );
         }
+    }
+
+    #[tokio::test]
+    async fn test_retry_after_is_honored() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(429).insert_header("Retry-After", "1"))
+            .up_to_n_times(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(200))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let client = client();
+        let started = Instant::now();
+        let response = client.send(|http| http.get(server.uri())).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert!(started.elapsed() >= Duration::from_secs(1));
+    }
+
+    #[tokio::test]
+    async fn test_long_retry_after_returns_the_response() {
+        let server = MockServer::start().await;
+        Mock::given(method("PUT"))
+            .respond_with(
+                ResponseTemplate::new(429)
+                    .insert_header("Retry-After", "3600")
+                    .set_body_string("slow down"),
+            )
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let response = client()
+            .send(|http| http.put(server.uri()).body("payload"))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+        assert_eq!(response.text().await.unwrap(), "slow down");
+    }
+
+    #[tokio::test]
+    async fn test_server_errors_are_retried_then_returned() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(503))
+            .expect(4)
+            .mount(&server)
+            .await;
+
+        let client = client();
+        let response = client.send(|http| http.get(server.uri())).await.unwrap();
+        assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+
+        // Every attempt counts, and none holds a permit afterwards
+        let stats = client.get_stats();
+        assert_eq!(stats.total_requests, 4);
+        assert_eq!(stats.failed_requests, 4);
+        assert_eq!(stats.active_connections, 0);
+        assert_eq!(stats.hosts.len(), 1);
+        assert_eq!(stats.hosts[0].failed_requests, 4);
+    }
+
+    #[tokio::test]
+    async fn test_post_is_only_retried_on_request() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(503))
+            .up_to_n_times(1)
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(200))
+            .mount(&server)
+            .await;
+
+        let client = client();
+        let response = client
+            .send(|http| http.post(server.uri()).body("payload"))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+
+        let response = client
+            .send_idempotent(|http| http.post(server.uri()).body("payload"))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn test_permit_is_released_while_backing_off() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(429).insert_header("Retry-After", "1"))
+            .up_to_n_times(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(200))
+            .mount(&server)
+            .await;
+
+        let mut client = HttpClient::new(1).unwrap();
+        client.base_delay_ms = 10;
+        let waiting = client.clone();
+        let uri = server.uri();
+        let first = tokio::spawn(async move { waiting.send(|http| http.get(uri)).await });
+
+        // The first request is asleep on its Retry-After; the only permit is free
+        tokio::time::sleep(Duration::from_millis(300)).await;
+        let started = Instant::now();
+        let response = client.send(|http| http.get(server.uri())).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert!(started.elapsed() < Duration::from_millis(500));
 
-        // All retries exhausted
-        self.update_stats(start_time.elapsed(), false);
-        Err(last_error.unwrap_or(HttpClientError::NetworkError("Unknown error".to_string())))
+        assert_eq!(first.await.unwrap().unwrap().status(), StatusCode::OK);
     }
-}
\ No newline at end of file
+}
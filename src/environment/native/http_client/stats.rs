@@ -1,5 +1,12 @@
 //! Connection pool statistics
 
+use super::circuit_breaker::CircuitBreakerState;
+use std::collections::VecDeque;
+use std::time::Duration;
+
+/// Latency samples kept per host for percentiles
+const LATENCY_WINDOW: usize = 256;
+
 /// Connection pool statistics
 #[derive(Debug, Clone)]
 pub struct PoolStats {
@@ -8,4 +15,98 @@ pub struct PoolStats {
     pub total_requests: u64,
     pub failed_requests: u64,
     pub average_response_time_ms: f64,
-}
\ No newline at end of file
+    /// Per-host breaker state and latency, sorted by host
+    pub hosts: Vec<HostStats>,
+}
+
+impl PoolStats {
+    pub(super) fn empty() -> Self {
+        Self {
+            active_connections: 0,
+            idle_connections: 0,
+            total_requests: 0,
+            failed_requests: 0,
+            average_response_time_ms: 0.0,
+            hosts: Vec::new(),
+        }
+    }
+}
+
+/// Statistics of one host
+#[derive(Debug, Clone, PartialEq)]
+pub struct HostStats {
+    /// Circuit breaker key: host, with the port if the URL has one
+    pub host: String,
+    pub breaker_state: CircuitBreakerState,
+    pub total_requests: u64,
+    pub failed_requests: u64,
+    /// Latency percentiles over the most recent requests
+    pub p50_ms: u64,
+    pub p90_ms: u64,
+    pub p99_ms: u64,
+}
+
+/// Requests and recent latencies of one host
+#[derive(Debug, Default)]
+pub(super) struct HostMetrics {
+    pub(super) total_requests: u64,
+    pub(super) failed_requests: u64,
+    latencies_ms: VecDeque<u64>,
+}
+
+impl HostMetrics {
+    pub(super) fn record(&mut self, elapsed: Duration, success: bool) {
+        self.total_requests += 1;
+        if !success {
+            self.failed_requests += 1;
+        }
+        if self.latencies_ms.len() == LATENCY_WINDOW {
+            self.latencies_ms.pop_front();
+        }
+        self.latencies_ms.push_back(elapsed.as_millis() as u64);
+    }
+
+    /// Nearest-rank percentile of the recent latencies; 0 without samples
+    pub(super) fn percentile(&self, percent: u32) -> u64 {
+        if self.latencies_ms.is_empty() {
+            return 0;
+        }
+        let mut sorted: Vec<u64> = self.latencies_ms.iter().copied().collect();
+        sorted.sort_unstable();
+        let rank = (sorted.len() * percent as usize).div_ceil(100).max(1);
+        sorted[rank - 1]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_percentiles() {
+        let mut metrics = HostMetrics::default();
+        assert_eq!(metrics.percentile(50), 0);
+
+        for ms in (1..=100).rev() {
+            metrics.record(Duration::from_millis(ms), ms != 7);
+        }
+        assert_eq!(metrics.total_requests, 100);
+        assert_eq!(metrics.failed_requests, 1);
+        assert_eq!(metrics.percentile(50), 50);
+        assert_eq!(metrics.percentile(90), 90);
+        assert_eq!(metrics.percentile(99), 99);
+    }
+
+    #[test]
+    fn test_latency_window_keeps_recent_samples() {
+        let mut metrics = HostMetrics::default();
+        for _ in 0..LATENCY_WINDOW {
+            metrics.record(Duration::from_millis(1000), true);
+        }
+        for _ in 0..LATENCY_WINDOW {
+            metrics.record(Duration::from_millis(10), true);
+        }
+        assert_eq!(metrics.percentile(99), 10);
+        assert_eq!(metrics.total_requests, 2 * LATENCY_WINDOW as u64);
+    }
+}
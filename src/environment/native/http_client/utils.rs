@@ -1,29 +1,32 @@
 //! Utility and statistics methods
 
-use super::{client::HttpClient, errors::HttpClientError, stats::PoolStats};
+use super::{
+    client::HttpClient,
+    stats::{HostStats, PoolStats},
+};
 use std::time::Duration;
 
 impl HttpClient {
     /// Record circuit breaker success
     pub(super) fn record_circuit_breaker_success(&self, host: &str) {
-        if let Ok(mut breakers) = self.circuit_breakers.lock() {
-            if let Some(breaker) = breakers.get_mut(host) {
-                breaker.record_success();
-            }
+        if let Ok(mut breakers) = self.circuit_breakers.lock()
+            && let Some(breaker) = breakers.get_mut(host)
+        {
+            breaker.record_success();
         }
     }
 
     /// Record circuit breaker failure
     pub(super) fn record_circuit_breaker_failure(&self, host: &str) {
-        if let Ok(mut breakers) = self.circuit_breakers.lock() {
-            if let Some(breaker) = breakers.get_mut(host) {
-                breaker.record_failure();
-            }
+        if let Ok(mut breakers) = self.circuit_breakers.lock()
+            && let Some(breaker) = breakers.get_mut(host)
+        {
+            breaker.record_failure();
         }
     }
 
-    /// Update connection pool statistics
-    pub(super) fn update_stats(&self, elapsed: Duration, success: bool) {
+    /// Update connection pool and per-host statistics
+    pub(super) fn update_stats(&self, host: &str, elapsed: Duration, success: bool) {
         if let Ok(mut stats) = self.stats.lock() {
             stats.total_requests += 1;
             if !success {
@@ -36,24 +39,51 @@ impl HttpClient {
                 (stats.average_response_time_ms * (stats.total_requests - 1) as f64 + elapsed_ms) 
                 / stats.total_requests as f64;
         }
+
+        if let Ok(mut hosts) = self.hosts.lock() {
+            hosts
+                .entry(host.to_string())
+                .or_default()
+                .record(elapsed, success);
+        }
     }
 
-    /// Get current pool statistics
+    /// Get current pool statistics, with every host contacted so far
     pub fn get_stats(&self) -> PoolStats {
-        self.stats.lock()
+        let mut stats = self
+            .stats
+            .lock()
             .map(|stats| stats.clone())
-            .unwrap_or_else(|_| PoolStats {
-                active_connections: 0,
-                idle_connections: 0,
-                total_requests: 0,
-                failed_requests: 0,
-                average_response_time_ms: 0.0,
+            .unwrap_or_else(|_| PoolStats::empty());
+        stats.active_connections = self
+            .max_connections
+            .saturating_sub(self.pool_semaphore.available_permits());
+
+        let (Ok(hosts), Ok(breakers)) = (self.hosts.lock(), self.circuit_breakers.lock()) else {
+            return stats;
+        };
+        stats.hosts = hosts
+            .iter()
+            .filter_map(|(host, metrics)| {
+                let breaker = breakers.get(host)?;
+                Some(HostStats {
+                    host: host.clone(),
+                    breaker_state: breaker.stats().state.clone(),
+                    total_requests: metrics.total_requests,
+                    failed_requests: metrics.failed_requests,
+                    p50_ms: metrics.percentile(50),
+                    p90_ms: metrics.percentile(90),
+                    p99_ms: metrics.percentile(99),
+                })
             })
+            .collect();
+        stats.hosts.sort_by(|a, b| a.host.cmp(&b.host));
+        stats
     }
 
     /// Get circuit breaker status for a host
     pub fn get_circuit_breaker_status(&self, host: &str) -> Option<String> {
         self.circuit_breakers.lock().ok()
-            .and_then(|breakers| breakers.get(host).map(|b| format!("{:?}", b.state)))
+            .and_then(|breakers| breakers.get(host).map(|b| format!("{:?}", b.stats().state)))
     }
-}
\ No newline at end of file
+}
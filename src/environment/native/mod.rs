@@ -2,6 +2,7 @@
 
 pub mod core;
 pub mod file_handling;
+pub mod http_client;
 pub mod model;
 pub mod platform;
 pub mod settings;
@@ -11,6 +12,7 @@ pub mod window;
 // Re-export main API
 pub use core::Environment;
 pub use file_handling::handle_file_event;
+pub use http_client::HttpClient;
 pub use model::Model;
 pub use settings::Settings;
 pub use window::{NewWindowPopup, NewWindowPopupProps};
@@ -12,7 +12,8 @@ use megalodon::entities::StatusVisibility;
 impl Model {
     /// List all conversations for home timeline
     ///
-    /// Returns conversation summaries sorted by last_message_at descending.
+    /// Returns the signed-in account's conversations (and unowned ones) sorted
+    /// by last_message_at descending.
     /// Each summary includes title, preview, timestamp, and unread count.
     ///
     /// # Returns
@@ -24,7 +25,7 @@ impl Model {
     /// unread counts and last message preview.
     pub async fn list_conversations(&self) -> Result<Vec<ConversationSummary>, ModelError> {
         self.database()
-            .list_conversations()
+            .list_conversations_for_user(&active_user_id()?)
             .await
             .map_err(|e| ModelError::QueryFailed(format!("Failed to list conversations: {}", e)))
     }
@@ -230,7 +231,7 @@ impl Model {
         message_id: String,
         favorited: bool,
     ) -> Result<Status, ModelError> {
-        let user_id = "hardcoded-david-maple"; // Q39: MVP hardcoded user
+        let user_id = &active_user_id()?;
         let record_id = RecordId::parse_simple(&message_id)
             .map_err(|e| ModelError::QueryFailed(format!("Invalid message ID: {}", e)))?;
 
@@ -274,7 +275,7 @@ impl Model {
         message_id: String,
         bookmarked: bool,
     ) -> Result<Status, ModelError> {
-        let user_id = "hardcoded-david-maple"; // Q39: MVP hardcoded user
+        let user_id = &active_user_id()?;
         let record_id = RecordId::parse_simple(&message_id)
             .map_err(|e| ModelError::QueryFailed(format!("Invalid message ID: {}", e)))?;
 
@@ -427,7 +428,7 @@ impl Model {
             .archive_status(
                 &status,
                 crate::environment::native::model::archive_manager::ArchiveReason::Manual,
-                "hardcoded-david-maple".to_string(), // MVP: hardcoded user
+                active_user_id()?,
             )
             .await
             .map_err(|e| ModelError::QueryFailed(format!("Failed to archive status: {}", e)))?;
@@ -446,16 +447,39 @@ impl Model {
         ))
     }
 
-    /// No-op logout (Q9: No real auth for MVP)
+    /// End the agent sessions of a signed-out account
+    ///
+    /// Terminates the running agents of every conversation the account owns
+    /// and forgets their session IDs, so the next sign-in starts fresh agents.
+    /// The HTTP backends' in-memory histories are dropped as well.
     ///
     /// # Arguments
-    /// * `_user_id` - Ignored for hardcoded auth
+    /// * `user_id` - Provider user ID of the signed-out account
     ///
     /// # Returns
-    /// * Always `Ok(())`
-    pub async fn logout(&self, _user_id: String) -> Result<(), ModelError> {
-        // No-op for hardcoded auth
-        Ok(())
+    /// * `Ok(usize)` - Number of conversations whose sessions were ended
+    /// * `Err(ModelError)` - Database update failed
+    pub async fn logout(&self, user_id: String) -> Result<usize, ModelError> {
+        let cleared = self
+            .database()
+            .clear_agent_sessions_for_user(&user_id)
+            .await
+            .map_err(|e| ModelError::QueryFailed(format!("Failed to clear sessions: {}", e)))?;
+
+        for conversation_id in &cleared {
+            // Agents not spawned in this run have nothing to terminate
+            if let Err(e) = self
+                .agent_manager()
+                .terminate_session(&conversation_id.to_sql())
+                .await
+            {
+                log::debug!("No running agent for {}: {}", conversation_id.to_sql(), e);
+            }
+        }
+        crate::services::llm::openai::clear_sessions();
+
+        log::info!("Ended agent sessions of {} conversations", cleared.len());
+        Ok(cleared.len())
     }
 
     /// Subscribe to real-time message stream from all active agent sessions
@@ -584,6 +608,12 @@ impl Model {
     }
 }
 
+/// User id of the signed-in account that per-user data is stored under
+fn active_user_id() -> Result<String, ModelError> {
+    crate::auth::active_user_id()
+        .ok_or_else(|| ModelError::AuthenticationFailed("No account is signed in".to_string()))
+}
+
 /// Extract display content from SerializedMessage based on message type
 ///
 /// Handles different content structures:
@@ -881,7 +911,7 @@ impl Model {
         _max_id: Option<String>,
         _limit: u32,
     ) -> Result<Vec<crate::environment::model::Notification>, ModelError> {
-        let user_id = "hardcoded-david-maple"; // Q39: MVP hardcoded user
+        let user_id = &active_user_id()?;
 
         // Get unread messages as notifications
         let messages = self
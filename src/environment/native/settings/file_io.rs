@@ -11,19 +11,17 @@ use std::path::PathBuf;
 /// Error type for data directory access failures
 #[derive(Debug)]
 pub enum DataDirectoryError {
-    /// Failed to determine project directories
-    ProjectDirsNotFound,
     /// Failed to create data directory
     DirectoryCreationFailed(std::io::Error),
+    /// No profile, so no settings directory
+    ProfileUnavailable(String),
 }
 
 impl std::fmt::Display for DataDirectoryError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
-            Self::ProjectDirsNotFound => {
-                write!(f, "Unable to determine system project directories")
-            }
             Self::DirectoryCreationFailed(e) => write!(f, "Failed to create data directory: {e}"),
+            Self::ProfileUnavailable(e) => write!(f, "{e}"),
         }
     }
 }
@@ -31,13 +29,12 @@ impl std::fmt::Display for DataDirectoryError {
 impl std::error::Error for DataDirectoryError {}
 
 /// Try to get the data directory with proper error handling
+///
+/// Settings are stored per profile (see `config::profile`).
 pub async fn try_data_directory() -> Result<PathBuf, DataDirectoryError> {
-    use directories_next::ProjectDirs;
-
-    let proj_dirs = ProjectDirs::from("com", "stylemac", "cyrup")
-        .ok_or(DataDirectoryError::ProjectDirsNotFound)?;
-
-    let dirs = proj_dirs.config_dir().to_path_buf();
+    let dirs = crate::config::profile::active_profile()
+        .map_err(DataDirectoryError::ProfileUnavailable)?
+        .settings_dir();
 
     if !dirs.exists() {
         tokio::fs::create_dir_all(&dirs)
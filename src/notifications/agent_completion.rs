@@ -0,0 +1,261 @@
+//! Agent-completion notifications and their actions
+//!
+//! When an agent finishes a reply, [`notify_agent_completion`] checks the
+//! conversation's policy and quiet hours, claims a delivery receipt (so a
+//! reply is never announced twice) and posts a notification naming the
+//! agent and conversation with a snippet of the reply.
+//!
+//! [`spawn_action_router`] turns clicks on those notifications back into
+//! app actions: "open" selects the conversation, and inline replies are sent
+//! to the same agent through `agent_chat::send_message`, threaded under the
+//! reply that was announced.
+
+use super::backend::{NotificationEvent, NotificationRequest};
+use super::content::NotificationContent;
+use super::error::{NotificationError, NotificationResult};
+use super::policy::{DeliveryContext, app_focused, should_notify};
+use super::service::NotificationService;
+use crate::database::Database;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, OnceLock};
+use surrealdb_types::{RecordId, ToSql};
+
+/// Action id for opening the conversation (`default` is also sent by
+/// freedesktop servers when the notification body is clicked)
+const OPEN_ACTION: &str = "default";
+/// Maximum characters of the reply shown in the notification body
+const SNIPPET_CHARS: usize = 140;
+/// Maximum characters of the notification title
+const TITLE_CHARS: usize = 120;
+
+/// A finished agent reply
+#[derive(Debug, Clone)]
+pub struct AgentCompletion {
+    pub conversation_id: RecordId,
+    /// The agent's reply message
+    pub message_id: RecordId,
+    pub agent_id: RecordId,
+    /// Whether the user @mentioned this agent in the prompt
+    pub mentioned: bool,
+}
+
+/// Where a delivered notification's actions lead, keyed by notification id
+///
+/// Entries are removed when the notification is acted on or dismissed.
+static ROUTES: OnceLock<Mutex<HashMap<String, AgentCompletion>>> = OnceLock::new();
+
+/// Conversations to open, fed by the action router
+static OPEN_CHANNEL: OnceLock<(flume::Sender<RecordId>, flume::Receiver<RecordId>)> =
+    OnceLock::new();
+
+fn routes() -> std::sync::MutexGuard<'static, HashMap<String, AgentCompletion>> {
+    ROUTES
+        .get_or_init(Default::default)
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+}
+
+/// Notify the user that an agent finished replying
+///
+/// # Returns
+/// * `Ok(true)` - Notification delivered
+/// * `Ok(false)` - Suppressed by policy, quiet hours, or an earlier receipt
+/// * `Err(NotificationError)` - Storage lookup or delivery failed
+pub async fn notify_agent_completion(
+    database: &Database,
+    completion: &AgentCompletion,
+) -> NotificationResult<bool> {
+    let policy = database
+        .get_notification_policy(&completion.conversation_id)
+        .await
+        .map_err(NotificationError::Storage)?;
+    let quiet_hours = database
+        .get_quiet_hours()
+        .await
+        .map_err(NotificationError::Storage)?;
+
+    let context = DeliveryContext {
+        app_focused: app_focused(),
+        mentioned: completion.mentioned,
+        now: chrono::Local::now().time(),
+    };
+    if !should_notify(policy, quiet_hours, context) {
+        log::debug!(
+            "[Notifications] Suppressed completion for {} ({:?})",
+            completion.message_id.to_sql(),
+            policy
+        );
+        return Ok(false);
+    }
+
+    let claimed = database
+        .claim_notification(&completion.message_id, &completion.conversation_id)
+        .await
+        .map_err(NotificationError::Storage)?;
+    if !claimed {
+        log::debug!(
+            "[Notifications] Duplicate completion for {} ignored",
+            completion.message_id.to_sql()
+        );
+        return Ok(false);
+    }
+
+    let service = NotificationService::shared();
+    let notification_id = completion.message_id.to_sql();
+    let delivery = async {
+        let request = NotificationRequest::new(
+            notification_id.clone(),
+            completion_content(database, completion).await?,
+        );
+        service.deliver(&request).await
+    };
+
+    if let Err(e) = delivery.await {
+        // Let a later attempt retry rather than marking the reply as announced
+        if let Err(release_err) = database.release_notification(&completion.message_id).await {
+            log::warn!("[Notifications] {}", release_err);
+        }
+        return Err(e);
+    }
+
+    database
+        .complete_notification_receipt(&completion.message_id, service.platform_name())
+        .await
+        .map_err(NotificationError::Storage)?;
+    routes().insert(notification_id, completion.clone());
+
+    Ok(true)
+}
+
+/// Title, snippet and actions for a completion notification
+async fn completion_content(
+    database: &Database,
+    completion: &AgentCompletion,
+) -> NotificationResult<NotificationContent> {
+    let message = database
+        .get_message(&completion.message_id)
+        .await
+        .map_err(NotificationError::Storage)?;
+    let agent = database
+        .get_template(&completion.agent_id)
+        .await
+        .map_err(NotificationError::Storage)?;
+    let conversation = database
+        .get_conversation(&completion.conversation_id)
+        .await
+        .map_err(NotificationError::Storage)?;
+
+    let title = truncate(
+        &format!("{} replied in {}", agent.name, conversation.title),
+        TITLE_CHARS,
+    );
+    let mut body = truncate(&message.content, SNIPPET_CHARS);
+    if body.is_empty() {
+        body = "(empty reply)".to_string();
+    }
+
+    Ok(NotificationContent::new(title, body)
+        .with_action(OPEN_ACTION, "Open conversation")
+        .with_inline_reply(format!("Reply to {}", agent.name)))
+}
+
+/// Collapse whitespace and cut to `max` characters, adding an ellipsis
+fn truncate(text: &str, max: usize) -> String {
+    let collapsed = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    if collapsed.chars().count() <= max {
+        return collapsed;
+    }
+    let mut cut: String = collapsed.chars().take(max - 1).collect();
+    cut.push('…');
+    cut
+}
+
+/// Start routing notification actions (idempotent)
+///
+/// Returns a receiver of conversations the user asked to open. Inline
+/// replies are sent directly; the UI only needs to follow the receiver.
+pub fn spawn_action_router(database: Arc<Database>) -> flume::Receiver<RecordId> {
+    let mut started = false;
+    let (open_tx, open_rx) = OPEN_CHANNEL.get_or_init(|| {
+        started = true;
+        flume::unbounded()
+    });
+
+    if started && let Some(events) = NotificationService::shared().subscribe() {
+        let open_tx = open_tx.clone();
+        tokio::spawn(async move {
+            while let Ok(event) = events.recv_async().await {
+                route_event(&database, &open_tx, event);
+            }
+            log::debug!("[Notifications] Action router stopped");
+        });
+    }
+
+    open_rx.clone()
+}
+
+fn route_event(
+    database: &Arc<Database>,
+    open_tx: &flume::Sender<RecordId>,
+    event: NotificationEvent,
+) {
+    match event {
+        NotificationEvent::ActionInvoked {
+            notification_id,
+            action_id,
+        } => {
+            // A handled notification has nothing left to route
+            let Some(completion) = routes().remove(&notification_id) else {
+                return;
+            };
+            log::debug!(
+                "[Notifications] Action '{}' on {}",
+                action_id,
+                notification_id
+            );
+            let _ = open_tx.send(completion.conversation_id);
+        }
+        NotificationEvent::Replied {
+            notification_id,
+            text,
+        } => {
+            let Some(completion) = routes().remove(&notification_id) else {
+                return;
+            };
+            if text.trim().is_empty() {
+                return;
+            }
+
+            let database = database.clone();
+            tokio::spawn(async move {
+                if let Err(e) = crate::services::agent_chat::send_message(
+                    database,
+                    completion.conversation_id,
+                    text,
+                    Some(vec![completion.agent_id]),
+                    false,
+                    Some(completion.message_id),
+                )
+                .await
+                {
+                    log::error!("[Notifications] Inline reply failed: {}", e);
+                }
+            });
+        }
+        NotificationEvent::Closed { notification_id } => {
+            routes().remove(&notification_id);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::truncate;
+
+    #[test]
+    fn test_truncate_collapses_and_cuts() {
+        assert_eq!(truncate("  Done.\n\nAll   good ", 140), "Done. All good");
+        assert_eq!(truncate("abcdef", 4), "abc…");
+        assert_eq!(truncate("héllo wörld", 5).chars().count(), 5);
+    }
+}
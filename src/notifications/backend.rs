@@ -40,12 +40,32 @@ pub struct DeliveryReceipt {
     pub success: bool,
 }
 
+/// User interaction with a delivered notification
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum NotificationEvent {
+    /// A notification button (or the notification body) was clicked
+    ActionInvoked {
+        notification_id: String,
+        action_id: String,
+    },
+    /// Text was submitted through the inline reply field
+    Replied {
+        notification_id: String,
+        text: String,
+    },
+    /// The notification was dismissed or expired
+    Closed { notification_id: String },
+}
+
 /// Platform-specific notification backend trait
 ///
 /// Implemented by platform backends (MacOSBackend, WindowsBackend, LinuxBackend)
 #[async_trait]
 pub trait PlatformBackend: Send + Sync {
     /// Deliver a notification to the platform
+    ///
+    /// Delivering again with the same `notification_id` updates the existing
+    /// notification in place where the platform supports it.
     async fn deliver_notification(
         &self,
         request: &NotificationRequest,
@@ -54,6 +74,16 @@ pub trait PlatformBackend: Send + Sync {
     /// Check if platform is authorized to show notifications
     async fn check_authorization(&self) -> NotificationResult<bool>;
 
+    /// Withdraw a previously delivered notification
+    async fn close_notification(&self, _notification_id: &str) -> NotificationResult<()> {
+        Ok(())
+    }
+
+    /// Receiver for interaction events, if the backend reports them
+    fn subscribe(&self) -> Option<flume::Receiver<NotificationEvent>> {
+        None
+    }
+
     /// Get platform name for debugging
     fn platform_name(&self) -> &'static str;
 }
@@ -5,6 +5,25 @@
 
 use serde::{Deserialize, Serialize};
 
+/// How intrusively the platform should present a notification
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Urgency {
+    Low,
+    #[default]
+    Normal,
+    /// Stays on screen until dismissed where the platform supports it
+    Critical,
+}
+
+/// A button shown on the notification
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NotificationAction {
+    /// Identifier reported back when the action is invoked
+    pub id: String,
+    /// Button label
+    pub label: String,
+}
+
 /// Notification content with title and body
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct NotificationContent {
@@ -12,6 +31,15 @@ pub struct NotificationContent {
     pub title: String,
     /// Notification body text
     pub body: String,
+    /// Presentation urgency
+    #[serde(default)]
+    pub urgency: Urgency,
+    /// Buttons, in display order
+    #[serde(default)]
+    pub actions: Vec<NotificationAction>,
+    /// Placeholder for an inline reply field, where the platform offers one
+    #[serde(default)]
+    pub inline_reply: Option<String>,
 }
 
 impl NotificationContent {
@@ -20,9 +48,33 @@ impl NotificationContent {
         Self {
             title: title.into(),
             body: body.into(),
+            urgency: Urgency::default(),
+            actions: Vec::new(),
+            inline_reply: None,
         }
     }
 
+    /// Set the presentation urgency
+    pub fn with_urgency(mut self, urgency: Urgency) -> Self {
+        self.urgency = urgency;
+        self
+    }
+
+    /// Add a button
+    pub fn with_action(mut self, id: impl Into<String>, label: impl Into<String>) -> Self {
+        self.actions.push(NotificationAction {
+            id: id.into(),
+            label: label.into(),
+        });
+        self
+    }
+
+    /// Offer an inline reply field with the given placeholder text
+    pub fn with_inline_reply(mut self, placeholder: impl Into<String>) -> Self {
+        self.inline_reply = Some(placeholder.into());
+        self
+    }
+
     /// Validate content meets basic requirements
     pub fn validate(&self) -> Result<(), String> {
         if self.title.is_empty() {
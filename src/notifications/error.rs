@@ -19,4 +19,7 @@ pub enum NotificationError {
 
     #[error("Delivery failed: {0}")]
     DeliveryFailed(String),
+
+    #[error("Notification storage error: {0}")]
+    Storage(String),
 }
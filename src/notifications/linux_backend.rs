@@ -0,0 +1,310 @@
+//! Linux notification backend using the freedesktop notification spec
+//!
+//! Speaks `org.freedesktop.Notifications` on the session bus directly via
+//! zbus. Re-delivering a request with the same `notification_id` passes the
+//! server-assigned id as `replaces_id`, so streaming updates edit one popup
+//! instead of stacking new ones.
+
+use async_trait::async_trait;
+use futures_util::StreamExt;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::SystemTime;
+use tokio::sync::OnceCell;
+use zbus::zvariant::Value;
+
+use super::backend::{DeliveryReceipt, NotificationEvent, NotificationRequest, PlatformBackend};
+use super::content::Urgency;
+use super::error::{NotificationError, NotificationResult};
+
+const PLATFORM: &str = "Linux";
+const APP_NAME: &str = "Cyrup";
+const DESKTOP_ENTRY: &str = "cyrup-chat";
+/// Action key servers with the `inline-reply` capability render as a text field
+const INLINE_REPLY_ACTION: &str = "inline-reply";
+
+/// Client side of the freedesktop notification interface
+#[zbus::proxy(
+    interface = "org.freedesktop.Notifications",
+    default_service = "org.freedesktop.Notifications",
+    default_path = "/org/freedesktop/Notifications"
+)]
+pub trait Notifications {
+    #[allow(clippy::too_many_arguments)]
+    fn notify(
+        &self,
+        app_name: &str,
+        replaces_id: u32,
+        app_icon: &str,
+        summary: &str,
+        body: &str,
+        actions: &[&str],
+        hints: HashMap<&str, Value<'_>>,
+        expire_timeout: i32,
+    ) -> zbus::Result<u32>;
+
+    fn close_notification(&self, id: u32) -> zbus::Result<()>;
+
+    fn get_capabilities(&self) -> zbus::Result<Vec<String>>;
+
+    #[zbus(signal)]
+    fn action_invoked(&self, id: u32, action_key: String) -> zbus::Result<()>;
+
+    #[zbus(signal)]
+    fn notification_closed(&self, id: u32, reason: u32) -> zbus::Result<()>;
+
+    /// Inline reply submitted (KDE / `inline-reply` capability extension)
+    #[zbus(signal)]
+    fn notification_replied(&self, id: u32, text: String) -> zbus::Result<()>;
+}
+
+/// Our notification ids mapped to server-assigned ids
+type IdMap = Arc<Mutex<HashMap<String, u32>>>;
+
+/// Linux notification backend over D-Bus
+pub struct LinuxBackend {
+    connection: OnceCell<zbus::Connection>,
+    listener: OnceCell<()>,
+    ids: IdMap,
+    events_tx: flume::Sender<NotificationEvent>,
+    events_rx: flume::Receiver<NotificationEvent>,
+}
+
+impl LinuxBackend {
+    /// Backend that connects to the session bus on first use
+    pub fn new() -> Self {
+        Self::with_cell(OnceCell::new())
+    }
+
+    /// Backend using an existing connection
+    ///
+    /// Used with peer-to-peer connections to an in-process server in tests.
+    pub fn with_connection(connection: zbus::Connection) -> Self {
+        Self::with_cell(OnceCell::new_with(Some(connection)))
+    }
+
+    fn with_cell(connection: OnceCell<zbus::Connection>) -> Self {
+        let (events_tx, events_rx) = flume::unbounded();
+        Self {
+            connection,
+            listener: OnceCell::new(),
+            ids: Arc::new(Mutex::new(HashMap::new())),
+            events_tx,
+            events_rx,
+        }
+    }
+
+    async fn proxy(&self) -> NotificationResult<NotificationsProxy<'static>> {
+        let connection = self
+            .connection
+            .get_or_try_init(|| async { zbus::Connection::session().await.map_err(platform_error) })
+            .await?;
+
+        let proxy = NotificationsProxy::new(connection)
+            .await
+            .map_err(platform_error)?;
+
+        self.listener
+            .get_or_try_init(|| {
+                spawn_signal_listener(proxy.clone(), self.ids.clone(), self.events_tx.clone())
+            })
+            .await?;
+
+        Ok(proxy)
+    }
+
+    fn server_id(&self, notification_id: &str) -> Option<u32> {
+        lock(&self.ids).get(notification_id).copied()
+    }
+}
+
+#[async_trait]
+impl PlatformBackend for LinuxBackend {
+    async fn deliver_notification(
+        &self,
+        request: &NotificationRequest,
+    ) -> NotificationResult<DeliveryReceipt> {
+        let proxy = self.proxy().await?;
+        let content = &request.content;
+
+        // Actions are a flat [key, label, key, label, ...] list
+        let mut actions: Vec<&str> = content
+            .actions
+            .iter()
+            .flat_map(|action| [action.id.as_str(), action.label.as_str()])
+            .collect();
+
+        let mut hints = HashMap::new();
+        hints.insert("urgency", Value::U8(urgency_level(content.urgency)));
+        hints.insert("desktop-entry", Value::from(DESKTOP_ENTRY));
+
+        // Servers without inline reply support ignore the unknown action key
+        if let Some(placeholder) = &content.inline_reply {
+            actions.extend([INLINE_REPLY_ACTION, "Reply"]);
+            hints.insert(
+                "x-kde-reply-placeholder-text",
+                Value::from(placeholder.as_str()),
+            );
+        }
+
+        // Critical notifications stay until dismissed; others use the server default
+        let expire_timeout = match content.urgency {
+            Urgency::Critical => 0,
+            _ => -1,
+        };
+
+        let replaces_id = self.server_id(&request.notification_id).unwrap_or(0);
+
+        let server_id = proxy
+            .notify(
+                APP_NAME,
+                replaces_id,
+                "",
+                &content.title,
+                &content.body,
+                &actions,
+                hints,
+                expire_timeout,
+            )
+            .await
+            .map_err(|e| NotificationError::DeliveryFailed(e.to_string()))?;
+
+        lock(&self.ids).insert(request.notification_id.clone(), server_id);
+
+        Ok(DeliveryReceipt {
+            notification_id: request.notification_id.clone(),
+            platform: PLATFORM.to_string(),
+            delivered_at: SystemTime::now(),
+            success: true,
+        })
+    }
+
+    async fn check_authorization(&self) -> NotificationResult<bool> {
+        // The freedesktop spec has no permission model; a reachable server is enough
+        let proxy = match self.proxy().await {
+            Ok(proxy) => proxy,
+            Err(e) => {
+                log::debug!("Notification server unavailable: {}", e);
+                return Ok(false);
+            }
+        };
+        Ok(proxy.get_capabilities().await.is_ok())
+    }
+
+    async fn close_notification(&self, notification_id: &str) -> NotificationResult<()> {
+        let Some(server_id) = self.server_id(notification_id) else {
+            return Ok(());
+        };
+
+        self.proxy()
+            .await?
+            .close_notification(server_id)
+            .await
+            .map_err(platform_error)
+    }
+
+    fn subscribe(&self) -> Option<flume::Receiver<NotificationEvent>> {
+        Some(self.events_rx.clone())
+    }
+
+    fn platform_name(&self) -> &'static str {
+        PLATFORM
+    }
+}
+
+impl Default for LinuxBackend {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Forward ActionInvoked, NotificationReplied and NotificationClosed signals as [`NotificationEvent`]s
+async fn spawn_signal_listener(
+    proxy: NotificationsProxy<'static>,
+    ids: IdMap,
+    events: flume::Sender<NotificationEvent>,
+) -> NotificationResult<()> {
+    let mut invoked = proxy
+        .receive_action_invoked()
+        .await
+        .map_err(platform_error)?;
+    let mut closed = proxy
+        .receive_notification_closed()
+        .await
+        .map_err(platform_error)?;
+    let mut replied = proxy
+        .receive_notification_replied()
+        .await
+        .map_err(platform_error)?;
+
+    tokio::spawn(async move {
+        loop {
+            let event = tokio::select! {
+                Some(signal) = invoked.next() => {
+                    let Ok(args) = signal.args() else { continue };
+                    let Some(notification_id) = notification_id_for(&ids, *args.id()) else {
+                        continue;
+                    };
+                    NotificationEvent::ActionInvoked {
+                        notification_id,
+                        action_id: args.action_key().clone(),
+                    }
+                }
+                Some(signal) = replied.next() => {
+                    let Ok(args) = signal.args() else { continue };
+                    let Some(notification_id) = notification_id_for(&ids, *args.id()) else {
+                        continue;
+                    };
+                    NotificationEvent::Replied {
+                        notification_id,
+                        text: args.text().clone(),
+                    }
+                }
+                Some(signal) = closed.next() => {
+                    let Ok(args) = signal.args() else { continue };
+                    let Some(notification_id) = notification_id_for(&ids, *args.id()) else {
+                        continue;
+                    };
+                    lock(&ids).remove(&notification_id);
+                    NotificationEvent::Closed { notification_id }
+                }
+                else => break,
+            };
+
+            if events.send(event).is_err() {
+                break;
+            }
+        }
+        log::debug!("Notification signal listener stopped");
+    });
+
+    Ok(())
+}
+
+/// Reverse lookup; signals for other applications' notifications yield `None`
+fn notification_id_for(ids: &IdMap, server_id: u32) -> Option<String> {
+    lock(ids)
+        .iter()
+        .find(|(_, id)| **id == server_id)
+        .map(|(notification_id, _)| notification_id.clone())
+}
+
+fn lock(ids: &IdMap) -> std::sync::MutexGuard<'_, HashMap<String, u32>> {
+    ids.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+/// Urgency byte from the freedesktop spec (0 low, 1 normal, 2 critical)
+fn urgency_level(urgency: Urgency) -> u8 {
+    match urgency {
+        Urgency::Low => 0,
+        Urgency::Normal => 1,
+        Urgency::Critical => 2,
+    }
+}
+
+fn platform_error(e: zbus::Error) -> NotificationError {
+    NotificationError::PlatformError {
+        platform: PLATFORM.to_string(),
+        message: e.to_string(),
+    }
+}
@@ -1,36 +1,236 @@
 //! macOS notification backend using UserNotifications framework
 //!
 //! Adapted from ecs-notifications macos.rs with Bevy removed.
+//!
+//! Buttons and the inline reply field are `UNNotificationCategory` actions.
+//! A category is registered for each distinct set of actions the app sends,
+//! and the notification center's delegate forwards the user's responses as
+//! [`NotificationEvent`]s, the same events the Linux backend reports.
 
 #[cfg(target_os = "macos")]
 use objc2::rc::Retained;
 #[cfg(target_os = "macos")]
-use objc2_foundation::NSString;
+use objc2::runtime::{AnyObject, NSObject, NSObjectProtocol, ProtocolObject};
+#[cfg(target_os = "macos")]
+use objc2::{AllocAnyThread, DefinedClass, define_class, msg_send};
+#[cfg(target_os = "macos")]
+use objc2_foundation::{NSArray, NSSet, NSString};
 #[cfg(target_os = "macos")]
 use objc2_user_notifications::{
-    UNAuthorizationStatus, UNMutableNotificationContent, UNNotificationRequest,
-    UNTimeIntervalNotificationTrigger, UNUserNotificationCenter,
+    UNAuthorizationStatus, UNMutableNotificationContent, UNNotificationAction,
+    UNNotificationActionOptions, UNNotificationCategory, UNNotificationCategoryOptions,
+    UNNotificationDefaultActionIdentifier, UNNotificationDismissActionIdentifier,
+    UNNotificationRequest, UNNotificationResponse, UNTextInputNotificationAction,
+    UNTextInputNotificationResponse, UNTimeIntervalNotificationTrigger, UNUserNotificationCenter,
+    UNUserNotificationCenterDelegate,
 };
 
 use async_trait::async_trait;
+use std::collections::BTreeMap;
+use std::sync::{Mutex, OnceLock};
 use std::time::SystemTime;
 
-use super::backend::{DeliveryReceipt, NotificationRequest, PlatformBackend};
+use super::backend::{DeliveryReceipt, NotificationEvent, NotificationRequest, PlatformBackend};
+use super::content::NotificationContent;
 use super::error::{NotificationError, NotificationResult};
 
+/// Action id of the inline reply field
+const INLINE_REPLY_ACTION: &str = "inline-reply";
+/// Reported for clicks on the notification body, like freedesktop servers do
+const DEFAULT_ACTION: &str = "default";
+
+/// Actions of one notification category
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct CategorySpec {
+    /// `(id, label)` of each button
+    buttons: Vec<(String, String)>,
+    /// Placeholder of the inline reply field
+    reply_placeholder: Option<String>,
+}
+
+impl CategorySpec {
+    fn for_content(content: &NotificationContent) -> Option<Self> {
+        let buttons: Vec<_> = content
+            .actions
+            .iter()
+            .map(|action| (action.id.clone(), action.label.clone()))
+            .collect();
+        if buttons.is_empty() && content.inline_reply.is_none() {
+            return None;
+        }
+        Some(Self {
+            buttons,
+            reply_placeholder: content.inline_reply.clone(),
+        })
+    }
+
+    /// Category identifier; equal specs share a category
+    fn identifier(&self) -> String {
+        let mut id = String::from("cyrup");
+        for (action, label) in &self.buttons {
+            id.push_str(&format!("|{}={}", action, label));
+        }
+        if let Some(placeholder) = &self.reply_placeholder {
+            id.push_str(&format!("|{}={}", INLINE_REPLY_ACTION, placeholder));
+        }
+        id
+    }
+}
+
+/// Every category registered so far, by identifier
+///
+/// `setNotificationCategories` replaces the whole set, so it is always called
+/// with all of them.
+static CATEGORIES: Mutex<BTreeMap<String, CategorySpec>> = Mutex::new(BTreeMap::new());
+
 /// macOS notification backend using UserNotifications framework
-pub struct MacOSBackend;
+pub struct MacOSBackend {
+    delegate_installed: OnceLock<()>,
+    events_tx: flume::Sender<NotificationEvent>,
+    events_rx: flume::Receiver<NotificationEvent>,
+}
 
 impl MacOSBackend {
     pub fn new() -> Self {
-        Self
+        let (events_tx, events_rx) = flume::unbounded();
+        Self {
+            delegate_installed: OnceLock::new(),
+            events_tx,
+            events_rx,
+        }
     }
 
     #[cfg(target_os = "macos")]
-    #[allow(dead_code)] // Helper method for notification system - pending full notification integration
     fn get_notification_center() -> Retained<UNUserNotificationCenter> {
         UNUserNotificationCenter::currentNotificationCenter()
     }
+
+    /// Become the notification center's delegate so responses reach us
+    #[cfg(target_os = "macos")]
+    fn install_delegate(&self) {
+        self.delegate_installed.get_or_init(|| {
+            let delegate = NotificationDelegate::new(self.events_tx.clone());
+            Self::get_notification_center().setDelegate(Some(ProtocolObject::from_ref(&*delegate)));
+            // The center only keeps a weak reference; the delegate lives as
+            // long as the process
+            std::mem::forget(delegate);
+        });
+    }
+
+    /// Register `spec`'s category unless it already is
+    #[cfg(target_os = "macos")]
+    fn register_category(spec: &CategorySpec) -> String {
+        let identifier = spec.identifier();
+        let mut categories = CATEGORIES.lock().unwrap_or_else(|e| e.into_inner());
+        if categories.contains_key(&identifier) {
+            return identifier;
+        }
+        categories.insert(identifier.clone(), spec.clone());
+
+        let categories: Vec<Retained<UNNotificationCategory>> = categories
+            .iter()
+            .map(|(identifier, spec)| build_category(identifier, spec))
+            .collect();
+        Self::get_notification_center()
+            .setNotificationCategories(&NSSet::from_retained_slice(&categories));
+        identifier
+    }
+}
+
+/// Native category with a button per action and an optional reply field
+#[cfg(target_os = "macos")]
+fn build_category(identifier: &str, spec: &CategorySpec) -> Retained<UNNotificationCategory> {
+    let mut actions: Vec<Retained<UNNotificationAction>> = spec
+        .buttons
+        .iter()
+        .map(|(id, label)| {
+            UNNotificationAction::actionWithIdentifier_title_options(
+                &NSString::from_str(id),
+                &NSString::from_str(label),
+                UNNotificationActionOptions::Foreground,
+            )
+        })
+        .collect();
+    if let Some(placeholder) = &spec.reply_placeholder {
+        let reply =
+            UNTextInputNotificationAction::actionWithIdentifier_title_options_textInputButtonTitle_textInputPlaceholder(
+                &NSString::from_str(INLINE_REPLY_ACTION),
+                &NSString::from_str("Reply"),
+                UNNotificationActionOptions::empty(),
+                &NSString::from_str("Send"),
+                &NSString::from_str(placeholder),
+            );
+        actions.push(Retained::into_super(reply));
+    }
+
+    UNNotificationCategory::categoryWithIdentifier_actions_intentIdentifiers_options(
+        &NSString::from_str(identifier),
+        &NSArray::from_retained_slice(&actions),
+        &NSArray::new(),
+        UNNotificationCategoryOptions::CustomDismissAction,
+    )
+}
+
+#[cfg(target_os = "macos")]
+define_class!(
+    /// Forwards notification responses to the backend's event channel
+    #[unsafe(super(NSObject))]
+    #[name = "CyrupNotificationDelegate"]
+    #[ivars = flume::Sender<NotificationEvent>]
+    struct NotificationDelegate;
+
+    unsafe impl NSObjectProtocol for NotificationDelegate {}
+
+    unsafe impl UNUserNotificationCenterDelegate for NotificationDelegate {
+        #[unsafe(method(userNotificationCenter:didReceiveNotificationResponse:withCompletionHandler:))]
+        fn did_receive_response(
+            &self,
+            _center: &UNUserNotificationCenter,
+            response: &UNNotificationResponse,
+            completion_handler: &block2::DynBlock<dyn Fn()>,
+        ) {
+            let _ = self.ivars().send(response_event(response));
+            completion_handler.call(());
+        }
+    }
+);
+
+#[cfg(target_os = "macos")]
+impl NotificationDelegate {
+    fn new(events: flume::Sender<NotificationEvent>) -> Retained<Self> {
+        let this = Self::alloc().set_ivars(events);
+        unsafe { msg_send![super(this), init] }
+    }
+}
+
+/// Translate a notification response into the backend-neutral event
+#[cfg(target_os = "macos")]
+fn response_event(response: &UNNotificationResponse) -> NotificationEvent {
+    let notification_id = response.notification().request().identifier().to_string();
+    let action = response.actionIdentifier();
+
+    if unsafe { &*action == UNNotificationDismissActionIdentifier } {
+        return NotificationEvent::Closed { notification_id };
+    }
+    if unsafe { &*action == UNNotificationDefaultActionIdentifier } {
+        return NotificationEvent::ActionInvoked {
+            notification_id,
+            action_id: DEFAULT_ACTION.to_string(),
+        };
+    }
+
+    let object: &AnyObject = response;
+    if let Some(reply) = object.downcast_ref::<UNTextInputNotificationResponse>() {
+        return NotificationEvent::Replied {
+            notification_id,
+            text: reply.userText().to_string(),
+        };
+    }
+
+    NotificationEvent::ActionInvoked {
+        notification_id,
+        action_id: action.to_string(),
+    }
 }
 
 #[async_trait]
@@ -48,6 +248,12 @@ impl PlatformBackend for MacOSBackend {
                 });
             }
 
+            // Responses to the actions arrive through the delegate
+            let category = CategorySpec::for_content(&request.content).map(|spec| {
+                self.install_delegate();
+                Self::register_category(&spec)
+            });
+
             // Clone data for use in blocking task
             let notification_id = request.notification_id.clone();
             let title = request.content.title.clone();
@@ -66,6 +272,9 @@ impl PlatformBackend for MacOSBackend {
 
                 content.setTitle(&title_ns);
                 content.setBody(&body_ns);
+                if let Some(category) = &category {
+                    content.setCategoryIdentifier(&NSString::from_str(category));
+                }
 
                 // Set default sound
                 let default_sound = objc2_user_notifications::UNNotificationSound::defaultSound();
@@ -172,6 +381,27 @@ impl PlatformBackend for MacOSBackend {
         Ok(false)
     }
 
+    async fn close_notification(&self, notification_id: &str) -> NotificationResult<()> {
+        #[cfg(target_os = "macos")]
+        {
+            let ids = NSArray::from_retained_slice(&[NSString::from_str(notification_id)]);
+            let center = Self::get_notification_center();
+            center.removeDeliveredNotificationsWithIdentifiers(&ids);
+            center.removePendingNotificationRequestsWithIdentifiers(&ids);
+        }
+        #[cfg(not(target_os = "macos"))]
+        let _ = notification_id;
+        Ok(())
+    }
+
+    fn subscribe(&self) -> Option<flume::Receiver<NotificationEvent>> {
+        // Install early: a click that launches or activates the app is
+        // delivered to whichever delegate is set at that moment
+        #[cfg(target_os = "macos")]
+        self.install_delegate();
+        Some(self.events_rx.clone())
+    }
+
     fn platform_name(&self) -> &'static str {
         "macOS"
     }
@@ -182,3 +412,23 @@ impl Default for MacOSBackend {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_category_spec_is_keyed_by_actions() {
+        let content = NotificationContent::new("Done", "All good")
+            .with_action(DEFAULT_ACTION, "Open conversation")
+            .with_inline_reply("Reply to Researcher");
+        let spec = CategorySpec::for_content(&content).unwrap();
+        assert_eq!(
+            spec.identifier(),
+            "cyrup|default=Open conversation|inline-reply=Reply to Researcher"
+        );
+
+        let plain = NotificationContent::new("Done", "All good");
+        assert_eq!(CategorySpec::for_content(&plain), None);
+    }
+}
@@ -1,20 +1,28 @@
 //! Native OS notifications for agent responses
 //!
 //! Adapted from ecs-notifications with Bevy ECS removed.
-//! Provides simple notification delivery for macOS using
-//! UserNotifications framework.
+//! Provides simple notification delivery for macOS using the
+//! UserNotifications framework and for Linux using the freedesktop
+//! notification service over D-Bus.
 //!
 //! # Design Decision Q29
 //! Triggers native OS notification when agent finishes responding.
 
+pub mod agent_completion;
 pub mod backend;
 pub mod content;
 pub mod error;
+pub mod policy;
 pub mod service;
 
+#[cfg(target_os = "linux")]
+pub mod linux_backend;
 #[cfg(target_os = "macos")]
 pub mod macos_backend;
 
 // Re-export public API
+pub use backend::{NotificationEvent, NotificationRequest};
+pub use content::{NotificationAction, NotificationContent, Urgency};
 pub use error::{NotificationError, NotificationResult};
+pub use policy::{NotificationPolicy, QuietHours, set_app_focused};
 pub use service::NotificationService;
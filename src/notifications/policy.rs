@@ -0,0 +1,195 @@
+//! When agent-completion notifications are allowed to fire
+//!
+//! Each conversation has a [`NotificationPolicy`]; [`QuietHours`] apply
+//! globally on top of it. The decision itself is a pure function so it can
+//! be tested without a window or a notification server.
+
+use chrono::NaiveTime;
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// Whether any application window currently has keyboard focus
+static APP_FOCUSED: AtomicBool = AtomicBool::new(true);
+
+/// Record window focus changes (fed from `AppEvent::FocusChange`)
+pub fn set_app_focused(focused: bool) {
+    APP_FOCUSED.store(focused, Ordering::Relaxed);
+}
+
+/// Whether the application currently has focus
+pub fn app_focused() -> bool {
+    APP_FOCUSED.load(Ordering::Relaxed)
+}
+
+/// Per-conversation notification policy
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum NotificationPolicy {
+    /// Notify for every agent reply
+    #[default]
+    Always,
+    /// Notify only while the app is in the background
+    WhenUnfocused,
+    /// Notify only for replies from agents the user @mentioned
+    MentionsOnly,
+    /// Never notify
+    Muted,
+}
+
+impl NotificationPolicy {
+    /// Every policy, in the order shown in the UI
+    pub const ALL: [NotificationPolicy; 4] = [
+        NotificationPolicy::Always,
+        NotificationPolicy::WhenUnfocused,
+        NotificationPolicy::MentionsOnly,
+        NotificationPolicy::Muted,
+    ];
+
+    /// Storage key (matches the serde representation)
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            NotificationPolicy::Always => "always",
+            NotificationPolicy::WhenUnfocused => "when_unfocused",
+            NotificationPolicy::MentionsOnly => "mentions_only",
+            NotificationPolicy::Muted => "muted",
+        }
+    }
+
+    /// Parse a storage key, falling back to the default for unknown values
+    pub fn from_key(key: &str) -> Self {
+        Self::ALL
+            .into_iter()
+            .find(|policy| policy.as_str() == key)
+            .unwrap_or_default()
+    }
+
+    /// Label for menus and preferences
+    pub fn label(&self) -> &'static str {
+        match self {
+            NotificationPolicy::Always => "Always",
+            NotificationPolicy::WhenUnfocused => "Only when unfocused",
+            NotificationPolicy::MentionsOnly => "Mentions only",
+            NotificationPolicy::Muted => "Muted",
+        }
+    }
+}
+
+/// Daily do-not-disturb window in local time
+///
+/// `start > end` wraps past midnight (22:00–07:00). `start == end` is empty.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct QuietHours {
+    pub start: NaiveTime,
+    pub end: NaiveTime,
+}
+
+impl QuietHours {
+    /// True if `time` falls inside the window (start inclusive, end exclusive)
+    pub fn contains(&self, time: NaiveTime) -> bool {
+        if self.start <= self.end {
+            self.start <= time && time < self.end
+        } else {
+            time >= self.start || time < self.end
+        }
+    }
+}
+
+/// Circumstances of one agent reply
+#[derive(Debug, Clone, Copy)]
+pub struct DeliveryContext {
+    /// Whether the app had focus when the reply finished
+    pub app_focused: bool,
+    /// Whether the user @mentioned the replying agent
+    pub mentioned: bool,
+    /// Local wall-clock time
+    pub now: NaiveTime,
+}
+
+/// Decide whether a reply should produce a notification
+pub fn should_notify(
+    policy: NotificationPolicy,
+    quiet_hours: Option<QuietHours>,
+    context: DeliveryContext,
+) -> bool {
+    if quiet_hours.is_some_and(|quiet| quiet.contains(context.now)) {
+        return false;
+    }
+
+    match policy {
+        NotificationPolicy::Always => true,
+        NotificationPolicy::WhenUnfocused => !context.app_focused,
+        NotificationPolicy::MentionsOnly => context.mentioned,
+        NotificationPolicy::Muted => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn time(h: u32, m: u32) -> NaiveTime {
+        NaiveTime::from_hms_opt(h, m, 0).unwrap()
+    }
+
+    fn context(app_focused: bool, mentioned: bool) -> DeliveryContext {
+        DeliveryContext {
+            app_focused,
+            mentioned,
+            now: time(12, 0),
+        }
+    }
+
+    #[test]
+    fn test_policies() {
+        use NotificationPolicy::*;
+
+        assert!(should_notify(Always, None, context(true, false)));
+        assert!(!should_notify(WhenUnfocused, None, context(true, true)));
+        assert!(should_notify(WhenUnfocused, None, context(false, false)));
+        assert!(!should_notify(MentionsOnly, None, context(false, false)));
+        assert!(should_notify(MentionsOnly, None, context(true, true)));
+        assert!(!should_notify(Muted, None, context(false, true)));
+    }
+
+    #[test]
+    fn test_quiet_hours_override_policy() {
+        let lunch = QuietHours {
+            start: time(11, 30),
+            end: time(13, 0),
+        };
+        assert!(!should_notify(
+            NotificationPolicy::Always,
+            Some(lunch),
+            context(false, true)
+        ));
+    }
+
+    #[test]
+    fn test_quiet_hours_wrap_midnight() {
+        let night = QuietHours {
+            start: time(22, 0),
+            end: time(7, 0),
+        };
+        assert!(night.contains(time(23, 15)));
+        assert!(night.contains(time(3, 0)));
+        assert!(!night.contains(time(7, 0)));
+        assert!(!night.contains(time(12, 0)));
+
+        let empty = QuietHours {
+            start: time(9, 0),
+            end: time(9, 0),
+        };
+        assert!(!empty.contains(time(9, 0)));
+    }
+
+    #[test]
+    fn test_policy_keys_round_trip() {
+        for policy in NotificationPolicy::ALL {
+            assert_eq!(NotificationPolicy::from_key(policy.as_str()), policy);
+        }
+        assert_eq!(
+            NotificationPolicy::from_key("unknown"),
+            NotificationPolicy::Always
+        );
+    }
+}
@@ -2,39 +2,96 @@
 //!
 //! Provides simple API for OS notification delivery.
 
-use super::backend::{NotificationRequest, PlatformBackend};
+use super::backend::{NotificationEvent, NotificationRequest, PlatformBackend};
 use super::content::NotificationContent;
 use super::error::NotificationResult;
+use std::sync::{Arc, OnceLock};
 
+#[cfg(target_os = "linux")]
+use super::linux_backend::LinuxBackend;
 #[cfg(target_os = "macos")]
 use super::macos_backend::MacOSBackend;
 
+static SHARED: OnceLock<NotificationService> = OnceLock::new();
+
 /// Notification service for agent responses
 ///
 /// Handles platform-specific notification delivery with compile-time
 /// backend selection.
+#[derive(Clone)]
 pub struct NotificationService {
-    backend: Box<dyn PlatformBackend>,
+    backend: Arc<dyn PlatformBackend>,
 }
 
 impl NotificationService {
     /// Create new notification service with platform backend
     pub fn new() -> Self {
-        let backend: Box<dyn PlatformBackend> = {
+        let backend: Arc<dyn PlatformBackend> = {
             #[cfg(target_os = "macos")]
             {
-                Box::new(MacOSBackend::new())
+                Arc::new(MacOSBackend::new())
+            }
+
+            #[cfg(target_os = "linux")]
+            {
+                Arc::new(LinuxBackend::new())
             }
 
-            #[cfg(not(target_os = "macos"))]
+            #[cfg(not(any(target_os = "macos", target_os = "linux")))]
             {
-                compile_error!("Notifications only supported on macOS currently")
+                compile_error!("Notifications only supported on macOS and Linux currently")
             }
         };
 
         Self { backend }
     }
 
+    /// Service with an explicit backend (tests, alternative transports)
+    pub fn with_backend(backend: Arc<dyn PlatformBackend>) -> Self {
+        Self { backend }
+    }
+
+    /// Process-wide service
+    ///
+    /// Backends keep per-notification state (replacement ids, signal
+    /// subscriptions), so updates and action events only work through a
+    /// single long-lived instance.
+    pub fn shared() -> &'static Self {
+        SHARED.get_or_init(Self::new)
+    }
+
+    /// Deliver (or update in place) a notification
+    ///
+    /// # Errors
+    /// Returns error if the content is invalid or delivery fails
+    pub async fn deliver(&self, request: &NotificationRequest) -> NotificationResult<()> {
+        request
+            .content
+            .validate()
+            .map_err(super::error::NotificationError::InvalidContent)?;
+
+        self.backend.deliver_notification(request).await?;
+        Ok(())
+    }
+
+    /// Withdraw a delivered notification
+    ///
+    /// # Errors
+    /// Returns error if the platform rejects the request
+    pub async fn close(&self, notification_id: &str) -> NotificationResult<()> {
+        self.backend.close_notification(notification_id).await
+    }
+
+    /// Name of the platform backend (stored on delivery receipts)
+    pub fn platform_name(&self) -> &'static str {
+        self.backend.platform_name()
+    }
+
+    /// Receiver for action clicks and dismissals, if the platform reports them
+    pub fn subscribe(&self) -> Option<flume::Receiver<NotificationEvent>> {
+        self.backend.subscribe()
+    }
+
     /// Send OS notification with title and body
     ///
     /// # Arguments
@@ -57,20 +114,14 @@ impl NotificationService {
         title: impl Into<String>,
         body: impl Into<String>,
     ) -> NotificationResult<()> {
-        let service = Self::new();
-
-        // Create content and validate
         let content = NotificationContent::new(title, body);
-        content
-            .validate()
-            .map_err(super::error::NotificationError::InvalidContent)?;
 
         // Generate unique notification ID
         let notification_id = uuid::Uuid::new_v4().to_string();
         let request = NotificationRequest::new(notification_id, content);
 
         // Deliver notification
-        service.backend.deliver_notification(&request).await?;
+        Self::shared().deliver(&request).await?;
 
         log::info!("OS notification delivered successfully");
         Ok(())
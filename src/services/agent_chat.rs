@@ -1,18 +1,21 @@
-//! Stateless agent chat service using ClaudeSDKClient
+//! Stateless agent chat service
 //!
 //! Implements database-driven chat with:
-//! - Fresh subprocess per message using resume parameter
-//! - Lazy spawn pattern via conversation.agent_session_id
+//! - Pluggable inference backends chosen per template (src/services/llm)
+//! - Sessions resumed per agent via conversation.agent_sessions
 //! - Streaming responses via INSERT + UPDATE pattern
 //! - LIVE QUERY subscribers receive Action::Update automatically
 
 use crate::database::Database;
+use crate::notifications::agent_completion::{AgentCompletion, notify_agent_completion};
+use crate::services::external_mcp;
+use crate::services::llm::{
+    AgentEvent, BackendResolver, ChatRequest, EventStream, TemplateBackends,
+};
+use crate::view_model::agent::AgentTemplate;
 use crate::view_model::message::{AuthorType, Message, MessageType};
 use flume::{Receiver, Sender, unbounded};
 use futures_util::stream::{FuturesUnordered, StreamExt}; // For concurrent agent execution
-use kodegen_tools_claude_agent::{
-    ClaudeAgentOptions, ClaudeSDKClient, ContentBlock, Message as AgentMessage, SystemPrompt,
-};
 use std::sync::{Arc, OnceLock};
 use surrealdb_types::{RecordId, ToSql};
 use tokio::time::{Duration, Instant};
@@ -37,7 +40,9 @@ pub fn get_tool_event_channel() -> &'static (Sender<String>, Receiver<String>) {
 /// * `database` - Database connection
 /// * `conversation_id` - Conversation ID
 /// * `user_message` - User message content (may contain @mentions for multi-agent)
-/// * `mentioned_agents` - Optional agent IDs to route to (empty = all participants)
+/// * `target_agents` - Optional agent IDs to route to (`None` = all participants)
+/// * `mentioned` - The user @mentioned `target_agents` in the message, which
+///   lets their replies through mention-only notification policies
 /// * `parent_message_id` - Optional parent message ID for threading
 ///
 /// # Errors
@@ -47,8 +52,8 @@ pub fn get_tool_event_channel() -> &'static (Sender<String>, Receiver<String>) {
 /// Single agent (participants.len() == 1):
 /// 1. INSERT user message to database
 /// 2. GET conversation (includes agent_sessions HashMap and participants Vec)
-/// 3. GET template for model/system_prompt/max_turns
-/// 4. CREATE ClaudeSDKClient with resume from agent_sessions[agent_id]
+/// 3. GET template for backend/model/system_prompt/max_turns
+/// 4. Start a turn on the template's backend, resuming agent_sessions[agent_id]
 /// 5. Stream responses with debouncing (100ms OR 50 chars)
 /// 6. Store session_id via update_agent_session()
 ///
@@ -62,7 +67,33 @@ pub async fn send_message(
     database: Arc<Database>,
     conversation_id: RecordId,
     user_message: String,
-    mentioned_agents: Option<Vec<RecordId>>,
+    target_agents: Option<Vec<RecordId>>,
+    mentioned: bool,
+    parent_message_id: Option<RecordId>,
+) -> Result<(), String> {
+    send_message_with(
+        &TemplateBackends,
+        database,
+        conversation_id,
+        user_message,
+        target_agents,
+        mentioned,
+        parent_message_id,
+    )
+    .await
+}
+
+/// [`send_message`] with the backends chosen by `backends`
+///
+/// # Errors
+/// Returns error if message cannot be sent or database operations fail
+pub async fn send_message_with(
+    backends: &dyn BackendResolver,
+    database: Arc<Database>,
+    conversation_id: RecordId,
+    user_message: String,
+    target_agents: Option<Vec<RecordId>>,
+    mentioned: bool,
     parent_message_id: Option<RecordId>,
 ) -> Result<(), String> {
     // 1. Save user message to database
@@ -87,8 +118,8 @@ pub async fn send_message(
     let conversation = database.get_conversation(&conversation_id).await?;
 
     // 3. Determine which agents to message
-    let target_agents: Vec<RecordId> = if let Some(agents) = mentioned_agents {
-        // Multi-agent: Use @mentioned agents
+    let target_agents: Vec<RecordId> = if let Some(agents) = target_agents {
+        // Explicit targets: @mentioned agents, or the agent a reply addresses
         agents
     } else if conversation.participants.len() == 1 {
         // Single-agent: Use the one participant
@@ -114,23 +145,27 @@ pub async fn send_message(
         // Single agent path: Direct execution with session persistence
         let agent_id = &target_agents[0];
         send_to_single_agent(
+            backends,
             database,
             conversation_id,
             user_message,
             user_msg_id,
             agent_id,
             conversation.agent_sessions.get(&agent_id.to_sql()).cloned(),
+            mentioned,
         )
         .await
     } else {
         // Multi-agent path: Concurrent execution with FuturesUnordered
         send_to_multiple_agents(
+            backends,
             database,
             conversation_id,
             user_message,
             user_msg_id,
             target_agents,
             conversation.agent_sessions,
+            mentioned,
         )
         .await
     }
@@ -138,71 +173,81 @@ pub async fn send_message(
 
 /// Single agent message handler with session persistence
 async fn send_to_single_agent(
+    backends: &dyn BackendResolver,
     database: Arc<Database>,
     conversation_id: RecordId,
     user_message: String,
     user_msg_id: RecordId,
     agent_id: &RecordId,
     existing_session_id: Option<String>,
+    mentioned: bool,
 ) -> Result<(), String> {
     log::debug!("[Chat] Single agent mode: {}", agent_id.to_sql());
 
     // Get agent template
     let template = database.get_template(agent_id).await?;
 
-    // Create ClaudeSDKClient (fresh subprocess each time)
-    let options = ClaudeAgentOptions {
-        model: Some(template.model.to_string()),
-        system_prompt: Some(SystemPrompt::String(template.system_prompt.clone())),
-        max_turns: Some(template.max_turns),
-        // Enable core development tools
-        allowed_tools: vec![
-            "Read".into(),      // Read files
-            "Write".into(),     // Write files
-            "Edit".into(),      // Edit files
-            "Bash".into(),      // Execute commands
-            "Glob".into(),      // Find files by pattern
-            "Grep".into(),      // Search file contents
-            "Task".into(),      // Spawn sub-agents
-            "WebFetch".into(),  // Fetch web content
-            "WebSearch".into(), // Search the web
-        ],
-        // Resume from previous session if exists (lazy spawn pattern)
-        resume: existing_session_id
-            .as_ref()
-            .map(|id| kodegen_tools_claude_agent::types::identifiers::SessionId::from(id.as_str())),
-        ..Default::default()
-    };
-
-    let mut client = ClaudeSDKClient::new(options, None)
-        .await
-        .map_err(|e| format!("Failed to create Claude client: {}", e))?;
-
-    // Send message
-    client
-        .send_message(&user_message)
-        .await
-        .map_err(|e| format!("Failed to send to agent: {}", e))?;
+    let events = start_turn(
+        backends,
+        &database,
+        &template,
+        user_message,
+        existing_session_id,
+    )
+    .await?;
 
     // Stream responses
     stream_agent_responses(
-        client,
+        events,
         database,
         conversation_id,
         user_msg_id,
         agent_id.clone(),
+        mentioned,
     )
     .await
 }
 
+/// Start a turn on the template's backend
+///
+/// Resumes `session_id` (fresh process/request each time, lazy spawn
+/// pattern) with the template's model, prompt and enabled MCP servers.
+async fn start_turn(
+    backends: &dyn BackendResolver,
+    database: &Database,
+    template: &AgentTemplate,
+    user_message: String,
+    session_id: Option<String>,
+) -> Result<EventStream, String> {
+    let request = ChatRequest {
+        model: template.model_name(),
+        system_prompt: template.system_prompt.clone(),
+        max_turns: template.max_turns,
+        // User-registered MCP servers enabled on this template
+        mcp: external_mcp::agent_mcp(database, &template.id).await,
+        session_id,
+        message: user_message,
+    };
+
+    let backend = backends.backend(template);
+    log::debug!(
+        "[Chat] Starting {} turn for {}",
+        backend.name(),
+        template.id.to_sql()
+    );
+    backend.chat(request).await
+}
+
 /// Multi-agent message handler with concurrent execution
 async fn send_to_multiple_agents(
+    backends: &dyn BackendResolver,
     database: Arc<Database>,
     conversation_id: RecordId,
     user_message: String,
     user_msg_id: RecordId,
     target_agents: Vec<RecordId>,
     agent_sessions: std::collections::HashMap<String, String>,
+    mentioned: bool,
 ) -> Result<(), String> {
     log::info!("[Chat] Multi-agent mode: {} agents", target_agents.len());
 
@@ -222,52 +267,26 @@ async fn send_to_multiple_agents(
             // Get agent template
             let template = database.get_template(&agent_id).await?;
 
-            // Create ClaudeSDKClient
-            let options = ClaudeAgentOptions {
-                model: Some(template.model.to_string().to_lowercase()),
-                system_prompt: Some(SystemPrompt::String(template.system_prompt.clone())),
-                max_turns: Some(template.max_turns),
-                allowed_tools: vec![
-                    "Read".into(),
-                    "Write".into(),
-                    "Edit".into(),
-                    "Bash".into(),
-                    "Glob".into(),
-                    "Grep".into(),
-                    "Task".into(),
-                    "WebFetch".into(),
-                    "WebSearch".into(),
-                ],
-                // Resume from previous session if exists
-                resume: existing_session_id.as_ref().map(|id| {
-                    kodegen_tools_claude_agent::types::identifiers::SessionId::from(id.as_str())
-                }),
-                ..Default::default()
-            };
-
-            let mut client = ClaudeSDKClient::new(options, None).await.map_err(|e| {
-                format!(
-                    "Failed to create Claude client for {}: {}",
-                    agent_id.to_sql(),
-                    e
-                )
-            })?;
-
             log::debug!("[Chat] Sending message to agent {}", agent_id.to_sql());
 
-            // Send message
-            client
-                .send_message(&user_message)
-                .await
-                .map_err(|e| format!("Failed to send to agent {}: {}", agent_id.to_sql(), e))?;
+            let events = start_turn(
+                backends,
+                &database,
+                &template,
+                user_message,
+                existing_session_id,
+            )
+            .await
+            .map_err(|e| format!("{}: {}", agent_id.to_sql(), e))?;
 
             // Stream responses
             stream_agent_responses(
-                client,
+                events,
                 database,
                 conversation_id,
                 user_msg_id,
                 agent_id.clone(),
+                mentioned,
             )
             .await
         });
@@ -316,14 +335,17 @@ async fn send_to_multiple_agents(
 
 /// Stream agent responses and update database
 ///
-/// Consumes ClaudeSDKClient stream and updates database with responses.
+/// Consumes a backend's event stream and updates database with responses.
 /// LIVE QUERY subscribers receive notifications automatically.
+/// A successful reply triggers an agent-completion notification, subject to
+/// the conversation's notification policy.
 async fn stream_agent_responses(
-    mut client: ClaudeSDKClient,
+    mut events: EventStream,
     database: Arc<Database>,
     conversation_id: RecordId,
     user_msg_id: RecordId,
     agent_id: RecordId,
+    mentioned: bool,
 ) -> Result<(), String> {
     let mut accumulated_text = String::new();
     let mut message_id: Option<RecordId> = None;
@@ -335,127 +357,100 @@ async fn stream_agent_responses(
     let mut chars_since_last_update: usize = 0;
     const MIN_CHARS_FOR_UPDATE: usize = 50;
 
-    // Process stream messages
-    while let Some(message) = client.next_message().await {
-        match message {
-            Ok(AgentMessage::Assistant {
-                message,
-                session_id: sid,
-                ..
-            }) => {
+    // Process stream events
+    while let Some(event) = events.next().await {
+        match event {
+            Ok(AgentEvent::Session(sid)) => {
                 // Store session_id for later
-                if let Some(sid) = sid {
-                    session_id = Some(sid.as_str().to_string());
-                }
-
-                // Iterate through content blocks
-                for block in &message.content {
-                    match block {
-                        ContentBlock::Text { text } => {
-                            // Accumulate text chunks
-                            let chunk_len = text.len();
-                            accumulated_text.push_str(text);
-                            chars_since_last_update += chunk_len;
-
-                            // Debouncing logic: Only update if conditions are met
-                            let should_update = message_id.is_none()  // Always insert first chunk
-                                || last_update.elapsed() >= update_interval  // Time-based: 100ms elapsed
-                                || chars_since_last_update >= MIN_CHARS_FOR_UPDATE; // Size-based: 50+ chars
-
-                            if should_update {
-                                // First chunk: INSERT message
-                                if message_id.is_none() {
-                                    let msg = Message {
-                                        id: RecordId::new("message", "temp"),
-                                        conversation_id: conversation_id.clone(),
-                                        author: "Assistant".to_string(),
-                                        author_type: AuthorType::Agent,
-                                        content: accumulated_text.clone(),
-                                        timestamp: chrono::Utc::now().into(),
-                                        in_reply_to: Some(user_msg_id.clone()),
-                                        message_type: MessageType::Normal,
-                                        attachments: Vec::new(),
-                                        unread: true,
-                                        deleted: false,
-                                        pinned: false,
-                                    };
-
-                                    match database.insert_message(&msg).await {
-                                        Ok(id) => {
-                                            message_id = Some(id);
-                                            last_update = Instant::now();
-                                            chars_since_last_update = 0;
-                                            log::debug!(
-                                                "[AgentChat] Inserted initial message: {:?}",
-                                                message_id
-                                            );
-                                        }
-                                        Err(e) => {
-                                            log::error!(
-                                                "[AgentChat] Failed to insert message: {}",
-                                                e
-                                            );
-                                            return Err(e);
-                                        }
-                                    }
-                                } else {
-                                    // Subsequent chunks: UPDATE message (debounced)
-                                    // LIVE QUERY subscribers receive Action::Update automatically
-                                    if let Some(id) = message_id.as_ref() {
-                                        match database
-                                            .update_message_content(id, accumulated_text.clone())
-                                            .await
-                                        {
-                                            Ok(_) => {
-                                                last_update = Instant::now();
-                                                chars_since_last_update = 0;
-                                                log::debug!(
-                                                    "[AgentChat] Updated message with {} chars (debounced)",
-                                                    accumulated_text.len()
-                                                );
-                                            }
-                                            Err(e) => {
-                                                log::error!(
-                                                    "[AgentChat] Failed to update message: {}",
-                                                    e
-                                                );
-                                                return Err(e);
-                                            }
-                                        }
-                                    } else {
-                                        log::error!(
-                                            "[AgentChat] message_id is None in update path"
-                                        );
-                                        return Err(
-                                            "Internal error: message_id not set before update"
-                                                .to_string(),
-                                        );
-                                    }
-                                }
+                session_id = Some(sid);
+            }
+            Ok(AgentEvent::Text(text)) => {
+                // Accumulate text chunks
+                let chunk_len = text.len();
+                accumulated_text.push_str(&text);
+                chars_since_last_update += chunk_len;
+
+                // Debouncing logic: Only update if conditions are met
+                let should_update = message_id.is_none()  // Always insert first chunk
+                    || last_update.elapsed() >= update_interval  // Time-based: 100ms elapsed
+                    || chars_since_last_update >= MIN_CHARS_FOR_UPDATE; // Size-based: 50+ chars
+
+                if should_update {
+                    // First chunk: INSERT message
+                    if message_id.is_none() {
+                        let msg = Message {
+                            id: RecordId::new("message", "temp"),
+                            conversation_id: conversation_id.clone(),
+                            author: "Assistant".to_string(),
+                            author_type: AuthorType::Agent,
+                            content: accumulated_text.clone(),
+                            timestamp: chrono::Utc::now().into(),
+                            in_reply_to: Some(user_msg_id.clone()),
+                            message_type: MessageType::Normal,
+                            attachments: Vec::new(),
+                            unread: true,
+                            deleted: false,
+                            pinned: false,
+                        };
+
+                        match database.insert_message(&msg).await {
+                            Ok(id) => {
+                                message_id = Some(id);
+                                last_update = Instant::now();
+                                chars_since_last_update = 0;
+                                log::debug!(
+                                    "[AgentChat] Inserted initial message: {:?}",
+                                    message_id
+                                );
                             }
-                        }
-                        ContentBlock::ToolUse { name, .. } => {
-                            log::info!("[AgentChat] Agent using tool: {}", name);
-
-                            // Broadcast to UI subscribers
-                            let (sender, _) = get_tool_event_channel();
-                            if let Err(e) = sender.send(name.clone()) {
-                                log::warn!("[AgentChat] Failed to broadcast tool event: {}", e);
+                            Err(e) => {
+                                log::error!("[AgentChat] Failed to insert message: {}", e);
+                                return Err(e);
                             }
                         }
-                        ContentBlock::Thinking { thinking, .. } => {
-                            log::debug!("[AgentChat] Agent thinking: {}", thinking);
+                    } else {
+                        // Subsequent chunks: UPDATE message (debounced)
+                        // LIVE QUERY subscribers receive Action::Update automatically
+                        if let Some(id) = message_id.as_ref() {
+                            match database
+                                .update_message_content(id, accumulated_text.clone())
+                                .await
+                            {
+                                Ok(_) => {
+                                    last_update = Instant::now();
+                                    chars_since_last_update = 0;
+                                    log::debug!(
+                                        "[AgentChat] Updated message with {} chars (debounced)",
+                                        accumulated_text.len()
+                                    );
+                                }
+                                Err(e) => {
+                                    log::error!("[AgentChat] Failed to update message: {}", e);
+                                    return Err(e);
+                                }
+                            }
+                        } else {
+                            log::error!("[AgentChat] message_id is None in update path");
+                            return Err(
+                                "Internal error: message_id not set before update".to_string()
+                            );
                         }
-                        _ => {}
                     }
                 }
             }
-            Ok(AgentMessage::Result {
-                is_error,
-                result,
-                session_id: sid,
-                ..
-            }) => {
+            Ok(AgentEvent::ToolUse { name }) => {
+                log::info!("[AgentChat] Agent using tool: {}", name);
+
+                // Broadcast to UI subscribers
+                let (sender, _) = get_tool_event_channel();
+                if let Err(e) = sender.send(name) {
+                    log::warn!("[AgentChat] Failed to broadcast tool event: {}", e);
+                }
+            }
+            Ok(AgentEvent::Thinking(thinking)) => {
+                log::debug!("[AgentChat] Agent thinking: {}", thinking);
+            }
+            Ok(AgentEvent::Finished(outcome)) => {
                 // IMPORTANT: Flush any pending updates before completing
                 if let Some(id) = message_id.as_ref()
                     && chars_since_last_update > 0
@@ -477,12 +472,8 @@ async fn stream_agent_responses(
                     }
                 }
 
-                // Store session_id from Result
-                session_id = Some(sid.as_str().to_string());
-
                 // Handle completion or error
-                if is_error {
-                    let error_text = result.unwrap_or_else(|| "Unknown error".to_string());
+                if let Err(error_text) = outcome {
                     log::error!("[AgentChat] Agent completed with error: {}", error_text);
 
                     // Save error message
@@ -506,13 +497,25 @@ async fn stream_agent_responses(
                     }
                 } else {
                     log::info!("[AgentChat] Agent completed successfully");
+
+                    if let Some(id) = message_id.clone() {
+                        let completion = AgentCompletion {
+                            conversation_id: conversation_id.clone(),
+                            message_id: id,
+                            agent_id: agent_id.clone(),
+                            mentioned,
+                        };
+                        let database = database.clone();
+                        tokio::spawn(async move {
+                            if let Err(e) = notify_agent_completion(&database, &completion).await {
+                                log::warn!("[AgentChat] Completion notification failed: {}", e);
+                            }
+                        });
+                    }
                 }
 
                 break; // Stream complete
             }
-            Ok(AgentMessage::System { subtype, data }) => {
-                log::debug!("[AgentChat] System message: {} - {:?}", subtype, data);
-            }
             Err(e) => {
                 log::error!("[AgentChat] Stream error: {}", e);
 
@@ -537,7 +540,6 @@ async fn stream_agent_responses(
 
                 break;
             }
-            _ => {}
         }
     }
 
@@ -0,0 +1,80 @@
+//! Bearer token check for API requests
+
+use super::ApiState;
+use super::handlers::ApiError;
+use axum::extract::{Request, State};
+use axum::http::{StatusCode, header};
+use axum::middleware::Next;
+use axum::response::Response;
+
+/// Reject requests without the profile's API token
+///
+/// Accepts only `Authorization: Bearer <token>`.
+pub(super) async fn require_token(
+    State(state): State<ApiState>,
+    request: Request,
+    next: Next,
+) -> Result<Response, ApiError> {
+    let token = bearer_token(&request).map(str::to_owned);
+    check_token(&state, token.as_deref())?;
+    Ok(next.run(request).await)
+}
+
+/// Like [`require_token`], but also accepts a `token` query parameter
+///
+/// Only for the WebSocket route: browser WebSocket clients cannot set
+/// headers. Kept off every other route so tokens do not end up in URLs
+/// that get logged or shared.
+pub(super) async fn require_token_or_query(
+    State(state): State<ApiState>,
+    request: Request,
+    next: Next,
+) -> Result<Response, ApiError> {
+    let token = bearer_token(&request).map(str::to_owned).or_else(|| {
+        request.uri().query().and_then(|query| {
+            url::form_urlencoded::parse(query.as_bytes())
+                .find(|(key, _)| key == "token")
+                .map(|(_, value)| value.into_owned())
+        })
+    });
+    check_token(&state, token.as_deref())?;
+    Ok(next.run(request).await)
+}
+
+fn bearer_token(request: &Request) -> Option<&str> {
+    request
+        .headers()
+        .get(header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "))
+        .map(str::trim)
+}
+
+fn check_token(state: &ApiState, token: Option<&str>) -> Result<(), ApiError> {
+    match token {
+        Some(token) if constant_time_eq(token.as_bytes(), state.token.as_bytes()) => Ok(()),
+        Some(_) => Err(ApiError::new(StatusCode::UNAUTHORIZED, "Invalid API token")),
+        None => Err(ApiError::new(StatusCode::UNAUTHORIZED, "Missing API token")),
+    }
+}
+
+/// Compare without returning early on the first differing byte
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+    a.iter().zip(b).fold(0u8, |diff, (x, y)| diff | (x ^ y)) == 0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_constant_time_eq() {
+        assert!(constant_time_eq(b"secret", b"secret"));
+        assert!(!constant_time_eq(b"secret", b"secreT"));
+        assert!(!constant_time_eq(b"secret", b"secret2"));
+        assert!(!constant_time_eq(b"", b"x"));
+    }
+}
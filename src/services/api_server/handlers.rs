@@ -0,0 +1,487 @@
+//! REST handlers and their JSON shapes
+//!
+//! Record IDs are exchanged as strings (`conversation:abc`); path
+//! parameters also accept the bare key (`abc`).
+
+use super::ApiState;
+use crate::database::messages::MessageCursor;
+use crate::services::agent_chat;
+use crate::view_model::agent::AgentTemplate;
+use crate::view_model::conversation::{Conversation, ConversationSummary};
+use crate::view_model::message::{AuthorType, Message, MessageType};
+use axum::Json;
+use axum::extract::{Path, Query, State};
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use surrealdb_types::{RecordId, ToSql};
+use utoipa::{IntoParams, ToSchema};
+
+/// Messages returned per page unless `limit` is given
+const DEFAULT_PAGE: usize = 50;
+/// Largest page a client may request
+const MAX_PAGE: usize = 200;
+
+/// Error body returned with every non-2xx response
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ErrorBody {
+    pub error: String,
+}
+
+/// Handler error carrying an HTTP status
+#[derive(Debug)]
+pub struct ApiError {
+    status: StatusCode,
+    message: String,
+}
+
+impl ApiError {
+    pub fn new(status: StatusCode, message: impl Into<String>) -> Self {
+        Self {
+            status,
+            message: message.into(),
+        }
+    }
+
+    fn bad_request(message: impl Into<String>) -> Self {
+        Self::new(StatusCode::BAD_REQUEST, message)
+    }
+}
+
+/// Database errors: "... not found ..." becomes 404, anything else 500
+impl From<String> for ApiError {
+    fn from(message: String) -> Self {
+        let status = if message.contains("not found") {
+            StatusCode::NOT_FOUND
+        } else {
+            log::error!("[ApiServer] {}", message);
+            StatusCode::INTERNAL_SERVER_ERROR
+        };
+        Self { status, message }
+    }
+}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        (
+            self.status,
+            Json(ErrorBody {
+                error: self.message,
+            }),
+        )
+            .into_response()
+    }
+}
+
+/// Agent template
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ApiTemplate {
+    pub id: String,
+    pub name: String,
+    /// `claude`, `openai` or `local`
+    pub backend: String,
+    /// `sonnet`, `haiku` or `opus` on Claude, otherwise the backend's model name
+    pub model: String,
+    pub max_turns: u32,
+}
+
+impl From<AgentTemplate> for ApiTemplate {
+    fn from(template: AgentTemplate) -> Self {
+        Self {
+            id: template.id.to_sql(),
+            backend: template.backend.to_string(),
+            model: template.model_name(),
+            name: template.name,
+            max_turns: template.max_turns,
+        }
+    }
+}
+
+/// Conversation list entry
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ApiConversationSummary {
+    pub id: String,
+    pub title: String,
+    pub participants: Vec<String>,
+    pub last_message_preview: String,
+    pub last_message_at: DateTime<Utc>,
+    pub unread_count: u32,
+}
+
+impl From<ConversationSummary> for ApiConversationSummary {
+    fn from(summary: ConversationSummary) -> Self {
+        Self {
+            id: summary.id.to_sql(),
+            title: summary.title,
+            participants: summary.participants.iter().map(ToSql::to_sql).collect(),
+            last_message_preview: summary.last_message_preview,
+            last_message_at: *summary.last_message_timestamp,
+            unread_count: summary.unread_count,
+        }
+    }
+}
+
+/// Conversation details
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ApiConversation {
+    pub id: String,
+    pub title: String,
+    pub summary: String,
+    pub participants: Vec<String>,
+    pub last_message_at: DateTime<Utc>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl From<Conversation> for ApiConversation {
+    fn from(conversation: Conversation) -> Self {
+        Self {
+            id: conversation.id.to_sql(),
+            title: conversation.title,
+            summary: conversation.summary,
+            participants: conversation
+                .participants
+                .iter()
+                .map(ToSql::to_sql)
+                .collect(),
+            last_message_at: *conversation.last_message_at,
+            created_at: *conversation.created_at,
+        }
+    }
+}
+
+/// Chat message
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct ApiMessage {
+    pub id: String,
+    pub conversation_id: String,
+    pub author: String,
+    /// `human`, `agent`, `system` or `tool`
+    pub author_type: String,
+    /// `normal`, `error`, `system` or `tool`
+    pub message_type: String,
+    pub content: String,
+    pub timestamp: DateTime<Utc>,
+    pub in_reply_to: Option<String>,
+    pub pinned: bool,
+    pub unread: bool,
+}
+
+impl From<Message> for ApiMessage {
+    fn from(message: Message) -> Self {
+        let author_type = match message.author_type {
+            AuthorType::Human => "human",
+            AuthorType::Agent => "agent",
+            AuthorType::System => "system",
+            AuthorType::Tool => "tool",
+        };
+        let message_type = match message.message_type {
+            MessageType::Normal => "normal",
+            MessageType::Error => "error",
+            MessageType::System => "system",
+            MessageType::Tool => "tool",
+        };
+
+        Self {
+            id: message.id.to_sql(),
+            conversation_id: message.conversation_id.to_sql(),
+            author: message.author,
+            author_type: author_type.to_string(),
+            message_type: message_type.to_string(),
+            content: message.content,
+            timestamp: *message.timestamp,
+            in_reply_to: message.in_reply_to.as_ref().map(ToSql::to_sql),
+            pinned: message.pinned,
+            unread: message.unread,
+        }
+    }
+}
+
+/// One page of messages, oldest first
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ApiMessagePage {
+    pub messages: Vec<ApiMessage>,
+    /// True if older messages exist before this page
+    pub has_more: bool,
+}
+
+/// New conversation
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct CreateConversation {
+    pub title: String,
+    /// Template names or IDs (at least one)
+    pub participants: Vec<String>,
+}
+
+/// New user message
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct PostMessage {
+    pub content: String,
+    /// Template names or IDs to address; all participants when omitted
+    #[serde(default)]
+    pub agents: Option<Vec<String>>,
+    /// Message ID this one replies to
+    #[serde(default)]
+    pub in_reply_to: Option<String>,
+}
+
+/// Acknowledgement for a sent message
+#[derive(Debug, Serialize, ToSchema)]
+pub struct MessageAccepted {
+    pub conversation_id: String,
+}
+
+/// Paging for `GET /conversations/{id}/messages`
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct MessageQuery {
+    /// Page size (default 50, max 200)
+    pub limit: Option<usize>,
+    /// Return messages older than this message ID, which must belong to
+    /// the conversation
+    pub before: Option<String>,
+}
+
+/// Parse a path ID, accepting `table:key` or a bare key
+pub(super) fn parse_id(table: &str, input: &str) -> Result<RecordId, ApiError> {
+    if input.contains(':') {
+        RecordId::parse_simple(input)
+            .map_err(|e| ApiError::bad_request(format!("Invalid ID: {}", e)))
+    } else {
+        Ok(RecordId::new(table, input))
+    }
+}
+
+/// List agent templates
+#[utoipa::path(
+    get,
+    path = "/api/v1/templates",
+    tag = "templates",
+    responses((status = 200, body = Vec<ApiTemplate>)),
+    security(("bearer" = []))
+)]
+pub(super) async fn list_templates(
+    State(state): State<ApiState>,
+) -> Result<Json<Vec<ApiTemplate>>, ApiError> {
+    let templates = state.database.list_templates().await?;
+    Ok(Json(templates.into_iter().map(ApiTemplate::from).collect()))
+}
+
+/// List conversations, most recent first
+#[utoipa::path(
+    get,
+    path = "/api/v1/conversations",
+    tag = "conversations",
+    responses((status = 200, body = Vec<ApiConversationSummary>)),
+    security(("bearer" = []))
+)]
+pub(super) async fn list_conversations(
+    State(state): State<ApiState>,
+) -> Result<Json<Vec<ApiConversationSummary>>, ApiError> {
+    let conversations = state.database.list_conversations().await?;
+    Ok(Json(
+        conversations
+            .into_iter()
+            .map(ApiConversationSummary::from)
+            .collect(),
+    ))
+}
+
+/// Start a conversation
+#[utoipa::path(
+    post,
+    path = "/api/v1/conversations",
+    tag = "conversations",
+    request_body = CreateConversation,
+    responses(
+        (status = 201, body = ApiConversation),
+        (status = 400, body = ErrorBody),
+        (status = 404, description = "Unknown participant", body = ErrorBody)
+    ),
+    security(("bearer" = []))
+)]
+pub(super) async fn create_conversation(
+    State(state): State<ApiState>,
+    Json(request): Json<CreateConversation>,
+) -> Result<(StatusCode, Json<ApiConversation>), ApiError> {
+    if request.participants.is_empty() {
+        return Err(ApiError::bad_request(
+            "At least one participant is required",
+        ));
+    }
+
+    let mut participants = Vec::with_capacity(request.participants.len());
+    for participant in &request.participants {
+        participants.push(state.database.find_template(participant).await?.id);
+    }
+
+    let now = Utc::now();
+    let id = state
+        .database
+        .create_conversation(&Conversation {
+            title: request.title,
+            participants,
+            last_message_at: now.into(),
+            ..Default::default()
+        })
+        .await?;
+
+    let conversation = state.database.get_conversation(&id).await?;
+    Ok((StatusCode::CREATED, Json(conversation.into())))
+}
+
+/// Get one conversation
+#[utoipa::path(
+    get,
+    path = "/api/v1/conversations/{id}",
+    tag = "conversations",
+    params(("id" = String, Path, description = "Conversation ID")),
+    responses((status = 200, body = ApiConversation), (status = 404, body = ErrorBody)),
+    security(("bearer" = []))
+)]
+pub(super) async fn get_conversation(
+    State(state): State<ApiState>,
+    Path(id): Path<String>,
+) -> Result<Json<ApiConversation>, ApiError> {
+    let id = parse_id("conversation", &id)?;
+    let conversation = state.database.get_conversation(&id).await?;
+    Ok(Json(conversation.into()))
+}
+
+/// Page through a conversation's messages, newest page first
+#[utoipa::path(
+    get,
+    path = "/api/v1/conversations/{id}/messages",
+    tag = "messages",
+    params(("id" = String, Path, description = "Conversation ID"), MessageQuery),
+    responses(
+        (status = 200, body = ApiMessagePage),
+        (status = 400, description = "Cursor from another conversation", body = ErrorBody),
+        (status = 404, body = ErrorBody)
+    ),
+    security(("bearer" = []))
+)]
+pub(super) async fn list_messages(
+    State(state): State<ApiState>,
+    Path(id): Path<String>,
+    Query(query): Query<MessageQuery>,
+) -> Result<Json<ApiMessagePage>, ApiError> {
+    let id = parse_id("conversation", &id)?;
+    state.database.get_conversation(&id).await?;
+
+    let limit = query.limit.unwrap_or(DEFAULT_PAGE).clamp(1, MAX_PAGE);
+    let cursor = match query.before {
+        Some(before) => {
+            let message = state
+                .database
+                .get_message(&parse_id("message", &before)?)
+                .await?;
+            if message.conversation_id != id {
+                return Err(ApiError::bad_request(format!(
+                    "{} is not in this conversation",
+                    before
+                )));
+            }
+            Some(MessageCursor::from(&message))
+        }
+        None => None,
+    };
+
+    let page = state
+        .database
+        .get_messages_before(&id, cursor.as_ref(), limit)
+        .await?;
+
+    Ok(Json(ApiMessagePage {
+        messages: page.messages.into_iter().map(ApiMessage::from).collect(),
+        has_more: page.has_more,
+    }))
+}
+
+/// Post a user message and wait for the agents to answer
+///
+/// Replies stream into the conversation exactly as if the message had been
+/// typed in the app, so WebSocket subscribers see them as they are written.
+/// The response reports whether the send succeeded; a client that
+/// disconnects early does not cancel the agents' turn.
+#[utoipa::path(
+    post,
+    path = "/api/v1/conversations/{id}/messages",
+    tag = "messages",
+    params(("id" = String, Path, description = "Conversation ID")),
+    request_body = PostMessage,
+    responses(
+        (status = 200, body = MessageAccepted),
+        (status = 400, body = ErrorBody),
+        (status = 404, body = ErrorBody),
+        (status = 500, description = "The message could not be sent", body = ErrorBody)
+    ),
+    security(("bearer" = []))
+)]
+pub(super) async fn post_message(
+    State(state): State<ApiState>,
+    Path(id): Path<String>,
+    Json(request): Json<PostMessage>,
+) -> Result<(StatusCode, Json<MessageAccepted>), ApiError> {
+    if request.content.trim().is_empty() {
+        return Err(ApiError::bad_request("Message content is empty"));
+    }
+
+    let id = parse_id("conversation", &id)?;
+    let conversation = state.database.get_conversation(&id).await?;
+
+    let agents = match &request.agents {
+        Some(names) => {
+            let mut agents = Vec::with_capacity(names.len());
+            for name in names {
+                let agent = state.database.find_template(name).await?.id;
+                if !conversation.participants.contains(&agent) {
+                    return Err(ApiError::bad_request(format!(
+                        "{} is not in this conversation",
+                        name
+                    )));
+                }
+                agents.push(agent);
+            }
+            Some(agents)
+        }
+        None if conversation.participants.is_empty() => {
+            return Err(ApiError::bad_request("This conversation has no agents"));
+        }
+        None => None,
+    };
+    let in_reply_to = request
+        .in_reply_to
+        .as_deref()
+        .map(|parent| parse_id("message", parent))
+        .transpose()?;
+
+    // Spawned so the turn outlives a client that disconnects
+    let database = Arc::clone(&state.database);
+    let conversation_id = id.clone();
+    let sent = tokio::spawn(async move {
+        agent_chat::send_message(
+            database,
+            conversation_id,
+            request.content,
+            agents,
+            false,
+            in_reply_to,
+        )
+        .await
+    });
+    sent.await.map_err(|e| {
+        ApiError::new(
+            StatusCode::INTERNAL_SERVER_ERROR,
+            format!("Message task failed: {}", e),
+        )
+    })??;
+
+    Ok((
+        StatusCode::OK,
+        Json(MessageAccepted {
+            conversation_id: id.to_sql(),
+        }),
+    ))
+}
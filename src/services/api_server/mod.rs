@@ -0,0 +1,599 @@
+//! Opt-in local HTTP/WebSocket API
+//!
+//! Lets editors and scripts on the same machine read conversations, post
+//! messages and follow replies. The server listens on 127.0.0.1 only and
+//! every request (except the OpenAPI document) must carry the profile's API
+//! token as `Authorization: Bearer <token>`. Only the WebSocket route also
+//! accepts `?token=<token>`, for clients that cannot set headers.
+//!
+//! Settings live in `api_server.json` and the token in `api_token` (mode
+//! 0600 on Unix) inside the profile's settings directory, so local tools
+//! can read the token without going through the keyring.
+//!
+//! Routes, all under `/api/v1`:
+//! - `GET  /openapi.json` - OpenAPI description generated from the handlers
+//! - `GET  /templates`
+//! - `GET  /conversations`, `POST /conversations`
+//! - `GET  /conversations/{id}`
+//! - `GET  /conversations/{id}/messages`, `POST /conversations/{id}/messages`
+//! - `GET  /conversations/{id}/ws` - LIVE QUERY message notifications
+//!
+//! The MCP server ([`crate::services::mcp_server`]) is mounted at `/mcp`
+//! (streamable HTTP transport) behind the same token.
+
+mod auth;
+mod handlers;
+mod openapi;
+mod ws;
+
+pub use handlers::{ApiConversation, ApiConversationSummary, ApiMessage, ApiTemplate};
+pub use openapi::ApiDoc;
+
+use crate::database::Database;
+use axum::Router;
+use axum::routing::get;
+use serde::{Deserialize, Serialize};
+use std::net::{Ipv4Addr, SocketAddr};
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::io::AsyncWriteExt;
+use tokio::sync::{Mutex, watch};
+use tokio::task::JoinHandle;
+
+/// Default listening port
+pub const DEFAULT_PORT: u16 = 7717;
+
+/// How long [`ApiServer::stop`] waits for in-flight requests
+const SHUTDOWN_GRACE: Duration = Duration::from_secs(2);
+
+const CONFIG_FILE: &str = "api_server.json";
+const TOKEN_FILE: &str = "api_token";
+
+/// Persisted server settings
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ApiServerConfig {
+    pub enabled: bool,
+    pub port: u16,
+}
+
+impl Default for ApiServerConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            port: DEFAULT_PORT,
+        }
+    }
+}
+
+/// Shared state for request handlers
+#[derive(Clone)]
+pub(crate) struct ApiState {
+    pub database: Arc<Database>,
+    pub token: Arc<str>,
+    /// Flips to `true` when the server stops, closing open WebSockets
+    pub shutdown: watch::Receiver<bool>,
+}
+
+/// A running server
+///
+/// [`ApiServer::stop`] shuts it down and waits for it; dropping the handle
+/// also starts a graceful shutdown but does not wait for it to finish.
+pub struct ApiServer {
+    addr: SocketAddr,
+    shutdown: watch::Sender<bool>,
+    task: JoinHandle<()>,
+}
+
+impl ApiServer {
+    /// Bind 127.0.0.1:`port` and serve in the background
+    ///
+    /// # Errors
+    /// Returns error if the port cannot be bound
+    pub async fn start(database: Arc<Database>, port: u16, token: String) -> Result<Self, String> {
+        let listener = tokio::net::TcpListener::bind((Ipv4Addr::LOCALHOST, port))
+            .await
+            .map_err(|e| format!("Could not listen on 127.0.0.1:{}: {}", port, e))?;
+        let addr = listener
+            .local_addr()
+            .map_err(|e| format!("Could not read listening address: {}", e))?;
+
+        let (shutdown, shutdown_rx) = watch::channel(false);
+        let app = router(ApiState {
+            database,
+            token: token.into(),
+            shutdown: shutdown_rx.clone(),
+        });
+
+        let task = tokio::spawn(async move {
+            let mut shutdown_rx = shutdown_rx;
+            let server = axum::serve(listener, app).with_graceful_shutdown(async move {
+                let _ = shutdown_rx.wait_for(|stopped| *stopped).await;
+            });
+            if let Err(e) = server.await {
+                log::error!("[ApiServer] Server error: {}", e);
+            }
+            log::info!("[ApiServer] Stopped");
+        });
+
+        log::info!("[ApiServer] Listening on http://{}", addr);
+        Ok(Self {
+            addr,
+            shutdown,
+            task,
+        })
+    }
+
+    /// Address the server is bound to
+    pub fn addr(&self) -> SocketAddr {
+        self.addr
+    }
+
+    /// Shut down, giving in-flight requests a moment to finish
+    ///
+    /// The port is free again once this returns.
+    pub async fn stop(mut self) {
+        let _ = self.shutdown.send(true);
+        if tokio::time::timeout(SHUTDOWN_GRACE, &mut self.task)
+            .await
+            .is_err()
+        {
+            log::warn!("[ApiServer] Requests still running after shutdown; aborting");
+            self.task.abort();
+            let _ = self.task.await;
+        }
+    }
+}
+
+/// Build the API router
+pub(crate) fn router(state: ApiState) -> Router {
+    let authenticated = Router::new()
+        .route("/templates", get(handlers::list_templates))
+        .route(
+            "/conversations",
+            get(handlers::list_conversations).post(handlers::create_conversation),
+        )
+        .route("/conversations/{id}", get(handlers::get_conversation))
+        .route(
+            "/conversations/{id}/messages",
+            get(handlers::list_messages).post(handlers::post_message),
+        )
+        .route_layer(axum::middleware::from_fn_with_state(
+            state.clone(),
+            auth::require_token,
+        ))
+        .merge(
+            Router::new()
+                .route("/conversations/{id}/ws", get(ws::subscribe))
+                .route_layer(axum::middleware::from_fn_with_state(
+                    state.clone(),
+                    auth::require_token_or_query,
+                )),
+        );
+
+    let api = authenticated.route("/openapi.json", get(openapi::openapi_json));
+
+    let mcp = Router::new()
+        .nest_service(
+            "/mcp",
+            crate::services::mcp_server::http_service(Arc::clone(&state.database)),
+        )
+        .layer(axum::middleware::from_fn_with_state(
+            state.clone(),
+            auth::require_token,
+        ));
+
+    Router::new()
+        .nest("/api/v1", api)
+        .merge(mcp)
+        .with_state(state)
+}
+
+/// Server started by the app; held across [`apply_config`] so changes apply in order
+static RUNNING: Mutex<Option<ApiServer>> = Mutex::const_new(None);
+
+/// Start, restart or stop the app's server to match `config`
+///
+/// # Returns
+/// * `Ok(Some(addr))` - Server running at `addr`
+/// * `Ok(None)` - Server disabled (and stopped if it was running)
+/// * `Err(String)` - Token or port unavailable
+pub async fn apply_config(
+    database: Arc<Database>,
+    config: ApiServerConfig,
+) -> Result<Option<SocketAddr>, String> {
+    let mut running = RUNNING.lock().await;
+    if let Some(server) = running.take() {
+        server.stop().await;
+    }
+
+    if !config.enabled {
+        return Ok(None);
+    }
+
+    let token = load_or_create_token().await?;
+    let server = ApiServer::start(database, config.port, token).await?;
+    let addr = server.addr();
+    *running = Some(server);
+    Ok(Some(addr))
+}
+
+/// Stop the app's server, if it is running
+pub async fn stop_running() {
+    if let Some(server) = RUNNING.lock().await.take() {
+        server.stop().await;
+    }
+}
+
+/// Address of the app's server, if it is running
+pub async fn running_addr() -> Option<SocketAddr> {
+    RUNNING.lock().await.as_ref().map(ApiServer::addr)
+}
+
+/// Start the server at app startup if the profile enabled it
+pub async fn start_if_enabled(database: Arc<Database>) {
+    let config = load_config().await;
+    if config.enabled
+        && let Err(e) = apply_config(database, config).await
+    {
+        log::error!("[ApiServer] Not started: {}", e);
+    }
+}
+
+fn settings_path(file: &str) -> Result<PathBuf, String> {
+    Ok(crate::config::profile::active_profile()?
+        .settings_dir()
+        .join(file))
+}
+
+/// Load this profile's server settings (defaults if none were saved)
+pub async fn load_config() -> ApiServerConfig {
+    let path = match settings_path(CONFIG_FILE) {
+        Ok(path) => path,
+        Err(e) => {
+            log::warn!("[ApiServer] Using default settings: {}", e);
+            return ApiServerConfig::default();
+        }
+    };
+    let Ok(data) = tokio::fs::read(&path).await else {
+        return ApiServerConfig::default();
+    };
+    serde_json::from_slice(&data).unwrap_or_else(|e| {
+        log::warn!("Could not parse {}: {e}", path.display());
+        ApiServerConfig::default()
+    })
+}
+
+/// Save this profile's server settings
+pub async fn save_config(config: &ApiServerConfig) -> Result<(), String> {
+    let path = settings_path(CONFIG_FILE)?;
+    let data = serde_json::to_string_pretty(config)
+        .map_err(|e| format!("Could not serialize API settings: {e}"))?;
+    write_settings_file(&path, data.as_bytes()).await
+}
+
+/// The profile's API token, generated on first use
+pub async fn load_or_create_token() -> Result<String, String> {
+    let path = settings_path(TOKEN_FILE)?;
+    match tokio::fs::read_to_string(&path).await {
+        Ok(token) if !token.trim().is_empty() => Ok(token.trim().to_string()),
+        _ => regenerate_token().await,
+    }
+}
+
+/// Replace the API token
+///
+/// A running server keeps the old token until it is restarted with [`apply_config`].
+pub async fn regenerate_token() -> Result<String, String> {
+    let token = format!(
+        "{}{}",
+        uuid::Uuid::new_v4().simple(),
+        uuid::Uuid::new_v4().simple()
+    );
+    write_settings_file(&settings_path(TOKEN_FILE)?, token.as_bytes()).await?;
+    Ok(token)
+}
+
+/// Location of the token file, for display to the user
+pub fn token_path() -> Result<PathBuf, String> {
+    settings_path(TOKEN_FILE)
+}
+
+async fn write_settings_file(path: &std::path::Path, data: &[u8]) -> Result<(), String> {
+    if let Some(dir) = path.parent() {
+        tokio::fs::create_dir_all(dir)
+            .await
+            .map_err(|e| format!("Could not create {}: {e}", dir.display()))?;
+    }
+    let mut options = tokio::fs::OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    options.mode(0o600);
+
+    let mut file = options
+        .open(path)
+        .await
+        .map_err(|e| format!("Could not write {}: {e}", path.display()))?;
+    file.write_all(data)
+        .await
+        .map_err(|e| format!("Could not write {}: {e}", path.display()))?;
+
+    // Files created before the mode was set keep their old permissions
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        tokio::fs::set_permissions(path, std::fs::Permissions::from_mode(0o600))
+            .await
+            .map_err(|e| format!("Could not restrict {}: {e}", path.display()))?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::view_model::conversation::Conversation;
+    use crate::view_model::message::{AuthorType, Message, MessageType};
+    use axum::body::Body;
+    use axum::http::{Request, StatusCode, header};
+    use futures_util::StreamExt;
+    use http_body_util::BodyExt;
+    use serde_json::{Value, json};
+    use surrealdb_types::{RecordId, ToSql};
+    use tokio_tungstenite::tungstenite::Message as WsMessage;
+    use tower::ServiceExt;
+
+    const TOKEN: &str = "test-token";
+
+    /// Router over a fresh in-memory database; keep the sender alive so
+    /// WebSockets aren't told to shut down
+    async fn app() -> (Router, Arc<Database>, watch::Sender<bool>) {
+        let database = Arc::new(Database::in_memory().await.unwrap());
+        let (shutdown, shutdown_rx) = watch::channel(false);
+        let router = router(ApiState {
+            database: Arc::clone(&database),
+            token: TOKEN.into(),
+            shutdown: shutdown_rx,
+        });
+        (router, database, shutdown)
+    }
+
+    async fn call(
+        router: &Router,
+        request: axum::http::request::Builder,
+        body: Option<Value>,
+    ) -> (StatusCode, Value) {
+        let request = match body {
+            Some(body) => request
+                .header(header::CONTENT_TYPE, "application/json")
+                .body(Body::from(body.to_string())),
+            None => request.body(Body::empty()),
+        };
+        let response = router.clone().oneshot(request.unwrap()).await.unwrap();
+        let status = response.status();
+        let bytes = response.into_body().collect().await.unwrap().to_bytes();
+        (status, serde_json::from_slice(&bytes).unwrap_or_default())
+    }
+
+    fn authorized(method: &str, uri: &str) -> axum::http::request::Builder {
+        Request::builder()
+            .method(method)
+            .uri(uri)
+            .header(header::AUTHORIZATION, format!("Bearer {}", TOKEN))
+    }
+
+    async fn conversation(database: &Database, title: &str) -> RecordId {
+        database
+            .create_conversation(&Conversation {
+                title: title.to_string(),
+                last_message_at: chrono::Utc::now().into(),
+                ..Default::default()
+            })
+            .await
+            .unwrap()
+    }
+
+    async fn message(database: &Database, conversation_id: &RecordId, content: &str) -> RecordId {
+        database
+            .insert_message(&Message {
+                id: RecordId::new("message", "default"),
+                conversation_id: conversation_id.clone(),
+                author: "User".to_string(),
+                author_type: AuthorType::Human,
+                content: content.to_string(),
+                timestamp: chrono::Utc::now().into(),
+                in_reply_to: None,
+                message_type: MessageType::Normal,
+                attachments: Vec::new(),
+                unread: false,
+                deleted: false,
+                pinned: false,
+            })
+            .await
+            .unwrap()
+    }
+
+    fn query_value(id: &RecordId) -> String {
+        url::form_urlencoded::byte_serialize(id.to_sql().as_bytes()).collect()
+    }
+
+    #[tokio::test]
+    async fn test_requests_need_the_token() {
+        let (router, _database, _shutdown) = app().await;
+
+        let (status, body) = call(&router, Request::get("/api/v1/conversations"), None).await;
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+        assert_eq!(body["error"], "Missing API token");
+
+        let wrong = Request::get("/api/v1/conversations")
+            .header(header::AUTHORIZATION, "Bearer not-the-token");
+        let (status, body) = call(&router, wrong, None).await;
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+        assert_eq!(body["error"], "Invalid API token");
+
+        // The query token is only honoured on the WebSocket route
+        let (status, _) = call(
+            &router,
+            Request::get(format!("/api/v1/conversations?token={}", TOKEN)),
+            None,
+        )
+        .await;
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+        let (status, _) = call(&router, Request::post("/mcp"), None).await;
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+        let (status, _) = call(
+            &router,
+            Request::post(format!("/mcp?token={}", TOKEN)),
+            None,
+        )
+        .await;
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+
+        let (status, body) = call(&router, authorized("GET", "/api/v1/conversations"), None).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body, json!([]));
+    }
+
+    #[tokio::test]
+    async fn test_websocket_route_accepts_a_query_token() {
+        let (router, database, _shutdown) = app().await;
+        let id = query_value(&conversation(&database, "Live").await);
+
+        let (status, _) = call(
+            &router,
+            Request::get(format!("/api/v1/conversations/{}/ws?token=nope", id)),
+            None,
+        )
+        .await;
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+
+        // Percent-encoded like any other query value ("%74" is "t")
+        let (status, _) = call(
+            &router,
+            Request::get(format!(
+                "/api/v1/conversations/{}/ws?token=%74est-token",
+                id
+            )),
+            None,
+        )
+        .await;
+        assert_ne!(status, StatusCode::UNAUTHORIZED);
+    }
+
+    #[tokio::test]
+    async fn test_openapi_document_is_public() {
+        let (router, _database, _shutdown) = app().await;
+
+        let (status, body) = call(&router, Request::get("/api/v1/openapi.json"), None).await;
+        assert_eq!(status, StatusCode::OK);
+        assert!(body["paths"]["/api/v1/conversations/{id}/messages"].is_object());
+    }
+
+    #[tokio::test]
+    async fn test_before_cursor_must_be_in_the_conversation() {
+        let (router, database, _shutdown) = app().await;
+        let first = conversation(&database, "First").await;
+        let second = conversation(&database, "Second").await;
+        let own = message(&database, &first, "older").await;
+        message(&database, &first, "newer").await;
+        let foreign = message(&database, &second, "elsewhere").await;
+
+        let uri = |before: &RecordId| {
+            format!(
+                "/api/v1/conversations/{}/messages?before={}",
+                query_value(&first),
+                query_value(before)
+            )
+        };
+
+        let (status, body) = call(&router, authorized("GET", &uri(&foreign)), None).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert!(
+            body["error"]
+                .as_str()
+                .unwrap()
+                .contains("not in this conversation")
+        );
+
+        let (status, _) = call(&router, authorized("GET", &uri(&own)), None).await;
+        assert_eq!(status, StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn test_post_message_reports_rejections() {
+        let (router, database, _shutdown) = app().await;
+        let empty = conversation(&database, "No agents").await;
+        let uri = format!("/api/v1/conversations/{}/messages", query_value(&empty));
+
+        let (status, _) = call(
+            &router,
+            authorized("POST", &uri),
+            Some(json!({ "content": "  " })),
+        )
+        .await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+
+        let (status, body) = call(
+            &router,
+            authorized("POST", &uri),
+            Some(json!({ "content": "hello" })),
+        )
+        .await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(body["error"], "This conversation has no agents");
+
+        let (status, _) = call(
+            &router,
+            authorized("POST", "/api/v1/conversations/missing/messages"),
+            Some(json!({ "content": "hello" })),
+        )
+        .await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn test_websocket_forwards_new_messages() {
+        let database = Arc::new(Database::in_memory().await.unwrap());
+        let conversation_id = conversation(&database, "Live").await;
+        let server = ApiServer::start(Arc::clone(&database), 0, TOKEN.to_string())
+            .await
+            .unwrap();
+
+        let url = format!(
+            "ws://{}/api/v1/conversations/{}/ws?token={}",
+            server.addr(),
+            query_value(&conversation_id),
+            TOKEN
+        );
+        let (mut socket, _) = tokio_tungstenite::connect_async(url.as_str())
+            .await
+            .unwrap();
+
+        // The live query starts after the upgrade; write until it sees one
+        let mut event = None;
+        for attempt in 0..50 {
+            message(&database, &conversation_id, &format!("hello {}", attempt)).await;
+            if let Ok(Some(Ok(WsMessage::Text(text)))) =
+                tokio::time::timeout(Duration::from_millis(200), socket.next()).await
+            {
+                event = Some(serde_json::from_str::<Value>(&text).unwrap());
+                break;
+            }
+        }
+        let event = event.expect("no message event forwarded");
+        assert_eq!(event["action"], "create");
+        assert_eq!(
+            event["message"]["conversation_id"],
+            conversation_id.to_sql()
+        );
+        assert!(
+            event["message"]["content"]
+                .as_str()
+                .unwrap()
+                .starts_with("hello ")
+        );
+
+        server.stop().await;
+    }
+}
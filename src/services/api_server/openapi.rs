@@ -0,0 +1,88 @@
+//! OpenAPI description assembled from the handler annotations
+
+use super::handlers::{
+    ApiConversation, ApiConversationSummary, ApiMessage, ApiMessagePage, ApiTemplate,
+    CreateConversation, ErrorBody, MessageAccepted, PostMessage,
+};
+use super::ws::MessageEvent;
+use axum::Json;
+use utoipa::openapi::security::{HttpAuthScheme, HttpBuilder, SecurityScheme};
+use utoipa::{Modify, OpenApi};
+
+/// OpenAPI document for the local API
+#[derive(OpenApi)]
+#[openapi(
+    info(
+        title = "CYRUP local API",
+        description = "Localhost-only access to conversations, messages and agent templates"
+    ),
+    paths(
+        super::handlers::list_templates,
+        super::handlers::list_conversations,
+        super::handlers::create_conversation,
+        super::handlers::get_conversation,
+        super::handlers::list_messages,
+        super::handlers::post_message,
+        super::ws::subscribe,
+    ),
+    components(schemas(
+        ApiTemplate,
+        ApiConversationSummary,
+        ApiConversation,
+        ApiMessage,
+        ApiMessagePage,
+        CreateConversation,
+        PostMessage,
+        MessageAccepted,
+        MessageEvent,
+        ErrorBody,
+    )),
+    modifiers(&BearerAuth),
+    tags(
+        (name = "conversations"),
+        (name = "messages"),
+        (name = "templates")
+    )
+)]
+pub struct ApiDoc;
+
+struct BearerAuth;
+
+impl Modify for BearerAuth {
+    fn modify(&self, openapi: &mut utoipa::openapi::OpenApi) {
+        let components = openapi.components.get_or_insert_with(Default::default);
+        components.add_security_scheme(
+            "bearer",
+            SecurityScheme::Http(HttpBuilder::new().scheme(HttpAuthScheme::Bearer).build()),
+        );
+    }
+}
+
+/// Serve the OpenAPI document (no token required)
+pub(super) async fn openapi_json() -> Json<utoipa::openapi::OpenApi> {
+    Json(ApiDoc::openapi())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_every_route_is_documented() {
+        let doc = ApiDoc::openapi();
+        for path in [
+            "/api/v1/templates",
+            "/api/v1/conversations",
+            "/api/v1/conversations/{id}",
+            "/api/v1/conversations/{id}/messages",
+            "/api/v1/conversations/{id}/ws",
+        ] {
+            assert!(doc.paths.paths.contains_key(path), "{path} missing");
+        }
+        assert!(
+            doc.components
+                .as_ref()
+                .is_some_and(|c| c.security_schemes.contains_key("bearer"))
+        );
+    }
+}
@@ -0,0 +1,111 @@
+//! WebSocket feed of a conversation's message changes
+//!
+//! Forwards the same LIVE QUERY notifications `ChatComponent` renders: a
+//! streaming agent reply arrives as one `create` followed by `update`s
+//! carrying the full content so far. Messages sent by the client are
+//! ignored.
+
+use super::ApiState;
+use super::handlers::{ApiError, ApiMessage, parse_id};
+use axum::extract::ws::{Message as WsMessage, WebSocket, WebSocketUpgrade};
+use axum::extract::{Path, State};
+use axum::response::Response;
+use futures_util::StreamExt;
+use serde::Serialize;
+use surrealdb_types::{Action, RecordId, ToSql};
+use utoipa::ToSchema;
+
+/// One frame sent over the WebSocket
+#[derive(Debug, Serialize, ToSchema)]
+pub struct MessageEvent {
+    /// `create`, `update` or `delete`
+    pub action: String,
+    pub message: ApiMessage,
+}
+
+/// Subscribe to message changes (WebSocket upgrade)
+#[utoipa::path(
+    get,
+    path = "/api/v1/conversations/{id}/ws",
+    tag = "messages",
+    params(
+        ("id" = String, Path, description = "Conversation ID"),
+        ("token" = Option<String>, Query, description = "API token, for clients that cannot send headers")
+    ),
+    responses(
+        (status = 101, description = "Switching protocols; frames are MessageEvent JSON", body = MessageEvent),
+        (status = 404, body = super::handlers::ErrorBody)
+    ),
+    security(("bearer" = []))
+)]
+pub(super) async fn subscribe(
+    State(state): State<ApiState>,
+    Path(id): Path<String>,
+    upgrade: WebSocketUpgrade,
+) -> Result<Response, ApiError> {
+    let id = parse_id("conversation", &id)?;
+    state.database.get_conversation(&id).await?;
+
+    Ok(upgrade.on_upgrade(move |socket| forward(socket, state, id)))
+}
+
+async fn forward(mut socket: WebSocket, state: ApiState, conversation_id: RecordId) {
+    let mut notifications = match state.database.live_messages(&conversation_id).await {
+        Ok(stream) => stream,
+        Err(e) => {
+            log::error!("[ApiServer] {}", e);
+            let _ = socket.send(WsMessage::Close(None)).await;
+            return;
+        }
+    };
+    let mut shutdown = state.shutdown.clone();
+
+    log::debug!(
+        "[ApiServer] WebSocket subscribed to {}",
+        conversation_id.to_sql()
+    );
+
+    loop {
+        tokio::select! {
+            notification = notifications.next() => {
+                let notification = match notification {
+                    Some(Ok(notification)) => notification,
+                    Some(Err(e)) => {
+                        log::warn!("[ApiServer] Live query error: {}", e);
+                        continue;
+                    }
+                    None => break,
+                };
+                let action = match notification.action {
+                    Action::Create => "create",
+                    Action::Update => "update",
+                    Action::Delete => "delete",
+                    _ => continue,
+                };
+                let event = MessageEvent {
+                    action: action.to_string(),
+                    message: notification.data.into(),
+                };
+                let Ok(json) = serde_json::to_string(&event) else {
+                    continue;
+                };
+                if socket.send(WsMessage::Text(json.into())).await.is_err() {
+                    break;
+                }
+            }
+            incoming = socket.recv() => match incoming {
+                Some(Ok(WsMessage::Close(_))) | Some(Err(_)) | None => break,
+                Some(Ok(_)) => {}
+            },
+            _ = shutdown.wait_for(|stopped| *stopped) => {
+                let _ = socket.send(WsMessage::Close(None)).await;
+                break;
+            }
+        }
+    }
+
+    log::debug!(
+        "[ApiServer] WebSocket for {} closed",
+        conversation_id.to_sql()
+    );
+}
@@ -0,0 +1,379 @@
+//! Built-in slash commands
+
+use super::{CommandContext, CommandOutcome, CommandRegistry, SlashCommand};
+use crate::services::summarizer::SummarizerService;
+use crate::view_model::agent::AgentModel;
+use crate::view_model::message::AuthorType;
+use crate::view_model::snippet::PromptSnippet;
+use async_trait::async_trait;
+use std::sync::Arc;
+
+/// Messages scanned when looking for the latest agent reply
+const PIN_LOOKBACK: usize = 20;
+/// Numbered names `/export` tries before giving up (`title (2).md`, ...)
+const EXPORT_NAME_ATTEMPTS: usize = 1000;
+
+pub(super) fn commands() -> Vec<Arc<dyn SlashCommand>> {
+    vec![
+        Arc::new(Summarize),
+        Arc::new(Pin),
+        Arc::new(Export),
+        Arc::new(ClearContext),
+        Arc::new(Model),
+        Arc::new(Snippet),
+    ]
+}
+
+/// `/summarize` - refresh the conversation title and summary now
+struct Summarize;
+
+#[async_trait]
+impl SlashCommand for Summarize {
+    fn name(&self) -> &str {
+        "summarize"
+    }
+
+    fn description(&self) -> &str {
+        "Update the conversation title and summary now"
+    }
+
+    async fn run(&self, context: CommandContext) -> Result<CommandOutcome, String> {
+        let output = SummarizerService::new(context.database)
+            .summarize_now(&context.conversation_id)
+            .await?;
+        Ok(CommandOutcome::Status(format!(
+            "Summarized as \"{}\"",
+            output.title
+        )))
+    }
+}
+
+/// `/pin` - pin the latest agent reply
+struct Pin;
+
+#[async_trait]
+impl SlashCommand for Pin {
+    fn name(&self) -> &str {
+        "pin"
+    }
+
+    fn description(&self) -> &str {
+        "Pin the latest agent reply"
+    }
+
+    async fn run(&self, context: CommandContext) -> Result<CommandOutcome, String> {
+        let page = context
+            .database
+            .get_messages_before(&context.conversation_id, None, PIN_LOOKBACK)
+            .await?;
+
+        let latest = page
+            .messages
+            .iter()
+            .rev()
+            .find(|m| m.author_type == AuthorType::Agent)
+            .ok_or_else(|| "No agent reply to pin".to_string())?;
+
+        if latest.pinned {
+            return Ok(CommandOutcome::Status("Already pinned".to_string()));
+        }
+        context.database.pin_message(&latest.id).await?;
+        Ok(CommandOutcome::Status(format!(
+            "Pinned reply from {}",
+            latest.author
+        )))
+    }
+}
+
+/// `/export` - write the conversation to a Markdown file
+struct Export;
+
+#[async_trait]
+impl SlashCommand for Export {
+    fn name(&self) -> &str {
+        "export"
+    }
+
+    fn description(&self) -> &str {
+        "Save the conversation as Markdown in Downloads"
+    }
+
+    async fn run(&self, context: CommandContext) -> Result<CommandOutcome, String> {
+        let transcript = context
+            .database
+            .export_transcript(&context.conversation_id)
+            .await?;
+
+        let dir = dirs::download_dir()
+            .or_else(dirs::home_dir)
+            .ok_or_else(|| "Could not find a Downloads folder".to_string())?;
+        let path = write_new_file(
+            &dir,
+            &file_stem(&transcript.title),
+            transcript.to_markdown(),
+        )
+        .await?;
+
+        Ok(CommandOutcome::Status(format!(
+            "Exported to {}",
+            path.display()
+        )))
+    }
+}
+
+/// Write `contents` to `<stem>.md` in `dir`, or `<stem> (2).md` and so on
+/// if that exists; never overwrites a file
+async fn write_new_file(
+    dir: &std::path::Path,
+    stem: &str,
+    contents: String,
+) -> Result<std::path::PathBuf, String> {
+    use tokio::io::AsyncWriteExt;
+
+    for n in 1..=EXPORT_NAME_ATTEMPTS {
+        let path = match n {
+            1 => dir.join(format!("{}.md", stem)),
+            n => dir.join(format!("{} ({}).md", stem, n)),
+        };
+        let mut file = match tokio::fs::OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(&path)
+            .await
+        {
+            Ok(file) => file,
+            Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists => continue,
+            Err(e) => return Err(format!("Failed to create {}: {}", path.display(), e)),
+        };
+        file.write_all(contents.as_bytes())
+            .await
+            .map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+        return Ok(path);
+    }
+
+    Err(format!(
+        "Too many exports named {} in {}",
+        stem,
+        dir.display()
+    ))
+}
+
+/// Conversation title made safe for a file name
+fn file_stem(title: &str) -> String {
+    let stem: String = title
+        .chars()
+        .map(|c| {
+            if c.is_alphanumeric() || c == '-' || c == '_' {
+                c
+            } else {
+                '-'
+            }
+        })
+        .collect();
+    let stem = stem.trim_matches('-');
+    if stem.is_empty() {
+        "conversation".to_string()
+    } else {
+        stem.chars().take(80).collect()
+    }
+}
+
+/// `/clear-context` - start fresh agent sessions, keeping the history
+struct ClearContext;
+
+#[async_trait]
+impl SlashCommand for ClearContext {
+    fn name(&self) -> &str {
+        "clear-context"
+    }
+
+    fn description(&self) -> &str {
+        "Start fresh agent sessions; messages are kept"
+    }
+
+    async fn run(&self, context: CommandContext) -> Result<CommandOutcome, String> {
+        context
+            .database
+            .clear_agent_sessions(&context.conversation_id)
+            .await?;
+        Ok(CommandOutcome::Status(
+            "Agents will start with a fresh context".to_string(),
+        ))
+    }
+}
+
+/// `/model <sonnet|haiku|opus> [agent]` - switch a participant's model
+///
+/// Updates the agent's template, so the change applies to every
+/// conversation the agent takes part in.
+struct Model;
+
+#[async_trait]
+impl SlashCommand for Model {
+    fn name(&self) -> &str {
+        "model"
+    }
+
+    fn description(&self) -> &str {
+        "Switch an agent's model in every conversation"
+    }
+
+    fn usage(&self) -> &str {
+        "<sonnet|haiku|opus> [agent]"
+    }
+
+    async fn run(&self, context: CommandContext) -> Result<CommandOutcome, String> {
+        let (model, agent) = context
+            .args
+            .split_once(char::is_whitespace)
+            .map(|(model, agent)| (model, agent.trim().trim_start_matches('@')))
+            .unwrap_or((context.args.as_str(), ""));
+        let model = parse_model(model)?;
+
+        let conversation = context
+            .database
+            .get_conversation(&context.conversation_id)
+            .await?;
+        let mut participants = Vec::with_capacity(conversation.participants.len());
+        for id in &conversation.participants {
+            participants.push(context.database.get_template(id).await?);
+        }
+
+        let mut template = if agent.is_empty() {
+            match participants.len() {
+                1 => participants.remove(0),
+                _ => return Err("Name the agent: /model <model> <agent>".to_string()),
+            }
+        } else {
+            participants
+                .into_iter()
+                .find(|t| t.name.eq_ignore_ascii_case(agent))
+                .ok_or_else(|| format!("No agent named {} in this conversation", agent))?
+        };
+
+        if template.model == model {
+            return Ok(CommandOutcome::Status(format!(
+                "{} already uses {}",
+                template.name, model
+            )));
+        }
+        template.model = model;
+        context.database.update_template(&template).await?;
+
+        // The model belongs to the template, shared by all its conversations
+        Ok(CommandOutcome::Status(format!(
+            "{} now uses {} in every conversation",
+            template.name, model
+        )))
+    }
+}
+
+fn parse_model(name: &str) -> Result<AgentModel, String> {
+    match name.to_ascii_lowercase().as_str() {
+        "sonnet" => Ok(AgentModel::Sonnet),
+        "haiku" => Ok(AgentModel::Haiku),
+        "opus" => Ok(AgentModel::Opus),
+        "" => Err("Usage: /model <sonnet|haiku|opus> [agent]".to_string()),
+        other => Err(format!(
+            "Unknown model {}; use sonnet, haiku or opus",
+            other
+        )),
+    }
+}
+
+/// `/snippet add|delete|list` - manage prompt snippets
+struct Snippet;
+
+#[async_trait]
+impl SlashCommand for Snippet {
+    fn name(&self) -> &str {
+        "snippet"
+    }
+
+    fn description(&self) -> &str {
+        "Manage prompt snippets"
+    }
+
+    fn usage(&self) -> &str {
+        "add <name> <body> | delete <name> | list"
+    }
+
+    async fn run(&self, context: CommandContext) -> Result<CommandOutcome, String> {
+        let (action, rest) = context
+            .args
+            .split_once(char::is_whitespace)
+            .unwrap_or((context.args.as_str(), ""));
+        let rest = rest.trim();
+
+        match action {
+            "add" => {
+                let (name, body) = rest
+                    .split_once(char::is_whitespace)
+                    .ok_or_else(|| format!("Usage: /snippet {}", self.usage()))?;
+                let name = name.trim_start_matches('/');
+                if CommandRegistry::global().get(name).is_some() {
+                    return Err(format!("/{} is a built-in command", name));
+                }
+                let snippet = PromptSnippet {
+                    name: name.to_string(),
+                    body: body.trim().to_string(),
+                    ..Default::default()
+                };
+                context.database.create_snippet(&snippet).await?;
+                Ok(CommandOutcome::Status(format!("Saved /{}", snippet.name)))
+            }
+            "delete" => {
+                let name = rest.trim_start_matches('/');
+                if context.database.delete_snippet(name).await? {
+                    Ok(CommandOutcome::Status(format!("Deleted /{}", name)))
+                } else {
+                    Err(format!("No snippet named /{}", name))
+                }
+            }
+            "list" | "" => {
+                let snippets = context.database.list_snippets().await?;
+                if snippets.is_empty() {
+                    return Ok(CommandOutcome::Status("No snippets yet".to_string()));
+                }
+                let names: Vec<String> = snippets.iter().map(|s| format!("/{}", s.name)).collect();
+                Ok(CommandOutcome::Status(names.join("  ")))
+            }
+            other => Err(format!("Unknown action {}; use add, delete or list", other)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_model() {
+        assert_eq!(parse_model("Opus"), Ok(AgentModel::Opus));
+        assert!(parse_model("").is_err());
+        assert!(parse_model("gpt").is_err());
+    }
+
+    #[test]
+    fn test_file_stem() {
+        assert_eq!(file_stem("Q3 plan: v2/final"), "Q3-plan--v2-final");
+        assert_eq!(file_stem("???"), "conversation");
+    }
+
+    #[tokio::test]
+    async fn test_write_new_file_keeps_existing_exports() {
+        let dir = tempfile::tempdir().unwrap();
+
+        let first = write_new_file(dir.path(), "plan", "one".to_string())
+            .await
+            .unwrap();
+        let second = write_new_file(dir.path(), "plan", "two".to_string())
+            .await
+            .unwrap();
+
+        assert_eq!(first, dir.path().join("plan.md"));
+        assert_eq!(second, dir.path().join("plan (2).md"));
+        assert_eq!(std::fs::read_to_string(&first).unwrap(), "one");
+        assert_eq!(std::fs::read_to_string(&second).unwrap(), "two");
+    }
+}
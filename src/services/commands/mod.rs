@@ -0,0 +1,205 @@
+//! Slash commands for the chat input
+//!
+//! Text starting with `/` is dispatched to a [`SlashCommand`] from the
+//! global [`CommandRegistry`] instead of being sent to the agents. Built-in
+//! commands are registered on first use; other features add their own with
+//! [`CommandRegistry::register`]. Names that match no command fall back to
+//! the user's prompt snippets, which expand into the input for review.
+
+mod builtin;
+
+use crate::database::Database;
+use crate::view_model::snippet::SnippetArgs;
+use async_trait::async_trait;
+use std::collections::BTreeMap;
+use std::sync::{Arc, OnceLock, RwLock};
+use surrealdb_types::RecordId;
+
+/// Where a command was invoked
+#[derive(Clone)]
+pub struct CommandContext {
+    pub database: Arc<Database>,
+    pub conversation_id: RecordId,
+    /// Everything after the command name, trimmed
+    pub args: String,
+}
+
+/// What the chat view should do after a command ran
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CommandOutcome {
+    /// Show a short confirmation to the user
+    Status(String),
+    /// Put text in the input for the user to review and send
+    Insert(String),
+    /// Send text to the conversation as a normal message
+    Send(String),
+}
+
+/// Name, summary and usage shown in the command palette
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CommandInfo {
+    pub name: String,
+    pub description: String,
+    pub usage: String,
+}
+
+/// A command invoked as `/name args` from the chat input
+#[async_trait]
+pub trait SlashCommand: Send + Sync {
+    /// Name without the leading slash
+    fn name(&self) -> &str;
+
+    /// One-line summary for the palette
+    fn description(&self) -> &str;
+
+    /// Argument syntax, e.g. `<model> [agent]`
+    fn usage(&self) -> &str {
+        ""
+    }
+
+    async fn run(&self, context: CommandContext) -> Result<CommandOutcome, String>;
+}
+
+/// Named set of slash commands
+#[derive(Default)]
+pub struct CommandRegistry {
+    commands: RwLock<BTreeMap<String, Arc<dyn SlashCommand>>>,
+}
+
+static GLOBAL: OnceLock<CommandRegistry> = OnceLock::new();
+
+impl CommandRegistry {
+    /// Registry used by the chat input, with the built-in commands
+    pub fn global() -> &'static CommandRegistry {
+        GLOBAL.get_or_init(|| {
+            let registry = CommandRegistry::default();
+            for command in builtin::commands() {
+                if let Err(e) = registry.register(command) {
+                    log::error!("[Commands] {}", e);
+                }
+            }
+            registry
+        })
+    }
+
+    /// Add a command
+    ///
+    /// # Errors
+    /// Returns error if a command with the same name is already registered
+    pub fn register(&self, command: Arc<dyn SlashCommand>) -> Result<(), String> {
+        let name = command.name().to_string();
+        let mut commands = self.commands.write().unwrap_or_else(|e| e.into_inner());
+        if commands.contains_key(&name) {
+            return Err(format!("Command /{} is already registered", name));
+        }
+        commands.insert(name, command);
+        Ok(())
+    }
+
+    /// Look up a command by name
+    pub fn get(&self, name: &str) -> Option<Arc<dyn SlashCommand>> {
+        self.commands
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .get(name)
+            .cloned()
+    }
+
+    /// Commands whose name starts with `prefix`, sorted by name
+    pub fn matching(&self, prefix: &str) -> Vec<CommandInfo> {
+        self.commands
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .values()
+            .filter(|command| command.name().starts_with(prefix))
+            .map(|command| CommandInfo {
+                name: command.name().to_string(),
+                description: command.description().to_string(),
+                usage: command.usage().to_string(),
+            })
+            .collect()
+    }
+}
+
+/// Split `/name args` into name and arguments
+///
+/// Returns `None` for ordinary messages, including `//escaped` text and
+/// paths like `/usr/bin` that contain a second slash in the name.
+pub fn parse_command(input: &str) -> Option<(&str, &str)> {
+    let rest = input.trim_start().strip_prefix('/')?;
+    let (name, args) = rest.split_once(char::is_whitespace).unwrap_or((rest, ""));
+    if name.is_empty() || name.contains('/') {
+        return None;
+    }
+    Some((name, args.trim()))
+}
+
+/// Run the command in `input`, if it is one
+///
+/// # Returns
+/// * `None` - Not a command; send `input` as a message
+/// * `Some(Ok(outcome))` - Command or snippet ran
+/// * `Some(Err(msg))` - Unknown command or the command failed
+pub async fn execute(
+    registry: &CommandRegistry,
+    database: Arc<Database>,
+    conversation_id: RecordId,
+    input: &str,
+) -> Option<Result<CommandOutcome, String>> {
+    let (name, args) = parse_command(input)?;
+
+    if let Some(command) = registry.get(name) {
+        log::debug!("[Commands] Running /{}", name);
+        let context = CommandContext {
+            database,
+            conversation_id,
+            args: args.to_string(),
+        };
+        return Some(command.run(context).await);
+    }
+
+    let result = match database.get_snippet_by_name(name).await {
+        Ok(Some(snippet)) => snippet
+            .expand(&SnippetArgs::parse(args))
+            .map(CommandOutcome::Insert),
+        Ok(None) => Err(format!("Unknown command /{}", name)),
+        Err(e) => Err(e),
+    };
+    Some(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_command() {
+        assert_eq!(parse_command("/pin"), Some(("pin", "")));
+        assert_eq!(
+            parse_command("  /model  opus Researcher "),
+            Some(("model", "opus Researcher"))
+        );
+        assert_eq!(parse_command("hello /pin"), None);
+        assert_eq!(parse_command("/"), None);
+        assert_eq!(parse_command("/usr/bin is a path"), None);
+        assert_eq!(parse_command("//not a command"), None);
+    }
+
+    #[test]
+    fn test_builtins_registered_once() {
+        let registry = CommandRegistry::global();
+        let names: Vec<String> = registry.matching("").into_iter().map(|c| c.name).collect();
+        for builtin in [
+            "summarize",
+            "pin",
+            "export",
+            "clear-context",
+            "model",
+            "snippet",
+        ] {
+            assert!(names.iter().any(|n| n == builtin), "missing /{builtin}");
+        }
+        assert!(registry.register(builtin::commands().remove(0)).is_err());
+        assert_eq!(registry.matching("cl").len(), 1);
+    }
+}
@@ -0,0 +1,269 @@
+//! Minimal five-field cron expressions for scheduled prompts
+//!
+//! Supports `minute hour day-of-month month day-of-week` with `*`, lists,
+//! ranges and steps (`*/15`, `1-5`, `MON-FRI`, `0,30`), plus the `@hourly`,
+//! `@daily`, `@weekly` and `@monthly` shorthands. Times are evaluated in the
+//! local timezone; wall-clock times skipped by a DST change never match.
+//!
+//! As in classic cron, when both day-of-month and day-of-week are
+//! restricted a day matches if *either* field matches.
+
+use chrono::{DateTime, Datelike, Duration, Local, NaiveDate, TimeZone, Timelike};
+use std::str::FromStr;
+
+/// How far ahead `next_after` searches before giving up (covers Feb 29)
+const SEARCH_DAYS: i64 = 366 * 8;
+
+const MONTH_NAMES: [&str; 12] = [
+    "jan", "feb", "mar", "apr", "may", "jun", "jul", "aug", "sep", "oct", "nov", "dec",
+];
+const DAY_NAMES: [&str; 7] = ["sun", "mon", "tue", "wed", "thu", "fri", "sat"];
+
+/// Parsed cron expression
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CronExpr {
+    minutes: u64,
+    hours: u64,
+    days_of_month: u64,
+    months: u64,
+    days_of_week: u64,
+    dom_restricted: bool,
+    dow_restricted: bool,
+}
+
+impl FromStr for CronExpr {
+    type Err = String;
+
+    fn from_str(expr: &str) -> Result<Self, Self::Err> {
+        let expr = match expr.trim() {
+            "@hourly" => "0 * * * *",
+            "@daily" | "@midnight" => "0 0 * * *",
+            "@weekly" => "0 0 * * 0",
+            "@monthly" => "0 0 1 * *",
+            other => other,
+        };
+
+        let fields: Vec<&str> = expr.split_whitespace().collect();
+        let [minute, hour, dom, month, dow] = fields[..] else {
+            return Err(format!(
+                "Expected 5 cron fields (minute hour day month weekday), got {}",
+                fields.len()
+            ));
+        };
+
+        // Day-of-week accepts 7 as an alias for Sunday
+        let mut days_of_week = parse_field(dow, 0, 7, &DAY_NAMES)?;
+        if days_of_week & (1 << 7) != 0 {
+            days_of_week = (days_of_week & !(1 << 7)) | 1;
+        }
+
+        Ok(Self {
+            minutes: parse_field(minute, 0, 59, &[])?,
+            hours: parse_field(hour, 0, 23, &[])?,
+            days_of_month: parse_field(dom, 1, 31, &[])?,
+            months: parse_field(month, 1, 12, &MONTH_NAMES)?,
+            days_of_week,
+            dom_restricted: dom != "*",
+            dow_restricted: dow != "*",
+        })
+    }
+}
+
+impl CronExpr {
+    /// First matching minute strictly after `after`
+    ///
+    /// Returns `None` if nothing matches within the search horizon
+    /// (for example `0 0 31 2 *`).
+    pub fn next_after(&self, after: DateTime<Local>) -> Option<DateTime<Local>> {
+        let start = after.naive_local().with_second(0)?.with_nanosecond(0)? + Duration::minutes(1);
+
+        for offset in 0..SEARCH_DAYS {
+            let date = start.date() + Duration::days(offset);
+            if !self.day_matches(date) {
+                continue;
+            }
+
+            for hour in bits(self.hours, 0, 23) {
+                for minute in bits(self.minutes, 0, 59) {
+                    let Some(candidate) = date.and_hms_opt(hour, minute, 0) else {
+                        continue;
+                    };
+                    if candidate < start {
+                        continue;
+                    }
+                    // Skipped by a DST jump forward: no such local time
+                    if let Some(local) = Local.from_local_datetime(&candidate).earliest() {
+                        return Some(local);
+                    }
+                }
+            }
+        }
+
+        None
+    }
+
+    fn day_matches(&self, date: NaiveDate) -> bool {
+        if !has(self.months, date.month()) {
+            return false;
+        }
+
+        let dom = has(self.days_of_month, date.day());
+        let dow = has(self.days_of_week, date.weekday().num_days_from_sunday());
+
+        match (self.dom_restricted, self.dow_restricted) {
+            (true, true) => dom || dow,
+            (true, false) => dom,
+            (false, true) => dow,
+            (false, false) => true,
+        }
+    }
+}
+
+fn has(set: u64, value: u32) -> bool {
+    set & (1 << value) != 0
+}
+
+fn bits(set: u64, min: u32, max: u32) -> impl Iterator<Item = u32> {
+    (min..=max).filter(move |v| has(set, *v))
+}
+
+/// Parse one field into a bitset of allowed values
+fn parse_field(field: &str, min: u32, max: u32, names: &[&str]) -> Result<u64, String> {
+    let mut set = 0u64;
+
+    for part in field.split(',') {
+        let (range, step) = match part.split_once('/') {
+            Some((range, step)) => {
+                let step: u32 = step
+                    .parse()
+                    .map_err(|_| format!("Invalid step '{}' in '{}'", step, field))?;
+                if step == 0 {
+                    return Err(format!("Step cannot be zero in '{}'", field));
+                }
+                (range, step)
+            }
+            None => (part, 1),
+        };
+
+        let (start, end) = if range == "*" {
+            (min, max)
+        } else if let Some((a, b)) = range.split_once('-') {
+            (value(a, min, max, names)?, value(b, min, max, names)?)
+        } else {
+            let start = value(range, min, max, names)?;
+            // "5/15" means "from 5 to the end, every 15"
+            (start, if part.contains('/') { max } else { start })
+        };
+
+        if start > end {
+            return Err(format!("Range '{}' is backwards", range));
+        }
+
+        for v in (start..=end).step_by(step as usize) {
+            set |= 1 << v;
+        }
+    }
+
+    Ok(set)
+}
+
+fn value(token: &str, min: u32, max: u32, names: &[&str]) -> Result<u32, String> {
+    let lower = token.to_ascii_lowercase();
+    // Named values are 0-based (days) or 1-based (months) like their numbers
+    if let Some(index) = names.iter().position(|name| *name == lower) {
+        return Ok(index as u32 + min);
+    }
+
+    let v: u32 = token
+        .parse()
+        .map_err(|_| format!("Invalid cron value '{}'", token))?;
+    if v < min || v > max {
+        return Err(format!("Value {} out of range {}-{}", v, min, max));
+    }
+    Ok(v)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn local(y: i32, mo: u32, d: u32, h: u32, mi: u32) -> DateTime<Local> {
+        Local
+            .from_local_datetime(
+                &NaiveDate::from_ymd_opt(y, mo, d)
+                    .unwrap()
+                    .and_hms_opt(h, mi, 0)
+                    .unwrap(),
+            )
+            .earliest()
+            .unwrap()
+    }
+
+    fn next(expr: &str, after: DateTime<Local>) -> DateTime<Local> {
+        expr.parse::<CronExpr>().unwrap().next_after(after).unwrap()
+    }
+
+    #[test]
+    fn test_weekdays_at_nine() {
+        // 2026-10-16 is a Friday
+        let friday_morning = local(2026, 10, 16, 8, 59);
+        assert_eq!(
+            next("0 9 * * MON-FRI", friday_morning),
+            local(2026, 10, 16, 9, 0)
+        );
+
+        // After Friday's run the next one is Monday
+        assert_eq!(
+            next("0 9 * * 1-5", local(2026, 10, 16, 9, 0)),
+            local(2026, 10, 19, 9, 0)
+        );
+    }
+
+    #[test]
+    fn test_steps_lists_and_aliases() {
+        let start = local(2026, 1, 1, 10, 7);
+        assert_eq!(next("*/15 * * * *", start), local(2026, 1, 1, 10, 15));
+        assert_eq!(next("5,50 * * * *", start), local(2026, 1, 1, 10, 50));
+        assert_eq!(next("@daily", start), local(2026, 1, 2, 0, 0));
+        assert_eq!(next("0 0 * * 7", start), local(2026, 1, 4, 0, 0));
+        assert_eq!(next("0 12 1 jan,jul *", start), local(2026, 1, 1, 12, 0));
+        assert_eq!(
+            next("0 12 1 jan,jul *", local(2026, 1, 1, 12, 0)),
+            local(2026, 7, 1, 12, 0)
+        );
+    }
+
+    #[test]
+    fn test_day_of_month_or_day_of_week() {
+        // Runs on the 10th and on every Friday
+        let start = local(2026, 11, 1, 0, 0);
+        assert_eq!(next("0 0 10 * 5", start), local(2026, 11, 6, 0, 0));
+        assert_eq!(
+            next("0 0 10 * 5", local(2026, 11, 6, 0, 0)),
+            local(2026, 11, 10, 0, 0)
+        );
+        assert_eq!(
+            next("0 0 10 * 5", local(2026, 11, 10, 0, 0)),
+            local(2026, 11, 13, 0, 0)
+        );
+    }
+
+    #[test]
+    fn test_invalid_expressions() {
+        for expr in [
+            "",
+            "* * * *",
+            "60 * * * *",
+            "*/0 * * * *",
+            "5-1 * * * *",
+            "0 0 * * funday",
+        ] {
+            assert!(
+                expr.parse::<CronExpr>().is_err(),
+                "{expr} should be rejected"
+            );
+        }
+        let impossible: CronExpr = "0 0 31 2 *".parse().unwrap();
+        assert_eq!(impossible.next_after(local(2026, 1, 1, 0, 0)), None);
+    }
+}
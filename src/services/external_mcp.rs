@@ -0,0 +1,576 @@
+//! User-registered MCP servers for agents
+//!
+//! Resolves the servers enabled on an agent template into the
+//! `ClaudeAgentOptions` MCP configuration, keeps their secrets in the
+//! credential vault, and connects to a server directly for health checks
+//! and for backends that call MCP tools themselves (see src/services/llm).
+//!
+//! Secrets are stored as one vault entry per server (provider
+//! `mcp_server_<key>`), a JSON object mapping each name in
+//! `McpServer::secret_keys` to its value. For stdio servers they become
+//! environment variables of the spawned process; for HTTP servers, request
+//! headers.
+//!
+//! Direct connections use rmcp's client transports: a child process, or
+//! streamable HTTP over the shared [`HttpClient`] pool with the secrets
+//! added to each request. Health checks drive the connection through
+//! `kodegen_mcp_client`.
+
+use crate::auth::{CredentialType, CredentialVault};
+use crate::database::Database;
+use crate::environment::native::HttpClient;
+use crate::view_model::mcp_server::{McpServer, McpTransport};
+use futures_util::StreamExt;
+use futures_util::stream::BoxStream;
+use kodegen_mcp_client::KodegenClient;
+use kodegen_tools_claude_agent::types::mcp::{
+    McpHttpServerConfig, McpServerConfig, McpServers, McpStdioServerConfig,
+};
+use reqwest::header::{ACCEPT, CONTENT_TYPE, HeaderMap, HeaderName, HeaderValue};
+use rmcp::model::{ClientInfo, ClientJsonRpcMessage, ServerJsonRpcMessage};
+use rmcp::service::RunningService;
+use rmcp::transport::streamable_http_client::{
+    StreamableHttpClient, StreamableHttpClientTransportConfig, StreamableHttpError,
+    StreamableHttpPostResponse,
+};
+use rmcp::transport::{ConfigureCommandExt, StreamableHttpClientTransport, TokioChildProcess};
+use rmcp::{RoleClient, ServiceExt};
+use sse_stream::{Error as SseError, Sse, SseStream};
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Arc;
+use std::time::Duration;
+use surrealdb_types::{RecordId, ToSql};
+
+/// Longest a health check may take, including process start-up
+const HEALTH_CHECK_TIMEOUT: Duration = Duration::from_secs(20);
+
+/// Per-request limit for MCP over HTTP, replacing the pool's 30s default so
+/// slow tool calls and event streams are not cut off
+const HTTP_REQUEST_TIMEOUT: Duration = Duration::from_secs(300);
+
+const EVENT_STREAM_MIME_TYPE: &str = "text/event-stream";
+const JSON_MIME_TYPE: &str = "application/json";
+const ACCEPT_STREAM_OR_JSON: &str = "text/event-stream, application/json";
+const HEADER_SESSION_ID: &str = "Mcp-Session-Id";
+const HEADER_LAST_EVENT_ID: &str = "Last-Event-ID";
+
+/// MCP configuration for one agent run
+#[derive(Debug, Clone, Default)]
+pub struct AgentMcp {
+    /// Enabled servers with their secret values
+    pub servers: Vec<(McpServer, HashMap<String, String>)>,
+}
+
+impl AgentMcp {
+    /// Value for `ClaudeAgentOptions::mcp_servers`
+    pub fn mcp_servers(&self) -> McpServers {
+        if self.servers.is_empty() {
+            return McpServers::default();
+        }
+        McpServers::Dict(
+            self.servers
+                .iter()
+                .map(|(server, secrets)| {
+                    (server.name.clone(), server_config(server, secrets.clone()))
+                })
+                .collect(),
+        )
+    }
+
+    /// `mcp__<name>` entries to append to `allowed_tools`
+    pub fn allowed_tools(&self) -> Vec<String> {
+        self.servers
+            .iter()
+            .map(|(server, _)| server.tool_prefix())
+            .collect()
+    }
+}
+
+/// A tool reported by a health check
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct McpToolInfo {
+    pub name: String,
+    pub description: String,
+}
+
+/// Build the MCP configuration for a template's agent
+///
+/// Never fails the agent run: servers whose secrets cannot be read are
+/// left out and logged, so the agent still answers with its other tools.
+pub async fn agent_mcp(database: &Database, template_id: &RecordId) -> AgentMcp {
+    let servers = match database.list_template_mcp_servers(template_id).await {
+        Ok(servers) => servers,
+        Err(e) => {
+            log::error!(
+                "[MCP] Could not load servers for {}: {}",
+                template_id.to_sql(),
+                e
+            );
+            return AgentMcp::default();
+        }
+    };
+
+    let mut mcp = AgentMcp::default();
+    for server in servers {
+        let secrets = match load_secrets(&server).await {
+            Ok(secrets) => secrets,
+            Err(e) => {
+                log::error!("[MCP] Skipping server '{}': {}", server.name, e);
+                continue;
+            }
+        };
+        mcp.servers.push((server, secrets));
+    }
+    mcp
+}
+
+fn server_config(server: &McpServer, secrets: HashMap<String, String>) -> McpServerConfig {
+    match server.transport {
+        McpTransport::Stdio => {
+            let mut env = server.env.clone();
+            env.extend(secrets);
+            McpServerConfig::Stdio(McpStdioServerConfig {
+                command: server.command.clone(),
+                args: Some(server.args.clone()),
+                env: Some(env),
+            })
+        }
+        McpTransport::Http => McpServerConfig::Http(McpHttpServerConfig {
+            url: server.url.clone(),
+            headers: Some(secrets),
+        }),
+    }
+}
+
+fn vault_provider(server_id: &RecordId) -> String {
+    let sql = server_id.to_sql();
+    let key = sql
+        .split_once(':')
+        .map_or(sql.as_str(), |(_, key)| key.trim_matches(['⟨', '⟩', '`']));
+    format!("mcp_server_{}", key)
+}
+
+/// Store a server's secret values, replacing any previous ones
+///
+/// An empty map removes the vault entry.
+pub async fn store_secrets(
+    server_id: &RecordId,
+    secrets: &BTreeMap<String, String>,
+) -> Result<(), String> {
+    let vault = CredentialVault::new()
+        .await
+        .map_err(|e| format!("Credential vault unavailable: {}", e))?;
+    let provider = vault_provider(server_id);
+
+    if secrets.is_empty() {
+        vault
+            .delete_credential(&provider, &CredentialType::ApplicationSecret)
+            .await
+            .map_err(|e| format!("Could not remove MCP secrets: {}", e))?;
+        return Ok(());
+    }
+
+    let json = serde_json::to_string(secrets)
+        .map_err(|e| format!("Could not encode MCP secrets: {}", e))?;
+    vault
+        .store_credential(&provider, CredentialType::ApplicationSecret, json, None)
+        .await
+        .map_err(|e| format!("Could not store MCP secrets: {}", e))
+}
+
+/// Secret values for a server's `secret_keys`
+///
+/// Names without a stored value are logged and left out.
+pub async fn load_secrets(server: &McpServer) -> Result<HashMap<String, String>, String> {
+    if server.secret_keys.is_empty() {
+        return Ok(HashMap::new());
+    }
+
+    let vault = CredentialVault::new()
+        .await
+        .map_err(|e| format!("Credential vault unavailable: {}", e))?;
+    let stored: HashMap<String, String> = match vault
+        .get_credential(
+            &vault_provider(&server.id),
+            &CredentialType::ApplicationSecret,
+        )
+        .await
+        .map_err(|e| format!("Could not read MCP secrets: {}", e))?
+    {
+        Some(json) => serde_json::from_str(&json)
+            .map_err(|e| format!("Could not decode MCP secrets: {}", e))?,
+        None => HashMap::new(),
+    };
+
+    let mut secrets = HashMap::new();
+    for key in &server.secret_keys {
+        match stored.get(key) {
+            Some(value) => {
+                secrets.insert(key.clone(), value.clone());
+            }
+            None => log::warn!("[MCP] No value stored for '{}' of '{}'", key, server.name),
+        }
+    }
+    Ok(secrets)
+}
+
+/// Remove a server's secrets from the vault
+pub async fn delete_secrets(server_id: &RecordId) -> Result<(), String> {
+    store_secrets(server_id, &BTreeMap::new()).await
+}
+
+/// Connect to a server and list its tools
+///
+/// # Errors
+/// Returns error if the server cannot be started or reached, fails the MCP
+/// handshake, or does not answer within 20 seconds
+pub async fn health_check(
+    server: &McpServer,
+    secrets: &HashMap<String, String>,
+) -> Result<Vec<McpToolInfo>, String> {
+    let check = async { list_tools(connect(server, secrets).await?).await };
+    tokio::time::timeout(HEALTH_CHECK_TIMEOUT, check)
+        .await
+        .map_err(|_| {
+            format!(
+                "'{}' did not respond within {}s",
+                server.name,
+                HEALTH_CHECK_TIMEOUT.as_secs()
+            )
+        })?
+}
+
+/// Start or reach a server and complete the MCP handshake
+///
+/// Stdio servers run as a child process that stops when the returned
+/// client is cancelled or dropped.
+///
+/// # Errors
+/// Returns error if the process cannot be started, a secret is not a valid
+/// HTTP header, or the handshake fails
+pub async fn connect(
+    server: &McpServer,
+    secrets: &HashMap<String, String>,
+) -> Result<RunningService<RoleClient, ClientInfo>, String> {
+    let client = match server.transport {
+        McpTransport::Stdio => {
+            let command = tokio::process::Command::new(&server.command).configure(|cmd| {
+                cmd.args(&server.args).envs(&server.env).envs(secrets);
+            });
+            let transport = TokioChildProcess::new(command)
+                .map_err(|e| format!("Could not start '{}': {}", server.command, e))?;
+            ClientInfo::default()
+                .serve(transport)
+                .await
+                .map_err(|e| format!("MCP handshake failed: {}", e))?
+        }
+        McpTransport::Http => {
+            let mut headers = HeaderMap::new();
+            for (name, value) in secrets {
+                let name = HeaderName::from_bytes(name.as_bytes())
+                    .map_err(|e| format!("Invalid header name '{}': {}", name, e))?;
+                let value = HeaderValue::from_str(value)
+                    .map_err(|e| format!("Invalid value for header '{}': {}", name, e))?;
+                headers.insert(name, value);
+            }
+            let transport = StreamableHttpClientTransport::with_client(
+                PooledHttp {
+                    client: HttpClient::shared().client().clone(),
+                    headers: Arc::new(headers),
+                },
+                StreamableHttpClientTransportConfig::with_uri(server.url.clone()),
+            );
+            ClientInfo::default()
+                .serve(transport)
+                .await
+                .map_err(|e| format!("MCP handshake failed: {}", e))?
+        }
+    };
+
+    Ok(client)
+}
+
+async fn list_tools(
+    service: RunningService<RoleClient, ClientInfo>,
+) -> Result<Vec<McpToolInfo>, String> {
+    let (client, connection) = KodegenClient::from_service(service);
+    let result = client
+        .list_tools()
+        .await
+        .map_err(|e| format!("Could not list tools: {}", e));
+    // Stops a stdio child process as well
+    if let Err(e) = connection.close().await {
+        log::debug!("[MCP] Closing health check client: {}", e);
+    }
+
+    let mut tools: Vec<McpToolInfo> = result?
+        .into_iter()
+        .map(|tool| McpToolInfo {
+            name: tool.name.to_string(),
+            description: tool.description.map(|d| d.to_string()).unwrap_or_default(),
+        })
+        .collect();
+    tools.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(tools)
+}
+
+/// Streamable HTTP client over the shared connection pool
+///
+/// Same requests as rmcp's `reqwest::Client` transport, plus the server's
+/// secret headers on each one; the pooled client itself carries none.
+#[derive(Clone)]
+struct PooledHttp {
+    client: reqwest::Client,
+    headers: Arc<HeaderMap>,
+}
+
+impl PooledHttp {
+    fn request(
+        &self,
+        method: reqwest::Method,
+        uri: &str,
+        auth_token: Option<String>,
+    ) -> reqwest::RequestBuilder {
+        let request = self
+            .client
+            .request(method, uri)
+            .headers(self.headers.as_ref().clone())
+            .timeout(HTTP_REQUEST_TIMEOUT);
+        match auth_token {
+            Some(token) => request.bearer_auth(token),
+            None => request,
+        }
+    }
+}
+
+impl StreamableHttpClient for PooledHttp {
+    type Error = reqwest::Error;
+
+    async fn post_message(
+        &self,
+        uri: Arc<str>,
+        message: ClientJsonRpcMessage,
+        session_id: Option<Arc<str>>,
+        auth_token: Option<String>,
+    ) -> Result<StreamableHttpPostResponse, StreamableHttpError<Self::Error>> {
+        let mut request = self
+            .request(reqwest::Method::POST, &uri, auth_token)
+            .header(ACCEPT, ACCEPT_STREAM_OR_JSON);
+        if let Some(session_id) = session_id {
+            request = request.header(HEADER_SESSION_ID, session_id.as_ref());
+        }
+        let response = request.json(&message).send().await?.error_for_status()?;
+        if response.status() == reqwest::StatusCode::ACCEPTED {
+            return Ok(StreamableHttpPostResponse::Accepted);
+        }
+
+        let session_id = response
+            .headers()
+            .get(HEADER_SESSION_ID)
+            .and_then(|value| value.to_str().ok())
+            .map(str::to_string);
+        let content_type = response
+            .headers()
+            .get(CONTENT_TYPE)
+            .map(|value| String::from_utf8_lossy(value.as_bytes()).into_owned());
+        match content_type.as_deref() {
+            Some(ct) if ct.starts_with(EVENT_STREAM_MIME_TYPE) => {
+                let stream = SseStream::from_byte_stream(response.bytes_stream()).boxed();
+                Ok(StreamableHttpPostResponse::Sse(stream, session_id))
+            }
+            Some(ct) if ct.starts_with(JSON_MIME_TYPE) => {
+                let message: ServerJsonRpcMessage = response.json().await?;
+                Ok(StreamableHttpPostResponse::Json(message, session_id))
+            }
+            _ => Err(StreamableHttpError::UnexpectedContentType(content_type)),
+        }
+    }
+
+    async fn delete_session(
+        &self,
+        uri: Arc<str>,
+        session_id: Arc<str>,
+        auth_token: Option<String>,
+    ) -> Result<(), StreamableHttpError<Self::Error>> {
+        let response = self
+            .request(reqwest::Method::DELETE, &uri, auth_token)
+            .header(HEADER_SESSION_ID, session_id.as_ref())
+            .send()
+            .await?;
+        // Servers without session deletion answer 405; nothing to clean up
+        if response.status() != reqwest::StatusCode::METHOD_NOT_ALLOWED {
+            response.error_for_status()?;
+        }
+        Ok(())
+    }
+
+    async fn get_stream(
+        &self,
+        uri: Arc<str>,
+        session_id: Arc<str>,
+        last_event_id: Option<String>,
+        auth_token: Option<String>,
+    ) -> Result<BoxStream<'static, Result<Sse, SseError>>, StreamableHttpError<Self::Error>> {
+        let mut request = self
+            .request(reqwest::Method::GET, &uri, auth_token)
+            .header(ACCEPT, ACCEPT_STREAM_OR_JSON)
+            .header(HEADER_SESSION_ID, session_id.as_ref());
+        if let Some(last_event_id) = last_event_id {
+            request = request.header(HEADER_LAST_EVENT_ID, last_event_id);
+        }
+        let response = request.send().await?;
+        if response.status() == reqwest::StatusCode::METHOD_NOT_ALLOWED {
+            return Err(StreamableHttpError::ServerDoesNotSupportSse);
+        }
+        let response = response.error_for_status()?;
+        Ok(SseStream::from_byte_stream(response.bytes_stream()).boxed())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::response::IntoResponse;
+    use rmcp::handler::server::tool::ToolRouter;
+    use rmcp::model::{CallToolResult, Content, ServerCapabilities, ServerInfo};
+    use rmcp::transport::streamable_http_server::session::local::LocalSessionManager;
+    use rmcp::transport::streamable_http_server::{
+        StreamableHttpServerConfig, StreamableHttpService,
+    };
+    use rmcp::{ErrorData as McpError, ServerHandler, tool, tool_handler, tool_router};
+
+    /// Minimal MCP server with two tools
+    #[derive(Clone)]
+    struct Stub {
+        tool_router: ToolRouter<Self>,
+    }
+
+    #[tool_router]
+    impl Stub {
+        #[tool(description = "Reply with pong")]
+        async fn ping(&self) -> Result<CallToolResult, McpError> {
+            Ok(CallToolResult::success(vec![Content::text("pong")]))
+        }
+
+        #[tool(description = "Reply with the time")]
+        async fn clock(&self) -> Result<CallToolResult, McpError> {
+            Ok(CallToolResult::success(vec![Content::text("noon")]))
+        }
+    }
+
+    #[tool_handler]
+    impl ServerHandler for Stub {
+        fn get_info(&self) -> ServerInfo {
+            ServerInfo {
+                capabilities: ServerCapabilities::builder().enable_tools().build(),
+                ..Default::default()
+            }
+        }
+    }
+
+    /// Serve [`Stub`] on a free port, answering only requests that carry
+    /// `x-api-key: <api_key>` if one is given
+    async fn serve_stub(api_key: Option<&'static str>) -> String {
+        let service = StreamableHttpService::new(
+            || {
+                Ok(Stub {
+                    tool_router: Stub::tool_router(),
+                })
+            },
+            std::sync::Arc::new(LocalSessionManager::default()),
+            StreamableHttpServerConfig::default(),
+        );
+        let app = axum::Router::new().nest_service("/mcp", service).layer(
+            axum::middleware::from_fn(
+                move |request: axum::extract::Request, next: axum::middleware::Next| async move {
+                    let sent = request
+                        .headers()
+                        .get("x-api-key")
+                        .and_then(|v| v.to_str().ok());
+                    if api_key.is_some_and(|key| sent != Some(key)) {
+                        return axum::http::StatusCode::UNAUTHORIZED.into_response();
+                    }
+                    next.run(request).await
+                },
+            ),
+        );
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, app).await });
+        format!("http://{}/mcp", addr)
+    }
+
+    #[tokio::test]
+    async fn test_health_check_lists_stub_tools() {
+        let server = McpServer {
+            name: "stub".to_string(),
+            transport: McpTransport::Http,
+            url: serve_stub(None).await,
+            ..Default::default()
+        };
+
+        let tools = health_check(&server, &HashMap::new()).await.unwrap();
+        let names: Vec<&str> = tools.iter().map(|t| t.name.as_str()).collect();
+        assert_eq!(names, ["clock", "ping"]);
+        assert_eq!(tools[1].description, "Reply with pong");
+    }
+
+    #[tokio::test]
+    async fn test_health_check_sends_secret_headers() {
+        let server = McpServer {
+            name: "keyed".to_string(),
+            transport: McpTransport::Http,
+            url: serve_stub(Some("s3cret")).await,
+            secret_keys: vec!["x-api-key".to_string()],
+            ..Default::default()
+        };
+
+        assert!(health_check(&server, &HashMap::new()).await.is_err());
+
+        let secrets = HashMap::from([("x-api-key".to_string(), "s3cret".to_string())]);
+        let tools = health_check(&server, &secrets).await.unwrap();
+        assert_eq!(tools.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_health_check_reports_unreachable_server() {
+        let server = McpServer {
+            name: "missing".to_string(),
+            command: "cyrup-no-such-mcp-server".to_string(),
+            ..Default::default()
+        };
+        assert!(health_check(&server, &HashMap::new()).await.is_err());
+    }
+
+    #[test]
+    fn test_server_config_routes_secrets_by_transport() {
+        let secrets = HashMap::from([("API_KEY".to_string(), "s3cret".to_string())]);
+
+        let stdio = McpServer {
+            name: "files".to_string(),
+            command: "mcp-files".to_string(),
+            env: HashMap::from([("ROOT".to_string(), "/tmp".to_string())]),
+            ..Default::default()
+        };
+        match server_config(&stdio, secrets.clone()) {
+            McpServerConfig::Stdio(config) => {
+                let env = config.env.unwrap();
+                assert_eq!(env["ROOT"], "/tmp");
+                assert_eq!(env["API_KEY"], "s3cret");
+            }
+            _ => panic!("expected stdio config"),
+        }
+
+        let http = McpServer {
+            transport: McpTransport::Http,
+            url: "https://example.com/mcp".to_string(),
+            ..stdio
+        };
+        match server_config(&http, secrets) {
+            McpServerConfig::Http(config) => {
+                assert_eq!(config.headers.unwrap()["API_KEY"], "s3cret")
+            }
+            _ => panic!("expected http config"),
+        }
+    }
+}
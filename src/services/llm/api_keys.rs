@@ -0,0 +1,366 @@
+//! Inference backend API keys
+//!
+//! Keys are stored in the credential vault as [`CredentialType::ApiKey`]:
+//! one default per service (vault provider `anthropic` or `openai`) and
+//! optional per-template overrides (`llm_backend_<template>`), so teams can
+//! bill agents to separate accounts. An agent uses the first of:
+//! 1. its template's override
+//! 2. the service default from the vault
+//! 3. the service's environment variable (`ANTHROPIC_API_KEY`, `OPENAI_API_KEY`)
+//!
+//! Claude Code agents receive their key through the subprocess environment
+//! (see [`claude_env`]); the HTTP backends send it as a bearer token.
+
+use super::openai::DEFAULT_OPENAI_URL;
+use crate::auth::{CredentialType, CredentialVault};
+use crate::environment::native::HttpClient;
+use reqwest::StatusCode;
+use std::collections::HashMap;
+use surrealdb_types::{RecordId, ToSql};
+
+/// Anthropic API root used to validate keys
+pub const DEFAULT_ANTHROPIC_URL: &str = "https://api.anthropic.com";
+
+/// `anthropic-version` header sent with validation requests
+const ANTHROPIC_VERSION: &str = "2023-06-01";
+
+/// Service a key authenticates against
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeyService {
+    /// Anthropic API, used by the Claude Code CLI
+    Anthropic,
+    /// OpenAI API, used by the `openai` backend
+    Openai,
+}
+
+impl KeyService {
+    pub const ALL: [KeyService; 2] = [KeyService::Anthropic, KeyService::Openai];
+
+    /// Vault provider of the service's default key
+    pub fn vault_provider(self) -> &'static str {
+        match self {
+            KeyService::Anthropic => "anthropic",
+            KeyService::Openai => "openai",
+        }
+    }
+
+    /// Environment variable consulted when the vault has no key
+    pub fn env_var(self) -> &'static str {
+        match self {
+            KeyService::Anthropic => "ANTHROPIC_API_KEY",
+            KeyService::Openai => "OPENAI_API_KEY",
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            KeyService::Anthropic => "Anthropic",
+            KeyService::Openai => "OpenAI",
+        }
+    }
+}
+
+/// Vault provider of a template's key override
+fn template_provider(template_id: &RecordId) -> String {
+    let sql = template_id.to_sql();
+    let key = sql
+        .split_once(':')
+        .map_or(sql.as_str(), |(_, key)| key.trim_matches(['⟨', '⟩', '`']));
+    format!("llm_backend_{}", key)
+}
+
+async fn vault() -> Result<CredentialVault, String> {
+    CredentialVault::new()
+        .await
+        .map_err(|e| format!("Credential vault unavailable: {}", e))
+}
+
+async fn load(vault: &CredentialVault, provider: &str) -> Result<Option<String>, String> {
+    let key = vault
+        .get_credential(provider, &CredentialType::ApiKey)
+        .await
+        .map_err(|e| format!("Could not read API key: {}", e))?;
+    if key.is_some() {
+        return Ok(key);
+    }
+    // Template keys were stored as application secrets before ApiKey existed
+    vault
+        .get_credential(provider, &CredentialType::ApplicationSecret)
+        .await
+        .map_err(|e| format!("Could not read API key: {}", e))
+}
+
+async fn store(vault: &CredentialVault, provider: &str, key: &str) -> Result<(), String> {
+    let _ = vault
+        .delete_credential(provider, &CredentialType::ApplicationSecret)
+        .await;
+
+    if key.trim().is_empty() {
+        return vault
+            .delete_credential(provider, &CredentialType::ApiKey)
+            .await
+            .map(|_| ())
+            .map_err(|e| format!("Could not remove API key: {}", e));
+    }
+    vault
+        .store_credential(
+            provider,
+            CredentialType::ApiKey,
+            key.trim().to_string(),
+            None,
+        )
+        .await
+        .map_err(|e| format!("Could not store API key: {}", e))
+}
+
+/// The key an agent would use, from the vault or the environment
+///
+/// Vault errors are logged and fall through to the next source.
+pub async fn resolve_key(
+    template_id: Option<&RecordId>,
+    service: Option<KeyService>,
+) -> Option<String> {
+    let vault = match vault().await {
+        Ok(vault) => Some(vault),
+        Err(e) => {
+            log::warn!("[LLM] {}", e);
+            None
+        }
+    };
+    if let Some(vault) = &vault
+        && let Some(key) = resolve_in_vault(vault, template_id, service).await
+    {
+        return Some(key);
+    }
+    service
+        .and_then(|service| std::env::var(service.env_var()).ok())
+        .filter(|key| !key.trim().is_empty())
+}
+
+async fn resolve_in_vault(
+    vault: &CredentialVault,
+    template_id: Option<&RecordId>,
+    service: Option<KeyService>,
+) -> Option<String> {
+    let providers = template_id
+        .map(template_provider)
+        .into_iter()
+        .chain(service.map(|service| service.vault_provider().to_string()));
+    for provider in providers {
+        match load(vault, &provider).await {
+            Ok(Some(key)) => return Some(key),
+            Ok(None) => {}
+            Err(e) => log::warn!("[LLM] {}", e),
+        }
+    }
+    None
+}
+
+/// Environment for a Claude Code subprocess running `template_id`
+///
+/// Empty when no key is configured: the CLI then falls back to its own login.
+pub async fn claude_env(template_id: Option<&RecordId>) -> HashMap<String, String> {
+    resolve_key(template_id, Some(KeyService::Anthropic))
+        .await
+        .map(|key| HashMap::from([(KeyService::Anthropic.env_var().to_string(), key)]))
+        .unwrap_or_default()
+}
+
+/// Whether a template has its own key
+pub async fn has_template_key(template_id: &RecordId) -> bool {
+    match vault().await {
+        Ok(vault) => matches!(
+            load(&vault, &template_provider(template_id)).await,
+            Ok(Some(_))
+        ),
+        Err(_) => false,
+    }
+}
+
+/// Store a template's key override; an empty key removes it
+pub async fn store_template_key(template_id: &RecordId, key: &str) -> Result<(), String> {
+    store(&vault().await?, &template_provider(template_id), key).await
+}
+
+/// Whether a default key for the service is stored in the vault
+pub async fn has_default_key(service: KeyService) -> bool {
+    match vault().await {
+        Ok(vault) => matches!(load(&vault, service.vault_provider()).await, Ok(Some(_))),
+        Err(_) => false,
+    }
+}
+
+/// Store the service's default key; an empty key removes it
+pub async fn store_default_key(service: KeyService, key: &str) -> Result<(), String> {
+    store(&vault().await?, service.vault_provider(), key).await
+}
+
+/// Check a key against the service by listing its models
+///
+/// # Arguments
+/// * `base_url` - API root; `None` for the public API
+///
+/// # Returns
+/// * `Ok(())` - The service accepted the key
+/// * `Err(String)` - Rejected key or unreachable service
+pub async fn validate_key(
+    service: KeyService,
+    key: &str,
+    base_url: Option<&str>,
+) -> Result<(), String> {
+    let key = key.trim();
+    if key.is_empty() {
+        return Err("Enter an API key to check".to_string());
+    }
+
+    let response = HttpClient::shared()
+        .send(|http| match service {
+            KeyService::Anthropic => {
+                let base = base_url
+                    .unwrap_or(DEFAULT_ANTHROPIC_URL)
+                    .trim_end_matches('/');
+                http.get(format!("{}/v1/models", base))
+                    .header("x-api-key", key)
+                    .header("anthropic-version", ANTHROPIC_VERSION)
+            }
+            KeyService::Openai => {
+                let base = base_url.unwrap_or(DEFAULT_OPENAI_URL).trim_end_matches('/');
+                http.get(format!("{}/models", base)).bearer_auth(key)
+            }
+        })
+        .await
+        .map_err(|e| format!("Could not reach {}: {}", service.label(), e))?;
+    match response.status() {
+        status if status.is_success() => Ok(()),
+        StatusCode::UNAUTHORIZED | StatusCode::FORBIDDEN => {
+            Err(format!("{} rejected the API key", service.label()))
+        }
+        status => Err(format!("{} answered {}", service.label(), status)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::auth::credential_store::EncryptedFileStore;
+    use std::sync::Arc;
+    use wiremock::matchers::{header, method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    async fn store_in(vault: &CredentialVault, provider: &str, key: &str) {
+        store(vault, provider, key).await.unwrap();
+    }
+
+    #[test]
+    fn test_template_provider() {
+        assert_eq!(
+            template_provider(&RecordId::new("agent_template", "abc")),
+            "llm_backend_abc"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_template_override_wins_over_default() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = EncryptedFileStore::open(dir.path().join("keys.credentials"), b"test").unwrap();
+        let vault = CredentialVault::with_store(Arc::new(file));
+        let team = RecordId::new("agent_template", "team");
+        let other = RecordId::new("agent_template", "other");
+
+        store_in(&vault, "anthropic", "sk-default").await;
+        store_in(&vault, &template_provider(&team), "sk-team").await;
+
+        let anthropic = Some(KeyService::Anthropic);
+        assert_eq!(
+            resolve_in_vault(&vault, Some(&team), anthropic)
+                .await
+                .as_deref(),
+            Some("sk-team")
+        );
+        assert_eq!(
+            resolve_in_vault(&vault, Some(&other), anthropic)
+                .await
+                .as_deref(),
+            Some("sk-default")
+        );
+        assert_eq!(resolve_in_vault(&vault, Some(&other), None).await, None);
+
+        // An empty key removes the override
+        store(&vault, &template_provider(&team), " ").await.unwrap();
+        assert_eq!(
+            resolve_in_vault(&vault, Some(&team), anthropic)
+                .await
+                .as_deref(),
+            Some("sk-default")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_legacy_template_keys_are_read() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = EncryptedFileStore::open(dir.path().join("keys.credentials"), b"test").unwrap();
+        let vault = CredentialVault::with_store(Arc::new(file));
+        let template = RecordId::new("agent_template", "legacy");
+        vault
+            .store_credential(
+                &template_provider(&template),
+                CredentialType::ApplicationSecret,
+                "sk-legacy".to_string(),
+                None,
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(
+            resolve_in_vault(&vault, Some(&template), None)
+                .await
+                .as_deref(),
+            Some("sk-legacy")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_validate_key() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/v1/models"))
+            .and(header("x-api-key", "sk-good"))
+            .and(header("anthropic-version", ANTHROPIC_VERSION))
+            .respond_with(ResponseTemplate::new(200))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/v1/models"))
+            .respond_with(ResponseTemplate::new(401))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/models"))
+            .and(header("Authorization", "Bearer sk-openai"))
+            .respond_with(ResponseTemplate::new(200))
+            .mount(&server)
+            .await;
+
+        let base = Some(server.uri());
+        let anthropic = KeyService::Anthropic;
+        assert!(
+            validate_key(anthropic, "sk-good", base.as_deref())
+                .await
+                .is_ok()
+        );
+        let rejected = validate_key(anthropic, "sk-bad", base.as_deref())
+            .await
+            .unwrap_err();
+        assert!(rejected.contains("rejected"), "{}", rejected);
+        assert!(
+            validate_key(anthropic, "  ", base.as_deref())
+                .await
+                .is_err()
+        );
+        assert!(
+            validate_key(KeyService::Openai, "sk-openai", base.as_deref())
+                .await
+                .is_ok()
+        );
+    }
+}
@@ -0,0 +1,142 @@
+//! Claude Code backend
+//!
+//! Spawns a fresh `ClaudeSDKClient` subprocess per turn and resumes the
+//! stored session, so the Claude CLI keeps the conversation history. The
+//! subprocess gets the template's Anthropic API key in its environment.
+
+use super::{AgentEvent, ChatRequest, EventStream, LlmBackend, api_keys};
+use crate::view_model::agent::AgentTemplate;
+use async_trait::async_trait;
+use futures_util::stream::{self, StreamExt};
+use kodegen_tools_claude_agent::types::identifiers::SessionId;
+use kodegen_tools_claude_agent::{
+    ClaudeAgentOptions, ClaudeSDKClient, ContentBlock, Message as AgentMessage, SystemPrompt,
+};
+use surrealdb_types::RecordId;
+
+/// Built-in Claude Code tools every agent may use
+const CLAUDE_TOOLS: &[&str] = &[
+    "Read",      // Read files
+    "Write",     // Write files
+    "Edit",      // Edit files
+    "Bash",      // Execute commands
+    "Glob",      // Find files by pattern
+    "Grep",      // Search file contents
+    "Task",      // Spawn sub-agents
+    "WebFetch",  // Fetch web content
+    "WebSearch", // Search the web
+];
+
+/// Runs agents through the Claude Code CLI
+#[derive(Debug, Clone, Default)]
+pub struct ClaudeBackend {
+    /// Template whose own API key is preferred over the default
+    template: Option<RecordId>,
+}
+
+impl ClaudeBackend {
+    /// Backend for a template, billed to its API key if it has one
+    pub fn for_template(template: &AgentTemplate) -> Self {
+        Self {
+            template: Some(template.id.clone()),
+        }
+    }
+}
+
+#[async_trait]
+impl LlmBackend for ClaudeBackend {
+    fn name(&self) -> &str {
+        "claude"
+    }
+
+    async fn chat(&self, request: ChatRequest) -> Result<EventStream, String> {
+        let mut allowed_tools: Vec<String> = CLAUDE_TOOLS.iter().map(|&t| t.into()).collect();
+        allowed_tools.extend(request.mcp.allowed_tools());
+
+        // Sessions of the HTTP backends mean nothing to the CLI (the
+        // template switched backends) - start over instead of failing
+        let resume = request
+            .session_id
+            .as_deref()
+            .filter(|id| !id.starts_with(super::openai::SESSION_PREFIX))
+            .map(SessionId::from);
+
+        let options = ClaudeAgentOptions {
+            model: Some(request.model),
+            system_prompt: Some(SystemPrompt::String(request.system_prompt)),
+            max_turns: Some(request.max_turns),
+            allowed_tools,
+            mcp_servers: request.mcp.mcp_servers(),
+            // Resume from previous session if exists (lazy spawn pattern)
+            resume,
+            env: api_keys::claude_env(self.template.as_ref()).await,
+            ..Default::default()
+        };
+
+        let mut client = ClaudeSDKClient::new(options, None)
+            .await
+            .map_err(|e| format!("Failed to create Claude client: {}", e))?;
+
+        client
+            .send_message(&request.message)
+            .await
+            .map_err(|e| format!("Failed to send to agent: {}", e))?;
+
+        let messages = stream::unfold(client, |mut client| async move {
+            client.next_message().await.map(|message| (message, client))
+        });
+
+        Ok(messages
+            .flat_map(|message| stream::iter(events(message)))
+            .boxed())
+    }
+}
+
+/// Translate one CLI message into agent events
+fn events<E: std::fmt::Display>(
+    message: Result<AgentMessage, E>,
+) -> Vec<Result<AgentEvent, String>> {
+    let mut events = Vec::new();
+    match message {
+        Ok(AgentMessage::Assistant {
+            message,
+            session_id,
+            ..
+        }) => {
+            if let Some(sid) = session_id {
+                events.push(Ok(AgentEvent::Session(sid.as_str().to_string())));
+            }
+            for block in message.content {
+                match block {
+                    ContentBlock::Text { text } => events.push(Ok(AgentEvent::Text(text))),
+                    ContentBlock::ToolUse { name, .. } => {
+                        events.push(Ok(AgentEvent::ToolUse { name }))
+                    }
+                    ContentBlock::Thinking { thinking, .. } => {
+                        events.push(Ok(AgentEvent::Thinking(thinking)))
+                    }
+                    _ => {}
+                }
+            }
+        }
+        Ok(AgentMessage::Result {
+            is_error,
+            result,
+            session_id,
+            ..
+        }) => {
+            events.push(Ok(AgentEvent::Session(session_id.as_str().to_string())));
+            events.push(Ok(AgentEvent::Finished(if is_error {
+                Err(result.unwrap_or_else(|| "Unknown error".to_string()))
+            } else {
+                Ok(())
+            })));
+        }
+        Ok(AgentMessage::System { subtype, data }) => {
+            log::debug!("[Claude] System message: {} - {:?}", subtype, data);
+        }
+        Ok(_) => {}
+        Err(e) => events.push(Err(e.to_string())),
+    }
+    events
+}
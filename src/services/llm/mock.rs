@@ -0,0 +1,230 @@
+//! Scripted backend for running agent chat without a model
+//!
+//! Each call to [`LlmBackend::chat`] replays the next scripted turn, step
+//! by step, and records the request it was given. Scripts are built in
+//! code or loaded from JSON fixtures ([`ScriptedBackend::from_json`]);
+//! [`ScriptedAgents`] gives each agent of a conversation its own script.
+//!
+//! Waits use tokio's clock, so a test running with paused time
+//! (`#[tokio::test(start_paused = true)]`) replays timings exactly.
+
+use super::{AgentEvent, BackendResolver, ChatRequest, EventStream, LlmBackend};
+use crate::view_model::agent::AgentTemplate;
+use async_trait::async_trait;
+use futures_util::stream::{self, StreamExt};
+use parking_lot::Mutex;
+use serde::Deserialize;
+use std::collections::{HashMap, VecDeque};
+use std::sync::Arc;
+use std::time::Duration;
+
+/// One step of a scripted turn
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ScriptStep {
+    /// Emit an event
+    Event(AgentEvent),
+    /// Emit a transport error
+    Fail(String),
+    /// Pause before the next step
+    Wait(Duration),
+}
+
+/// A script step as written in a JSON fixture
+///
+/// `{"session": "s1"}`, `{"text": "Hel"}`, `{"tool_use": "Read"}`,
+/// `{"thinking": "..."}`, `"finish"`, `{"error": "..."}` (the agent reports
+/// an error), `{"fail": "..."}` (the stream breaks), `{"wait_ms": 120}`
+#[derive(Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum FixtureStep {
+    Session(String),
+    Text(String),
+    ToolUse(String),
+    Thinking(String),
+    Finish,
+    Error(String),
+    Fail(String),
+    WaitMs(u64),
+}
+
+impl From<FixtureStep> for ScriptStep {
+    fn from(step: FixtureStep) -> Self {
+        match step {
+            FixtureStep::Session(id) => ScriptStep::Event(AgentEvent::Session(id)),
+            FixtureStep::Text(text) => ScriptStep::Event(AgentEvent::Text(text)),
+            FixtureStep::ToolUse(name) => ScriptStep::Event(AgentEvent::ToolUse { name }),
+            FixtureStep::Thinking(text) => ScriptStep::Event(AgentEvent::Thinking(text)),
+            FixtureStep::Finish => ScriptStep::Event(AgentEvent::Finished(Ok(()))),
+            FixtureStep::Error(error) => ScriptStep::Event(AgentEvent::Finished(Err(error))),
+            FixtureStep::Fail(error) => ScriptStep::Fail(error),
+            FixtureStep::WaitMs(ms) => ScriptStep::Wait(Duration::from_millis(ms)),
+        }
+    }
+}
+
+/// Backend that replays scripted turns in order
+#[derive(Default)]
+pub struct ScriptedBackend {
+    turns: Mutex<VecDeque<Vec<ScriptStep>>>,
+    requests: Mutex<Vec<ChatRequest>>,
+}
+
+impl ScriptedBackend {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Backend replaying a JSON fixture: an array of turns, each an array of steps
+    ///
+    /// # Errors
+    /// Returns error if the JSON does not match the fixture format
+    pub fn from_json(json: &str) -> Result<Self, String> {
+        let turns: Vec<Vec<FixtureStep>> =
+            serde_json::from_str(json).map_err(|e| format!("Invalid script fixture: {}", e))?;
+        Ok(turns.into_iter().fold(Self::new(), |backend, steps| {
+            backend.turn(steps.into_iter().map(ScriptStep::from).collect())
+        }))
+    }
+
+    /// Queue a turn
+    pub fn turn(self, steps: Vec<ScriptStep>) -> Self {
+        self.turns.lock().push_back(steps);
+        self
+    }
+
+    /// Queue a turn that replies with `text` in session `session_id`
+    pub fn reply(self, session_id: &str, text: &str) -> Self {
+        self.turn(vec![
+            ScriptStep::Event(AgentEvent::Session(session_id.to_string())),
+            ScriptStep::Event(AgentEvent::Text(text.to_string())),
+            ScriptStep::Event(AgentEvent::Finished(Ok(()))),
+        ])
+    }
+
+    /// Requests received so far, oldest first
+    pub fn requests(&self) -> Vec<ChatRequest> {
+        self.requests.lock().clone()
+    }
+}
+
+#[async_trait]
+impl LlmBackend for ScriptedBackend {
+    fn name(&self) -> &str {
+        "scripted"
+    }
+
+    async fn chat(&self, request: ChatRequest) -> Result<EventStream, String> {
+        self.requests.lock().push(request);
+        let steps = self
+            .turns
+            .lock()
+            .pop_front()
+            .ok_or_else(|| "No scripted turn left".to_string())?;
+
+        Ok(stream::iter(steps)
+            .filter_map(|step| async move {
+                match step {
+                    ScriptStep::Event(event) => Some(Ok(event)),
+                    ScriptStep::Fail(error) => Some(Err(error)),
+                    ScriptStep::Wait(duration) => {
+                        tokio::time::sleep(duration).await;
+                        None
+                    }
+                }
+            })
+            .boxed())
+    }
+}
+
+/// Scripted backends per agent, keyed by template name
+///
+/// Agents without a script get an empty backend, so their turn fails to
+/// start.
+#[derive(Default)]
+pub struct ScriptedAgents {
+    agents: HashMap<String, Arc<ScriptedBackend>>,
+}
+
+impl ScriptedAgents {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Script the agent whose template is named `name`
+    pub fn agent(mut self, name: &str, backend: ScriptedBackend) -> Self {
+        self.agents.insert(name.to_string(), Arc::new(backend));
+        self
+    }
+
+    /// The backend scripted for `name`, to inspect its requests
+    pub fn get(&self, name: &str) -> Option<Arc<ScriptedBackend>> {
+        self.agents.get(name).cloned()
+    }
+}
+
+impl BackendResolver for ScriptedAgents {
+    fn backend(&self, template: &AgentTemplate) -> Arc<dyn LlmBackend> {
+        match self.agents.get(&template.name) {
+            Some(backend) => backend.clone(),
+            None => Arc::new(ScriptedBackend::new()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_replays_turns_in_order() {
+        let backend = ScriptedBackend::new().reply("s1", "first").turn(vec![
+            ScriptStep::Wait(Duration::from_millis(1)),
+            ScriptStep::Fail("boom".to_string()),
+        ]);
+
+        let first: Vec<_> = backend
+            .chat(ChatRequest::default())
+            .await
+            .unwrap()
+            .collect()
+            .await;
+        assert_eq!(first[1], Ok(AgentEvent::Text("first".to_string())));
+
+        let request = ChatRequest {
+            message: "second".to_string(),
+            ..Default::default()
+        };
+        let second: Vec<_> = backend.chat(request).await.unwrap().collect().await;
+        assert_eq!(second, vec![Err("boom".to_string())]);
+
+        assert!(backend.chat(ChatRequest::default()).await.is_err());
+        assert_eq!(backend.requests()[1].message, "second");
+    }
+
+    #[tokio::test]
+    async fn test_loads_json_fixture() {
+        let backend = ScriptedBackend::from_json(
+            r#"[[{"session": "s1"}, {"wait_ms": 5}, {"tool_use": "Read"}, {"error": "denied"}]]"#,
+        )
+        .unwrap();
+
+        let events: Vec<_> = backend
+            .chat(ChatRequest::default())
+            .await
+            .unwrap()
+            .collect()
+            .await;
+        assert_eq!(
+            events,
+            vec![
+                Ok(AgentEvent::Session("s1".to_string())),
+                Ok(AgentEvent::ToolUse {
+                    name: "Read".to_string()
+                }),
+                Ok(AgentEvent::Finished(Err("denied".to_string()))),
+            ]
+        );
+
+        assert!(ScriptedBackend::from_json(r#"[[{"speak": "hi"}]]"#).is_err());
+    }
+}
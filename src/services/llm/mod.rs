@@ -0,0 +1,109 @@
+//! Inference backends for agent chat
+//!
+//! Every agent turn goes through an [`LlmBackend`]: it takes one user
+//! message plus the agent's configuration and streams back backend-neutral
+//! [`AgentEvent`]s, which src/services/agent_chat.rs writes to the database.
+//! The template's `backend` field picks the implementation:
+//! - `claude` - [`ClaudeBackend`], the Claude Code subprocess with its built-in tools
+//! - `openai` - [`OpenAiBackend`] against an OpenAI-compatible HTTP endpoint
+//! - `local` - [`OpenAiBackend`] against Ollama or a llama.cpp server, which
+//!   both serve the same `/v1/chat/completions` API
+//!
+//! Each backend owns its sessions. The session id reported through
+//! [`AgentEvent::Session`] is stored per agent in `conversation.agent_sessions`
+//! and handed back on the next turn. [`ScriptedBackend`] replays canned
+//! events so agent chat can run without any model.
+
+pub mod api_keys;
+pub mod claude;
+pub mod mock;
+pub mod openai;
+
+pub use claude::ClaudeBackend;
+pub use mock::{ScriptStep, ScriptedAgents, ScriptedBackend};
+pub use openai::OpenAiBackend;
+
+use crate::services::external_mcp::AgentMcp;
+use crate::view_model::agent::{AgentBackend, AgentTemplate};
+use async_trait::async_trait;
+use futures_util::stream::BoxStream;
+use std::sync::Arc;
+
+/// One agent turn, as handed to a backend
+#[derive(Debug, Clone, Default)]
+pub struct ChatRequest {
+    /// Backend-specific model name (see [`AgentTemplate::model_name`])
+    pub model: String,
+    pub system_prompt: String,
+    /// Upper bound on model round trips, including tool calls
+    pub max_turns: u32,
+    /// User-registered MCP servers enabled on the template
+    pub mcp: AgentMcp,
+    /// Session to continue, as reported by an earlier [`AgentEvent::Session`]
+    pub session_id: Option<String>,
+    /// The user's message
+    pub message: String,
+}
+
+/// Progress of an agent turn
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AgentEvent {
+    /// Session this turn belongs to (may be repeated)
+    Session(String),
+    /// Reply text, appended to what was streamed before
+    Text(String),
+    /// The agent called a tool
+    ToolUse { name: String },
+    /// Reasoning text, not shown in the conversation
+    Thinking(String),
+    /// The turn is over; `Err` carries the agent's error report
+    Finished(Result<(), String>),
+}
+
+/// Stream of events for one turn
+///
+/// An `Err` item is a transport failure (process died, connection lost)
+/// and ends the turn like [`AgentEvent::Finished`] does.
+pub type EventStream = BoxStream<'static, Result<AgentEvent, String>>;
+
+/// An inference backend
+#[async_trait]
+pub trait LlmBackend: Send + Sync {
+    /// Short name for logs and error messages
+    fn name(&self) -> &str;
+
+    /// Start a turn and stream its events
+    ///
+    /// # Errors
+    /// Returns error if the turn cannot be started (missing credentials,
+    /// process failed to spawn); failures later in the turn arrive on the stream
+    async fn chat(&self, request: ChatRequest) -> Result<EventStream, String>;
+}
+
+/// Chooses the backend that runs an agent
+///
+/// Agent chat resolves the backend per template, so tests can swap in a
+/// [`ScriptedBackend`] without touching the templates.
+pub trait BackendResolver: Send + Sync {
+    fn backend(&self, template: &AgentTemplate) -> Arc<dyn LlmBackend>;
+}
+
+/// The backend configured on each template
+pub struct TemplateBackends;
+
+impl BackendResolver for TemplateBackends {
+    fn backend(&self, template: &AgentTemplate) -> Arc<dyn LlmBackend> {
+        match template.backend {
+            AgentBackend::Claude => Arc::new(ClaudeBackend::for_template(template)),
+            AgentBackend::Openai => Arc::new(OpenAiBackend::openai(template)),
+            AgentBackend::Local => Arc::new(OpenAiBackend::local(template)),
+        }
+    }
+}
+
+/// Every template runs on the same backend
+impl BackendResolver for Arc<dyn LlmBackend> {
+    fn backend(&self, _template: &AgentTemplate) -> Arc<dyn LlmBackend> {
+        Arc::clone(self)
+    }
+}
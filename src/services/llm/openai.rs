@@ -0,0 +1,798 @@
+//! OpenAI-compatible HTTP backend
+//!
+//! Talks to any server implementing streaming `/v1/chat/completions`: the
+//! OpenAI API itself, hosted gateways, and local servers such as Ollama
+//! (`http://localhost:11434/v1`) or llama.cpp (`llama-server`).
+//!
+//! These endpoints are stateless, so the backend keeps each session's
+//! message history in memory under a generated session id. Histories do
+//! not survive a restart, idle ones expire, only the most recently used are
+//! kept, and all are dropped at sign-out; the next turn then starts a fresh
+//! session.
+//!
+//! Tools come from the MCP servers enabled on the template: they are
+//! offered to the model as functions named `mcp__<server>__<tool>`, and
+//! the backend runs the calls itself, looping until the model answers
+//! without calling a tool or `max_turns` round trips are used up.
+//!
+//! API keys come from the credential vault (see src/services/llm/api_keys.rs):
+//! the template's own key, else the default OpenAI key or `OPENAI_API_KEY`.
+
+use super::api_keys::{self, KeyService};
+use super::{AgentEvent, ChatRequest, EventStream, LlmBackend};
+use crate::environment::native::HttpClient;
+use crate::services::external_mcp::{self, AgentMcp};
+use crate::view_model::agent::AgentTemplate;
+use async_trait::async_trait;
+use futures_util::stream::{self, StreamExt};
+use parking_lot::Mutex;
+use rmcp::RoleClient;
+use rmcp::model::{CallToolRequestParam, CallToolResult, ClientInfo};
+use rmcp::service::RunningService;
+use serde_json::{Value, json};
+use std::collections::HashMap;
+use std::sync::OnceLock;
+use std::time::{Duration, Instant};
+use surrealdb_types::RecordId;
+use tokio::sync::mpsc::{UnboundedSender, unbounded_channel};
+
+/// Default endpoint of the `openai` backend
+pub const DEFAULT_OPENAI_URL: &str = "https://api.openai.com/v1";
+
+/// Default endpoint of the `local` backend (Ollama)
+pub const DEFAULT_LOCAL_URL: &str = "http://localhost:11434/v1";
+
+/// Prefix of session ids issued by this backend
+pub const SESSION_PREFIX: &str = "http-";
+
+/// Longest function name the chat completions API accepts
+const MAX_FUNCTION_NAME: usize = 64;
+
+/// Longest a streamed completion may take; local models can be slow
+const STREAM_TIMEOUT: Duration = Duration::from_secs(600);
+
+/// Most session histories kept; the least recently used are dropped first
+const MAX_SESSIONS: usize = 64;
+
+/// How long an idle session's history is kept
+const SESSION_TTL: Duration = Duration::from_secs(6 * 60 * 60);
+
+/// A session's message history (system prompt excluded)
+struct Session {
+    history: Vec<Value>,
+    last_used: Instant,
+}
+
+/// Histories keyed by session id
+static SESSIONS: OnceLock<Mutex<HashMap<String, Session>>> = OnceLock::new();
+
+fn sessions() -> &'static Mutex<HashMap<String, Session>> {
+    SESSIONS.get_or_init(Default::default)
+}
+
+/// Take a session's history for the turn continuing it
+fn take_session(id: &str) -> Option<Vec<Value>> {
+    let mut sessions = sessions().lock();
+    prune(&mut sessions, Instant::now());
+    sessions.remove(id).map(|session| session.history)
+}
+
+/// Keep a session's history for its next turn
+fn store_session(id: String, history: Vec<Value>) {
+    let now = Instant::now();
+    let mut sessions = sessions().lock();
+    sessions.insert(
+        id,
+        Session {
+            history,
+            last_used: now,
+        },
+    );
+    prune(&mut sessions, now);
+}
+
+/// Drop expired histories, then the least recently used beyond the limit
+fn prune(sessions: &mut HashMap<String, Session>, now: Instant) {
+    sessions.retain(|_, session| now.duration_since(session.last_used) < SESSION_TTL);
+    while sessions.len() > MAX_SESSIONS {
+        let Some(oldest) = sessions
+            .iter()
+            .min_by_key(|(_, session)| session.last_used)
+            .map(|(id, _)| id.clone())
+        else {
+            break;
+        };
+        sessions.remove(&oldest);
+    }
+}
+
+/// Forget every session's history
+///
+/// Called at sign-out, so no conversation history outlives the account in
+/// memory.
+pub fn clear_sessions() {
+    let mut sessions = sessions().lock();
+    log::info!("[LLM] Dropping {} HTTP session histories", sessions.len());
+    sessions.clear();
+}
+
+/// Runs agents against an OpenAI-compatible endpoint
+pub struct OpenAiBackend {
+    label: &'static str,
+    base_url: String,
+    /// Template whose own API key is preferred
+    template: Option<RecordId>,
+    /// Service whose default key applies when the template has none
+    service: Option<KeyService>,
+    key_required: bool,
+}
+
+impl OpenAiBackend {
+    /// Endpoint without authentication, e.g. a local server
+    pub fn new(base_url: impl Into<String>) -> Self {
+        Self {
+            label: "openai-compatible",
+            base_url: base_url.into(),
+            template: None,
+            service: None,
+            key_required: false,
+        }
+    }
+
+    /// The template's `openai` backend: its URL (or the OpenAI API) and API key
+    pub fn openai(template: &AgentTemplate) -> Self {
+        Self {
+            label: "openai",
+            template: Some(template.id.clone()),
+            service: Some(KeyService::Openai),
+            key_required: true,
+            ..Self::new(endpoint(template, DEFAULT_OPENAI_URL))
+        }
+    }
+
+    /// The template's `local` backend: Ollama unless another URL is set
+    ///
+    /// An API key is sent only if one is stored (llama.cpp `--api-key`).
+    pub fn local(template: &AgentTemplate) -> Self {
+        Self {
+            label: "local",
+            template: Some(template.id.clone()),
+            ..Self::new(endpoint(template, DEFAULT_LOCAL_URL))
+        }
+    }
+
+    async fn api_key(&self) -> Result<Option<String>, String> {
+        if self.template.is_none() && self.service.is_none() {
+            return Ok(None);
+        }
+        let key = api_keys::resolve_key(self.template.as_ref(), self.service).await;
+        if key.is_none() && self.key_required {
+            return Err(format!(
+                "No API key stored for this agent{}",
+                self.service
+                    .map(|service| format!(
+                        ", no default {} key and {} is not set",
+                        service.label(),
+                        service.env_var()
+                    ))
+                    .unwrap_or_default()
+            ));
+        }
+        Ok(key)
+    }
+}
+
+fn endpoint(template: &AgentTemplate, default: &str) -> String {
+    template
+        .backend_url
+        .as_deref()
+        .map(str::trim)
+        .filter(|url| !url.is_empty())
+        .unwrap_or(default)
+        .to_string()
+}
+
+#[async_trait]
+impl LlmBackend for OpenAiBackend {
+    fn name(&self) -> &str {
+        self.label
+    }
+
+    async fn chat(&self, request: ChatRequest) -> Result<EventStream, String> {
+        if request.model.trim().is_empty() {
+            return Err(format!(
+                "No model configured for the {} backend",
+                self.label
+            ));
+        }
+        let api_key = self.api_key().await?;
+
+        let stored = request
+            .session_id
+            .as_ref()
+            .and_then(|id| take_session(id).map(|history| (id.clone(), history)));
+        let (session_id, history) = stored.unwrap_or_else(|| {
+            if let Some(id) = &request.session_id {
+                log::info!("[LLM] Session {} not found, starting a new one", id);
+            }
+            (
+                format!("{}{}", SESSION_PREFIX, uuid::Uuid::new_v4().simple()),
+                Vec::new(),
+            )
+        });
+
+        let (tx, rx) = unbounded_channel();
+        let turn = Turn {
+            label: self.label,
+            url: format!("{}/chat/completions", self.base_url.trim_end_matches('/')),
+            api_key,
+            request,
+            session_id,
+            history,
+            tx,
+        };
+        tokio::spawn(turn.run());
+
+        Ok(stream::unfold(rx, |mut rx| async move {
+            rx.recv().await.map(|event| (event, rx))
+        })
+        .boxed())
+    }
+}
+
+/// Why a turn ended early
+enum Failure {
+    /// The endpoint rejected the request or the agent loop gave up
+    Agent(String),
+    /// The connection or the stream broke
+    Transport(String),
+}
+
+/// One turn, driven on its own task and reported through `tx`
+struct Turn {
+    label: &'static str,
+    url: String,
+    api_key: Option<String>,
+    request: ChatRequest,
+    session_id: String,
+    history: Vec<Value>,
+    tx: UnboundedSender<Result<AgentEvent, String>>,
+}
+
+impl Turn {
+    async fn run(mut self) {
+        let _ = self
+            .tx
+            .send(Ok(AgentEvent::Session(self.session_id.clone())));
+        self.history
+            .push(json!({ "role": "user", "content": self.request.message }));
+
+        let tools = McpTools::connect(&self.request.mcp).await;
+        let outcome = self.converse(&tools).await;
+        tools.close().await;
+
+        // Keep the history for the next turn, even after a failure
+        let Turn {
+            session_id,
+            history,
+            tx,
+            ..
+        } = self;
+        store_session(session_id, history);
+
+        let _ = tx.send(match outcome {
+            Ok(()) => Ok(AgentEvent::Finished(Ok(()))),
+            Err(Failure::Agent(e)) => Ok(AgentEvent::Finished(Err(e))),
+            Err(Failure::Transport(e)) => Err(e),
+        });
+    }
+
+    async fn converse(&mut self, tools: &McpTools) -> Result<(), Failure> {
+        for _ in 0..self.request.max_turns.max(1) {
+            let reply = self.complete(tools).await?;
+            self.history.push(reply.message());
+            if reply.tool_calls.is_empty() {
+                return Ok(());
+            }
+
+            for call in &reply.tool_calls {
+                let _ = self.tx.send(Ok(AgentEvent::ToolUse {
+                    name: call.name.clone(),
+                }));
+                let output = tools.call(&call.name, &call.arguments).await;
+                self.history.push(json!({
+                    "role": "tool",
+                    "tool_call_id": call.id,
+                    "content": output,
+                }));
+            }
+        }
+
+        Err(Failure::Agent(format!(
+            "Stopped after {} turns without a final answer",
+            self.request.max_turns
+        )))
+    }
+
+    /// One streamed chat completion
+    async fn complete(&self, tools: &McpTools) -> Result<Reply, Failure> {
+        let mut messages = vec![json!({ "role": "system", "content": self.request.system_prompt })];
+        messages.extend(self.history.iter().cloned());
+
+        let mut body = json!({
+            "model": self.request.model,
+            "messages": messages,
+            "stream": true,
+        });
+        if !tools.definitions.is_empty() {
+            body["tools"] = Value::Array(tools.definitions.clone());
+        }
+
+        let response = HttpClient::shared()
+            .send(|http| {
+                let request = http.post(&self.url).json(&body).timeout(STREAM_TIMEOUT);
+                match &self.api_key {
+                    Some(key) => request.bearer_auth(key),
+                    None => request,
+                }
+            })
+            .await
+            .map_err(|e| {
+                Failure::Transport(format!("Could not reach {} backend: {}", self.label, e))
+            })?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let text = response.text().await.unwrap_or_default();
+            return Err(Failure::Agent(format!(
+                "{} backend returned {}: {}",
+                self.label,
+                status,
+                error_message(&text)
+            )));
+        }
+
+        let mut reply = Reply::default();
+        let mut sse = SseBuffer::default();
+        let mut chunks = response.bytes_stream();
+        while let Some(chunk) = chunks.next().await {
+            let chunk = chunk.map_err(|e| Failure::Transport(format!("Stream error: {}", e)))?;
+            for data in sse.push(&chunk) {
+                if data == "[DONE]" {
+                    return Ok(reply);
+                }
+                let value: Value = serde_json::from_str(&data)
+                    .map_err(|e| Failure::Transport(format!("Invalid stream chunk: {}", e)))?;
+                if value.get("error").is_some() {
+                    return Err(Failure::Agent(error_message(&data)));
+                }
+                for event in reply.apply(&value) {
+                    let _ = self.tx.send(Ok(event));
+                }
+            }
+        }
+        Ok(reply)
+    }
+}
+
+/// Human-readable error from an error response body
+fn error_message(body: &str) -> String {
+    serde_json::from_str::<Value>(body)
+        .ok()
+        .and_then(|v| v["error"]["message"].as_str().map(str::to_string))
+        .unwrap_or_else(|| body.trim().to_string())
+}
+
+/// Splits a server-sent event stream into `data:` payloads
+#[derive(Default)]
+struct SseBuffer {
+    pending: Vec<u8>,
+}
+
+impl SseBuffer {
+    /// Add received bytes and return the payloads of all complete lines
+    fn push(&mut self, bytes: &[u8]) -> Vec<String> {
+        self.pending.extend_from_slice(bytes);
+        let mut payloads = Vec::new();
+        while let Some(end) = self.pending.iter().position(|&b| b == b'\n') {
+            let line: Vec<u8> = self.pending.drain(..=end).collect();
+            let line = String::from_utf8_lossy(&line);
+            if let Some(data) = line.trim_end().strip_prefix("data:") {
+                payloads.push(data.trim_start().to_string());
+            }
+        }
+        payloads
+    }
+}
+
+/// A tool call assembled from streamed deltas
+#[derive(Debug, Default, Clone, PartialEq)]
+struct ToolCall {
+    id: String,
+    name: String,
+    arguments: String,
+}
+
+/// The assistant message assembled from streamed deltas
+#[derive(Debug, Default)]
+struct Reply {
+    content: String,
+    tool_calls: Vec<ToolCall>,
+}
+
+impl Reply {
+    /// Apply one `chat.completion.chunk` and return the events it produces
+    fn apply(&mut self, chunk: &Value) -> Vec<AgentEvent> {
+        let delta = &chunk["choices"][0]["delta"];
+        let mut events = Vec::new();
+
+        if let Some(text) = delta["content"].as_str()
+            && !text.is_empty()
+        {
+            self.content.push_str(text);
+            events.push(AgentEvent::Text(text.to_string()));
+        }
+
+        // Reasoning models served by Ollama and llama.cpp
+        if let Some(text) = delta["reasoning_content"]
+            .as_str()
+            .or(delta["reasoning"].as_str())
+            && !text.is_empty()
+        {
+            events.push(AgentEvent::Thinking(text.to_string()));
+        }
+
+        for call in delta["tool_calls"].as_array().into_iter().flatten() {
+            let index = call["index"].as_u64().unwrap_or(0) as usize;
+            if self.tool_calls.len() <= index {
+                self.tool_calls.resize(index + 1, ToolCall::default());
+            }
+            let slot = &mut self.tool_calls[index];
+            if let Some(id) = call["id"].as_str() {
+                slot.id = id.to_string();
+            }
+            if let Some(name) = call["function"]["name"].as_str() {
+                slot.name.push_str(name);
+            }
+            if let Some(arguments) = call["function"]["arguments"].as_str() {
+                slot.arguments.push_str(arguments);
+            }
+        }
+
+        events
+    }
+
+    /// The reply as a history entry
+    fn message(&self) -> Value {
+        let mut message = json!({ "role": "assistant", "content": self.content });
+        if !self.tool_calls.is_empty() {
+            message["tool_calls"] = self
+                .tool_calls
+                .iter()
+                .map(|call| {
+                    json!({
+                        "id": call.id,
+                        "type": "function",
+                        "function": { "name": call.name, "arguments": call.arguments },
+                    })
+                })
+                .collect();
+        }
+        message
+    }
+}
+
+/// Function name for an MCP tool, restricted to `[A-Za-z0-9_-]{1,64}`
+fn function_name(server: &str, tool: &str) -> String {
+    format!("mcp__{}__{}", server, tool)
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '_' || c == '-' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .take(MAX_FUNCTION_NAME)
+        .collect()
+}
+
+/// Connected MCP servers and the functions they provide
+#[derive(Default)]
+struct McpTools {
+    clients: Vec<RunningService<RoleClient, ClientInfo>>,
+    /// Function name → (client index, MCP tool name)
+    routes: HashMap<String, (usize, String)>,
+    /// `tools` entries for the completion request
+    definitions: Vec<Value>,
+}
+
+impl McpTools {
+    /// Connect to every enabled server, skipping (and logging) failures
+    async fn connect(mcp: &AgentMcp) -> Self {
+        let mut tools = Self::default();
+        for (server, secrets) in &mcp.servers {
+            let client = match external_mcp::connect(server, secrets).await {
+                Ok(client) => client,
+                Err(e) => {
+                    log::error!("[LLM] Skipping MCP server '{}': {}", server.name, e);
+                    continue;
+                }
+            };
+            let listed = match client.list_all_tools().await {
+                Ok(listed) => listed,
+                Err(e) => {
+                    log::error!("[LLM] Could not list tools of '{}': {}", server.name, e);
+                    let _ = client.cancel().await;
+                    continue;
+                }
+            };
+
+            for tool in listed {
+                let name = function_name(&server.name, &tool.name);
+                tools.definitions.push(json!({
+                    "type": "function",
+                    "function": {
+                        "name": name,
+                        "description": tool.description.as_deref().unwrap_or_default(),
+                        "parameters": Value::Object(tool.input_schema.as_ref().clone()),
+                    },
+                }));
+                tools
+                    .routes
+                    .insert(name, (tools.clients.len(), tool.name.to_string()));
+            }
+            tools.clients.push(client);
+        }
+        tools
+    }
+
+    /// Run a tool call and return its output for the model
+    ///
+    /// Failures are reported to the model as the output rather than ending
+    /// the turn, so it can recover.
+    async fn call(&self, function: &str, arguments: &str) -> String {
+        let Some((index, tool)) = self.routes.get(function) else {
+            return format!("Unknown tool: {}", function);
+        };
+        let arguments = match arguments.trim() {
+            "" => None,
+            text => match serde_json::from_str(text) {
+                Ok(Value::Object(map)) => Some(map),
+                Ok(_) => return format!("Arguments for {} must be a JSON object", function),
+                Err(e) => return format!("Invalid arguments for {}: {}", function, e),
+            },
+        };
+
+        let param = CallToolRequestParam {
+            name: tool.clone().into(),
+            arguments,
+        };
+        match self.clients[*index].call_tool(param).await {
+            Ok(result) => tool_output(&result),
+            Err(e) => format!("Tool {} failed: {}", function, e),
+        }
+    }
+
+    async fn close(self) {
+        for client in self.clients {
+            if let Err(e) = client.cancel().await {
+                log::debug!("[LLM] Closing MCP client: {}", e);
+            }
+        }
+    }
+}
+
+fn tool_output(result: &CallToolResult) -> String {
+    let text = result
+        .content
+        .iter()
+        .map(|content| match content.as_text() {
+            Some(text) => text.text.clone(),
+            None => serde_json::to_string(content).unwrap_or_default(),
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+    if result.is_error == Some(true) {
+        format!("Error: {}", text)
+    } else {
+        text
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    fn sse(chunks: &[Value]) -> String {
+        let mut body: String = chunks
+            .iter()
+            .map(|chunk| format!("data: {}\n\n", chunk))
+            .collect();
+        body.push_str("data: [DONE]\n\n");
+        body
+    }
+
+    fn text_chunk(text: &str) -> Value {
+        json!({ "choices": [{ "index": 0, "delta": { "content": text } }] })
+    }
+
+    #[test]
+    fn test_sse_buffer_joins_split_lines() {
+        let mut buffer = SseBuffer::default();
+        let bytes = "data: {\"a\":\"é\"}\n\n: keep-alive\ndata: [DONE]\n".as_bytes();
+        let (head, tail) = bytes.split_at(13); // inside the two-byte 'é'
+
+        assert!(buffer.push(head).is_empty());
+        assert_eq!(buffer.push(tail), vec!["{\"a\":\"é\"}", "[DONE]"]);
+    }
+
+    #[test]
+    fn test_reply_assembles_tool_calls() {
+        let mut reply = Reply::default();
+        let chunks = [
+            json!({ "choices": [{ "delta": { "content": "Checking" } }] }),
+            json!({ "choices": [{ "delta": { "tool_calls": [
+                { "index": 0, "id": "call_1", "function": { "name": "mcp__files__read", "arguments": "{\"pa" } }
+            ] } }] }),
+            json!({ "choices": [{ "delta": { "tool_calls": [
+                { "index": 0, "function": { "arguments": "th\":\"a\"}" } }
+            ] } }] }),
+        ];
+
+        let events: Vec<_> = chunks.iter().flat_map(|c| reply.apply(c)).collect();
+        assert_eq!(events, vec![AgentEvent::Text("Checking".to_string())]);
+        assert_eq!(
+            reply.tool_calls,
+            vec![ToolCall {
+                id: "call_1".to_string(),
+                name: "mcp__files__read".to_string(),
+                arguments: "{\"path\":\"a\"}".to_string(),
+            }]
+        );
+        assert_eq!(
+            reply.message()["tool_calls"][0]["function"]["name"],
+            "mcp__files__read"
+        );
+    }
+
+    #[test]
+    fn test_prune_drops_expired_then_least_recently_used() {
+        let start = Instant::now();
+        let session = |seconds: u64| Session {
+            history: Vec::new(),
+            last_used: start + Duration::from_secs(seconds),
+        };
+        let mut sessions: HashMap<String, Session> = (0..MAX_SESSIONS as u64 + 2)
+            .map(|i| (format!("s{i}"), session(i)))
+            .collect();
+
+        prune(
+            &mut sessions,
+            start + Duration::from_secs(MAX_SESSIONS as u64 + 2),
+        );
+        assert_eq!(sessions.len(), MAX_SESSIONS);
+        assert!(!sessions.contains_key("s0") && !sessions.contains_key("s1"));
+
+        prune(&mut sessions, start + SESSION_TTL + Duration::from_secs(10));
+        assert_eq!(sessions.len(), MAX_SESSIONS - 9);
+        assert!(!sessions.contains_key("s10") && sessions.contains_key("s11"));
+    }
+
+    #[test]
+    fn test_function_name_is_sanitized() {
+        assert_eq!(
+            function_name("github", "list.issues"),
+            "mcp__github__list_issues"
+        );
+        assert_eq!(
+            function_name("s", &"x".repeat(100)).len(),
+            MAX_FUNCTION_NAME
+        );
+    }
+
+    async fn collect(stream: EventStream) -> Vec<Result<AgentEvent, String>> {
+        stream.collect().await
+    }
+
+    #[tokio::test]
+    async fn test_streams_reply_and_continues_session() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/v1/chat/completions"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("content-type", "text/event-stream")
+                    .set_body_string(sse(&[text_chunk("Hel"), text_chunk("lo")])),
+            )
+            .mount(&server)
+            .await;
+
+        let backend = OpenAiBackend::new(format!("{}/v1", server.uri()));
+        let request = ChatRequest {
+            model: "llama3".to_string(),
+            system_prompt: "Be brief.".to_string(),
+            max_turns: 5,
+            message: "Hi".to_string(),
+            ..Default::default()
+        };
+
+        let events = collect(backend.chat(request.clone()).await.unwrap()).await;
+        let Some(Ok(AgentEvent::Session(session_id))) = events.first().cloned() else {
+            panic!("expected a session first, got {:?}", events);
+        };
+        assert_eq!(
+            events[1..],
+            [
+                Ok(AgentEvent::Text("Hel".to_string())),
+                Ok(AgentEvent::Text("lo".to_string())),
+                Ok(AgentEvent::Finished(Ok(()))),
+            ]
+        );
+
+        // The next turn replays the history under the same session
+        let events = collect(
+            backend
+                .chat(ChatRequest {
+                    session_id: Some(session_id.clone()),
+                    message: "Again".to_string(),
+                    ..request
+                })
+                .await
+                .unwrap(),
+        )
+        .await;
+        assert_eq!(events[0], Ok(AgentEvent::Session(session_id)));
+
+        let requests = server.received_requests().await.unwrap();
+        let body: Value = serde_json::from_slice(&requests[1].body).unwrap();
+        let contents: Vec<&str> = body["messages"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|m| m["content"].as_str().unwrap())
+            .collect();
+        assert_eq!(contents, ["Be brief.", "Hi", "Hello", "Again"]);
+        assert_eq!(body["model"], "llama3");
+        assert!(body.get("tools").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_error_response_finishes_with_error() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .respond_with(
+                ResponseTemplate::new(404)
+                    .set_body_json(json!({ "error": { "message": "model 'nope' not found" } })),
+            )
+            .mount(&server)
+            .await;
+
+        let backend = OpenAiBackend::new(server.uri());
+        let events = collect(
+            backend
+                .chat(ChatRequest {
+                    model: "nope".to_string(),
+                    message: "Hi".to_string(),
+                    max_turns: 1,
+                    ..Default::default()
+                })
+                .await
+                .unwrap(),
+        )
+        .await;
+
+        match events.last() {
+            Some(Ok(AgentEvent::Finished(Err(e)))) => assert!(e.contains("model 'nope' not found")),
+            other => panic!("expected an agent error, got {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_missing_model_is_rejected() {
+        let backend = OpenAiBackend::new("http://127.0.0.1:9");
+        assert!(backend.chat(ChatRequest::default()).await.is_err());
+    }
+}
@@ -0,0 +1,516 @@
+//! MCP server exposing conversations and agents to other MCP clients
+//!
+//! Lets another agent consult or delegate to a CYRUP agent: it can list
+//! agent templates, start a conversation with them and send messages (the
+//! `send_message` tool returns once the addressed agents have replied).
+//! Conversations are readable as Markdown resources, and every agent
+//! template is offered as a prompt carrying its system prompt.
+//!
+//! Transports:
+//! - stdio: `cyrup-cli mcp`, for clients that spawn the server themselves
+//! - streamable HTTP: `/mcp` on the local API server
+//!   ([`crate::services::api_server`]), behind the same bearer token
+
+use crate::database::Database;
+use crate::database::messages::MessageCursor;
+use crate::services::agent_chat;
+use crate::services::api_server::{ApiConversation, ApiMessage, ApiTemplate};
+use crate::services::llm::{BackendResolver, TemplateBackends};
+use crate::view_model::conversation::Conversation;
+use crate::view_model::message::AuthorType;
+use rmcp::handler::server::tool::ToolRouter;
+use rmcp::handler::server::wrapper::Parameters;
+use rmcp::model::{
+    CallToolResult, Content, GetPromptRequestParam, GetPromptResult, Implementation,
+    ListPromptsResult, ListResourcesResult, PaginatedRequestParam, Prompt, PromptArgument,
+    PromptMessage, PromptMessageRole, RawResource, ReadResourceRequestParam, ReadResourceResult,
+    Resource, ResourceContents, ServerCapabilities, ServerInfo,
+};
+use rmcp::service::RequestContext;
+use rmcp::transport::streamable_http_server::session::local::LocalSessionManager;
+use rmcp::transport::streamable_http_server::{StreamableHttpServerConfig, StreamableHttpService};
+use rmcp::{
+    ErrorData as McpError, RoleServer, ServerHandler, ServiceExt, schemars, tool, tool_handler,
+    tool_router,
+};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use surrealdb_types::{RecordId, ToSql};
+
+/// Prefix of conversation resource URIs; the record key follows
+const CONVERSATION_URI: &str = "cyrup://conversations/";
+/// Resource listing the agent templates
+const TEMPLATES_URI: &str = "cyrup://templates";
+
+/// Search hits returned unless `limit` is given
+const DEFAULT_SEARCH_LIMIT: usize = 20;
+/// Largest result set a client may request
+const MAX_SEARCH_LIMIT: usize = 100;
+/// Messages scanned for replies after `send_message` completes
+const REPLY_WINDOW: usize = 100;
+
+const INSTRUCTIONS: &str = "CYRUP Chat hosts persistent conversations with Claude agents. \
+Use list_templates to see the available agents, create_conversation to start talking to one \
+or more of them, and send_message to ask a question; it returns the agents' replies. \
+Existing conversations are available as resources.";
+
+/// MCP handler backed by the profile database
+#[derive(Clone)]
+pub struct CyrupMcp {
+    database: Arc<Database>,
+    backends: Arc<dyn BackendResolver>,
+    tool_router: ToolRouter<Self>,
+}
+
+/// Arguments for `create_conversation`
+#[derive(Debug, Deserialize, schemars::JsonSchema)]
+pub struct CreateConversationArgs {
+    /// Conversation title
+    pub title: String,
+    /// Template names or IDs of the agents to include (at least one)
+    pub agents: Vec<String>,
+}
+
+/// Arguments for `send_message`
+#[derive(Debug, Deserialize, schemars::JsonSchema)]
+pub struct SendMessageArgs {
+    /// Conversation ID (`conversation:key` or the bare key)
+    pub conversation: String,
+    /// Message text
+    pub content: String,
+    /// Template names or IDs to address; all participants when omitted
+    #[serde(default)]
+    pub agents: Option<Vec<String>>,
+}
+
+/// Arguments for `search_messages`
+#[derive(Debug, Deserialize, schemars::JsonSchema)]
+pub struct SearchMessagesArgs {
+    /// Text to look for in message content
+    pub query: String,
+    /// Limit the search to one conversation
+    #[serde(default)]
+    pub conversation: Option<String>,
+    /// Maximum number of hits, newest first (default 20, at most 100)
+    #[serde(default)]
+    pub limit: Option<usize>,
+}
+
+/// Result of `send_message`
+#[derive(Debug, Serialize)]
+struct Replies {
+    conversation_id: String,
+    replies: Vec<ApiMessage>,
+}
+
+#[tool_router]
+impl CyrupMcp {
+    pub fn new(database: Arc<Database>) -> Self {
+        Self::with_backends(database, Arc::new(TemplateBackends))
+    }
+
+    /// Handler whose agents run on the backends chosen by `backends`
+    pub fn with_backends(database: Arc<Database>, backends: Arc<dyn BackendResolver>) -> Self {
+        Self {
+            database,
+            backends,
+            tool_router: Self::tool_router(),
+        }
+    }
+
+    #[tool(description = "List the agent templates that can take part in conversations")]
+    async fn list_templates(&self) -> Result<CallToolResult, McpError> {
+        let templates = self.database.list_templates().await.map(|templates| {
+            templates
+                .into_iter()
+                .map(ApiTemplate::from)
+                .collect::<Vec<_>>()
+        });
+        Ok(json_result(templates))
+    }
+
+    #[tool(description = "Start a conversation with one or more agents and return it")]
+    async fn create_conversation(
+        &self,
+        Parameters(args): Parameters<CreateConversationArgs>,
+    ) -> Result<CallToolResult, McpError> {
+        Ok(json_result(self.create(args).await))
+    }
+
+    #[tool(
+        description = "Send a message to a conversation and wait for the agents to reply; returns their replies"
+    )]
+    async fn send_message(
+        &self,
+        Parameters(args): Parameters<SendMessageArgs>,
+    ) -> Result<CallToolResult, McpError> {
+        Ok(json_result(self.send(args).await))
+    }
+
+    #[tool(description = "Search message content across conversations, newest first")]
+    async fn search_messages(
+        &self,
+        Parameters(args): Parameters<SearchMessagesArgs>,
+    ) -> Result<CallToolResult, McpError> {
+        Ok(json_result(self.search(args).await))
+    }
+}
+
+impl CyrupMcp {
+    async fn create(&self, args: CreateConversationArgs) -> Result<ApiConversation, String> {
+        if args.agents.is_empty() {
+            return Err("At least one agent is required".to_string());
+        }
+
+        let mut participants = Vec::with_capacity(args.agents.len());
+        for agent in &args.agents {
+            participants.push(self.database.find_template(agent).await?.id);
+        }
+
+        let id = self
+            .database
+            .create_conversation(&Conversation {
+                title: args.title,
+                participants,
+                last_message_at: chrono::Utc::now().into(),
+                ..Default::default()
+            })
+            .await?;
+
+        Ok(self.database.get_conversation(&id).await?.into())
+    }
+
+    async fn send(&self, args: SendMessageArgs) -> Result<Replies, String> {
+        if args.content.trim().is_empty() {
+            return Err("Message content is empty".to_string());
+        }
+
+        let conversation_id = parse_id("conversation", &args.conversation)?;
+        let conversation = self.database.get_conversation(&conversation_id).await?;
+
+        let agents = match &args.agents {
+            Some(names) => {
+                let mut agents = Vec::with_capacity(names.len());
+                for name in names {
+                    let agent = self.database.find_template(name).await?.id;
+                    if !conversation.participants.contains(&agent) {
+                        return Err(format!("{} is not in this conversation", name));
+                    }
+                    agents.push(agent);
+                }
+                Some(agents)
+            }
+            None => None,
+        };
+
+        // Everything after the current newest message belongs to this exchange
+        let cursor = self
+            .database
+            .get_messages_before(&conversation_id, None, 1)
+            .await?
+            .messages
+            .last()
+            .map(MessageCursor::from);
+
+        agent_chat::send_message_with(
+            self.backends.as_ref(),
+            Arc::clone(&self.database),
+            conversation_id.clone(),
+            args.content,
+            agents,
+            false,
+            None,
+        )
+        .await?;
+
+        let replies = self
+            .database
+            .get_messages_after(&conversation_id, cursor.as_ref(), REPLY_WINDOW)
+            .await?
+            .messages
+            .into_iter()
+            .filter(|message| message.author_type != AuthorType::Human)
+            .map(ApiMessage::from)
+            .collect();
+
+        Ok(Replies {
+            conversation_id: conversation_id.to_sql(),
+            replies,
+        })
+    }
+
+    async fn search(&self, args: SearchMessagesArgs) -> Result<Vec<ApiMessage>, String> {
+        let query = args.query.trim();
+        if query.is_empty() {
+            return Err("Search query is empty".to_string());
+        }
+        let limit = args
+            .limit
+            .unwrap_or(DEFAULT_SEARCH_LIMIT)
+            .clamp(1, MAX_SEARCH_LIMIT);
+
+        let conversation = match &args.conversation {
+            Some(conversation) => Some(parse_id("conversation", conversation)?),
+            None => None,
+        };
+
+        let hits = self
+            .database
+            .search_recent_messages(conversation.as_ref(), query, limit)
+            .await?;
+
+        Ok(hits.into_iter().map(ApiMessage::from).collect())
+    }
+
+    async fn conversation_resources(&self) -> Result<Vec<Resource>, String> {
+        let mut templates = RawResource::new(TEMPLATES_URI, "Agent templates");
+        templates.description = Some("Agents available to conversations".to_string());
+        templates.mime_type = Some("application/json".to_string());
+
+        let mut resources = vec![templates.no_annotation()];
+        for summary in self.database.list_conversations().await? {
+            let mut resource = RawResource::new(conversation_uri(&summary.id), summary.title);
+            resource.description = Some(summary.last_message_preview);
+            resource.mime_type = Some("text/markdown".to_string());
+            resources.push(resource.no_annotation());
+        }
+        Ok(resources)
+    }
+
+    async fn read(&self, uri: &str) -> Result<String, McpError> {
+        if uri == TEMPLATES_URI {
+            let templates: Vec<ApiTemplate> = self
+                .database
+                .list_templates()
+                .await
+                .map_err(internal_error)?
+                .into_iter()
+                .map(ApiTemplate::from)
+                .collect();
+            return serde_json::to_string_pretty(&templates)
+                .map_err(|e| internal_error(e.to_string()));
+        }
+
+        let Some(conversation_id) = parse_conversation_uri(uri) else {
+            return Err(McpError::resource_not_found(
+                format!("Unknown resource: {}", uri),
+                None,
+            ));
+        };
+        match self.database.export_transcript(&conversation_id).await {
+            Ok(transcript) => Ok(transcript.to_markdown()),
+            Err(e) if e.contains("not found") => Err(McpError::resource_not_found(e, None)),
+            Err(e) => Err(internal_error(e)),
+        }
+    }
+}
+
+#[tool_handler]
+impl ServerHandler for CyrupMcp {
+    fn get_info(&self) -> ServerInfo {
+        ServerInfo {
+            capabilities: ServerCapabilities::builder()
+                .enable_tools()
+                .enable_resources()
+                .enable_prompts()
+                .build(),
+            server_info: Implementation {
+                name: "cyrup".to_string(),
+                version: env!("CARGO_PKG_VERSION").to_string(),
+                ..Default::default()
+            },
+            instructions: Some(INSTRUCTIONS.to_string()),
+            ..Default::default()
+        }
+    }
+
+    async fn list_resources(
+        &self,
+        _request: Option<PaginatedRequestParam>,
+        _context: RequestContext<RoleServer>,
+    ) -> Result<ListResourcesResult, McpError> {
+        let resources = self
+            .conversation_resources()
+            .await
+            .map_err(internal_error)?;
+        Ok(ListResourcesResult::with_all_items(resources))
+    }
+
+    async fn read_resource(
+        &self,
+        ReadResourceRequestParam { uri }: ReadResourceRequestParam,
+        _context: RequestContext<RoleServer>,
+    ) -> Result<ReadResourceResult, McpError> {
+        let text = self.read(&uri).await?;
+        Ok(ReadResourceResult {
+            contents: vec![ResourceContents::text(text, uri)],
+        })
+    }
+
+    async fn list_prompts(
+        &self,
+        _request: Option<PaginatedRequestParam>,
+        _context: RequestContext<RoleServer>,
+    ) -> Result<ListPromptsResult, McpError> {
+        let templates = self
+            .database
+            .list_templates()
+            .await
+            .map_err(internal_error)?;
+
+        let prompts = templates
+            .into_iter()
+            .map(|template| {
+                Prompt::new(
+                    template.name.clone(),
+                    Some(format!(
+                        "Act as the {} agent ({})",
+                        template.name, template.model
+                    )),
+                    Some(vec![PromptArgument {
+                        name: "message".to_string(),
+                        title: None,
+                        description: Some("First message for the agent".to_string()),
+                        required: Some(false),
+                    }]),
+                )
+            })
+            .collect();
+        Ok(ListPromptsResult::with_all_items(prompts))
+    }
+
+    async fn get_prompt(
+        &self,
+        GetPromptRequestParam { name, arguments }: GetPromptRequestParam,
+        _context: RequestContext<RoleServer>,
+    ) -> Result<GetPromptResult, McpError> {
+        let template = self
+            .database
+            .find_template(&name)
+            .await
+            .map_err(|e| McpError::invalid_params(e, None))?;
+
+        let mut messages = vec![PromptMessage::new_text(
+            PromptMessageRole::User,
+            template.system_prompt,
+        )];
+        if let Some(message) = arguments
+            .as_ref()
+            .and_then(|arguments| arguments.get("message"))
+            .and_then(|message| message.as_str())
+            .filter(|message| !message.trim().is_empty())
+        {
+            messages.push(PromptMessage::new_text(
+                PromptMessageRole::User,
+                message.to_string(),
+            ));
+        }
+
+        Ok(GetPromptResult {
+            description: Some(format!("System prompt of the {} agent", template.name)),
+            messages,
+        })
+    }
+}
+
+/// Serve MCP over stdin/stdout until the client disconnects
+///
+/// # Errors
+/// Returns error if the handshake fails or the transport breaks
+pub async fn serve_stdio(database: Arc<Database>) -> Result<(), String> {
+    let service = CyrupMcp::new(database)
+        .serve(rmcp::transport::stdio())
+        .await
+        .map_err(|e| format!("MCP handshake failed: {}", e))?;
+    service
+        .waiting()
+        .await
+        .map_err(|e| format!("MCP server stopped: {}", e))?;
+    Ok(())
+}
+
+/// Streamable HTTP service, one MCP session per client
+pub fn http_service(
+    database: Arc<Database>,
+) -> StreamableHttpService<CyrupMcp, LocalSessionManager> {
+    StreamableHttpService::new(
+        move || Ok(CyrupMcp::new(Arc::clone(&database))),
+        Arc::new(LocalSessionManager::default()),
+        StreamableHttpServerConfig::default(),
+    )
+}
+
+/// Tool output as pretty JSON, or a tool error the model can read
+fn json_result<T: Serialize>(result: Result<T, String>) -> CallToolResult {
+    match result.and_then(|value| {
+        serde_json::to_string_pretty(&value).map_err(|e| format!("Could not encode result: {}", e))
+    }) {
+        Ok(json) => CallToolResult::success(vec![Content::text(json)]),
+        Err(e) => CallToolResult::error(vec![Content::text(e)]),
+    }
+}
+
+fn internal_error(message: String) -> McpError {
+    log::error!("[McpServer] {}", message);
+    McpError::internal_error(message, None)
+}
+
+/// Parse an ID argument, accepting `table:key` or a bare key
+fn parse_id(table: &str, input: &str) -> Result<RecordId, String> {
+    let input = input.trim();
+    if input.contains(':') {
+        RecordId::parse_simple(input).map_err(|e| format!("Invalid ID '{}': {}", input, e))
+    } else {
+        Ok(RecordId::new(table, input))
+    }
+}
+
+fn conversation_uri(id: &RecordId) -> String {
+    let sql = id.to_sql();
+    let key = sql
+        .split_once(':')
+        .map_or(sql.as_str(), |(_, key)| key.trim_matches(['⟨', '⟩', '`']));
+    format!("{}{}", CONVERSATION_URI, key)
+}
+
+fn parse_conversation_uri(uri: &str) -> Option<RecordId> {
+    uri.strip_prefix(CONVERSATION_URI)
+        .filter(|key| !key.is_empty() && !key.contains('/'))
+        .map(|key| RecordId::new("conversation", key))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_conversation_uri_round_trip() {
+        let id = RecordId::new("conversation", "abc123");
+        let uri = conversation_uri(&id);
+        assert_eq!(uri, "cyrup://conversations/abc123");
+        assert_eq!(parse_conversation_uri(&uri), Some(id));
+
+        assert_eq!(parse_conversation_uri(TEMPLATES_URI), None);
+        assert_eq!(parse_conversation_uri("cyrup://conversations/"), None);
+        assert_eq!(parse_conversation_uri("cyrup://conversations/a/b"), None);
+    }
+
+    #[test]
+    fn test_tools_are_registered() {
+        let mut names: Vec<String> = CyrupMcp::tool_router()
+            .list_all()
+            .into_iter()
+            .map(|tool| tool.name.to_string())
+            .collect();
+        names.sort();
+        assert_eq!(
+            names,
+            [
+                "create_conversation",
+                "list_templates",
+                "search_messages",
+                "send_message"
+            ]
+        );
+    }
+}
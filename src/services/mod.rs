@@ -4,6 +4,14 @@
 //! between database operations and external services (like Claude agents).
 
 pub mod agent_chat;
+pub mod api_server;
+pub mod commands;
+pub mod cron;
+pub mod external_mcp;
+pub mod llm;
+pub mod mcp_server;
 pub mod mention_parser;
 pub mod message_stream;
+pub mod scheduler;
+pub mod shutdown;
 pub mod summarizer;
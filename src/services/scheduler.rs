@@ -0,0 +1,106 @@
+//! In-process scheduler for recurring agent prompts
+//!
+//! Polls the schedule table and sends each due prompt through
+//! `agent_chat::send_message`, exactly as if the user had typed it. Due
+//! times live in the database, so runs missed while the machine slept or
+//! the app was closed are caught up on the next tick — once, not once per
+//! missed occurrence.
+//!
+//! Agent replies raise the usual completion notification (subject to the
+//! conversation's notification policy); failures always notify.
+
+use crate::database::Database;
+use crate::notifications::NotificationService;
+use crate::services::agent_chat;
+use crate::view_model::schedule::Schedule;
+use chrono::{DateTime, Utc};
+use std::sync::Arc;
+use std::time::Duration;
+use surrealdb_types::ToSql;
+
+/// How often due schedules are checked
+const TICK: Duration = Duration::from_secs(30);
+
+/// Spawn the background scheduler task
+pub fn spawn_scheduler(database: Arc<Database>) -> tokio::task::JoinHandle<()> {
+    tokio::spawn(async move {
+        loop {
+            match run_due(&database, Utc::now()).await {
+                Ok(0) => {}
+                Ok(started) => log::info!("[Scheduler] Started {} scheduled prompt(s)", started),
+                Err(e) => log::error!("[Scheduler] Failed to check schedules: {}", e),
+            }
+
+            tokio::time::sleep(TICK).await;
+        }
+    })
+}
+
+/// Start every schedule due at `now`
+///
+/// Each schedule is advanced before its prompt is sent, so a slow or failing
+/// run is never started twice.
+///
+/// # Returns
+/// * `Ok(usize)` - Number of prompts started
+/// * `Err(String)` - Schedules could not be read or advanced
+pub async fn run_due(database: &Arc<Database>, now: DateTime<Utc>) -> Result<usize, String> {
+    let due = database.due_schedules(now).await?;
+
+    for schedule in &due {
+        database.record_schedule_run(schedule, now).await?;
+
+        if now - schedule.next_run_at > chrono::Duration::from_std(TICK * 2).unwrap_or_default() {
+            log::info!(
+                "[Scheduler] Catching up {} (was due {})",
+                schedule.id.to_sql(),
+                schedule.next_run_at
+            );
+        }
+
+        let database = Arc::clone(database);
+        let schedule = schedule.clone();
+        tokio::spawn(async move {
+            let error = run_schedule(&database, &schedule).await.err();
+
+            if let Some(e) = &error {
+                log::error!("[Scheduler] {} failed: {}", schedule.id.to_sql(), e);
+                notify_failure(&schedule, e).await;
+            }
+            if let Err(e) = database.set_schedule_error(&schedule.id, error).await {
+                log::warn!("[Scheduler] {}", e);
+            }
+        });
+    }
+
+    Ok(due.len())
+}
+
+/// Send one schedule's prompt to its conversation
+async fn run_schedule(database: &Arc<Database>, schedule: &Schedule) -> Result<(), String> {
+    log::debug!(
+        "[Scheduler] Running {} in {}",
+        schedule.id.to_sql(),
+        schedule.conversation_id.to_sql()
+    );
+
+    agent_chat::send_message(
+        Arc::clone(database),
+        schedule.conversation_id.clone(),
+        schedule.prompt.clone(),
+        schedule.agent_id.clone().map(|agent| vec![agent]),
+        false,
+        None,
+    )
+    .await
+}
+
+async fn notify_failure(schedule: &Schedule, error: &str) {
+    let prompt: String = schedule.prompt.chars().take(80).collect();
+    let body = format!("\"{}\": {}", prompt, error);
+    let body: String = body.chars().take(500).collect();
+
+    if let Err(e) = NotificationService::send_notification("Scheduled prompt failed", body).await {
+        log::warn!("[Scheduler] Failure notification not delivered: {}", e);
+    }
+}
@@ -0,0 +1,59 @@
+//! Orderly shutdown before the process is replaced
+//!
+//! Every profile shares one SurrealKV directory, which a single process can
+//! hold at a time. Before a profile switch relaunches the app, [`shutdown`]
+//! stops the background tasks and the API server, asks the UI to drop its
+//! environment and waits for the database handles to be released, so no
+//! write is cut off and the new process finds the store unlocked.
+
+use crate::database::Database;
+use std::sync::Mutex;
+use std::time::Duration;
+use tokio::sync::Notify;
+use tokio::task::JoinHandle;
+
+/// How long [`shutdown`] waits for the UI to drop its database handles
+const RELEASE_WAIT: Duration = Duration::from_secs(5);
+
+/// Background tasks that hold the database
+static TASKS: Mutex<Vec<JoinHandle<()>>> = Mutex::new(Vec::new());
+
+static RELEASE: Notify = Notify::const_new();
+
+/// Stop `task` when the app shuts down
+pub fn register_task(task: JoinHandle<()>) {
+    TASKS.lock().unwrap_or_else(|e| e.into_inner()).push(task);
+}
+
+/// Resolves when the UI should drop its environment
+pub async fn release_requested() {
+    RELEASE.notified().await;
+}
+
+/// Stop everything that uses the database and wait for it to be closed
+///
+/// Gives up waiting after [`RELEASE_WAIT`]; the next process then waits for
+/// the store lock itself (see [`Database::connect`]).
+pub async fn shutdown() {
+    let tasks = std::mem::take(&mut *TASKS.lock().unwrap_or_else(|e| e.into_inner()));
+    for task in tasks {
+        task.abort();
+        let _ = task.await;
+    }
+
+    crate::services::api_server::stop_running().await;
+
+    RELEASE.notify_one();
+    let deadline = tokio::time::Instant::now() + RELEASE_WAIT;
+    while Database::open_files() > 0 {
+        if tokio::time::Instant::now() >= deadline {
+            log::warn!(
+                "[Shutdown] {} database handle(s) still open; relaunching anyway",
+                Database::open_files()
+            );
+            return;
+        }
+        tokio::time::sleep(Duration::from_millis(50)).await;
+    }
+    log::info!("[Shutdown] Database closed");
+}
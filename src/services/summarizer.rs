@@ -135,6 +135,36 @@ impl SummarizerService {
         result.map(Some)
     }
 
+    /// Summarize immediately, ignoring the cooldown
+    ///
+    /// Still refuses to start while a run for the same conversation is in
+    /// progress, and resets the cooldown afterwards.
+    pub async fn summarize_now(
+        &self,
+        conversation_id: &RecordId,
+    ) -> Result<SummarizerOutput, String> {
+        let conversation_id_str = conversation_id.to_sql();
+
+        let mut limiters = self.rate_limiters.lock().await;
+        let limiter = limiters
+            .entry(conversation_id_str.clone())
+            .or_insert_with(RateLimiter::new);
+        if limiter.in_progress {
+            return Err("Summarization already in progress".to_string());
+        }
+        limiter.mark_started();
+        drop(limiters);
+
+        let result = self.run_summarizer(conversation_id).await;
+
+        let mut limiters = self.rate_limiters.lock().await;
+        if let Some(limiter) = limiters.get_mut(&conversation_id_str) {
+            limiter.mark_finished();
+        }
+
+        result
+    }
+
     /// Run summarizer agent and update database
     ///
     /// # Steps
@@ -188,11 +218,14 @@ SUMMARY: <summary here>"#,
             conversation.summary, messages_text
         );
 
-        // Spawn ephemeral agent
-        let options = kodegen_tools_claude_agent::ClaudeAgentOptions::builder()
-            .system_prompt("You are a conversation summarizer. Extract key points and generate concise titles.")
-            .max_turns(1)
-            .build();
+        // Spawn ephemeral agent, billed to the default Anthropic key
+        let options = kodegen_tools_claude_agent::ClaudeAgentOptions {
+            env: crate::services::llm::api_keys::claude_env(None).await,
+            ..kodegen_tools_claude_agent::ClaudeAgentOptions::builder()
+                .system_prompt("You are a conversation summarizer. Extract key points and generate concise titles.")
+                .max_turns(1)
+                .build()
+        };
 
         let stream = kodegen_tools_claude_agent::query(&prompt, Some(options))
             .await
@@ -15,6 +15,9 @@ use surrealdb_types::{RecordId, SurrealValue};
 /// - max_turns → max_turns (int, default 50)
 /// - icon → icon (option<string>)
 /// - color → color (option<string>)
+/// - backend → backend (string: "claude", "openai", "local", default "claude")
+/// - backend_url → backend_url (option<string>) ← OpenAI-compatible base URL
+/// - backend_model → backend_model (option<string>) ← model name for non-Claude backends
 /// - created_at → created_at (datetime)
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, SurrealValue)]
 pub struct AgentTemplate {
@@ -25,9 +28,27 @@ pub struct AgentTemplate {
     pub max_turns: u32,
     pub icon: Option<String>,
     pub color: Option<String>,
+    pub backend: AgentBackend,
+    pub backend_url: Option<String>,
+    pub backend_model: Option<String>,
     pub created_at: DateTime<Utc>,
 }
 
+impl AgentTemplate {
+    /// Model name sent to the template's backend
+    ///
+    /// Claude uses [`AgentModel`]; other backends use the free-form
+    /// `backend_model` (empty if unset).
+    pub fn model_name(&self) -> String {
+        match self.backend {
+            AgentBackend::Claude => self.model.to_string(),
+            AgentBackend::Openai | AgentBackend::Local => {
+                self.backend_model.clone().unwrap_or_default()
+            }
+        }
+    }
+}
+
 /// Claude model variants
 ///
 /// Serializes to lowercase strings for database storage:
@@ -54,6 +75,32 @@ impl std::fmt::Display for AgentModel {
     }
 }
 
+/// Inference backend that runs an agent
+///
+/// Serializes to lowercase strings for database storage:
+/// - Claude → "claude" (Claude Code subprocess, built-in tools)
+/// - Openai → "openai" (OpenAI-compatible HTTP endpoint)
+/// - Local → "local" (Ollama or llama.cpp server on this machine)
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, SurrealValue, Default)]
+#[serde(rename_all = "lowercase")]
+#[surreal(untagged, lowercase)]
+pub enum AgentBackend {
+    #[default]
+    Claude,
+    Openai,
+    Local,
+}
+
+impl std::fmt::Display for AgentBackend {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AgentBackend::Claude => write!(f, "claude"),
+            AgentBackend::Openai => write!(f, "openai"),
+            AgentBackend::Local => write!(f, "local"),
+        }
+    }
+}
+
 impl Default for AgentTemplate {
     fn default() -> Self {
         Self {
@@ -64,6 +111,9 @@ impl Default for AgentTemplate {
             max_turns: 50,
             icon: None,
             color: None,
+            backend: AgentBackend::default(),
+            backend_url: None,
+            backend_model: None,
             created_at: chrono::Utc::now(),
         }
     }
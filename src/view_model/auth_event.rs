@@ -0,0 +1,38 @@
+//! Account audit trail types
+//!
+//! Aligns with src/database/schema.rs auth_event table
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use surrealdb_types::{RecordId, SurrealValue};
+
+/// What happened to the account
+///
+/// Serializes to lowercase strings for database storage.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, SurrealValue)]
+#[serde(rename_all = "lowercase")]
+#[surreal(untagged, lowercase)]
+pub enum AuthEventKind {
+    SignIn,
+    SignOut,
+}
+
+/// One sign-in or sign-out of an account
+///
+/// Database mapping:
+/// - user_id → user_id (string) ← provider user ID, as on conversations
+/// - provider → provider (string)
+/// - kind → kind (string)
+/// - tokens_revoked → tokens_revoked (bool) ← sign-out reached the provider
+/// - detail → detail (option<string>) ← why revocation failed, if it did
+/// - created_at → created_at (datetime)
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, SurrealValue)]
+pub struct AuthEvent {
+    pub id: RecordId,
+    pub user_id: String,
+    pub provider: String,
+    pub kind: AuthEventKind,
+    pub tokens_revoked: bool,
+    pub detail: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
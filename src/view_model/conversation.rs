@@ -18,6 +18,7 @@ use surrealdb_types::{Datetime, RecordId, SurrealValue};
 /// - last_summarized_message_id → last_summarized_message_id (option<record<message>>)
 /// - last_message_at → last_message_at (datetime)
 /// - created_at → created_at (datetime)
+/// - user_id → user_id (option<string>) ← owning account, NONE = shared
 ///
 /// Design:
 /// - Supports 1:N agents via participants Vec
@@ -36,6 +37,10 @@ pub struct Conversation {
     pub last_summarized_message_id: Option<RecordId>,
     pub last_message_at: Datetime,
     pub created_at: Datetime,
+    /// Account that owns the conversation; `None` for conversations created
+    /// before accounts were tracked or by headless tools, shown to everyone
+    #[serde(default)]
+    pub user_id: Option<String>,
 }
 
 /// Lightweight conversation summary for list views
@@ -67,6 +72,7 @@ impl Default for Conversation {
             last_summarized_message_id: None,
             last_message_at: now.into(),
             created_at: now.into(),
+            user_id: None,
         }
     }
 }
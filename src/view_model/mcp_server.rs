@@ -0,0 +1,202 @@
+//! External MCP server types
+//!
+//! Aligns with src/database/schema.rs mcp_server and template_mcp_server tables
+//!
+//! Servers are registered once and enabled per agent template. Secret
+//! values (API keys in environment variables or HTTP headers) are never
+//! stored in the database: `secret_keys` only names them, and the values
+//! live in the credential vault (see src/services/external_mcp.rs).
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use surrealdb_types::{RecordId, SurrealValue};
+
+/// How the agent reaches the server
+///
+/// Serializes to lowercase strings for database storage.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, SurrealValue, Default)]
+#[serde(rename_all = "lowercase")]
+#[surreal(untagged, lowercase)]
+pub enum McpTransport {
+    /// Spawned as a child process speaking MCP over stdin/stdout
+    #[default]
+    Stdio,
+    /// Streamable HTTP endpoint
+    Http,
+}
+
+impl std::fmt::Display for McpTransport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            McpTransport::Stdio => write!(f, "stdio"),
+            McpTransport::Http => write!(f, "http"),
+        }
+    }
+}
+
+/// User-registered MCP server
+///
+/// Database mapping:
+/// - name → name (string, unique; agents see tools as `mcp__<name>__<tool>`)
+/// - transport → transport (string: "stdio", "http")
+/// - command → command (string) ← stdio only
+/// - args → args (array<string>) ← stdio only
+/// - env → env (object) ← non-secret environment variables, stdio only
+/// - url → url (string) ← http only
+/// - secret_keys → secret_keys (array<string>) ← env vars (stdio) or headers (http) held in the vault
+/// - created_at → created_at (datetime)
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, SurrealValue)]
+pub struct McpServer {
+    pub id: RecordId,
+    pub name: String,
+    pub transport: McpTransport,
+    pub command: String,
+    pub args: Vec<String>,
+    pub env: HashMap<String, String>,
+    pub url: String,
+    pub secret_keys: Vec<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl McpServer {
+    /// Check the fields required by the chosen transport
+    ///
+    /// # Errors
+    /// Describes the first invalid field
+    pub fn validate(&self) -> Result<(), String> {
+        if !is_server_name(&self.name) {
+            return Err("Server names may only contain letters, digits, '-' and '_'".to_string());
+        }
+        match self.transport {
+            McpTransport::Stdio if self.command.trim().is_empty() => {
+                Err("A stdio server needs a command".to_string())
+            }
+            McpTransport::Http
+                if !(self.url.starts_with("http://") || self.url.starts_with("https://")) =>
+            {
+                Err("An HTTP server needs an http:// or https:// URL".to_string())
+            }
+            _ => match self.secret_keys.iter().find(|key| key.trim().is_empty()) {
+                Some(_) => Err("Secret names cannot be empty".to_string()),
+                None => Ok(()),
+            },
+        }
+    }
+
+    /// `allowed_tools` entry that permits every tool of this server
+    pub fn tool_prefix(&self) -> String {
+        format!("mcp__{}", self.name)
+    }
+}
+
+impl Default for McpServer {
+    fn default() -> Self {
+        Self {
+            id: RecordId::new("mcp_server", "default"),
+            name: String::new(),
+            transport: McpTransport::default(),
+            command: String::new(),
+            args: Vec::new(),
+            env: HashMap::new(),
+            url: String::new(),
+            secret_keys: Vec::new(),
+            created_at: Utc::now(),
+        }
+    }
+}
+
+/// Valid server names: letters, digits, `-` and `_`
+pub fn is_server_name(name: &str) -> bool {
+    !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+}
+
+/// Parse `KEY=value` lines, as typed into the editor
+///
+/// Blank lines and `#` comments are skipped; values may contain `=`.
+///
+/// # Errors
+/// Names the first line without a key
+pub fn parse_key_values(text: &str) -> Result<BTreeMap<String, String>, String> {
+    let mut values = BTreeMap::new();
+    for line in text.lines().map(str::trim) {
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        match line.split_once('=') {
+            Some((key, value)) if !key.trim().is_empty() => {
+                values.insert(key.trim().to_string(), value.trim().to_string());
+            }
+            _ => return Err(format!("Expected KEY=value, got '{}'", line)),
+        }
+    }
+    Ok(values)
+}
+
+/// Inverse of [`parse_key_values`], sorted by key
+pub fn format_key_values<'a>(values: impl IntoIterator<Item = (&'a String, &'a String)>) -> String {
+    let sorted: BTreeMap<_, _> = values.into_iter().collect();
+    sorted
+        .into_iter()
+        .map(|(key, value)| format!("{}={}", key, value))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate() {
+        let stdio = McpServer {
+            name: "github".to_string(),
+            command: "npx".to_string(),
+            ..Default::default()
+        };
+        assert!(stdio.validate().is_ok());
+        assert_eq!(stdio.tool_prefix(), "mcp__github");
+
+        let bad_name = McpServer {
+            name: "git hub".to_string(),
+            ..stdio.clone()
+        };
+        assert!(bad_name.validate().is_err());
+
+        let no_command = McpServer {
+            command: " ".to_string(),
+            ..stdio.clone()
+        };
+        assert!(no_command.validate().is_err());
+
+        let http = McpServer {
+            transport: McpTransport::Http,
+            url: "ftp://example.com".to_string(),
+            ..stdio
+        };
+        assert!(http.validate().is_err());
+        assert!(
+            McpServer {
+                url: "https://example.com/mcp".to_string(),
+                ..http
+            }
+            .validate()
+            .is_ok()
+        );
+    }
+
+    #[test]
+    fn test_key_values_round_trip() {
+        let parsed = parse_key_values("# comment\nTOKEN = a=b\n\nREGION=eu\n").unwrap();
+        assert_eq!(parsed["TOKEN"], "a=b");
+        assert_eq!(parsed["REGION"], "eu");
+        assert_eq!(format_key_values(&parsed), "REGION=eu\nTOKEN=a=b");
+
+        assert!(parse_key_values("no equals sign").is_err());
+        assert!(parse_key_values("=value").is_err());
+        assert!(parse_key_values("").unwrap().is_empty());
+    }
+}
@@ -13,9 +13,14 @@ pub mod types;
 
 // Agent chat types (AGENT_2)
 pub mod agent;
+pub mod auth_event;
 pub mod conversation;
+pub mod mcp_server;
 pub mod message;
+pub mod schedule;
+pub mod snippet;
 pub mod token_budget;
+pub mod transcript;
 
 // Re-export Mastodon types for API compatibility
 pub use account::*;
@@ -0,0 +1,138 @@
+//! Scheduled prompt types
+//!
+//! Aligns with src/database/schema.rs schedule table
+
+use crate::services::cron::CronExpr;
+use chrono::{DateTime, Duration, Local, Utc};
+use serde::{Deserialize, Serialize};
+use surrealdb_types::{RecordId, SurrealValue};
+
+/// Shortest interval accepted for repeating prompts
+pub const MIN_INTERVAL_SECS: i64 = 60;
+
+/// A prompt sent to a conversation on a cron expression or fixed interval
+///
+/// Database mapping:
+/// - conversation_id → conversation_id (record<conversation>, cascade delete)
+/// - agent_id → agent_id (option<record<agent_template>>) ← None = all participants
+/// - prompt → prompt (string)
+/// - cron → cron (option<string>) ← exactly one of cron / interval_secs
+/// - interval_secs → interval_secs (option<int>)
+/// - paused → paused (bool)
+/// - next_run_at → next_run_at (datetime)
+/// - last_run_at → last_run_at (option<datetime>)
+/// - last_error → last_error (option<string>)
+/// - created_at → created_at (datetime)
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, SurrealValue)]
+pub struct Schedule {
+    pub id: RecordId,
+    pub conversation_id: RecordId,
+    pub agent_id: Option<RecordId>,
+    pub prompt: String,
+    pub cron: Option<String>,
+    pub interval_secs: Option<i64>,
+    pub paused: bool,
+    pub next_run_at: DateTime<Utc>,
+    pub last_run_at: Option<DateTime<Utc>>,
+    pub last_error: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// When a schedule fires
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ScheduleTrigger {
+    /// Five-field cron expression in local time
+    Cron(String),
+    /// Fixed interval in seconds, measured from the previous run
+    Interval(i64),
+}
+
+impl ScheduleTrigger {
+    /// Check the trigger can produce a future run
+    pub fn validate(&self) -> Result<(), String> {
+        match self {
+            ScheduleTrigger::Cron(expr) => {
+                let cron: CronExpr = expr.parse()?;
+                cron.next_after(Local::now())
+                    .map(|_| ())
+                    .ok_or_else(|| format!("Cron expression '{}' never fires", expr))
+            }
+            ScheduleTrigger::Interval(secs) if *secs < MIN_INTERVAL_SECS => Err(format!(
+                "Interval must be at least {} seconds",
+                MIN_INTERVAL_SECS
+            )),
+            ScheduleTrigger::Interval(_) => Ok(()),
+        }
+    }
+
+    /// First run strictly after `after`
+    pub fn next_after(&self, after: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        match self {
+            ScheduleTrigger::Cron(expr) => expr
+                .parse::<CronExpr>()
+                .ok()?
+                .next_after(after.with_timezone(&Local))
+                .map(|next| next.with_timezone(&Utc)),
+            ScheduleTrigger::Interval(secs) => Some(after + Duration::seconds(*secs)),
+        }
+    }
+}
+
+impl Schedule {
+    /// The trigger stored on this schedule
+    pub fn trigger(&self) -> Option<ScheduleTrigger> {
+        match (&self.cron, self.interval_secs) {
+            (Some(expr), _) => Some(ScheduleTrigger::Cron(expr.clone())),
+            (None, Some(secs)) => Some(ScheduleTrigger::Interval(secs)),
+            (None, None) => None,
+        }
+    }
+
+    /// Set the trigger fields from a [`ScheduleTrigger`]
+    pub fn set_trigger(&mut self, trigger: ScheduleTrigger) {
+        match trigger {
+            ScheduleTrigger::Cron(expr) => {
+                self.cron = Some(expr);
+                self.interval_secs = None;
+            }
+            ScheduleTrigger::Interval(secs) => {
+                self.cron = None;
+                self.interval_secs = Some(secs);
+            }
+        }
+    }
+
+    /// Human-readable trigger for lists
+    pub fn describe_trigger(&self) -> String {
+        match self.trigger() {
+            Some(ScheduleTrigger::Cron(expr)) => format!("cron: {}", expr),
+            Some(ScheduleTrigger::Interval(secs)) if secs % 3600 == 0 => {
+                format!("every {}h", secs / 3600)
+            }
+            Some(ScheduleTrigger::Interval(secs)) if secs % 60 == 0 => {
+                format!("every {}m", secs / 60)
+            }
+            Some(ScheduleTrigger::Interval(secs)) => format!("every {}s", secs),
+            None => "no trigger".to_string(),
+        }
+    }
+}
+
+impl Default for Schedule {
+    fn default() -> Self {
+        let now = Utc::now();
+        Self {
+            id: RecordId::new("schedule", "default"),
+            conversation_id: RecordId::new("conversation", "default"),
+            agent_id: None,
+            prompt: String::new(),
+            cron: None,
+            interval_secs: Some(3600),
+            paused: false,
+            next_run_at: now,
+            last_run_at: None,
+            last_error: None,
+            created_at: now,
+        }
+    }
+}
@@ -0,0 +1,273 @@
+//! Prompt snippet types
+//!
+//! Aligns with src/database/schema.rs prompt_snippet table
+//!
+//! A snippet body may contain `{{param}}` or `{{param=default}}`
+//! placeholders. Invoking `/name` fills them from `key=value` pairs or, in
+//! order of first appearance, from positional arguments.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use surrealdb_types::{RecordId, SurrealValue};
+
+/// User-defined prompt template invoked as a slash command
+///
+/// Database mapping:
+/// - name → name (string, unique, used as `/name`)
+/// - description → description (string)
+/// - body → body (string with `{{param}}` placeholders)
+/// - created_at → created_at (datetime)
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, SurrealValue)]
+pub struct PromptSnippet {
+    pub id: RecordId,
+    pub name: String,
+    pub description: String,
+    pub body: String,
+    pub created_at: DateTime<Utc>,
+}
+
+/// One `{{...}}` placeholder
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SnippetParam {
+    pub name: String,
+    pub default: Option<String>,
+}
+
+/// Arguments given after `/name`
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SnippetArgs {
+    pub named: HashMap<String, String>,
+    pub positional: Vec<String>,
+}
+
+impl SnippetArgs {
+    /// Split `a b="c d" e=f` into positional and `key=value` arguments
+    ///
+    /// Double quotes group words; there is no escape syntax.
+    pub fn parse(input: &str) -> Self {
+        let mut args = Self::default();
+        let mut token = String::new();
+        let mut in_quotes = false;
+        let mut has_token = false;
+
+        let mut flush = |token: &mut String, has_token: &mut bool| {
+            if !*has_token {
+                return;
+            }
+            match token.split_once('=') {
+                Some((key, value)) if is_param_name(key) => {
+                    args.named.insert(key.to_string(), value.to_string());
+                }
+                _ => args.positional.push(token.clone()),
+            }
+            token.clear();
+            *has_token = false;
+        };
+
+        for c in input.chars() {
+            match c {
+                '"' => {
+                    in_quotes = !in_quotes;
+                    has_token = true;
+                }
+                c if c.is_whitespace() && !in_quotes => flush(&mut token, &mut has_token),
+                c => {
+                    token.push(c);
+                    has_token = true;
+                }
+            }
+        }
+        flush(&mut token, &mut has_token);
+
+        args
+    }
+}
+
+impl PromptSnippet {
+    /// Placeholders in order of first appearance (duplicates removed)
+    pub fn parameters(&self) -> Vec<SnippetParam> {
+        let mut params: Vec<SnippetParam> = Vec::new();
+        for (_, inner) in placeholders(&self.body) {
+            let param = parse_placeholder(inner);
+            if !params.iter().any(|p| p.name == param.name) {
+                params.push(param);
+            }
+        }
+        params
+    }
+
+    /// Fill placeholders from `args`
+    ///
+    /// # Returns
+    /// * `Ok(String)` - Expanded prompt
+    /// * `Err(String)` - Lists parameters that have neither a value nor a default
+    pub fn expand(&self, args: &SnippetArgs) -> Result<String, String> {
+        let params = self.parameters();
+        let mut positional = args.positional.iter();
+        let mut values: HashMap<String, String> = HashMap::new();
+        let mut missing = Vec::new();
+
+        for param in &params {
+            let value = args
+                .named
+                .get(&param.name)
+                .cloned()
+                .or_else(|| positional.next().cloned())
+                .or_else(|| param.default.clone());
+            match value {
+                Some(value) => {
+                    values.insert(param.name.clone(), value);
+                }
+                None => missing.push(param.name.clone()),
+            }
+        }
+
+        if !missing.is_empty() {
+            return Err(format!("/{} needs: {}", self.name, missing.join(", ")));
+        }
+
+        let mut expanded = String::with_capacity(self.body.len());
+        let mut rest = self.body.as_str();
+        for (range, inner) in placeholders(&self.body) {
+            let offset = self.body.len() - rest.len();
+            expanded.push_str(&rest[..range.start - offset]);
+            let name = parse_placeholder(inner).name;
+            expanded.push_str(values.get(&name).map(String::as_str).unwrap_or_default());
+            rest = &self.body[range.end..];
+        }
+        expanded.push_str(rest);
+
+        // Leftover positional arguments are appended, so `/ask more context` works
+        let extra: Vec<&str> = positional.map(String::as_str).collect();
+        if !extra.is_empty() {
+            if !expanded.is_empty() {
+                expanded.push(' ');
+            }
+            expanded.push_str(&extra.join(" "));
+        }
+
+        Ok(expanded)
+    }
+}
+
+/// Valid snippet and parameter names: letters, digits, `-` and `_`
+pub fn is_param_name(name: &str) -> bool {
+    !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+}
+
+/// Byte ranges of `{{...}}` placeholders and their trimmed contents
+fn placeholders(body: &str) -> Vec<(std::ops::Range<usize>, &str)> {
+    let mut found = Vec::new();
+    let mut search_from = 0;
+
+    while let Some(start) = body[search_from..].find("{{").map(|i| i + search_from) {
+        let Some(end) = body[start + 2..].find("}}").map(|i| i + start + 2) else {
+            break;
+        };
+        let inner = body[start + 2..end].trim();
+        let name = inner.split('=').next().unwrap_or_default().trim();
+        if is_param_name(name) {
+            found.push((start..end + 2, inner));
+        }
+        search_from = end + 2;
+    }
+
+    found
+}
+
+fn parse_placeholder(inner: &str) -> SnippetParam {
+    match inner.split_once('=') {
+        Some((name, default)) => SnippetParam {
+            name: name.trim().to_string(),
+            default: Some(default.trim().to_string()),
+        },
+        None => SnippetParam {
+            name: inner.trim().to_string(),
+            default: None,
+        },
+    }
+}
+
+impl Default for PromptSnippet {
+    fn default() -> Self {
+        Self {
+            id: RecordId::new("prompt_snippet", "default"),
+            name: String::new(),
+            description: String::new(),
+            body: String::new(),
+            created_at: Utc::now(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn snippet(body: &str) -> PromptSnippet {
+        PromptSnippet {
+            name: "review".to_string(),
+            body: body.to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_parse_args() {
+        let args = SnippetArgs::parse(r#"main lang="rust 2024" depth=3 "two words""#);
+        assert_eq!(args.positional, ["main", "two words"]);
+        assert_eq!(args.named["lang"], "rust 2024");
+        assert_eq!(args.named["depth"], "3");
+        assert_eq!(SnippetArgs::parse("   "), SnippetArgs::default());
+    }
+
+    #[test]
+    fn test_parameters_in_order_with_defaults() {
+        let s = snippet("Review {{branch}} in {{ lang = rust }} then {{branch}} again");
+        assert_eq!(
+            s.parameters(),
+            [
+                SnippetParam {
+                    name: "branch".to_string(),
+                    default: None
+                },
+                SnippetParam {
+                    name: "lang".to_string(),
+                    default: Some("rust".to_string())
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_expand() {
+        let s = snippet("Review {{branch}} in {{lang=rust}}.");
+        assert_eq!(
+            s.expand(&SnippetArgs::parse("main")).unwrap(),
+            "Review main in rust."
+        );
+        assert_eq!(
+            s.expand(&SnippetArgs::parse("lang=go branch=dev extra words"))
+                .unwrap(),
+            "Review dev in go. extra words"
+        );
+        assert_eq!(
+            s.expand(&SnippetArgs::default()).unwrap_err(),
+            "/review needs: branch"
+        );
+    }
+
+    #[test]
+    fn test_non_placeholders_are_left_alone() {
+        let s = snippet("Use {{ }} and {{not valid!}} and {{open");
+        assert!(s.parameters().is_empty());
+        assert_eq!(
+            s.expand(&SnippetArgs::default()).unwrap(),
+            "Use {{ }} and {{not valid!}} and {{open"
+        );
+    }
+}
@@ -5,12 +5,13 @@
 
 use super::agent::AgentModel;
 use super::message::Message;
+use serde::{Deserialize, Serialize};
 
 /// Token budget configuration for context window management
 ///
 /// Provides model-specific token budgets and message estimation heuristics.
 /// Used by database layer to calculate dynamic message retrieval limits.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TokenBudgetConfig {
     /// Maximum tokens for input context window
     pub max_tokens: usize,
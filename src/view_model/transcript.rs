@@ -0,0 +1,66 @@
+//! Portable conversation transcripts
+//!
+//! A transcript carries one conversation with its messages and the agent
+//! templates it uses, keyed by name rather than record ID, so it can be
+//! imported into another profile or machine. Used by `/export` and by the
+//! `cyrup-cli` export/import commands.
+
+use crate::view_model::agent::AgentModel;
+use crate::view_model::message::{AuthorType, MessageType};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::fmt::Write as _;
+
+/// Current transcript format version
+pub const TRANSCRIPT_VERSION: u32 = 1;
+
+/// One conversation in portable form
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Transcript {
+    pub version: u32,
+    pub title: String,
+    pub summary: String,
+    pub agents: Vec<TranscriptAgent>,
+    pub messages: Vec<TranscriptMessage>,
+}
+
+/// Agent template referenced by the conversation
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct TranscriptAgent {
+    pub name: String,
+    pub system_prompt: String,
+    pub model: AgentModel,
+    pub max_turns: u32,
+}
+
+/// Message without database identifiers
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct TranscriptMessage {
+    pub author: String,
+    pub author_type: AuthorType,
+    pub message_type: MessageType,
+    pub content: String,
+    pub timestamp: DateTime<Utc>,
+    #[serde(default)]
+    pub pinned: bool,
+}
+
+impl Transcript {
+    /// Render as Markdown for reading or sharing
+    pub fn to_markdown(&self) -> String {
+        let mut markdown = format!("# {}\n\n", self.title);
+        if !self.summary.is_empty() {
+            let _ = write!(markdown, "> {}\n\n", self.summary.replace('\n', "\n> "));
+        }
+        for message in &self.messages {
+            let _ = write!(
+                markdown,
+                "## {} ({})\n\n{}\n\n",
+                message.author,
+                message.timestamp.format("%Y-%m-%d %H:%M"),
+                message.content.trim_end()
+            );
+        }
+        markdown
+    }
+}
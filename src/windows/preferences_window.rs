@@ -3,8 +3,14 @@ use crate::components::loggedin::Action;
 use crate::environment::types::{AppEvent, TimelineDirection};
 use crate::environment::{Environment, OpenWindowState};
 use crate::loc;
+use crate::database::BackupConfig;
+use crate::database::backup::list_backups;
+use crate::notifications::QuietHours;
+use crate::services::api_server::{self, ApiServerConfig};
 use crate::widgets::*;
+use chrono::NaiveTime;
 use dioxus::prelude::*;
+use std::path::PathBuf;
 use std::rc::Rc;
 use std::str::FromStr;
 
@@ -70,6 +76,11 @@ impl OpenWindowState for PreferencesWindowState {
                             }
                         }
                     }
+                    QuietHoursSetting {}
+                    ContextBudgetSetting {}
+                    BackupSetting {}
+                    LocalApiSetting {}
+                    ProfileSetting {}
                 }
             }
         }
@@ -155,6 +166,464 @@ fn PostInlineSetting(open_inline: bool, onchange: EventHandler<bool>) -> Element
     }
 }
 
+#[component]
+fn QuietHoursSetting() -> Element {
+    let environment = use_context::<Environment>();
+    let database = environment.database.clone();
+    let mut quiet_hours = use_signal(|| Option::<QuietHours>::None);
+    let mut error = use_signal(|| Option::<String>::None);
+
+    let load_database = database.clone();
+    use_future(move || {
+        let database = load_database.clone();
+        async move {
+            match database.get_quiet_hours().await {
+                Ok(stored) => quiet_hours.set(stored),
+                Err(e) => error.set(Some(e)),
+            }
+        }
+    });
+
+    let save = use_callback(move |value: Option<QuietHours>| {
+        quiet_hours.set(value);
+        let database = database.clone();
+        spawn(async move {
+            if let Err(e) = database.set_quiet_hours(value).await {
+                error.set(Some(e));
+            }
+        });
+    });
+
+    let current = quiet_hours();
+    let window = current.unwrap_or(QuietHours {
+        start: NaiveTime::from_hms_opt(22, 0, 0).unwrap_or_default(),
+        end: NaiveTime::from_hms_opt(7, 0, 0).unwrap_or_default(),
+    });
+    let start = window.start.format("%H:%M").to_string();
+    let end = window.end.format("%H:%M").to_string();
+
+    rsx! {
+        VStack {
+            class: "gap-1",
+            HStack {
+                class: "gap-4 items-center",
+                Checkbox {
+                    checked: current.is_some(),
+                    onchange: move |enabled: bool| save.call(enabled.then_some(window))
+                }
+                h4 { "Do not disturb" }
+            }
+            if current.is_some() {
+                HStack {
+                    class: "gap-2 items-center",
+                    input {
+                        r#type: "time",
+                        value: "{start}",
+                        onchange: move |e| {
+                            if let Ok(start) = NaiveTime::parse_from_str(&e.value(), "%H:%M") {
+                                save.call(Some(QuietHours { start, ..window }));
+                            }
+                        }
+                    }
+                    Label { "to" }
+                    input {
+                        r#type: "time",
+                        value: "{end}",
+                        onchange: move |e| {
+                            if let Ok(end) = NaiveTime::parse_from_str(&e.value(), "%H:%M") {
+                                save.call(Some(QuietHours { end, ..window }));
+                            }
+                        }
+                    }
+                }
+            }
+            p {
+                class: "text-sm text-gray-500",
+                "Agent reply notifications are held back during these hours"
+            }
+            if let Some(message) = error() {
+                ErrorBox {
+                    content: message,
+                    onclick: move |_| error.set(None)
+                }
+            }
+        }
+    }
+}
+
+#[component]
+fn ContextBudgetSetting() -> Element {
+    let environment = use_context::<Environment>();
+    let database = environment.database.clone();
+    let mut max_tokens = use_signal(|| database.token_budget_config().max_tokens);
+    let mut error = use_signal(|| Option::<String>::None);
+
+    let save = use_callback(move |value: usize| {
+        max_tokens.set(value);
+        let database = database.clone();
+        spawn(async move {
+            let mut config = database.token_budget_config();
+            config.max_tokens = value;
+            let saved = match crate::config::profile::active_profile() {
+                Ok(profile) => profile.save_token_budget(&config).await,
+                Err(e) => Err(e),
+            };
+            match saved {
+                Ok(()) => database.set_token_budget_config(config),
+                Err(e) => error.set(Some(e)),
+            }
+        });
+    });
+
+    rsx! {
+        VStack {
+            class: "gap-1",
+            h4 { "Context budget" }
+            HStack {
+                class: "gap-2 items-center",
+                input {
+                    r#type: "number",
+                    min: "1000",
+                    step: "1000",
+                    value: "{max_tokens}",
+                    onchange: move |e| {
+                        if let Ok(value) = e.value().parse::<usize>()
+                            && value > 0
+                        {
+                            save.call(value);
+                        }
+                    }
+                }
+                Label { "tokens" }
+            }
+            p {
+                class: "text-sm text-gray-500",
+                "How much conversation history is sent to agents with each message"
+            }
+            if let Some(message) = error() {
+                ErrorBox {
+                    content: message,
+                    onclick: move |_| error.set(None)
+                }
+            }
+        }
+    }
+}
+
+#[component]
+fn BackupSetting() -> Element {
+    let config = match crate::config::profile::active_profile() {
+        Ok(profile) => BackupConfig::new(profile.backup_dir()),
+        Err(e) => {
+            return rsx! {
+                ErrorBox { content: e, onclick: move |_| {} }
+            };
+        }
+    };
+    let environment = use_context::<Environment>();
+    let database = environment.database.clone();
+    let mut snapshots = use_signal(Vec::<PathBuf>::new);
+    // Snapshot awaiting a second click to confirm the restore
+    let mut confirm = use_signal(|| Option::<PathBuf>::None);
+    let mut error = use_signal(|| Option::<String>::None);
+    let mut status = use_signal(|| Option::<String>::None);
+
+    let directory = config.directory.clone();
+    let refresh = use_callback(move |_: ()| {
+        let directory = directory.clone();
+        spawn(async move {
+            match list_backups(&directory).await {
+                Ok(mut found) => {
+                    found.reverse();
+                    snapshots.set(found);
+                }
+                Err(e) => error.set(Some(e)),
+            }
+        });
+    });
+    use_future(move || async move { refresh.call(()) });
+
+    let backup_database = database.clone();
+    let backup_config = config.clone();
+    let back_up = move |_| {
+        let database = backup_database.clone();
+        let config = backup_config.clone();
+        spawn(async move {
+            match database.create_backup(&config).await {
+                Ok(_) => refresh.call(()),
+                Err(e) => error.set(Some(e)),
+            }
+        });
+    };
+
+    let restore = use_callback(move |snapshot: PathBuf| {
+        if confirm() != Some(snapshot.clone()) {
+            confirm.set(Some(snapshot));
+            return;
+        }
+        confirm.set(None);
+        let database = database.clone();
+        let config = config.clone();
+        spawn(async move {
+            match database.restore_backup(&snapshot, &config).await {
+                Ok(_) => {
+                    status.set(Some(
+                        "Restored. Restart CYRUP to reload conversations.".to_string(),
+                    ));
+                    refresh.call(());
+                }
+                Err(e) => error.set(Some(e)),
+            }
+        });
+    });
+
+    // Newest first, each with its restore button label
+    let rows: Vec<(PathBuf, &str)> = snapshots()
+        .into_iter()
+        .map(|snapshot| {
+            let label = if confirm() == Some(snapshot.clone()) {
+                "Confirm Restore"
+            } else {
+                "Restore"
+            };
+            (snapshot, label)
+        })
+        .collect();
+
+    rsx! {
+        VStack {
+            class: "gap-1",
+            HStack {
+                class: "gap-4 items-center",
+                h4 { "Backups" }
+                TextButton {
+                    text: "Back Up Now",
+                    title: "Take a snapshot of this profile's conversations",
+                    onclick: back_up
+                }
+            }
+            p {
+                class: "text-sm text-gray-500",
+                "Snapshots are taken daily and the last week is kept. Restoring saves the current state as a new snapshot first."
+            }
+            for (snapshot, label) in rows {
+                HStack {
+                    key: "{snapshot.display()}",
+                    class: "gap-4 items-center",
+                    Label { "{snapshot.file_name().unwrap_or_default().to_string_lossy()}" }
+                    TextButton {
+                        text: label,
+                        title: "Replace all conversations with this snapshot",
+                        onclick: {
+                            let snapshot = snapshot.clone();
+                            move |_| restore.call(snapshot.clone())
+                        }
+                    }
+                }
+            }
+            if let Some(message) = status() {
+                Label { class: "text-sm text-gray-500", "{message}" }
+            }
+            if let Some(message) = error() {
+                ErrorBox {
+                    content: message,
+                    onclick: move |_| error.set(None)
+                }
+            }
+        }
+    }
+}
+
+#[component]
+fn LocalApiSetting() -> Element {
+    let environment = use_context::<Environment>();
+    let database = environment.database.clone();
+    let mut config = use_signal(ApiServerConfig::default);
+    let mut running = use_signal(|| Option::<std::net::SocketAddr>::None);
+    let mut error = use_signal(|| Option::<String>::None);
+    let mut status = use_signal(|| Option::<String>::None);
+
+    use_future(move || async move {
+        config.set(api_server::load_config().await);
+        running.set(api_server::running_addr().await);
+    });
+
+    let apply = use_callback(move |value: ApiServerConfig| {
+        config.set(value);
+        let database = database.clone();
+        spawn(async move {
+            if let Err(e) = api_server::save_config(&value).await {
+                error.set(Some(e));
+                return;
+            }
+            match api_server::apply_config(database, value).await {
+                Ok(addr) => {
+                    running.set(addr);
+                    error.set(None);
+                }
+                Err(e) => {
+                    running.set(None);
+                    error.set(Some(e));
+                }
+            }
+        });
+    });
+
+    let current = config();
+    let token_path = api_server::token_path()
+        .map(|path| path.display().to_string())
+        .unwrap_or_else(|e| e);
+
+    rsx! {
+        VStack {
+            class: "gap-1",
+            HStack {
+                class: "gap-4 items-center",
+                Checkbox {
+                    checked: current.enabled,
+                    onchange: move |enabled: bool| apply.call(ApiServerConfig { enabled, ..current })
+                }
+                h4 { "Local API" }
+            }
+            p {
+                class: "text-sm text-gray-500",
+                "Lets editors, scripts and MCP clients on this machine read and send messages. Only listens on 127.0.0.1."
+            }
+            if current.enabled {
+                HStack {
+                    class: "gap-2 items-center",
+                    Label { "Port" }
+                    input {
+                        r#type: "number",
+                        min: "1",
+                        max: "65535",
+                        value: "{current.port}",
+                        onchange: move |e| {
+                            if let Ok(port) = e.value().parse::<u16>()
+                                && port != 0
+                            {
+                                apply.call(ApiServerConfig { port, ..current });
+                            }
+                        }
+                    }
+                    if let Some(addr) = running() {
+                        Label { class: "text-sm text-gray-500", "Listening on http://{addr}/api/v1" }
+                    }
+                }
+                HStack {
+                    class: "gap-4 items-center",
+                    Label { class: "text-sm text-gray-500", "Token: {token_path}" }
+                    TextButton {
+                        text: "Regenerate Token",
+                        title: "Invalidate the current token and restart the server",
+                        onclick: move |_| {
+                            spawn(async move {
+                                match api_server::regenerate_token().await {
+                                    Ok(_) => {
+                                        status.set(Some("New token written".to_string()));
+                                        apply.call(config());
+                                    }
+                                    Err(e) => error.set(Some(e)),
+                                }
+                            });
+                        }
+                    }
+                }
+            }
+            if let Some(message) = status() {
+                Label { class: "text-sm text-gray-500", "{message}" }
+            }
+            if let Some(message) = error() {
+                ErrorBox {
+                    content: message,
+                    onclick: move |_| error.set(None)
+                }
+            }
+        }
+    }
+}
+
+#[component]
+fn ProfileSetting() -> Element {
+    let profile = match crate::config::profile::active_profile() {
+        Ok(profile) => profile,
+        Err(e) => {
+            return rsx! {
+                ErrorBox { content: e, onclick: move |_| {} }
+            };
+        }
+    };
+    let current = profile.name().to_string();
+    let known = profile.known_profiles();
+    let mut new_name = use_signal(String::new);
+    let mut error = use_signal(|| Option::<String>::None);
+
+    let switch_to = move |name: String| {
+        spawn(async move {
+            if let Err(e) = crate::config::profile::switch_profile(&name).await {
+                error.set(Some(e));
+            }
+        });
+    };
+
+    rsx! {
+        VStack {
+            class: "gap-1",
+            h4 { "Profile" }
+            p {
+                class: "text-sm text-gray-500",
+                "Each profile has its own conversations, settings and credentials. Switching restarts the app."
+            }
+            for name in known {
+                HStack {
+                    key: "{name}",
+                    class: "gap-4 items-center",
+                    Label { "{name}" }
+                    if name == current {
+                        Label { class: "text-sm text-gray-500", "(active)" }
+                    } else {
+                        TextButton {
+                            text: "Switch & Restart",
+                            title: "Restart using this profile",
+                            onclick: {
+                                let name = name.clone();
+                                move |_| switch_to(name.clone())
+                            }
+                        }
+                    }
+                }
+            }
+            HStack {
+                class: "gap-4 items-center",
+                input {
+                    r#type: "text",
+                    placeholder: "new-profile",
+                    value: "{new_name}",
+                    oninput: move |e| new_name.set(e.value())
+                }
+                TextButton {
+                    text: "Create & Restart",
+                    title: "Create a new profile and restart",
+                    disabled: new_name.read().is_empty(),
+                    onclick: move |_| {
+                        let name = new_name.read().trim().to_string();
+                        match crate::config::profile::validate_name(&name) {
+                            Ok(()) => switch_to(name),
+                            Err(e) => error.set(Some(e)),
+                        }
+                    }
+                }
+            }
+            if let Some(message) = error() {
+                ErrorBox {
+                    content: message,
+                    onclick: move |_| error.set(None)
+                }
+            }
+        }
+    }
+}
+
 impl FromStr for TimelineDirection {
     type Err = String;
 
@@ -0,0 +1,37 @@
+//! Tests for the sign-in/sign-out audit trail
+
+use super::fixtures;
+use cyrup::view_model::auth_event::AuthEventKind;
+
+#[tokio::test]
+async fn test_auth_events_are_listed_per_account_newest_first() {
+    let fixture = fixtures::standard().await;
+    let db = &fixture.db;
+
+    db.record_auth_event("alice", "google", AuthEventKind::SignIn, false, None)
+        .await
+        .unwrap();
+    db.record_auth_event("bob", "github", AuthEventKind::SignIn, false, None)
+        .await
+        .unwrap();
+    db.record_auth_event(
+        "alice",
+        "google",
+        AuthEventKind::SignOut,
+        false,
+        Some("Token revocation failed after 3 attempts".to_string()),
+    )
+    .await
+    .unwrap();
+
+    let events = db.list_auth_events("alice", 10).await.unwrap();
+    assert_eq!(events.len(), 2);
+    assert_eq!(events[0].kind, AuthEventKind::SignOut);
+    assert!(!events[0].tokens_revoked);
+    assert!(events[0].detail.is_some());
+    assert_eq!(events[1].kind, AuthEventKind::SignIn);
+    assert!(events.iter().all(|event| event.provider == "google"));
+
+    assert_eq!(db.list_auth_events("alice", 1).await.unwrap().len(), 1);
+    assert!(db.list_auth_events("carol", 10).await.unwrap().is_empty());
+}
@@ -0,0 +1,156 @@
+//! Tests for snapshot backup, rotation and restore
+
+use super::fixtures;
+use cyrup::database::BackupConfig;
+use cyrup::database::backup::list_backups;
+use cyrup::view_model::agent::AgentTemplate;
+use std::io::{Read, Write};
+use std::path::Path;
+use tempfile::TempDir;
+
+fn config(dir: &TempDir, keep: usize) -> BackupConfig {
+    BackupConfig {
+        keep,
+        ..BackupConfig::new(dir.path().to_path_buf())
+    }
+}
+
+fn read_snapshot(path: &Path) -> String {
+    let file = std::fs::File::open(path).expect("Snapshot missing");
+    let mut sql = String::new();
+    snap::read::FrameDecoder::new(file)
+        .read_to_string(&mut sql)
+        .expect("Snapshot is not a compressed export");
+    sql
+}
+
+fn write_snapshot(path: &Path, sql: &str) {
+    let file = std::fs::File::create(path).expect("Failed to create snapshot");
+    let mut encoder = snap::write::FrameEncoder::new(file);
+    encoder.write_all(sql.as_bytes()).unwrap();
+    encoder.flush().unwrap();
+}
+
+async fn template_names(db: &cyrup::database::Database) -> Vec<String> {
+    let mut names: Vec<String> = db
+        .list_templates()
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|t| t.name)
+        .collect();
+    names.sort();
+    names
+}
+
+#[tokio::test]
+async fn test_backup_is_a_compressed_export() {
+    let fixture = fixtures::standard().await;
+    let dir = tempfile::tempdir().unwrap();
+
+    let path = fixture.db.create_backup(&config(&dir, 7)).await.unwrap();
+
+    assert_eq!(list_backups(dir.path()).await.unwrap(), [path.clone()]);
+    assert!(read_snapshot(&path).contains("general kenobi"));
+}
+
+#[tokio::test]
+async fn test_oldest_backups_are_rotated_out() {
+    let fixture = fixtures::standard().await;
+    let dir = tempfile::tempdir().unwrap();
+    for stamp in ["20200101000000", "20200102000000", "20200103000000"] {
+        write_snapshot(&dir.path().join(format!("chat-{stamp}.surql.sz")), "");
+    }
+
+    let newest = fixture.db.create_backup(&config(&dir, 2)).await.unwrap();
+
+    assert_eq!(
+        list_backups(dir.path()).await.unwrap(),
+        [dir.path().join("chat-20200103000000.surql.sz"), newest]
+    );
+}
+
+#[tokio::test]
+async fn test_restore_round_trip() {
+    let fixture = fixtures::standard().await;
+    let db = &fixture.db;
+    let snapshots = tempfile::tempdir().unwrap();
+    let safety_dir = tempfile::tempdir().unwrap();
+
+    let snapshot = db.create_backup(&config(&snapshots, 7)).await.unwrap();
+
+    // Diverge from the snapshot
+    db.create_template(&AgentTemplate {
+        name: "Added later".to_string(),
+        ..Default::default()
+    })
+    .await
+    .unwrap();
+    db.client().query("DELETE message").await.unwrap();
+
+    let safety = db
+        .restore_backup(&snapshot, &config(&safety_dir, 7))
+        .await
+        .unwrap();
+
+    assert_eq!(template_names(db).await, ["Researcher", "Reviewer"]);
+    let solo = db
+        .get_all_messages(&fixture.conversations[0])
+        .await
+        .unwrap();
+    assert_eq!(solo.len(), 4);
+    assert!(db.get_message(&fixture.messages[2]).await.unwrap().pinned);
+    assert_eq!(
+        db.get_message(&fixture.messages[1])
+            .await
+            .unwrap()
+            .in_reply_to,
+        Some(fixture.messages[0].clone())
+    );
+
+    // The replaced state was saved first
+    assert!(read_snapshot(&safety).contains("Added later"));
+}
+
+#[tokio::test]
+async fn test_failed_restore_changes_nothing() {
+    let fixture = fixtures::standard().await;
+    let db = &fixture.db;
+    let dir = tempfile::tempdir().unwrap();
+    let corrupt = dir.path().join("corrupt.surql.sz");
+    write_snapshot(&corrupt, "OPTION IMPORT;\nTHROW 'snapshot is corrupt';\n");
+
+    let error = db
+        .restore_backup(&corrupt, &config(&dir, 7))
+        .await
+        .unwrap_err();
+
+    assert!(error.contains("Failed to import backup"), "{error}");
+    assert_eq!(template_names(db).await, ["Researcher", "Reviewer"]);
+    let solo = db
+        .get_all_messages(&fixture.conversations[0])
+        .await
+        .unwrap();
+    assert_eq!(solo.len(), 4);
+}
+
+#[tokio::test]
+async fn test_restore_keeps_the_restored_snapshot() {
+    let fixture = fixtures::standard().await;
+    let db = &fixture.db;
+    let dir = tempfile::tempdir().unwrap();
+
+    let oldest = dir.path().join("chat-20200101000000.surql.sz");
+    let export = read_snapshot(&db.create_backup(&config(&dir, 7)).await.unwrap());
+    for file in list_backups(dir.path()).await.unwrap() {
+        std::fs::remove_file(file).unwrap();
+    }
+    write_snapshot(&oldest, &export);
+    write_snapshot(&dir.path().join("chat-20200102000000.surql.sz"), &export);
+
+    // Retention is full, and the snapshot being restored is the oldest
+    let safety = db.restore_backup(&oldest, &config(&dir, 2)).await.unwrap();
+
+    assert_eq!(list_backups(dir.path()).await.unwrap(), [oldest, safety]);
+    assert_eq!(template_names(db).await, ["Researcher", "Reviewer"]);
+}
@@ -0,0 +1,236 @@
+//! In-memory database fixtures
+//!
+//! [`FixtureBuilder`] describes templates, conversations, messages (with
+//! replies, unread and pinned flags), reactions and bookmarks by index, then
+//! inserts them into a fresh [`Database::in_memory`] instance. Message
+//! timestamps are one second apart in declaration order, so ordering
+//! assertions are deterministic.
+
+use chrono::{Duration, Utc};
+use cyrup::database::Database;
+use cyrup::view_model::agent::AgentTemplate;
+use cyrup::view_model::conversation::Conversation;
+use cyrup::view_model::message::{AuthorType, Message};
+use surrealdb_types::{RecordId, ToSql};
+
+/// User id used for fixture reactions and bookmarks
+pub const USER: &str = "fixture-user";
+
+/// A populated in-memory database and the ids of everything inserted
+pub struct Fixture {
+    pub db: Database,
+    pub templates: Vec<RecordId>,
+    pub conversations: Vec<RecordId>,
+    pub messages: Vec<RecordId>,
+}
+
+struct MessageSpec {
+    conversation: usize,
+    author_type: AuthorType,
+    content: String,
+    in_reply_to: Option<usize>,
+    unread: bool,
+    pinned: bool,
+}
+
+/// Declarative builder for [`Fixture`]
+#[derive(Default)]
+pub struct FixtureBuilder {
+    templates: Vec<String>,
+    conversations: Vec<(String, Vec<usize>)>,
+    messages: Vec<MessageSpec>,
+    reactions: Vec<(usize, String)>,
+    bookmarks: Vec<usize>,
+}
+
+impl FixtureBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add an agent template
+    pub fn template(mut self, name: &str) -> Self {
+        self.templates.push(name.to_string());
+        self
+    }
+
+    /// Add a conversation whose participants are template indices
+    pub fn conversation(mut self, title: &str, participants: &[usize]) -> Self {
+        self.conversations
+            .push((title.to_string(), participants.to_vec()));
+        self
+    }
+
+    /// Add a human message to a conversation
+    pub fn human(self, conversation: usize, content: &str) -> Self {
+        self.message(conversation, AuthorType::Human, content, None)
+    }
+
+    /// Add an agent message to a conversation
+    pub fn agent(self, conversation: usize, content: &str) -> Self {
+        self.message(conversation, AuthorType::Agent, content, None)
+    }
+
+    /// Add an agent reply to an earlier message (by message index)
+    pub fn reply(self, conversation: usize, to: usize, content: &str) -> Self {
+        self.message(conversation, AuthorType::Agent, content, Some(to))
+    }
+
+    /// Mark the most recently added message as unread
+    pub fn unread(mut self) -> Self {
+        if let Some(last) = self.messages.last_mut() {
+            last.unread = true;
+        }
+        self
+    }
+
+    /// Mark the most recently added message as pinned (bypasses the pin limit)
+    pub fn pinned(mut self) -> Self {
+        if let Some(last) = self.messages.last_mut() {
+            last.pinned = true;
+        }
+        self
+    }
+
+    /// React to a message (by message index) as [`USER`]
+    pub fn reaction(mut self, message: usize, emoji: &str) -> Self {
+        self.reactions.push((message, emoji.to_string()));
+        self
+    }
+
+    /// Bookmark a message (by message index) as [`USER`]
+    pub fn bookmark(mut self, message: usize) -> Self {
+        self.bookmarks.push(message);
+        self
+    }
+
+    fn message(
+        mut self,
+        conversation: usize,
+        author_type: AuthorType,
+        content: &str,
+        in_reply_to: Option<usize>,
+    ) -> Self {
+        self.messages.push(MessageSpec {
+            conversation,
+            author_type,
+            content: content.to_string(),
+            in_reply_to,
+            unread: false,
+            pinned: false,
+        });
+        self
+    }
+
+    /// Insert everything into a fresh in-memory database
+    pub async fn build(self) -> Fixture {
+        let db = Database::in_memory()
+            .await
+            .expect("Failed to open in-memory database");
+
+        let mut templates = Vec::new();
+        for name in &self.templates {
+            let template = AgentTemplate {
+                name: name.clone(),
+                ..Default::default()
+            };
+            templates.push(db.create_template(&template).await.expect("Seed template"));
+        }
+
+        let epoch = Utc::now() - Duration::days(1);
+
+        let mut conversations = Vec::new();
+        for (title, participants) in &self.conversations {
+            let conversation = Conversation {
+                title: title.clone(),
+                participants: participants.iter().map(|&i| templates[i].clone()).collect(),
+                last_message_at: epoch.into(),
+                ..Default::default()
+            };
+            conversations.push(
+                db.create_conversation(&conversation)
+                    .await
+                    .expect("Seed conversation"),
+            );
+        }
+
+        let mut messages: Vec<RecordId> = Vec::new();
+        for (i, spec) in self.messages.iter().enumerate() {
+            let message = Message {
+                conversation_id: conversations[spec.conversation].clone(),
+                author: match spec.author_type {
+                    AuthorType::Human => "user".to_string(),
+                    _ => "agent".to_string(),
+                },
+                author_type: spec.author_type,
+                content: spec.content.clone(),
+                timestamp: (epoch + Duration::seconds(i as i64 + 1)).into(),
+                in_reply_to: spec.in_reply_to.map(|m| messages[m].clone()),
+                unread: spec.unread,
+                pinned: spec.pinned,
+                ..Default::default()
+            };
+            messages.push(db.insert_message(&message).await.expect("Seed message"));
+        }
+
+        for (message, emoji) in &self.reactions {
+            db.add_reaction(&key(&messages[*message]), USER, emoji)
+                .await
+                .expect("Seed reaction");
+        }
+
+        for message in &self.bookmarks {
+            db.bookmark_message(USER, &key(&messages[*message]))
+                .await
+                .expect("Seed bookmark");
+        }
+
+        Fixture {
+            db,
+            templates,
+            conversations,
+            messages,
+        }
+    }
+}
+
+/// Record key without the table prefix, as taken by the reaction and
+/// bookmark APIs (`type::record('message', $key)`)
+pub fn key(id: &RecordId) -> String {
+    let sql = id.to_sql();
+    match sql.split_once(':') {
+        Some((_, key)) => key.trim_matches(['⟨', '⟩', '`']).to_string(),
+        None => sql,
+    }
+}
+
+/// Two agents, two conversations:
+///
+/// | index | conversation | author | flags                     |
+/// |-------|--------------|--------|---------------------------|
+/// | 0     | 0            | human  |                           |
+/// | 1     | 0            | agent  | reply to 0, unread        |
+/// | 2     | 0            | agent  | unread, pinned, 👍 ×1, 🔖 |
+/// | 3     | 0            | human  |                           |
+/// | 4     | 1            | human  |                           |
+/// | 5     | 1            | agent  | reply to 4                |
+pub async fn standard() -> Fixture {
+    FixtureBuilder::new()
+        .template("Researcher")
+        .template("Reviewer")
+        .conversation("Solo", &[0])
+        .conversation("Group", &[0, 1])
+        .human(0, "hello there")
+        .reply(0, 0, "general kenobi")
+        .unread()
+        .agent(0, "an important finding")
+        .unread()
+        .pinned()
+        .reaction(2, "👍")
+        .bookmark(2)
+        .human(0, "thanks")
+        .human(1, "what do you both think?")
+        .reply(1, 4, "looks good to me")
+        .build()
+        .await
+}
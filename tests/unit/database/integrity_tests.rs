@@ -0,0 +1,175 @@
+//! Tests for referential integrity checks and repairs
+
+use super::fixtures::{self, FixtureBuilder};
+use cyrup::database::BackupConfig;
+use cyrup::database::backup::list_backups;
+use surrealdb_types::ToSql;
+
+#[tokio::test]
+async fn test_standard_fixture_is_clean() {
+    let fixture = fixtures::standard().await;
+
+    let report = fixture.db.check_integrity().await.unwrap();
+
+    assert!(report.is_clean(), "{report:?}");
+    assert_eq!(fixture.db.repair_integrity(&report).await.unwrap(), 0);
+}
+
+#[tokio::test]
+async fn test_dangling_reply_is_cleared() {
+    let fixture = fixtures::standard().await;
+    let db = &fixture.db;
+    db.client()
+        .query("DELETE $id")
+        .bind(("id", fixture.messages[0].clone()))
+        .await
+        .unwrap();
+
+    let report = db.check_integrity().await.unwrap();
+    assert_eq!(report.dangling_replies, [fixture.messages[1].clone()]);
+    assert_eq!(report.issue_count(), 1);
+
+    assert_eq!(db.repair_integrity(&report).await.unwrap(), 1);
+    let reply = db.get_message(&fixture.messages[1]).await.unwrap();
+    assert_eq!(reply.in_reply_to, None);
+    assert!(db.check_integrity().await.unwrap().is_clean());
+}
+
+#[tokio::test]
+async fn test_orphaned_session_is_removed() {
+    let fixture = fixtures::standard().await;
+    let db = &fixture.db;
+    // The Reviewer is not a participant of the solo conversation
+    db.update_agent_session(&fixture.conversations[0], &fixture.templates[1], "stray")
+        .await
+        .unwrap();
+
+    let report = db.check_integrity().await.unwrap();
+    assert_eq!(
+        report.orphaned_sessions,
+        [(
+            fixture.conversations[0].clone(),
+            fixture.templates[1].to_sql()
+        )]
+    );
+
+    assert_eq!(db.repair_integrity(&report).await.unwrap(), 1);
+    let conversation = db
+        .get_conversation(&fixture.conversations[0])
+        .await
+        .unwrap();
+    assert!(conversation.agent_sessions.is_empty());
+    assert!(db.check_integrity().await.unwrap().is_clean());
+}
+
+#[tokio::test]
+async fn test_deleted_template_is_removed_from_participants() {
+    let fixture = fixtures::standard().await;
+    let db = &fixture.db;
+    db.delete_template(&fixture.templates[1]).await.unwrap();
+
+    let report = db.check_integrity().await.unwrap();
+    assert_eq!(
+        report.missing_participants,
+        [(
+            fixture.conversations[1].clone(),
+            fixture.templates[1].clone()
+        )]
+    );
+
+    assert_eq!(db.repair_integrity(&report).await.unwrap(), 1);
+    let group = db
+        .get_conversation(&fixture.conversations[1])
+        .await
+        .unwrap();
+    assert_eq!(group.participants, [fixture.templates[0].clone()]);
+    assert!(db.check_integrity().await.unwrap().is_clean());
+}
+
+#[tokio::test]
+async fn test_last_participant_is_kept() {
+    let fixture = fixtures::standard().await;
+    let db = &fixture.db;
+    db.delete_template(&fixture.templates[0]).await.unwrap();
+
+    let report = db.check_integrity().await.unwrap();
+    assert_eq!(report.missing_participants.len(), 2);
+
+    // Only the group conversation has another participant to fall back on
+    assert_eq!(db.repair_integrity(&report).await.unwrap(), 1);
+    let solo = db
+        .get_conversation(&fixture.conversations[0])
+        .await
+        .unwrap();
+    assert_eq!(solo.participants, [fixture.templates[0].clone()]);
+    let group = db
+        .get_conversation(&fixture.conversations[1])
+        .await
+        .unwrap();
+    assert_eq!(group.participants, [fixture.templates[1].clone()]);
+}
+
+#[tokio::test]
+async fn test_oldest_excess_pins_are_unpinned() {
+    let mut builder = FixtureBuilder::new()
+        .template("Researcher")
+        .conversation("Pinned", &[0]);
+    for i in 0..7 {
+        builder = builder.human(0, &format!("pin {i}")).pinned();
+    }
+    let fixture = builder.build().await;
+    let db = &fixture.db;
+
+    let report = db.check_integrity().await.unwrap();
+    assert_eq!(
+        report.excess_pins,
+        [fixture.messages[1].clone(), fixture.messages[0].clone()]
+    );
+
+    assert_eq!(db.repair_integrity(&report).await.unwrap(), 2);
+    let pinned = db
+        .get_pinned_messages(&fixture.conversations[0])
+        .await
+        .unwrap();
+    assert_eq!(pinned.len(), 5);
+    assert!(!db.get_message(&fixture.messages[0]).await.unwrap().pinned);
+    assert!(db.check_integrity().await.unwrap().is_clean());
+}
+
+#[tokio::test]
+async fn test_repair_is_preceded_by_a_snapshot() {
+    let fixture = fixtures::standard().await;
+    let db = &fixture.db;
+    db.delete_template(&fixture.templates[1]).await.unwrap();
+    let report = db.check_integrity().await.unwrap();
+    let dir = tempfile::tempdir().unwrap();
+
+    let config = BackupConfig::new(dir.path().to_path_buf());
+    assert_eq!(db.backup_and_repair(&report, &config).await.unwrap(), 1);
+    assert_eq!(list_backups(dir.path()).await.unwrap().len(), 1);
+    assert!(db.check_integrity().await.unwrap().is_clean());
+}
+
+#[tokio::test]
+async fn test_repair_is_skipped_without_a_snapshot() {
+    let fixture = fixtures::standard().await;
+    let db = &fixture.db;
+    db.delete_template(&fixture.templates[1]).await.unwrap();
+    let report = db.check_integrity().await.unwrap();
+
+    // A file where the backup directory should be
+    let dir = tempfile::tempdir().unwrap();
+    let blocked = dir.path().join("backups");
+    std::fs::write(&blocked, "").unwrap();
+
+    let error = db
+        .backup_and_repair(&report, &BackupConfig::new(blocked))
+        .await
+        .unwrap_err();
+    assert!(error.contains("Repair skipped"), "{error}");
+    let group = db
+        .get_conversation(&fixture.conversations[1])
+        .await
+        .unwrap();
+    assert_eq!(group.participants.len(), 2);
+}
@@ -0,0 +1,109 @@
+//! Tests for MCP server registration and per-template selection
+
+use super::fixtures;
+use cyrup::view_model::mcp_server::{McpServer, McpTransport};
+
+fn stdio(name: &str) -> McpServer {
+    McpServer {
+        name: name.to_string(),
+        command: "npx".to_string(),
+        args: vec![
+            "-y".to_string(),
+            "@modelcontextprotocol/server-github".to_string(),
+        ],
+        secret_keys: vec!["GITHUB_TOKEN".to_string()],
+        ..Default::default()
+    }
+}
+
+#[tokio::test]
+async fn test_mcp_server_crud() {
+    let fixture = fixtures::standard().await;
+    let db = &fixture.db;
+
+    let id = db.create_mcp_server(&stdio("github")).await.unwrap();
+
+    let mut server = db.get_mcp_server(&id).await.unwrap();
+    assert_eq!(server.transport, McpTransport::Stdio);
+    assert_eq!(server.args.len(), 2);
+    assert_eq!(server.secret_keys, vec!["GITHUB_TOKEN".to_string()]);
+
+    server.transport = McpTransport::Http;
+    server.url = "https://example.com/mcp".to_string();
+    server.env.insert("REGION".to_string(), "eu".to_string());
+    db.update_mcp_server(&server).await.unwrap();
+
+    let updated = db.get_mcp_server(&id).await.unwrap();
+    assert_eq!(updated.transport, McpTransport::Http);
+    assert_eq!(updated.url, "https://example.com/mcp");
+    assert_eq!(updated.env.get("REGION").map(String::as_str), Some("eu"));
+
+    assert_eq!(db.list_mcp_servers().await.unwrap().len(), 1);
+    db.delete_mcp_server(&id).await.unwrap();
+    assert!(db.list_mcp_servers().await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn test_invalid_and_duplicate_servers_are_rejected() {
+    let fixture = fixtures::standard().await;
+    let db = &fixture.db;
+
+    db.create_mcp_server(&stdio("github")).await.unwrap();
+    let duplicate = db.create_mcp_server(&stdio("github")).await.unwrap_err();
+    assert!(duplicate.contains("already exists"), "{}", duplicate);
+
+    let no_url = McpServer {
+        transport: McpTransport::Http,
+        ..stdio("remote")
+    };
+    assert!(db.create_mcp_server(&no_url).await.is_err());
+    assert!(db.create_mcp_server(&stdio("has space")).await.is_err());
+}
+
+#[tokio::test]
+async fn test_template_server_selection() {
+    let fixture = fixtures::standard().await;
+    let db = &fixture.db;
+    let template = &fixture.templates[0];
+
+    let github = db.create_mcp_server(&stdio("github")).await.unwrap();
+    let files = db.create_mcp_server(&stdio("files")).await.unwrap();
+
+    db.set_template_mcp_servers(template, &[github.clone(), files.clone()])
+        .await
+        .unwrap();
+    let names: Vec<_> = db
+        .list_template_mcp_servers(template)
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|s| s.name)
+        .collect();
+    assert_eq!(names, vec!["files", "github"]);
+
+    // Replacing the selection drops servers no longer listed
+    db.set_template_mcp_servers(template, std::slice::from_ref(&github))
+        .await
+        .unwrap();
+    assert_eq!(
+        db.list_template_mcp_servers(template).await.unwrap().len(),
+        1
+    );
+
+    // Other templates are unaffected
+    assert!(
+        db.list_template_mcp_servers(&fixture.templates[1])
+            .await
+            .unwrap()
+            .is_empty()
+    );
+
+    // Deleting a server removes it from every template
+    db.delete_mcp_server(&github).await.unwrap();
+    assert!(
+        db.list_template_mcp_servers(template)
+            .await
+            .unwrap()
+            .is_empty()
+    );
+}
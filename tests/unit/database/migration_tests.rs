@@ -0,0 +1,263 @@
+//! Integration tests for the schema migration framework
+//!
+//! Every test builds a fixture database at a past schema version, then
+//! migrates it forward (or back) and checks the resulting state.
+
+use cyrup::database::migration::latest_version;
+use cyrup::database::{Database, MIGRATIONS, MigrationOptions};
+use serde::Deserialize;
+use surrealdb_types::{RecordId, SurrealValue};
+use tempfile::TempDir;
+
+const NO_BACKUP: MigrationOptions = MigrationOptions {
+    dry_run: false,
+    backup: false,
+};
+
+/// Create a file-backed database at `version` and seed it with data shaped
+/// the way that version stored it
+///
+/// The schema is the baseline plus each migration's `up` applied in order,
+/// so the fixture is a real version-`version` database.
+async fn fixture_at_version(version: i64) -> (TempDir, Database) {
+    let dir = tempfile::tempdir().expect("Failed to create temp dir");
+    let db = Database::connect(dir.path().join("chat.db"), "chat")
+        .await
+        .expect("Failed to open fixture database");
+
+    db.migrate_to(version, NO_BACKUP)
+        .await
+        .expect("Failed to migrate fixture to starting version");
+
+    // Only fields every version defines, so the seed fits any schema
+    let template_id = RecordId::new("agent_template", "seed");
+    db.client()
+        .query("CREATE $template CONTENT { name: 'Seed', system_prompt: '', model: 'sonnet' }")
+        .bind(("template", template_id.clone()))
+        .await
+        .and_then(|response| response.check())
+        .expect("Failed to seed template");
+
+    if version == 0 {
+        // Pre-unification data: multi-agent rooms lived in their own table
+        db.client()
+            .query(
+                "CREATE room:legacy CONTENT {
+                    title: 'Legacy Room',
+                    participants: [$template],
+                    summary: '',
+                    last_message_at: time::now(),
+                    created_at: time::now()
+                }",
+            )
+            .bind(("template", template_id))
+            .await
+            .expect("Failed to seed legacy room");
+    }
+
+    (dir, db)
+}
+
+#[tokio::test]
+async fn test_migrates_every_past_version_to_latest() {
+    for version in 0..=latest_version() {
+        let (_dir, db) = fixture_at_version(version).await;
+
+        let report = db.migrate(NO_BACKUP).await.expect("Migration failed");
+
+        assert_eq!(report.from_version, version);
+        assert_eq!(report.to_version, latest_version());
+        assert_eq!(
+            report.applied,
+            ((version + 1)..=latest_version()).collect::<Vec<_>>()
+        );
+        assert_eq!(db.get_schema_version().await.unwrap(), latest_version());
+    }
+}
+
+#[tokio::test]
+async fn test_legacy_rooms_become_conversations() {
+    let (_dir, db) = fixture_at_version(0).await;
+
+    db.migrate(NO_BACKUP).await.expect("Migration failed");
+
+    let conversations = db.list_conversations().await.expect("List failed");
+    assert!(conversations.iter().any(|c| c.title == "Legacy Room"));
+}
+
+#[tokio::test]
+async fn test_checksums_are_recorded() {
+    let (_dir, db) = fixture_at_version(0).await;
+    db.migrate(NO_BACKUP).await.expect("Migration failed");
+
+    #[derive(Deserialize, SurrealValue)]
+    struct Applied {
+        version: i64,
+        checksum: Option<String>,
+    }
+
+    let mut response = db
+        .client()
+        .query("SELECT version, checksum FROM schema_version ORDER BY version")
+        .await
+        .unwrap();
+    let applied: Vec<Applied> = response.take(0).unwrap();
+
+    assert_eq!(applied.len(), MIGRATIONS.len());
+    for (record, migration) in applied.iter().zip(MIGRATIONS) {
+        assert_eq!(record.version, migration.version);
+        assert_eq!(record.checksum.as_deref(), Some(migration.checksum().as_str()));
+    }
+}
+
+#[tokio::test]
+async fn test_dry_run_changes_nothing() {
+    let (_dir, db) = fixture_at_version(0).await;
+
+    let report = db
+        .migrate(MigrationOptions {
+            dry_run: true,
+            backup: true,
+        })
+        .await
+        .expect("Dry run failed");
+
+    assert!(report.dry_run);
+    assert!(report.backup_path.is_none());
+    assert_eq!(report.applied.len(), MIGRATIONS.len());
+    assert_eq!(db.get_schema_version().await.unwrap(), 0);
+}
+
+#[tokio::test]
+async fn test_down_then_up_round_trip() {
+    let (_dir, db) = fixture_at_version(latest_version()).await;
+
+    let down = db.migrate_to(1, NO_BACKUP).await.expect("Revert failed");
+    assert_eq!(down.reverted, ((2..=latest_version()).rev()).collect::<Vec<_>>());
+    assert_eq!(db.get_schema_version().await.unwrap(), 1);
+
+    db.migrate(NO_BACKUP).await.expect("Re-apply failed");
+    assert_eq!(db.get_schema_version().await.unwrap(), latest_version());
+}
+
+#[tokio::test]
+async fn test_irreversible_migration_is_rejected() {
+    let (_dir, db) = fixture_at_version(latest_version()).await;
+
+    let result = db.migrate_to(0, NO_BACKUP).await;
+
+    assert!(result.is_err());
+    assert_eq!(db.get_schema_version().await.unwrap(), latest_version());
+}
+
+#[tokio::test]
+async fn test_backup_taken_before_migrating() {
+    let (_dir, db) = fixture_at_version(0).await;
+
+    let report = db
+        .migrate(MigrationOptions::default())
+        .await
+        .expect("Migration failed");
+
+    let backup = report.backup_path.expect("No backup taken");
+    let export = std::fs::read_to_string(&backup).expect("Backup unreadable");
+    assert!(export.contains("Legacy Room"), "backup is missing pre-migration data");
+}
+
+#[tokio::test]
+async fn test_reverted_schema_survives_reopen() {
+    let (dir, db) = fixture_at_version(latest_version()).await;
+    db.migrate_to(6, NO_BACKUP).await.expect("Revert failed");
+    drop(db);
+
+    let db = Database::connect(dir.path().join("chat.db"), "chat")
+        .await
+        .expect("Failed to reopen database");
+    assert_eq!(db.get_schema_version().await.unwrap(), 6);
+
+    // Migration 7's backend field (DEFAULT "claude") must stay reverted
+    #[derive(Deserialize, SurrealValue)]
+    struct Backend {
+        backend: Option<String>,
+    }
+
+    let mut response = db
+        .client()
+        .query(
+            "CREATE agent_template:reopened CONTENT { name: 'Reopened', system_prompt: '', model: 'sonnet' };
+             SELECT backend FROM agent_template:reopened",
+        )
+        .await
+        .unwrap();
+    let rows: Vec<Backend> = response.take(1).unwrap();
+
+    assert_eq!(rows.len(), 1);
+    assert_eq!(rows[0].backend, None);
+}
+
+#[tokio::test]
+async fn test_user_data_is_rekeyed_by_account_key() {
+    let (_dir, db) = fixture_at_version(9).await;
+    db.client()
+        .query(
+            "CREATE auth_event CONTENT { user_id: '1234', provider: 'github', kind: 'signin' };
+             CREATE conversation:owned CONTENT {
+                title: 'Owned',
+                participants: [$template],
+                last_message_at: time::now(),
+                user_id: '1234'
+             };
+             CREATE conversation:unknown CONTENT {
+                title: 'Unknown',
+                participants: [$template],
+                last_message_at: time::now(),
+                user_id: '5678'
+             };
+             CREATE conversation:shared CONTENT {
+                title: 'Shared',
+                participants: [$template],
+                last_message_at: time::now(),
+                user_id: 'team:alpha'
+             };",
+        )
+        .bind(("template", RecordId::new("agent_template", "seed")))
+        .await
+        .and_then(|response| response.check())
+        .expect("Failed to seed owned conversations");
+
+    #[derive(Deserialize, SurrealValue)]
+    struct Owner {
+        user_id: Option<String>,
+    }
+
+    async fn owners(db: &Database) -> Vec<Option<String>> {
+        let mut response = db
+            .client()
+            .query("SELECT user_id FROM [conversation:owned, conversation:unknown, conversation:shared]")
+            .await
+            .unwrap();
+        let rows: Vec<Owner> = response.take(0).unwrap();
+        rows.into_iter().map(|row| row.user_id).collect()
+    }
+
+    db.migrate(NO_BACKUP).await.expect("Migration failed");
+    assert_eq!(
+        owners(&db).await,
+        vec![
+            Some("github:1234".to_string()),
+            Some("5678".to_string()),
+            Some("team:alpha".to_string())
+        ]
+    );
+
+    // Only the keys Migration 10 built are reverted
+    db.migrate_to(9, NO_BACKUP).await.expect("Revert failed");
+    assert_eq!(
+        owners(&db).await,
+        vec![
+            Some("1234".to_string()),
+            Some("5678".to_string()),
+            Some("team:alpha".to_string())
+        ]
+    );
+}
@@ -0,0 +1,12 @@
+pub mod fixtures;
+mod auth_event_tests;
+mod backup_tests;
+mod integrity_tests;
+mod mcp_server_tests;
+mod migration_tests;
+mod notification_tests;
+mod open_tests;
+mod query_tests;
+mod schedule_tests;
+mod snippet_tests;
+mod transcript_tests;
@@ -0,0 +1,85 @@
+//! Tests for notification policies, quiet hours and delivery receipts
+
+use super::fixtures;
+use chrono::NaiveTime;
+use cyrup::notifications::{NotificationPolicy, QuietHours};
+
+#[tokio::test]
+async fn test_policy_defaults_and_updates() {
+    let fixture = fixtures::standard().await;
+    let db = &fixture.db;
+    let (solo, group) = (&fixture.conversations[0], &fixture.conversations[1]);
+
+    assert_eq!(
+        db.get_notification_policy(solo).await.unwrap(),
+        NotificationPolicy::Always
+    );
+
+    db.set_notification_policy(solo, NotificationPolicy::MentionsOnly)
+        .await
+        .unwrap();
+    db.set_notification_policy(solo, NotificationPolicy::Muted)
+        .await
+        .unwrap();
+
+    assert_eq!(
+        db.get_notification_policy(solo).await.unwrap(),
+        NotificationPolicy::Muted
+    );
+    assert_eq!(
+        db.get_notification_policy(group).await.unwrap(),
+        NotificationPolicy::Always
+    );
+}
+
+#[tokio::test]
+async fn test_quiet_hours_round_trip() {
+    let fixture = fixtures::standard().await;
+    let db = &fixture.db;
+    assert_eq!(db.get_quiet_hours().await.unwrap(), None);
+
+    let night = QuietHours {
+        start: NaiveTime::from_hms_opt(22, 30, 0).unwrap(),
+        end: NaiveTime::from_hms_opt(7, 0, 0).unwrap(),
+    };
+    db.set_quiet_hours(Some(night)).await.unwrap();
+    assert_eq!(db.get_quiet_hours().await.unwrap(), Some(night));
+
+    db.set_quiet_hours(None).await.unwrap();
+    assert_eq!(db.get_quiet_hours().await.unwrap(), None);
+}
+
+#[tokio::test]
+async fn test_receipt_claim_suppresses_duplicates() {
+    let fixture = fixtures::standard().await;
+    let db = &fixture.db;
+    let conversation = &fixture.conversations[0];
+    let message = &fixture.messages[1];
+
+    assert!(db.claim_notification(message, conversation).await.unwrap());
+    assert!(!db.claim_notification(message, conversation).await.unwrap());
+
+    db.complete_notification_receipt(message, "Linux")
+        .await
+        .unwrap();
+    assert!(!db.claim_notification(message, conversation).await.unwrap());
+
+    // Other replies are unaffected
+    assert!(
+        db.claim_notification(&fixture.messages[2], conversation)
+            .await
+            .unwrap()
+    );
+}
+
+#[tokio::test]
+async fn test_released_claim_can_be_retried() {
+    let fixture = fixtures::standard().await;
+    let db = &fixture.db;
+    let conversation = &fixture.conversations[0];
+    let message = &fixture.messages[1];
+
+    assert!(db.claim_notification(message, conversation).await.unwrap());
+    db.release_notification(message).await.unwrap();
+    assert!(db.claim_notification(message, conversation).await.unwrap());
+}
@@ -0,0 +1,33 @@
+//! Tests for reopening a file-backed database
+//!
+//! A profile switch closes the store in one process and opens it in the
+//! next; the second open must not fail on the first one's lock.
+
+use cyrup::database::Database;
+use cyrup::view_model::agent::AgentTemplate;
+
+#[tokio::test]
+async fn test_reopen_after_drop() {
+    let dir = tempfile::tempdir().expect("Failed to create temp dir");
+    let path = dir.path().join("chat.db");
+
+    let db = Database::open(path.clone(), "chat")
+        .await
+        .expect("First open failed");
+    let template_id = db
+        .create_template(&AgentTemplate::default())
+        .await
+        .expect("Failed to create template");
+    let copy = db.clone();
+    drop(db);
+    drop(copy);
+
+    let db = Database::open(path, "chat")
+        .await
+        .expect("Reopen after drop failed");
+    let template = db
+        .get_template(&template_id)
+        .await
+        .expect("Template lost across reopen");
+    assert_eq!(template.name, AgentTemplate::default().name);
+}
@@ -0,0 +1,749 @@
+//! Integration tests for the public `Database` query API
+//!
+//! Each test runs against its own in-memory database built from
+//! [`fixtures::standard`] or a custom [`FixtureBuilder`].
+
+use super::fixtures::{self, FixtureBuilder, USER, key};
+use chrono::{Duration, Utc};
+use cyrup::database::Database;
+use cyrup::database::bookmarks::LEGACY_USER_ID;
+use cyrup::view_model::agent::AgentTemplate;
+use cyrup::view_model::auth_event::AuthEventKind;
+use cyrup::view_model::conversation::Conversation;
+use cyrup::view_model::message::{AuthorType, Message};
+use surrealdb_types::{Datetime, RecordId, ToSql};
+
+// ============================================================================
+// Templates
+// ============================================================================
+
+#[tokio::test]
+async fn test_template_crud() {
+    let db = Database::in_memory().await.unwrap();
+
+    let id = db
+        .create_template(&AgentTemplate {
+            name: "Writer".to_string(),
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+
+    let mut template = db.get_template(&id).await.unwrap();
+    assert_eq!(template.name, "Writer");
+
+    template.max_turns = 7;
+    db.update_template(&template).await.unwrap();
+    assert_eq!(db.get_template(&id).await.unwrap().max_turns, 7);
+
+    assert_eq!(db.list_templates().await.unwrap().len(), 1);
+    assert_eq!(db.list_agent_templates().await.unwrap().len(), 1);
+
+    db.delete_template(&id).await.unwrap();
+    assert!(db.get_template(&id).await.is_err());
+    assert!(db.delete_template(&id).await.is_err());
+}
+
+#[tokio::test]
+async fn test_find_template_by_name_or_id() {
+    let fixture = fixtures::standard().await;
+    let db = &fixture.db;
+    let reviewer = &fixture.templates[1];
+
+    assert_eq!(&db.find_template("reviewer").await.unwrap().id, reviewer);
+    assert_eq!(
+        &db.find_template(&reviewer.to_sql()).await.unwrap().id,
+        reviewer
+    );
+    assert_eq!(
+        &db.find_template(&key(reviewer)).await.unwrap().id,
+        reviewer
+    );
+    assert!(db.find_template("Nobody").await.is_err());
+}
+
+// ============================================================================
+// Conversations
+// ============================================================================
+
+#[tokio::test]
+async fn test_conversation_requires_participants() {
+    let db = Database::in_memory().await.unwrap();
+
+    let result = db
+        .create_conversation(&Conversation {
+            participants: Vec::new(),
+            ..Default::default()
+        })
+        .await;
+
+    assert!(result.is_err());
+}
+
+#[tokio::test]
+async fn test_get_conversation() {
+    let f = fixtures::standard().await;
+
+    let group = f.db.get_conversation(&f.conversations[1]).await.unwrap();
+    assert_eq!(group.title, "Group");
+    assert_eq!(group.participants, f.templates);
+    assert!(group.agent_sessions.is_empty());
+
+    assert!(
+        f.db.get_conversation(&RecordId::new("conversation", "missing"))
+            .await
+            .is_err()
+    );
+}
+
+#[tokio::test]
+async fn test_conversation_lists_are_newest_first_with_unread_counts() {
+    let f = fixtures::standard().await;
+
+    for summaries in [
+        f.db.list_conversations().await.unwrap(),
+        f.db.list_recent_conversations(USER, 10).await.unwrap(),
+    ] {
+        let titles: Vec<_> = summaries.iter().map(|s| s.title.as_str()).collect();
+        assert_eq!(titles, ["Group", "Solo"]);
+
+        assert_eq!(summaries[0].last_message_preview, "looks good to me");
+        assert_eq!(summaries[0].unread_count, 0);
+        assert_eq!(summaries[1].last_message_preview, "thanks");
+        assert_eq!(summaries[1].unread_count, 2);
+    }
+
+    assert_eq!(f.db.list_recent_conversations(USER, 1).await.unwrap().len(), 1);
+}
+
+#[tokio::test]
+async fn test_conversations_are_scoped_to_their_account() {
+    let f = fixtures::standard().await;
+    let template = f.templates[0].clone();
+
+    for (title, owner) in [("Alice's", "alice"), ("Bob's", "bob")] {
+        f.db.create_conversation(&Conversation {
+            title: title.to_string(),
+            participants: vec![template.clone()],
+            user_id: Some(owner.to_string()),
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+    }
+
+    let titles = |summaries: Vec<cyrup::view_model::conversation::ConversationSummary>| {
+        let mut titles: Vec<_> = summaries.into_iter().map(|s| s.title).collect();
+        titles.sort();
+        titles
+    };
+
+    // Unowned fixture conversations are shared by every account
+    assert_eq!(
+        titles(f.db.list_conversations_for_user("alice").await.unwrap()),
+        ["Alice's", "Group", "Solo"]
+    );
+    assert_eq!(
+        titles(f.db.list_recent_conversations("bob", 10).await.unwrap()),
+        ["Bob's", "Group", "Solo"]
+    );
+    assert_eq!(f.db.list_conversations().await.unwrap().len(), 4);
+
+    let alices = f.db.list_conversations_for_user("alice").await.unwrap();
+    let id = &alices.iter().find(|s| s.title == "Alice's").unwrap().id;
+    let conversation = f.db.get_conversation(id).await.unwrap();
+    assert_eq!(conversation.user_id.as_deref(), Some("alice"));
+}
+
+#[tokio::test]
+async fn test_empty_conversation_preview() {
+    let f = FixtureBuilder::new()
+        .template("Solo")
+        .conversation("Empty", &[0])
+        .build()
+        .await;
+
+    let summaries = f.db.list_conversations().await.unwrap();
+    assert_eq!(summaries[0].last_message_preview, "No messages yet");
+    assert_eq!(summaries[0].unread_count, 0);
+}
+
+#[tokio::test]
+async fn test_update_conversation_summary() {
+    let f = fixtures::standard().await;
+
+    f.db.update_conversation_summary(&f.conversations[0], "A greeting", "Hello")
+        .await
+        .unwrap();
+
+    let conversation = f.db.get_conversation(&f.conversations[0]).await.unwrap();
+    assert_eq!(conversation.summary, "A greeting");
+    assert_eq!(conversation.title, "Hello");
+}
+
+#[tokio::test]
+async fn test_update_agent_session() {
+    let f = fixtures::standard().await;
+
+    f.db.update_agent_session(&f.conversations[1], &f.templates[1], "session-1")
+        .await
+        .unwrap();
+
+    let conversation = f.db.get_conversation(&f.conversations[1]).await.unwrap();
+    assert_eq!(conversation.agent_sessions.len(), 1);
+    assert!(conversation.agent_sessions.values().any(|s| s == "session-1"));
+
+    let unknown = RecordId::new("agent_template", "missing");
+    assert!(
+        f.db.update_agent_session(&f.conversations[1], &unknown, "session-2")
+            .await
+            .is_err()
+    );
+}
+
+#[tokio::test]
+async fn test_sign_out_clears_only_the_accounts_sessions() {
+    let f = fixtures::standard().await;
+    let owned =
+        f.db.create_conversation(&Conversation {
+            title: "Alice's".to_string(),
+            participants: vec![f.templates[0].clone()],
+            user_id: Some("alice".to_string()),
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+
+    for conversation in [&owned, &f.conversations[0]] {
+        f.db.update_agent_session(conversation, &f.templates[0], "session")
+            .await
+            .unwrap();
+    }
+
+    assert_eq!(
+        f.db.clear_agent_sessions_for_user("alice").await.unwrap(),
+        [owned.clone()]
+    );
+    assert!(f.db.clear_agent_sessions_for_user("alice").await.unwrap().is_empty());
+
+    let owned = f.db.get_conversation(&owned).await.unwrap();
+    assert!(owned.agent_sessions.is_empty());
+    // Shared conversations keep their agents for the other accounts
+    let shared = f.db.get_conversation(&f.conversations[0]).await.unwrap();
+    assert_eq!(shared.agent_sessions.len(), 1);
+}
+
+#[tokio::test]
+async fn test_add_participant_is_idempotent() {
+    let f = fixtures::standard().await;
+
+    f.db.add_participant(&f.conversations[0], &f.templates[1])
+        .await
+        .unwrap();
+    f.db.add_participant(&f.conversations[0], &f.templates[1])
+        .await
+        .unwrap();
+
+    let conversation = f.db.get_conversation(&f.conversations[0]).await.unwrap();
+    assert_eq!(conversation.participants, f.templates);
+
+    let unknown = RecordId::new("agent_template", "missing");
+    assert!(f.db.add_participant(&f.conversations[0], &unknown).await.is_err());
+}
+
+#[tokio::test]
+async fn test_update_last_message_at_reorders_list() {
+    let f = fixtures::standard().await;
+
+    let now: Datetime = Utc::now().into();
+    f.db.update_last_message_at(&f.conversations[0], now)
+        .await
+        .unwrap();
+
+    let summaries = f.db.list_conversations().await.unwrap();
+    assert_eq!(summaries[0].id, f.conversations[0]);
+}
+
+// ============================================================================
+// Messages
+// ============================================================================
+
+#[tokio::test]
+async fn test_insert_message_bumps_conversation() {
+    let f = fixtures::standard().await;
+
+    let timestamp: Datetime = Utc::now().into();
+    let id = f
+        .db
+        .insert_message(&Message {
+            conversation_id: f.conversations[0].clone(),
+            author: "user".to_string(),
+            content: "one more thing".to_string(),
+            timestamp,
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+
+    assert_eq!(f.db.get_message(&id).await.unwrap().content, "one more thing");
+    let conversation = f.db.get_conversation(&f.conversations[0]).await.unwrap();
+    assert_eq!(conversation.last_message_at, timestamp);
+}
+
+#[tokio::test]
+async fn test_get_message_not_found() {
+    let db = Database::in_memory().await.unwrap();
+    assert!(
+        db.get_message(&RecordId::new("message", "missing"))
+            .await
+            .is_err()
+    );
+}
+
+#[tokio::test]
+async fn test_history_queries_hide_deleted_messages() {
+    let f = fixtures::standard().await;
+
+    f.db.delete_message(&f.messages[3]).await.unwrap();
+
+    let all = f.db.get_all_messages(&f.conversations[0]).await.unwrap();
+    let ids: Vec<_> = all.iter().map(|m| m.id.clone()).collect();
+    assert_eq!(ids, f.messages[..3]);
+
+    let recent = f.db.get_recent_messages(&f.conversations[0]).await.unwrap();
+    assert_eq!(recent.len(), 3);
+
+    // Soft delete keeps the record
+    assert!(f.db.get_message(&f.messages[3]).await.unwrap().deleted);
+}
+
+#[tokio::test]
+async fn test_recent_messages_respect_token_budget() {
+    let mut builder = FixtureBuilder::new().template("Chatty").conversation("Long", &[0]);
+    for i in 0..15 {
+        builder = builder.human(0, &format!("message {i}"));
+    }
+    let f = builder.build().await;
+
+    // The smallest possible budget clamps to 10 messages
+    let mut config = f.db.token_budget_config();
+    config.max_tokens = 0;
+    f.db.set_token_budget_config(config);
+
+    let recent = f.db.get_recent_messages(&f.conversations[0]).await.unwrap();
+    assert_eq!(recent.len(), 10);
+    assert_eq!(recent[0].content, "message 0");
+}
+
+#[tokio::test]
+async fn test_keyset_pagination() {
+    let mut builder = FixtureBuilder::new().template("Pager").conversation("Paged", &[0]);
+    for i in 0..7 {
+        builder = builder.human(0, &format!("page {i}"));
+    }
+    let f = builder.build().await;
+    let conversation = &f.conversations[0];
+
+    let newest = f.db.get_messages_before(conversation, None, 3).await.unwrap();
+    assert!(newest.has_more);
+    assert_eq!(newest.messages.first().unwrap().id, f.messages[4]);
+    assert_eq!(newest.messages.last().unwrap().id, f.messages[6]);
+
+    let cursor = newest.first_cursor().unwrap();
+    let older = f
+        .db
+        .get_messages_before(conversation, Some(&cursor), 3)
+        .await
+        .unwrap();
+    assert!(older.has_more);
+    assert_eq!(older.messages.first().unwrap().id, f.messages[1]);
+
+    let cursor = older.first_cursor().unwrap();
+    let oldest = f
+        .db
+        .get_messages_before(conversation, Some(&cursor), 3)
+        .await
+        .unwrap();
+    assert!(!oldest.has_more);
+    assert_eq!(oldest.messages.len(), 1);
+
+    let cursor = oldest.last_cursor().unwrap();
+    let forward = f
+        .db
+        .get_messages_after(conversation, Some(&cursor), 10)
+        .await
+        .unwrap();
+    assert!(!forward.has_more);
+    assert_eq!(forward.messages.len(), 6);
+
+    let (before, after) = f.db.get_messages_around(&f.messages[3], 2).await.unwrap();
+    let before: Vec<_> = before.messages.iter().map(|m| m.id.clone()).collect();
+    let after: Vec<_> = after.messages.iter().map(|m| m.id.clone()).collect();
+    assert_eq!(before, f.messages[1..=3]);
+    assert_eq!(after, f.messages[4..=5]);
+}
+
+#[tokio::test]
+async fn test_deleted_message_cannot_be_jumped_to() {
+    let f = fixtures::standard().await;
+    f.db.delete_message(&f.messages[3]).await.unwrap();
+
+    assert!(f.db.get_messages_around(&f.messages[3], 2).await.is_err());
+}
+
+#[tokio::test]
+async fn test_search_messages_is_scoped_to_conversation() {
+    let f = fixtures::standard().await;
+
+    let hits = f
+        .db
+        .search_messages(&f.conversations[0], "important")
+        .await
+        .unwrap();
+    assert_eq!(hits.len(), 1);
+    assert_eq!(hits[0].id, f.messages[2]);
+
+    let none = f
+        .db
+        .search_messages(&f.conversations[1], "important")
+        .await
+        .unwrap();
+    assert!(none.is_empty());
+}
+
+#[tokio::test]
+async fn test_update_message_content() {
+    let f = fixtures::standard().await;
+
+    f.db.update_message_content(&f.messages[1], "edited".to_string())
+        .await
+        .unwrap();
+
+    assert_eq!(f.db.get_message(&f.messages[1]).await.unwrap().content, "edited");
+}
+
+#[tokio::test]
+async fn test_replies_link_to_parent() {
+    let f = fixtures::standard().await;
+
+    let reply = f.db.get_message(&f.messages[5]).await.unwrap();
+    assert_eq!(reply.in_reply_to, Some(f.messages[4].clone()));
+    assert_eq!(reply.author_type, AuthorType::Agent);
+}
+
+// ============================================================================
+// Unread semantics
+// ============================================================================
+
+#[tokio::test]
+async fn test_unread_count_excludes_deleted() {
+    let f = fixtures::standard().await;
+
+    assert_eq!(f.db.get_unread_count(&f.conversations[0]).await.unwrap(), 2);
+
+    f.db.delete_message(&f.messages[1]).await.unwrap();
+    assert_eq!(f.db.get_unread_count(&f.conversations[0]).await.unwrap(), 1);
+    assert_eq!(f.db.get_unread_count(&f.conversations[1]).await.unwrap(), 0);
+}
+
+#[tokio::test]
+async fn test_unread_messages_are_agent_only_and_newest_first() {
+    let f = FixtureBuilder::new()
+        .template("Agent")
+        .conversation("Mixed", &[0])
+        .human(0, "unread human")
+        .unread()
+        .agent(0, "first agent")
+        .unread()
+        .agent(0, "second agent")
+        .unread()
+        .build()
+        .await;
+
+    let unread = f.db.get_unread_messages(USER).await.unwrap();
+    let contents: Vec<_> = unread.iter().map(|m| m.content.as_str()).collect();
+    assert_eq!(contents, ["second agent", "first agent"]);
+}
+
+#[tokio::test]
+async fn test_mark_read_clears_only_that_conversation() {
+    let f = FixtureBuilder::new()
+        .template("Agent")
+        .conversation("A", &[0])
+        .conversation("B", &[0])
+        .agent(0, "a")
+        .unread()
+        .agent(1, "b")
+        .unread()
+        .build()
+        .await;
+
+    f.db.mark_messages_read(&f.conversations[0]).await.unwrap();
+    assert_eq!(f.db.get_unread_count(&f.conversations[0]).await.unwrap(), 0);
+    assert_eq!(f.db.get_unread_count(&f.conversations[1]).await.unwrap(), 1);
+
+    f.db.mark_messages_as_read(&f.conversations[1]).await.unwrap();
+    assert_eq!(f.db.get_unread_count(&f.conversations[1]).await.unwrap(), 0);
+    assert!(f.db.get_unread_messages(USER).await.unwrap().is_empty());
+}
+
+// ============================================================================
+// Pin semantics
+// ============================================================================
+
+#[tokio::test]
+async fn test_pin_limit_is_five_per_conversation() {
+    let mut builder = FixtureBuilder::new()
+        .template("Agent")
+        .conversation("Pins", &[0])
+        .conversation("Other", &[0]);
+    for i in 0..6 {
+        builder = builder.human(0, &format!("pin {i}"));
+    }
+    let f = builder.human(1, "elsewhere").build().await;
+    let conversation = &f.conversations[0];
+
+    for id in &f.messages[..5] {
+        assert!(f.db.toggle_pin_message(id, conversation).await.unwrap());
+    }
+
+    let err = f
+        .db
+        .toggle_pin_message(&f.messages[5], conversation)
+        .await
+        .unwrap_err();
+    assert!(err.contains("Maximum 5"));
+    assert!(f.db.pin_message(&f.messages[5]).await.is_err());
+
+    // The limit is per conversation
+    f.db.pin_message(&f.messages[6]).await.unwrap();
+
+    // Unpinning frees a slot
+    assert!(!f.db.toggle_pin_message(&f.messages[0], conversation).await.unwrap());
+    f.db.pin_message(&f.messages[5]).await.unwrap();
+
+    let pinned = f.db.get_pinned_messages(conversation).await.unwrap();
+    let ids: Vec<_> = pinned.iter().map(|m| m.id.clone()).collect();
+    assert_eq!(ids, f.messages[1..=5]);
+}
+
+#[tokio::test]
+async fn test_pin_and_unpin() {
+    let f = fixtures::standard().await;
+
+    // Pinning an already pinned message is a no-op
+    f.db.pin_message(&f.messages[2]).await.unwrap();
+    assert!(f.db.get_message(&f.messages[2]).await.unwrap().pinned);
+
+    f.db.unpin_message(&f.messages[2]).await.unwrap();
+    assert!(
+        f.db.get_pinned_messages(&f.conversations[0])
+            .await
+            .unwrap()
+            .is_empty()
+    );
+}
+
+#[tokio::test]
+async fn test_deleted_pins_are_hidden() {
+    let f = fixtures::standard().await;
+
+    f.db.delete_message(&f.messages[2]).await.unwrap();
+    assert!(
+        f.db.get_pinned_messages(&f.conversations[0])
+            .await
+            .unwrap()
+            .is_empty()
+    );
+}
+
+// ============================================================================
+// Reactions and bookmarks
+// ============================================================================
+
+#[tokio::test]
+async fn test_reactions_are_unique_per_user_and_emoji() {
+    let f = fixtures::standard().await;
+    let message = key(&f.messages[2]);
+
+    let first = f.db.add_reaction(&message, "other-user", "👍").await.unwrap();
+    let again = f.db.add_reaction(&message, "other-user", "👍").await.unwrap();
+    assert_eq!(first, again);
+
+    f.db.add_reaction(&message, USER, "🎯").await.unwrap();
+
+    let counts = f.db.get_reaction_counts(&message).await.unwrap();
+    assert_eq!(counts[0], ("👍".to_string(), 2));
+    assert!(counts.contains(&("🎯".to_string(), 1)));
+    assert_eq!(f.db.get_message_reactions(&message).await.unwrap().len(), 3);
+
+    f.db.remove_reaction(&message, USER, "🎯").await.unwrap();
+    f.db.remove_reaction_by_id(&first).await.unwrap();
+
+    let reactions = f.db.get_message_reactions(&message).await.unwrap();
+    assert_eq!(reactions.len(), 1);
+    assert_eq!(reactions[0].user_id, USER);
+}
+
+#[tokio::test]
+async fn test_bookmarks() {
+    let f = fixtures::standard().await;
+    let bookmarked = key(&f.messages[2]);
+    let other = key(&f.messages[5]);
+
+    assert!(f.db.is_bookmarked(USER, &bookmarked).await.unwrap());
+    assert!(!f.db.is_bookmarked(USER, &other).await.unwrap());
+    assert!(!f.db.is_bookmarked("other-user", &bookmarked).await.unwrap());
+
+    f.db.add_bookmark(USER, &other).await.unwrap();
+    let messages = f.db.get_bookmarked_messages(USER).await.unwrap();
+    let ids: Vec<_> = messages.iter().map(|m| m.id.clone()).collect();
+    assert_eq!(ids.len(), 2);
+    assert!(ids.contains(&f.messages[2]) && ids.contains(&f.messages[5]));
+
+    f.db.remove_bookmark(USER, &bookmarked).await.unwrap();
+    f.db.unbookmark_message(USER, &other).await.unwrap();
+    assert!(f.db.get_bookmarked_messages(USER).await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn test_legacy_user_data_is_adopted_by_first_account() {
+    let f = fixtures::standard().await;
+    let message = key(&f.messages[5]);
+    f.db.add_bookmark(LEGACY_USER_ID, &message).await.unwrap();
+    f.db.add_reaction(&message, LEGACY_USER_ID, "🎉").await.unwrap();
+
+    f.db.adopt_legacy_user_data("alice", "alice-local").await.unwrap();
+    // A second account finds nothing left to adopt
+    f.db.adopt_legacy_user_data("bob", "bob-local").await.unwrap();
+
+    assert!(f.db.is_bookmarked("alice", &message).await.unwrap());
+    assert!(f.db.get_bookmarked_messages(LEGACY_USER_ID).await.unwrap().is_empty());
+    assert!(f.db.get_bookmarked_messages("bob").await.unwrap().is_empty());
+    let reactions = f.db.get_message_reactions(&message).await.unwrap();
+    assert!(reactions.iter().any(|r| r.emoji == "🎉" && r.user_id == "alice"));
+    assert!(reactions.iter().all(|r| r.user_id != LEGACY_USER_ID));
+}
+
+#[tokio::test]
+async fn test_provider_local_user_data_moves_to_account_key() {
+    let f = fixtures::standard().await;
+    let message = key(&f.messages[5]);
+    f.db.add_bookmark("1234", &message).await.unwrap();
+    f.db.record_auth_event("1234", "github", AuthEventKind::SignIn, false, None)
+        .await
+        .unwrap();
+
+    f.db.adopt_legacy_user_data("github:1234", "1234").await.unwrap();
+
+    assert!(f.db.is_bookmarked("github:1234", &message).await.unwrap());
+    assert!(f.db.get_bookmarked_messages("1234").await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn test_provider_local_user_data_stays_with_its_provider() {
+    let f = fixtures::standard().await;
+    let message = key(&f.messages[5]);
+    f.db.add_bookmark("1234", &message).await.unwrap();
+    f.db.record_auth_event("1234", "github", AuthEventKind::SignIn, false, None)
+        .await
+        .unwrap();
+    f.db.record_auth_event("1234", "google", AuthEventKind::SignIn, false, None)
+        .await
+        .unwrap();
+
+    // Same local id, other provider: nothing moves
+    f.db.adopt_legacy_user_data("google:1234", "1234").await.unwrap();
+    assert!(f.db.is_bookmarked("1234", &message).await.unwrap());
+    assert!(
+        f.db.get_bookmarked_messages("google:1234")
+            .await
+            .unwrap()
+            .is_empty()
+    );
+
+    f.db.adopt_legacy_user_data("github:1234", "1234").await.unwrap();
+    assert!(f.db.is_bookmarked("github:1234", &message).await.unwrap());
+}
+
+// ============================================================================
+// Cascades and integrity
+// ============================================================================
+
+#[tokio::test]
+async fn test_deleting_conversation_cascades_to_messages() {
+    let f = fixtures::standard().await;
+
+    f.db.client()
+        .query("DELETE $conversation")
+        .bind(("conversation", f.conversations[0].clone()))
+        .await
+        .unwrap();
+
+    for id in &f.messages[..4] {
+        assert!(f.db.get_message(id).await.is_err());
+    }
+    assert!(f.db.get_message(&f.messages[4]).await.is_ok());
+
+    // Reactions and bookmarks on the removed messages go with them
+    assert!(
+        f.db.get_message_reactions(&key(&f.messages[2]))
+            .await
+            .unwrap()
+            .is_empty()
+    );
+    assert!(f.db.get_bookmarked_messages(USER).await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn test_integrity_detects_and_repairs_dangling_replies() {
+    let f = fixtures::standard().await;
+    assert!(f.db.check_integrity().await.unwrap().is_clean());
+
+    // Hard delete bypasses the soft-delete path and orphans the reply
+    f.db.client()
+        .query("DELETE $message")
+        .bind(("message", f.messages[4].clone()))
+        .await
+        .unwrap();
+
+    let report = f.db.check_integrity().await.unwrap();
+    assert_eq!(report.dangling_replies, [f.messages[5].clone()]);
+
+    assert_eq!(f.db.repair_integrity(&report).await.unwrap(), 1);
+    assert_eq!(f.db.get_message(&f.messages[5]).await.unwrap().in_reply_to, None);
+    assert!(f.db.check_integrity().await.unwrap().is_clean());
+}
+
+#[tokio::test]
+async fn test_integrity_unpins_oldest_excess_pins() {
+    let mut builder = FixtureBuilder::new().template("Agent").conversation("Pins", &[0]);
+    for i in 0..7 {
+        builder = builder.human(0, &format!("pin {i}")).pinned();
+    }
+    let f = builder.build().await;
+
+    let report = f.db.check_integrity().await.unwrap();
+    assert_eq!(report.excess_pins.len(), 2);
+
+    f.db.repair_integrity(&report).await.unwrap();
+
+    let pinned = f.db.get_pinned_messages(&f.conversations[0]).await.unwrap();
+    let ids: Vec<_> = pinned.iter().map(|m| m.id.clone()).collect();
+    assert_eq!(ids, f.messages[2..]);
+}
+
+#[tokio::test]
+async fn test_in_memory_databases_are_isolated() {
+    let a = fixtures::standard().await;
+    let b = Database::in_memory().await.unwrap();
+
+    assert_eq!(a.db.list_conversations().await.unwrap().len(), 2);
+    assert!(b.list_conversations().await.unwrap().is_empty());
+    assert!(b.data_path().is_none());
+
+    // Timestamps in fixtures are strictly ordered in declaration order
+    let all = a.db.get_all_messages(&a.conversations[0]).await.unwrap();
+    assert!(all.windows(2).all(|w| w[0].timestamp < w[1].timestamp));
+    assert!(*all[0].timestamp < Utc::now() - Duration::hours(1));
+}
@@ -0,0 +1,126 @@
+//! Tests for scheduled prompt storage and due-run queries
+
+use super::fixtures;
+use chrono::{Duration, Utc};
+use cyrup::view_model::schedule::{Schedule, ScheduleTrigger};
+
+fn hourly(conversation: &surrealdb_types::RecordId, prompt: &str) -> Schedule {
+    Schedule {
+        conversation_id: conversation.clone(),
+        prompt: prompt.to_string(),
+        interval_secs: Some(3600),
+        cron: None,
+        ..Default::default()
+    }
+}
+
+#[tokio::test]
+async fn test_schedule_crud() {
+    let fixture = fixtures::standard().await;
+    let db = &fixture.db;
+
+    let id = db
+        .create_schedule(&hourly(&fixture.conversations[0], "summarize"))
+        .await
+        .unwrap();
+
+    let mut schedule = db.get_schedule(&id).await.unwrap();
+    assert_eq!(schedule.prompt, "summarize");
+    assert!(schedule.next_run_at > Utc::now() + Duration::minutes(59));
+    assert!(!schedule.paused);
+
+    schedule.set_trigger(ScheduleTrigger::Cron("0 9 * * MON-FRI".to_string()));
+    schedule.agent_id = Some(fixture.templates[0].clone());
+    db.update_schedule(&schedule).await.unwrap();
+
+    let updated = db.get_schedule(&id).await.unwrap();
+    assert_eq!(updated.cron.as_deref(), Some("0 9 * * MON-FRI"));
+    assert_eq!(updated.interval_secs, None);
+    assert_eq!(updated.agent_id, Some(fixture.templates[0].clone()));
+
+    assert_eq!(db.list_schedules().await.unwrap().len(), 1);
+    db.delete_schedule(&id).await.unwrap();
+    assert!(db.list_schedules().await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn test_invalid_schedules_are_rejected() {
+    let fixture = fixtures::standard().await;
+    let conversation = &fixture.conversations[0];
+
+    let mut bad_cron = hourly(conversation, "x");
+    bad_cron.set_trigger(ScheduleTrigger::Cron("every morning".to_string()));
+    let mut too_fast = hourly(conversation, "x");
+    too_fast.set_trigger(ScheduleTrigger::Interval(5));
+    let empty_prompt = hourly(conversation, "   ");
+
+    for schedule in [bad_cron, too_fast, empty_prompt] {
+        assert!(fixture.db.create_schedule(&schedule).await.is_err());
+    }
+}
+
+#[tokio::test]
+async fn test_due_runs_collapse_missed_occurrences() {
+    let fixture = fixtures::standard().await;
+    let db = &fixture.db;
+
+    let id = db
+        .create_schedule(&hourly(&fixture.conversations[0], "standup"))
+        .await
+        .unwrap();
+    let schedule = db.get_schedule(&id).await.unwrap();
+
+    assert!(db.due_schedules(Utc::now()).await.unwrap().is_empty());
+
+    // Asleep for a day: 24 runs missed, one is due
+    let wake = Utc::now() + Duration::days(1);
+    let due = db.due_schedules(wake).await.unwrap();
+    assert_eq!(due.len(), 1);
+
+    db.record_schedule_run(&schedule, wake).await.unwrap();
+    assert!(db.due_schedules(wake).await.unwrap().is_empty());
+
+    let advanced = db.get_schedule(&id).await.unwrap();
+    assert_eq!(advanced.next_run_at, wake + Duration::hours(1));
+    assert_eq!(advanced.last_run_at, Some(wake));
+}
+
+#[tokio::test]
+async fn test_paused_schedules_are_not_due() {
+    let fixture = fixtures::standard().await;
+    let db = &fixture.db;
+
+    let id = db
+        .create_schedule(&hourly(&fixture.conversations[0], "standup"))
+        .await
+        .unwrap();
+    db.set_schedule_paused(&id, true).await.unwrap();
+
+    let later = Utc::now() + Duration::days(1);
+    assert!(db.due_schedules(later).await.unwrap().is_empty());
+
+    db.set_schedule_paused(&id, false).await.unwrap();
+    assert_eq!(db.due_schedules(later).await.unwrap().len(), 1);
+}
+
+#[tokio::test]
+async fn test_run_errors_are_recorded() {
+    let fixture = fixtures::standard().await;
+    let db = &fixture.db;
+
+    let id = db
+        .create_schedule(&hourly(&fixture.conversations[0], "standup"))
+        .await
+        .unwrap();
+
+    db.set_schedule_error(&id, Some("agent unavailable".to_string()))
+        .await
+        .unwrap();
+    assert_eq!(
+        db.get_schedule(&id).await.unwrap().last_error.as_deref(),
+        Some("agent unavailable")
+    );
+
+    db.set_schedule_error(&id, None).await.unwrap();
+    assert_eq!(db.get_schedule(&id).await.unwrap().last_error, None);
+}
@@ -0,0 +1,75 @@
+//! Tests for prompt snippet storage
+
+use super::fixtures;
+use cyrup::view_model::snippet::{PromptSnippet, SnippetArgs};
+
+fn snippet(name: &str, body: &str) -> PromptSnippet {
+    PromptSnippet {
+        name: name.to_string(),
+        body: body.to_string(),
+        ..Default::default()
+    }
+}
+
+#[tokio::test]
+async fn test_snippet_crud() {
+    let fixture = fixtures::standard().await;
+    let db = &fixture.db;
+
+    db.create_snippet(&snippet("review", "Review {{branch}} for {{focus=bugs}}"))
+        .await
+        .unwrap();
+    db.create_snippet(&snippet("eli5", "Explain like I'm five"))
+        .await
+        .unwrap();
+
+    let names: Vec<String> = db
+        .list_snippets()
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|s| s.name)
+        .collect();
+    assert_eq!(names, ["eli5", "review"]);
+
+    let mut review = db.get_snippet_by_name("review").await.unwrap().unwrap();
+    assert_eq!(
+        review.expand(&SnippetArgs::parse("main")).unwrap(),
+        "Review main for bugs"
+    );
+
+    review.description = "Code review".to_string();
+    db.update_snippet(&review).await.unwrap();
+    let updated = db.get_snippet_by_name("review").await.unwrap().unwrap();
+    assert_eq!(updated.description, "Code review");
+
+    assert!(db.delete_snippet("review").await.unwrap());
+    assert!(!db.delete_snippet("review").await.unwrap());
+    assert!(db.get_snippet_by_name("review").await.unwrap().is_none());
+}
+
+#[tokio::test]
+async fn test_snippet_names_are_unique() {
+    let fixture = fixtures::standard().await;
+    let db = &fixture.db;
+
+    db.create_snippet(&snippet("todo", "List the TODOs"))
+        .await
+        .unwrap();
+    let err = db
+        .create_snippet(&snippet("todo", "Something else"))
+        .await
+        .unwrap_err();
+    assert_eq!(err, "A snippet named /todo already exists");
+}
+
+#[tokio::test]
+async fn test_snippet_validation() {
+    let fixture = fixtures::standard().await;
+    let db = &fixture.db;
+
+    assert!(db.create_snippet(&snippet("has space", "x")).await.is_err());
+    assert!(db.create_snippet(&snippet("", "x")).await.is_err());
+    assert!(db.create_snippet(&snippet("empty", "   ")).await.is_err());
+    assert!(db.list_snippets().await.unwrap().is_empty());
+}
@@ -0,0 +1,101 @@
+//! Tests for transcript export and import
+
+use super::fixtures;
+use cyrup::database::Database;
+use cyrup::view_model::message::AuthorType;
+use cyrup::view_model::transcript::{TRANSCRIPT_VERSION, Transcript};
+
+#[tokio::test]
+async fn test_export_transcript() {
+    let fixture = fixtures::standard().await;
+    let db = &fixture.db;
+
+    let transcript = db
+        .export_transcript(&fixture.conversations[1])
+        .await
+        .unwrap();
+
+    assert_eq!(transcript.version, TRANSCRIPT_VERSION);
+    assert_eq!(transcript.title, "Group");
+    let agents: Vec<&str> = transcript.agents.iter().map(|a| a.name.as_str()).collect();
+    assert_eq!(agents, ["Researcher", "Reviewer"]);
+    let contents: Vec<&str> = transcript
+        .messages
+        .iter()
+        .map(|m| m.content.as_str())
+        .collect();
+    assert_eq!(contents, ["what do you both think?", "looks good to me"]);
+
+    let markdown = transcript.to_markdown();
+    assert!(markdown.starts_with("# Group\n"));
+    assert!(markdown.contains("looks good to me"));
+}
+
+#[tokio::test]
+async fn test_import_round_trip_into_fresh_database() {
+    let fixture = fixtures::standard().await;
+    let transcript = fixture
+        .db
+        .export_transcript(&fixture.conversations[0])
+        .await
+        .unwrap();
+
+    // Through JSON, as the CLI does
+    let json = serde_json::to_string(&transcript).unwrap();
+    let parsed: Transcript = serde_json::from_str(&json).unwrap();
+
+    let target = Database::in_memory().await.unwrap();
+    let id = target.import_transcript(&parsed).await.unwrap();
+
+    let conversation = target.get_conversation(&id).await.unwrap();
+    assert_eq!(conversation.title, "Solo");
+    assert!(conversation.agent_sessions.is_empty());
+
+    let template = target
+        .get_template(&conversation.participants[0])
+        .await
+        .unwrap();
+    assert_eq!(template.name, "Researcher");
+
+    let messages = target.get_all_messages(&id).await.unwrap();
+    assert_eq!(messages.len(), transcript.messages.len());
+    assert_eq!(messages[1].author_type, AuthorType::Agent);
+    assert!(messages.iter().any(|m| m.pinned));
+    assert!(messages.iter().all(|m| !m.unread));
+    assert_eq!(target.export_transcript(&id).await.unwrap(), transcript);
+}
+
+#[tokio::test]
+async fn test_import_reuses_templates_by_name() {
+    let fixture = fixtures::standard().await;
+    let db = &fixture.db;
+
+    let transcript = db
+        .export_transcript(&fixture.conversations[1])
+        .await
+        .unwrap();
+    let id = db.import_transcript(&transcript).await.unwrap();
+
+    assert_eq!(db.list_templates().await.unwrap().len(), 2);
+    assert_eq!(
+        db.get_conversation(&id).await.unwrap().participants,
+        fixture.templates
+    );
+}
+
+#[tokio::test]
+async fn test_import_rejects_unsupported_transcripts() {
+    let fixture = fixtures::standard().await;
+    let db = &fixture.db;
+
+    let mut transcript = db
+        .export_transcript(&fixture.conversations[0])
+        .await
+        .unwrap();
+    transcript.version = TRANSCRIPT_VERSION + 1;
+    assert!(db.import_transcript(&transcript).await.is_err());
+
+    transcript.version = TRANSCRIPT_VERSION;
+    transcript.agents.clear();
+    assert!(db.import_transcript(&transcript).await.is_err());
+}
@@ -1,2 +1,4 @@
-// Unit tests module
-pub mod i18n;
\ No newline at end of file
+pub mod database;
+pub mod i18n;
+pub mod notifications;
+pub mod services;
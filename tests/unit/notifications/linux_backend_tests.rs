@@ -0,0 +1,234 @@
+//! Tests for the freedesktop D-Bus notification backend
+//!
+//! The backend talks to an in-process mock `org.freedesktop.Notifications`
+//! server over a peer-to-peer socket pair, so no session bus is required.
+
+use cyrup::notifications::backend::{NotificationRequest, PlatformBackend};
+use cyrup::notifications::linux_backend::LinuxBackend;
+use cyrup::notifications::{NotificationContent, NotificationEvent, Urgency};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use zbus::object_server::SignalEmitter;
+use zbus::zvariant::OwnedValue;
+
+const PATH: &str = "/org/freedesktop/Notifications";
+
+/// One `Notify` call as seen by the mock server
+#[derive(Debug, Clone)]
+struct Received {
+    replaces_id: u32,
+    summary: String,
+    body: String,
+    actions: Vec<String>,
+    urgency: Option<u8>,
+    expire_timeout: i32,
+}
+
+#[derive(Default)]
+struct MockNotifications {
+    next_id: u32,
+    received: Arc<Mutex<Vec<Received>>>,
+}
+
+#[zbus::interface(name = "org.freedesktop.Notifications")]
+impl MockNotifications {
+    #[allow(clippy::too_many_arguments)]
+    async fn notify(
+        &mut self,
+        _app_name: String,
+        replaces_id: u32,
+        _app_icon: String,
+        summary: String,
+        body: String,
+        actions: Vec<String>,
+        mut hints: HashMap<String, OwnedValue>,
+        expire_timeout: i32,
+    ) -> u32 {
+        let urgency = hints.remove("urgency").and_then(|v| u8::try_from(v).ok());
+
+        self.received.lock().unwrap().push(Received {
+            replaces_id,
+            summary,
+            body,
+            actions,
+            urgency,
+            expire_timeout,
+        });
+
+        if replaces_id != 0 {
+            return replaces_id;
+        }
+        self.next_id += 1;
+        self.next_id
+    }
+
+    async fn close_notification(
+        &self,
+        id: u32,
+        #[zbus(signal_emitter)] emitter: SignalEmitter<'_>,
+    ) -> zbus::fdo::Result<()> {
+        // Reason 3: closed by a call to CloseNotification
+        Self::notification_closed(&emitter, id, 3).await?;
+        Ok(())
+    }
+
+    fn get_capabilities(&self) -> Vec<String> {
+        vec!["actions".to_string(), "body".to_string()]
+    }
+
+    #[zbus(signal)]
+    async fn action_invoked(emitter: &SignalEmitter<'_>, id: u32, action_key: &str)
+    -> zbus::Result<()>;
+
+    #[zbus(signal)]
+    async fn notification_closed(
+        emitter: &SignalEmitter<'_>,
+        id: u32,
+        reason: u32,
+    ) -> zbus::Result<()>;
+}
+
+/// Backend connected to a fresh mock server
+async fn connect() -> (LinuxBackend, zbus::Connection, Arc<Mutex<Vec<Received>>>) {
+    let received = Arc::new(Mutex::new(Vec::new()));
+    let mock = MockNotifications {
+        next_id: 0,
+        received: received.clone(),
+    };
+
+    let guid = zbus::Guid::generate();
+    let (client_stream, server_stream) = tokio::net::UnixStream::pair().unwrap();
+
+    let (client, server) = futures_util::try_join!(
+        zbus::connection::Builder::unix_stream(client_stream)
+            .p2p()
+            .build(),
+        zbus::connection::Builder::unix_stream(server_stream)
+            .server(guid)
+            .unwrap()
+            .p2p()
+            .serve_at(PATH, mock)
+            .unwrap()
+            .build(),
+    )
+    .unwrap();
+
+    (LinuxBackend::with_connection(client), server, received)
+}
+
+async fn next_event(events: &flume::Receiver<NotificationEvent>) -> NotificationEvent {
+    tokio::time::timeout(Duration::from_secs(2), events.recv_async())
+        .await
+        .expect("Timed out waiting for notification event")
+        .expect("Event channel closed")
+}
+
+#[tokio::test]
+async fn test_delivers_content_actions_and_urgency() {
+    let (backend, _server, received) = connect().await;
+
+    let content = NotificationContent::new("Agent Response", "Done")
+        .with_urgency(Urgency::Critical)
+        .with_action("open", "Open conversation");
+    backend
+        .deliver_notification(&NotificationRequest::new("n1", content))
+        .await
+        .unwrap();
+
+    let calls = received.lock().unwrap().clone();
+    assert_eq!(calls.len(), 1);
+    assert_eq!(calls[0].summary, "Agent Response");
+    assert_eq!(calls[0].body, "Done");
+    assert_eq!(calls[0].actions, ["open", "Open conversation"]);
+    assert_eq!(calls[0].urgency, Some(2));
+    assert_eq!(calls[0].expire_timeout, 0);
+    assert_eq!(calls[0].replaces_id, 0);
+}
+
+#[tokio::test]
+async fn test_redelivery_replaces_existing_notification() {
+    let (backend, _server, received) = connect().await;
+
+    for body in ["Thinking…", "Half way", "Done"] {
+        backend
+            .deliver_notification(&NotificationRequest::new(
+                "stream",
+                NotificationContent::new("Agent", body),
+            ))
+            .await
+            .unwrap();
+    }
+    backend
+        .deliver_notification(&NotificationRequest::new(
+            "other",
+            NotificationContent::new("Agent", "Separate"),
+        ))
+        .await
+        .unwrap();
+
+    let replaces: Vec<u32> = received
+        .lock()
+        .unwrap()
+        .iter()
+        .map(|r| r.replaces_id)
+        .collect();
+    assert_eq!(replaces, [0, 1, 1, 0]);
+}
+
+#[tokio::test]
+async fn test_action_invoked_is_reported() {
+    let (backend, server, _received) = connect().await;
+    let events = backend.subscribe().expect("Linux backend reports events");
+
+    let content = NotificationContent::new("Agent", "Done").with_action("open", "Open");
+    backend
+        .deliver_notification(&NotificationRequest::new("n1", content))
+        .await
+        .unwrap();
+
+    let iface = server
+        .object_server()
+        .interface::<_, MockNotifications>(PATH)
+        .await
+        .unwrap();
+    MockNotifications::action_invoked(iface.signal_emitter(), 1, "open")
+        .await
+        .unwrap();
+
+    assert_eq!(
+        next_event(&events).await,
+        NotificationEvent::ActionInvoked {
+            notification_id: "n1".to_string(),
+            action_id: "open".to_string(),
+        }
+    );
+}
+
+#[tokio::test]
+async fn test_close_reports_closed_and_forgets_id() {
+    let (backend, _server, received) = connect().await;
+    let events = backend.subscribe().unwrap();
+
+    let request = NotificationRequest::new("n1", NotificationContent::new("Agent", "Done"));
+    backend.deliver_notification(&request).await.unwrap();
+    backend.close_notification("n1").await.unwrap();
+
+    assert_eq!(
+        next_event(&events).await,
+        NotificationEvent::Closed {
+            notification_id: "n1".to_string()
+        }
+    );
+
+    // A closed notification cannot be replaced; the next delivery is new
+    backend.deliver_notification(&request).await.unwrap();
+    assert_eq!(received.lock().unwrap().last().unwrap().replaces_id, 0);
+}
+
+#[tokio::test]
+async fn test_reachable_server_is_authorized() {
+    let (backend, _server, _received) = connect().await;
+    assert!(backend.check_authorization().await.unwrap());
+    assert_eq!(backend.platform_name(), "Linux");
+}
@@ -0,0 +1,392 @@
+//! Tests for agent chat against scripted backends
+//!
+//! Every test subscribes to the conversation's live message query before
+//! sending, so the assertions cover each database write in order (the
+//! same notifications the chat view renders), not just the final state.
+//! Time is paused: script waits advance tokio's clock exactly, which makes
+//! the 100ms / 50 character debouncing deterministic. For the same reason
+//! writes are drained up to an end-of-turn marker rather than with a
+//! timeout, which the paused clock would skip to while the live query is
+//! still delivering.
+
+use super::super::database::fixtures::{self, Fixture};
+use cyrup::database::Database;
+use cyrup::services::agent_chat;
+use cyrup::services::llm::{
+    AgentEvent, BackendResolver, LlmBackend, ScriptStep, ScriptedAgents, ScriptedBackend,
+    TemplateBackends,
+};
+use cyrup::view_model::agent::{AgentBackend, AgentTemplate};
+use cyrup::view_model::message::{AuthorType, Message};
+use futures_util::StreamExt;
+use std::sync::Arc;
+use surrealdb_types::{Action, RecordId, ToSql};
+use tokio::time::Duration;
+
+/// A message write as seen by live query subscribers
+#[derive(Debug, PartialEq, Eq)]
+enum Write {
+    Create(AuthorType, String),
+    Update(String),
+}
+
+fn agent(text: &str) -> Write {
+    Write::Create(AuthorType::Agent, text.to_string())
+}
+
+fn update(text: &str) -> Write {
+    Write::Update(text.to_string())
+}
+
+/// Send `text` to `conversation` and return the message writes it caused
+async fn send(
+    backends: &dyn BackendResolver,
+    db: &Arc<Database>,
+    conversation: &RecordId,
+    text: &str,
+    mentioned: Option<Vec<RecordId>>,
+) -> (Result<(), String>, Vec<Write>) {
+    let mut live = db.live_messages(conversation).await.unwrap();
+
+    // The tests' explicit targets stand for @mentions in the composer
+    let is_mention = mentioned.is_some();
+    let result = agent_chat::send_message_with(
+        backends,
+        db.clone(),
+        conversation.clone(),
+        text.to_string(),
+        mentioned,
+        is_mention,
+        None,
+    )
+    .await;
+
+    // Notifications arrive in commit order: once the marker comes through,
+    // every write of the turn has been seen
+    let marker = db
+        .insert_message(&Message {
+            conversation_id: conversation.clone(),
+            author: "test".to_string(),
+            author_type: AuthorType::System,
+            content: "end of turn".to_string(),
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+
+    let mut writes = Vec::new();
+    loop {
+        let notification = live.next().await.expect("live query ended").unwrap();
+        let message = notification.data;
+        if message.id == marker {
+            break;
+        }
+        writes.push(match notification.action {
+            Action::Create => Write::Create(message.author_type, message.content),
+            Action::Update => Write::Update(message.content),
+            other => panic!("unexpected {:?}", other),
+        });
+    }
+
+    db.client()
+        .query("DELETE $id")
+        .bind(("id", marker))
+        .await
+        .unwrap();
+
+    // Every turn starts with the user's message
+    assert_eq!(
+        writes.first(),
+        Some(&Write::Create(AuthorType::Human, text.to_string()))
+    );
+    (result, writes.split_off(1))
+}
+
+async fn session(db: &Database, conversation: &RecordId, agent: &RecordId) -> Option<String> {
+    db.get_conversation(conversation)
+        .await
+        .unwrap()
+        .agent_sessions
+        .get(&agent.to_sql())
+        .cloned()
+}
+
+fn split(fixture: Fixture) -> (Arc<Database>, Vec<RecordId>, Vec<RecordId>) {
+    (
+        Arc::new(fixture.db),
+        fixture.conversations,
+        fixture.templates,
+    )
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_reply_is_stored_and_session_resumed() {
+    let (db, conversations, templates) = split(fixtures::standard().await);
+
+    let scripted = Arc::new(
+        ScriptedBackend::new()
+            .reply("s1", "Hi from the script")
+            .reply("s1", "Still here"),
+    );
+    let backends: Arc<dyn LlmBackend> = scripted.clone();
+
+    let (result, writes) = send(&backends, &db, &conversations[0], "hello", None).await;
+    assert_eq!(result, Ok(()));
+    assert_eq!(writes, [agent("Hi from the script")]);
+
+    let (_, writes) = send(&backends, &db, &conversations[0], "again", None).await;
+    assert_eq!(writes, [agent("Still here")]);
+    assert_eq!(
+        session(&db, &conversations[0], &templates[0])
+            .await
+            .as_deref(),
+        Some("s1")
+    );
+
+    let requests = scripted.requests();
+    assert_eq!(requests[0].model, "sonnet");
+    assert_eq!(requests[0].session_id, None);
+    assert_eq!(requests[1].session_id.as_deref(), Some("s1"));
+    assert_eq!(requests[1].message, "again");
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_streaming_writes_are_debounced() {
+    let (db, conversations, templates) = split(fixtures::standard().await);
+    let backends: Arc<dyn LlmBackend> = Arc::new(
+        ScriptedBackend::from_json(include_str!("../../fixtures/agent_scripts/debounce.json"))
+            .unwrap(),
+    );
+
+    let (result, writes) = send(&backends, &db, &conversations[0], "hi", None).await;
+    assert_eq!(result, Ok(()));
+
+    let long = ", and this chunk alone is long enough to cross fifty";
+    assert_eq!(
+        writes,
+        [
+            // First chunk is inserted immediately
+            agent("Hel"),
+            // "lo" arrives 10ms later with 2 chars: held back
+            // " there" arrives 110ms after the insert: time-based update
+            update("Hello there"),
+            // 52 chars at once: size-based update
+            update(&format!("Hello there{}", long)),
+            // "!" is held back, then flushed when the turn finishes
+            update(&format!("Hello there{}!", long)),
+        ]
+    );
+    assert_eq!(
+        session(&db, &conversations[0], &templates[0])
+            .await
+            .as_deref(),
+        Some("d1")
+    );
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_agent_error_is_recorded() {
+    let (db, conversations, templates) = split(fixtures::standard().await);
+    let backends: Arc<dyn LlmBackend> = Arc::new(
+        ScriptedBackend::from_json(include_str!(
+            "../../fixtures/agent_scripts/agent_error.json"
+        ))
+        .unwrap(),
+    );
+
+    let (result, writes) = send(&backends, &db, &conversations[0], "run it", None).await;
+    assert_eq!(result, Ok(()));
+    assert_eq!(
+        writes,
+        [
+            agent("Let me check"),
+            Write::Create(
+                AuthorType::System,
+                "⚠️ Agent Error: Permission denied".to_string()
+            ),
+        ]
+    );
+    assert_eq!(
+        session(&db, &conversations[0], &templates[0])
+            .await
+            .as_deref(),
+        Some("e1")
+    );
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_stream_error_keeps_written_text_and_session() {
+    let (db, conversations, templates) = split(fixtures::standard().await);
+    let backends: Arc<dyn LlmBackend> = Arc::new(
+        ScriptedBackend::from_json(include_str!(
+            "../../fixtures/agent_scripts/stream_error.json"
+        ))
+        .unwrap(),
+    );
+
+    let (result, writes) = send(&backends, &db, &conversations[0], "explain", None).await;
+    assert_eq!(result, Ok(()));
+    // Text held back by debouncing is not flushed when the stream breaks
+    assert_eq!(
+        writes,
+        [
+            agent("Half an ans"),
+            Write::Create(
+                AuthorType::System,
+                "⚠️ Stream error: connection reset".to_string()
+            ),
+        ]
+    );
+    assert_eq!(
+        session(&db, &conversations[0], &templates[0])
+            .await
+            .as_deref(),
+        Some("x1")
+    );
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_turn_that_cannot_start_fails_the_send() {
+    let (db, conversations, _) = split(fixtures::standard().await);
+    let backends: Arc<dyn LlmBackend> = Arc::new(ScriptedBackend::new());
+
+    let (result, writes) = send(&backends, &db, &conversations[0], "anyone?", None).await;
+    assert_eq!(result, Err("No scripted turn left".to_string()));
+    assert!(writes.is_empty());
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_multi_agent_partial_failure() {
+    let (db, conversations, templates) = split(fixtures::standard().await);
+    let agents = ScriptedAgents::new()
+        .agent(
+            "Researcher",
+            ScriptedBackend::new().turn(vec![
+                ScriptStep::Wait(Duration::from_millis(20)),
+                ScriptStep::Event(AgentEvent::Session("r1".to_string())),
+                ScriptStep::Event(AgentEvent::Text("Sources look solid".to_string())),
+                ScriptStep::Event(AgentEvent::Finished(Ok(()))),
+            ]),
+        )
+        .agent(
+            "Reviewer",
+            ScriptedBackend::new().turn(vec![
+                ScriptStep::Event(AgentEvent::Session("v1".to_string())),
+                ScriptStep::Event(AgentEvent::Text("I think".to_string())),
+                ScriptStep::Fail("connection reset".to_string()),
+            ]),
+        );
+
+    let (result, writes) = send(&agents, &db, &conversations[1], "thoughts?", None).await;
+
+    // The Reviewer's failure is recorded, and the Researcher still answers
+    assert_eq!(result, Ok(()));
+    let reviewer: Vec<&Write> = writes
+        .iter()
+        .filter(|write| **write != agent("Sources look solid"))
+        .collect();
+    assert_eq!(writes.len(), 3);
+    assert_eq!(
+        reviewer,
+        [
+            &agent("I think"),
+            &Write::Create(
+                AuthorType::System,
+                "⚠️ Stream error: connection reset".to_string()
+            ),
+        ]
+    );
+    assert_eq!(
+        session(&db, &conversations[1], &templates[0])
+            .await
+            .as_deref(),
+        Some("r1")
+    );
+    assert_eq!(
+        session(&db, &conversations[1], &templates[1])
+            .await
+            .as_deref(),
+        Some("v1")
+    );
+
+    // Both agents received the same message
+    for name in ["Researcher", "Reviewer"] {
+        let requests = agents.get(name).unwrap().requests();
+        assert_eq!(requests.len(), 1);
+        assert_eq!(requests[0].message, "thoughts?");
+    }
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_multi_agent_unstartable_agent_is_skipped() {
+    let (db, conversations, templates) = split(fixtures::standard().await);
+    let agents =
+        ScriptedAgents::new().agent("Researcher", ScriptedBackend::new().reply("r1", "Done"));
+
+    let (result, writes) = send(&agents, &db, &conversations[1], "go", None).await;
+    assert_eq!(result, Ok(()));
+    assert_eq!(writes, [agent("Done")]);
+    assert_eq!(session(&db, &conversations[1], &templates[1]).await, None);
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_multi_agent_total_failure() {
+    let (db, conversations, _) = split(fixtures::standard().await);
+
+    let (result, writes) = send(
+        &ScriptedAgents::new(),
+        &db,
+        &conversations[1],
+        "hello?",
+        None,
+    )
+    .await;
+    let error = result.unwrap_err();
+    assert!(
+        error.starts_with("All 2 agents failed to respond"),
+        "{}",
+        error
+    );
+    assert!(error.contains("No scripted turn left"), "{}", error);
+    assert!(writes.is_empty());
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_mentions_limit_the_agents() {
+    let (db, conversations, templates) = split(fixtures::standard().await);
+    let agents = ScriptedAgents::new()
+        .agent("Researcher", ScriptedBackend::new())
+        .agent("Reviewer", ScriptedBackend::new().reply("v1", "On it"));
+
+    let (result, writes) = send(
+        &agents,
+        &db,
+        &conversations[1],
+        "@Reviewer please check",
+        Some(vec![templates[1].clone()]),
+    )
+    .await;
+    assert_eq!(result, Ok(()));
+    assert_eq!(writes, [agent("On it")]);
+    assert!(agents.get("Researcher").unwrap().requests().is_empty());
+}
+
+#[test]
+fn test_template_selects_backend() {
+    let names: Vec<String> = [
+        AgentBackend::Claude,
+        AgentBackend::Openai,
+        AgentBackend::Local,
+    ]
+    .into_iter()
+    .map(|backend| {
+        let template = AgentTemplate {
+            backend,
+            backend_model: Some("llama3".to_string()),
+            ..Default::default()
+        };
+        TemplateBackends.backend(&template).name().to_string()
+    })
+    .collect();
+    assert_eq!(names, ["claude", "openai", "local"]);
+}
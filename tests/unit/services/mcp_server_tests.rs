@@ -0,0 +1,246 @@
+//! Tests for the MCP server over an in-process transport
+//!
+//! Each test serves [`CyrupMcp`] on one end of an in-memory duplex pipe and
+//! drives it with an rmcp client on the other, so requests go through the
+//! same JSON-RPC handling as stdio and HTTP clients. Agents run on a
+//! [`ScriptedBackend`].
+
+use super::super::database::fixtures::{self, Fixture};
+use cyrup::database::Database;
+use cyrup::services::llm::{BackendResolver, LlmBackend, ScriptedBackend};
+use cyrup::services::mcp_server::CyrupMcp;
+use rmcp::model::{
+    CallToolRequestParam, ClientInfo, GetPromptRequestParam, PromptMessageContent,
+    ReadResourceRequestParam, ResourceContents,
+};
+use rmcp::service::RunningService;
+use rmcp::{RoleClient, ServiceExt};
+use serde_json::{Value, json};
+use std::sync::Arc;
+use surrealdb_types::ToSql;
+
+type Client = RunningService<RoleClient, ClientInfo>;
+
+/// Serve `database` over MCP and return a connected client
+async fn connect(database: Arc<Database>, backend: Arc<dyn LlmBackend>) -> Client {
+    let backends: Arc<dyn BackendResolver> = Arc::new(backend);
+    let (server_io, client_io) = tokio::io::duplex(64 * 1024);
+    tokio::spawn(async move {
+        let server = CyrupMcp::with_backends(database, backends)
+            .serve(server_io)
+            .await
+            .expect("MCP server handshake failed");
+        let _ = server.waiting().await;
+    });
+    ClientInfo::default()
+        .serve(client_io)
+        .await
+        .expect("MCP client handshake failed")
+}
+
+async fn standard(backend: ScriptedBackend) -> (Client, Fixture, Arc<ScriptedBackend>) {
+    let fixture = fixtures::standard().await;
+    let scripted = Arc::new(backend);
+    let client = connect(Arc::new(fixture.db.clone()), scripted.clone()).await;
+    (client, fixture, scripted)
+}
+
+/// Call a tool; returns whether it reported an error, and its text output
+async fn call(client: &Client, tool: &str, arguments: Value) -> (bool, String) {
+    let result = client
+        .call_tool(CallToolRequestParam {
+            name: tool.to_string().into(),
+            arguments: arguments.as_object().cloned(),
+        })
+        .await
+        .expect("Tool call failed");
+    let text = result.content[0]
+        .as_text()
+        .expect("Tool output is not text")
+        .text
+        .clone();
+    (result.is_error == Some(true), text)
+}
+
+async fn call_json(client: &Client, tool: &str, arguments: Value) -> Value {
+    let (is_error, text) = call(client, tool, arguments).await;
+    assert!(!is_error, "{} failed: {}", tool, text);
+    serde_json::from_str(&text).expect("Tool output is not JSON")
+}
+
+fn contents(result: &Value) -> Vec<&str> {
+    result
+        .as_array()
+        .expect("Expected a list of messages")
+        .iter()
+        .map(|message| message["content"].as_str().unwrap())
+        .collect()
+}
+
+#[tokio::test]
+async fn test_create_conversation_with_named_agents() {
+    let (client, f, _) = standard(ScriptedBackend::new()).await;
+
+    let created = call_json(
+        &client,
+        "create_conversation",
+        json!({ "title": "Plan", "agents": ["Researcher", f.templates[1].to_sql()] }),
+    )
+    .await;
+
+    assert_eq!(created["title"], "Plan");
+    assert_eq!(
+        created["participants"],
+        json!([f.templates[0].to_sql(), f.templates[1].to_sql()])
+    );
+    let titles: Vec<String> =
+        f.db.list_conversations()
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|summary| summary.title)
+            .collect();
+    assert!(titles.contains(&"Plan".to_string()));
+
+    let (is_error, text) = call(
+        &client,
+        "create_conversation",
+        json!({ "title": "Empty", "agents": [] }),
+    )
+    .await;
+    assert!(is_error);
+    assert_eq!(text, "At least one agent is required");
+}
+
+#[tokio::test]
+async fn test_send_message_returns_agent_replies() {
+    let (client, f, scripted) =
+        standard(ScriptedBackend::new().reply("s1", "Hi from the script")).await;
+
+    let sent = call_json(
+        &client,
+        "send_message",
+        json!({ "conversation": f.conversations[0].to_sql(), "content": "hello" }),
+    )
+    .await;
+
+    assert_eq!(sent["conversation_id"], f.conversations[0].to_sql());
+    assert_eq!(contents(&sent["replies"]), ["Hi from the script"]);
+    assert_eq!(scripted.requests()[0].message, "hello");
+
+    // Only participants can be addressed
+    let (is_error, text) = call(
+        &client,
+        "send_message",
+        json!({
+            "conversation": f.conversations[0].to_sql(),
+            "content": "hello",
+            "agents": ["Reviewer"]
+        }),
+    )
+    .await;
+    assert!(is_error);
+    assert_eq!(text, "Reviewer is not in this conversation");
+    assert_eq!(scripted.requests().len(), 1);
+}
+
+#[tokio::test]
+async fn test_search_messages_newest_first() {
+    let (client, f, _) = standard(ScriptedBackend::new()).await;
+
+    let hits = call_json(&client, "search_messages", json!({ "query": "th" })).await;
+    assert_eq!(
+        contents(&hits),
+        ["what do you both think?", "thanks", "hello there"]
+    );
+
+    let hits = call_json(
+        &client,
+        "search_messages",
+        json!({ "query": "th", "limit": 2 }),
+    )
+    .await;
+    assert_eq!(contents(&hits), ["what do you both think?", "thanks"]);
+
+    let hits = call_json(
+        &client,
+        "search_messages",
+        json!({ "query": "th", "conversation": f.conversations[0].to_sql() }),
+    )
+    .await;
+    assert_eq!(contents(&hits), ["thanks", "hello there"]);
+}
+
+#[tokio::test]
+async fn test_read_resource() {
+    let (client, f, _) = standard(ScriptedBackend::new()).await;
+
+    let read = |uri: String| {
+        let client = &client;
+        async move {
+            client
+                .read_resource(ReadResourceRequestParam { uri })
+                .await
+                .map(|result| match &result.contents[0] {
+                    ResourceContents::TextResourceContents { text, .. } => text.clone(),
+                    other => panic!("unexpected contents {:?}", other),
+                })
+        }
+    };
+
+    let templates: Value =
+        serde_json::from_str(&read("cyrup://templates".to_string()).await.unwrap()).unwrap();
+    let names: Vec<&str> = templates
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|template| template["name"].as_str().unwrap())
+        .collect();
+    assert!(names.contains(&"Researcher") && names.contains(&"Reviewer"));
+
+    let key = fixtures::key(&f.conversations[0]);
+    let transcript = read(format!("cyrup://conversations/{}", key))
+        .await
+        .unwrap();
+    assert!(transcript.contains("hello there"));
+    assert!(!transcript.contains("looks good to me"));
+
+    assert!(
+        read("cyrup://conversations/missing".to_string())
+            .await
+            .is_err()
+    );
+    assert!(read("cyrup://elsewhere".to_string()).await.is_err());
+}
+
+#[tokio::test]
+async fn test_get_prompt_carries_the_system_prompt() {
+    let (client, f, _) = standard(ScriptedBackend::new()).await;
+    let template = f.db.find_template("Researcher").await.unwrap();
+
+    let prompt = client
+        .get_prompt(GetPromptRequestParam {
+            name: "Researcher".to_string(),
+            arguments: json!({ "message": "Start here" }).as_object().cloned(),
+        })
+        .await
+        .unwrap();
+
+    let texts: Vec<&str> = prompt
+        .messages
+        .iter()
+        .map(|message| match &message.content {
+            PromptMessageContent::Text { text } => text.as_str(),
+            other => panic!("unexpected content {:?}", other),
+        })
+        .collect();
+    assert_eq!(texts, [template.system_prompt.as_str(), "Start here"]);
+
+    let unknown = client
+        .get_prompt(GetPromptRequestParam {
+            name: "Nobody".to_string(),
+            arguments: None,
+        })
+        .await;
+    assert!(unknown.is_err());
+}
@@ -0,0 +1,2 @@
+mod agent_chat_tests;
+mod mcp_server_tests;